@@ -0,0 +1,122 @@
+//-----------------------------------------------------------------------------
+//! Log file header with the build and system info, so bug reports contain the environment
+//-----------------------------------------------------------------------------
+use crate::{Error, Logger, Result};
+use std::io::Write;
+//-----------------------------------------------------------------------------
+
+impl Logger {
+    /// Sets a line of the log file header ( GPU, driver version, versions of the used crates )
+    ///
+    /// Setting an existing key replaces its value. New and changed entries are also written to
+    /// the already opened log file right away, as they are usually known only after it's opened.
+    pub fn set_header_entry(&self, key: &str, value: &str) -> Result<()> {
+        let Ok(mut header) = self.header.lock() else {
+            return Err(Error::Poisoned);
+        };
+
+        match header.iter_mut().find(|(k, _)| k == key) {
+            Some((_, v)) if v == value => return Ok(()),
+            Some((_, v)) => *v = value.to_owned(),
+            None => header.push((key.to_owned(), value.to_owned())),
+        }
+        drop(header);
+
+        let Ok(mut file) = self.file.lock() else {
+            return Err(Error::Poisoned);
+        };
+        if let Some(file) = file.as_mut() {
+            write_header_entry(file, key, value)?;
+            file.flush()?;
+        }
+
+        return Ok(());
+    }
+
+    /// Writes the system info and the header entries at the start of a new log file
+    pub(crate) fn write_header(&self, file: &mut impl Write) -> Result<()> {
+        let system = [
+            ("soh_log", env!("CARGO_PKG_VERSION").to_owned()),
+            ("os", os_name()),
+            ("cpu", cpu_name()),
+        ];
+        for (key, value) in &system {
+            write_header_entry(file, key, value)?;
+        }
+
+        let Ok(header) = self.header.lock() else {
+            return Err(Error::Poisoned);
+        };
+        for (key, value) in header.iter() {
+            write_header_entry(file, key, value)?;
+        }
+
+        return Ok(());
+    }
+}
+
+fn write_header_entry(file: &mut impl Write, key: &str, value: &str) -> Result<()> {
+    writeln!(file, "[HEADER] {key}: {value}")?;
+    return Ok(());
+}
+
+//-----------------------------------------------------------------------------
+// System info
+
+/// Distribution name with the OS and the architecture
+fn os_name() -> String {
+    let os = format!("{} {}", std::env::consts::OS, std::env::consts::ARCH);
+
+    return match distribution_name() {
+        Some(name) => format!("{name} ( {os} )"),
+        None => os,
+    };
+}
+
+#[cfg(target_os = "linux")]
+fn distribution_name() -> Option<String> {
+    let os_release = std::fs::read_to_string("/etc/os-release").ok()?;
+
+    let name = os_release
+        .lines()
+        .find_map(|line| line.strip_prefix("PRETTY_NAME="))?
+        .trim_matches('"');
+
+    return Some(name.to_owned());
+}
+
+#[cfg(not(target_os = "linux"))]
+fn distribution_name() -> Option<String> {
+    return None;
+}
+
+/// CPU model with the number of hardware threads
+fn cpu_name() -> String {
+    let model = cpu_model().unwrap_or_else(|| "unknown".to_owned());
+
+    return match std::thread::available_parallelism() {
+        Ok(threads) => format!("{model} ( {threads} threads )"),
+        Err(_) => model,
+    };
+}
+
+#[cfg(target_os = "linux")]
+fn cpu_model() -> Option<String> {
+    let cpuinfo = std::fs::read_to_string("/proc/cpuinfo").ok()?;
+
+    let model = cpuinfo
+        .lines()
+        .find_map(|line| line.strip_prefix("model name"))?
+        .trim_start()
+        .strip_prefix(':')?
+        .trim();
+
+    return Some(model.to_owned());
+}
+
+#[cfg(not(target_os = "linux"))]
+fn cpu_model() -> Option<String> {
+    return None;
+}
+
+//-----------------------------------------------------------------------------
@@ -0,0 +1,61 @@
+//-----------------------------------------------------------------------------
+//! Formatting of binary data for the log
+//-----------------------------------------------------------------------------
+use std::fmt::Write;
+//-----------------------------------------------------------------------------
+/// Number of bytes shown on a single line
+const BYTES_PER_LINE: usize = 16;
+
+/// Formats `bytes` as an aligned hex + ASCII dump, showing at most `max_len` bytes.
+///
+/// The first line contains the label and the total length, every following line shows the
+/// offset, 16 bytes in hex and their printable ASCII characters.
+///
+/// ```rust
+/// let dump = soh_log::hexdump("header", &[0x03, 0x02, 0x23, 0x07, b'S', b'P', b'V'], 4);
+///
+/// assert_eq!(
+///     dump,
+///     "header: 7 bytes\n\
+///      00000000  03 02 23 07                                       |..#.|\n\
+///      ... 3 more bytes"
+/// );
+/// ```
+pub fn hexdump(label: &str, bytes: &[u8], max_len: usize) -> String {
+    let shown = &bytes[..bytes.len().min(max_len)];
+
+    let mut res = format!("{label}: {} bytes", bytes.len());
+
+    for (i, line) in shown.chunks(BYTES_PER_LINE).enumerate() {
+        let _ = write!(res, "\n{:08x} ", i * BYTES_PER_LINE);
+
+        for col in 0..BYTES_PER_LINE {
+            // Extra space between the two halves of the line
+            if col % 8 == 0 {
+                res.push(' ');
+            }
+
+            match line.get(col) {
+                Some(byte) => {
+                    let _ = write!(res, "{byte:02x} ");
+                }
+                None => res.push_str("   "),
+            }
+        }
+
+        res.push_str(" |");
+        res.extend(line.iter().map(|&byte| match byte {
+            0x20..=0x7e => byte as char,
+            _ => '.',
+        }));
+        res.push('|');
+    }
+
+    if shown.len() < bytes.len() {
+        let _ = write!(res, "\n... {} more bytes", bytes.len() - shown.len());
+    }
+
+    return res;
+}
+
+//-----------------------------------------------------------------------------
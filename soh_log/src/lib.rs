@@ -1,11 +1,42 @@
 //-----------------------------------------------------------------------------
 //! Very simple logger. It logs messages to the file and to stderr.
 //-----------------------------------------------------------------------------
-use anyhow::{anyhow, Result};
 use std::{io::Write, ops::DerefMut};
+//-----------------------------------------------------------------------------
+mod header;
+
+mod hexdump;
+pub use hexdump::hexdump;
+
+mod progress;
+pub use progress::Progress;
+
+mod sink;
+pub use sink::{FlushGuard, FlushPolicy};
+
+mod span;
+pub use span::Span;
+
+mod timing;
+pub use timing::{Stopwatch, TimeScope};
+//-----------------------------------------------------------------------------
+/// Errors of the logger
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    /// The log file couldn't be created
+    #[error("Failed to open the log file: {0}")]
+    Io(#[from] std::io::Error),
+
+    /// A thread panicked while holding a lock of the logger
+    #[error("Logger lock is poisoned")]
+    Poisoned,
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
+
 //-----------------------------------------------------------------------------
 /// The priority of a log message.
-#[derive(Clone, Copy, PartialEq, PartialOrd, Eq, Ord)]
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Eq, Ord)]
 pub enum Prio {
     Debug,
     Info,
@@ -75,22 +106,92 @@ impl std::fmt::Display for Prio {
 /// The following macros are provided to simplify logging with the global logger:
 ///
 /// - `open_logfile!($filepath)`: Opens a log file at the specified file path. (By default no file
-///   is opened and messages are only logged to stderr).
+///   is opened and messages are only logged to stderr). The header of the file also contains the
+///   name, version and build profile of the calling crate, and the git hash if the
+///   `SOH_GIT_HASH` environment variable is set at build time ( from a build script ).
+/// - `open_target_logfile!($target, $filepath)`: Opens an additional log file which only receives
+///   messages of the given target.
 /// - `set_min_priority_stderr!($priority)`: Sets the minimum priority that should be logged to
 ///   stderr.
 /// - `set_min_priority_file!($priority)`: Sets the minimum priority that should be logged
 ///   to the file.
+/// - `set_flush_policy!($policy)`: Sets when the log files are flushed ( see [FlushPolicy] ),
+///   returns a [FlushGuard] which flushes them when dropped.
+/// - `flush_all!()`: Flushes all log files.
 ///
 /// - `log_fatal!(...)`: Logs a fatal message.
 /// - `log_error!(...)`: Logs an error message.
 /// - `log_warning!(...)`: Logs a warning message.
 /// - `log_info!(...)`: Logs an info message.
 /// - `log_debug!(...)`: Logs a debug message (available only in a debug build).
+/// - `log_hexdump!($priority, $label, $bytes)`: Logs a hex + ASCII dump of binary data.
+/// - `progress!($label, $total)`: Creates a [Progress] status line.
+/// - `log_time_scope!($label)`: Logs the time spent in the current scope.
+/// - `stopwatch!()`: Creates a [Stopwatch], `stopwatch!($stopwatch, $label)` logs a lap.
+/// - `span!($name, $field = $value, ...)`: Enters a [Span] ( see below ).
 ///
 /// The `log_X` macros use the `println!` style of arguments, which means they support formatted
 /// strings.
+///
+/// # Targets
+///
+/// Messages can be tagged with a target ( `log_info!(target: "vk", ...)` ). Tagged messages go to
+/// the main log file as usual and additionally to every log file opened for that target with
+/// [Logger::open_target_logfile]. Targets are hierarchical: a file opened for `"vk"` also receives
+/// messages of `"vk::pipeline"`.
+///
+/// ```rust,no_run
+/// use soh_log::*;
+///
+/// open_logfile!("main.log").unwrap();
+/// open_target_logfile!("vk", "vk.log").unwrap();
+///
+/// log_info!(target: "vk::pipeline", "Goes to both main.log and vk.log");
+/// log_info!("Goes only to main.log");
+/// ```
+///
+/// # Spans
+///
+/// Spans are named scopes with optional fields. They nest, prefix the records logged inside of
+/// them with their path and log the time spent in them at the debug priority.
+///
+/// ```rust
+/// use soh_log::*;
+///
+/// for n in 0..2 {
+///     let _frame = span!("frame", n = n);
+///     {
+///         let _draw = span!("draw");
+///         log_info!("3 draw calls"); // Logs: "frame{n=0}:draw: 3 draw calls"
+///     } // Logs: "frame{n=0}:draw: 1.23ms"
+/// } // Logs: "frame{n=0}: 1.23ms"
+/// ```
+///
+/// # Flushing
+///
+/// Log files are buffered and by default flushed after every record. Flushing less often is
+/// faster, [FlushPolicy] chooses when. The records are also flushed on panics and when the
+/// returned [FlushGuard] is dropped at the end of `main`:
+///
+/// ```rust,no_run
+/// use soh_log::*;
+///
+/// open_logfile!("main.log").unwrap();
+/// let _flush = set_flush_policy!(FlushPolicy::Priority(Prio::Warning)).unwrap();
+///
+/// log_info!("Buffered");
+/// log_warning!("Flushed together with the info above");
+/// log_info!("Flushed when `_flush` is dropped");
+/// ```
 pub struct Logger {
-    file: std::sync::Mutex<Option<std::fs::File>>,
+    file: std::sync::Mutex<Option<LogFile>>,
+    target_files: std::sync::Mutex<Vec<TargetFile>>,
+    header: std::sync::Mutex<Vec<(String, String)>>,
+    flush_policy: std::sync::RwLock<FlushPolicy>,
+    last_flush: std::sync::Mutex<Option<std::time::Instant>>,
+    flush_thread_running: std::sync::atomic::AtomicBool,
+    panic_hook_installed: std::sync::atomic::AtomicBool,
+    status_line: std::sync::Mutex<Option<String>>,
     min_priority_stderr: std::sync::RwLock<Prio>,
     min_priority_file: std::sync::RwLock<Prio>,
 }
@@ -103,32 +204,77 @@ impl Logger {
     pub const fn new(min_priority_stderr: Prio, min_priority_file: Prio) -> Logger {
         return Logger {
             file: std::sync::Mutex::new(None),
+            target_files: std::sync::Mutex::new(Vec::new()),
+            header: std::sync::Mutex::new(Vec::new()),
+            flush_policy: std::sync::RwLock::new(FlushPolicy::EveryRecord),
+            last_flush: std::sync::Mutex::new(None),
+            flush_thread_running: std::sync::atomic::AtomicBool::new(false),
+            panic_hook_installed: std::sync::atomic::AtomicBool::new(false),
+            status_line: std::sync::Mutex::new(None),
             min_priority_stderr: std::sync::RwLock::new(min_priority_stderr),
             min_priority_file: std::sync::RwLock::new(min_priority_file),
         };
     }
 
     /// Loads the file at the specified path and opens it for logging.
+    ///
+    /// The file starts with a header of the system info and the entries set with
+    /// [Logger::set_header_entry].
     pub fn open_logfile(&self, filename: &str) -> Result<()> {
-        let file = std::fs::File::create(filename)?;
+        let mut file = LogFile::new(std::fs::File::create(filename)?);
+        self.write_header(&mut file)?;
+        file.flush()?;
 
         let Ok(mut lock) = self.file.lock() else {
-            return Err(anyhow!("Failed to acquire lock for the file"));
+            return Err(Error::Poisoned);
         };
         *lock = Some(file);
 
         return Ok(());
     }
 
+    /// Opens a log file which receives only the messages of the `target` ( and its sub-targets ).
+    ///
+    /// Several files can be opened for different targets. Opening a file for an already routed
+    /// target replaces the previous file.
+    pub fn open_target_logfile(&self, target: &str, filename: &str) -> Result<()> {
+        let file = LogFile::new(std::fs::File::create(filename)?);
+
+        let Ok(mut lock) = self.target_files.lock() else {
+            return Err(Error::Poisoned);
+        };
+
+        let target_file = TargetFile {
+            target: target.to_owned(),
+            file,
+        };
+
+        match lock.iter_mut().find(|f| f.target == target) {
+            Some(existing) => *existing = target_file,
+            None => lock.push(target_file),
+        }
+
+        return Ok(());
+    }
+
+    /// Logs a message, prefixed with the active spans of the thread
     pub fn log(&self, priority: Prio, msg: &str) {
-        self.log_stderr(priority, msg);
-        self.log_file(priority, msg);
+        let msg = span::with_span_path(msg);
+        self.log_stderr(priority, None, &msg);
+        self.log_file(priority, None, &msg);
+    }
+
+    /// Logs a message tagged with a target
+    pub fn log_target(&self, priority: Prio, target: &str, msg: &str) {
+        let msg = span::with_span_path(msg);
+        self.log_stderr(priority, Some(target), &msg);
+        self.log_file(priority, Some(target), &msg);
     }
 
     /// Sets the minimum priority that should be logged to stderr.
     pub fn set_min_priority_stderr(&self, min_priority_stderr: Prio) -> Result<()> {
         let Ok(mut p) = self.min_priority_stderr.write() else {
-            return Err(anyhow!("Cannot get write lock for logger"));
+            return Err(Error::Poisoned);
         };
 
         *p = min_priority_stderr;
@@ -138,40 +284,120 @@ impl Logger {
     /// Sets the minimum priority that should be logged to the file.
     pub fn set_min_priority_file(&self, min_priority_file: Prio) -> Result<()> {
         let Ok(mut p) = self.min_priority_file.write() else {
-            return Err(anyhow!("Cannot get write lock for logger"));
+            return Err(Error::Poisoned);
         };
 
         *p = min_priority_file;
         return Ok(());
     }
 
-    fn log_stderr(&self, priority: Prio, msg: &str) {
+    fn log_stderr(&self, priority: Prio, target: Option<&str>, msg: &str) {
         if priority < *self.min_priority_stderr.read().unwrap() {
             return;
         }
 
-        eprintln!(
-            "  {color}{prio:#5}\x1b[0m - {msg}",
-            color = priority.get_color(),
-            prio = priority.to_string(),
-        );
+        let line = match target {
+            Some(target) => format!(
+                "  {color}{prio:#5}\x1b[0m - [{target}] {msg}",
+                color = priority.get_color(),
+                prio = priority.to_string(),
+            ),
+            None => format!(
+                "  {color}{prio:#5}\x1b[0m - {msg}",
+                color = priority.get_color(),
+                prio = priority.to_string(),
+            ),
+        };
+
+        let Ok(status_line) = self.status_line.lock() else {
+            eprintln!("{line}");
+            return;
+        };
+
+        // Print the message above the status line and redraw it
+        let mut stderr = std::io::stderr().lock();
+        let _ = match status_line.as_deref() {
+            Some(status) => write!(stderr, "\r\x1b[2K{line}\n{status}"),
+            None => writeln!(stderr, "{line}"),
+        };
+        let _ = stderr.flush();
     }
 
-    fn log_file(&self, priority: Prio, msg: &str) {
+    /// Replaces the status line at the bottom of stderr ( `None` removes it )
+    fn set_status_line(&self, status: Option<String>) {
+        let Ok(mut status_line) = self.status_line.lock() else {
+            return;
+        };
+
+        let mut stderr = std::io::stderr().lock();
+        let _ = match status.as_deref() {
+            Some(status) => write!(stderr, "\r\x1b[2K{status}"),
+            None => write!(stderr, "\r\x1b[2K"),
+        };
+        let _ = stderr.flush();
+
+        *status_line = status;
+    }
+
+    fn log_file(&self, priority: Prio, target: Option<&str>, msg: &str) {
         if priority < *self.min_priority_file.read().unwrap() {
             return;
         }
 
-        let Ok(mut lock) = self.file.lock() else {
+        let flush = self.should_flush(priority);
+
+        if let Ok(mut lock) = self.file.lock() {
+            if let Some(file) = lock.deref_mut() {
+                write_record(file, priority, target, msg, flush);
+            }
+        }
+
+        let Some(target) = target else {
+            return;
+        };
+
+        let Ok(mut lock) = self.target_files.lock() else {
             return;
         };
 
-        if let Some(file) = lock.deref_mut() {
-            let _ = writeln!(file, "[{priority}] {msg}");
+        for target_file in lock.iter_mut().filter(|f| f.routes(target)) {
+            write_record(&mut target_file.file, priority, Some(target), msg, flush);
         }
     }
 }
 
+/// Log file receiving the messages of a single target
+struct TargetFile {
+    target: String,
+    file: LogFile,
+}
+
+impl TargetFile {
+    /// Whether the messages of `target` belong to this file
+    fn routes(&self, target: &str) -> bool {
+        let Some(rest) = target.strip_prefix(self.target.as_str()) else {
+            return false;
+        };
+
+        return rest.is_empty() || rest.starts_with("::");
+    }
+}
+
+/// Formatting shared by all log files
+fn write_record(file: &mut LogFile, priority: Prio, target: Option<&str>, msg: &str, flush: bool) {
+    let _ = match target {
+        Some(target) => writeln!(file, "[{priority}] [{target}] {msg}"),
+        None => writeln!(file, "[{priority}] {msg}"),
+    };
+
+    if flush {
+        let _ = file.flush();
+    }
+}
+
+/// Log files are buffered, [FlushPolicy] decides when they are flushed
+type LogFile = std::io::BufWriter<std::fs::File>;
+
 //-----------------------------------------------------------------------------
 /// Trait which adds the `expect_log` and `unwrap_log` methods
 ///
@@ -183,7 +409,7 @@ pub trait LogError {
     fn unwrap_log(self) -> Self::Output;
 }
 
-impl<T, E> LogError for Result<T, E>
+impl<T, E> LogError for std::result::Result<T, E>
 where
     E: std::fmt::Debug,
 {
@@ -238,11 +464,42 @@ pub static LOGGER: Logger = Logger::new(Prio::Debug, Prio::Info);
 //-----------------------------------------------------------------------------
 // Macros for the global instance
 
-/// Opens a log file at the specified file path.
+/// Opens a log file at the specified file path, with the calling crate's build info in the
+/// header.
 #[macro_export]
 macro_rules! open_logfile {
-    ($filepath:expr) => {
-        $crate::LOGGER.open_logfile($filepath)
+    ($filepath:expr) => {{
+        let package = concat!(env!("CARGO_PKG_NAME"), " ", env!("CARGO_PKG_VERSION"));
+        let profile = if cfg!(debug_assertions) {
+            "debug"
+        } else {
+            "release"
+        };
+
+        $crate::LOGGER
+            .set_header_entry("application", package)
+            .and_then(|_| $crate::LOGGER.set_header_entry("profile", profile))
+            .and_then(|_| match option_env!("SOH_GIT_HASH") {
+                Some(hash) => $crate::LOGGER.set_header_entry("git", hash),
+                None => Ok(()),
+            })
+            .and_then(|_| $crate::LOGGER.open_logfile($filepath))
+    }};
+}
+
+/// Opens a log file that receives only the messages of the target.
+#[macro_export]
+macro_rules! open_target_logfile {
+    ($target:expr, $filepath:expr) => {
+        $crate::LOGGER.open_target_logfile($target, $filepath)
+    };
+}
+
+/// Creates a [Progress] status line using the global logger.
+#[macro_export]
+macro_rules! progress {
+    ($label:expr, $total:expr) => {
+        $crate::LOGGER.progress($label, $total)
     };
 }
 
@@ -262,9 +519,40 @@ macro_rules! set_min_priority_file {
     };
 }
 
+/// Sets when the log files are flushed, returns a [FlushGuard] which flushes them when dropped.
+#[macro_export]
+macro_rules! set_flush_policy {
+    ($policy:expr) => {
+        $crate::LOGGER.install_flush_policy($policy)
+    };
+}
+
+/// Flushes all log files.
+#[macro_export]
+macro_rules! flush_all {
+    () => {
+        $crate::LOGGER.flush_all()
+    };
+}
+
+/// Enters a [Span] of the global logger, the span lasts until the returned guard is dropped.
+#[macro_export]
+macro_rules! span {
+    ($name:expr $(, $key:ident = $value:expr)* $(,)?) => {
+        $crate::LOGGER.span(
+            $name,
+            &[$((stringify!($key), &$value as &dyn std::fmt::Display)),*],
+        )
+    };
+}
+
 /// Logs a message with the specified priority.
 #[macro_export]
 macro_rules! log_prio {
+    ($priority:expr, target: $target:expr, $($args:tt)*) => {
+        let msg = std::fmt::format(format_args!($($args)*));
+        $crate::LOGGER.log_target($priority, $target, &msg);
+    };
     ($priority:expr, $($args:tt)*) => {
         let msg = std::fmt::format(format_args!($($args)*));
         $crate::LOGGER.log($priority, &msg);
@@ -274,6 +562,10 @@ macro_rules! log_prio {
 /// Logs a fatal error message.
 #[macro_export]
 macro_rules! log_fatal {
+    (target: $target:expr, $($args:tt)*) => {
+        let msg = std::fmt::format(format_args!($($args)*));
+        $crate::LOGGER.log_target($crate::Prio::Fatal, $target, &msg);
+    };
     ($($args:tt)*) => {
         let msg = std::fmt::format(format_args!($($args)*));
         $crate::LOGGER.log($crate::Prio::Fatal, &msg);
@@ -283,6 +575,10 @@ macro_rules! log_fatal {
 /// Logs an error message.
 #[macro_export]
 macro_rules! log_error {
+    (target: $target:expr, $($args:tt)*) => {
+        let msg = std::fmt::format(format_args!($($args)*));
+        $crate::LOGGER.log_target($crate::Prio::Error, $target, &msg);
+    };
     ($($args:tt)*) => {
         let msg = std::fmt::format(format_args!($($args)*));
         $crate::LOGGER.log($crate::Prio::Error, &msg);
@@ -292,6 +588,10 @@ macro_rules! log_error {
 /// Logs a warning message.
 #[macro_export]
 macro_rules! log_warning {
+    (target: $target:expr, $($args:tt)*) => {
+        let msg = std::fmt::format(format_args!($($args)*));
+        $crate::LOGGER.log_target($crate::Prio::Warning, $target, &msg);
+    };
     ($($args:tt)*) => {
         let msg = std::fmt::format(format_args!($($args)*));
         $crate::LOGGER.log($crate::Prio::Warning, &msg);
@@ -301,6 +601,10 @@ macro_rules! log_warning {
 /// Logs an info message.
 #[macro_export]
 macro_rules! log_info {
+    (target: $target:expr, $($args:tt)*) => {
+        let msg = std::fmt::format(format_args!($($args)*));
+        $crate::LOGGER.log_target($crate::Prio::Info, $target, &msg);
+    };
     ($($args:tt)*) => {
         let msg = std::fmt::format(format_args!($($args)*));
         $crate::LOGGER.log($crate::Prio::Info, &msg);
@@ -311,6 +615,10 @@ macro_rules! log_info {
 #[cfg(debug_assertions)]
 #[macro_export]
 macro_rules! log_debug {
+    (target: $target:expr, $($args:tt)*) => {
+        let msg = std::fmt::format(format_args!($($args)*));
+        $crate::LOGGER.log_target($crate::Prio::Debug, $target, &msg);
+    };
     ($($args:tt)*) => {
         let msg = std::fmt::format(format_args!($($args)*));
         $crate::LOGGER.log($crate::Prio::Debug, &msg);
@@ -323,4 +631,83 @@ macro_rules! log_debug {
     ($($args:tt)*) => {};
 }
 
+/// Logs a hex + ASCII dump of binary data.
+///
+/// An optional fourth argument limits the number of bytes shown.
+///
+/// ```rust
+/// use soh_log::*;
+///
+/// let spirv_header = [0x03, 0x02, 0x23, 0x07, 0x00, 0x00, 0x01, 0x00];
+///
+/// log_hexdump!(Prio::Debug, "SPIR-V header", &spirv_header);
+/// log_hexdump!(Prio::Debug, "SPIR-V magic", &spirv_header, 4);
+/// ```
+#[macro_export]
+macro_rules! log_hexdump {
+    ($priority:expr, $label:expr, $bytes:expr) => {
+        $crate::log_hexdump!($priority, $label, $bytes, usize::MAX)
+    };
+    ($priority:expr, $label:expr, $bytes:expr, $max_len:expr) => {
+        let msg = $crate::hexdump($label, $bytes, $max_len);
+        $crate::LOGGER.log($priority, &msg);
+    };
+}
+
+/// Logs the time spent in the current scope when the scope exits.
+///
+/// Pass `memory` as the second argument to also log the change of the resident memory.
+///
+/// ```rust
+/// use soh_log::*;
+///
+/// fn load_assets() {
+///     log_time_scope!("load assets");
+///     // ...
+/// } // Logs: "load assets: 1.23ms"
+///
+/// fn build_meshes() {
+///     log_time_scope!("build meshes", memory);
+///     // ...
+/// } // Logs: "build meshes: 1.23ms, memory +512 KiB"
+/// # load_assets();
+/// # build_meshes();
+/// ```
+#[macro_export]
+macro_rules! log_time_scope {
+    ($label:expr) => {
+        let _time_scope = $crate::LOGGER.time_scope($label, false);
+    };
+    ($label:expr, memory) => {
+        let _time_scope = $crate::LOGGER.time_scope($label, true);
+    };
+}
+
+/// Creates a [Stopwatch], or logs a lap of an existing one.
+///
+/// ```rust
+/// use soh_log::*;
+///
+/// let mut stopwatch = stopwatch!();
+/// // ...
+/// stopwatch!(stopwatch, "parse"); // Logs: "parse: 1.23ms ( total 1.23ms )"
+/// // ...
+/// stopwatch!(stopwatch, "compile"); // Logs: "compile: 2.00ms ( total 3.23ms )"
+/// ```
+#[macro_export]
+macro_rules! stopwatch {
+    () => {
+        $crate::Stopwatch::new()
+    };
+    ($stopwatch:expr, $label:expr) => {
+        let lap = $stopwatch.lap();
+        let msg = format!(
+            "{}: {lap:.2?} ( total {:.2?} )",
+            $label,
+            $stopwatch.elapsed()
+        );
+        $crate::LOGGER.log($crate::Prio::Info, &msg);
+    };
+}
+
 //-----------------------------------------------------------------------------
@@ -0,0 +1,106 @@
+//-----------------------------------------------------------------------------
+//! Status line for long operations
+//-----------------------------------------------------------------------------
+use crate::{Logger, Prio};
+use std::sync::atomic::{AtomicUsize, Ordering};
+//-----------------------------------------------------------------------------
+/// Progress of a long operation shown as an updating line at the bottom of stderr.
+///
+/// Messages logged while the progress is alive are printed above the status line. When the
+/// progress is finished ( or dropped ) the status line is removed and a summary is logged to the
+/// log file.
+///
+/// The counter is atomic, so the handle can be shared between threads.
+///
+/// ```rust
+/// use soh_log::*;
+///
+/// let progress = progress!("shader precompilation", 40);
+///
+/// for i in 0..40 {
+///     if i == 20 {
+///         log_info!("Printed above the status line");
+///     }
+///     progress.inc();
+/// }
+///
+/// progress.finish();
+/// ```
+pub struct Progress<'a> {
+    logger: &'a Logger,
+    label: String,
+    total: usize,
+    done: AtomicUsize,
+    start: std::time::Instant,
+}
+
+impl Logger {
+    /// Starts showing a progress status line ( replaces the current one if there is any )
+    pub fn progress(&self, label: &str, total: usize) -> Progress<'_> {
+        let progress = Progress {
+            logger: self,
+            label: label.to_owned(),
+            total,
+            done: AtomicUsize::new(0),
+            start: std::time::Instant::now(),
+        };
+        progress.draw(0);
+
+        return progress;
+    }
+}
+
+impl Progress<'_> {
+    /// Number of finished steps
+    pub fn done(&self) -> usize {
+        return self.done.load(Ordering::Relaxed);
+    }
+
+    /// Marks one more step as finished
+    pub fn inc(&self) {
+        self.add(1);
+    }
+
+    /// Marks `steps` more steps as finished
+    pub fn add(&self, steps: usize) {
+        let done = self.done.fetch_add(steps, Ordering::Relaxed) + steps;
+        self.draw(done);
+    }
+
+    /// Sets the number of finished steps
+    pub fn set(&self, done: usize) {
+        self.done.store(done, Ordering::Relaxed);
+        self.draw(done);
+    }
+
+    /// Removes the status line and logs the summary to the file
+    pub fn finish(self) {
+        // Summary is logged on drop
+    }
+
+    fn draw(&self, done: usize) {
+        let percent = (done.min(self.total) * 100)
+            .checked_div(self.total)
+            .unwrap_or(100);
+
+        let status = format!("  {}: {done}/{} ({percent}%)", self.label, self.total);
+        self.logger.set_status_line(Some(status));
+    }
+}
+
+impl Drop for Progress<'_> {
+    fn drop(&mut self) {
+        self.logger.set_status_line(None);
+
+        let msg = format!(
+            "{}: {}/{} done in {:.2?}",
+            self.label,
+            self.done(),
+            self.total,
+            self.start.elapsed()
+        );
+        self.logger.log_file(Prio::Info, None, &msg);
+    }
+}
+
+//-----------------------------------------------------------------------------
@@ -0,0 +1,252 @@
+//-----------------------------------------------------------------------------
+//! Flushing of the buffered log files
+//-----------------------------------------------------------------------------
+use crate::{Error, Logger, Prio, Result};
+use std::io::Write;
+use std::sync::atomic::Ordering;
+use std::time::{Duration, Instant};
+//-----------------------------------------------------------------------------
+
+/// When the log files are flushed to the disk
+///
+/// Records still in the buffer are lost if the process crashes, fatal records are always flushed.
+/// [Logger::install_flush_policy] also flushes on panics, and the [FlushGuard] flushes at the end
+/// of `main`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum FlushPolicy {
+    /// After every record ( the default, nothing is lost )
+    #[default]
+    EveryRecord,
+    /// Every interval: a background thread started by [Logger::install_flush_policy] flushes the
+    /// files, a record logged after the interval since the last flush is flushed right away
+    Interval(Duration),
+    /// After records of the priority and higher
+    Priority(Prio),
+}
+
+/// Flushes the log files when dropped, returned when the flush policy is set. Keep it alive until
+/// the end of `main`, otherwise the records buffered at the exit are lost ( the global logger is
+/// never dropped ).
+#[must_use = "the log files are flushed when the guard is dropped"]
+pub struct FlushGuard<'a> {
+    logger: &'a Logger,
+}
+
+//-----------------------------------------------------------------------------
+
+impl Logger {
+    /// Sets when the log files are flushed, the returned guard flushes them when dropped
+    ///
+    /// Nothing flushes a buffering policy on panics and [FlushPolicy::Interval] only flushes on
+    /// the next record, [Self::install_flush_policy] does both for a `'static` logger.
+    pub fn set_flush_policy(&self, policy: FlushPolicy) -> Result<FlushGuard<'_>> {
+        let Ok(mut p) = self.flush_policy.write() else {
+            return Err(Error::Poisoned);
+        };
+        *p = policy;
+
+        return Ok(FlushGuard { logger: self });
+    }
+
+    /// Like [Self::set_flush_policy], and for the buffering policies also installs the panic hook
+    /// of [Self::flush_on_panic] and starts the flushing thread of [FlushPolicy::Interval]
+    pub fn install_flush_policy(&'static self, policy: FlushPolicy) -> Result<FlushGuard<'static>> {
+        let guard = self.set_flush_policy(policy)?;
+
+        if policy != FlushPolicy::EveryRecord {
+            self.flush_on_panic();
+        }
+
+        if matches!(policy, FlushPolicy::Interval(_))
+            && !self.flush_thread_running.swap(true, Ordering::AcqRel)
+        {
+            let res = std::thread::Builder::new()
+                .name("soh_log flush".to_string())
+                .spawn(move || self.flush_periodically());
+
+            if let Err(err) = res {
+                self.flush_thread_running.store(false, Ordering::Release);
+                return Err(err.into());
+            }
+        }
+
+        return Ok(guard);
+    }
+
+    /// Flushes the main log file and the target log files
+    pub fn flush_all(&self) -> Result<()> {
+        let Ok(mut file) = self.file.lock() else {
+            return Err(Error::Poisoned);
+        };
+        if let Some(file) = file.as_mut() {
+            file.flush()?;
+        }
+        drop(file);
+
+        let Ok(mut target_files) = self.target_files.lock() else {
+            return Err(Error::Poisoned);
+        };
+        for target_file in target_files.iter_mut() {
+            target_file.file.flush()?;
+        }
+
+        return Ok(());
+    }
+
+    /// Installs a panic hook which flushes the log files before calling the previous hook ( only
+    /// once, calling it again does nothing )
+    pub fn flush_on_panic(&'static self) {
+        if self.panic_hook_installed.swap(true, Ordering::AcqRel) {
+            return;
+        }
+
+        let prev_hook = std::panic::take_hook();
+
+        std::panic::set_hook(Box::new(move |info| {
+            let _ = self.flush_all();
+            prev_hook(info);
+        }));
+    }
+
+    /// Body of the flushing thread, runs while the policy is [FlushPolicy::Interval]
+    fn flush_periodically(&self) {
+        loop {
+            let interval = match self.flush_policy.read().map(|policy| *policy) {
+                Ok(FlushPolicy::Interval(interval)) => interval,
+                _ => {
+                    self.flush_thread_running.store(false, Ordering::Release);
+
+                    // The policy may have been switched back before the flag was cleared
+                    let is_interval = self
+                        .flush_policy
+                        .read()
+                        .is_ok_and(|policy| matches!(*policy, FlushPolicy::Interval(_)));
+                    if is_interval && !self.flush_thread_running.swap(true, Ordering::AcqRel) {
+                        continue;
+                    }
+                    return;
+                }
+            };
+
+            std::thread::sleep(interval);
+
+            let Ok(mut last_flush) = self.last_flush.lock() else {
+                return;
+            };
+
+            // Skip it if a record was flushed in the meantime
+            let now = Instant::now();
+            if last_flush.is_some_and(|last| now - last < interval) {
+                continue;
+            }
+
+            *last_flush = Some(now);
+            drop(last_flush);
+
+            let _ = self.flush_all();
+        }
+    }
+
+    /// Whether a record of the `priority` should be flushed right away
+    pub(crate) fn should_flush(&self, priority: Prio) -> bool {
+        if priority == Prio::Fatal {
+            return true;
+        }
+
+        let Ok(policy) = self.flush_policy.read() else {
+            return true;
+        };
+
+        match *policy {
+            FlushPolicy::EveryRecord => return true,
+            FlushPolicy::Priority(min_priority) => return priority >= min_priority,
+            FlushPolicy::Interval(interval) => {
+                let Ok(mut last_flush) = self.last_flush.lock() else {
+                    return true;
+                };
+
+                let now = Instant::now();
+                if last_flush.is_some_and(|last| now - last < interval) {
+                    return false;
+                }
+
+                *last_flush = Some(now);
+                return true;
+            }
+        }
+    }
+}
+
+impl Drop for FlushGuard<'_> {
+    fn drop(&mut self) {
+        let _ = self.logger.flush_all();
+    }
+}
+
+//-----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn read_log(path: &std::path::Path) -> String {
+        return std::fs::read_to_string(path).unwrap();
+    }
+
+    #[test]
+    fn test_flush_guard() {
+        let path = std::env::temp_dir().join(format!("soh_log_guard_{}.log", std::process::id()));
+        let logger = Logger::new(Prio::Fatal, Prio::Debug);
+        logger.open_logfile(path.to_str().unwrap()).unwrap();
+
+        // Records below the priority stay in the buffer until the guard is dropped
+        let guard = logger
+            .set_flush_policy(FlushPolicy::Priority(Prio::Warning))
+            .unwrap();
+        logger.log(Prio::Info, "buffered");
+        assert!(!read_log(&path).contains("buffered"));
+
+        logger.log(Prio::Warning, "flushed");
+        assert!(read_log(&path).contains("[INFO] buffered"));
+        assert!(read_log(&path).contains("[WARN] flushed"));
+
+        logger.log(Prio::Debug, "at exit");
+        assert!(!read_log(&path).contains("at exit"));
+        drop(guard);
+        assert!(read_log(&path).contains("[DEBUG] at exit"));
+
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_flush_interval() {
+        let path =
+            std::env::temp_dir().join(format!("soh_log_interval_{}.log", std::process::id()));
+        let logger: &'static Logger = Box::leak(Box::new(Logger::new(Prio::Fatal, Prio::Debug)));
+        logger.open_logfile(path.to_str().unwrap()).unwrap();
+
+        let interval = Duration::from_millis(100);
+        let _guard = logger
+            .install_flush_policy(FlushPolicy::Interval(interval))
+            .unwrap();
+        logger.log(Prio::Info, "first");
+        logger.log(Prio::Info, "buffered");
+        assert!(!read_log(&path).contains("buffered"));
+
+        // The thread flushes without another record
+        let start = Instant::now();
+        while !read_log(&path).contains("[INFO] buffered") {
+            assert!(start.elapsed() < Duration::from_secs(5));
+            std::thread::sleep(interval);
+        }
+
+        // Switching back to flushing every record stops the thread
+        let _guard = logger.set_flush_policy(FlushPolicy::EveryRecord).unwrap();
+        while logger.flush_thread_running.load(Ordering::Acquire) {
+            assert!(start.elapsed() < Duration::from_secs(5));
+            std::thread::sleep(interval);
+        }
+
+        std::fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,86 @@
+//-----------------------------------------------------------------------------
+//! Lightweight structured tracing: nested spans which time their scope and prefix the records
+//! logged inside of them
+//-----------------------------------------------------------------------------
+use crate::{Logger, Prio};
+use std::cell::RefCell;
+use std::time::Instant;
+//-----------------------------------------------------------------------------
+
+thread_local! {
+    /// Active spans of the thread from the outermost, formatted as `name{field=value}`
+    static SPANS: RefCell<Vec<String>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Active span, created by [Logger::span] or the `span!` macro. Logs the time spent in it at the
+/// debug priority when dropped.
+///
+/// Spans are per thread, so the guard can't be sent to another thread.
+pub struct Span<'a> {
+    logger: &'a Logger,
+    /// Position in the thread's span stack
+    depth: usize,
+    start: Instant,
+    _not_send: std::marker::PhantomData<*const ()>,
+}
+
+impl Logger {
+    /// Enters a span, records logged on this thread until the returned guard is dropped are
+    /// prefixed with its path ( `frame{n=3}:draw: message` )
+    pub fn span(&self, name: &str, fields: &[(&str, &dyn std::fmt::Display)]) -> Span<'_> {
+        let mut span = name.to_owned();
+        if !fields.is_empty() {
+            let fields = fields
+                .iter()
+                .map(|(key, value)| format!("{key}={value}"))
+                .collect::<Vec<_>>();
+            span = format!("{span}{{{}}}", fields.join(" "));
+        }
+
+        let depth = SPANS.with_borrow_mut(|spans| {
+            spans.push(span);
+            return spans.len() - 1;
+        });
+
+        return Span {
+            logger: self,
+            depth,
+            start: Instant::now(),
+            _not_send: std::marker::PhantomData,
+        };
+    }
+}
+
+impl Drop for Span<'_> {
+    fn drop(&mut self) {
+        let elapsed = self.start.elapsed();
+
+        // Also leaves the inner spans if they were leaked, nothing to do if an outer span already
+        // left this one
+        let path = SPANS.with_borrow_mut(|spans| {
+            let path = spans.get(..=self.depth)?.join(":");
+            spans.truncate(self.depth);
+            return Some(path);
+        });
+        let Some(path) = path else {
+            return;
+        };
+
+        // The path already has the prefix of the outer spans
+        let msg = format!("{path}: {elapsed:.2?}");
+        self.logger.log_stderr(Prio::Debug, None, &msg);
+        self.logger.log_file(Prio::Debug, None, &msg);
+    }
+}
+
+/// Prefixes the message with the path of the active spans
+pub(crate) fn with_span_path(msg: &str) -> std::borrow::Cow<'_, str> {
+    return SPANS.with_borrow(|spans| {
+        if spans.is_empty() {
+            return std::borrow::Cow::Borrowed(msg);
+        }
+        return std::borrow::Cow::Owned(format!("{}: {msg}", spans.join(":")));
+    });
+}
+
+//-----------------------------------------------------------------------------
@@ -0,0 +1,133 @@
+//-----------------------------------------------------------------------------
+//! Coarse CPU profiling through the logger
+//-----------------------------------------------------------------------------
+use crate::{Logger, Prio};
+use std::time::{Duration, Instant};
+//-----------------------------------------------------------------------------
+/// Logs the time spent in a scope when dropped. Created by [Logger::time_scope] or the
+/// `log_time_scope!` macro.
+///
+/// Optionally also logs the change of the resident memory of the process ( only on Linux ).
+pub struct TimeScope<'a> {
+    logger: &'a Logger,
+    label: String,
+    start: Instant,
+    start_memory: Option<usize>,
+}
+
+impl Logger {
+    /// Starts timing a scope, the elapsed time is logged when the returned value is dropped
+    pub fn time_scope(&self, label: &str, track_memory: bool) -> TimeScope<'_> {
+        let start_memory = if track_memory {
+            resident_memory()
+        } else {
+            None
+        };
+
+        return TimeScope {
+            logger: self,
+            label: label.to_owned(),
+            start: Instant::now(),
+            start_memory,
+        };
+    }
+}
+
+impl Drop for TimeScope<'_> {
+    fn drop(&mut self) {
+        let elapsed = self.start.elapsed();
+
+        let memory_delta = self
+            .start_memory
+            .zip(resident_memory())
+            .map(|(start, end)| end as isize - start as isize);
+
+        let msg = match memory_delta {
+            Some(delta) => format!(
+                "{}: {elapsed:.2?}, memory {delta:+} KiB",
+                self.label,
+                delta = delta / 1024
+            ),
+            None => format!("{}: {elapsed:.2?}", self.label),
+        };
+
+        self.logger.log(Prio::Info, &msg);
+    }
+}
+
+/// Resident memory of the process in bytes
+#[cfg(target_os = "linux")]
+fn resident_memory() -> Option<usize> {
+    let status = std::fs::read_to_string("/proc/self/status").ok()?;
+
+    let kib = status
+        .lines()
+        .find_map(|line| line.strip_prefix("VmRSS:"))?
+        .trim()
+        .strip_suffix("kB")?
+        .trim()
+        .parse::<usize>()
+        .ok()?;
+
+    return Some(kib * 1024);
+}
+
+#[cfg(not(target_os = "linux"))]
+fn resident_memory() -> Option<usize> {
+    return None;
+}
+
+//-----------------------------------------------------------------------------
+/// Measures the total time and the time between laps.
+///
+/// ```rust
+/// let mut stopwatch = soh_log::Stopwatch::new();
+///
+/// let first = stopwatch.lap();
+/// let second = stopwatch.lap();
+///
+/// assert!(first + second <= stopwatch.elapsed());
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct Stopwatch {
+    start: Instant,
+    last_lap: Instant,
+}
+
+impl Stopwatch {
+    pub fn new() -> Self {
+        let now = Instant::now();
+
+        return Stopwatch {
+            start: now,
+            last_lap: now,
+        };
+    }
+
+    /// Time since the creation ( or the last restart )
+    pub fn elapsed(&self) -> Duration {
+        return self.start.elapsed();
+    }
+
+    /// Time since the previous lap, starts a new lap
+    pub fn lap(&mut self) -> Duration {
+        let now = Instant::now();
+        let lap = now - self.last_lap;
+        self.last_lap = now;
+
+        return lap;
+    }
+
+    /// Resets the total time and the lap
+    pub fn restart(&mut self) {
+        *self = Self::new();
+    }
+}
+
+impl Default for Stopwatch {
+    fn default() -> Self {
+        return Self::new();
+    }
+}
+
+//-----------------------------------------------------------------------------
@@ -41,12 +41,74 @@ fn get_data(input: &syn::ItemStruct) -> VecData {
     };
 }
 
+/// Primitive types getting the const operations
+const CONST_TYPES: [&str; 10] = [
+    "i8", "i16", "i32", "i64", "u8", "u16", "u32", "u64", "f32", "f64",
+];
+
+//-----------------------------------------------------------------------------
+/// Builds the doc comments of the generated items, the examples use the struct name and its
+/// number of fields so every vector gets its own doctests
+struct Docs {
+    struct_name: String,
+    num_of_fields: usize,
+}
+
+impl Docs {
+    /// Doc comment with an example
+    fn example(&self, summary: &str, code: &str) -> String {
+        return format!(
+            "{summary}\n\n# Examples\n\n```\nuse soh_math::{};\n\n{code}\n```",
+            self.struct_name
+        );
+    }
+
+    /// Constructor call of the vector with the components `f(0), f(1), ...`
+    fn vec<D: std::fmt::Display>(&self, f: impl Fn(usize) -> D) -> String {
+        let args = (0..self.num_of_fields)
+            .map(|i| f(i).to_string())
+            .collect::<Vec<_>>();
+        return format!("{}::new({})", self.struct_name, args.join(", "));
+    }
+
+    /// `new` with the integer components `1, 2, 3, ...`
+    fn ints(&self) -> String {
+        return self.vec(|i| i + 1);
+    }
+
+    /// `new` with the float components `1.0, 2.0, 3.0, ...`
+    fn floats(&self) -> String {
+        return self.vec(|i| format!("{:?}", (i + 1) as f32));
+    }
+
+    /// `new` with the float components `x, y, 0.0, ...`
+    fn floats_xy(&self, x: f32, y: f32) -> String {
+        return self.vec(|i| match i {
+            0 => format!("{x:?}"),
+            1 => format!("{y:?}"),
+            _ => "0.0".to_string(),
+        });
+    }
+
+    /// Tuple or array of the integer components `1, 2, 3, ...`
+    fn int_list(&self) -> String {
+        return (1..=self.num_of_fields)
+            .map(|i| i.to_string())
+            .collect::<Vec<_>>()
+            .join(", ");
+    }
+}
+
 //-----------------------------------------------------------------------------
 /// This attribute implements a lot of the generic stuff for vectors:
 /// - Derive macros ( Debug, Copy, etc... )
 /// - Operator overloads ( add, sub, mul, div )
 /// - Convert trait
 /// - Some simple math ( dot product, len )
+/// - Const fn operations for the primitive types
+///
+/// Every generated item gets a doc comment with an example written for the struct it's applied
+/// to ( `Vec3::new(1, 2, 3)` for `Vec3` ), so the examples are tested for each vector.
 #[proc_macro_attribute]
 pub fn impl_vec(_attr: TokenStream, item: TokenStream) -> TokenStream {
     // Parse input
@@ -61,25 +123,456 @@ pub fn impl_vec(_attr: TokenStream, item: TokenStream) -> TokenStream {
         field_indexes,
     } = get_data(&input);
 
-    // Use hypot for 2D length
+    let docs = Docs {
+        struct_name: struct_name.to_string(),
+        num_of_fields,
+    };
+    let name = struct_name.to_string();
+    let ints = docs.ints();
+    let floats = docs.floats();
+    let ints2 = docs.vec(|i| 2 * (i + 1));
+    let ints_inc = docs.vec(|i| i + 2);
+    let list = docs.int_list();
+    // Sums for the expected results ( 1 + 2 + 3..., 1 + 4 + 9... )
+    let sum = (1..=num_of_fields).sum::<usize>();
+    let sum_sq = (1..=num_of_fields).map(|i| i * i).sum::<usize>();
+
+    // Vector structs without a doc comment get one
+    let has_doc = input.attrs.iter().any(|attr| attr.path().is_ident("doc"));
+    let doc_struct = match has_doc {
+        true => quote! {},
+        false => {
+            let doc = format!("Vector with {num_of_fields} components");
+            quote! { #[doc = #doc] }
+        }
+    };
+
+    let first_field = &field_names[0];
+    let last_field = &field_names[num_of_fields - 1];
+    let doc_new = docs.example(
+        "Constructor",
+        &format!(
+            "let v = {floats};\n\nassert_eq!(v.{first_field}, 1.0);\nassert_eq!(v.{last_field}, {:?});",
+            num_of_fields as f32
+        ),
+    );
+    let doc_splat = docs.example(
+        "Vector with all components equal `value`",
+        &format!("assert_eq!({name}::splat(2.0), {});", docs.vec(|_| "2.0")),
+    );
+    let doc_map = docs.example(
+        "Apply a mapping functor to coordinates to create a new vector",
+        &format!("let v = {floats}.map(|c| c as i32);\nassert_eq!(v, {ints});"),
+    );
+    let doc_transform = docs.example(
+        "Apply a functor on the vector, changing it's coordinates",
+        &format!(
+            "let mut v = {floats};\nv.transform(|c| *c *= 2.0);\n\nassert_eq!(v, {});",
+            docs.vec(|i| format!("{:?}", (2 * (i + 1)) as f32))
+        ),
+    );
+
+    let doc_whole_consts = docs.example(
+        "Vectors with all components equal `0`, `1` and `2`",
+        &format!(
+            "use soh_math::traits::WholeConsts;\n\nassert_eq!({name}::<i32>::TWO, {name}::splat(2));"
+        ),
+    );
+    let [doc_zero, doc_one, doc_two] =
+        [("zero", "0.0"), ("one", "1.0"), ("two", "2.0")].map(|(word, value)| {
+            docs.example(
+                &format!("Vector with all components equal {word}"),
+                &format!("assert_eq!({name}::<f32>::{word}(), {name}::splat({value}));"),
+            )
+        });
+
+    let doc_len2 = docs.example(
+        "Calculate the squared len of the vector (faster than [Self::len])",
+        &format!("assert_eq!({ints}.len2(), {sum_sq});"),
+    );
+    let doc_distance2 = docs.example(
+        "Calculate the squared distance to `other` (faster than [Self::distance])",
+        &format!(
+            "assert_eq!({ints}.distance2(&{name}::splat(1)), {});",
+            (0..num_of_fields).map(|i| i * i).sum::<usize>()
+        ),
+    );
+    let doc_midpoint = docs.example(
+        "Calculate the point halfway between two points",
+        &format!(
+            "let a = {name}::splat(0.0);\nlet b = {};\n\nassert_eq!({name}::midpoint(&a, &b), {floats});",
+            docs.vec(|i| format!("{:?}", (2 * (i + 1)) as f32))
+        ),
+    );
+    let doc_dot = docs.example(
+        "Calculate the dot product of two vectors",
+        &format!(
+            "assert_eq!({name}::dot(&{ints}, &{name}::splat(2)), {});",
+            2 * sum
+        ),
+    );
+    let doc_mul = docs.example(
+        "Component vise multiplication",
+        &format!("assert_eq!({name}::mul(&{ints}, &{name}::splat(2)), {ints2});"),
+    );
+    let doc_div = docs.example(
+        "Component vise division",
+        &format!("assert_eq!({name}::div(&{ints2}, &{name}::splat(2)), {ints});"),
+    );
+
+    let ord_note = |method: &str| {
+        return format!(
+            "\n\nThis inherent method takes precedence over the lexicographic [Ord::{method}] \
+             of the derived `Ord`, so `a.{method}(..)` is component wise. Call \
+             `Ord::{method}(a, ..)` for the lexicographic result"
+        );
+    };
+    let doc_min = docs.example(
+        &format!("Component wise minimum{}", ord_note("min")),
+        &format!(
+            "assert_eq!({name}::min({ints}, {name}::splat(2)), {});\nassert_eq!(Ord::min({ints}, {name}::splat(2)), {ints});",
+            docs.vec(|i| (i + 1).min(2))
+        ),
+    );
+    let doc_max = docs.example(
+        &format!("Component wise maximum{}", ord_note("max")),
+        &format!(
+            "assert_eq!({name}::max({ints}, {name}::splat(2)), {});\nassert_eq!(Ord::max({ints}, {name}::splat(2)), {name}::splat(2));",
+            docs.vec(|i| (i + 1).max(2))
+        ),
+    );
+    let doc_clamp = docs.example(
+        &format!(
+            "Clamp every component between the components of `lo` and `hi` ( `lo` should be less \
+             or equal than `hi` ){}",
+            ord_note("clamp")
+        ),
+        &format!(
+            "let lo = {name}::splat(2);\nlet hi = {name}::splat(3);\n\nassert_eq!({ints}.clamp(lo, hi), {});",
+            docs.vec(|i| (i + 1).clamp(2, 3))
+        ),
+    );
+    let doc_clamp_scalar = docs.example(
+        "Clamp every component between `lo` and `hi`",
+        &format!(
+            "assert_eq!({ints}.clamp_scalar(2, 3), {});",
+            docs.vec(|i| (i + 1).clamp(2, 3))
+        ),
+    );
+    let doc_abs = docs.example(
+        "Absolute value of every component",
+        &format!(
+            "assert_eq!({}.abs(), {ints});",
+            docs.vec(|i| format!("-{}", i + 1))
+        ),
+    );
+
+    let doc_len = docs.example(
+        "Calculate the len of the vector ( for comparisons prefer using [Self::len2] )",
+        &format!(
+            "let v: {name}<f32> = {};\n\nassert!((v.len() - 5.0).abs() < 1e-6);",
+            docs.floats_xy(3.0, 4.0)
+        ),
+    );
+    let doc_normalized = docs.example(
+        "Calculate and return a normalized version of `self`",
+        &format!(
+            "let v: {name}<f32> = {};\nlet v = v.normalized();\n\nassert!((v.len() - 1.0).abs() < 1e-6);\nassert!((v.x - 0.6).abs() < 1e-6);",
+            docs.floats_xy(3.0, 4.0)
+        ),
+    );
+    let doc_distance = docs.example(
+        "Calculate the distance to `other` ( for comparisons prefer using [Self::distance2] )",
+        &format!(
+            "let a: {name}<f32> = {name}::splat(1.0);\nlet b = a + {};\n\nassert!((a.distance(&b) - 5.0).abs() < 1e-6);",
+            docs.floats_xy(3.0, 4.0)
+        ),
+    );
+    let doc_move_towards = docs.example(
+        "Move towards `target` by at most `max_delta` without overshooting it",
+        &format!(
+            "let start = {name}::splat(0.0);\nlet target = {};\n\nassert_eq!(start.move_towards(&target, 2.5), {});\nassert_eq!(start.move_towards(&target, 100.0), target);",
+            docs.floats_xy(10.0, 0.0),
+            docs.floats_xy(2.5, 0.0)
+        ),
+    );
+    let doc_normalize = docs.example(
+        "Make the len of vector 1.0",
+        &format!(
+            "let mut v: {name}<f32> = {};\nv.normalize();\n\nassert!((v.len() - 1.0).abs() < 1e-6);",
+            docs.floats_xy(3.0, 4.0)
+        ),
+    );
+
+    let doc_reflect = docs.example(
+        "Reflect off a surface with the unit length `normal` ( `self - normal * 2 dot(self, normal)` )",
+        &format!(
+            "let v: {name}<f32> = {};\nlet normal = {};\n\nassert_eq!(v.reflect(&normal), {});",
+            docs.floats_xy(1.0, -1.0),
+            docs.floats_xy(0.0, 1.0),
+            docs.floats_xy(1.0, 1.0)
+        ),
+    );
+    let doc_project_onto = docs.example(
+        "Component of `self` parallel to `other`, the zero vector if `other` has zero len",
+        &format!(
+            "let v: {name}<f32> = {};\nlet axis = {};\n\nassert_eq!(v.project_onto(&axis), {});\nassert_eq!(v.project_onto(&{name}::splat(0.0)), {name}::splat(0.0));",
+            docs.floats_xy(3.0, 4.0),
+            docs.floats_xy(2.0, 0.0),
+            docs.floats_xy(3.0, 0.0)
+        ),
+    );
+    let doc_reject_from = docs.example(
+        "Component of `self` perpendicular to `other` ( `self - self.project_onto(other)`, so \
+         `self` if `other` has zero len )",
+        &format!(
+            "let v: {name}<f32> = {};\nlet axis = {};\n\nassert_eq!(v.reject_from(&axis), {});\nassert_eq!(v.reject_from(&{name}::splat(0.0)), v);",
+            docs.floats_xy(3.0, 4.0),
+            docs.floats_xy(2.0, 0.0),
+            docs.floats_xy(0.0, 4.0)
+        ),
+    );
+
+    let doc_convert = docs.example(
+        "Convert the components with [From]",
+        &format!(
+            "use soh_math::Convert;\n\nlet v: {name}<f64> = {name}::<f32>::splat(1.5).convert();\nassert_eq!(v, {name}::splat(1.5));"
+        ),
+    );
+
+    let doc_add = docs.example(
+        "Component wise addition",
+        &format!("assert_eq!({ints} + {name}::splat(1), {ints_inc});"),
+    );
+    let doc_add_assign = docs.example(
+        "Component wise addition",
+        &format!("let mut v = {ints};\nv += {name}::splat(1);\n\nassert_eq!(v, {ints_inc});"),
+    );
+    let doc_sub = docs.example(
+        "Component wise subtraction",
+        &format!("assert_eq!({ints_inc} - {name}::splat(1), {ints});"),
+    );
+    let doc_sub_assign = docs.example(
+        "Component wise subtraction",
+        &format!("let mut v = {ints_inc};\nv -= {name}::splat(1);\n\nassert_eq!(v, {ints});"),
+    );
+    let doc_mul_scalar = docs.example(
+        "Multiplication of every component by a scalar",
+        &format!("assert_eq!({ints} * 2, {ints2});"),
+    );
+    let doc_mul_assign = docs.example(
+        "Multiplication of every component by a scalar",
+        &format!("let mut v = {ints};\nv *= 2;\n\nassert_eq!(v, {ints2});"),
+    );
+    let doc_div_scalar = docs.example(
+        "Division of every component by a scalar",
+        &format!("assert_eq!({ints2} / 2, {ints});"),
+    );
+    let doc_div_assign = docs.example(
+        "Division of every component by a scalar",
+        &format!("let mut v = {ints2};\nv /= 2;\n\nassert_eq!(v, {ints});"),
+    );
+    // Operators taking references, for the generic code holding references
+    let doc_ref_op = |summary: &str, op: &str, lhs: &str, rhs: &str, result: &str| {
+        docs.example(
+            &format!("{summary} of references ( copies the operands )"),
+            &format!(
+                "let a = {lhs};\nlet b = {rhs};\n\nassert_eq!(&a {op} &b, {result});\nassert_eq!(a {op} &b, {result});\nassert_eq!(&a {op} b, {result});"
+            ),
+        )
+    };
+    let doc_add_ref = doc_ref_op(
+        "Component wise addition",
+        "+",
+        &ints,
+        &format!("{name}::splat(1)"),
+        &ints_inc,
+    );
+    let doc_sub_ref = doc_ref_op(
+        "Component wise subtraction",
+        "-",
+        &ints_inc,
+        &format!("{name}::splat(1)"),
+        &ints,
+    );
+    let doc_add_assign_ref = docs.example(
+        "Component wise addition of a reference",
+        &format!("let mut v = {ints};\nv += &{name}::splat(1);\n\nassert_eq!(v, {ints_inc});"),
+    );
+    let doc_sub_assign_ref = docs.example(
+        "Component wise subtraction of a reference",
+        &format!("let mut v = {ints_inc};\nv -= &{name}::splat(1);\n\nassert_eq!(v, {ints});"),
+    );
+    let doc_mul_scalar_ref = docs.example(
+        "Multiplication of every component of a reference by a scalar",
+        &format!("let v = {ints};\nassert_eq!(&v * 2, {ints2});"),
+    );
+    let doc_div_scalar_ref = docs.example(
+        "Division of every component of a reference by a scalar",
+        &format!("let v = {ints2};\nassert_eq!(&v / 2, {ints});"),
+    );
+    let doc_neg_ref = docs.example(
+        "Negate every component of a reference",
+        &format!(
+            "let v = {ints};\nassert_eq!(-&v, {});",
+            docs.vec(|i| format!("-{}", i + 1))
+        ),
+    );
+
+    let doc_neg = docs.example(
+        "Negate every component",
+        &format!(
+            "assert_eq!(-{ints}, {});",
+            docs.vec(|i| format!("-{}", i + 1))
+        ),
+    );
+
+    let doc_index = docs.example(
+        "Component by index, in the declaration order of the fields ( same as the arrays )\n\n\
+         # Panics\n\n\
+         If the index is out of range",
+        &format!(
+            "let v = {ints};\n\nassert_eq!(v[0], v.{first_field});\nassert_eq!(v[{}], v.{last_field});",
+            num_of_fields - 1
+        ),
+    );
+    let doc_index_mut = docs.example(
+        "Mutable component by index, in the declaration order of the fields\n\n\
+         # Panics\n\n\
+         If the index is out of range",
+        &format!("let mut v = {ints};\nv[0] = 10;\n\nassert_eq!(v.{first_field}, 10);"),
+    );
+    let doc_iter = docs.example(
+        "Iterate over the components in the declaration order of the fields",
+        &format!("let v = {ints};\n\nassert_eq!(v.iter().sum::<i32>(), {sum});"),
+    );
+    let doc_iter_mut = docs.example(
+        "Iterate over the mutable components in the declaration order of the fields",
+        &format!(
+            "let mut v = {ints};\nfor c in v.iter_mut() {{\n    *c *= 2;\n}}\n\nassert_eq!(v, {ints2});"
+        ),
+    );
+    let doc_into_iter = docs.example(
+        "Iterate over the components in the declaration order of the fields",
+        &format!(
+            "let v = {ints};\n\nassert_eq!(v.into_iter().collect::<Vec<_>>(), [{list}]);\nfor c in &v {{\n    assert!(*c > 0);\n}}"
+        ),
+    );
+
+    let doc_from_iter = docs.example(
+        "Vector from the first items of the iterator, the missing components are `T::default()`",
+        &format!(
+            "let v: {name}<i32> = (1..).collect();\nassert_eq!(v, {ints});\n\nlet v: {name}<i32> = [1].into_iter().collect();\nassert_eq!(v, {});",
+            docs.vec(|i| (i == 0) as i32)
+        ),
+    );
+    let doc_from_array = docs.example(
+        "Vector from an array of the components",
+        &format!("assert_eq!({name}::from([{list}]), {ints});"),
+    );
+    let doc_into_array = docs.example(
+        "Array of the components",
+        &format!(
+            "let array: [i32; {num_of_fields}] = {ints}.into();\nassert_eq!(array, [{list}]);"
+        ),
+    );
+    let doc_from_tuple = docs.example(
+        "Vector from a tuple of the components",
+        &format!("assert_eq!({name}::from(({list})), {ints});"),
+    );
+    let doc_into_tuple = docs.example(
+        "Tuple of the components",
+        &format!(
+            "let tuple: ({}) = {ints}.into();\nassert_eq!(tuple, ({list}));",
+            vec!["i32"; num_of_fields].join(", ")
+        ),
+    );
+
+    // Use hypot for 2D length ( through `Real` so it's deterministic with the feature )
     let len_impl = if num_of_fields == 2 {
         quote! {
+            #[doc = #doc_len]
             pub fn len(&self) -> #ttype {
-                return #ttype::hypot(self.x, self.y);
+                return crate::real::Real::hypot(self.x, self.y);
             }
         }
     } else {
         quote! {
+            #[doc = #doc_len]
             pub fn len(&self) -> #ttype {
                 return self.len2().sqrt();
             }
         }
     };
 
+    // Operations usable in constants ( trait methods can't be called in const fn, so they are
+    // implemented for each primitive type )
+    let const_impls = CONST_TYPES.iter().map(|name| {
+        let ctype = quote::format_ident!("{}", name);
+        let field_names = &field_names;
+
+        // The examples are only on the `i32` operations, the rest are the same
+        let doc = |summary: &str, code: String| match *name == "i32" {
+            true => docs.example(summary, &code),
+            false => summary.to_string(),
+        };
+        let struct_name_str = struct_name.to_string();
+        let ints = ints.replacen("::new", "::<i32>::new", 1);
+        let ints_inc = ints_inc.replacen("::new", "::<i32>::new", 1);
+        let doc_add = doc(
+            "Addition usable in constants",
+            format!(
+                "const V: {struct_name_str}<i32> = {ints}.const_add({struct_name_str}::splat(1));\nassert_eq!(V, {ints_inc});"
+            ),
+        );
+        let doc_sub = doc(
+            "Subtraction usable in constants",
+            format!(
+                "const V: {struct_name_str}<i32> = {ints_inc}.const_sub({struct_name_str}::splat(1));\nassert_eq!(V, {ints});"
+            ),
+        );
+        let doc_mul = doc(
+            "Multiplication by a scalar usable in constants",
+            format!("const V: {struct_name_str}<i32> = {ints}.const_mul(2);\nassert_eq!(V, {ints2});"),
+        );
+        let doc_dot = doc(
+            "Dot product usable in constants",
+            format!(
+                "const DOT: i32 = {ints}.const_dot({struct_name_str}::splat(2));\nassert_eq!(DOT, {});",
+                2 * sum
+            ),
+        );
+
+        quote! {
+            impl #struct_name<#ctype> {
+                #[doc = #doc_add]
+                pub const fn const_add(self, rhs: Self) -> Self {
+                    return #struct_name { #(#field_names: self.#field_names + rhs.#field_names),* };
+                }
+
+                #[doc = #doc_sub]
+                pub const fn const_sub(self, rhs: Self) -> Self {
+                    return #struct_name { #(#field_names: self.#field_names - rhs.#field_names),* };
+                }
+
+                #[doc = #doc_mul]
+                pub const fn const_mul(self, rhs: #ctype) -> Self {
+                    return #struct_name { #(#field_names: self.#field_names * rhs),* };
+                }
+
+                #[doc = #doc_dot]
+                pub const fn const_dot(self, rhs: Self) -> #ctype {
+                    return #(self.#field_names * rhs.#field_names)+*;
+                }
+            }
+        }
+    });
+
     let a = quote! {
         #[repr(C)]
         #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
         #[derive(Debug, Default, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
+        #doc_struct
         #input
 
         // Struct implementations
@@ -87,14 +580,21 @@ pub fn impl_vec(_attr: TokenStream, item: TokenStream) -> TokenStream {
         where
             #ttype: Copy,
         {
-            /// Constructor
+            #[doc = #doc_new]
             pub const fn new(#(#field_names: #ttype),*) -> Self {
                 return #struct_name {
                     #(#field_names,)*
                 };
             }
 
-            /// Apply a mapping functor to coordinates to create a new vector
+            #[doc = #doc_splat]
+            pub const fn splat(value: #ttype) -> Self {
+                return #struct_name {
+                    #(#field_names: value,)*
+                };
+            }
+
+            #[doc = #doc_map]
             pub fn map<Func, U>(&self, mut f: Func) -> #struct_name<U>
             where
                 Func: FnMut(#ttype) -> U,
@@ -104,7 +604,7 @@ pub fn impl_vec(_attr: TokenStream, item: TokenStream) -> TokenStream {
                 };
             }
 
-            /// Apply a functor on the vector, changing it's coordinates
+            #[doc = #doc_transform]
             pub fn transform<Func>(&mut self, mut f: Func)
             where
                 Func: FnMut(&mut #ttype),
@@ -115,12 +615,16 @@ pub fn impl_vec(_attr: TokenStream, item: TokenStream) -> TokenStream {
 
         //----------------------------------------------------------------------
         // One, Zero
+        #[doc = #doc_whole_consts]
         impl<#ttype> crate::traits::WholeConsts for #struct_name<#ttype>
         where
             #ttype: crate::traits::WholeConsts,
         {
+            /// All components equal zero
             const ZERO: Self = #struct_name { #(#field_names: #ttype::ZERO),* };
+            /// All components equal one
             const ONE:  Self = #struct_name { #(#field_names: #ttype::ONE),* };
+            /// All components equal two
             const TWO:  Self = #struct_name { #(#field_names: #ttype::TWO),* };
         }
 
@@ -128,17 +632,17 @@ pub fn impl_vec(_attr: TokenStream, item: TokenStream) -> TokenStream {
         where
             #ttype: crate::traits::WholeConsts,
         {
-            /// Vector with all components equal zero
+            #[doc = #doc_zero]
             pub const fn zero() -> Self {
                 return <Self as crate::traits::WholeConsts>::ZERO;
             }
 
-            /// Vector with all components equal one
+            #[doc = #doc_one]
             pub const fn one() -> Self {
                 return <Self as crate::traits::WholeConsts>::ONE;
             }
 
-            /// Vector with all components equal two
+            #[doc = #doc_two]
             pub const fn two() -> Self {
                 return <Self as crate::traits::WholeConsts>::TWO;
             }
@@ -150,24 +654,37 @@ pub fn impl_vec(_attr: TokenStream, item: TokenStream) -> TokenStream {
         where
             #ttype: num_traits::Num + Copy,
         {
-            /// Calculate the squared len of the vector (faster than [Self::len])
+            #[doc = #doc_len2]
             pub fn len2(&self) -> #ttype {
                 return #(self.#field_names * self.#field_names)+*;
             }
 
-            /// Calculate the dot product of two vectors
+            #[doc = #doc_distance2]
+            pub fn distance2(&self, other: &Self) -> #ttype {
+                return (*self - *other).len2();
+            }
+
+            #[doc = #doc_midpoint]
+            pub fn midpoint(vec1: &Self, vec2: &Self) -> Self {
+                let two = #ttype::one() + #ttype::one();
+                return #struct_name {
+                    #(#field_names: (vec1.#field_names + vec2.#field_names) / two),*
+                };
+            }
+
+            #[doc = #doc_dot]
             pub fn dot(vec1: &Self, vec2: &Self) -> #ttype {
                 return #(vec1.#field_names * vec2.#field_names)+*;
             }
 
-            /// Component vise multiplication
+            #[doc = #doc_mul]
             pub fn mul(vec1: &Self, vec2: &Self) -> Self {
                 return #struct_name {
                     #(#field_names: vec1.#field_names * vec2.#field_names),*
                 };
             }
 
-            /// Component vise division
+            #[doc = #doc_div]
             pub fn div(vec1: &Self, vec2: &Self) -> Self {
                 return #struct_name {
                     #(#field_names: vec1.#field_names / vec2.#field_names),*
@@ -175,31 +692,121 @@ pub fn impl_vec(_attr: TokenStream, item: TokenStream) -> TokenStream {
             }
         }
 
+        // Comparisons
+        impl<#ttype> #struct_name<#ttype>
+        where
+            #ttype: PartialOrd + Copy,
+        {
+            #[doc = #doc_min]
+            pub fn min(self, other: Self) -> Self {
+                return #struct_name {
+                    #(#field_names: match other.#field_names < self.#field_names {
+                        true => other.#field_names,
+                        false => self.#field_names,
+                    }),*
+                };
+            }
+
+            #[doc = #doc_max]
+            pub fn max(self, other: Self) -> Self {
+                return #struct_name {
+                    #(#field_names: match other.#field_names > self.#field_names {
+                        true => other.#field_names,
+                        false => self.#field_names,
+                    }),*
+                };
+            }
+
+            #[doc = #doc_clamp]
+            pub fn clamp(self, lo: Self, hi: Self) -> Self {
+                debug_assert!(
+                    #(lo.#field_names <= hi.#field_names)&&*,
+                    "clamp bounds should be ordered"
+                );
+                return self.max(lo).min(hi);
+            }
+
+            #[doc = #doc_clamp_scalar]
+            pub fn clamp_scalar(self, lo: #ttype, hi: #ttype) -> Self {
+                return self.clamp(Self::splat(lo), Self::splat(hi));
+            }
+        }
+
+        impl<#ttype> #struct_name<#ttype>
+        where
+            #ttype: num_traits::Signed + Copy,
+        {
+            #[doc = #doc_abs]
+            pub fn abs(&self) -> Self {
+                return #struct_name {
+                    #(#field_names: self.#field_names.abs()),*
+                };
+            }
+        }
+
         // Float impl
         impl<#ttype> #struct_name<#ttype>
         where
-            #ttype: num_traits::Float,
+            #ttype: num_traits::Float + crate::real::Real,
         {
-            /// Calculate the len of the vector ( for comparisons prefer using [Self::len2] )
             #len_impl
 
-            /// Calculate and return a normalized version of `self`
+            #[doc = #doc_normalized]
             pub fn normalized(&self) -> Self {
                 return *self / self.len();
             }
+
+            #[doc = #doc_distance]
+            pub fn distance(&self, other: &Self) -> #ttype {
+                return (*self - *other).len();
+            }
+
+            #[doc = #doc_move_towards]
+            pub fn move_towards(&self, target: &Self, max_delta: #ttype) -> Self {
+                let delta = *target - *self;
+                let distance = delta.len();
+
+                if distance <= max_delta || distance == #ttype::zero() {
+                    return *target;
+                }
+
+                return *self + delta * (max_delta / distance);
+            }
+
+            #[doc = #doc_reflect]
+            pub fn reflect(&self, normal: &Self) -> Self {
+                let two = #ttype::one() + #ttype::one();
+                return *self - *normal * (two * Self::dot(self, normal));
+            }
+
+            #[doc = #doc_project_onto]
+            pub fn project_onto(&self, other: &Self) -> Self {
+                let len2 = other.len2();
+                if len2 == #ttype::zero() {
+                    return *other;
+                }
+
+                return *other * (Self::dot(self, other) / len2);
+            }
+
+            #[doc = #doc_reject_from]
+            pub fn reject_from(&self, other: &Self) -> Self {
+                return *self - self.project_onto(other);
+            }
         }
 
         impl<#ttype> #struct_name<#ttype>
         where
-            #ttype: num_traits::Float + std::ops::DivAssign,
+            #ttype: num_traits::Float + crate::real::Real + std::ops::DivAssign,
         {
-            /// Make the len of vector 1.0
+            #[doc = #doc_normalize]
             pub fn normalize(&mut self) {
                 *self /= self.len();
             }
         }
 
         // Convert
+        #[doc = #doc_convert]
         impl<S, D> crate::Convert<#struct_name<D>> for #struct_name<S>
         where
             S: Copy,
@@ -214,7 +821,8 @@ pub fn impl_vec(_attr: TokenStream, item: TokenStream) -> TokenStream {
 
         // Operator implementations
         macro_rules! impl_op {
-            ($trait:ident, $fn:ident, $op:tt) => {
+            ($trait:ident, $fn:ident, $op:tt, $doc:expr, $ref_doc:expr) => {
+                #[doc = $doc]
                 impl<#ttype> std::ops::$trait for #struct_name<#ttype>
                 where
                     #ttype: std::ops::$trait<Output = #ttype>,
@@ -224,11 +832,45 @@ pub fn impl_vec(_attr: TokenStream, item: TokenStream) -> TokenStream {
                         return Self { #(#field_names: self.#field_names $op rhs.#field_names),* };
                     }
                 }
+
+                #[doc = $ref_doc]
+                impl<#ttype> std::ops::$trait<&#struct_name<#ttype>> for &#struct_name<#ttype>
+                where
+                    #ttype: std::ops::$trait<Output = #ttype> + Copy,
+                {
+                    type Output = #struct_name<#ttype>;
+                    fn $fn(self, rhs: &#struct_name<#ttype>) -> Self::Output {
+                        return *self $op *rhs;
+                    }
+                }
+
+                #[doc = $ref_doc]
+                impl<#ttype> std::ops::$trait<&#struct_name<#ttype>> for #struct_name<#ttype>
+                where
+                    #ttype: std::ops::$trait<Output = #ttype> + Copy,
+                {
+                    type Output = Self;
+                    fn $fn(self, rhs: &Self) -> Self::Output {
+                        return self $op *rhs;
+                    }
+                }
+
+                #[doc = $ref_doc]
+                impl<#ttype> std::ops::$trait<#struct_name<#ttype>> for &#struct_name<#ttype>
+                where
+                    #ttype: std::ops::$trait<Output = #ttype> + Copy,
+                {
+                    type Output = #struct_name<#ttype>;
+                    fn $fn(self, rhs: #struct_name<#ttype>) -> Self::Output {
+                        return *self $op rhs;
+                    }
+                }
             };
         }
 
         macro_rules! impl_op_assign {
-            ($trait:ident, $fn:ident, $op:tt) => {
+            ($trait:ident, $fn:ident, $op:tt, $doc:expr, $ref_doc:expr) => {
+                #[doc = $doc]
                 impl<#ttype> std::ops::$trait for #struct_name<#ttype>
                 where
                     #ttype: std::ops::$trait,
@@ -237,11 +879,22 @@ pub fn impl_vec(_attr: TokenStream, item: TokenStream) -> TokenStream {
                         #(self.#field_names $op rhs.#field_names;)*
                     }
                 }
+
+                #[doc = $ref_doc]
+                impl<#ttype> std::ops::$trait<&#struct_name<#ttype>> for #struct_name<#ttype>
+                where
+                    #ttype: std::ops::$trait + Copy,
+                {
+                    fn $fn(&mut self, rhs: &Self) {
+                        #(self.#field_names $op rhs.#field_names;)*
+                    }
+                }
             };
         }
 
         macro_rules! impl_scalar_op {
-            ($trait:ident, $fn:ident, $op:tt) => {
+            ($trait:ident, $fn:ident, $op:tt, $doc:expr, $ref_doc:expr) => {
+                #[doc = $doc]
                 impl<#ttype> std::ops::$trait<#ttype> for #struct_name<#ttype>
                 where
                     #ttype: std::ops::$trait<Output = #ttype> + Copy,
@@ -251,11 +904,23 @@ pub fn impl_vec(_attr: TokenStream, item: TokenStream) -> TokenStream {
                         return Self { #(#field_names: self.#field_names $op rhs),* };
                     }
                 }
+
+                #[doc = $ref_doc]
+                impl<#ttype> std::ops::$trait<#ttype> for &#struct_name<#ttype>
+                where
+                    #ttype: std::ops::$trait<Output = #ttype> + Copy,
+                {
+                    type Output = #struct_name<#ttype>;
+                    fn $fn(self, rhs: #ttype) -> Self::Output {
+                        return *self $op rhs;
+                    }
+                }
             };
         }
 
         macro_rules! impl_scalar_op_assign {
-            ($trait:ident, $fn:ident, $op:tt) => {
+            ($trait:ident, $fn:ident, $op:tt, $doc:expr) => {
+                #[doc = $doc]
                 impl<#ttype> std::ops::$trait<#ttype> for #struct_name<#ttype>
                 where
                     #ttype: std::ops::$trait + Copy,
@@ -267,18 +932,19 @@ pub fn impl_vec(_attr: TokenStream, item: TokenStream) -> TokenStream {
             };
         }
 
-        impl_op!(Add, add, +);
-        impl_op_assign!(AddAssign, add_assign, +=);
+        impl_op!(Add, add, +, #doc_add, #doc_add_ref);
+        impl_op_assign!(AddAssign, add_assign, +=, #doc_add_assign, #doc_add_assign_ref);
 
-        impl_op!(Sub, sub, -);
-        impl_op_assign!(SubAssign, sub_assign, -=);
+        impl_op!(Sub, sub, -, #doc_sub, #doc_sub_ref);
+        impl_op_assign!(SubAssign, sub_assign, -=, #doc_sub_assign, #doc_sub_assign_ref);
 
-        impl_scalar_op!(Mul, mul, *);
-        impl_scalar_op_assign!(MulAssign, mul_assign, *=);
+        impl_scalar_op!(Mul, mul, *, #doc_mul_scalar, #doc_mul_scalar_ref);
+        impl_scalar_op_assign!(MulAssign, mul_assign, *=, #doc_mul_assign);
 
-        impl_scalar_op!(Div, div, /);
-        impl_scalar_op_assign!(DivAssign, div_assign, /=);
+        impl_scalar_op!(Div, div, /, #doc_div_scalar, #doc_div_scalar_ref);
+        impl_scalar_op_assign!(DivAssign, div_assign, /=, #doc_div_assign);
 
+        #[doc = #doc_neg]
         impl<#ttype> std::ops::Neg for #struct_name<#ttype>
         where
             T: std::ops::Neg<Output = T>,
@@ -292,7 +958,86 @@ pub fn impl_vec(_attr: TokenStream, item: TokenStream) -> TokenStream {
             }
         }
 
+        #[doc = #doc_neg_ref]
+        impl<#ttype> std::ops::Neg for &#struct_name<#ttype>
+        where
+            #ttype: std::ops::Neg<Output = #ttype> + Copy,
+        {
+            type Output = #struct_name<#ttype>;
+
+            fn neg(self) -> Self::Output {
+                return -*self;
+            }
+        }
+
+        // Indexing
+        #[doc = #doc_index]
+        impl<#ttype> std::ops::Index<usize> for #struct_name<#ttype> {
+            type Output = #ttype;
+
+            fn index(&self, index: usize) -> &Self::Output {
+                return match index {
+                    #(#field_indexes => &self.#field_names,)*
+                    _ => panic!(
+                        "index out of range: {} has {} components but the index is {}",
+                        stringify!(#struct_name),
+                        #num_of_fields,
+                        index
+                    ),
+                };
+            }
+        }
+
+        #[doc = #doc_index_mut]
+        impl<#ttype> std::ops::IndexMut<usize> for #struct_name<#ttype> {
+            fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+                return match index {
+                    #(#field_indexes => &mut self.#field_names,)*
+                    _ => panic!(
+                        "index out of range: {} has {} components but the index is {}",
+                        stringify!(#struct_name),
+                        #num_of_fields,
+                        index
+                    ),
+                };
+            }
+        }
+
+        // Iterators
+        impl<#ttype> #struct_name<#ttype> {
+            #[doc = #doc_iter]
+            pub fn iter(&self) -> std::array::IntoIter<&#ttype, #num_of_fields> {
+                return [#(&self.#field_names),*].into_iter();
+            }
+
+            #[doc = #doc_iter_mut]
+            pub fn iter_mut(&mut self) -> std::array::IntoIter<&mut #ttype, #num_of_fields> {
+                return [#(&mut self.#field_names),*].into_iter();
+            }
+        }
+
+        #[doc = #doc_into_iter]
+        impl<#ttype> IntoIterator for #struct_name<#ttype> {
+            type Item = #ttype;
+            type IntoIter = std::array::IntoIter<#ttype, #num_of_fields>;
+
+            fn into_iter(self) -> Self::IntoIter {
+                return [#(self.#field_names),*].into_iter();
+            }
+        }
+
+        #[doc = #doc_into_iter]
+        impl<'a, #ttype> IntoIterator for &'a #struct_name<#ttype> {
+            type Item = &'a #ttype;
+            type IntoIter = std::array::IntoIter<&'a #ttype, #num_of_fields>;
+
+            fn into_iter(self) -> Self::IntoIter {
+                return self.iter();
+            }
+        }
+
         // From implementations
+        #[doc = #doc_from_iter]
         impl<#ttype> FromIterator<#ttype> for #struct_name<#ttype>
         where
             #ttype: Default,
@@ -320,6 +1065,7 @@ pub fn impl_vec(_attr: TokenStream, item: TokenStream) -> TokenStream {
         //     }
         // }
 
+        #[doc = #doc_from_array]
         impl<#ttype> From<[#ttype; #num_of_fields]> for #struct_name<#ttype>
         where
             #ttype: Copy,
@@ -331,6 +1077,7 @@ pub fn impl_vec(_attr: TokenStream, item: TokenStream) -> TokenStream {
             }
         }
 
+        #[doc = #doc_into_array]
         impl<#ttype> From<#struct_name<#ttype>> for [#ttype; #num_of_fields]
         where
             #ttype: Copy,
@@ -342,6 +1089,7 @@ pub fn impl_vec(_attr: TokenStream, item: TokenStream) -> TokenStream {
             }
         }
 
+        #[doc = #doc_from_tuple]
         impl<#ttype> From<( #(#field_types),* )> for #struct_name<#ttype>
         where
             #ttype: Copy,
@@ -353,6 +1101,7 @@ pub fn impl_vec(_attr: TokenStream, item: TokenStream) -> TokenStream {
             }
         }
 
+        #[doc = #doc_into_tuple]
         impl<#ttype> From<#struct_name<#ttype>> for ( #(#field_types),* )
         where
             #ttype: Copy,
@@ -363,6 +1112,271 @@ pub fn impl_vec(_attr: TokenStream, item: TokenStream) -> TokenStream {
                 );
             }
         }
+
+        #(#const_impls)*
+    };
+
+    TokenStream::from(a)
+}
+
+//-----------------------------------------------------------------------------
+/// Arguments of [impl_vec_tests]: `Vec3, f64`
+struct TestsArgs {
+    struct_name: syn::Ident,
+    ttype: syn::Ident,
+}
+
+impl syn::parse::Parse for TestsArgs {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let struct_name = input.parse()?;
+        input.parse::<syn::Token![,]>()?;
+        let ttype = input.parse()?;
+        return Ok(TestsArgs { struct_name, ttype });
+    }
+}
+
+/// Standard battery of tests for a vector created with [macro@impl_vec] and a primitive component
+/// type: `impl_vec_tests!(Vec3, f64)` creates the test module `vec3_f64_tests` next to the
+/// invocation. The vector should be in scope.
+///
+/// - Operator identities ( `a + b - b == a`, `a * 2 / 2 == a`, etc... )
+/// - `From` / `Into` round trips
+/// - Dot product and len relations
+/// - Component wise `min` / `max` / `clamp`
+/// - Normalization, projections and reflections for the float types
+#[proc_macro]
+pub fn impl_vec_tests(item: TokenStream) -> TokenStream {
+    let TestsArgs { struct_name, ttype } = syn::parse_macro_input!(item as TestsArgs);
+
+    let type_name = ttype.to_string();
+    let is_float = type_name.starts_with('f');
+    let is_signed = is_float || type_name.starts_with('i');
+
+    let mod_name = quote::format_ident!(
+        "{}_{}_tests",
+        struct_name.to_string().to_lowercase(),
+        type_name
+    );
+
+    let neg_test = if is_signed {
+        quote! {
+            assert_eq!(-(-a), a);
+            assert_eq!(-&a, -a);
+            assert_eq!((-a).abs(), a);
+            assert_eq!(V::min(-a, a), -a);
+            assert_eq!(a + (-a), V::splat(0 as #ttype));
+            assert_eq!(a - b, a + (-b));
+        }
+    } else {
+        quote! {}
+    };
+
+    let float_test = if is_float {
+        quote! {
+            #[test]
+            fn test_normalize() {
+                let (a, b) = samples();
+                let eps = 1.0e-5;
+
+                assert!((a.len() * a.len() - a.len2()).abs() < eps * a.len2());
+                assert!((a.normalized().len() - 1.0).abs() < eps);
+                assert!((a.normalized() * a.len() - a).len() < eps * a.len());
+                assert!((a.distance(&b) - (b - a).len()).abs() < eps);
+
+                let mut n = a;
+                n.normalize();
+                assert_eq!(n, a.normalized());
+
+                assert_eq!(a.move_towards(&b, 0.0), a);
+                assert_eq!(a.move_towards(&b, a.distance(&b) * 2.0), b);
+                assert!((a.move_towards(&b, 1.0).distance(&a) - 1.0).abs() < eps);
+
+                // Projections of parallel vectors
+                let zero = V::splat(0.0);
+                assert!((a.project_onto(&b) - a).len() < eps * a.len());
+                assert!(a.reject_from(&b).len() < eps * a.len());
+                assert_eq!(a.project_onto(&zero), zero);
+                assert_eq!(a.reject_from(&zero), a);
+            }
+
+            #[test]
+            fn test_projections() {
+                let (a, b) = mixed_samples();
+                let eps = 1.0e-5;
+
+                let project = a.project_onto(&b);
+                let reject = a.reject_from(&b);
+                assert!(reject.len() > 1.0);
+                assert!((project + reject - a).len() < eps * a.len());
+                assert!(V::dot(&reject, &b).abs() < eps * a.len() * b.len());
+                assert!(V::dot(&project, &reject).abs() < eps * a.len2());
+
+                // Only the component along the normal flips
+                let normal = b.normalized();
+                let reflected = a.reflect(&normal);
+                assert!((reflected.reflect(&normal) - a).len() < eps * a.len());
+                assert!((reflected.len() - a.len()).abs() < eps * a.len());
+                assert!((V::dot(&reflected, &normal) + V::dot(&a, &normal)).abs() < eps * a.len());
+                assert!((reflected.reject_from(&normal) - reject).len() < eps * a.len());
+                assert!((reflected - a).len() > 1.0);
+            }
+        }
+    } else {
+        quote! {}
+    };
+
+    let a = quote! {
+        #[cfg(test)]
+        mod #mod_name {
+            use super::*;
+
+            type V = #struct_name<#ttype>;
+
+            /// `a` = ( 1, 2, 3, ... ) and `b` = ( 3, 6, 9, ... ) ( `b` is bigger, so `b - a`
+            /// works for the unsigned types )
+            fn samples() -> (V, V) {
+                let a = (1..).map(|i| i as #ttype).collect::<V>();
+                let b = (1..).map(|i| (3 * i) as #ttype).collect::<V>();
+                return (a, b);
+            }
+
+            /// `a` = ( 1, 5, 1, 5, ... ) and `b` = ( 3, 2, 3, 2, ... ), not parallel and neither
+            /// is bigger in every component
+            fn mixed_samples() -> (V, V) {
+                let a = (0..).map(|i| (if i % 2 == 0 { 1 } else { 5 }) as #ttype).collect::<V>();
+                let b = (0..).map(|i| (if i % 2 == 0 { 3 } else { 2 }) as #ttype).collect::<V>();
+                return (a, b);
+            }
+
+            #[test]
+            fn test_ops() {
+                let (a, b) = samples();
+                let zero = V::splat(0 as #ttype);
+                let two = 2 as #ttype;
+
+                assert_eq!(a + zero, a);
+                assert_eq!(a + b, b + a);
+                assert_eq!(b - a + a, b);
+                assert_eq!(a - a, zero);
+                assert_eq!(a + a, a * two);
+                assert_eq!(a * two / two, a);
+                assert_eq!(a * (1 as #ttype), a);
+                assert_eq!(V::mul(&a, &V::splat(two)), a * two);
+                assert_eq!(V::div(&(a * two), &a), V::splat(two));
+
+                let mut c = a;
+                c += b;
+                assert_eq!(c, a + b);
+                c -= b;
+                assert_eq!(c, a);
+                c *= two;
+                assert_eq!(c, a * two);
+                c /= two;
+                assert_eq!(c, a);
+
+                // Reference operands
+                assert_eq!(&a + &b, a + b);
+                assert_eq!(a + &b, a + b);
+                assert_eq!(&b - a, b - a);
+                assert_eq!(&a * two, a * two);
+                assert_eq!(&(a * two) / two, a);
+                c += &b;
+                c -= &a;
+                assert_eq!(c, b);
+
+                #neg_test
+            }
+
+            #[test]
+            fn test_conversions() {
+                let (a, _) = samples();
+
+                let array: [#ttype; _] = a.into();
+                assert_eq!(V::from(array), a);
+                assert_eq!(array.iter().copied().collect::<V>(), a);
+                assert_eq!(a.map(|c| c), a);
+                assert_eq!(crate::Convert::<V>::convert(&a), a);
+
+                // Missing components are zero
+                let first = std::iter::once(1 as #ttype).collect::<V>();
+                assert_eq!(first.len2(), 1 as #ttype);
+            }
+
+            #[test]
+            fn test_min_max() {
+                let (a, b) = samples();
+                let one = 1 as #ttype;
+                let two = 2 as #ttype;
+
+                assert_eq!(V::min(a, b), a);
+                assert_eq!(V::max(a, b), b);
+                assert_eq!(b.min(a), a.min(b));
+                assert_eq!(a.clamp(a, b), a);
+                assert_eq!(b.clamp(a, b), b);
+                assert_eq!((b * two).clamp(a, b), b);
+
+                let clamped = b.clamp_scalar(one, two);
+                assert!(clamped.len2() >= V::splat(one).len2());
+                assert!(clamped.len2() <= V::splat(two).len2());
+                assert_eq!(V::splat(one).clamp_scalar(two, two), V::splat(two));
+
+                // Each component on its own
+                let (a, b) = mixed_samples();
+                let min = (0..).map(|i| (if i % 2 == 0 { 1 } else { 2 }) as #ttype).collect::<V>();
+                let max = (0..).map(|i| (if i % 2 == 0 { 3 } else { 5 }) as #ttype).collect::<V>();
+
+                assert_eq!(V::min(a, b), min);
+                assert_eq!(V::max(a, b), max);
+                assert_eq!(b.min(a), min);
+                assert_eq!(a.clamp(min, V::splat(two)), V::min(a, V::splat(two)).max(min));
+                assert_eq!(V::splat(two * two).clamp(min, max), V::splat(two * two).min(max));
+            }
+
+            #[test]
+            fn test_index() {
+                let (a, _) = samples();
+                let array: [#ttype; _] = a.into();
+
+                // Same order as the arrays
+                for (i, c) in a.iter().enumerate() {
+                    assert_eq!(a[i], array[i]);
+                    assert_eq!(*c, array[i]);
+                }
+                assert_eq!(a.into_iter().collect::<Vec<_>>(), array);
+                assert_eq!((&a).into_iter().count(), array.len());
+
+                let mut c = a;
+                for i in 0..array.len() {
+                    c[i] = array[i] * (2 as #ttype);
+                }
+                assert_eq!(c, a * (2 as #ttype));
+
+                c.iter_mut().for_each(|c| *c = 1 as #ttype);
+                assert_eq!(c, V::splat(1 as #ttype));
+            }
+
+            #[test]
+            #[should_panic]
+            fn test_index_out_of_range() {
+                let (a, _) = samples();
+                let array: [#ttype; _] = a.into();
+                let _ = a[array.len()];
+            }
+
+            #[test]
+            fn test_dot_len() {
+                let (a, b) = samples();
+
+                assert_eq!(V::dot(&a, &a), a.len2());
+                assert_eq!(V::dot(&a, &b), V::dot(&b, &a));
+                assert_eq!(V::dot(&a, &(a * (3 as #ttype))), a.len2() * (3 as #ttype));
+                assert_eq!(b.distance2(&a), (b - a).len2());
+                assert_eq!(V::midpoint(&a, &a), a);
+                assert_eq!(V::splat(0 as #ttype).len2(), 0 as #ttype);
+            }
+
+            #float_test
+        }
     };
 
     TokenStream::from(a)
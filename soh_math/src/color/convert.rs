@@ -1,5 +1,5 @@
 //-----------------------------------------------------------------------------
-use super::{Hsluv, Hsv, Rgb};
+use super::{Hsluv, Hsv, Lab, Rgb};
 use std::cmp::{max, min};
 //-----------------------------------------------------------------------------
 // `hex_to` functions:
@@ -63,6 +63,30 @@ pub fn rgb_to_hsluv(rgb: &Rgb) -> Hsluv {
     return Hsluv::new(h, s, l);
 }
 
+pub fn rgb_to_lab(rgb: &Rgb) -> Lab {
+    // XYZ of the sRGB white, so that white has no chroma
+    const WHITE: (f64, f64, f64) = (0.950_455_927, 1.0, 1.089_057_751);
+    // CIE constants ( 6/29 )^3 and ( 29/6 )^2 / 3
+    const EPSILON: f64 = 216.0 / 24389.0;
+    const SLOPE: f64 = 841.0 / 108.0;
+
+    let f = |t: f64| {
+        if t > EPSILON {
+            return t.cbrt();
+        }
+        return SLOPE * t + 4.0 / 29.0;
+    };
+
+    let r = rgb.r as f64 / 255.0;
+    let g = rgb.g as f64 / 255.0;
+    let b = rgb.b as f64 / 255.0;
+
+    let (x, y, z) = hsluv::rgb_to_xyz((r, g, b));
+    let (fx, fy, fz) = (f(x / WHITE.0), f(y / WHITE.1), f(z / WHITE.2));
+
+    return Lab::new(116.0 * fy - 16.0, 500.0 * (fx - fy), 200.0 * (fy - fz));
+}
+
 //-----------------------------------------------------------------------------
 // `hsv_to` functions:
 fn hsv_to_rgb_float(hsv: &Hsv) -> (f64, f64, f64) {
@@ -0,0 +1,148 @@
+//-----------------------------------------------------------------------------
+// Perceptual color differences ( deltaE ), a difference around 1.0 is just noticeable
+//-----------------------------------------------------------------------------
+use super::Lab;
+use crate::real::Real;
+//-----------------------------------------------------------------------------
+
+/// CIE76: euclidean distance in L*a*b*
+pub fn delta_e_76(lab1: &Lab, lab2: &Lab) -> f64 {
+    let (dl, da, db) = (lab1.l - lab2.l, lab1.a - lab2.a, lab1.b - lab2.b);
+    return (dl * dl + da * da + db * db).sqrt();
+}
+
+/// CIEDE2000: corrects CIE76 for the perceptual non-uniformity of L*a*b* ( mostly in the blues
+/// and for low chroma )
+///
+/// source:
+/// Gaurav Sharma, Wencheng Wu, Edul N. Dalal, "The CIEDE2000 Color-Difference Formula:
+/// Implementation Notes, Supplementary Test Data, and Mathematical Observations"
+pub fn delta_e_2000(lab1: &Lab, lab2: &Lab) -> f64 {
+    // Multiplied out, `powi` isn't bit-exact across platforms
+    let pow7 = |x: f64| {
+        let x2 = x * x;
+        return x2 * x2 * x2 * x;
+    };
+    let pow25_7 = pow7(25.0);
+
+    /*
+     * Stretch the a* axis for the low chroma colors
+     */
+    let c_mean = (Real::hypot(lab1.a, lab1.b) + Real::hypot(lab2.a, lab2.b)) * 0.5;
+    let g = 0.5 * (1.0 - (pow7(c_mean) / (pow7(c_mean) + pow25_7)).sqrt());
+
+    let a1 = lab1.a * (1.0 + g);
+    let a2 = lab2.a * (1.0 + g);
+
+    let c1 = Real::hypot(a1, lab1.b);
+    let c2 = Real::hypot(a2, lab2.b);
+
+    let hue = |a: f64, b: f64| {
+        if a == 0.0 && b == 0.0 {
+            return 0.0;
+        }
+        return Real::atan2(b, a).to_degrees().rem_euclid(360.0);
+    };
+    let h1 = hue(a1, lab1.b);
+    let h2 = hue(a2, lab2.b);
+
+    /*
+     * Differences in lightness, chroma and hue
+     */
+    let dl = lab2.l - lab1.l;
+    let dc = c2 - c1;
+
+    let dh = if c1 * c2 == 0.0 {
+        0.0
+    } else if (h2 - h1).abs() <= 180.0 {
+        h2 - h1
+    } else if h2 - h1 > 180.0 {
+        h2 - h1 - 360.0
+    } else {
+        h2 - h1 + 360.0
+    };
+    let dh_big = 2.0 * (c1 * c2).sqrt() * Real::sin(dh.to_radians() * 0.5);
+
+    /*
+     * Means
+     */
+    let l_mean = (lab1.l + lab2.l) * 0.5;
+    let c_mean = (c1 + c2) * 0.5;
+
+    let h_mean = if c1 * c2 == 0.0 {
+        h1 + h2
+    } else if (h1 - h2).abs() <= 180.0 {
+        (h1 + h2) * 0.5
+    } else if h1 + h2 < 360.0 {
+        (h1 + h2 + 360.0) * 0.5
+    } else {
+        (h1 + h2 - 360.0) * 0.5
+    };
+
+    /*
+     * Weighting functions
+     */
+    let cos_deg = |deg: f64| Real::cos(deg.to_radians());
+    let t = 1.0 - 0.17 * cos_deg(h_mean - 30.0)
+        + 0.24 * cos_deg(2.0 * h_mean)
+        + 0.32 * cos_deg(3.0 * h_mean + 6.0)
+        - 0.20 * cos_deg(4.0 * h_mean - 63.0);
+
+    let l50 = (l_mean - 50.0) * (l_mean - 50.0);
+    let sl = 1.0 + 0.015 * l50 / (20.0 + l50).sqrt();
+    let sc = 1.0 + 0.045 * c_mean;
+    let sh = 1.0 + 0.015 * c_mean * t;
+
+    let h_275 = (h_mean - 275.0) / 25.0;
+    let d_theta = 30.0 * Real::exp(-h_275 * h_275);
+    let rc = 2.0 * (pow7(c_mean) / (pow7(c_mean) + pow25_7)).sqrt();
+    let rt = -rc * Real::sin((2.0 * d_theta).to_radians());
+
+    let (l, c, h) = (dl / sl, dc / sc, dh_big / sh);
+
+    return (l * l + c * c + h * h + rt * c * h).sqrt();
+}
+
+//-----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_delta_e() {
+        let a = Lab::new(50.0, 0.0, 0.0);
+        let b = Lab::new(53.0, 4.0, 0.0);
+
+        assert_eq!(delta_e_76(&a, &b), 5.0);
+        assert_eq!(delta_e_76(&a, &a), 0.0);
+        assert_eq!(delta_e_2000(&a, &a), 0.0);
+
+        // Test data of Sharma et al.
+        let pairs = [
+            ((50.0, 2.6772, -79.7751), (50.0, 0.0, -82.7485), 2.0425),
+            ((50.0, 0.0, 0.0), (50.0, -1.0, 2.0), 2.3669),
+            ((50.0, 2.5, 0.0), (73.0, 25.0, -18.0), 27.1492),
+            (
+                (60.2574, -34.0099, 36.2677),
+                (60.4626, -34.1751, 39.4387),
+                1.2644,
+            ),
+            (
+                (22.7233, 20.0904, -46.694),
+                (23.0331, 14.973, -42.5619),
+                2.0373,
+            ),
+        ];
+
+        for ((l1, a1, b1), (l2, a2, b2), expected) in pairs {
+            let lab1 = Lab::new(l1, a1, b1);
+            let lab2 = Lab::new(l2, a2, b2);
+
+            assert!((delta_e_2000(&lab1, &lab2) - expected).abs() < 1.0e-4);
+            assert!((delta_e_2000(&lab2, &lab1) - expected).abs() < 1.0e-4);
+        }
+    }
+}
+
+//-----------------------------------------------------------------------------
@@ -0,0 +1,90 @@
+//-----------------------------------------------------------------------------
+use super::lerp::lerp_rgb;
+use super::Rgb;
+//-----------------------------------------------------------------------------
+
+/// Color map made of color stops at positions in [0, 1]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct Gradient {
+    /// Sorted by the position
+    stops: Vec<(f64, Rgb)>,
+}
+
+//-----------------------------------------------------------------------------
+
+impl Gradient {
+    /// Create a gradient from `(position, color)` stops (positions are clamped to [0, 1])
+    ///
+    /// Panics if there are no stops
+    pub fn new(stops: impl IntoIterator<Item = (f64, Rgb)>) -> Self {
+        let mut stops: Vec<(f64, Rgb)> = stops
+            .into_iter()
+            .map(|(pos, color)| (pos.clamp(0.0, 1.0), color))
+            .collect();
+        assert!(!stops.is_empty(), "Gradient needs at least one color stop");
+
+        stops.sort_by(|a, b| a.0.total_cmp(&b.0));
+        return Gradient { stops };
+    }
+
+    /// Colors spread evenly over [0, 1]
+    pub fn even(colors: &[Rgb]) -> Self {
+        let last = colors.len().saturating_sub(1).max(1) as f64;
+        return Gradient::new(
+            colors
+                .iter()
+                .enumerate()
+                .map(|(i, color)| (i as f64 / last, *color)),
+        );
+    }
+
+    /// Black to white
+    pub fn grayscale() -> Self {
+        return Gradient::even(&[super::BLACK, super::WHITE]);
+    }
+
+    /// Black through red and yellow to white
+    pub fn heat() -> Self {
+        return Gradient::even(&[
+            Rgb::new(0, 0, 0),
+            Rgb::new(180, 20, 20),
+            Rgb::new(250, 160, 0),
+            Rgb::new(255, 255, 255),
+        ]);
+    }
+
+    /// Perceptually uniform blue-green-yellow map (approximation of viridis)
+    pub fn viridis() -> Self {
+        return Gradient::even(&[
+            Rgb::new(68, 1, 84),
+            Rgb::new(59, 82, 139),
+            Rgb::new(33, 145, 140),
+            Rgb::new(94, 201, 98),
+            Rgb::new(253, 231, 37),
+        ]);
+    }
+
+    pub fn stops(&self) -> &[(f64, Rgb)] {
+        return &self.stops;
+    }
+
+    /// Color at the position `t` (clamped to [0, 1])
+    pub fn sample(&self, t: f64) -> Rgb {
+        let t = t.clamp(0.0, 1.0);
+
+        let next = self.stops.partition_point(|(pos, _)| *pos <= t);
+        if next == 0 {
+            return self.stops[0].1;
+        }
+        if next == self.stops.len() {
+            return self.stops[next - 1].1;
+        }
+
+        let (pos0, color0) = self.stops[next - 1];
+        let (pos1, color1) = self.stops[next];
+        return lerp_rgb(&color0, &color1, (t - pos0) / (pos1 - pos0));
+    }
+}
+
+//-----------------------------------------------------------------------------
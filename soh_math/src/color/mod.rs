@@ -1,6 +1,12 @@
 //-----------------------------------------------------------------------------
 pub mod convert;
+pub mod difference;
+pub mod gradient;
 pub mod lerp;
+pub mod palette;
+//-----------------------------------------------------------------------------
+pub use gradient::Gradient;
+pub use palette::Palette;
 //-----------------------------------------------------------------------------
 
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -27,6 +33,15 @@ pub struct Hsluv {
     pub v: f64,
 }
 
+/// CIE L*a*b* ( D65 white point ), `l` is in [0, 100]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Copy, Clone, PartialEq, PartialOrd)]
+pub struct Lab {
+    pub l: f64,
+    pub a: f64,
+    pub b: f64,
+}
+
 //-----------------------------------------------------------------------------
 
 impl Rgb {
@@ -77,6 +92,24 @@ impl Default for Hsluv {
     }
 }
 
+//-----------------------------------------------------------------------------
+
+impl Lab {
+    pub const fn new(l: f64, a: f64, b: f64) -> Self {
+        return Lab { l, a, b };
+    }
+}
+
+impl Default for Lab {
+    fn default() -> Self {
+        return Lab {
+            l: 0.0,
+            a: 0.0,
+            b: 0.0,
+        };
+    }
+}
+
 //-----------------------------------------------------------------------------
 // Utility colors:
 pub const BLACK: Rgb = Rgb::new(0, 0, 0);
@@ -207,6 +240,36 @@ mod test {
                 assert!(eps_cmp(hsv_1.v, hsv.v, 1e-3));
             });
     }
+
+    #[test]
+    fn check_lab() {
+        let white = convert::rgb_to_lab(&WHITE);
+        assert!(eps_cmp(white.l, 100.0, 1e-6));
+        assert!(eps_cmp(white.a, 0.0, 1e-6));
+        assert!(eps_cmp(white.b, 0.0, 1e-6));
+
+        let red = convert::rgb_to_lab(&Rgb::new(255, 0, 0));
+        assert!(eps_cmp(red.l, 53.24, 1e-2));
+        assert!(eps_cmp(red.a, 80.09, 1e-2));
+        assert!(eps_cmp(red.b, 67.20, 1e-2));
+
+        assert_eq!(convert::rgb_to_lab(&BLACK), Lab::default());
+    }
+
+    #[test]
+    fn check_gradient() {
+        let gradient = Gradient::new([(1.0, WHITE), (0.0, BLACK), (0.5, RED)]);
+        assert_eq!(gradient.stops()[1], (0.5, RED));
+
+        assert_eq!(gradient.sample(-1.0), BLACK);
+        assert_eq!(gradient.sample(0.5), RED);
+        assert_eq!(gradient.sample(2.0), WHITE);
+        assert_eq!(gradient.sample(0.25), Rgb::new(119, 14, 18));
+
+        let single = Gradient::even(&[GREEN]);
+        assert_eq!(single.sample(0.3), GREEN);
+        assert_eq!(Gradient::grayscale().sample(0.5), Rgb::new(128, 128, 128));
+    }
 }
 
 //-----------------------------------------------------------------------------
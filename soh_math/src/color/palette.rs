@@ -0,0 +1,161 @@
+//-----------------------------------------------------------------------------
+use super::convert::rgb_to_lab;
+use super::{Lab, Rgb};
+//-----------------------------------------------------------------------------
+
+/// Fixed set of colors with fast nearest color lookups ( dithering, palette quantized
+/// rendering )
+///
+/// Colors are compared by CIE76 ( see [super::difference::delta_e_76] ), the palette keeps them
+/// in a k-d tree over L*a*b*
+#[derive(Debug, Clone, PartialEq)]
+pub struct Palette {
+    colors: Vec<Rgb>,
+    /// Balanced k-d tree: the median of a slice is its root, the halves are the subtrees
+    tree: Vec<Node>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Node {
+    lab: [f64; 3],
+    idx: usize,
+}
+
+//-----------------------------------------------------------------------------
+
+impl Palette {
+    /// Panics if there are no colors
+    pub fn new(colors: impl IntoIterator<Item = Rgb>) -> Self {
+        let colors: Vec<Rgb> = colors.into_iter().collect();
+        assert!(!colors.is_empty(), "Palette needs at least one color");
+
+        let mut tree = colors
+            .iter()
+            .enumerate()
+            .map(|(idx, color)| {
+                let Lab { l, a, b } = rgb_to_lab(color);
+                return Node {
+                    lab: [l, a, b],
+                    idx,
+                };
+            })
+            .collect::<Vec<_>>();
+
+        build(&mut tree, 0);
+
+        return Palette { colors, tree };
+    }
+
+    pub fn colors(&self) -> &[Rgb] {
+        return &self.colors;
+    }
+
+    /// Index of the palette color closest to `color`
+    pub fn nearest_index(&self, color: &Rgb) -> usize {
+        let Lab { l, a, b } = rgb_to_lab(color);
+
+        let mut best = (0, f64::INFINITY);
+        search(&self.tree, 0, &[l, a, b], &mut best);
+
+        return best.0;
+    }
+
+    /// Palette color closest to `color`
+    pub fn nearest(&self, color: &Rgb) -> Rgb {
+        return self.colors[self.nearest_index(color)];
+    }
+}
+
+//-----------------------------------------------------------------------------
+// K-d tree
+
+fn build(nodes: &mut [Node], depth: usize) {
+    if nodes.len() <= 1 {
+        return;
+    }
+
+    let axis = depth % 3;
+    let mid = nodes.len() / 2;
+    nodes.select_nth_unstable_by(mid, |a, b| a.lab[axis].total_cmp(&b.lab[axis]));
+
+    let (left, right) = nodes.split_at_mut(mid);
+    build(left, depth + 1);
+    build(&mut right[1..], depth + 1);
+}
+
+/// Find the node closest to `target`, `best` is the index and the squared distance
+fn search(nodes: &[Node], depth: usize, target: &[f64; 3], best: &mut (usize, f64)) {
+    if nodes.is_empty() {
+        return;
+    }
+
+    let mid = nodes.len() / 2;
+    let node = &nodes[mid];
+
+    let dist2 = (0..3)
+        .map(|i| (node.lab[i] - target[i]).powi(2))
+        .sum::<f64>();
+    if dist2 < best.1 {
+        *best = (node.idx, dist2);
+    }
+
+    /*
+     * Search the half with the target first, the other one only if the splitting plane is
+     * closer than the best match
+     */
+    let axis = depth % 3;
+    let diff = target[axis] - node.lab[axis];
+
+    let (near, far) = if diff < 0.0 {
+        (&nodes[..mid], &nodes[mid + 1..])
+    } else {
+        (&nodes[mid + 1..], &nodes[..mid])
+    };
+
+    search(near, depth + 1, target, best);
+    if diff * diff < best.1 {
+        search(far, depth + 1, target, best);
+    }
+}
+
+//-----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod test {
+    use super::super::difference::delta_e_76;
+    use super::super::{BLACK, RED, WHITE};
+    use super::*;
+    use proptest::prelude::*;
+
+    fn rgb() -> impl Strategy<Value = Rgb> {
+        return any::<[u8; 3]>().prop_map(|[r, g, b]| Rgb::new(r, g, b));
+    }
+
+    #[test]
+    fn test_palette() {
+        let palette = Palette::new([BLACK, WHITE, RED]);
+
+        assert_eq!(palette.nearest(&Rgb::new(20, 10, 10)), BLACK);
+        assert_eq!(palette.nearest(&Rgb::new(200, 210, 220)), WHITE);
+        assert_eq!(palette.nearest(&Rgb::new(250, 0, 0)), RED);
+        assert_eq!(palette.nearest_index(&RED), 2);
+    }
+
+    proptest! {
+        #[test]
+        fn prop_nearest_matches_brute_force(
+            colors in proptest::collection::vec(rgb(), 1..64),
+            color in rgb(),
+        ) {
+            let palette = Palette::new(colors.iter().copied());
+            let lab = rgb_to_lab(&color);
+
+            let distance = |c: &Rgb| delta_e_76(&rgb_to_lab(c), &lab);
+            let expected = colors.iter().map(distance).fold(f64::INFINITY, f64::min);
+
+            prop_assert!((distance(&palette.nearest(&color)) - expected).abs() < 1.0e-9);
+        }
+    }
+}
+
+//-----------------------------------------------------------------------------
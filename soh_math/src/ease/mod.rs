@@ -0,0 +1,243 @@
+//-----------------------------------------------------------------------------
+// Easing functions
+//
+// All functions map the progress `t` in [0, 1] so that `f(0) = 0` and `f(1) = 1` ( `back` and
+// `elastic` overshoot in between )
+//-----------------------------------------------------------------------------
+use crate::real::Real;
+use num_traits::{Float, FloatConst};
+//-----------------------------------------------------------------------------
+
+/// Easing function selectable at runtime
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Ease {
+    #[default]
+    Linear,
+    QuadIn,
+    QuadOut,
+    QuadInOut,
+    CubicIn,
+    CubicOut,
+    CubicInOut,
+    SineIn,
+    SineOut,
+    SineInOut,
+    ExpoIn,
+    ExpoOut,
+    ExpoInOut,
+    BackIn,
+    BackOut,
+    ElasticOut,
+    BounceOut,
+}
+
+//-----------------------------------------------------------------------------
+
+impl Ease {
+    /// Apply the easing to `t` (clamped to [0, 1])
+    pub fn apply<T>(self, t: T) -> T
+    where
+        T: Float + FloatConst + Real,
+    {
+        let t = t.max(T::zero()).min(T::one());
+
+        return match self {
+            Ease::Linear => t,
+            Ease::QuadIn => quad_in(t),
+            Ease::QuadOut => quad_out(t),
+            Ease::QuadInOut => quad_in_out(t),
+            Ease::CubicIn => cubic_in(t),
+            Ease::CubicOut => cubic_out(t),
+            Ease::CubicInOut => cubic_in_out(t),
+            Ease::SineIn => sine_in(t),
+            Ease::SineOut => sine_out(t),
+            Ease::SineInOut => sine_in_out(t),
+            Ease::ExpoIn => expo_in(t),
+            Ease::ExpoOut => expo_out(t),
+            Ease::ExpoInOut => expo_in_out(t),
+            Ease::BackIn => back_in(t),
+            Ease::BackOut => back_out(t),
+            Ease::ElasticOut => elastic_out(t),
+            Ease::BounceOut => bounce_out(t),
+        };
+    }
+}
+
+//-----------------------------------------------------------------------------
+
+fn c<T: Float>(value: f64) -> T {
+    return T::from(value).unwrap();
+}
+
+/// Turn an "in" function into an "out" function
+fn reverse<T: Float>(t: T, f: impl Fn(T) -> T) -> T {
+    return T::one() - f(T::one() - t);
+}
+
+/// First half "in", second half "out"
+fn in_out<T: Float>(t: T, f: impl Fn(T) -> T) -> T {
+    let half = c::<T>(0.5);
+
+    if t < half {
+        return f(t * c(2.0)) * half;
+    } else {
+        return T::one() - f((T::one() - t) * c(2.0)) * half;
+    }
+}
+
+//-----------------------------------------------------------------------------
+
+pub fn quad_in<T: Float>(t: T) -> T {
+    return t * t;
+}
+
+pub fn quad_out<T: Float>(t: T) -> T {
+    return reverse(t, quad_in);
+}
+
+pub fn quad_in_out<T: Float>(t: T) -> T {
+    return in_out(t, quad_in);
+}
+
+pub fn cubic_in<T: Float>(t: T) -> T {
+    return t * t * t;
+}
+
+pub fn cubic_out<T: Float>(t: T) -> T {
+    return reverse(t, cubic_in);
+}
+
+pub fn cubic_in_out<T: Float>(t: T) -> T {
+    return in_out(t, cubic_in);
+}
+
+pub fn sine_in<T: Float + FloatConst + Real>(t: T) -> T {
+    return T::one() - Real::cos(t * T::FRAC_PI_2());
+}
+
+pub fn sine_out<T: Float + FloatConst + Real>(t: T) -> T {
+    return Real::sin(t * T::FRAC_PI_2());
+}
+
+pub fn sine_in_out<T: Float + FloatConst + Real>(t: T) -> T {
+    return (T::one() - Real::cos(t * T::PI())) * c(0.5);
+}
+
+pub fn expo_in<T: Float + Real>(t: T) -> T {
+    if t <= T::zero() {
+        return T::zero();
+    }
+    return Real::powf(c::<T>(2.0), c::<T>(10.0) * (t - T::one()));
+}
+
+pub fn expo_out<T: Float + Real>(t: T) -> T {
+    return reverse(t, expo_in);
+}
+
+pub fn expo_in_out<T: Float + Real>(t: T) -> T {
+    return in_out(t, expo_in);
+}
+
+/// Pulls back before moving forward
+pub fn back_in<T: Float>(t: T) -> T {
+    let s = c::<T>(1.70158);
+    return t * t * ((s + T::one()) * t - s);
+}
+
+/// Overshoots the target before settling
+pub fn back_out<T: Float>(t: T) -> T {
+    return reverse(t, back_in);
+}
+
+/// Springs around the target
+pub fn elastic_out<T: Float + FloatConst + Real>(t: T) -> T {
+    if t <= T::zero() || t >= T::one() {
+        return t.max(T::zero()).min(T::one());
+    }
+
+    let period = c::<T>(2.0) * T::PI() / c(3.0);
+    return Real::powf(c::<T>(2.0), c::<T>(-10.0) * t)
+        * Real::sin((t * c(10.0) - c(0.75)) * period)
+        + T::one();
+}
+
+/// Bounces off the target like a dropped ball
+pub fn bounce_out<T: Float>(t: T) -> T {
+    let (n, d) = (c::<T>(7.5625), c::<T>(2.75));
+
+    if t < T::one() / d {
+        return n * t * t;
+    } else if t < c::<T>(2.0) / d {
+        let t = t - c::<T>(1.5) / d;
+        return n * t * t + c(0.75);
+    } else if t < c::<T>(2.5) / d {
+        let t = t - c::<T>(2.25) / d;
+        return n * t * t + c(0.9375);
+    } else {
+        let t = t - c::<T>(2.625) / d;
+        return n * t * t + c(0.984375);
+    }
+}
+
+//-----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const ALL: [Ease; 17] = [
+        Ease::Linear,
+        Ease::QuadIn,
+        Ease::QuadOut,
+        Ease::QuadInOut,
+        Ease::CubicIn,
+        Ease::CubicOut,
+        Ease::CubicInOut,
+        Ease::SineIn,
+        Ease::SineOut,
+        Ease::SineInOut,
+        Ease::ExpoIn,
+        Ease::ExpoOut,
+        Ease::ExpoInOut,
+        Ease::BackIn,
+        Ease::BackOut,
+        Ease::ElasticOut,
+        Ease::BounceOut,
+    ];
+
+    #[test]
+    fn test_ease_end_points() {
+        for ease in ALL {
+            assert!(ease.apply(0.0f64).abs() < 1e-3, "{ease:?}");
+            assert!((ease.apply(1.0f64) - 1.0).abs() < 1e-3, "{ease:?}");
+
+            // Clamped outside of [0, 1]
+            assert_eq!(ease.apply(-1.0f32), ease.apply(0.0f32), "{ease:?}");
+            assert_eq!(ease.apply(2.0f32), ease.apply(1.0f32), "{ease:?}");
+        }
+    }
+
+    #[test]
+    fn test_ease_shape() {
+        assert_eq!(Ease::Linear.apply(0.25), 0.25);
+        assert_eq!(quad_in(0.5), 0.25);
+        assert_eq!(quad_out(0.5), 0.75);
+        assert_eq!(cubic_in_out(0.5), 0.5);
+        assert!((sine_in_out(0.5f64) - 0.5).abs() < 1e-12);
+
+        // Symmetric in/out functions
+        for t in [0.1, 0.3, 0.45] {
+            assert!((quad_in_out(t) + quad_in_out(1.0 - t) - 1.0f64).abs() < 1e-12);
+            assert!((cubic_out(t) - (1.0 - cubic_in(1.0 - t))).abs() < 1e-12);
+        }
+
+        // Overshooting functions
+        assert!(back_in(0.2f64) < 0.0);
+        assert!(back_out(0.8f64) > 1.0);
+        assert!((0..100).any(|i| elastic_out(i as f64 / 100.0) > 1.0));
+        assert!((0..=100).all(|i| bounce_out(i as f64 / 100.0) <= 1.0 + 1e-12));
+    }
+}
+
+//-----------------------------------------------------------------------------
@@ -1,7 +1,9 @@
 //-----------------------------------------------------------------------------
 // Different complex number based fractals implemented on CPU
 //-----------------------------------------------------------------------------
-use crate::Complex;
+use crate::image::ImageBuffer;
+use crate::real::Real;
+use crate::{Complex, Rect};
 //-----------------------------------------------------------------------------
 /// Sentinel value for when iteration didn't blow up
 pub const QUALIFIED: f64 = -999.99;
@@ -39,6 +41,29 @@ pub trait Fractal {
             return self.iter_to_value(z, pixel_coord, counter, length_bound);
         }
     }
+
+    /// Function that iterates every pixel of an image covering `area` of the complex plane
+    /// ( the top row is at `area.max.y`, pixels are sampled at their centers )
+    fn render(
+        &self,
+        width: usize,
+        height: usize,
+        area: &Rect<f64>,
+        iteration_bound: u64,
+        length_bound: f64,
+    ) -> ImageBuffer<f64> {
+        let pixel_width = area.width() / width as f64;
+        let pixel_height = area.height() / height as f64;
+
+        return ImageBuffer::from_fn(width, height, |x, y| {
+            let pixel_coord = Complex::new(
+                area.min.x + (x as f64 + 0.5) * pixel_width,
+                area.max.y - (y as f64 + 0.5) * pixel_height,
+            );
+
+            return self.iterate(pixel_coord, iteration_bound, length_bound);
+        });
+    }
 }
 
 //-----------------------------------------------------------------------------
@@ -301,7 +326,7 @@ where
 // Helper functions:
 #[inline(always)]
 fn iter_to_value(pow: f64, z: Complex<f64>, counter: u64, length_bound: f64) -> f64 {
-    let ln_sub = (z.len2().ln() / length_bound.ln()).ln() / pow.ln();
+    let ln_sub = Real::ln(Real::ln(z.len2()) / Real::ln(length_bound)) / Real::ln(pow);
     let smooth_n = (counter + 1) as f64 - ln_sub;
     return smooth_n;
 }
@@ -318,7 +343,7 @@ fn iter_to_value_lambda(
     let b = mod_lambda * length_bound;
     let mod_l_z = z.len() * mod_lambda;
 
-    let ln_sub = (mod_l_z.ln() / b.ln()).ln() / pow.ln();
+    let ln_sub = Real::ln(Real::ln(mod_l_z) / Real::ln(b)) / Real::ln(pow);
     let smooth_n = (counter + 1) as f64 - ln_sub;
     return smooth_n;
 }
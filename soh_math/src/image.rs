@@ -0,0 +1,337 @@
+//-----------------------------------------------------------------------------
+//! Pixel buffer used as the output of the fractals, noises and gradients
+//!
+//! Pixels are stored row-major from the top left corner: the pixel (x, y) is at index
+//! `y * width + x`
+//-----------------------------------------------------------------------------
+use crate::color::Rgb;
+//-----------------------------------------------------------------------------
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Default, Clone, Hash, PartialEq, Eq)]
+pub struct ImageBuffer<P> {
+    width: usize,
+    height: usize,
+    pixels: Vec<P>,
+}
+
+//-----------------------------------------------------------------------------
+// Constructors
+impl<P> ImageBuffer<P> {
+    /// Image filled with `pixel`
+    pub fn filled(width: usize, height: usize, pixel: P) -> Self
+    where
+        P: Clone,
+    {
+        return ImageBuffer {
+            width,
+            height,
+            pixels: vec![pixel; width * height],
+        };
+    }
+
+    /// Image with every pixel computed from its coordinate
+    pub fn from_fn(width: usize, height: usize, mut f: impl FnMut(usize, usize) -> P) -> Self {
+        let pixels = (0..width * height)
+            .map(|i| f(i % width, i / width))
+            .collect();
+
+        return ImageBuffer {
+            width,
+            height,
+            pixels,
+        };
+    }
+
+    /// Image made of row-major `pixels`
+    ///
+    /// Panics if the number of pixels doesn't match the size
+    pub fn from_vec(width: usize, height: usize, pixels: Vec<P>) -> Self {
+        assert_eq!(
+            pixels.len(),
+            width * height,
+            "Number of pixels doesn't match the image size"
+        );
+
+        return ImageBuffer {
+            width,
+            height,
+            pixels,
+        };
+    }
+}
+
+impl<P> ImageBuffer<P>
+where
+    P: Clone + Default,
+{
+    /// Image filled with the default pixel
+    pub fn new(width: usize, height: usize) -> Self {
+        return Self::filled(width, height, P::default());
+    }
+}
+
+//-----------------------------------------------------------------------------
+// Getters
+impl<P> ImageBuffer<P> {
+    pub fn width(&self) -> usize {
+        return self.width;
+    }
+
+    pub fn height(&self) -> usize {
+        return self.height;
+    }
+
+    pub fn pixels(&self) -> &[P] {
+        return &self.pixels;
+    }
+
+    pub fn pixels_mut(&mut self) -> &mut [P] {
+        return &mut self.pixels;
+    }
+
+    pub fn into_vec(self) -> Vec<P> {
+        return self.pixels;
+    }
+}
+
+//-----------------------------------------------------------------------------
+// Pixel access
+impl<P> ImageBuffer<P> {
+    fn index(&self, x: usize, y: usize) -> Option<usize> {
+        if x >= self.width || y >= self.height {
+            return None;
+        }
+        return Some(y * self.width + x);
+    }
+
+    /// Pixel at (x, y), `None` if it's outside of the image
+    pub fn get(&self, x: usize, y: usize) -> Option<&P> {
+        let idx = self.index(x, y)?;
+        return Some(&self.pixels[idx]);
+    }
+
+    pub fn get_mut(&mut self, x: usize, y: usize) -> Option<&mut P> {
+        let idx = self.index(x, y)?;
+        return Some(&mut self.pixels[idx]);
+    }
+
+    /// Set the pixel at (x, y), does nothing if it's outside of the image
+    pub fn set(&mut self, x: usize, y: usize, pixel: P) {
+        if let Some(p) = self.get_mut(x, y) {
+            *p = pixel;
+        }
+    }
+
+    /// Rows from the top
+    pub fn rows(&self) -> impl ExactSizeIterator<Item = &[P]> {
+        // `chunks_exact` panics on zero
+        return self
+            .pixels
+            .chunks_exact(self.width.max(1))
+            .take(self.height);
+    }
+
+    pub fn rows_mut(&mut self) -> impl ExactSizeIterator<Item = &mut [P]> {
+        let height = self.height;
+        return self.pixels.chunks_exact_mut(self.width.max(1)).take(height);
+    }
+}
+
+//-----------------------------------------------------------------------------
+// Drawing
+impl<P> ImageBuffer<P>
+where
+    P: Clone,
+{
+    pub fn fill(&mut self, pixel: P) {
+        self.pixels.fill(pixel);
+    }
+
+    /// Copy `src` with its top left corner at (x, y), the parts outside of `self` are clipped
+    pub fn blit(&mut self, src: &ImageBuffer<P>, x: isize, y: isize) {
+        /* Clip the source rectangle to the destination */
+        let src_x = x.min(0).unsigned_abs();
+        let src_y = y.min(0).unsigned_abs();
+        let dst_x = x.max(0) as usize;
+        let dst_y = y.max(0) as usize;
+
+        let width = src
+            .width
+            .saturating_sub(src_x)
+            .min(self.width.saturating_sub(dst_x));
+        let height = src
+            .height
+            .saturating_sub(src_y)
+            .min(self.height.saturating_sub(dst_y));
+
+        for row in 0..height {
+            let src_start = (src_y + row) * src.width + src_x;
+            let dst_start = (dst_y + row) * self.width + dst_x;
+
+            self.pixels[dst_start..dst_start + width]
+                .clone_from_slice(&src.pixels[src_start..src_start + width]);
+        }
+    }
+
+    /// Image made of the pixels converted by `f` ( fractal values to colors with a gradient )
+    pub fn map<Q>(&self, f: impl FnMut(&P) -> Q) -> ImageBuffer<Q> {
+        return ImageBuffer {
+            width: self.width,
+            height: self.height,
+            pixels: self.pixels.iter().map(f).collect(),
+        };
+    }
+}
+
+impl<P> std::ops::Index<(usize, usize)> for ImageBuffer<P> {
+    type Output = P;
+
+    /// Panics if (x, y) is outside of the image
+    fn index(&self, (x, y): (usize, usize)) -> &Self::Output {
+        return self.get(x, y).expect("pixel is outside of the image");
+    }
+}
+
+impl<P> std::ops::IndexMut<(usize, usize)> for ImageBuffer<P> {
+    fn index_mut(&mut self, (x, y): (usize, usize)) -> &mut Self::Output {
+        return self.get_mut(x, y).expect("pixel is outside of the image");
+    }
+}
+
+//-----------------------------------------------------------------------------
+// Export
+impl ImageBuffer<Rgb> {
+    /// Write as a binary PPM ( P6 )
+    pub fn write_ppm(&self, mut writer: impl std::io::Write) -> std::io::Result<()> {
+        write!(writer, "P6\n{} {}\n255\n", self.width, self.height)?;
+
+        let data = self
+            .pixels
+            .iter()
+            .flat_map(|p| [p.r, p.g, p.b])
+            .collect::<Vec<_>>();
+
+        return writer.write_all(&data);
+    }
+
+    /// Write as an 8 bit RGB PNG
+    #[cfg(feature = "png")]
+    pub fn write_png(&self, writer: impl std::io::Write) -> std::io::Result<()> {
+        let mut encoder = png::Encoder::new(writer, self.width as u32, self.height as u32);
+        encoder.set_color(png::ColorType::Rgb);
+        encoder.set_depth(png::BitDepth::Eight);
+
+        let data = self
+            .pixels
+            .iter()
+            .flat_map(|p| [p.r, p.g, p.b])
+            .collect::<Vec<_>>();
+
+        let mut writer = encoder.write_header().map_err(std::io::Error::other)?;
+        writer
+            .write_image_data(&data)
+            .map_err(std::io::Error::other)?;
+
+        return writer.finish().map_err(std::io::Error::other);
+    }
+}
+
+//-----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_pixels() {
+        let mut image = ImageBuffer::from_fn(3, 2, |x, y| x + y * 10);
+        assert_eq!(image.pixels(), &[0, 1, 2, 10, 11, 12]);
+        assert_eq!(image[(2, 1)], 12);
+        assert_eq!(image.get(3, 0), None);
+
+        image.set(1, 1, 99);
+        image.set(5, 5, 99);
+        assert_eq!(
+            image.rows().collect::<Vec<_>>(),
+            [&[0, 1, 2], &[10, 99, 12]]
+        );
+
+        for row in image.rows_mut() {
+            row[0] = 7;
+        }
+        image[(2, 0)] = 8;
+        assert_eq!(image.pixels(), &[7, 1, 8, 7, 99, 12]);
+
+        image.fill(0);
+        assert_eq!(image, ImageBuffer::new(3, 2));
+        assert_eq!(ImageBuffer::<u8>::new(0, 0).rows().count(), 0);
+    }
+
+    #[test]
+    fn test_blit() {
+        let src = ImageBuffer::from_fn(2, 2, |x, y| 1 + x + y * 2);
+
+        let mut image = ImageBuffer::filled(3, 3, 0);
+        image.blit(&src, 1, 1);
+        assert_eq!(image.pixels(), &[0, 0, 0, 0, 1, 2, 0, 3, 4]);
+
+        // Clipped on every side
+        let mut image = ImageBuffer::filled(3, 3, 0);
+        image.blit(&src, -1, -1);
+        image.blit(&src, 2, 2);
+        assert_eq!(image.pixels(), &[4, 0, 0, 0, 0, 0, 0, 0, 1]);
+
+        // Fully outside
+        let mut image = ImageBuffer::filled(3, 3, 0);
+        image.blit(&src, 5, -5);
+        assert_eq!(image, ImageBuffer::filled(3, 3, 0));
+    }
+
+    #[test]
+    fn test_fractal_render() {
+        use crate::fractal::{Fractal, Multibrot, QUALIFIED};
+        use crate::{Complex, Rect, Vec2};
+
+        let mandelbrot = Multibrot {
+            start_point: Complex::new(0.0, 0.0),
+            pow: 2u32,
+        };
+        let area = Rect::new(Vec2::new(-2.0, -2.0), Vec2::new(2.0, 2.0));
+
+        let image = mandelbrot.render(4, 4, &area, 100, 4.0);
+        assert_eq!((image.width(), image.height()), (4, 4));
+
+        // (-0.5, 0.5) is in the set, (1.5, 1.5) escapes
+        assert_eq!(image[(1, 1)], QUALIFIED);
+        assert_ne!(image[(3, 0)], QUALIFIED);
+    }
+
+    #[test]
+    fn test_ppm() {
+        let image = ImageBuffer::from_vec(2, 1, vec![Rgb::new(1, 2, 3), Rgb::new(4, 5, 6)]);
+
+        let mut data = Vec::new();
+        image.write_ppm(&mut data).unwrap();
+        assert_eq!(data, b"P6\n2 1\n255\n\x01\x02\x03\x04\x05\x06");
+    }
+
+    #[cfg(feature = "png")]
+    #[test]
+    fn test_png() {
+        let image = ImageBuffer::from_fn(4, 3, |x, y| Rgb::new(x as u8, y as u8, 0));
+
+        let mut data = Vec::new();
+        image.write_png(&mut data).unwrap();
+
+        let decoder = png::Decoder::new(std::io::Cursor::new(data));
+        let mut reader = decoder.read_info().unwrap();
+        let mut buf = vec![0; reader.output_buffer_size().unwrap()];
+        reader.next_frame(&mut buf).unwrap();
+
+        assert_eq!((reader.info().width, reader.info().height), (4, 3));
+        assert_eq!(&buf[3 * 5..3 * 6], &[1, 1, 0]);
+    }
+}
+
+//-----------------------------------------------------------------------------
@@ -1,4 +1,5 @@
 //-----------------------------------------------------------------------------
+use crate::real::Real;
 use crate::traits::{RealConsts, WholeConsts};
 //-----------------------------------------------------------------------------
 
@@ -67,13 +68,13 @@ where
 
 impl<T> Complex<T>
 where
-    T: num_traits::Float,
+    T: num_traits::Float + Real,
 {
     /// Create a complex number from angle with unit length
     pub fn from_angle(angle: T) -> Self {
         return Complex {
-            re: angle.cos(),
-            im: angle.sin(),
+            re: Real::cos(angle),
+            im: Real::sin(angle),
         };
     }
 
@@ -148,25 +149,25 @@ where
 
 impl<T> Complex<T>
 where
-    T: num_traits::Float + WholeConsts + RealConsts + From<f32>,
+    T: num_traits::Float + Real + WholeConsts + RealConsts + From<f32>,
 {
     /// Get the angle of the complex number
     pub fn phi(&self) -> T {
-        return self.im.atan2(self.re);
+        return Real::atan2(self.im, self.re);
     }
 
     /// Calculate the length (absolute value)
     pub fn len(&self) -> T {
-        return T::hypot(self.re, self.im);
+        return Real::hypot(self.re, self.im);
     }
 
     /// Calculate the exponential
     pub fn exp(&self) -> Self {
-        let exp_re = self.re.exp();
+        let exp_re = Real::exp(self.re);
 
         return Complex {
-            re: exp_re * self.im.cos(),
-            im: exp_re * self.im.sin(),
+            re: exp_re * Real::cos(self.im),
+            im: exp_re * Real::sin(self.im),
         };
     }
 
@@ -180,7 +181,7 @@ where
 
     /// Calculate the natural logarithm of the length
     pub fn ln_len(&self) -> T {
-        return self.len2().ln() * T::ONE_HALF;
+        return Real::ln(self.len2()) * T::ONE_HALF;
     }
 
     /// Calculate the float power of the number
@@ -190,7 +191,7 @@ where
         }
 
         let res_ln_len = pow * self.ln_len();
-        return Complex::from_param(res_ln_len.exp(), pow * self.phi());
+        return Complex::from_param(Real::exp(res_ln_len), pow * self.phi());
     }
 
     /// Calculate the complex power
@@ -200,7 +201,7 @@ where
         }
 
         let c = pow * Complex::new(self.ln_len(), self.phi());
-        return Complex::from_param(c.re.exp(), c.im);
+        return Complex::from_param(Real::exp(c.re), c.im);
     }
 
     /// Calculate the inverse
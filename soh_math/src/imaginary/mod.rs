@@ -10,7 +10,7 @@ pub use quat::*;
 mod tests {
     #[cfg(feature = "f128")]
     use f128_num::{f128, f128_inner};
-    use soh_rng::Engine64;
+    use proptest::prelude::*;
 
     use crate::*;
 
@@ -37,16 +37,6 @@ mod tests {
         assert!((c.powf(2.0) - c_squared).len() < 1.0e-10);
         assert!((c.powc(Complex::new(2.0, 0.0)) - c_squared).len() < 1.0e-10);
 
-        // Test exp and ln
-        let mut rng = soh_rng::RNG64::new(0xdeadbeef);
-
-        for _ in 0..100_000 {
-            let c = Complex::from_param(rng.gen_range(0.5, 1.5), rng.gen_to(std::f64::consts::TAU));
-            let c_1 = c.exp().ln();
-
-            assert!((c - c_1).len() < 1.0e-10);
-        }
-
         // Test f128
         #[cfg(feature = "f128")]
         {
@@ -69,74 +59,224 @@ mod tests {
 
     #[test]
     fn test_quat() {
-        // Test that rotation quaternions are unit length
-        let mut rng = soh_rng::RNG64::new(0xdeadbeef);
+        let q1 = Quaternion::new(1.0, Vec3::new(2.0, 3.0, 4.0));
+        let q2 = Quaternion::new(-1.0, Vec3::new(0.5, 0.0, 2.0));
+        assert_eq!(Quaternion::dot(&q1, &q2), 8.0);
+        assert_eq!(Quaternion::dot(&q1, &q1), q1.len2());
 
+        // Fix the drift after many multiplications
+        let step = Quaternion::from_axis_angle(Vec3::new(1.0, 2.0, 3.0), 0.1f32);
+        let mut q = Quaternion::one();
         for _ in 0..100_000 {
-            let axis: Vec3<f64> = Vec3::new(
-                rng.gen_range(-1.0, 1.0),
-                rng.gen_range(-1.0, 1.0),
-                rng.gen_range(-1.0, 1.0),
-            );
+            q *= step;
+        }
+        assert!(!q.is_normalized(1.0e-6));
 
-            let angle = rng.gen_to(std::f64::consts::TAU);
+        q.normalize();
+        assert!(q.is_normalized(1.0e-6));
+    }
+
+    /*
+     * Properties checked on generated numbers
+     */
+    const EPS: f64 = 1.0e-10;
+
+    proptest! {
+        #[test]
+        fn prop_complex_exp_ln(
+            len in 0.5..1.5,
+            angle in strategy::angle(),
+        ) {
+            let c = Complex::from_param(len, angle);
+            prop_assert!((c - c.exp().ln()).len() < EPS);
+        }
+
+        #[test]
+        fn prop_complex_invert(c in strategy::complex(-5.0..5.0)) {
+            prop_assume!(c.len() > 1.0e-3);
+            prop_assert!((c * c.invert() - Complex::new(1.0, 0.0)).len() < EPS);
+        }
 
-            let rotation_quat = Quaternion::from_axis_angle(axis, angle);
+        #[test]
+        fn prop_quat_rotation_unit(q in strategy::rotation_quat()) {
+            prop_assert!((q.len() - 1.0).abs() < EPS);
+        }
 
-            let delta = (rotation_quat.len() - 1.0).abs();
+        #[test]
+        fn prop_quat_simple_rotations(
+            angle in strategy::angle(),
+            point in strategy::vec3(-1.0..1.0),
+        ) {
+            let axes = [
+                (Mat3::yaw(angle), Vec3::new(0.0, 0.0, 1.0)),
+                (Mat3::pitch(angle), Vec3::new(0.0, 1.0, 0.0)),
+                (Mat3::roll(angle), Vec3::new(1.0, 0.0, 0.0)),
+            ];
 
-            if delta > 1.0e-10 {
-                dbg!(delta, rotation_quat.len(), rotation_quat);
+            for (m, axis) in axes {
+                let q = Quaternion::from_axis_angle(axis, angle);
+                prop_assert!((m * point - q.rotate(point)).len() < EPS);
             }
+        }
+
+        #[test]
+        fn prop_quat_yaw_pitch_roll(
+            yaw in strategy::angle(),
+            pitch in strategy::angle(),
+            roll in strategy::angle(),
+        ) {
+            let q = Quaternion::from_yaw_pitch_roll(yaw, pitch, roll);
+            let m = Mat3::yaw_pitch_roll(yaw, pitch, roll);
+            prop_assert!(q.is_normalized(EPS));
+            prop_assert!((Mat3::from_quat(q) - m).norm() < EPS);
 
-            assert!(delta < 1.0e-10);
+            // Same angles as the matrix ( ±pi may come out as each other )
+            let angle_diff = |a: f64, b: f64| {
+                return ((a - b) + std::f64::consts::PI).rem_euclid(std::f64::consts::TAU)
+                    - std::f64::consts::PI;
+            };
+            let (yaw2, pitch2, roll2) = q.get_euler_angles();
+            let (m_yaw, m_pitch, m_roll) = m.get_euler_angles();
+            prop_assert!(angle_diff(yaw2, m_yaw).abs() < 1.0e-6);
+            prop_assert!(angle_diff(pitch2, m_pitch).abs() < 1.0e-6);
+            prop_assert!(angle_diff(roll2, m_roll).abs() < 1.0e-6);
+
+            let back = Quaternion::from_yaw_pitch_roll(yaw2, pitch2, roll2);
+            prop_assert!(back.angle_to(&q) < 1.0e-6);
         }
 
-        // Test simple rotations
-        for _ in 0..100_000 {
-            let eps = 1.0e-10;
-            let angle = rng.gen_to(std::f64::consts::TAU);
-            let point: Vec3<f64> = Vec3::new(
-                rng.gen_range(-1.0, 1.0),
-                rng.gen_range(-1.0, 1.0),
-                rng.gen_range(-1.0, 1.0),
-            );
-
-            // Test yaw
-            let m_yaw = Mat3::yaw(angle);
-            let q_yaw = Quaternion::from_axis_angle(Vec3::new(0.0, 0.0, 1.0), angle);
-            let p_1 = m_yaw * point;
-            let p_2 = q_yaw.rotate(point);
-            assert!((p_1 - p_2).len() < eps);
-
-            // Test pitch
-            let m_pitch = Mat3::pitch(angle);
-            let q_pitch = Quaternion::from_axis_angle(Vec3::new(0.0, 1.0, 0.0), angle);
-            let p_1 = m_pitch * point;
-            let p_2 = q_pitch.rotate(point);
-            assert!((p_1 - p_2).len() < eps);
-
-            // Test roll
-            let m_roll = Mat3::roll(angle);
-            let q_roll = Quaternion::from_axis_angle(Vec3::new(1.0, 0.0, 0.0), angle);
-            let p_1 = m_roll * point;
-            let p_2 = q_roll.rotate(point);
-            assert!((p_1 - p_2).len() < eps);
-        }
-
-        // Test exp and ln
-        for _ in 0..100_000 {
-            let q = Quaternion::new(
-                rng.gen_range(-1.0, 1.0),
-                Vec3::new(
-                    rng.gen_range(-1.0, 1.0),
-                    rng.gen_range(-1.0, 1.0),
-                    rng.gen_range(-1.0, 1.0),
-                ),
-            );
-            let q_1 = q.exp().ln();
-
-            assert!((q - q_1).len() < 1.0e-10);
+        #[test]
+        fn prop_quat_euler_gimbal_lock(
+            yaw in strategy::angle(),
+            roll in strategy::angle(),
+            sign in prop::bool::ANY,
+            offset in -1.0e-9..1.0e-9,
+        ) {
+            let half_pi = std::f64::consts::FRAC_PI_2;
+            let pitch = if sign { half_pi } else { -half_pi } + offset;
+
+            let q = Quaternion::from_yaw_pitch_roll(yaw, pitch, roll);
+            let (yaw2, pitch2, roll2) = q.get_euler_angles();
+            prop_assert!(!yaw2.is_nan() && !pitch2.is_nan() && !roll2.is_nan());
+
+            // Only the sum ( or the difference ) of the yaw and roll is defined
+            let back = Quaternion::from_yaw_pitch_roll(yaw2, pitch2, roll2);
+            prop_assert!(back.angle_to(&q) < 1.0e-3);
+        }
+
+        #[test]
+        fn prop_quat_rotation_round_trip(
+            q in strategy::rotation_quat(),
+            point in strategy::vec3(-1.0..1.0),
+        ) {
+            let back = q.invert().rotate(q.rotate(point));
+            prop_assert!((back - point).len() < EPS);
+        }
+
+        #[test]
+        fn prop_quat_normalize(
+            q in strategy::quat(-5.0..5.0),
+            point in strategy::vec3(-1.0..1.0),
+        ) {
+            prop_assume!(q.len() > 1.0e-3);
+
+            let mut n = q;
+            n.normalize();
+            prop_assert!(n.is_normalized(EPS));
+            prop_assert_eq!(n, q.normalized());
+
+            // Normalized quaternion only rotates
+            prop_assert!((n.rotate(point).len() - point.len()).abs() < EPS);
+        }
+
+        #[test]
+        fn prop_quat_angle_to(
+            axis in strategy::unit_vec3(),
+            a in strategy::angle(),
+            b in strategy::angle(),
+        ) {
+            let qa = Quaternion::from_axis_angle(axis, a);
+            let qb = Quaternion::from_axis_angle(axis, b);
+
+            // Shortest angle between the rotations
+            let delta = (a - b).abs();
+            let expected = delta.min(std::f64::consts::TAU - delta);
+
+            prop_assert!((qa.angle_to(&qb) - expected).abs() < 1.0e-6);
+            prop_assert!((qa.angle_to(&-qb) - expected).abs() < 1.0e-6);
+        }
+
+        #[test]
+        fn prop_quat_exp_ln(q in strategy::quat(-1.0..1.0)) {
+            prop_assume!(q.len() > 1.0e-3);
+            prop_assert!((q - q.exp().ln()).len() < EPS);
+        }
+
+        #[test]
+        fn prop_quat_scaled_axis(
+            axis in strategy::unit_vec3(),
+            angle in 0.0..std::f64::consts::PI,
+        ) {
+            let q = Quaternion::from_axis_angle(axis, angle);
+            let v = axis * angle;
+
+            prop_assert!((Quaternion::from_scaled_axis(v) - q).len() < EPS);
+            prop_assert!((q.to_axis_angle_vec() - v).len() < 1.0e-6);
+            prop_assert!(((-q).to_axis_angle_vec() - v).len() < 1.0e-6);
+
+            // Same as the matrix encoding
+            prop_assert!((Mat3::from_quat(q).to_axis_angle_vec() - v).len() < 1.0e-6);
+        }
+
+        #[test]
+        fn prop_quat_slerp(
+            axis in strategy::unit_vec3(),
+            a in strategy::angle(),
+            b in strategy::angle(),
+            t in 0.0..1.0,
+        ) {
+            let qa = Quaternion::from_axis_angle(axis, a);
+            let qb = Quaternion::from_axis_angle(axis, b);
+
+            let q = Quaternion::slerp(qa, qb, t);
+            prop_assert!(q.is_normalized(1.0e-6));
+
+            // Constant angular speed along the shortest arc
+            prop_assert!((qa.angle_to(&q) - qa.angle_to(&qb) * t).abs() < 1.0e-6);
+            prop_assert!((Quaternion::slerp(qa, qb, 0.0).angle_to(&qa)).abs() < 1.0e-6);
+            prop_assert!((Quaternion::slerp(qa, qb, 1.0).angle_to(&qb)).abs() < 1.0e-6);
+        }
+
+        #[test]
+        fn prop_quat_squad(
+            q0 in strategy::rotation_quat(),
+            q1 in strategy::rotation_quat(),
+            q2 in strategy::rotation_quat(),
+            q3 in strategy::rotation_quat(),
+            t in 0.0..1.0,
+        ) {
+            let s1 = Quaternion::squad_control(q0, q1, q2);
+            let s2 = Quaternion::squad_control(q1, q2, q3);
+
+            prop_assert!(Quaternion::squad(q1, q2, s1, s2, t).is_normalized(1.0e-6));
+            prop_assert!(Quaternion::squad(q1, q2, s1, s2, 0.0).angle_to(&q1) < 1.0e-6);
+            prop_assert!(Quaternion::squad(q1, q2, s1, s2, 1.0).angle_to(&q2) < 1.0e-6);
+        }
+
+        #[test]
+        fn prop_quat_squad_single_axis(
+            axis in strategy::unit_vec3(),
+            start in -1.0..1.0,
+            step in -1.0..1.0,
+            t in 0.0..1.0,
+        ) {
+            let q = |i: f64| Quaternion::from_axis_angle(axis, start + step * i);
+
+            // Evenly spaced rotations around one axis are interpolated with a constant speed
+            let s1 = Quaternion::squad_control(q(0.0), q(1.0), q(2.0));
+            let s2 = Quaternion::squad_control(q(1.0), q(2.0), q(3.0));
+
+            prop_assert!(Quaternion::squad(q(1.0), q(2.0), s1, s2, t).angle_to(&q(1.0 + t)) < 1.0e-6);
         }
     }
 }
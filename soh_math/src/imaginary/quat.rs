@@ -1,4 +1,5 @@
 //-----------------------------------------------------------------------------
+use crate::real::Real;
 use crate::traits::{RealConsts, WholeConsts};
 use crate::Vec3;
 //-----------------------------------------------------------------------------
@@ -58,17 +59,39 @@ where
 
 impl<T> Quaternion<T>
 where
-    T: num_traits::Float + RealConsts,
+    T: num_traits::Float + Real + RealConsts,
 {
     /// Create a unit quaternion from rotation axis and angle
     pub fn from_axis_angle(axis: Vec3<T>, angle: T) -> Self {
         let half_angle = angle * T::ONE_HALF;
 
-        let cos = half_angle.cos();
-        let sin = half_angle.sin();
+        let cos = Real::cos(half_angle);
+        let sin = Real::sin(half_angle);
 
         return Self::new(cos, axis.normalized() * sin);
     }
+
+    /// Create a unit quaternion for euler angles yaw pitch and roll ( same as
+    /// [crate::Mat3::yaw_pitch_roll] ).
+    /// The rotation is `yaw * pitch * roll`: first rotating around x-axis by `roll`, then around
+    /// y-axis by `pitch` and finally around z-axis by `yaw`
+    pub fn from_yaw_pitch_roll(yaw: T, pitch: T, roll: T) -> Self {
+        let yaw_cos = Real::cos(yaw * T::ONE_HALF);
+        let yaw_sin = Real::sin(yaw * T::ONE_HALF);
+        let pitch_cos = Real::cos(pitch * T::ONE_HALF);
+        let pitch_sin = Real::sin(pitch * T::ONE_HALF);
+        let roll_cos = Real::cos(roll * T::ONE_HALF);
+        let roll_sin = Real::sin(roll * T::ONE_HALF);
+
+        return Self::new(
+            yaw_cos * pitch_cos * roll_cos + yaw_sin * pitch_sin * roll_sin,
+            Vec3::new(
+                yaw_cos * pitch_cos * roll_sin - yaw_sin * pitch_sin * roll_cos,
+                yaw_cos * pitch_sin * roll_cos + yaw_sin * pitch_cos * roll_sin,
+                yaw_sin * pitch_cos * roll_cos - yaw_cos * pitch_sin * roll_sin,
+            ),
+        );
+    }
 }
 
 //-----------------------------------------------------------------------------
@@ -96,6 +119,11 @@ where
     pub fn len2(&self) -> T {
         return self.scalar * self.scalar + self.vector.len2();
     }
+
+    /// Calculate the dot product of two quaternions (4D vectors)
+    pub fn dot(q1: &Self, q2: &Self) -> T {
+        return q1.scalar * q2.scalar + Vec3::dot(&q1.vector, &q2.vector);
+    }
 }
 
 impl<T> Quaternion<T>
@@ -116,6 +144,9 @@ where
     T: num_traits::Num + std::ops::Neg<Output = T> + Copy,
 {
     /// Rotate a `point` using the quaternion
+    ///
+    /// The quaternion has to be of unit length, otherwise the point is also scaled by `len2()`
+    /// (see [`Quaternion::normalize`])
     pub fn rotate(&self, point: Vec3<T>) -> Vec3<T> {
         let p = Quaternion {
             scalar: T::zero(),
@@ -128,25 +159,48 @@ where
 
 impl<T> Quaternion<T>
 where
-    T: num_traits::Float + WholeConsts + RealConsts + Copy,
+    T: num_traits::Float + Real + WholeConsts + RealConsts + Copy,
 {
     /// Calculate the length (absolute value)
     pub fn len(&self) -> T {
         return self.len2().sqrt();
     }
 
+    /// Calculate and return a unit length version of `self`
+    pub fn normalized(&self) -> Self {
+        return *self / self.len();
+    }
+
+    /// Make the length 1.0 (undoes the drift accumulated after many multiplications)
+    pub fn normalize(&mut self) {
+        *self = self.normalized();
+    }
+
+    /// Check that the length is 1.0 within `eps`
+    pub fn is_normalized(&self, eps: T) -> bool {
+        return (self.len2() - T::ONE).abs() <= eps;
+    }
+
+    /// Calculate the angle of the rotation from `self` to `other` (both have to be unit length)
+    pub fn angle_to(&self, other: &Self) -> T {
+        // `q` and `-q` are the same rotation
+        let cos = Self::dot(self, other).abs().min(T::ONE);
+
+        return T::TWO * Real::acos(cos);
+    }
+
     /// Calculate the exponential
     pub fn exp(&self) -> Self {
         let len_v = self.vector.len();
-        let exp_s = self.scalar.exp();
+        let exp_s = Real::exp(self.scalar);
 
         if len_v < T::epsilon() {
-            return Self::new(self.scalar.exp(), Vec3::ZERO);
+            return Self::new(Real::exp(self.scalar), Vec3::ZERO);
         }
 
         return Self::new(
-            exp_s * len_v.cos(),
-            self.vector * exp_s * len_v.sin() / len_v,
+            exp_s * Real::cos(len_v),
+            self.vector * exp_s * Real::sin(len_v) / len_v,
         );
     }
 
@@ -154,14 +208,21 @@ where
     pub fn ln(&self) -> Self {
         let len_q = self.len();
         let len_v = self.vector.len();
-        let ln_len = len_q.ln();
+        let ln_len = Real::ln(len_q);
 
-        return Self::new(ln_len, self.vector * (self.scalar / len_q).acos() / len_v);
+        if len_v < T::epsilon() {
+            return Self::new(ln_len, Vec3::ZERO);
+        }
+
+        return Self::new(
+            ln_len,
+            self.vector * Real::acos(self.scalar / len_q) / len_v,
+        );
     }
 
     /// Calculate the natural logarithm of the length
     pub fn ln_len(&self) -> T {
-        return self.len2().ln() * T::ONE_HALF;
+        return Real::ln(self.len2()) * T::ONE_HALF;
     }
 
     /// Calculate rotation axis and angle from a unit quaternion
@@ -169,13 +230,121 @@ where
         let cos = self.scalar;
         let sin = self.vector.len();
 
-        return (self.vector / sin, T::atan2(sin, cos));
+        return (self.vector / sin, Real::atan2(sin, cos));
+    }
+
+    /// Get euler angles ( yaw, pitch, roll ) of a unit quaternion, the inverse of
+    /// [Self::from_yaw_pitch_roll] with the pitch in [-pi/2, pi/2].
+    ///
+    /// Same as [crate::Mat3::get_euler_angles] of the rotation matrix: in the gimbal lock
+    /// ( pitch near ±pi/2 ) the yaw is zero and the whole rotation around z-axis goes to the roll
+    pub fn get_euler_angles(&self) -> (T, T, T) {
+        let Quaternion {
+            scalar: s,
+            vector: Vec3 { x, y, z },
+        } = *self;
+
+        // Entries of the rotation matrix which are needed
+        let m00 = T::ONE - T::TWO * (y * y + z * z);
+        let m10 = T::TWO * (x * y + s * z);
+        let m20 = T::TWO * (x * z - s * y);
+
+        // Cosine of the pitch
+        let cos_pitch = Real::hypot(m00, m10);
+        let pitch = Real::atan2(-m20, cos_pitch);
+
+        let singular = cos_pitch < <T as num_traits::NumCast>::from(1.0e-6).unwrap();
+
+        if !singular {
+            let m21 = T::TWO * (y * z + s * x);
+            let m22 = T::ONE - T::TWO * (x * x + y * y);
+
+            return (Real::atan2(m10, m00), pitch, Real::atan2(m21, m22));
+        } else {
+            let m12 = T::TWO * (y * z - s * x);
+            let m11 = T::ONE - T::TWO * (x * x + z * z);
+
+            return (T::ZERO, pitch, Real::atan2(-m12, m11));
+        }
     }
 
     /// Calculate the inverse
     pub fn invert(&self) -> Self {
         return self.conjugate() / self.len2();
     }
+
+    /// Create a unit quaternion rotating around the rotation vector `v` by the angle `v.len()`
+    pub fn from_scaled_axis(v: Vec3<T>) -> Self {
+        return Self::new(T::ZERO, v * T::ONE_HALF).exp();
+    }
+
+    /// Calculate the rotation vector of a unit quaternion: the axis scaled by the angle in
+    /// [0, pi] ( inverse of [Self::from_scaled_axis] )
+    pub fn to_axis_angle_vec(&self) -> Vec3<T> {
+        // `q` and `-q` are the same rotation, the one with a positive scalar takes the short way
+        let q = if self.scalar < T::ZERO { -*self } else { *self };
+
+        return q.ln().vector * T::TWO;
+    }
+}
+
+//-----------------------------------------------------------------------------
+// Interpolation
+impl<T> Quaternion<T>
+where
+    T: num_traits::Float + Real + WholeConsts + RealConsts + Copy,
+{
+    /// Spherical interpolation between two unit quaternions along the shortest arc
+    pub fn slerp(a: Self, b: Self, t: T) -> Self {
+        // `b` and `-b` are the same rotation
+        let (b, cos) = match Self::dot(&a, &b) {
+            cos if cos < T::ZERO => (-b, -cos),
+            cos => (b, cos),
+        };
+
+        // Nearly the same rotation, the sine vanishes
+        if cos > T::ONE - T::epsilon().sqrt() {
+            return (a + (b - a) * t).normalized();
+        }
+
+        let angle = Real::acos(cos);
+        let sin = Real::sin(angle);
+
+        return (a * Real::sin((T::ONE - t) * angle) + b * Real::sin(t * angle)) / sin;
+    }
+
+    /// Spherical quadrangle interpolation from `q1` to `q2` with the control points `s1` and `s2`
+    /// (see [`Quaternion::squad_control`])
+    ///
+    /// Consecutive segments of a path join smoothly
+    pub fn squad(q1: Self, q2: Self, s1: Self, s2: Self, t: T) -> Self {
+        let outer = Self::slerp(q1, q2, t);
+        let inner = Self::slerp(s1, s2, t);
+
+        return Self::slerp(outer, inner, T::TWO * t * (T::ONE - t));
+    }
+
+    /// Calculate the control point of `q` for [`Quaternion::squad`] from its neighbours on the path
+    ///
+    /// Endpoints of a path can use themselves as the missing neighbour
+    pub fn squad_control(prev: Self, q: Self, next: Self) -> Self {
+        // Neighbours have to be on the same hemisphere as `q`
+        let prev = if Self::dot(&q, &prev) < T::ZERO {
+            -prev
+        } else {
+            prev
+        };
+        let next = if Self::dot(&q, &next) < T::ZERO {
+            -next
+        } else {
+            next
+        };
+
+        let inv = q.conjugate();
+        let sum = (inv * next).ln() + (inv * prev).ln();
+
+        return q * (-sum * T::ONE_HALF * T::ONE_HALF).exp();
+    }
 }
 
 //-----------------------------------------------------------------------------
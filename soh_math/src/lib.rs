@@ -3,17 +3,33 @@
 #[rustfmt::skip]
 pub mod mat;
 pub mod color;
+pub mod ease;
 pub mod fractal;
+pub mod image;
 pub mod imaginary;
+pub mod noise;
+pub mod pack;
+pub mod parse;
+pub mod real;
+pub mod rect;
+pub mod sdf;
+pub mod segment;
+pub mod transform;
 pub mod vec;
 //-----------------------------------------------------------------------------
 pub use imaginary::*;
 pub use mat::*;
+pub use rect::{Rect, Viewport};
+pub use segment::{Capsule, Segment2, Segment3};
+pub use transform::Transform;
 pub use vec::*;
 //-----------------------------------------------------------------------------
 pub mod traits;
 pub use traits::Convert;
 //-----------------------------------------------------------------------------
+#[cfg(any(test, feature = "proptest"))]
+pub mod strategy;
+//-----------------------------------------------------------------------------
 /// Linear interpolation
 pub fn lerp<V, T>(a: V, b: V, t: T) -> V
 where
@@ -22,6 +38,30 @@ where
     return a + (b - a) * t;
 }
 
+/// Cubic Hermite interpolation from point `p0` with tangent `m0` to point `p1` with tangent `m1`
+pub fn hermite<V, T>(p0: V, m0: V, p1: V, m1: V, t: T) -> V
+where
+    V: std::ops::Add<Output = V> + std::ops::Mul<T, Output = V> + Copy,
+    T: num_traits::Num + Copy,
+{
+    let one = T::one();
+    let two = one + one;
+    let three = two + one;
+
+    let t2 = t * t;
+    let t3 = t2 * t;
+
+    /*
+     * Hermite basis functions
+     */
+    let h00 = two * t3 - three * t2 + one;
+    let h10 = t3 - two * t2 + t;
+    let h01 = three * t2 - two * t3;
+    let h11 = t3 - t2;
+
+    return p0 * h00 + m0 * h10 + p1 * h01 + m1 * h11;
+}
+
 /// Find coordinate y of a point (x, y) that lies on a line that goes through points (x0, y0) and (x1, y1)
 pub fn linear_func<T>(x0: T, y0: T, x1: T, y1: T, x: T) -> T
 where
@@ -0,0 +1,120 @@
+//-----------------------------------------------------------------------------
+// Transformation of many vectors at once ( meshes, point clouds )
+//-----------------------------------------------------------------------------
+use crate::{Mat2, Mat3, Mat4, Vec2, Vec3, Vec4};
+//-----------------------------------------------------------------------------
+
+/// Vectors transformed per iteration of the unrolled loop
+const CHUNK_SIZE: usize = 8;
+
+//-----------------------------------------------------------------------------
+
+/// Apply `f` to every element in fixed size chunks, so the inner loop has no
+/// bounds checks and can be unrolled and vectorized by the compiler
+#[inline(always)]
+fn for_each_chunked<V, F>(values: &mut [V], f: F)
+where
+    F: Fn(&mut V),
+{
+    let mut chunks = values.chunks_exact_mut(CHUNK_SIZE);
+    for chunk in &mut chunks {
+        for value in chunk {
+            f(value);
+        }
+    }
+    for value in chunks.into_remainder() {
+        f(value);
+    }
+}
+
+//-----------------------------------------------------------------------------
+
+impl<T> Mat2<T>
+where
+    T: num_traits::Num + Copy,
+{
+    /// Multiply every vector by the matrix in place, like `*v = m * *v`
+    pub fn transform_slice(&self, vectors: &mut [Vec2<T>]) {
+        let [m0, m1, m2, m3] = self.0;
+
+        for_each_chunked(vectors, |v| {
+            let Vec2 { x, y } = *v;
+            *v = Vec2 {
+                x: m0 * x + m2 * y,
+                y: m1 * x + m3 * y,
+            };
+        });
+    }
+}
+
+impl<T> Mat3<T>
+where
+    T: num_traits::Num + Copy,
+{
+    /// Multiply every vector by the matrix in place, like `*v = m * *v`
+    pub fn transform_slice(&self, vectors: &mut [Vec3<T>]) {
+        let [m0, m1, m2, m3, m4, m5, m6, m7, m8] = self.0;
+
+        for_each_chunked(vectors, |v| {
+            let Vec3 { x, y, z } = *v;
+            *v = Vec3 {
+                x: m0 * x + m3 * y + m6 * z,
+                y: m1 * x + m4 * y + m7 * z,
+                z: m2 * x + m5 * y + m8 * z,
+            };
+        });
+    }
+}
+
+impl<T> Mat4<T>
+where
+    T: num_traits::Num + Copy,
+{
+    /// Multiply every vector by the matrix in place, like `*v = m * *v`
+    pub fn transform_slice(&self, vectors: &mut [Vec4<T>]) {
+        let [m0, m1, m2, m3, m4, m5, m6, m7, m8, m9, m10, m11, m12, m13, m14, m15] = self.0;
+
+        for_each_chunked(vectors, |v| {
+            let Vec4 { x, y, z, w } = *v;
+            *v = Vec4 {
+                x: m0 * x + m4 * y + m8  * z + m12 * w,
+                y: m1 * x + m5 * y + m9  * z + m13 * w,
+                z: m2 * x + m6 * y + m10 * z + m14 * w,
+                w: m3 * x + m7 * y + m11 * z + m15 * w,
+            };
+        });
+    }
+
+    /// Apply the affine transformation to every point in place ( `w = 1`, the
+    /// translation is applied ). The last row of the matrix is ignored, there
+    /// is no perspective division
+    pub fn transform_points(&self, points: &mut [Vec3<T>]) {
+        let [m0, m1, m2, _, m4, m5, m6, _, m8, m9, m10, _, m12, m13, m14, _] = self.0;
+
+        for_each_chunked(points, |p| {
+            let Vec3 { x, y, z } = *p;
+            *p = Vec3 {
+                x: m0 * x + m4 * y + m8  * z + m12,
+                y: m1 * x + m5 * y + m9  * z + m13,
+                z: m2 * x + m6 * y + m10 * z + m14,
+            };
+        });
+    }
+
+    /// Apply the affine transformation to every direction in place ( `w = 0`,
+    /// the translation is ignored ). The last row of the matrix is ignored
+    pub fn transform_vectors(&self, vectors: &mut [Vec3<T>]) {
+        let [m0, m1, m2, _, m4, m5, m6, _, m8, m9, m10, _, _, _, _, _] = self.0;
+
+        for_each_chunked(vectors, |v| {
+            let Vec3 { x, y, z } = *v;
+            *v = Vec3 {
+                x: m0 * x + m4 * y + m8  * z,
+                y: m1 * x + m5 * y + m9  * z,
+                z: m2 * x + m6 * y + m10 * z,
+            };
+        });
+    }
+}
+
+//-----------------------------------------------------------------------------
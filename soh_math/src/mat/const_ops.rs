@@ -0,0 +1,61 @@
+//-----------------------------------------------------------------------------
+// Matrix operations usable in constants
+//
+// Trait methods ( like `Mul::mul` ) can't be called in const fn, so these are implemented for
+// each primitive type. This allows baking transforms into statics:
+//
+// const TRANSFORM: Mat4<f32> = Mat4::from_translation(Vec3::new(1.0, 2.0, 3.0))
+//     .const_mul(Mat4::from_diagonal(Vec4::splat(2.0)));
+//
+// ( the element type has to be known, so untyped literals need a suffix like `1.0f32` )
+//-----------------------------------------------------------------------------
+use crate::{Mat2, Mat3, Mat4, Vec2, Vec3, Vec4};
+//-----------------------------------------------------------------------------
+
+macro_rules! impl_const_ops {
+    ($mat:ident, $vec:ident, $n:literal, $rows:tt, $($t:ty)*) => {
+        $(
+            impl_const_ops!(@type $mat, $vec, $n, $rows, $t);
+        )*
+    };
+    (@type $mat:ident, $vec:ident, $n:literal, [$($row:literal),*], $t:ty) => {
+            impl $mat<$t> {
+                /// Addition usable in constants
+                pub const fn const_add(self, rhs: Self) -> Self {
+                    let mut m = self.0;
+                    let mut i = 0;
+                    while i < $n * $n {
+                        m[i] += rhs.0[i];
+                        i += 1;
+                    }
+                    return $mat(m);
+                }
+
+                /// Matrix product usable in constants
+                pub const fn const_mul(self, rhs: Self) -> Self {
+                    let mut m = [0 as $t; $n * $n];
+                    let mut col = 0;
+                    while col < $n {
+                        let mut row = 0;
+                        while row < $n {
+                            m[col * $n + row] = self.row(row).const_dot(rhs.col(col));
+                            row += 1;
+                        }
+                        col += 1;
+                    }
+                    return $mat(m);
+                }
+
+                /// Matrix vector product usable in constants
+                pub const fn const_mul_vec(self, v: $vec<$t>) -> $vec<$t> {
+                    return $vec::new($(self.row($row).const_dot(v)),*);
+                }
+            }
+    };
+}
+
+impl_const_ops!(Mat2, Vec2, 2, [0, 1],       i8 i16 i32 i64 u8 u16 u32 u64 f32 f64);
+impl_const_ops!(Mat3, Vec3, 3, [0, 1, 2],    i8 i16 i32 i64 u8 u16 u32 u64 f32 f64);
+impl_const_ops!(Mat4, Vec4, 4, [0, 1, 2, 3], i8 i16 i32 i64 u8 u16 u32 u64 f32 f64);
+
+//-----------------------------------------------------------------------------
@@ -0,0 +1,210 @@
+//-----------------------------------------------------------------------------
+// Eigen decomposition of symmetric matrices and singular value decomposition
+//-----------------------------------------------------------------------------
+use crate::real::Real;
+use crate::traits::WholeConsts;
+use crate::{Mat2, Mat3, Vec2, Vec3};
+//-----------------------------------------------------------------------------
+
+/// Maximum number of Jacobi sweeps ( converges in less than 10 in practice )
+const MAX_SWEEPS: usize = 32;
+
+//-----------------------------------------------------------------------------
+
+impl<T> Mat2<T>
+where
+    T: num_traits::Float + Real + WholeConsts,
+{
+    /// Eigen decomposition of a symmetric matrix ( only the lower triangle is used )
+    ///
+    /// Returns the eigenvalues in descending order and a rotation matrix with the matching
+    /// eigenvectors as columns, so `self == v * diag(values) * v.t()`
+    pub fn symmetric_eigen(&self) -> (Vec2<T>, Mat2<T>) {
+        let (a, b, d) = (self.at(0, 0), self.at(1, 0), self.at(1, 1));
+
+        // A single Jacobi rotation diagonalizes a 2x2 matrix
+        let two = T::TWO;
+        let angle = Real::atan2(two * b, a - d) / two;
+        let (sin, cos) = Real::sin_cos(angle);
+
+        let l0 = a * cos * cos + two * b * cos * sin + d * sin * sin;
+        let l1 = a * sin * sin - two * b * cos * sin + d * cos * cos;
+
+        let v0 = Vec2::new(cos, sin);
+        if l0 >= l1 {
+            return (Vec2::new(l0, l1), Mat2::from_cols([v0, v0.perp()]));
+        } else {
+            return (Vec2::new(l1, l0), Mat2::from_cols([v0.perp(), -v0]));
+        }
+    }
+
+    /// Singular value decomposition
+    ///
+    /// Returns `(u, sigma, v)` where `u` and `v` are orthogonal and the singular values `sigma`
+    /// are non negative and in descending order, so `self == u * diag(sigma) * v.t()`
+    pub fn svd(&self) -> (Mat2<T>, Vec2<T>, Mat2<T>) {
+        let (values, v) = (self.t() * *self).symmetric_eigen();
+        let sigma = values.map(|x| x.max(T::ZERO).sqrt());
+        let tol = sigma.x * T::epsilon().sqrt();
+
+        /*
+         * Left singular vectors are the images of the right ones
+         */
+        let u0 = match sigma.x > tol {
+            true => (*self * v.col(0)).normalized(),
+            false => Vec2::X,
+        };
+
+        let mut u1 = u0.perp();
+        if sigma.y > tol && Vec2::dot(&u1, &(*self * v.col(1))) < T::ZERO {
+            u1 = -u1;
+        }
+
+        return (Mat2::from_cols([u0, u1]), sigma, v);
+    }
+
+    /// Polar decomposition `self == r * s` into an orthogonal matrix `r` and a symmetric positive
+    /// semi-definite matrix `s`
+    ///
+    /// `r` is a rotation when the determinant is positive and a reflection otherwise
+    pub fn polar(&self) -> (Mat2<T>, Mat2<T>) {
+        let (u, sigma, v) = self.svd();
+        let s = v * Mat2::new([sigma.x, T::ZERO, T::ZERO, sigma.y]) * v.t();
+
+        return (u * v.t(), s);
+    }
+}
+
+//-----------------------------------------------------------------------------
+
+impl<T> Mat3<T>
+where
+    T: num_traits::Float + Real + WholeConsts,
+{
+    /// Eigen decomposition of a symmetric matrix using Jacobi rotations
+    ///
+    /// Returns the eigenvalues in descending order and a rotation matrix with the matching
+    /// eigenvectors as columns, so `self == v * diag(values) * v.t()`
+    pub fn symmetric_eigen(&self) -> (Vec3<T>, Mat3<T>) {
+        let mut a = *self;
+        let mut v = Mat3::identity();
+
+        let off_diagonal = |m: &Mat3<T>| {
+            return m.at(1, 0) * m.at(1, 0) + m.at(2, 0) * m.at(2, 0) + m.at(2, 1) * m.at(2, 1);
+        };
+        let tol = T::epsilon() * T::epsilon() * self.0.iter().fold(T::ZERO, |acc, &x| acc + x * x);
+
+        /*
+         * Zero the off diagonal elements one by one until they are all small enough
+         */
+        for _ in 0..MAX_SWEEPS {
+            if off_diagonal(&a) <= tol {
+                break;
+            }
+
+            for (p, q) in [(0, 1), (0, 2), (1, 2)] {
+                let apq = a.at(p, q);
+                if apq == T::ZERO {
+                    continue;
+                }
+
+                let theta = (a.at(q, q) - a.at(p, p)) / (T::TWO * apq);
+                let t = theta.signum() / (theta.abs() + (theta * theta + T::ONE).sqrt());
+                let cos = T::ONE / (t * t + T::ONE).sqrt();
+                let sin = t * cos;
+
+                let mut rot = Mat3::identity();
+                *rot.at_mut(p, p) = cos;
+                *rot.at_mut(q, q) = cos;
+                *rot.at_mut(p, q) = sin;
+                *rot.at_mut(q, p) = -sin;
+
+                a = rot.t() * a * rot;
+                v *= rot;
+            }
+        }
+
+        /*
+         * Sort by the eigenvalue and keep `v` a rotation
+         */
+        let mut order = [0, 1, 2];
+        order.sort_by(|&i, &j| a.at(j, j).partial_cmp(&a.at(i, i)).unwrap());
+
+        let [i, j, k] = order;
+        let values = Vec3::new(a.at(i, i), a.at(j, j), a.at(k, k));
+        let mut vectors = Mat3::from_cols(order.map(|i| v.col(i)));
+
+        if vectors.det() < T::ZERO {
+            vectors = Mat3::from_cols([vectors.col(0), vectors.col(1), -vectors.col(2)]);
+        }
+
+        return (values, vectors);
+    }
+
+    /// Singular value decomposition
+    ///
+    /// Returns `(u, sigma, v)` where `u` and `v` are orthogonal and the singular values `sigma`
+    /// are non negative and in descending order, so `self == u * diag(sigma) * v.t()`
+    pub fn svd(&self) -> (Mat3<T>, Vec3<T>, Mat3<T>) {
+        let (values, v) = (self.t() * *self).symmetric_eigen();
+        let sigma = values.map(|x| x.max(T::ZERO).sqrt());
+        let tol = sigma.x * T::epsilon().sqrt();
+
+        /*
+         * Left singular vectors are the images of the right ones, orthogonalized
+         */
+        let u0 = match sigma.x > tol {
+            true => (*self * v.col(0)).normalized(),
+            false => Vec3::X,
+        };
+
+        let u1 = match sigma.y > tol {
+            true => {
+                let image = *self * v.col(1);
+                (image - u0 * Vec3::dot(&u0, &image)).normalized()
+            }
+            false => any_perpendicular(u0),
+        };
+
+        let mut u2 = Vec3::cross(&u0, &u1);
+        if sigma.z > tol && Vec3::dot(&u2, &(*self * v.col(2))) < T::ZERO {
+            u2 = -u2;
+        }
+
+        return (Mat3::from_cols([u0, u1, u2]), sigma, v);
+    }
+
+    /// Polar decomposition `self == r * s` into an orthogonal matrix `r` and a symmetric positive
+    /// semi-definite matrix `s`
+    ///
+    /// `r` is a rotation when the determinant is positive and a reflection otherwise
+    pub fn polar(&self) -> (Mat3<T>, Mat3<T>) {
+        let (u, sigma, v) = self.svd();
+        let diag = Mat3::new([
+            sigma.x, T::ZERO, T::ZERO,
+            T::ZERO, sigma.y, T::ZERO,
+            T::ZERO, T::ZERO, sigma.z,
+        ]);
+
+        return (u * v.t(), v * diag * v.t());
+    }
+}
+
+/// Some unit vector perpendicular to the unit vector `v`
+fn any_perpendicular<T>(v: Vec3<T>) -> Vec3<T>
+where
+    T: num_traits::Float + Real + WholeConsts,
+{
+    // Cross with the axis which is the furthest from being parallel
+    let axis = if v.x.abs() <= v.y.abs() && v.x.abs() <= v.z.abs() {
+        Vec3::X
+    } else if v.y.abs() <= v.z.abs() {
+        Vec3::Y
+    } else {
+        Vec3::Z
+    };
+
+    return Vec3::cross(&v, &axis).normalized();
+}
+
+//-----------------------------------------------------------------------------
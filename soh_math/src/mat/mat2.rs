@@ -1,4 +1,5 @@
 //-----------------------------------------------------------------------------
+use crate::real::Real;
 use crate::Vec2;
 //-----------------------------------------------------------------------------
 /// 2x2 matrix ( column major )
@@ -84,6 +85,14 @@ where
         ]);
     }
 
+    /// Construct a matrix with `diagonal` on the main diagonal
+    pub const fn from_diagonal(diagonal: Vec2<T>) -> Self {
+        return Mat2([
+            diagonal.x, T::ZERO,
+            T::ZERO,    diagonal.y,
+        ]);
+    }
+
     /// Get matrix determinant
     pub fn det(&self) -> T {
         return self.0[0] * self.0[3] - self.0[1] * self.0[2];
@@ -113,12 +122,12 @@ where
 
 impl<T> Mat2<T>
 where
-    T: num_traits::Float + std::iter::Sum,
+    T: num_traits::Float + Real + std::iter::Sum,
 {
     /// Construct a rotation matrix for angle `phi`
     pub fn rot(phi: T) -> Self {
-        let cos_phi = phi.cos();
-        let sin_phi = phi.sin();
+        let cos_phi = Real::cos(phi);
+        let sin_phi = Real::sin(phi);
 
         return Mat2([
              cos_phi, sin_phi,
@@ -244,6 +253,70 @@ where
     }
 }
 
+impl<T> std::ops::AddAssign for Mat2<T>
+where
+    T: num_traits::Num + Copy,
+{
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl<T> std::ops::SubAssign for Mat2<T>
+where
+    T: num_traits::Num + Copy,
+{
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl<T> std::ops::MulAssign<T> for Mat2<T>
+where
+    T: num_traits::Num + Copy,
+{
+    fn mul_assign(&mut self, rhs: T) {
+        *self = *self * rhs;
+    }
+}
+
+impl<T> std::ops::MulAssign for Mat2<T>
+where
+    T: num_traits::Num + Copy,
+{
+    /// `self = self * rhs` ( `rhs` is applied first )
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = *self * rhs;
+    }
+}
+
+impl<T> std::ops::DivAssign<T> for Mat2<T>
+where
+    T: num_traits::Num + Copy,
+{
+    fn div_assign(&mut self, rhs: T) {
+        *self = *self / rhs;
+    }
+}
+
+//-----------------------------------------------------------------------------
+// Element access by ( row, column ), like [Mat2::at]
+impl<T> std::ops::Index<(usize, usize)> for Mat2<T> {
+    type Output = T;
+
+    fn index(&self, (row, col): (usize, usize)) -> &Self::Output {
+        assert!(row < 2 && col < 2, "Index ( {row}, {col} ) is out of range of Mat2");
+        return &self.0[col * 2 + row];
+    }
+}
+
+impl<T> std::ops::IndexMut<(usize, usize)> for Mat2<T> {
+    fn index_mut(&mut self, (row, col): (usize, usize)) -> &mut Self::Output {
+        assert!(row < 2 && col < 2, "Index ( {row}, {col} ) is out of range of Mat2");
+        return &mut self.0[col * 2 + row];
+    }
+}
+
 //-----------------------------------------------------------------------------
 // Other
 impl<T> AsRef<[T]> for Mat2<T> {
@@ -1,4 +1,5 @@
 //-----------------------------------------------------------------------------
+use crate::real::Real;
 use crate::Vec3;
 //-----------------------------------------------------------------------------
 /// 3x3 matrix ( column major )
@@ -89,6 +90,15 @@ where
         ]);
     }
 
+    /// Construct a matrix with `diagonal` on the main diagonal
+    pub const fn from_diagonal(diagonal: Vec3<T>) -> Self {
+        return Mat3([
+            diagonal.x, T::ZERO,    T::ZERO,
+            T::ZERO,    diagonal.y, T::ZERO,
+            T::ZERO,    T::ZERO,    diagonal.z,
+        ]);
+    }
+
     /// Get matrix determinant
     pub fn det(&self) -> T {
         return self.0[0] * (self.0[4] * self.0[8] - self.0[7] * self.0[5])
@@ -137,13 +147,13 @@ where
 
 impl<T> Mat3<T>
 where
-    T: num_traits::Float + std::iter::Sum + From<f32>,
+    T: num_traits::Float + Real + std::iter::Sum + From<f32>,
 {
     /// Get a rotation matrix for yaw `phi`
     /// ( Rotation around the z-axis )
     pub fn yaw(phi: T) -> Self {
-        let phi_cos = phi.cos();
-        let phi_sin = phi.sin();
+        let phi_cos = Real::cos(phi);
+        let phi_sin = Real::sin(phi);
 
         return Mat3([
              phi_cos,   phi_sin,   T::zero(),
@@ -155,8 +165,8 @@ where
     /// Get a rotation matrix for pitch `theta`
     /// ( Rotation around the y-axis )
     pub fn pitch(theta: T) -> Self {
-        let theta_cos = theta.cos();
-        let theta_sin = theta.sin();
+        let theta_cos = Real::cos(theta);
+        let theta_sin = Real::sin(theta);
 
         return Mat3([
             theta_cos, T::zero(), -theta_sin,
@@ -168,8 +178,8 @@ where
     /// Get a rotation matrix for roll `psi`
     /// ( Rotation around the x-axis )
     pub fn roll(psi: T) -> Self {
-        let psi_cos = psi.cos();
-        let psi_sin = psi.sin();
+        let psi_cos = Real::cos(psi);
+        let psi_sin = Real::sin(psi);
 
         return Mat3([
             T::one(),   T::zero(), T::zero(),
@@ -182,12 +192,12 @@ where
     /// Identical to multiplying yaw * pitch * roll matrices separately
     /// ( First rotating around x-axis, then rotating around y-axis and finally around z-axis )
     pub fn yaw_pitch_roll(yaw: T, pitch: T, roll: T) -> Self {
-        let yaw_cos = yaw.cos();
-        let yaw_sin = yaw.sin();
-        let pitch_cos = pitch.cos();
-        let pitch_sin = pitch.sin();
-        let roll_cos = roll.cos();
-        let roll_sin = roll.sin();
+        let yaw_cos = Real::cos(yaw);
+        let yaw_sin = Real::sin(yaw);
+        let pitch_cos = Real::cos(pitch);
+        let pitch_sin = Real::sin(pitch);
+        let roll_cos = Real::cos(roll);
+        let roll_sin = Real::sin(roll);
 
         return Mat3([
             // First column
@@ -210,21 +220,21 @@ where
     /// source:
     /// <https://learnopencv.com/rotation-matrix-to-euler-angles/>
     pub fn get_euler_angles(&self) -> (T, T, T) {
-        let sy = T::hypot(self.at(0, 0), self.at(1, 0));
+        let sy = Real::hypot(self.at(0, 0), self.at(1, 0));
 
         let singular = sy < 1.0e-6.into();
 
         if !singular {
             return (
-                T::atan2(self.at(1, 0), self.at(0, 0)),
-                T::atan2(-self.at(2, 0), sy),
-                T::atan2(self.at(2, 1), self.at(2, 2)),
+                Real::atan2(self.at(1, 0), self.at(0, 0)),
+                Real::atan2(-self.at(2, 0), sy),
+                Real::atan2(self.at(2, 1), self.at(2, 2)),
             );
         } else {
             return (
                 T::zero(),
-                T::atan2(-self.at(2, 0), sy),
-                T::atan2(-self.at(1, 2), self.at(1, 1)),
+                Real::atan2(-self.at(2, 0), sy),
+                Real::atan2(-self.at(1, 2), self.at(1, 1)),
             );
         }
     }
@@ -235,8 +245,8 @@ where
     /// <https://songho.ca/opengl/gl_rotate.html>
     pub fn from_axis_angle(axis: Vec3<T>, angle: T) -> Self {
         // Angle related values
-        let cos = angle.cos();
-        let sin = angle.sin();
+        let cos = Real::cos(angle);
+        let sin = Real::sin(angle);
 
         let one_minus_cos = T::one() - cos;
 
@@ -330,6 +340,100 @@ where
     }
 }
 
+impl<T> Mat3<T>
+where
+    T: num_traits::Float + Real + crate::traits::WholeConsts + std::iter::Sum + From<f32>,
+{
+    /// Skew-symmetric matrix of the cross product ( `skew(v) * u == v x u` )
+    pub fn skew(v: Vec3<T>) -> Self {
+        let zero = T::ZERO;
+
+        return Mat3([
+            // First column
+            zero, v.z, -v.y,
+            // Second column
+            -v.z, zero, v.x,
+            // Third column
+            v.y, -v.x, zero,
+        ]);
+    }
+
+    /// Vector of a skew-symmetric matrix ( inverse of [Self::skew] )
+    pub fn unskew(&self) -> Vec3<T> {
+        return Vec3::new(self.at(2, 1), self.at(0, 2), self.at(1, 0));
+    }
+
+    /// Matrix exponential of a skew-symmetric generator, which is the rotation around the
+    /// generator's vector by the vector's length ( Rodrigues' formula )
+    pub fn exp(&self) -> Self {
+        let v = self.unskew();
+        let angle = v.len();
+
+        if angle < 1.0e-6.into() {
+            // First order approximation
+            return Self::identity() + *self;
+        }
+
+        return Self::from_axis_angle(v, angle);
+    }
+
+    /// Matrix logarithm of a rotation matrix, which is the skew-symmetric generator
+    /// ( `skew(axis * angle)` with the angle in [0, pi] )
+    pub fn log(&self) -> Self {
+        let one = T::ONE;
+        let one_half: T = 0.5.into();
+
+        let trace = self.at(0, 0) + self.at(1, 1) + self.at(2, 2);
+        let cos = ((trace - one) * one_half).max(-one).min(one);
+        let angle = Real::acos(cos);
+
+        // R - R^T = 2 sin(angle) skew(axis)
+        let antisym = (*self - self.t()) * one_half;
+
+        if angle < 1.0e-6.into() {
+            return antisym;
+        }
+
+        if cos > (-0.9).into() {
+            return antisym * (angle / Real::sin(angle));
+        }
+
+        /*
+         * Near pi the sine vanishes, so the axis is found from the symmetric part instead:
+         * (R + R^T) / 2 = cos I + (1 - cos) axis axis^T
+         */
+        let sym = ((*self + self.t()) * one_half - Self::scale(cos)) * (one / (one - cos));
+
+        let i = (0..3)
+            .max_by(|&a, &b| sym.at(a, a).partial_cmp(&sym.at(b, b)).unwrap())
+            .unwrap();
+        let mut axis = sym.col(i) / sym.at(i, i).sqrt();
+
+        // Sign of the axis is kept by the antisymmetric part
+        if Vec3::dot(&axis, &antisym.unskew()) < T::ZERO {
+            axis = -axis;
+        }
+
+        return Self::skew(axis.normalized() * angle);
+    }
+
+    /// Spherical interpolation between two rotation matrices
+    pub fn slerp(a: Self, b: Self, t: T) -> Self {
+        return a * ((a.t() * b).log() * t).exp();
+    }
+
+    /// Rotation around the rotation vector `v` by the angle `v.len()`
+    pub fn from_scaled_axis(v: Vec3<T>) -> Self {
+        return Self::skew(v).exp();
+    }
+
+    /// Rotation vector: the axis scaled by the angle in [0, pi] ( inverse of
+    /// [Self::from_scaled_axis] )
+    pub fn to_axis_angle_vec(&self) -> Vec3<T> {
+        return self.log().unskew();
+    }
+}
+
 //-----------------------------------------------------------------------------
 // Operator overloads
 impl<T> std::ops::Add for Mat3<T>
@@ -476,4 +580,68 @@ where
     }
 }
 
+impl<T> std::ops::AddAssign for Mat3<T>
+where
+    T: num_traits::Num + Copy,
+{
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl<T> std::ops::SubAssign for Mat3<T>
+where
+    T: num_traits::Num + Copy,
+{
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl<T> std::ops::MulAssign<T> for Mat3<T>
+where
+    T: num_traits::Num + Copy,
+{
+    fn mul_assign(&mut self, rhs: T) {
+        *self = *self * rhs;
+    }
+}
+
+impl<T> std::ops::MulAssign for Mat3<T>
+where
+    T: num_traits::Num + Copy,
+{
+    /// `self = self * rhs` ( `rhs` is applied first )
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = *self * rhs;
+    }
+}
+
+impl<T> std::ops::DivAssign<T> for Mat3<T>
+where
+    T: num_traits::Num + Copy,
+{
+    fn div_assign(&mut self, rhs: T) {
+        *self = *self / rhs;
+    }
+}
+
+//-----------------------------------------------------------------------------
+// Element access by ( row, column ), like [Mat3::at]
+impl<T> std::ops::Index<(usize, usize)> for Mat3<T> {
+    type Output = T;
+
+    fn index(&self, (row, col): (usize, usize)) -> &Self::Output {
+        assert!(row < 3 && col < 3, "Index ( {row}, {col} ) is out of range of Mat3");
+        return &self.0[col * 3 + row];
+    }
+}
+
+impl<T> std::ops::IndexMut<(usize, usize)> for Mat3<T> {
+    fn index_mut(&mut self, (row, col): (usize, usize)) -> &mut Self::Output {
+        assert!(row < 3 && col < 3, "Index ( {row}, {col} ) is out of range of Mat3");
+        return &mut self.0[col * 3 + row];
+    }
+}
+
 //-----------------------------------------------------------------------------
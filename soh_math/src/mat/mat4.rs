@@ -124,6 +124,26 @@ where
         ]);
     }
 
+    /// Construct a matrix with `diagonal` on the main diagonal
+    pub const fn from_diagonal(diagonal: Vec4<T>) -> Self {
+        return Mat4([
+            diagonal.x, T::ZERO,    T::ZERO,    T::ZERO,
+            T::ZERO,    diagonal.y, T::ZERO,    T::ZERO,
+            T::ZERO,    T::ZERO,    diagonal.z, T::ZERO,
+            T::ZERO,    T::ZERO,    T::ZERO,    diagonal.w,
+        ]);
+    }
+
+    /// Construct a translation matrix
+    pub const fn from_translation(offset: crate::Vec3<T>) -> Self {
+        return Mat4([
+            T::ONE,   T::ZERO,  T::ZERO,  T::ZERO,
+            T::ZERO,  T::ONE,   T::ZERO,  T::ZERO,
+            T::ZERO,  T::ZERO,  T::ONE,   T::ZERO,
+            offset.x, offset.y, offset.z, T::ONE,
+        ]);
+    }
+
     /// Get matrix determinant
     pub fn det(&self) -> T {
         return self.0[0]  * (self.0[5]  * (self.0[10] * self.0[15] - self.0[14] * self.0[11])
@@ -265,6 +285,34 @@ where
 impl<T> Mat4<T>
 where
     T: num_traits::Float + crate::traits::WholeConsts + std::iter::Sum,
+{
+    /// Construct an orthographic projection matrix mapping the box to the clip space of
+    /// [Self::perspective]: x from `left` to `right` and y from `bottom` to `top` go to -1..1,
+    /// z from `near` to `far` goes to 0..1 ( Vulkan depth ). Vulkan's y points down, so `bottom`
+    /// ends up at the top of the screen: `ortho(0, width, 0, height, ..)` takes y down pixel
+    /// coordinates
+    pub fn ortho(left: T, right: T, bottom: T, top: T, near: T, far: T) -> Self {
+        let width = right - left;
+        let height = top - bottom;
+        let depth = far - near;
+
+        return Mat4([
+            T::TWO / width,           T::ZERO,                  T::ZERO,        T::ZERO,
+            T::ZERO,                  T::TWO / height,          T::ZERO,        T::ZERO,
+            T::ZERO,                  T::ZERO,                  T::ONE / depth, T::ZERO,
+            -(right + left) / width,  -(top + bottom) / height, -near / depth,  T::ONE,
+        ]);
+    }
+
+    /// Get the norm
+    pub fn norm(&self) -> T {
+        return self.0.iter().map(|&x| x * x).sum::<T>().sqrt();
+    }
+}
+
+impl<T> Mat4<T>
+where
+    T: num_traits::Float + crate::real::Real + crate::traits::WholeConsts + std::iter::Sum + From<f32>,
 {
     /// Construct a perspective projection matrix
     ///
@@ -273,7 +321,7 @@ where
     /// * `near`: near plane
     /// * `far`: far plane
     pub fn perspective(fov: T, aspect: T, near: T, far: T) -> Self {
-        let cot = T::ONE / T::tan(fov.to_radians() / T::TWO);
+        let cot = T::ONE / crate::real::Real::tan(fov.to_radians() / T::TWO);
         let far_near = T::ONE / (far - near);
 
         return Mat4([
@@ -284,9 +332,23 @@ where
         ]);
     }
 
-    /// Get the norm
-    pub fn norm(&self) -> T {
-        return self.0.iter().map(|&x| x * x).sum::<T>().sqrt();
+    /// Create a rotation matrix from rotation axis and angle (see [crate::Mat3::from_axis_angle])
+    pub fn from_axis_angle(axis: crate::Vec3<T>, angle: T) -> Self {
+        let rotation = crate::Mat3::from_axis_angle(axis, angle);
+        return Self::from_3x3_vec(rotation, crate::Vec3::zero());
+    }
+
+    /// Create a rotation matrix from a unit quaternion (see [crate::Mat3::from_quat])
+    pub fn from_quat(quat: crate::Quaternion<T>) -> Self {
+        return Self::from_3x3_vec(crate::Mat3::from_quat(quat), crate::Vec3::zero());
+    }
+
+    /// View matrix of a camera at `pos` looking at `target` ( the inverse of the camera
+    /// transform, see [crate::Mat3::look_at] ). The camera looks along +Z like
+    /// [Self::perspective] expects, so `perspective * look_at` is the view-projection
+    pub fn look_at(pos: crate::Vec3<T>, target: crate::Vec3<T>, up: crate::Vec3<T>) -> Self {
+        let rotation = crate::Mat3::look_at(pos, target, up).t();
+        return Self::from_3x3_vec(rotation, -(rotation * pos));
     }
 }
 
@@ -488,4 +550,101 @@ where
     }
 }
 
+impl<T> std::ops::AddAssign for Mat4<T>
+where
+    T: num_traits::Num + Copy,
+{
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl<T> std::ops::SubAssign for Mat4<T>
+where
+    T: num_traits::Num + Copy,
+{
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl<T> std::ops::MulAssign<T> for Mat4<T>
+where
+    T: num_traits::Num + Copy,
+{
+    fn mul_assign(&mut self, rhs: T) {
+        *self = *self * rhs;
+    }
+}
+
+impl<T> std::ops::MulAssign for Mat4<T>
+where
+    T: num_traits::Num + Copy,
+{
+    /// `self = self * rhs` ( `rhs` is applied first )
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = *self * rhs;
+    }
+}
+
+impl<T> std::ops::DivAssign<T> for Mat4<T>
+where
+    T: num_traits::Num + Copy,
+{
+    fn div_assign(&mut self, rhs: T) {
+        *self = *self / rhs;
+    }
+}
+
+//-----------------------------------------------------------------------------
+// Element access by ( row, column ), like [Mat4::at]
+impl<T> std::ops::Index<(usize, usize)> for Mat4<T> {
+    type Output = T;
+
+    fn index(&self, (row, col): (usize, usize)) -> &Self::Output {
+        assert!(row < 4 && col < 4, "Index ( {row}, {col} ) is out of range of Mat4");
+        return &self.0[col * 4 + row];
+    }
+}
+
+impl<T> std::ops::IndexMut<(usize, usize)> for Mat4<T> {
+    fn index_mut(&mut self, (row, col): (usize, usize)) -> &mut Self::Output {
+        assert!(row < 4 && col < 4, "Index ( {row}, {col} ) is out of range of Mat4");
+        return &mut self.0[col * 4 + row];
+    }
+}
+
+//-----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use crate::*;
+
+    const EPS: f64 = 1.0e-9;
+
+    proptest! {
+        #[test]
+        fn prop_mat4_look_at(
+            pos in strategy::vec3(-10.0..10.0),
+            dir in strategy::unit_vec3(),
+            dist in 0.1..10.0f64,
+            up in strategy::unit_vec3(),
+        ) {
+            // The up vector can't be parallel to the view direction
+            prop_assume!(Vec3::cross(&up, &dir).len() > 0.1);
+
+            let target = pos + dir * dist;
+            let view = Mat4::look_at(pos, target, up);
+
+            let origin = view * Vec4::new(pos.x, pos.y, pos.z, 1.0);
+            prop_assert!(Vec4::new(0.0, 0.0, 0.0, 1.0).distance(&origin) < EPS);
+
+            let on_axis = view * Vec4::new(target.x, target.y, target.z, 1.0);
+            prop_assert!(Vec4::new(0.0, 0.0, dist, 1.0).distance(&on_axis) < EPS);
+        }
+    }
+}
+
 //-----------------------------------------------------------------------------
@@ -1,7 +1,11 @@
 //-----------------------------------------------------------------------------
+mod batch;
+mod const_ops;
+mod decompose;
 mod mat2;
 mod mat3;
 mod mat4;
+mod scalar_ops;
 //-----------------------------------------------------------------------------
 pub use mat2::*;
 pub use mat3::*;
@@ -10,10 +14,35 @@ pub use mat4::*;
 
 #[cfg(test)]
 mod tests {
-    use soh_rng::Engine64;
+    use proptest::prelude::*;
 
     use crate::*;
 
+    #[test]
+    fn test_const() {
+        const SCALE: Mat4<f32> = Mat4::from_diagonal(Vec4::new(2.0, 2.0, 2.0, 1.0));
+        const MOVE: Mat4<f32> = Mat4::from_translation(Vec3::new(1.0, 2.0, 3.0));
+        const TRANSFORM: Mat4<f32> = MOVE.const_mul(SCALE);
+        const POINT: Vec4<f32> = TRANSFORM.const_mul_vec(Vec4::new(1.0, 1.0, 1.0, 1.0));
+
+        assert_eq!(TRANSFORM, MOVE * SCALE);
+        assert_eq!(POINT, Vec4::new(3.0, 4.0, 5.0, 1.0));
+
+        const TABLE: [Vec2<i32>; 3] = [
+            Vec2::splat(1),
+            Vec2::new(1i32, 2).const_add(Vec2::new(3, 4)),
+            Vec2::new(5i32, 5).const_sub(Vec2::X).const_mul(2),
+        ];
+        assert_eq!(TABLE, [Vec2::new(1, 1), Vec2::new(4, 6), Vec2::new(8, 10)]);
+        assert_eq!(Vec3::new(1i32, 2, 3).const_dot(Vec3::new(4, 5, 6)), 32);
+
+        const ROT: Mat2<i32> = Mat2::from_rows([Vec2::new(0, -1), Vec2::new(1, 0)]);
+        assert_eq!(ROT.const_mul(ROT), Mat2::from_diagonal(Vec2::splat(-1)));
+        assert_eq!(ROT.const_add(ROT), ROT * 2);
+        assert_eq!(ROT.const_add(ROT), 2 * ROT);
+        assert_eq!(Mat3::from_diagonal(Vec3::splat(3.0)), Mat3::scale(3.0));
+    }
+
     #[test]
     fn test_mat2() {
         // Test that you can construct a matrix using f32
@@ -43,36 +72,10 @@ mod tests {
             m1 * m2,
             Mat2::from_rows([Vec2::new(4.0, 5.0), Vec2::new(18.0, 17.0)])
         );
-
-        // Test matrix inversion
-        let mut rng = soh_rng::RNG64::new(0xdeadbeef);
-        for _ in 0..100_000 {
-            let mat = Mat2::new([
-                rng.gen_range::<f64>(-5.0, 5.0),
-                rng.gen_range::<f64>(-5.0, 5.0),
-                rng.gen_range::<f64>(-5.0, 5.0),
-                rng.gen_range::<f64>(-5.0, 5.0),
-            ]);
-
-            // Be careful here
-            if mat.det().abs() < 1.0e-10 {
-                continue;
-            }
-
-            let mm = mat * mat.invert() - identity;
-            assert!(mm.norm() < 1.0e-10);
-        }
     }
 
     #[test]
     fn test_mat3() {
-        fn matrix_delta<T>(mat1: Mat3<T>, mat2: Mat3<T>) -> T
-        where
-            T: num_traits::Float + std::iter::Sum + From<f32>,
-        {
-            return (mat1 - mat2).norm();
-        }
-
         // Test that you can construct a matrix using f32
         let m = Mat3::<f32>::identity();
         assert_eq!(m.det(), 1.0);
@@ -129,53 +132,6 @@ mod tests {
             ])
         );
 
-        // Test matrix inversion
-        let mut rng = soh_rng::RNG64::new(0xdeadbeef);
-        for _ in 0..100_000 {
-            let mat = Mat3::new([
-                rng.gen_range::<f64>(-5.0, 5.0),
-                rng.gen_range::<f64>(-5.0, 5.0),
-                rng.gen_range::<f64>(-5.0, 5.0),
-                rng.gen_range::<f64>(-5.0, 5.0),
-                rng.gen_range::<f64>(-5.0, 5.0),
-                rng.gen_range::<f64>(-5.0, 5.0),
-                rng.gen_range::<f64>(-5.0, 5.0),
-                rng.gen_range::<f64>(-5.0, 5.0),
-                rng.gen_range::<f64>(-5.0, 5.0),
-            ]);
-
-            // Be careful here
-            if mat.det().abs() < 1.0e-10 {
-                continue;
-            }
-
-            let mm = mat * mat.invert() - identity;
-            assert!(mm.norm() < 1.0e-10);
-
-            let mm = mat * mat.invert_no_det() / mat.det() - identity;
-            assert!(mm.norm() < 1.0e-10);
-        }
-
-        // Test rotation matrixes
-        for _ in 0..100_000 {
-            let yaw = rng.gen_to::<f64>(std::f64::consts::TAU);
-            let pitch = rng.gen_to::<f64>(std::f64::consts::TAU);
-            let roll = rng.gen_to::<f64>(std::f64::consts::TAU);
-
-            let m_yaw = Mat3::yaw(yaw);
-            let m_pitch = Mat3::pitch(pitch);
-            let m_roll = Mat3::roll(roll);
-
-            let m_yaw_pitch_roll = Mat3::yaw_pitch_roll(yaw, pitch, roll);
-
-            let (yaw2, pitch2, roll2) = m_yaw_pitch_roll.get_euler_angles();
-            let m_yaw_pitch_roll2 = Mat3::yaw_pitch_roll(yaw2, pitch2, roll2);
-
-            assert!(matrix_delta(m_yaw_pitch_roll, m_yaw_pitch_roll2) < 1.0e-3);
-
-            assert!(matrix_delta(m_yaw * m_pitch * m_roll, m_yaw_pitch_roll) < 1.0e-10);
-        }
-
         // Test LookAt matrixes
         let m = Mat3::look_at(
             Vec3::new(0.0, 0.0, -1.0),
@@ -184,53 +140,28 @@ mod tests {
         );
         assert_eq!(m, Mat3::identity());
 
-        // Test axis rotation
-        for _ in 0..100_000 {
-            let eps = 1.0e-10;
-            let angle = rng.gen_to(std::f64::consts::TAU);
-
-            // Test yaw
-            let m_yaw_1 = Mat3::yaw(angle);
-            let m_yaw_2 = Mat3::from_axis_angle(Vec3::new(0.0, 0.0, 1.0), angle);
-            assert!(matrix_delta(m_yaw_1, m_yaw_2) < eps);
-
-            // Test pitch
-            let m_pitch_1 = Mat3::pitch(angle);
-            let m_pitch_2 = Mat3::from_axis_angle(Vec3::new(0.0, 1.0, 0.0), angle);
-            assert!(matrix_delta(m_pitch_1, m_pitch_2) < eps);
-
-            // Test roll
-            let m_roll_1 = Mat3::roll(angle);
-            let m_roll_2 = Mat3::from_axis_angle(Vec3::new(1.0, 0.0, 0.0), angle);
-            assert!(matrix_delta(m_roll_1, m_roll_2) < eps);
-        }
-
-        for _ in 0..100_000 {
-            let axis: Vec3<f64> = Vec3::new(
-                rng.gen_range(-1.0, 1.0),
-                rng.gen_range(-1.0, 1.0),
-                rng.gen_range(-1.0, 1.0),
-            );
-
-            let angle = rng.gen_to(std::f64::consts::TAU);
-
-            let point: Vec3<f64> = Vec3::new(
-                rng.gen_range(-1.0, 1.0),
-                rng.gen_range(-1.0, 1.0),
-                rng.gen_range(-1.0, 1.0),
-            );
-
-            let q_rotation = Quaternion::from_axis_angle(axis, angle);
-
-            let m_rotation = Mat3::from_axis_angle(axis, angle);
-            let m_rotation_quat = Mat3::from_quat(q_rotation);
-
-            let p_1 = q_rotation.rotate(point);
-            let p_2 = m_rotation * point;
-
-            assert!((p_1 - p_2).len() < 1.0e-10);
-            assert!(matrix_delta(m_rotation, m_rotation_quat) < 1.0e-10);
-        }
+        // Test logarithm of the half turn
+        let half_turn = Mat3::roll(std::f64::consts::PI);
+        let generator = Mat3::skew(Vec3::new(std::f64::consts::PI, 0.0, 0.0));
+        assert!((half_turn.log() - generator).norm() < 1.0e-6
+            || (half_turn.log() + generator).norm() < 1.0e-6);
+        assert!((half_turn.log().exp() - half_turn).norm() < 1.0e-10);
+        assert_eq!(Mat3::<f64>::identity().log(), Mat3::scale(0.0));
+
+        // Test decompositions of degenerate matrices
+        let (values, _) = Mat3::<f64>::scale(2.0).symmetric_eigen();
+        assert_eq!(values, Vec3::new(2.0, 2.0, 2.0));
+
+        let rank_one = Mat3::from_cols([Vec3::new(1.0f64, 2.0, 3.0), Vec3::zero(), Vec3::new(2.0, 4.0, 6.0)]);
+        let (u, sigma, v) = rank_one.svd();
+        let diag = Mat3::new([sigma.x, 0.0, 0.0, 0.0, sigma.y, 0.0, 0.0, 0.0, sigma.z]);
+        assert!(sigma.y.abs() < 1.0e-6 && sigma.z.abs() < 1.0e-6);
+        assert!((u * u.t() - Mat3::identity()).norm() < 1.0e-10);
+        assert!((u * diag * v.t() - rank_one).norm() < 1.0e-6);
+
+        let (u, sigma, _) = Mat3::<f64>::scale(0.0).svd();
+        assert_eq!(sigma, Vec3::zero());
+        assert!((u * u.t() - Mat3::identity()).norm() < 1.0e-10);
     }
 
     #[test]
@@ -263,8 +194,6 @@ mod tests {
         ]);
         assert_eq!(m1 * m2, m1xm2);
 
-        let mut rng = soh_rng::RNG64::new(0xdeadbeef);
-
         // Test determinants
         let m = Mat4::from_rows([
             Vec4::new(3, 1, 4, 1),
@@ -274,49 +203,289 @@ mod tests {
         ]);
         assert_eq!(m.det(), 98);
 
-        for _ in 0..100_000 {
-            let factor = rng.gen_range::<f32>(-10.0, 10.0);
-
-            let m = Mat4::scale(factor);
-            assert!((m.det() - factor.powi(4)).abs() < 1.0e-3);
-        }
-
         // Test matrix inversion
         assert!(Mat4::<f32>::identity().invert() == Mat4::identity());
         assert!(Mat4::scale(2.0).invert() == Mat4::scale(0.5));
 
-        for _ in 0..100_000 {
-            let mat = Mat4::new([
-                rng.gen_range::<f64>(-5.0, 5.0),
-                rng.gen_range::<f64>(-5.0, 5.0),
-                rng.gen_range::<f64>(-5.0, 5.0),
-                rng.gen_range::<f64>(-5.0, 5.0),
-                rng.gen_range::<f64>(-5.0, 5.0),
-                rng.gen_range::<f64>(-5.0, 5.0),
-                rng.gen_range::<f64>(-5.0, 5.0),
-                rng.gen_range::<f64>(-5.0, 5.0),
-                rng.gen_range::<f64>(-5.0, 5.0),
-                rng.gen_range::<f64>(-5.0, 5.0),
-                rng.gen_range::<f64>(-5.0, 5.0),
-                rng.gen_range::<f64>(-5.0, 5.0),
-                rng.gen_range::<f64>(-5.0, 5.0),
-                rng.gen_range::<f64>(-5.0, 5.0),
-                rng.gen_range::<f64>(-5.0, 5.0),
-                rng.gen_range::<f64>(-5.0, 5.0),
-            ]);
-
-            // Be careful here
-            if mat.det().abs() < 1.0e-10 {
-                continue;
+        // Rotations match the 3x3 ones, then the translation is added
+        let axis = Vec3::new(1.0, -2.0, 0.5);
+        let angle = 0.7;
+        let offset = Vec3::new(3.0, 4.0, -5.0);
+        let p = Vec3::new(-1.5, 2.0, 6.0);
+
+        let m = Mat4::from_translation(offset) * Mat4::from_axis_angle(axis, angle);
+        let expected = Mat3::from_axis_angle(axis, angle) * p + offset;
+        let v = m * Vec4::new(p.x, p.y, p.z, 1.0);
+        assert!((Vec3::new(v.x, v.y, v.z) - expected).len() < 1.0e-12);
+        assert_eq!(v.w, 1.0);
+
+        let quat = Quaternion::from_axis_angle(axis, angle);
+        assert!((Mat4::from_quat(quat) - Mat4::from_axis_angle(axis, angle)).norm() < 1.0e-12);
+
+        // Orthographic projection maps the box to the Vulkan clip space
+        let ortho = Mat4::ortho(-2.0, 6.0, 1.0, 5.0, 0.5, 10.5);
+        assert_eq!(ortho * Vec4::new(-2.0, 1.0, 0.5, 1.0), Vec4::new(-1.0, -1.0, 0.0, 1.0));
+        assert_eq!(ortho * Vec4::new(6.0, 5.0, 10.5, 1.0), Vec4::new(1.0, 1.0, 1.0, 1.0));
+        assert_eq!(ortho * Vec4::new(2.0, 3.0, 5.5, 1.0), Vec4::new(0.0, 0.0, 0.5, 1.0));
+
+        // y down pixel coordinates: the top left corner is at -1, -1 in Vulkan
+        let screen = Mat4::ortho(0.0, 800.0, 0.0, 600.0, 0.0, 1.0);
+        assert_eq!(screen * Vec4::new(0.0, 0.0, 0.0, 1.0), Vec4::new(-1.0, -1.0, 0.0, 1.0));
+        assert_eq!(screen * Vec4::new(800.0, 600.0, 0.0, 1.0), Vec4::new(1.0, 1.0, 0.0, 1.0));
+    }
+
+    /*
+     * Properties checked on generated matrices
+     */
+    const EPS: f64 = 1.0e-8;
+
+    proptest! {
+        #[test]
+        fn prop_mat_assign_ops(
+            m2 in strategy::mat2(-5.0..5.0),
+            n2 in strategy::mat2(-5.0..5.0),
+            m3 in strategy::mat3(-5.0..5.0),
+            n3 in strategy::mat3(-5.0..5.0),
+            m4 in strategy::mat4(-5.0..5.0),
+            n4 in strategy::mat4(-5.0..5.0),
+            factor in 0.5..5.0,
+        ) {
+            macro_rules! check_assign_ops {
+                ($m:expr, $n:expr) => {{
+                    let (m, n) = ($m, $n);
+
+                    let mut res = m;
+                    res *= n;
+                    prop_assert_eq!(res, m * n);
+
+                    let mut res = m;
+                    res += n;
+                    prop_assert_eq!(res, m + n);
+
+                    let mut res = m;
+                    res -= n;
+                    prop_assert_eq!(res, m - n);
+
+                    let mut res = m;
+                    res *= factor;
+                    prop_assert_eq!(res, m * factor);
+                    prop_assert_eq!(factor * m, m * factor);
+
+                    let mut res = m;
+                    res /= factor;
+                    prop_assert_eq!(res, m / factor);
+                }};
             }
 
-            let mm = mat * mat.invert() - identity;
-            assert!(mm.norm() < 1.0e-10);
+            check_assign_ops!(m2, n2);
+            check_assign_ops!(m3, n3);
+            check_assign_ops!(m4, n4);
+        }
 
-            let mm = mat * mat.invert_no_det() / mat.det() - identity;
-            assert!(mm.norm() < 1.0e-10);
+        #[test]
+        fn prop_mat_index(m in strategy::mat4(-5.0..5.0), row in 0..4usize, col in 0..4usize) {
+            prop_assert_eq!(m[(row, col)], m.at(row, col));
+
+            let mut res = m;
+            res[(row, col)] = 10.0;
+            prop_assert_eq!(res.at(row, col), 10.0);
+            prop_assert_eq!(res.row(row)[col], 10.0);
+        }
+
+        #[test]
+        fn prop_mat_transform_slice(
+            m2 in strategy::mat2(-5.0..5.0),
+            m3 in strategy::mat3(-5.0..5.0),
+            m4 in strategy::mat4(-5.0..5.0),
+            points in proptest::collection::vec(strategy::vec4(-100.0..100.0), 0..2000),
+        ) {
+            let mut res2 = points.iter().map(|p| Vec2::new(p.x, p.y)).collect::<Vec<_>>();
+            let mut res3 = points.iter().map(|p| Vec3::new(p.x, p.y, p.z)).collect::<Vec<_>>();
+            let mut res4 = points.clone();
+            let mut res_points = res3.clone();
+            let mut res_vectors = res3.clone();
+
+            m2.transform_slice(&mut res2);
+            m3.transform_slice(&mut res3);
+            m4.transform_slice(&mut res4);
+            m4.transform_points(&mut res_points);
+            m4.transform_vectors(&mut res_vectors);
+
+            for (i, p) in points.iter().enumerate() {
+                prop_assert_eq!(res2[i], m2 * Vec2::new(p.x, p.y));
+                prop_assert_eq!(res3[i], m3 * Vec3::new(p.x, p.y, p.z));
+                prop_assert_eq!(res4[i], m4 * *p);
+
+                let point = m4 * Vec4::new(p.x, p.y, p.z, 1.0);
+                prop_assert_eq!(res_points[i], Vec3::new(point.x, point.y, point.z));
+
+                let vector = m4 * Vec4::new(p.x, p.y, p.z, 0.0);
+                prop_assert_eq!(res_vectors[i], Vec3::new(vector.x, vector.y, vector.z));
+            }
+        }
+
+        #[test]
+        fn prop_mat_mul_associative(
+            a in strategy::mat3(-5.0..5.0),
+            b in strategy::mat3(-5.0..5.0),
+            c in strategy::mat3(-5.0..5.0),
+        ) {
+            prop_assert!(((a * b) * c - a * (b * c)).norm() < EPS);
+        }
+
+        #[test]
+        fn prop_mat_det_multiplicative(
+            a in strategy::mat4(-5.0..5.0),
+            b in strategy::mat4(-5.0..5.0),
+        ) {
+            let det = (a * b).det();
+            prop_assert!((det - a.det() * b.det()).abs() < EPS * det.abs().max(1.0));
+        }
+
+        #[test]
+        fn prop_mat_invert(
+            m2 in strategy::invertible_mat2(-5.0..5.0),
+            m3 in strategy::invertible_mat3(-5.0..5.0),
+            m4 in strategy::invertible_mat4(-5.0..5.0),
+        ) {
+            prop_assert!((m2 * m2.invert() - Mat2::identity()).norm() < EPS);
+
+            prop_assert!((m3 * m3.invert() - Mat3::identity()).norm() < EPS);
+            prop_assert!((m3 * m3.invert_no_det() / m3.det() - Mat3::identity()).norm() < EPS);
+
+            prop_assert!((m4 * m4.invert() - Mat4::identity()).norm() < EPS);
+            prop_assert!((m4 * m4.invert_no_det() / m4.det() - Mat4::identity()).norm() < EPS);
+        }
+
+        #[test]
+        fn prop_mat4_scale_det(factor in -10.0f32..10.0) {
+            prop_assert!((Mat4::scale(factor).det() - factor.powi(4)).abs() < 1.0e-3);
+        }
+
+        #[test]
+        fn prop_rotation_orthonormal(m in strategy::rotation_mat3()) {
+            prop_assert!((m * m.t() - Mat3::identity()).norm() < EPS);
+            prop_assert!((m.det() - 1.0).abs() < EPS);
+        }
+
+        #[test]
+        fn prop_euler_angles(
+            yaw in strategy::angle(),
+            pitch in strategy::angle(),
+            roll in strategy::angle(),
+        ) {
+            let m_yaw_pitch_roll = Mat3::yaw_pitch_roll(yaw, pitch, roll);
+            prop_assert!((Mat3::yaw(yaw) * Mat3::pitch(pitch) * Mat3::roll(roll) - m_yaw_pitch_roll).norm() < EPS);
+
+            let (yaw2, pitch2, roll2) = m_yaw_pitch_roll.get_euler_angles();
+            prop_assert!((Mat3::yaw_pitch_roll(yaw2, pitch2, roll2) - m_yaw_pitch_roll).norm() < 1.0e-3);
+        }
+
+        #[test]
+        fn prop_axis_rotation(angle in strategy::angle()) {
+            prop_assert!((Mat3::yaw(angle) - Mat3::from_axis_angle(Vec3::new(0.0, 0.0, 1.0), angle)).norm() < EPS);
+            prop_assert!((Mat3::pitch(angle) - Mat3::from_axis_angle(Vec3::new(0.0, 1.0, 0.0), angle)).norm() < EPS);
+            prop_assert!((Mat3::roll(angle) - Mat3::from_axis_angle(Vec3::new(1.0, 0.0, 0.0), angle)).norm() < EPS);
+        }
+
+        #[test]
+        fn prop_rotation_exp_log(
+            axis in strategy::unit_vec3(),
+            angle in 0.0..std::f64::consts::PI,
+        ) {
+            let generator = Mat3::skew(axis * angle);
+            let m = Mat3::from_axis_angle(axis, angle);
+
+            prop_assert!((generator.exp() - m).norm() < EPS);
+            prop_assert!((m.log() - generator).norm() < 1.0e-6);
+            prop_assert!((m.log().exp() - m).norm() < 1.0e-6);
+        }
+
+        #[test]
+        fn prop_rotation_scaled_axis(
+            axis in strategy::unit_vec3(),
+            angle in 0.0..std::f64::consts::PI,
+        ) {
+            let m = Mat3::from_axis_angle(axis, angle);
+            let v = axis * angle;
+
+            prop_assert!((Mat3::from_scaled_axis(v) - m).norm() < EPS);
+            prop_assert!((m.to_axis_angle_vec() - v).len() < 1.0e-6);
+        }
+
+        #[test]
+        fn prop_rotation_slerp(
+            axis in strategy::unit_vec3(),
+            angle in 0.0..3.0,
+            t in 0.0..1.0,
+            start in strategy::rotation_mat3(),
+        ) {
+            let end = start * Mat3::from_axis_angle(axis, angle);
+
+            prop_assert!((Mat3::slerp(start, end, 0.0) - start).norm() < 1.0e-6);
+            prop_assert!((Mat3::slerp(start, end, 1.0) - end).norm() < 1.0e-6);
+
+            // Same as the quaternion interpolation along the axis
+            let expected = start * Mat3::from_axis_angle(axis, angle * t);
+            prop_assert!((Mat3::slerp(start, end, t) - expected).norm() < 1.0e-6);
+        }
+
+        #[test]
+        fn prop_symmetric_eigen(m2 in strategy::mat2(-5.0..5.0), m3 in strategy::mat3(-5.0..5.0)) {
+            let s2 = m2 + m2.t();
+            let (values, v) = s2.symmetric_eigen();
+            let diag = Mat2::new([values.x, 0.0, 0.0, values.y]);
+            prop_assert!(values.x >= values.y);
+            prop_assert!((v * v.t() - Mat2::identity()).norm() < EPS);
+            prop_assert!((v * diag * v.t() - s2).norm() < EPS);
+
+            let s3 = m3 + m3.t();
+            let (values, v) = s3.symmetric_eigen();
+            let diag = Mat3::new([values.x, 0.0, 0.0, 0.0, values.y, 0.0, 0.0, 0.0, values.z]);
+            prop_assert!(values.x >= values.y && values.y >= values.z);
+            prop_assert!((v * v.t() - Mat3::identity()).norm() < EPS);
+            prop_assert!((v.det() - 1.0).abs() < EPS);
+            prop_assert!((v * diag * v.t() - s3).norm() < EPS);
+        }
+
+        #[test]
+        fn prop_svd(m2 in strategy::mat2(-5.0..5.0), m3 in strategy::mat3(-5.0..5.0)) {
+            let (u, sigma, v) = m2.svd();
+            let diag = Mat2::new([sigma.x, 0.0, 0.0, sigma.y]);
+            prop_assert!(sigma.x >= sigma.y && sigma.y >= 0.0);
+            prop_assert!((u * u.t() - Mat2::identity()).norm() < EPS);
+            prop_assert!((u * diag * v.t() - m2).norm() < 1.0e-6);
+
+            let (u, sigma, v) = m3.svd();
+            let diag = Mat3::new([sigma.x, 0.0, 0.0, 0.0, sigma.y, 0.0, 0.0, 0.0, sigma.z]);
+            prop_assert!(sigma.x >= sigma.y && sigma.y >= sigma.z && sigma.z >= 0.0);
+            prop_assert!((u * u.t() - Mat3::identity()).norm() < EPS);
+            prop_assert!((u * diag * v.t() - m3).norm() < 1.0e-6);
+        }
+
+        #[test]
+        fn prop_polar(m in strategy::invertible_mat3(-5.0..5.0)) {
+            let (r, s) = m.polar();
+            prop_assert!((r * r.t() - Mat3::identity()).norm() < EPS);
+            prop_assert!((s - s.t()).norm() < EPS);
+            prop_assert!((r * s - m).norm() < 1.0e-6);
+            prop_assert!(r.det() * m.det() > 0.0);
+        }
+
+        #[test]
+        fn prop_quat_matrix(
+            axis in strategy::unit_vec3(),
+            angle in strategy::angle(),
+            point in strategy::vec3(-1.0..1.0),
+        ) {
+            let q_rotation = Quaternion::from_axis_angle(axis, angle);
+            let m_rotation = Mat3::from_axis_angle(axis, angle);
+
+            prop_assert!((q_rotation.rotate(point) - m_rotation * point).len() < EPS);
+            prop_assert!((Mat3::from_quat(q_rotation) - m_rotation).norm() < EPS);
         }
     }
+
 }
 
 //-----------------------------------------------------------------------------
@@ -0,0 +1,28 @@
+//-----------------------------------------------------------------------------
+// Scalar on the left side of the product ( `2.0 * m` )
+//
+// Traits of a foreign type ( the primitive ) can't be implemented for any `T`, so these are
+// implemented for each primitive type.
+//-----------------------------------------------------------------------------
+use crate::{Mat2, Mat3, Mat4};
+//-----------------------------------------------------------------------------
+
+macro_rules! impl_scalar_ops {
+    ($mat:ident, $($t:ty)*) => {
+        $(
+            impl std::ops::Mul<$mat<$t>> for $t {
+                type Output = $mat<$t>;
+
+                fn mul(self, rhs: $mat<$t>) -> Self::Output {
+                    return rhs * self;
+                }
+            }
+        )*
+    };
+}
+
+impl_scalar_ops!(Mat2, i8 i16 i32 i64 u8 u16 u32 u64 f32 f64);
+impl_scalar_ops!(Mat3, i8 i16 i32 i64 u8 u16 u32 u64 f32 f64);
+impl_scalar_ops!(Mat4, i8 i16 i32 i64 u8 u16 u32 u64 f32 f64);
+
+//-----------------------------------------------------------------------------
@@ -0,0 +1,318 @@
+//-----------------------------------------------------------------------------
+// Procedural 2D noise
+//
+// Every noise is seeded by a `u64`, so a seed can be drawn from any soh_rng engine
+// ( `ValueNoise::new(rng.next().into())` )
+//-----------------------------------------------------------------------------
+use crate::Vec2;
+//-----------------------------------------------------------------------------
+
+/// Noise sampled at points of the plane
+pub trait Noise2 {
+    fn get(&self, p: Vec2<f64>) -> f64;
+}
+
+/// Value noise: random values on the integer lattice smoothly interpolated in [-1, 1]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ValueNoise {
+    seed: u64,
+}
+
+/// Distance used to find the nearest feature points of [Worley] noise
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DistanceMetric {
+    #[default]
+    Euclidean,
+    Manhattan,
+    Chebyshev,
+}
+
+/// Worley ( cellular ) noise: distances to the closest feature points, one feature point is
+/// placed randomly in every cell of the integer lattice
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Worley {
+    seed: u64,
+    metric: DistanceMetric,
+}
+
+/// Octaves summed by [fbm] and [ridged]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Octaves {
+    pub count: u32,
+    /// Frequency multiplier between the octaves
+    pub lacunarity: f64,
+    /// Amplitude multiplier between the octaves
+    pub persistence: f64,
+}
+
+//-----------------------------------------------------------------------------
+
+/// Hash a lattice point ( splitmix64 finalizer applied per coordinate )
+fn hash(seed: u64, x: i64, y: i64) -> u64 {
+    fn mix(mut h: u64) -> u64 {
+        h ^= h >> 30;
+        h = h.wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        h ^= h >> 27;
+        h = h.wrapping_mul(0x94d0_49bb_1331_11eb);
+        h ^= h >> 31;
+        return h;
+    }
+
+    let h = mix(seed ^ (x as u64).wrapping_mul(0x9e37_79b9_7f4a_7c15));
+    return mix(h ^ (y as u64).wrapping_mul(0xc2b2_ae3d_27d4_eb4f));
+}
+
+/// Map a hash to [0, 1)
+fn unit(h: u64) -> f64 {
+    return (h >> 11) as f64 / (1u64 << 53) as f64;
+}
+
+/// Quintic fade curve ( zero first and second derivatives at 0 and 1 )
+fn fade(t: f64) -> f64 {
+    return t * t * t * (t * (t * 6.0 - 15.0) + 10.0);
+}
+
+//-----------------------------------------------------------------------------
+// Value noise
+
+impl ValueNoise {
+    pub const fn new(seed: u64) -> Self {
+        return ValueNoise { seed };
+    }
+
+    fn lattice(&self, x: i64, y: i64) -> f64 {
+        return unit(hash(self.seed, x, y)) * 2.0 - 1.0;
+    }
+}
+
+impl Noise2 for ValueNoise {
+    fn get(&self, p: Vec2<f64>) -> f64 {
+        let (x0, y0) = (p.x.floor(), p.y.floor());
+        let (tx, ty) = (fade(p.x - x0), fade(p.y - y0));
+        let (x0, y0) = (x0 as i64, y0 as i64);
+
+        let bottom = crate::lerp(self.lattice(x0, y0), self.lattice(x0 + 1, y0), tx);
+        let top = crate::lerp(self.lattice(x0, y0 + 1), self.lattice(x0 + 1, y0 + 1), tx);
+
+        return crate::lerp(bottom, top, ty);
+    }
+}
+
+//-----------------------------------------------------------------------------
+// Worley noise
+
+impl DistanceMetric {
+    pub fn distance(self, a: Vec2<f64>, b: Vec2<f64>) -> f64 {
+        let d = a - b;
+
+        return match self {
+            DistanceMetric::Euclidean => d.len(),
+            DistanceMetric::Manhattan => d.x.abs() + d.y.abs(),
+            DistanceMetric::Chebyshev => d.x.abs().max(d.y.abs()),
+        };
+    }
+}
+
+impl Worley {
+    pub const fn new(seed: u64, metric: DistanceMetric) -> Self {
+        return Worley { seed, metric };
+    }
+
+    /// Distances to the closest ( F1 ) and the second closest ( F2 ) feature points
+    pub fn features(&self, p: Vec2<f64>) -> (f64, f64) {
+        let (cx, cy) = (p.x.floor() as i64, p.y.floor() as i64);
+
+        let mut f1 = f64::INFINITY;
+        let mut f2 = f64::INFINITY;
+
+        // The two closest feature points are at most two cells away
+        for y in cy - 2..=cy + 2 {
+            for x in cx - 2..=cx + 2 {
+                let h = hash(self.seed, x, y);
+                let feature = Vec2::new(x as f64 + unit(h), y as f64 + unit(h.rotate_left(32)));
+
+                let d = self.metric.distance(p, feature);
+                if d < f1 {
+                    f2 = f1;
+                    f1 = d;
+                } else if d < f2 {
+                    f2 = d;
+                }
+            }
+        }
+
+        return (f1, f2);
+    }
+}
+
+impl Noise2 for Worley {
+    /// Distance to the closest feature point ( F1 )
+    fn get(&self, p: Vec2<f64>) -> f64 {
+        return self.features(p).0;
+    }
+}
+
+//-----------------------------------------------------------------------------
+// Combinators
+
+impl Default for Octaves {
+    fn default() -> Self {
+        return Octaves {
+            count: 5,
+            lacunarity: 2.0,
+            persistence: 0.5,
+        };
+    }
+}
+
+/// Fractal brownian motion: octaves of `noise` summed and normalized to its range
+pub fn fbm(noise: &impl Noise2, p: Vec2<f64>, octaves: Octaves) -> f64 {
+    let mut sum = 0.0;
+    let mut norm = 0.0;
+    let mut frequency = 1.0;
+    let mut amplitude = 1.0;
+
+    for _ in 0..octaves.count {
+        sum += noise.get(p * frequency) * amplitude;
+        norm += amplitude;
+
+        frequency *= octaves.lacunarity;
+        amplitude *= octaves.persistence;
+    }
+
+    return if norm > 0.0 { sum / norm } else { 0.0 };
+}
+
+/// Ridged multifractal in [0, 1]: sharp ridges where `noise` ( in [-1, 1] ) crosses zero, the
+/// finer octaves are weighted by the coarser ones so the valleys stay smooth
+pub fn ridged(noise: &impl Noise2, p: Vec2<f64>, octaves: Octaves) -> f64 {
+    let mut sum = 0.0;
+    let mut norm = 0.0;
+    let mut frequency = 1.0;
+    let mut amplitude = 1.0;
+    let mut weight = 1.0;
+
+    for _ in 0..octaves.count {
+        let ridge = 1.0 - noise.get(p * frequency).abs();
+        let signal = ridge * ridge * weight;
+
+        sum += signal * amplitude;
+        norm += amplitude;
+        weight = (signal * 2.0).clamp(0.0, 1.0);
+
+        frequency *= octaves.lacunarity;
+        amplitude *= octaves.persistence;
+    }
+
+    return if norm > 0.0 { sum / norm } else { 0.0 };
+}
+
+/// Domain warping: offset `p` by `strength` times a vector made of two decorrelated samples of
+/// `noise`, sample another noise at the returned point
+pub fn warp(noise: &impl Noise2, p: Vec2<f64>, strength: f64) -> Vec2<f64> {
+    // Arbitrary far away offset decorrelates the second coordinate
+    let offset = Vec2::new(5.2, 1.3) * 17.0;
+    let delta = Vec2::new(noise.get(p), noise.get(p + offset));
+
+    return p + delta * strength;
+}
+
+//-----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::strategy;
+    use proptest::prelude::*;
+
+    const METRICS: [DistanceMetric; 3] = [
+        DistanceMetric::Euclidean,
+        DistanceMetric::Manhattan,
+        DistanceMetric::Chebyshev,
+    ];
+
+    #[test]
+    fn test_seed() {
+        let p = Vec2::new(0.3, 7.6);
+
+        assert_eq!(ValueNoise::new(1).get(p), ValueNoise::new(1).get(p));
+        assert_ne!(ValueNoise::new(1).get(p), ValueNoise::new(2).get(p));
+
+        let w = |seed| Worley::new(seed, DistanceMetric::Euclidean).get(p);
+        assert_eq!(w(1), w(1));
+        assert_ne!(w(1), w(2));
+    }
+
+    #[test]
+    fn test_value_noise_lattice() {
+        let noise = ValueNoise::new(0xdeadbeef);
+
+        for (x, y) in [(0, 0), (-3, 5), (10, -7)] {
+            let p = Vec2::new(x as f64, y as f64);
+            assert_eq!(noise.get(p), noise.lattice(x, y));
+        }
+    }
+
+    #[test]
+    fn test_octaves() {
+        let noise = ValueNoise::new(0xdeadbeef);
+        let p = Vec2::new(1.25, -0.5);
+
+        let single = Octaves {
+            count: 1,
+            ..Default::default()
+        };
+        assert_eq!(fbm(&noise, p, single), noise.get(p));
+
+        let none = Octaves {
+            count: 0,
+            ..Default::default()
+        };
+        assert_eq!(fbm(&noise, p, none), 0.0);
+        assert_eq!(ridged(&noise, p, none), 0.0);
+    }
+
+    proptest! {
+        #[test]
+        fn prop_value_noise_range(seed: u64, p in strategy::vec2(-100.0..100.0)) {
+            let noise = ValueNoise::new(seed);
+
+            prop_assert!((-1.0..=1.0).contains(&noise.get(p)));
+            prop_assert!((-1.0..=1.0).contains(&fbm(&noise, p, Octaves::default())));
+            prop_assert!((0.0..=1.0).contains(&ridged(&noise, p, Octaves::default())));
+        }
+
+        #[test]
+        fn prop_value_noise_continuous(seed: u64, p in strategy::vec2(-100.0..100.0)) {
+            let noise = ValueNoise::new(seed);
+            let dp = Vec2::new(1.0e-6, -1.0e-6);
+
+            prop_assert!((noise.get(p) - noise.get(p + dp)).abs() < 1.0e-4);
+        }
+
+        #[test]
+        fn prop_worley(seed: u64, p in strategy::vec2(-100.0..100.0)) {
+            for metric in METRICS {
+                let noise = Worley::new(seed, metric);
+                let (f1, f2) = noise.features(p);
+
+                prop_assert!(0.0 <= f1 && f1 <= f2);
+                // The feature point of the own cell is always close
+                prop_assert!(f1 <= metric.distance(Vec2::zero(), Vec2::new(1.0, 1.0)));
+
+                let dp = Vec2::new(1.0e-6, -1.0e-6);
+                prop_assert!((noise.get(p) - noise.get(p + dp)).abs() < 1.0e-5);
+            }
+        }
+
+        #[test]
+        fn prop_warp(seed: u64, p in strategy::vec2(-100.0..100.0), strength in 0.0..4.0) {
+            let noise = ValueNoise::new(seed);
+            prop_assert!(warp(&noise, p, strength).distance(&p) <= strength * 2.0f64.sqrt() + 1.0e-9);
+        }
+    }
+}
+
+//-----------------------------------------------------------------------------
@@ -0,0 +1,223 @@
+//-----------------------------------------------------------------------------
+//! Packed GPU formats for compressing vertex attributes and colors
+//!
+//! Each type is `#[repr(transparent)]` over its packed data, so it can be put directly into a
+//! vertex (soh_vk maps them to the matching `UNORM`/`SNORM` formats).
+//!
+//! * [`Unorm8x4`] - 4 values in [0, 1] as bytes ( `R8G8B8A8_UNORM` )
+//! * [`Snorm16x2`] - 2 values in [-1, 1] as `i16` ( `R16G16_SNORM` ), also used for
+//!   octahedral encoded normals
+//! * [`Snorm16x4`] - 4 values in [-1, 1] as `i16` ( `R16G16B16A16_SNORM` )
+//! * [`Rgb10A2`] - 10 bits per color and 2 bits of alpha ( `A2B10G10R10_UNORM_PACK32` )
+//-----------------------------------------------------------------------------
+use crate::{Vec2, Vec3, Vec4};
+//-----------------------------------------------------------------------------
+
+#[repr(transparent)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Default, Clone, Copy, Hash, PartialEq, Eq)]
+pub struct Unorm8x4(pub [u8; 4]);
+
+#[repr(transparent)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Default, Clone, Copy, Hash, PartialEq, Eq)]
+pub struct Snorm16x2(pub [i16; 2]);
+
+#[repr(transparent)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Default, Clone, Copy, Hash, PartialEq, Eq)]
+pub struct Snorm16x4(pub [i16; 4]);
+
+/// Red in the lowest 10 bits, then green, blue and alpha in the highest 2 bits
+#[repr(transparent)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Default, Clone, Copy, Hash, PartialEq, Eq)]
+pub struct Rgb10A2(pub u32);
+
+//-----------------------------------------------------------------------------
+// Scalar conversions ( rounding to the nearest value like the GPU does )
+
+/// Encode a value in [0, 1] using `max` as 1.0
+fn unorm(value: f32, max: u32) -> u32 {
+    return (value.clamp(0.0, 1.0) * max as f32).round() as u32;
+}
+
+fn from_unorm(value: u32, max: u32) -> f32 {
+    return value as f32 / max as f32;
+}
+
+/// Encode a value in [-1, 1]
+fn snorm16(value: f32) -> i16 {
+    return (value.clamp(-1.0, 1.0) * i16::MAX as f32).round() as i16;
+}
+
+/// Both -32768 and -32767 decode to -1.0
+fn from_snorm16(value: i16) -> f32 {
+    return (value as f32 / i16::MAX as f32).max(-1.0);
+}
+
+//-----------------------------------------------------------------------------
+
+impl Unorm8x4 {
+    /// Encode 4 values in [0, 1] ( values outside are clamped )
+    pub fn encode(v: Vec4<f32>) -> Self {
+        let b = |value: f32| unorm(value, u8::MAX as u32) as u8;
+        return Unorm8x4([b(v.x), b(v.y), b(v.z), b(v.w)]);
+    }
+
+    pub fn decode(&self) -> Vec4<f32> {
+        let f = |value: u8| from_unorm(value as u32, u8::MAX as u32);
+        let [x, y, z, w] = self.0;
+        return Vec4::new(f(x), f(y), f(z), f(w));
+    }
+}
+
+impl Snorm16x2 {
+    /// Encode 2 values in [-1, 1] ( values outside are clamped )
+    pub fn encode(v: Vec2<f32>) -> Self {
+        return Snorm16x2([snorm16(v.x), snorm16(v.y)]);
+    }
+
+    pub fn decode(&self) -> Vec2<f32> {
+        return Vec2::new(from_snorm16(self.0[0]), from_snorm16(self.0[1]));
+    }
+
+    /// Encode a unit vector by projecting it onto an octahedron unfolded into a square
+    ///
+    /// source:
+    /// <https://knarkowicz.wordpress.com/2014/04/16/octahedron-normal-vector-encoding/>
+    pub fn encode_octahedral(normal: Vec3<f32>) -> Self {
+        let n = normal / (normal.x.abs() + normal.y.abs() + normal.z.abs());
+
+        // The lower half is folded over the diagonals
+        let mut p = Vec2::new(n.x, n.y);
+        if n.z < 0.0 {
+            p = Vec2::new(
+                (1.0 - n.y.abs()) * n.x.signum(),
+                (1.0 - n.x.abs()) * n.y.signum(),
+            );
+        }
+
+        return Self::encode(p);
+    }
+
+    /// Decode a unit vector encoded with [`Snorm16x2::encode_octahedral`]
+    pub fn decode_octahedral(&self) -> Vec3<f32> {
+        let p = self.decode();
+        let z = 1.0 - p.x.abs() - p.y.abs();
+
+        // Unfold the lower half
+        let t = (-z).max(0.0);
+        let x = p.x - t * p.x.signum();
+        let y = p.y - t * p.y.signum();
+
+        return Vec3::new(x, y, z).normalized();
+    }
+}
+
+impl Snorm16x4 {
+    /// Encode 4 values in [-1, 1] ( values outside are clamped )
+    pub fn encode(v: Vec4<f32>) -> Self {
+        return Snorm16x4([snorm16(v.x), snorm16(v.y), snorm16(v.z), snorm16(v.w)]);
+    }
+
+    pub fn decode(&self) -> Vec4<f32> {
+        let [x, y, z, w] = self.0.map(from_snorm16);
+        return Vec4::new(x, y, z, w);
+    }
+}
+
+impl Rgb10A2 {
+    const COLOR_MAX: u32 = (1 << 10) - 1;
+    const ALPHA_MAX: u32 = (1 << 2) - 1;
+
+    /// Encode a color and alpha in [0, 1] ( values outside are clamped )
+    pub fn encode(v: Vec4<f32>) -> Self {
+        let r = unorm(v.x, Self::COLOR_MAX);
+        let g = unorm(v.y, Self::COLOR_MAX);
+        let b = unorm(v.z, Self::COLOR_MAX);
+        let a = unorm(v.w, Self::ALPHA_MAX);
+
+        return Rgb10A2(r | g << 10 | b << 20 | a << 30);
+    }
+
+    /// Encode an opaque color
+    pub fn encode_rgb(v: Vec3<f32>) -> Self {
+        return Self::encode(Vec4::new(v.x, v.y, v.z, 1.0));
+    }
+
+    pub fn decode(&self) -> Vec4<f32> {
+        let color = |shift: u32| from_unorm(self.0 >> shift & Self::COLOR_MAX, Self::COLOR_MAX);
+        let alpha = from_unorm(self.0 >> 30, Self::ALPHA_MAX);
+
+        return Vec4::new(color(0), color(10), color(20), alpha);
+    }
+
+    pub fn decode_rgb(&self) -> Vec3<f32> {
+        let v = self.decode();
+        return Vec3::new(v.x, v.y, v.z);
+    }
+}
+
+//-----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use proptest::prelude::*;
+
+    fn max_error(a: Vec4<f32>, b: Vec4<f32>) -> f32 {
+        let d = a - b;
+        return d.x.abs().max(d.y.abs()).max(d.z.abs()).max(d.w.abs());
+    }
+
+    #[test]
+    fn test_pack() {
+        assert_eq!(
+            Unorm8x4::encode(Vec4::new(0.0, 1.0, 0.5, 2.0)),
+            Unorm8x4([0, 255, 128, 255])
+        );
+        assert_eq!(
+            Snorm16x2::encode(Vec2::new(-1.0, 1.0)),
+            Snorm16x2([-32767, 32767])
+        );
+        assert_eq!(Snorm16x2([i16::MIN, 0]).decode(), Vec2::new(-1.0, 0.0));
+
+        assert_eq!(
+            Rgb10A2::encode_rgb(Vec3::new(1.0, 0.0, 1.0)).0,
+            0xC000_0000 | 0x3FF << 20 | 0x3FF
+        );
+        assert_eq!(Rgb10A2(u32::MAX).decode(), Vec4::new(1.0, 1.0, 1.0, 1.0));
+
+        // Axes survive the octahedral encoding exactly
+        for axis in [Vec3::X, Vec3::Y, Vec3::Z, -Vec3::X, -Vec3::Y, -Vec3::Z] {
+            assert_eq!(Snorm16x2::encode_octahedral(axis).decode_octahedral(), axis);
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn prop_pack_round_trip(v in crate::strategy::vec4(0.0..1.0)) {
+            let v = v.map(|x| x as f32);
+
+            prop_assert!(max_error(Unorm8x4::encode(v).decode(), v) <= 0.5 / 255.0 + 1.0e-6);
+
+            let signed = v * 2.0 - Vec4::one();
+            prop_assert!(max_error(Snorm16x4::encode(signed).decode(), signed) <= 0.5 / 32767.0 + 1.0e-6);
+
+            let rgb = Vec4::new(v.x, v.y, v.z, 1.0);
+            prop_assert!(max_error(Rgb10A2::encode(rgb).decode(), rgb) <= 0.5 / 1023.0 + 1.0e-6);
+            prop_assert!((Rgb10A2::encode(v).decode().w - v.w).abs() <= 0.5 / 3.0 + 1.0e-6);
+        }
+
+        #[test]
+        fn prop_octahedral_round_trip(normal in crate::strategy::unit_vec3()) {
+            let normal = normal.map(|x| x as f32);
+            let decoded = Snorm16x2::encode_octahedral(normal).decode_octahedral();
+
+            prop_assert!((decoded - normal).len() < 1.0e-4);
+        }
+    }
+}
+
+//-----------------------------------------------------------------------------
@@ -0,0 +1,291 @@
+//-----------------------------------------------------------------------------
+//! Parsing math values from strings ( config files, command line arguments )
+//!
+//! Values are separated by commas, semicolons or whitespace and may be enclosed in a pair of
+//! parentheses or brackets: `"1,2,3"`, `"(1 2 3)"`, `"[1, 2, 3]"`.
+//!
+//! * Matrices are written row by row: `"1 2; 3 4"`
+//! * [`Complex`] also accepts its `Display` form: `"1 - 2i"`
+//! * [`Rgb`] also accepts hex: `"#ff8000"`
+//-----------------------------------------------------------------------------
+use crate::color::Rgb;
+use crate::{Complex, Mat2, Mat3, Mat4, Vec2, Vec3, Vec4};
+use std::str::FromStr;
+//-----------------------------------------------------------------------------
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseError {
+    /// A value couldn't be parsed as a number
+    InvalidNumber(String),
+    /// Wrong number of values
+    WrongCount { expected: usize, found: usize },
+}
+
+impl std::fmt::Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        return match self {
+            ParseError::InvalidNumber(value) => write!(f, "invalid number \"{value}\""),
+            ParseError::WrongCount { expected, found } => {
+                write!(f, "expected {expected} values, found {found}")
+            }
+        };
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+//-----------------------------------------------------------------------------
+
+/// Strip a pair of enclosing parentheses or brackets
+fn strip_brackets(s: &str) -> &str {
+    let s = s.trim();
+
+    for (open, close) in [('(', ')'), ('[', ']')] {
+        if let Some(inner) = s.strip_prefix(open).and_then(|s| s.strip_suffix(close)) {
+            return inner;
+        }
+    }
+
+    return s;
+}
+
+/// Parse exactly `N` values
+fn parse_values<T: FromStr, const N: usize>(s: &str) -> Result<[T; N], ParseError> {
+    let tokens = strip_brackets(s)
+        .split(|c: char| c == ',' || c == ';' || c.is_whitespace())
+        .filter(|token| !token.is_empty())
+        .collect::<Vec<_>>();
+
+    if tokens.len() != N {
+        return Err(ParseError::WrongCount {
+            expected: N,
+            found: tokens.len(),
+        });
+    }
+
+    let values = tokens
+        .iter()
+        .map(|token| {
+            return token
+                .parse()
+                .map_err(|_| ParseError::InvalidNumber(token.to_string()));
+        })
+        .collect::<Result<Vec<T>, _>>()?;
+
+    return Ok(values.try_into().ok().unwrap());
+}
+
+/// Reorder values written row by row into column major order
+fn transpose<T: Copy, const N: usize, const SIZE: usize>(rows: [T; SIZE]) -> [T; SIZE] {
+    return std::array::from_fn(|i| rows[(i % N) * N + i / N]);
+}
+
+//-----------------------------------------------------------------------------
+// Vectors
+
+impl<T: FromStr> FromStr for Vec2<T> {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let [x, y] = parse_values(s)?;
+        return Ok(Vec2 { x, y });
+    }
+}
+
+impl<T: FromStr> FromStr for Vec3<T> {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let [x, y, z] = parse_values(s)?;
+        return Ok(Vec3 { x, y, z });
+    }
+}
+
+impl<T: FromStr> FromStr for Vec4<T> {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let [x, y, z, w] = parse_values(s)?;
+        return Ok(Vec4 { x, y, z, w });
+    }
+}
+
+//-----------------------------------------------------------------------------
+// Matrices
+
+impl<T: FromStr + Copy> FromStr for Mat2<T> {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        return Ok(Mat2(transpose::<T, 2, 4>(parse_values(s)?)));
+    }
+}
+
+impl<T: FromStr + Copy> FromStr for Mat3<T> {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        return Ok(Mat3(transpose::<T, 3, 9>(parse_values(s)?)));
+    }
+}
+
+impl<T: FromStr + Copy> FromStr for Mat4<T> {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        return Ok(Mat4(transpose::<T, 4, 16>(parse_values(s)?)));
+    }
+}
+
+//-----------------------------------------------------------------------------
+// Complex numbers
+
+impl<T> FromStr for Complex<T>
+where
+    T: FromStr + num_traits::Zero,
+{
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let compact = strip_brackets(s)
+            .chars()
+            .filter(|c| !c.is_whitespace())
+            .collect::<String>();
+
+        let Some(rest) = compact.strip_suffix('i') else {
+            let [re, im] = parse_values(s)?;
+            return Ok(Complex { re, im });
+        };
+
+        let parse = |token: &str| {
+            return token
+                .parse()
+                .map_err(|_| ParseError::InvalidNumber(token.to_string()));
+        };
+
+        /*
+         * `re + im i` form, the sign of the imaginary part isn't the leading one or an exponent's
+         */
+        let bytes = rest.as_bytes();
+        let split = (1..bytes.len()).rev().find(|&i| {
+            return (bytes[i] == b'+' || bytes[i] == b'-')
+                && !bytes[i - 1].eq_ignore_ascii_case(&b'e');
+        });
+
+        let (re, im) = match split {
+            Some(i) => (parse(&rest[..i])?, &rest[i..]),
+            None => (T::zero(), rest),
+        };
+
+        // `i` alone is a unit
+        let im = match im {
+            "" | "+" => "1",
+            "-" => "-1",
+            im => im.strip_prefix('+').unwrap_or(im),
+        };
+
+        return Ok(Complex { re, im: parse(im)? });
+    }
+}
+
+//-----------------------------------------------------------------------------
+// Colors
+
+impl FromStr for Rgb {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+
+        let Some(hex) = s.strip_prefix('#') else {
+            let [r, g, b] = parse_values(s)?;
+            return Ok(Rgb { r, g, b });
+        };
+
+        if hex.len() != 6 || !hex.is_ascii() {
+            return Err(ParseError::InvalidNumber(s.to_string()));
+        }
+
+        let [r, g, b] = [0, 2, 4].map(|i| u8::from_str_radix(&hex[i..i + 2], 16));
+        return match (r, g, b) {
+            (Ok(r), Ok(g), Ok(b)) => Ok(Rgb { r, g, b }),
+            _ => Err(ParseError::InvalidNumber(s.to_string())),
+        };
+    }
+}
+
+//-----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_vec() {
+        let v = Vec3::new(1.0, 2.0, 3.0);
+
+        assert_eq!("1,2,3".parse(), Ok(v));
+        assert_eq!("(1 2 3)".parse(), Ok(v));
+        assert_eq!(" [1, 2,  3] ".parse(), Ok(v));
+        assert_eq!("1 -2".parse(), Ok(Vec2::new(1, -2)));
+        assert_eq!("(1, 2, 3, 4e1)".parse(), Ok(Vec4::new(1.0, 2.0, 3.0, 40.0)));
+
+        assert_eq!(
+            "1,2".parse::<Vec3<f32>>(),
+            Err(ParseError::WrongCount {
+                expected: 3,
+                found: 2
+            })
+        );
+        assert_eq!(
+            "1,x".parse::<Vec2<f32>>(),
+            Err(ParseError::InvalidNumber("x".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_parse_mat() {
+        let m = Mat2::from_rows([Vec2::new(1, 2), Vec2::new(3, 4)]);
+        assert_eq!("1 2; 3 4".parse(), Ok(m));
+        assert_eq!("[1, 2, 3, 4]".parse(), Ok(m));
+
+        let m: Mat3<f64> = "1 2 3; 4 5 6; 7 8 9".parse().unwrap();
+        assert_eq!(m.row(1), Vec3::new(4.0, 5.0, 6.0));
+
+        let m: Mat4<f64> = "(1 0 0 5; 0 1 0 6; 0 0 1 7; 0 0 0 1)".parse().unwrap();
+        assert_eq!(m.col(3), Vec4::new(5.0, 6.0, 7.0, 1.0));
+        assert!("1 2 3".parse::<Mat2<f64>>().is_err());
+    }
+
+    #[test]
+    fn test_parse_complex() {
+        assert_eq!("1,2".parse(), Ok(Complex::new(1.0, 2.0)));
+        assert_eq!("(1 -2)".parse(), Ok(Complex::new(1.0, -2.0)));
+        assert_eq!("1 - 2i".parse(), Ok(Complex::new(1.0, -2.0)));
+        assert_eq!("-1.5+0.5i".parse(), Ok(Complex::new(-1.5, 0.5)));
+        assert_eq!("1e-3 + 2e+1i".parse(), Ok(Complex::new(1.0e-3, 20.0)));
+        assert_eq!("-3i".parse(), Ok(Complex::new(0.0, -3.0)));
+        assert_eq!("2 - i".parse(), Ok(Complex::new(2.0, -1.0)));
+        assert!("1 + xi".parse::<Complex<f64>>().is_err());
+
+        // Round trip through `Display`
+        let c = Complex::new(0.25, -4.0);
+        assert_eq!(c.to_string().parse(), Ok(c));
+    }
+
+    #[test]
+    fn test_parse_rgb() {
+        let c = Rgb::new(255, 128, 0);
+
+        assert_eq!("255,128,0".parse(), Ok(c));
+        assert_eq!("(255 128 0)".parse(), Ok(c));
+        assert_eq!("#ff8000".parse(), Ok(c));
+        assert_eq!("#FF8000".parse(), Ok(c));
+
+        assert!("256,0,0".parse::<Rgb>().is_err());
+        assert!("#ff80".parse::<Rgb>().is_err());
+        assert!("#gg8000".parse::<Rgb>().is_err());
+    }
+}
+
+//-----------------------------------------------------------------------------
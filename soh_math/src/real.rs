@@ -0,0 +1,679 @@
+//-----------------------------------------------------------------------------
+//! Transcendental functions used by the rotations, complex numbers and fractals
+//!
+//! By default they forward to the standard library, whose results depend on the platform's libm.
+//! With the `deterministic` feature `f32` and `f64` use the software implementations of [soft]
+//! instead, which are made of basic IEEE 754 operations only and give bit-exact results on every
+//! platform ( lockstep simulations, replays )
+//!
+//! Bit-exact with the feature: the vector and matrix functions ( rotations, angles, `Mat3::exp` /
+//! `log` ), [crate::Complex] and [crate::Quaternion], the fractals, [crate::Mat4::perspective],
+//! the easing functions of [crate::ease] and the color differences of [crate::color] ( not the
+//! conversions to L*a*b*, which use `cbrt` ). Calling the [num_traits::Float] methods directly
+//! ( `x.sin()` ) bypasses it.
+//-----------------------------------------------------------------------------
+
+/// Transcendental functions, call them with the path syntax ( `Real::sin(x)` ) as the method
+/// names collide with [num_traits::Float]
+pub trait Real: Copy {
+    fn sin(self) -> Self;
+    fn cos(self) -> Self;
+    fn sin_cos(self) -> (Self, Self);
+    fn tan(self) -> Self;
+    fn asin(self) -> Self;
+    fn acos(self) -> Self;
+    fn atan(self) -> Self;
+    fn atan2(self, x: Self) -> Self;
+    fn exp(self) -> Self;
+    fn ln(self) -> Self;
+    fn powf(self, n: Self) -> Self;
+    fn hypot(self, other: Self) -> Self;
+}
+
+//-----------------------------------------------------------------------------
+
+#[cfg(not(feature = "deterministic"))]
+macro_rules! impl_real {
+    ($($t:ty)*) => {
+        $(
+            impl Real for $t {
+                fn sin(self) -> Self {
+                    return self.sin();
+                }
+
+                fn cos(self) -> Self {
+                    return self.cos();
+                }
+
+                fn sin_cos(self) -> (Self, Self) {
+                    return self.sin_cos();
+                }
+
+                fn tan(self) -> Self {
+                    return self.tan();
+                }
+
+                fn asin(self) -> Self {
+                    return self.asin();
+                }
+
+                fn acos(self) -> Self {
+                    return self.acos();
+                }
+
+                fn atan(self) -> Self {
+                    return self.atan();
+                }
+
+                fn atan2(self, x: Self) -> Self {
+                    return self.atan2(x);
+                }
+
+                fn exp(self) -> Self {
+                    return self.exp();
+                }
+
+                fn ln(self) -> Self {
+                    return self.ln();
+                }
+
+                fn powf(self, n: Self) -> Self {
+                    return self.powf(n);
+                }
+
+                fn hypot(self, other: Self) -> Self {
+                    return self.hypot(other);
+                }
+            }
+        )*
+    };
+}
+
+// `f32` is computed in `f64` and rounded, so both types share the implementation
+#[cfg(feature = "deterministic")]
+macro_rules! impl_real {
+    ($($t:ty)*) => {
+        $(
+            impl Real for $t {
+                fn sin(self) -> Self {
+                    return soft::sin(self as f64) as $t;
+                }
+
+                fn cos(self) -> Self {
+                    return soft::cos(self as f64) as $t;
+                }
+
+                fn sin_cos(self) -> (Self, Self) {
+                    let (sin, cos) = soft::sin_cos(self as f64);
+                    return (sin as $t, cos as $t);
+                }
+
+                fn tan(self) -> Self {
+                    return soft::tan(self as f64) as $t;
+                }
+
+                fn asin(self) -> Self {
+                    return soft::asin(self as f64) as $t;
+                }
+
+                fn acos(self) -> Self {
+                    return soft::acos(self as f64) as $t;
+                }
+
+                fn atan(self) -> Self {
+                    return soft::atan(self as f64) as $t;
+                }
+
+                fn atan2(self, x: Self) -> Self {
+                    return soft::atan2(self as f64, x as f64) as $t;
+                }
+
+                fn exp(self) -> Self {
+                    return soft::exp(self as f64) as $t;
+                }
+
+                fn ln(self) -> Self {
+                    return soft::ln(self as f64) as $t;
+                }
+
+                fn powf(self, n: Self) -> Self {
+                    return soft::powf(self as f64, n as f64) as $t;
+                }
+
+                fn hypot(self, other: Self) -> Self {
+                    return soft::hypot(self as f64, other as f64) as $t;
+                }
+            }
+        )*
+    };
+}
+
+impl_real!(f32 f64);
+
+// `f128` is already implemented in software
+#[cfg(feature = "f128")]
+impl Real for f128_num::f128 {
+    fn sin(self) -> Self {
+        return num_traits::Float::sin(self);
+    }
+
+    fn cos(self) -> Self {
+        return num_traits::Float::cos(self);
+    }
+
+    fn sin_cos(self) -> (Self, Self) {
+        return num_traits::Float::sin_cos(self);
+    }
+
+    fn tan(self) -> Self {
+        return num_traits::Float::tan(self);
+    }
+
+    fn asin(self) -> Self {
+        return num_traits::Float::asin(self);
+    }
+
+    fn acos(self) -> Self {
+        return num_traits::Float::acos(self);
+    }
+
+    fn atan(self) -> Self {
+        return num_traits::Float::atan(self);
+    }
+
+    fn atan2(self, x: Self) -> Self {
+        return num_traits::Float::atan2(self, x);
+    }
+
+    fn exp(self) -> Self {
+        return num_traits::Float::exp(self);
+    }
+
+    fn ln(self) -> Self {
+        return num_traits::Float::ln(self);
+    }
+
+    fn powf(self, n: Self) -> Self {
+        return num_traits::Float::powf(self, n);
+    }
+
+    fn hypot(self, other: Self) -> Self {
+        return num_traits::Float::hypot(self, other);
+    }
+}
+
+//-----------------------------------------------------------------------------
+
+/// Software implementations made of `+ - * /`, `sqrt` and rounding only ( all of them are
+/// correctly rounded by IEEE 754, so the results don't depend on the platform )
+///
+/// The results are within a few ulp of the exact ones. The argument reduction of [sin] and [cos]
+/// loses precision for `|x| > 1e6`
+pub mod soft {
+    use std::f64::consts::{FRAC_PI_2, FRAC_PI_6, PI, SQRT_2};
+
+    /// pi / 2 split in parts with 33 significant bits, so their products with the quadrant
+    /// number are exact
+    const PIO2_1: f64 = 1.5707963267341256;
+    const PIO2_2: f64 = 6.077100506303966e-11;
+    const PIO2_3: f64 = 2.0222662487959506e-21;
+
+    /// ln 2 split the same way
+    const LN2_HI: f64 = 0.6931471803691238;
+    const LN2_LO: f64 = 1.9082149292705877e-10;
+
+    const SQRT_3: f64 = 1.7320508075688772;
+    const TAN_PI_12: f64 = 0.2679491924311227;
+
+    /// Evaluate the polynomial with the coefficients from the lowest degree
+    fn poly(x: f64, coefs: &[f64]) -> f64 {
+        return coefs.iter().rev().fold(0.0, |acc, &c| acc * x + c);
+    }
+
+    /// 2^k for k in [-1022, 1023]
+    fn pow2(k: i64) -> f64 {
+        return f64::from_bits(((k + 1023) as u64) << 52);
+    }
+
+    //-------------------------------------------------------------------------
+    // Trigonometry
+
+    /// sin and cos on [-pi/4, pi/4] ( Taylor series, the remainders are under 1e-19 )
+    fn kernel_sin_cos(x: f64) -> (f64, f64) {
+        let z = x * x;
+
+        let sin = x + x
+            * z
+            * poly(
+                z,
+                &[
+                    -1.0 / 6.0,
+                    1.0 / 120.0,
+                    -1.0 / 5040.0,
+                    1.0 / 362880.0,
+                    -1.0 / 39916800.0,
+                    1.0 / 6227020800.0,
+                    -1.0 / 1307674368000.0,
+                    1.0 / 355687428096000.0,
+                ],
+            );
+        let cos = 1.0 - 0.5 * z
+            + z * z
+                * poly(
+                    z,
+                    &[
+                        1.0 / 24.0,
+                        -1.0 / 720.0,
+                        1.0 / 40320.0,
+                        -1.0 / 3628800.0,
+                        1.0 / 479001600.0,
+                        -1.0 / 87178291200.0,
+                        1.0 / 20922789888000.0,
+                        -1.0 / 6402373705728000.0,
+                    ],
+                );
+
+        return (sin, cos);
+    }
+
+    pub fn sin_cos(x: f64) -> (f64, f64) {
+        if !x.is_finite() {
+            return (f64::NAN, f64::NAN);
+        }
+
+        /*
+         * Reduce to [-pi/4, pi/4] and the quadrant
+         */
+        let n = (x * (2.0 / PI)).round();
+        let r = ((x - n * PIO2_1) - n * PIO2_2) - n * PIO2_3;
+        let (sin, cos) = kernel_sin_cos(r);
+
+        return match (n as i64) & 3 {
+            0 => (sin, cos),
+            1 => (cos, -sin),
+            2 => (-sin, -cos),
+            _ => (-cos, sin),
+        };
+    }
+
+    pub fn sin(x: f64) -> f64 {
+        return sin_cos(x).0;
+    }
+
+    pub fn cos(x: f64) -> f64 {
+        return sin_cos(x).1;
+    }
+
+    pub fn tan(x: f64) -> f64 {
+        let (sin, cos) = sin_cos(x);
+        return sin / cos;
+    }
+
+    pub fn atan(x: f64) -> f64 {
+        if x.is_nan() {
+            return x;
+        }
+
+        /*
+         * Reduce to [0, tan(pi/12)] with atan(x) = pi/2 - atan(1/x) and
+         * atan(x) = pi/6 + atan((x * sqrt(3) - 1) / (x + sqrt(3)))
+         */
+        let mut a = x.abs();
+
+        let invert = a > 1.0;
+        if invert {
+            a = 1.0 / a;
+        }
+
+        let shift = a > TAN_PI_12;
+        if shift {
+            a = (a * SQRT_3 - 1.0) / (a + SQRT_3);
+        }
+
+        // Taylor series, the remainder is under 1e-19
+        let z = a * a;
+        let coefs = std::array::from_fn::<f64, 15, _>(|i| {
+            let sign = if i % 2 == 0 { 1.0 } else { -1.0 };
+            return sign / (2 * i + 1) as f64;
+        });
+        let mut res = a * poly(z, &coefs);
+
+        if shift {
+            res += FRAC_PI_6;
+        }
+        if invert {
+            res = FRAC_PI_2 - res;
+        }
+
+        return res.copysign(x);
+    }
+
+    pub fn atan2(y: f64, x: f64) -> f64 {
+        if x.is_nan() || y.is_nan() {
+            return f64::NAN;
+        }
+
+        /* Signed zeros and infinities */
+        if y == 0.0 {
+            return if x.is_sign_negative() {
+                PI.copysign(y)
+            } else {
+                y
+            };
+        }
+        if x.is_infinite() && y.is_infinite() {
+            let angle = if x > 0.0 { PI / 4.0 } else { 3.0 * PI / 4.0 };
+            return angle.copysign(y);
+        }
+        if x == 0.0 {
+            return FRAC_PI_2.copysign(y);
+        }
+
+        let angle = atan(y / x);
+        if x > 0.0 {
+            return angle;
+        }
+        return angle + PI.copysign(y);
+    }
+
+    pub fn asin(x: f64) -> f64 {
+        return atan2(x, ((1.0 - x) * (1.0 + x)).sqrt());
+    }
+
+    pub fn acos(x: f64) -> f64 {
+        return atan2(((1.0 - x) * (1.0 + x)).sqrt(), x);
+    }
+
+    //-------------------------------------------------------------------------
+    // Exponent and logarithm
+
+    pub fn exp(x: f64) -> f64 {
+        if x.is_nan() {
+            return x;
+        }
+        if x > 709.782712893384 {
+            return f64::INFINITY;
+        }
+        if x < -745.1332191019412 {
+            return 0.0;
+        }
+
+        /*
+         * exp(x) = 2^k * exp(r) with |r| <= ln(2) / 2
+         */
+        let k = (x * std::f64::consts::LOG2_E).round();
+        let r = (x - k * LN2_HI) - k * LN2_LO;
+
+        // Taylor series, the remainder is under 1e-17
+        let mut coef = 1.0;
+        let coefs = std::array::from_fn::<f64, 14, _>(|i| {
+            if i > 0 {
+                coef /= i as f64;
+            }
+            return coef;
+        });
+        let exp_r = poly(r, &coefs);
+
+        /* Scale in two steps near the limits of the exponent */
+        let k = k as i64;
+        return match k {
+            1024.. => exp_r * pow2(1023) * pow2(k - 1023),
+            ..-1022 => exp_r * pow2(-1022) * pow2(k + 1022),
+            _ => exp_r * pow2(k),
+        };
+    }
+
+    pub fn ln(x: f64) -> f64 {
+        if x.is_nan() || x < 0.0 {
+            return f64::NAN;
+        }
+        if x == 0.0 {
+            return f64::NEG_INFINITY;
+        }
+        if x.is_infinite() {
+            return x;
+        }
+
+        /*
+         * x = 2^e * m with m in [sqrt(2) / 2, sqrt(2)]
+         */
+        let (x, mut e) = if x < f64::MIN_POSITIVE {
+            (x * pow2(54), -54)
+        } else {
+            (x, 0)
+        };
+
+        let bits = x.to_bits();
+        e += ((bits >> 52) & 0x7ff) as i64 - 1023;
+        let mut m = f64::from_bits((bits & 0x000f_ffff_ffff_ffff) | (1023 << 52));
+        if m > SQRT_2 {
+            m *= 0.5;
+            e += 1;
+        }
+
+        /*
+         * ln(m) = 2 * atanh(f) with f = (m - 1) / (m + 1), |f| < 0.172
+         */
+        let f = (m - 1.0) / (m + 1.0);
+        let coefs = std::array::from_fn::<f64, 11, _>(|i| 1.0 / (2 * i + 1) as f64);
+        let ln_m = 2.0 * f * poly(f * f, &coefs);
+
+        let e = e as f64;
+        return e * LN2_HI + (e * LN2_LO + ln_m);
+    }
+
+    /// `x^n`: integer powers are multiplied out ( exact for small results ), the others are
+    /// `exp(n * ln(x))` whose relative error grows with `|n * ln(x)|`
+    pub fn powf(x: f64, n: f64) -> f64 {
+        if n == 0.0 || x == 1.0 {
+            return 1.0;
+        }
+        if x.is_nan() || n.is_nan() {
+            return f64::NAN;
+        }
+
+        let is_int = n.fract() == 0.0;
+        if is_int && n.abs() <= 64.0 {
+            return powi(x, n as i64);
+        }
+
+        // ln(0) = -inf and ln(inf) = inf give the right limits, so do infinite powers
+        let pow = exp(n * ln(x.abs()));
+
+        /*
+         * Negative bases only have real integer powers, the sign depends on the parity
+         */
+        if x < 0.0 && n.is_finite() {
+            return match (is_int, (n * 0.5).fract() != 0.0) {
+                (false, _) => f64::NAN,
+                (true, true) => -pow,
+                (true, false) => pow,
+            };
+        }
+        return pow;
+    }
+
+    /// Exponentiation by squaring
+    fn powi(x: f64, n: i64) -> f64 {
+        let mut res = 1.0;
+        let mut base = x;
+        let mut k = n.unsigned_abs();
+
+        while k != 0 {
+            if k & 1 == 1 {
+                res *= base;
+            }
+            base *= base;
+            k >>= 1;
+        }
+
+        return if n < 0 { 1.0 / res } else { res };
+    }
+
+    //-------------------------------------------------------------------------
+
+    pub fn hypot(x: f64, y: f64) -> f64 {
+        if x.is_infinite() || y.is_infinite() {
+            return f64::INFINITY;
+        }
+
+        /* Scale by the larger one to avoid overflows */
+        let (a, b) = (x.abs().max(y.abs()), x.abs().min(y.abs()));
+        if a == 0.0 || a.is_nan() || b.is_nan() {
+            return a + b;
+        }
+
+        let r = b / a;
+        return a * (1.0 + r * r).sqrt();
+    }
+}
+
+//-----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use proptest::prelude::*;
+
+    /// Relative difference, absolute near zero
+    fn assert_close(a: f64, b: f64) {
+        assert!(
+            (a - b).abs() <= 1.0e-14 * b.abs().max(1.0),
+            "{a} != {b} ( {} )",
+            a - b
+        );
+    }
+
+    #[test]
+    fn test_special_values() {
+        assert_eq!(soft::sin(0.0), 0.0);
+        assert_eq!(soft::cos(0.0), 1.0);
+        assert!(soft::sin(f64::INFINITY).is_nan());
+
+        assert_eq!(soft::exp(0.0), 1.0);
+        assert_eq!(soft::exp(1000.0), f64::INFINITY);
+        assert_eq!(soft::exp(-1000.0), 0.0);
+        assert!(soft::exp(-740.0) > 0.0);
+
+        assert_eq!(soft::ln(1.0), 0.0);
+        assert_eq!(soft::ln(0.0), f64::NEG_INFINITY);
+        assert!(soft::ln(-1.0).is_nan());
+        assert_close(
+            soft::ln(f64::MIN_POSITIVE / 16.0),
+            (f64::MIN_POSITIVE / 16.0).ln(),
+        );
+
+        assert_eq!(soft::atan2(0.0, -1.0), std::f64::consts::PI);
+        assert_eq!(soft::atan2(-0.0, 1.0), -0.0);
+        assert_eq!(soft::atan2(1.0, 0.0), std::f64::consts::FRAC_PI_2);
+        assert_eq!(soft::atan(f64::INFINITY), std::f64::consts::FRAC_PI_2);
+        assert_close(soft::acos(-1.0), std::f64::consts::PI);
+
+        assert_eq!(soft::hypot(3.0, 4.0), 5.0);
+        assert_eq!(
+            soft::hypot(1.0e300, 1.0e300),
+            1.0e300 * std::f64::consts::SQRT_2
+        );
+    }
+
+    #[test]
+    fn test_powf_special_values() {
+        assert_eq!(soft::powf(f64::NAN, 0.0), 1.0);
+        assert_eq!(soft::powf(1.0, f64::NAN), 1.0);
+        assert!(soft::powf(f64::NAN, 1.0).is_nan());
+        assert!(soft::powf(-2.0, 0.5).is_nan());
+
+        assert_eq!(soft::powf(2.0, 10.0), 1024.0);
+        assert_eq!(soft::powf(-2.0, 3.0), -8.0);
+        assert_eq!(soft::powf(-2.0, -2.0), 0.25);
+        assert_eq!(soft::powf(0.0, 2.0), 0.0);
+        assert_eq!(soft::powf(0.0, -1.0), f64::INFINITY);
+        assert_eq!(soft::powf(2.0, f64::INFINITY), f64::INFINITY);
+        assert_eq!(soft::powf(0.5, f64::INFINITY), 0.0);
+        assert_eq!(soft::powf(-0.5, f64::INFINITY), 0.0);
+        assert_eq!(soft::powf(f64::INFINITY, -1.0), 0.0);
+        assert_eq!(soft::powf(-1.0, 101.0), -1.0);
+        assert_eq!(soft::powf(-1.0, 100.0), 1.0);
+    }
+
+    #[test]
+    fn test_dispatch() {
+        assert!((Real::sin(0.5f32) - 0.5f32.sin()).abs() < 1.0e-6);
+        assert!((Real::exp(1.0f32) - std::f32::consts::E).abs() < 1.0e-6);
+        assert!((Real::tan(0.5f32) - 0.5f32.tan()).abs() < 1.0e-6);
+        assert!((Real::powf(2.0f32, 0.5) - std::f32::consts::SQRT_2).abs() < 1.0e-6);
+        assert_close(Real::atan2(1.0f64, 1.0), std::f64::consts::FRAC_PI_4);
+        assert_close(Real::asin(1.0f64), std::f64::consts::FRAC_PI_2);
+        assert_close(Real::atan(1.0f64), std::f64::consts::FRAC_PI_4);
+    }
+
+    /// With the feature every function uses the software implementation
+    #[cfg(feature = "deterministic")]
+    #[test]
+    fn test_dispatch_deterministic() {
+        for x in [-3.7, -0.5, 0.0, 0.3, 0.9, 2.5, 100.0f64] {
+            assert_eq!(Real::sin(x).to_bits(), soft::sin(x).to_bits());
+            assert_eq!(Real::cos(x).to_bits(), soft::cos(x).to_bits());
+            assert_eq!(Real::tan(x).to_bits(), soft::tan(x).to_bits());
+            assert_eq!(Real::atan(x).to_bits(), soft::atan(x).to_bits());
+            assert_eq!(Real::atan2(x, 1.5).to_bits(), soft::atan2(x, 1.5).to_bits());
+            assert_eq!(Real::exp(x).to_bits(), soft::exp(x).to_bits());
+            assert_eq!(Real::ln(x).to_bits(), soft::ln(x).to_bits());
+            assert_eq!(Real::powf(x, 1.5).to_bits(), soft::powf(x, 1.5).to_bits());
+            assert_eq!(Real::hypot(x, 2.0).to_bits(), soft::hypot(x, 2.0).to_bits());
+
+            let t = x.clamp(-1.0, 1.0);
+            assert_eq!(Real::asin(t).to_bits(), soft::asin(t).to_bits());
+            assert_eq!(Real::acos(t).to_bits(), soft::acos(t).to_bits());
+
+            let x32 = x as f32;
+            let soft_tan = soft::tan(x32 as f64) as f32;
+            let soft_pow = soft::powf(x32 as f64, 2.5) as f32;
+            assert_eq!(Real::tan(x32).to_bits(), soft_tan.to_bits());
+            assert_eq!(Real::powf(x32, 2.5).to_bits(), soft_pow.to_bits());
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn prop_trigonometry(x in -1.0e4..1.0e4f64) {
+            let (sin, cos) = soft::sin_cos(x);
+            assert_close(sin, x.sin());
+            assert_close(cos, x.cos());
+        }
+
+        #[test]
+        fn prop_inverse_trigonometry(y in -1.0e3..1.0e3f64, x in -1.0e3..1.0e3f64, t in -1.0..1.0f64) {
+            assert_close(soft::atan2(y, x), y.atan2(x));
+            assert_close(soft::atan(y), y.atan());
+            assert_close(soft::asin(t), t.asin());
+            assert_close(soft::acos(t), t.acos());
+        }
+
+        #[test]
+        fn prop_tan(x in -1.0e4..1.0e4f64) {
+            // The error of sin / cos is relative to cos near the poles
+            let tan = soft::tan(x);
+            prop_assert!((tan - x.tan()).abs() <= 1.0e-14 * tan.abs().max(1.0) * tan.abs().max(1.0));
+        }
+
+        #[test]
+        fn prop_exp_ln(x in -700.0..700.0f64, y in 1.0e-300..1.0e300f64) {
+            assert_close(soft::exp(x), x.exp());
+            assert_close(soft::ln(y), y.ln());
+            assert_close(soft::hypot(x, y), x.hypot(y));
+        }
+
+        #[test]
+        fn prop_powf(x in 1.0e-3..1.0e3f64, n in -10.0..10.0f64, k in -100..100i32) {
+            // The error grows with the exponent of the result, at most 70 here
+            let pow = soft::powf(x, n);
+            prop_assert!((pow - x.powf(n)).abs() <= 1.0e-13 * pow);
+
+            let pow = soft::powf(-x, k as f64);
+            prop_assert!((pow - (-x).powi(k)).abs() <= 1.0e-13 * pow.abs());
+        }
+    }
+}
+
+//-----------------------------------------------------------------------------
@@ -0,0 +1,226 @@
+//-----------------------------------------------------------------------------
+use crate::Vec2;
+//-----------------------------------------------------------------------------
+
+/// Axis aligned rectangle
+///
+/// Contains the points in [min, max) on both axes. A rectangle with `max <= min` on any axis is
+/// empty.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Default, Clone, Copy, Hash, PartialEq, Eq)]
+pub struct Rect<T> {
+    pub min: Vec2<T>,
+    pub max: Vec2<T>,
+}
+
+/// Area of the render target with the depth range ( like `VkViewport` )
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Viewport {
+    pub rect: Rect<f32>,
+    pub min_depth: f32,
+    pub max_depth: f32,
+}
+
+//-----------------------------------------------------------------------------
+
+fn partial_min<T: PartialOrd>(a: T, b: T) -> T {
+    return if b < a { b } else { a };
+}
+
+fn partial_max<T: PartialOrd>(a: T, b: T) -> T {
+    return if b > a { b } else { a };
+}
+
+//-----------------------------------------------------------------------------
+// Constructors
+impl<T> Rect<T> {
+    pub const fn new(min: Vec2<T>, max: Vec2<T>) -> Self {
+        return Rect { min, max };
+    }
+}
+
+impl<T> Rect<T>
+where
+    T: num_traits::Num + Copy,
+{
+    pub fn from_pos_size(pos: Vec2<T>, size: Vec2<T>) -> Self {
+        return Rect {
+            min: pos,
+            max: pos + size,
+        };
+    }
+
+    pub fn from_center_size(center: Vec2<T>, size: Vec2<T>) -> Self {
+        let two = T::one() + T::one();
+        return Self::from_pos_size(center - size / two, size);
+    }
+}
+
+impl Viewport {
+    /// Viewport with the full depth range [0, 1]
+    pub fn new(rect: Rect<f32>) -> Self {
+        return Viewport {
+            rect,
+            min_depth: 0.0,
+            max_depth: 1.0,
+        };
+    }
+
+    pub fn depth(mut self, min_depth: f32, max_depth: f32) -> Self {
+        self.min_depth = min_depth;
+        self.max_depth = max_depth;
+        return self;
+    }
+}
+
+//-----------------------------------------------------------------------------
+// Getters
+impl<T> Rect<T>
+where
+    T: num_traits::Num + PartialOrd + Copy,
+{
+    pub fn width(&self) -> T {
+        return self.max.x - self.min.x;
+    }
+    pub fn height(&self) -> T {
+        return self.max.y - self.min.y;
+    }
+    pub fn size(&self) -> Vec2<T> {
+        return self.max - self.min;
+    }
+    pub fn center(&self) -> Vec2<T> {
+        return Vec2::midpoint(&self.min, &self.max);
+    }
+    pub fn is_empty(&self) -> bool {
+        return self.max.x <= self.min.x || self.max.y <= self.min.y;
+    }
+}
+
+//-----------------------------------------------------------------------------
+// Specific implementation
+impl<T> Rect<T>
+where
+    T: num_traits::Num + PartialOrd + Copy,
+{
+    pub fn contains(&self, point: Vec2<T>) -> bool {
+        return point.x >= self.min.x
+            && point.x < self.max.x
+            && point.y >= self.min.y
+            && point.y < self.max.y;
+    }
+
+    /// Check that `other` is completely inside `self`
+    pub fn contains_rect(&self, other: &Rect<T>) -> bool {
+        return other.min.x >= self.min.x
+            && other.max.x <= self.max.x
+            && other.min.y >= self.min.y
+            && other.max.y <= self.max.y;
+    }
+
+    pub fn intersects(&self, other: &Rect<T>) -> bool {
+        return self.min.x < other.max.x
+            && other.min.x < self.max.x
+            && self.min.y < other.max.y
+            && other.min.y < self.max.y;
+    }
+
+    /// Overlapping part of both rects (empty rect if they don't overlap)
+    pub fn intersection(&self, other: &Rect<T>) -> Self {
+        let min = Vec2::new(
+            partial_max(self.min.x, other.min.x),
+            partial_max(self.min.y, other.min.y),
+        );
+        let max = Vec2::new(
+            partial_min(self.max.x, other.max.x),
+            partial_min(self.max.y, other.max.y),
+        );
+
+        return Rect {
+            min,
+            max: Vec2::new(partial_max(max.x, min.x), partial_max(max.y, min.y)),
+        };
+    }
+
+    /// Smallest rect containing both rects
+    pub fn union(&self, other: &Rect<T>) -> Self {
+        return Rect {
+            min: Vec2::new(
+                partial_min(self.min.x, other.min.x),
+                partial_min(self.min.y, other.min.y),
+            ),
+            max: Vec2::new(
+                partial_max(self.max.x, other.max.x),
+                partial_max(self.max.y, other.max.y),
+            ),
+        };
+    }
+
+    pub fn translate(&self, offset: Vec2<T>) -> Self {
+        return Rect {
+            min: self.min + offset,
+            max: self.max + offset,
+        };
+    }
+
+    /// Move the edges outwards by `amount` on each side (inwards if negative)
+    pub fn inflate(&self, amount: Vec2<T>) -> Self {
+        return Rect {
+            min: self.min - amount,
+            max: self.max + amount,
+        };
+    }
+}
+
+//-----------------------------------------------------------------------------
+// Convert
+impl<S, D> crate::Convert<Rect<D>> for Rect<S>
+where
+    S: Copy,
+    D: Copy + From<S>,
+{
+    fn convert(&self) -> Rect<D> {
+        return Rect {
+            min: self.min.convert(),
+            max: self.max.convert(),
+        };
+    }
+}
+
+//-----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_rect() {
+        let a = Rect::new(Vec2::new(0, 0), Vec2::new(4, 3));
+        let b = Rect::from_pos_size(Vec2::new(2, 1), Vec2::new(4, 4));
+
+        assert_eq!(a.size(), Vec2::new(4, 3));
+        assert_eq!(b.max, Vec2::new(6, 5));
+        assert_eq!(a.center(), Vec2::new(2, 1));
+
+        assert!(a.contains(Vec2::new(0, 0)));
+        assert!(!a.contains(Vec2::new(4, 0)));
+        assert!(a.intersects(&b));
+        assert_eq!(
+            a.intersection(&b),
+            Rect::new(Vec2::new(2, 1), Vec2::new(4, 3))
+        );
+        assert_eq!(a.union(&b), Rect::new(Vec2::new(0, 0), Vec2::new(6, 5)));
+        assert!(a.union(&b).contains_rect(&a));
+
+        // Rects which only touch don't intersect
+        let c = a.translate(Vec2::new(4, 0));
+        assert!(!a.intersects(&c));
+        assert!(a.intersection(&c).is_empty());
+
+        let d = Rect::from_center_size(Vec2::new(0.0, 0.0), Vec2::new(2.0, 4.0));
+        assert_eq!(d, Rect::new(Vec2::new(-1.0, -2.0), Vec2::new(1.0, 2.0)));
+        assert_eq!(d.inflate(Vec2::new(1.0, -1.0)).size(), Vec2::new(4.0, 2.0));
+    }
+}
+
+//-----------------------------------------------------------------------------
@@ -0,0 +1,266 @@
+//-----------------------------------------------------------------------------
+// 2D signed distance functions
+//
+// Shapes are centered at the origin ( translate the point by the center of the shape ). The
+// distance is negative inside of the shape, zero on the edge and positive outside.
+//-----------------------------------------------------------------------------
+use crate::real::Real;
+use crate::Vec2;
+use num_traits::Float;
+//-----------------------------------------------------------------------------
+
+fn c<T: Float>(value: f64) -> T {
+    return T::from(value).unwrap();
+}
+
+//-----------------------------------------------------------------------------
+// Shapes
+
+/// Circle of radius `radius`
+pub fn circle<T: Float + Real>(p: Vec2<T>, radius: T) -> T {
+    return p.len() - radius;
+}
+
+/// Axis aligned box with the half extents `half_size`
+pub fn rect<T: Float + Real>(p: Vec2<T>, half_size: Vec2<T>) -> T {
+    let d = p.map(|x| x.abs()) - half_size;
+
+    let outside = d.map(|x| x.max(T::zero())).len();
+    let inside = d.x.max(d.y).min(T::zero());
+
+    return outside + inside;
+}
+
+/// Axis aligned box with the half extents `half_size` and corners rounded by `radius`
+pub fn rounded_rect<T: Float + Real>(p: Vec2<T>, half_size: Vec2<T>, radius: T) -> T {
+    let radius = radius.min(half_size.x).min(half_size.y);
+
+    return round(rect(p, half_size - Vec2::splat(radius)), radius);
+}
+
+/// Line segment from `a` to `b` ( it has no inside, use [round] to give it a thickness )
+pub fn segment<T: Float + Real>(p: Vec2<T>, a: Vec2<T>, b: Vec2<T>) -> T {
+    let pa = p - a;
+    let ba = b - a;
+
+    let len2 = ba.len2();
+    let h = if len2 > T::zero() {
+        (Vec2::dot(&pa, &ba) / len2).max(T::zero()).min(T::one())
+    } else {
+        T::zero()
+    };
+
+    return (pa - ba * h).len();
+}
+
+/// Closed polygon through `vertices` ( may be concave, but not self-intersecting )
+pub fn polygon<T: Float + Real>(p: Vec2<T>, vertices: &[Vec2<T>]) -> T {
+    let Some(&last) = vertices.last() else {
+        return T::infinity();
+    };
+
+    let mut dist2 = T::infinity();
+    let mut inside = false;
+
+    let mut prev = last;
+    for &curr in vertices {
+        let edge = prev - curr;
+        let to_p = p - curr;
+
+        /* Distance to the edge */
+        let len2 = edge.len2();
+        let h = if len2 > T::zero() {
+            (Vec2::dot(&to_p, &edge) / len2)
+                .max(T::zero())
+                .min(T::one())
+        } else {
+            T::zero()
+        };
+        dist2 = dist2.min((to_p - edge * h).len2());
+
+        /* Even-odd rule: count the edges crossed by a ray to the right of the point */
+        if (curr.y > p.y) != (prev.y > p.y) {
+            let x = curr.x + (p.y - curr.y) * edge.x / edge.y;
+            if p.x < x {
+                inside = !inside;
+            }
+        }
+
+        prev = curr;
+    }
+
+    let dist = dist2.sqrt();
+
+    return if inside { -dist } else { dist };
+}
+
+//-----------------------------------------------------------------------------
+// Modifiers
+
+/// Grow the shape by `radius` rounding its corners
+pub fn round<T: Float>(d: T, radius: T) -> T {
+    return d - radius;
+}
+
+/// Turn the shape into an outline of thickness `2 * half_width` along its edge
+pub fn annular<T: Float>(d: T, half_width: T) -> T {
+    return d.abs() - half_width;
+}
+
+//-----------------------------------------------------------------------------
+// Boolean operations
+
+/// Both shapes
+pub fn union<T: Float>(a: T, b: T) -> T {
+    return a.min(b);
+}
+
+/// Area common to both shapes
+pub fn intersection<T: Float>(a: T, b: T) -> T {
+    return a.max(b);
+}
+
+/// Shape `a` with shape `b` cut out of it
+pub fn subtraction<T: Float>(a: T, b: T) -> T {
+    return a.max(-b);
+}
+
+/// Minimum blending the values closer than `k` ( polynomial smooth min )
+pub fn smooth_min<T: Float>(a: T, b: T, k: T) -> T {
+    if k <= T::zero() {
+        return a.min(b);
+    }
+
+    let h = (c::<T>(0.5) + c::<T>(0.5) * (b - a) / k)
+        .max(T::zero())
+        .min(T::one());
+
+    return b + (a - b) * h - k * h * (T::one() - h);
+}
+
+/// Union with the seam rounded over the distance `k`
+pub fn smooth_union<T: Float>(a: T, b: T, k: T) -> T {
+    return smooth_min(a, b, k);
+}
+
+/// Intersection with the seam rounded over the distance `k`
+pub fn smooth_intersection<T: Float>(a: T, b: T, k: T) -> T {
+    return -smooth_min(-a, -b, k);
+}
+
+/// Subtraction with the seam rounded over the distance `k`
+pub fn smooth_subtraction<T: Float>(a: T, b: T, k: T) -> T {
+    return -smooth_min(-a, b, k);
+}
+
+//-----------------------------------------------------------------------------
+// Rendering
+
+/// Coverage of a pixel in [0, 1] for antialiasing, `pixel_size` is the size of a pixel in the
+/// units of the distance
+pub fn coverage<T: Float>(d: T, pixel_size: T) -> T {
+    return (c::<T>(0.5) - d / pixel_size).max(T::zero()).min(T::one());
+}
+
+//-----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::strategy;
+    use proptest::prelude::*;
+
+    const EPS: f64 = 1.0e-10;
+
+    #[test]
+    fn test_shapes() {
+        let p = Vec2::new(3.0, 4.0);
+
+        assert_eq!(circle(p, 1.0), 4.0);
+        assert_eq!(circle(Vec2::zero(), 1.0), -1.0);
+
+        assert_eq!(rect(p, Vec2::new(1.0, 1.0)), Vec2::new(2.0, 3.0).len());
+        assert_eq!(rect(Vec2::new(0.5, 0.0), Vec2::new(1.0, 2.0)), -0.5);
+        assert_eq!(rect(Vec2::new(3.0, 0.0), Vec2::new(1.0, 2.0)), 2.0);
+
+        // Flat side isn't affected by the rounding
+        assert_eq!(
+            rounded_rect(Vec2::new(3.0, 0.0), Vec2::new(1.0, 2.0), 0.5),
+            2.0
+        );
+        let corner = rounded_rect(Vec2::new(2.0, 3.0), Vec2::new(1.0, 2.0), 0.5);
+        assert!((corner - (Vec2::new(1.5, 1.5).len() - 0.5)).abs() < EPS);
+
+        let (a, b) = (Vec2::new(-1.0, 0.0), Vec2::new(1.0, 0.0));
+        assert_eq!(segment(Vec2::new(0.0, 2.0), a, b), 2.0);
+        assert_eq!(segment(Vec2::new(4.0, 4.0), a, b), 5.0);
+        assert_eq!(
+            segment(Vec2::new(4.0, 4.0), a, a),
+            Vec2::new(5.0, 4.0).len()
+        );
+    }
+
+    #[test]
+    fn test_polygon() {
+        // Concave "L" shape
+        let l = [
+            Vec2::new(0.0, 0.0),
+            Vec2::new(2.0, 0.0),
+            Vec2::new(2.0, 1.0),
+            Vec2::new(1.0, 1.0),
+            Vec2::new(1.0, 2.0),
+            Vec2::new(0.0, 2.0),
+        ];
+
+        assert_eq!(polygon(Vec2::new(0.5, 0.25), &l), -0.25);
+        assert_eq!(polygon(Vec2::new(1.5, 1.5), &l), 0.5);
+        assert_eq!(polygon(Vec2::new(3.0, 0.5), &l), 1.0);
+        assert_eq!(polygon(Vec2::new(3.0, 2.0), &l), Vec2::new(1.0, 1.0).len());
+        assert_eq!(polygon(Vec2::new(3.0, 0.5), &[]), f64::INFINITY);
+    }
+
+    #[test]
+    fn test_combinators() {
+        assert_eq!(union(1.0, -2.0), -2.0);
+        assert_eq!(intersection(1.0, -2.0), 1.0);
+        assert_eq!(subtraction(-1.0, -2.0), 2.0);
+
+        // Far apart values aren't blended
+        assert_eq!(smooth_min(1.0, 5.0, 0.5), 1.0);
+        assert_eq!(smooth_min(1.0, 5.0, 0.0), 1.0);
+        // Equal values are pulled down by a quarter of `k`
+        assert_eq!(smooth_min(1.0, 1.0, 0.5), 0.875);
+
+        assert_eq!(coverage(-1.0, 1.0), 1.0);
+        assert_eq!(coverage(0.0, 1.0), 0.5);
+        assert_eq!(coverage(1.0, 1.0), 0.0);
+    }
+
+    proptest! {
+        #[test]
+        fn prop_rect_matches_polygon(
+            p in strategy::vec2(-3.0..3.0),
+            half_size in strategy::vec2(0.1..2.0),
+        ) {
+            let (w, h) = (half_size.x, half_size.y);
+            let vertices = [
+                Vec2::new(-w, -h),
+                Vec2::new(w, -h),
+                Vec2::new(w, h),
+                Vec2::new(-w, h),
+            ];
+
+            prop_assert!((rect(p, half_size) - polygon(p, &vertices)).abs() < EPS);
+        }
+
+        #[test]
+        fn prop_smooth_min_bounds(a in -5.0..5.0f64, b in -5.0..5.0f64, k in 0.0..2.0f64) {
+            let d = smooth_min(a, b, k);
+
+            prop_assert!(d <= a.min(b) + EPS);
+            prop_assert!(d >= a.min(b) - k * 0.25 - EPS);
+        }
+    }
+}
+
+//-----------------------------------------------------------------------------
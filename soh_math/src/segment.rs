@@ -0,0 +1,367 @@
+//-----------------------------------------------------------------------------
+//! Line segments and capsules with closest point queries ( collision and hit testing )
+//!
+//! source:
+//! Christer Ericson, "Real-Time Collision Detection", 5.1
+//-----------------------------------------------------------------------------
+use crate::real::Real;
+use crate::{Rect, Vec2, Vec3};
+use num_traits::Float;
+//-----------------------------------------------------------------------------
+
+/// Segment from `a` to `b` in 2D
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Default, Clone, Copy, Hash, PartialEq, Eq)]
+pub struct Segment2<T> {
+    pub a: Vec2<T>,
+    pub b: Vec2<T>,
+}
+
+/// Segment from `a` to `b` in 3D
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Default, Clone, Copy, Hash, PartialEq, Eq)]
+pub struct Segment3<T> {
+    pub a: Vec3<T>,
+    pub b: Vec3<T>,
+}
+
+/// Points within `radius` of a segment ( character collision shape )
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct Capsule<T> {
+    pub segment: Segment3<T>,
+    pub radius: T,
+}
+
+//-----------------------------------------------------------------------------
+
+fn clamp01<T: Float>(t: T) -> T {
+    return t.max(T::zero()).min(T::one());
+}
+
+// Same queries for both dimensions
+macro_rules! impl_segment {
+    ($segment:ident, $vec:ident) => {
+        impl<T> $segment<T> {
+            pub const fn new(a: $vec<T>, b: $vec<T>) -> Self {
+                return $segment { a, b };
+            }
+        }
+
+        impl<T> $segment<T>
+        where
+            T: Float + Real,
+        {
+            /// Vector from `a` to `b`
+            pub fn direction(&self) -> $vec<T> {
+                return self.b - self.a;
+            }
+
+            pub fn len(&self) -> T {
+                return self.direction().len();
+            }
+
+            /// Point at `t` in [0, 1] from `a` to `b`
+            pub fn point_at(&self, t: T) -> $vec<T> {
+                return self.a + self.direction() * t;
+            }
+
+            /// Parameter of the point of the segment closest to `p`
+            pub fn closest_param(&self, p: $vec<T>) -> T {
+                let d = self.direction();
+                let len2 = d.len2();
+
+                if len2 <= T::epsilon() {
+                    return T::zero();
+                }
+                return clamp01($vec::dot(&(p - self.a), &d) / len2);
+            }
+
+            /// Point of the segment closest to `p`
+            pub fn closest_point(&self, p: $vec<T>) -> $vec<T> {
+                return self.point_at(self.closest_param(p));
+            }
+
+            /// Squared distance to `p`
+            pub fn distance2(&self, p: $vec<T>) -> T {
+                return (p - self.closest_point(p)).len2();
+            }
+
+            pub fn distance(&self, p: $vec<T>) -> T {
+                return self.distance2(p).sqrt();
+            }
+
+            /// Parameters `(s, t)` of the closest points of `self` and `other`
+            pub fn closest_params(&self, other: &Self) -> (T, T) {
+                let d1 = self.direction();
+                let d2 = other.direction();
+                let r = self.a - other.a;
+
+                let a = d1.len2();
+                let e = d2.len2();
+                let f = $vec::dot(&d2, &r);
+
+                /* Degenerate segments */
+                if a <= T::epsilon() && e <= T::epsilon() {
+                    return (T::zero(), T::zero());
+                }
+                if a <= T::epsilon() {
+                    return (T::zero(), clamp01(f / e));
+                }
+
+                let c = $vec::dot(&d1, &r);
+                if e <= T::epsilon() {
+                    return (clamp01(-c / a), T::zero());
+                }
+
+                /*
+                 * Closest points of the lines, clamped to the segments ( parallel lines pick an
+                 * arbitrary point of `self` )
+                 */
+                let b = $vec::dot(&d1, &d2);
+                let denom = a * e - b * b;
+
+                let s = if denom > T::zero() {
+                    clamp01((b * f - c * e) / denom)
+                } else {
+                    T::zero()
+                };
+                let t = (b * s + f) / e;
+
+                if t < T::zero() {
+                    return (clamp01(-c / a), T::zero());
+                }
+                if t > T::one() {
+                    return (clamp01((b - c) / a), T::one());
+                }
+                return (s, t);
+            }
+
+            /// Closest points of `self` and `other`
+            pub fn closest_points(&self, other: &Self) -> ($vec<T>, $vec<T>) {
+                let (s, t) = self.closest_params(other);
+                return (self.point_at(s), other.point_at(t));
+            }
+
+            /// Distance between the closest points of the segments
+            pub fn distance_to_segment(&self, other: &Self) -> T {
+                let (p, q) = self.closest_points(other);
+                return (p - q).len();
+            }
+
+            /// Check that the segment touches the circle ( sphere in 3D )
+            pub fn intersects_circle(&self, center: $vec<T>, radius: T) -> bool {
+                return self.distance2(center) <= radius * radius;
+            }
+        }
+    };
+}
+
+impl_segment!(Segment2, Vec2);
+impl_segment!(Segment3, Vec3);
+
+//-----------------------------------------------------------------------------
+// 2D specific implementation
+impl<T> Segment2<T>
+where
+    T: Float + Real,
+{
+    /// Parameter range of the part of the segment inside `rect` ( Liang-Barsky clipping )
+    pub fn clip_rect(&self, rect: &Rect<T>) -> Option<(T, T)> {
+        let d = self.direction();
+
+        let mut t_min = T::zero();
+        let mut t_max = T::one();
+
+        let slabs = [
+            (d.x, self.a.x, rect.min.x, rect.max.x),
+            (d.y, self.a.y, rect.min.y, rect.max.y),
+        ];
+
+        for (d, start, min, max) in slabs {
+            if d.abs() <= T::epsilon() {
+                // Parallel to the slab
+                if start < min || start > max {
+                    return None;
+                }
+                continue;
+            }
+
+            let t1 = (min - start) / d;
+            let t2 = (max - start) / d;
+
+            t_min = t_min.max(t1.min(t2));
+            t_max = t_max.min(t1.max(t2));
+
+            if t_min > t_max {
+                return None;
+            }
+        }
+
+        return Some((t_min, t_max));
+    }
+
+    pub fn intersects_rect(&self, rect: &Rect<T>) -> bool {
+        return self.clip_rect(rect).is_some();
+    }
+}
+
+//-----------------------------------------------------------------------------
+// Capsule
+impl<T> Capsule<T> {
+    pub const fn new(a: Vec3<T>, b: Vec3<T>, radius: T) -> Self {
+        return Capsule {
+            segment: Segment3 { a, b },
+            radius,
+        };
+    }
+}
+
+impl<T> Capsule<T>
+where
+    T: Float + Real,
+{
+    /// Signed distance to the surface ( negative inside )
+    pub fn distance(&self, p: Vec3<T>) -> T {
+        return self.segment.distance(p) - self.radius;
+    }
+
+    pub fn contains(&self, p: Vec3<T>) -> bool {
+        return self.segment.distance2(p) <= self.radius * self.radius;
+    }
+
+    /// Point of the surface closest to `p` ( `p` itself if it's on the axis )
+    pub fn closest_point(&self, p: Vec3<T>) -> Vec3<T> {
+        let axis_point = self.segment.closest_point(p);
+        let offset = p - axis_point;
+        let len = offset.len();
+
+        if len <= T::epsilon() {
+            return p;
+        }
+        return axis_point + offset * (self.radius / len);
+    }
+
+    pub fn intersects(&self, other: &Capsule<T>) -> bool {
+        let radius = self.radius + other.radius;
+        let (p, q) = self.segment.closest_points(&other.segment);
+
+        return (p - q).len2() <= radius * radius;
+    }
+
+    pub fn intersects_sphere(&self, center: Vec3<T>, radius: T) -> bool {
+        return self.segment.intersects_circle(center, self.radius + radius);
+    }
+}
+
+//-----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::strategy;
+    use proptest::prelude::*;
+
+    const EPS: f64 = 1.0e-9;
+
+    #[test]
+    fn test_point_queries() {
+        let s = Segment2::new(Vec2::new(0.0, 0.0), Vec2::new(4.0, 0.0));
+
+        assert_eq!(s.len(), 4.0);
+        assert_eq!(s.closest_point(Vec2::new(1.0, 3.0)), Vec2::new(1.0, 0.0));
+        assert_eq!(s.closest_point(Vec2::new(-2.0, 1.0)), Vec2::new(0.0, 0.0));
+        assert_eq!(s.distance(Vec2::new(7.0, 4.0)), 5.0);
+
+        assert!(s.intersects_circle(Vec2::new(2.0, 1.0), 1.0));
+        assert!(!s.intersects_circle(Vec2::new(2.0, 1.5), 1.0));
+
+        // Degenerate segment is a point
+        let p = Segment3::new(Vec3::new(1.0, 1.0, 1.0), Vec3::new(1.0, 1.0, 1.0));
+        assert_eq!(p.distance(Vec3::new(1.0, 1.0, 3.0)), 2.0);
+    }
+
+    #[test]
+    fn test_closest_points() {
+        // Skew lines crossing above each other
+        let s1 = Segment3::new(Vec3::new(-1.0, 0.0, 0.0), Vec3::new(1.0, 0.0, 0.0));
+        let s2 = Segment3::new(Vec3::new(0.0, -1.0, 2.0), Vec3::new(0.0, 1.0, 2.0));
+        assert_eq!(s1.closest_params(&s2), (0.5, 0.5));
+        assert_eq!(s1.distance_to_segment(&s2), 2.0);
+
+        // Parallel
+        let s2 = Segment3::new(Vec3::new(3.0, 1.0, 0.0), Vec3::new(5.0, 1.0, 0.0));
+        let (p, q) = s1.closest_points(&s2);
+        assert_eq!((p, q), (Vec3::new(1.0, 0.0, 0.0), Vec3::new(3.0, 1.0, 0.0)));
+
+        // Crossing
+        let s1 = Segment2::new(Vec2::new(0.0, 0.0), Vec2::new(2.0, 2.0));
+        let s2 = Segment2::new(Vec2::new(0.0, 2.0), Vec2::new(2.0, 0.0));
+        assert_eq!(s1.distance_to_segment(&s2), 0.0);
+    }
+
+    #[test]
+    fn test_clip_rect() {
+        let rect = Rect::new(Vec2::new(0.0, 0.0), Vec2::new(2.0, 2.0));
+
+        let s = Segment2::new(Vec2::new(-1.0, 1.0), Vec2::new(3.0, 1.0));
+        assert_eq!(s.clip_rect(&rect), Some((0.25, 0.75)));
+
+        let inside = Segment2::new(Vec2::new(0.5, 0.5), Vec2::new(1.0, 1.5));
+        assert_eq!(inside.clip_rect(&rect), Some((0.0, 1.0)));
+
+        let miss = Segment2::new(Vec2::new(-1.0, 3.0), Vec2::new(3.0, 2.5));
+        assert!(!miss.intersects_rect(&rect));
+
+        let corner = Segment2::new(Vec2::new(-1.0, 1.0), Vec2::new(1.0, 3.0));
+        assert!(!corner.intersects_rect(&rect.inflate(Vec2::new(-0.1, -0.1))));
+        assert!(corner.intersects_rect(&rect));
+    }
+
+    #[test]
+    fn test_capsule() {
+        let c = Capsule::new(Vec3::new(0.0, 0.0, 0.0), Vec3::new(0.0, 2.0, 0.0), 0.5);
+
+        assert!(c.contains(Vec3::new(0.4, 1.0, 0.0)));
+        assert!(!c.contains(Vec3::new(0.0, 2.6, 0.0)));
+        assert_eq!(c.distance(Vec3::new(0.0, 4.0, 0.0)), 1.5);
+        assert_eq!(
+            c.closest_point(Vec3::new(0.0, 1.0, 3.0)),
+            Vec3::new(0.0, 1.0, 0.5)
+        );
+
+        let other = Capsule::new(Vec3::new(0.9, -1.0, 0.0), Vec3::new(0.9, 1.0, 0.0), 0.5);
+        assert!(c.intersects(&other));
+        assert!(!c.intersects(&Capsule {
+            radius: 0.3,
+            ..other
+        }));
+
+        assert!(c.intersects_sphere(Vec3::new(0.0, 3.0, 0.0), 0.5));
+        assert!(!c.intersects_sphere(Vec3::new(0.0, 3.1, 0.0), 0.5));
+    }
+
+    proptest! {
+        #[test]
+        fn prop_closest_points_are_closest(
+            a1 in strategy::vec3(-5.0..5.0),
+            b1 in strategy::vec3(-5.0..5.0),
+            a2 in strategy::vec3(-5.0..5.0),
+            b2 in strategy::vec3(-5.0..5.0),
+            s in 0.0..1.0,
+            t in 0.0..1.0,
+        ) {
+            let s1 = Segment3::new(a1, b1);
+            let s2 = Segment3::new(a2, b2);
+
+            // No other pair of points is closer
+            let distance = s1.distance_to_segment(&s2);
+            prop_assert!(distance <= s1.point_at(s).distance(&s2.point_at(t)) + EPS);
+
+            // Symmetric
+            prop_assert!((distance - s2.distance_to_segment(&s1)).abs() < EPS);
+        }
+    }
+}
+
+//-----------------------------------------------------------------------------
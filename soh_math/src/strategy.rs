@@ -0,0 +1,105 @@
+//-----------------------------------------------------------------------------
+//! Proptest strategies generating the math types
+//!
+//! Every strategy generates finite values in the given range, so the generated values can be
+//! shrunk towards zero when a property fails.
+//!
+//! ```
+//! use proptest::prelude::*;
+//! use soh_math::strategy;
+//!
+//! proptest! {
+//!     fn add_commutes(a in strategy::vec3(-5.0..5.0), b in strategy::vec3(-5.0..5.0)) {
+//!         prop_assert_eq!(a + b, b + a);
+//!     }
+//! }
+//!
+//! add_commutes();
+//! ```
+//-----------------------------------------------------------------------------
+use crate::{Complex, Mat2, Mat3, Mat4, Quaternion, Vec2, Vec3, Vec4};
+use proptest::prelude::*;
+use std::ops::Range;
+//-----------------------------------------------------------------------------
+
+/// Smallest determinant of the matrices generated by the `invertible_*` strategies
+pub const MIN_DET: f64 = 1.0e-3;
+
+//-----------------------------------------------------------------------------
+
+pub fn vec2(range: Range<f64>) -> impl Strategy<Value = Vec2<f64>> {
+    return proptest::array::uniform2(range).prop_map(|[x, y]| Vec2::new(x, y));
+}
+
+pub fn vec3(range: Range<f64>) -> impl Strategy<Value = Vec3<f64>> {
+    return proptest::array::uniform3(range).prop_map(|[x, y, z]| Vec3::new(x, y, z));
+}
+
+pub fn vec4(range: Range<f64>) -> impl Strategy<Value = Vec4<f64>> {
+    return proptest::array::uniform4(range).prop_map(|[x, y, z, w]| Vec4::new(x, y, z, w));
+}
+
+/// Vector of length 1
+pub fn unit_vec3() -> impl Strategy<Value = Vec3<f64>> {
+    return vec3(-1.0..1.0)
+        .prop_filter("vector is too short to normalize", |v| v.len() > 1.0e-3)
+        .prop_map(|v| v / v.len());
+}
+
+/// Angle in radians in [0, 2 pi)
+pub fn angle() -> impl Strategy<Value = f64> {
+    return 0.0..std::f64::consts::TAU;
+}
+
+//-----------------------------------------------------------------------------
+
+pub fn mat2(range: Range<f64>) -> impl Strategy<Value = Mat2<f64>> {
+    return proptest::array::uniform4(range).prop_map(Mat2::new);
+}
+
+pub fn mat3(range: Range<f64>) -> impl Strategy<Value = Mat3<f64>> {
+    return proptest::array::uniform9(range).prop_map(Mat3::new);
+}
+
+pub fn mat4(range: Range<f64>) -> impl Strategy<Value = Mat4<f64>> {
+    return proptest::array::uniform16(range).prop_map(Mat4::new);
+}
+
+/// Matrix with a determinant of at least [`MIN_DET`] by absolute value
+pub fn invertible_mat2(range: Range<f64>) -> impl Strategy<Value = Mat2<f64>> {
+    return mat2(range).prop_filter("matrix is singular", |m| m.det().abs() >= MIN_DET);
+}
+
+/// Matrix with a determinant of at least [`MIN_DET`] by absolute value
+pub fn invertible_mat3(range: Range<f64>) -> impl Strategy<Value = Mat3<f64>> {
+    return mat3(range).prop_filter("matrix is singular", |m| m.det().abs() >= MIN_DET);
+}
+
+/// Matrix with a determinant of at least [`MIN_DET`] by absolute value
+pub fn invertible_mat4(range: Range<f64>) -> impl Strategy<Value = Mat4<f64>> {
+    return mat4(range).prop_filter("matrix is singular", |m| m.det().abs() >= MIN_DET);
+}
+
+/// Rotation matrix around a random axis
+pub fn rotation_mat3() -> impl Strategy<Value = Mat3<f64>> {
+    return (unit_vec3(), angle()).prop_map(|(axis, angle)| Mat3::from_axis_angle(axis, angle));
+}
+
+//-----------------------------------------------------------------------------
+
+pub fn complex(range: Range<f64>) -> impl Strategy<Value = Complex<f64>> {
+    return proptest::array::uniform2(range).prop_map(|[re, im]| Complex::new(re, im));
+}
+
+pub fn quat(range: Range<f64>) -> impl Strategy<Value = Quaternion<f64>> {
+    return (range.clone(), vec3(range))
+        .prop_map(|(scalar, vector)| Quaternion::new(scalar, vector));
+}
+
+/// Quaternion of length 1 rotating around a random axis
+pub fn rotation_quat() -> impl Strategy<Value = Quaternion<f64>> {
+    return (unit_vec3(), angle())
+        .prop_map(|(axis, angle)| Quaternion::from_axis_angle(axis, angle));
+}
+
+//-----------------------------------------------------------------------------
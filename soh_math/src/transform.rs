@@ -0,0 +1,133 @@
+//-----------------------------------------------------------------------------
+use crate::traits::WholeConsts;
+use crate::{Mat3, Mat4, Quaternion, Vec3};
+//-----------------------------------------------------------------------------
+
+/// Translation, rotation and scale of an object ( applied in reverse order:
+/// scale first, translation last )
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Transform<T> {
+    pub translation: Vec3<T>,
+    pub rotation: Quaternion<T>,
+    pub scale: Vec3<T>,
+}
+
+//-----------------------------------------------------------------------------
+// Constructors
+impl<T> Transform<T> {
+    pub const fn new(translation: Vec3<T>, rotation: Quaternion<T>, scale: Vec3<T>) -> Self {
+        return Transform {
+            translation,
+            rotation,
+            scale,
+        };
+    }
+}
+
+impl<T> Transform<T>
+where
+    T: WholeConsts + Copy,
+{
+    pub const fn identity() -> Self {
+        return Transform {
+            translation: Vec3::new(T::ZERO, T::ZERO, T::ZERO),
+            rotation: Quaternion::ONE,
+            scale: Vec3::new(T::ONE, T::ONE, T::ONE),
+        };
+    }
+
+    pub const fn from_translation(translation: Vec3<T>) -> Self {
+        let mut res = Self::identity();
+        res.translation = translation;
+        return res;
+    }
+
+    pub const fn from_rotation(rotation: Quaternion<T>) -> Self {
+        let mut res = Self::identity();
+        res.rotation = rotation;
+        return res;
+    }
+}
+
+impl<T> Default for Transform<T>
+where
+    T: WholeConsts + Copy,
+{
+    fn default() -> Self {
+        return Self::identity();
+    }
+}
+
+//-----------------------------------------------------------------------------
+// Math functions
+impl<T> Transform<T>
+where
+    T: num_traits::Float + crate::real::Real + std::iter::Sum + From<f32> + WholeConsts,
+{
+    /// Matrix doing the same transformation
+    pub fn to_mat4(&self) -> Mat4<T> {
+        let m = Mat3::from_quat(self.rotation) * Mat3::from_diagonal(self.scale);
+
+        return Mat4::from_3x3_vec(m, self.translation);
+    }
+
+    pub fn transform_point(&self, point: Vec3<T>) -> Vec3<T> {
+        return self.rotation.rotate(Vec3::mul(&self.scale, &point)) + self.translation;
+    }
+
+    /// Transform of a child given in the space of `self`.
+    ///
+    /// The result is exact only when `self` has a uniform scale, otherwise the
+    /// skew can't be represented and [Transform::to_mat4] of both should be
+    /// multiplied instead.
+    pub fn then(&self, child: &Self) -> Self {
+        return Transform {
+            translation: self.transform_point(child.translation),
+            rotation: self.rotation * child.rotation,
+            scale: Vec3::mul(&self.scale, &child.scale),
+        };
+    }
+}
+
+//-----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::Vec4;
+
+    fn assert_close(a: Vec3<f32>, b: Vec3<f32>) {
+        assert!((a - b).len() < 1e-5, "{:?} != {:?}", a, b);
+    }
+
+    #[test]
+    fn test_transform() {
+        let parent = Transform::new(
+            Vec3::new(1.0, 2.0, 3.0),
+            Quaternion::from_axis_angle(Vec3::Z, 0.5),
+            Vec3::new(2.0, 2.0, 2.0),
+        );
+        let child = Transform::new(
+            Vec3::new(-1.0, 0.5, 0.0),
+            Quaternion::from_axis_angle(Vec3::X, 1.2),
+            Vec3::new(1.0, 3.0, 0.5),
+        );
+
+        let point = Vec3::new(0.3, -0.7, 1.1);
+
+        // The matrix agrees with transforming the point directly
+        let m = parent.to_mat4() * Vec4::new(point.x, point.y, point.z, 1.0);
+        assert_close(Vec3::new(m.x, m.y, m.z), parent.transform_point(point));
+
+        // Composition agrees with applying the transforms one by one
+        assert_close(
+            parent.then(&child).transform_point(point),
+            parent.transform_point(child.transform_point(point)),
+        );
+
+        assert_eq!(Transform::<f32>::identity().transform_point(point), point);
+    }
+}
+
+//-----------------------------------------------------------------------------
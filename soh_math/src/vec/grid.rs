@@ -0,0 +1,151 @@
+//-----------------------------------------------------------------------------
+// Helpers for integer vectors used as grid coordinates
+//
+// Grids are stored row-major: the cell (x, y) is at index `y * width + x`
+//-----------------------------------------------------------------------------
+use crate::{Vec2, Vec3};
+use num_traits::{PrimInt, Signed};
+//-----------------------------------------------------------------------------
+
+/// Absolute difference which doesn't underflow for unsigned integers
+fn abs_diff<T: PrimInt>(a: T, b: T) -> T {
+    return if a > b { a - b } else { b - a };
+}
+
+//-----------------------------------------------------------------------------
+
+impl<T> Vec2<T>
+where
+    T: PrimInt,
+{
+    /// Distance when moving only along the axes
+    pub fn manhattan(&self, other: &Self) -> T {
+        return abs_diff(self.x, other.x) + abs_diff(self.y, other.y);
+    }
+
+    /// Distance when moving diagonally costs the same as along the axes
+    pub fn chebyshev(&self, other: &Self) -> T {
+        return abs_diff(self.x, other.x).max(abs_diff(self.y, other.y));
+    }
+
+    /// Coordinate of the cell at `index` of a grid `width` cells wide
+    ///
+    /// Panics if the coordinate doesn't fit into `T`
+    pub fn from_index(index: usize, width: usize) -> Self {
+        let cast = |v: usize| T::from(v).expect("grid coordinate doesn't fit into the type");
+        return Vec2::new(cast(index % width), cast(index / width));
+    }
+
+    /// Index of the cell in a grid `width` cells wide (`None` if the cell is outside of the grid
+    /// horizontally or is negative)
+    pub fn to_index(&self, width: usize) -> Option<usize> {
+        let x = self.x.to_usize()?;
+        let y = self.y.to_usize()?;
+
+        if x >= width {
+            return None;
+        }
+        return Some(y * width + x);
+    }
+}
+
+impl<T> Vec2<T>
+where
+    T: PrimInt + Signed,
+{
+    /// Cells sharing an edge (right, up, left, down)
+    pub fn neighbors4(&self) -> [Self; 4] {
+        let (one, zero) = (T::one(), T::zero());
+
+        return [
+            *self + Vec2::new(one, zero),
+            *self + Vec2::new(zero, one),
+            *self + Vec2::new(-one, zero),
+            *self + Vec2::new(zero, -one),
+        ];
+    }
+
+    /// Cells sharing an edge or a corner (counterclockwise starting from the right)
+    pub fn neighbors8(&self) -> [Self; 8] {
+        let (one, zero) = (T::one(), T::zero());
+
+        return [
+            *self + Vec2::new(one, zero),
+            *self + Vec2::new(one, one),
+            *self + Vec2::new(zero, one),
+            *self + Vec2::new(-one, one),
+            *self + Vec2::new(-one, zero),
+            *self + Vec2::new(-one, -one),
+            *self + Vec2::new(zero, -one),
+            *self + Vec2::new(one, -one),
+        ];
+    }
+}
+
+//-----------------------------------------------------------------------------
+
+impl<T> Vec3<T>
+where
+    T: PrimInt,
+{
+    /// Distance when moving only along the axes
+    pub fn manhattan(&self, other: &Self) -> T {
+        return abs_diff(self.x, other.x) + abs_diff(self.y, other.y) + abs_diff(self.z, other.z);
+    }
+
+    /// Distance when moving diagonally costs the same as along the axes
+    pub fn chebyshev(&self, other: &Self) -> T {
+        return abs_diff(self.x, other.x)
+            .max(abs_diff(self.y, other.y))
+            .max(abs_diff(self.z, other.z));
+    }
+
+    /// Coordinate of the cell at `index` of a grid `width` x `height` cells in size (stored layer
+    /// by layer)
+    ///
+    /// Panics if the coordinate doesn't fit into `T`
+    pub fn from_index(index: usize, width: usize, height: usize) -> Self {
+        let cast = |v: usize| T::from(v).expect("grid coordinate doesn't fit into the type");
+        let layer = width * height;
+
+        return Vec3::new(
+            cast(index % width),
+            cast(index % layer / width),
+            cast(index / layer),
+        );
+    }
+
+    /// Index of the cell in a grid `width` x `height` cells in size (`None` if the cell is outside
+    /// of the grid horizontally or vertically or is negative)
+    pub fn to_index(&self, width: usize, height: usize) -> Option<usize> {
+        let x = self.x.to_usize()?;
+        let y = self.y.to_usize()?;
+        let z = self.z.to_usize()?;
+
+        if x >= width || y >= height {
+            return None;
+        }
+        return Some((z * height + y) * width + x);
+    }
+}
+
+impl<T> Vec3<T>
+where
+    T: PrimInt + Signed,
+{
+    /// Cells sharing a face (+x, -x, +y, -y, +z, -z)
+    pub fn neighbors6(&self) -> [Self; 6] {
+        let (one, zero) = (T::one(), T::zero());
+
+        return [
+            *self + Vec3::new(one, zero, zero),
+            *self + Vec3::new(-one, zero, zero),
+            *self + Vec3::new(zero, one, zero),
+            *self + Vec3::new(zero, -one, zero),
+            *self + Vec3::new(zero, zero, one),
+            *self + Vec3::new(zero, zero, -one),
+        ];
+    }
+}
+
+//-----------------------------------------------------------------------------
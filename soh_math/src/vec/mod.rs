@@ -1,4 +1,5 @@
 //-----------------------------------------------------------------------------
+mod grid;
 mod vec2;
 mod vec3;
 mod vec4;
@@ -7,11 +8,32 @@ pub use vec2::*;
 pub use vec3::*;
 pub use vec4::*;
 //-----------------------------------------------------------------------------
+pub type Vec2i = Vec2<i32>;
+pub type Vec2u = Vec2<u32>;
+pub type Vec3i = Vec3<i32>;
+pub type Vec3u = Vec3<u32>;
+pub type Vec4i = Vec4<i32>;
+pub type Vec4u = Vec4<u32>;
+//-----------------------------------------------------------------------------
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    // Standard tests of the generated API
+    macro_impl_vec::impl_vec_tests!(Vec2, f32);
+    macro_impl_vec::impl_vec_tests!(Vec2, f64);
+    macro_impl_vec::impl_vec_tests!(Vec2, i64);
+    macro_impl_vec::impl_vec_tests!(Vec2, u32);
+    macro_impl_vec::impl_vec_tests!(Vec3, f32);
+    macro_impl_vec::impl_vec_tests!(Vec3, f64);
+    macro_impl_vec::impl_vec_tests!(Vec3, i64);
+    macro_impl_vec::impl_vec_tests!(Vec3, u32);
+    macro_impl_vec::impl_vec_tests!(Vec4, f32);
+    macro_impl_vec::impl_vec_tests!(Vec4, f64);
+    macro_impl_vec::impl_vec_tests!(Vec4, i64);
+    macro_impl_vec::impl_vec_tests!(Vec4, u32);
+
     #[test]
     fn test_vec2() {
         let v1 = Vec2 { x: 11.0, y: 30.0 };
@@ -23,6 +45,87 @@ mod tests {
         assert_eq!(v2 * 2.0, Vec2 { x: 39.0, y: -19.0 });
         assert_eq!(v1 / 2.0, Vec2 { x: 5.5, y: 15.0 });
         assert_eq!(v2 / 2.0, Vec2 { x: 9.75, y: -4.75 });
+
+        // Rotations
+        use std::f64::consts::{FRAC_PI_2, PI};
+        let eps = 1.0e-12;
+
+        assert_eq!(Vec2::new(1, 2).perp(), Vec2::new(-2, 1));
+        assert!((Vec2::from_angle(FRAC_PI_2) - Vec2::Y).len() < eps);
+        assert!((Vec2::new(-1.0, 0.0).angle() - PI).abs() < eps);
+        assert!((v1.rotated(FRAC_PI_2) - v1.perp()).len() < eps);
+        assert!((v1.rotated(v1.angle_between(&v2)).normalized() - v2.normalized()).len() < eps);
+
+        assert!((Vec2::<f64>::X.angle_between(&Vec2::Y) - FRAC_PI_2).abs() < eps);
+        assert!((Vec2::<f64>::Y.angle_between(&Vec2::X) + FRAC_PI_2).abs() < eps);
+
+        // Distances
+        let a = Vec2::new(1.0, 1.0);
+        let b = Vec2::new(4.0, 5.0);
+        assert_eq!(a.distance(&b), 5.0);
+        assert_eq!(a.distance2(&b), 25.0);
+        assert_eq!(Vec2::midpoint(&a, &b), Vec2::new(2.5, 3.0));
+        assert_eq!(
+            Vec2::midpoint(&Vec2::new(0, 2), &Vec2::new(4, 6)),
+            Vec2::new(2, 4)
+        );
+
+        assert_eq!(a.move_towards(&b, 2.5), Vec2::midpoint(&a, &b));
+        assert_eq!(a.move_towards(&b, 10.0), b);
+        assert_eq!(b.move_towards(&b, 1.0), b);
+
+        for i in 0..16 {
+            let angle = i as f64 * PI / 8.0 - PI + 0.1;
+            let v = Vec2::from_angle(angle) * 3.0;
+            assert!((v.angle() - angle).abs() < eps);
+            assert!((Vec2::X.rotated(angle) * 3.0 - v).len() < eps);
+        }
+    }
+
+    #[test]
+    fn test_grid() {
+        let a = Vec2i::new(1, -2);
+        let b = Vec2i::new(-3, 4);
+        assert_eq!(a.manhattan(&b), 10);
+        assert_eq!(a.chebyshev(&b), 6);
+        assert_eq!(Vec2u::new(1, 5).manhattan(&Vec2u::new(3, 2)), 5);
+        assert_eq!(Vec3i::new(0, 0, 0).chebyshev(&Vec3i::new(1, -7, 3)), 7);
+
+        assert_eq!(
+            a.neighbors4()
+                .iter()
+                .filter(|n| n.manhattan(&a) == 1)
+                .count(),
+            4
+        );
+        assert_eq!(
+            a.neighbors8()
+                .iter()
+                .filter(|n| n.chebyshev(&a) == 1)
+                .count(),
+            8
+        );
+        assert!(Vec3i::zero()
+            .neighbors6()
+            .iter()
+            .all(|n| n.manhattan(&Vec3i::zero()) == 1));
+
+        // Index <-> coordinate of row-major grids
+        let width = 7;
+        for index in 0..70 {
+            let pos = Vec2u::from_index(index, width);
+            assert_eq!(pos.to_index(width), Some(index));
+        }
+        assert_eq!(Vec2u::from_index(15, width), Vec2u::new(1, 2));
+        assert_eq!(Vec2i::new(-1, 0).to_index(width), None);
+        assert_eq!(Vec2i::new(7, 0).to_index(width), None);
+
+        for index in 0..60 {
+            let pos = Vec3i::from_index(index, 5, 3);
+            assert_eq!(pos.to_index(5, 3), Some(index));
+        }
+        assert_eq!(Vec3i::from_index(17, 5, 3), Vec3i::new(2, 0, 1));
+        assert_eq!(Vec3i::new(0, 3, 0).to_index(5, 3), None);
     }
 
     #[test]
@@ -158,6 +261,28 @@ mod tests {
             }
         );
     }
+
+    #[test]
+    fn test_hermite() {
+        let p0 = Vec2::new(0.0, 0.0);
+        let m0 = Vec2::new(1.0, 2.0);
+        let p1 = Vec2::new(3.0, -1.0);
+        let m1 = Vec2::new(-2.0, 0.5);
+
+        assert_eq!(crate::hermite(p0, m0, p1, m1, 0.0), p0);
+        assert_eq!(crate::hermite(p0, m0, p1, m1, 1.0), p1);
+
+        // Tangents at the endpoints
+        let h = 1.0e-6;
+        let start = (crate::hermite(p0, m0, p1, m1, h) - p0) / h;
+        let end = (p1 - crate::hermite(p0, m0, p1, m1, 1.0 - h)) / h;
+        assert!((start - m0).len() < 1.0e-4);
+        assert!((end - m1).len() < 1.0e-4);
+
+        // Tangents of a line along it give the line
+        let d = p1 - p0;
+        assert!((crate::hermite(p0, d, p1, d, 0.25) - crate::lerp(p0, p1, 0.25)).len() < 1.0e-12);
+    }
 }
 
 //-----------------------------------------------------------------------------
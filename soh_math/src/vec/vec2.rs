@@ -22,9 +22,103 @@ impl<T> Vec2<T>
 where
     T: num_traits::Num + Copy,
 {
+    /// Z component of the cross product of the vectors extended to 3D ( positive when `vec2` is
+    /// counterclockwise from `vec1` ), also the signed area of their parallelogram
     pub fn cross(vec1: Vec2<T>, vec2: Vec2<T>) -> T {
         return vec1.x * vec2.y - vec1.y * vec2.x;
     }
 }
 
+impl<T> Vec2<T>
+where
+    T: num_traits::Num + std::ops::Neg<Output = T> + Copy,
+{
+    /// Rotate by 90 degrees counterclockwise
+    pub fn perp(&self) -> Self {
+        return Vec2::new(-self.y, self.x);
+    }
+}
+
+impl<T> Vec2<T>
+where
+    T: num_traits::Float + crate::real::Real,
+{
+    /// Create a unit vector pointing at `angle` (in radians) from the x axis
+    pub fn from_angle(angle: T) -> Self {
+        let (sin, cos) = crate::real::Real::sin_cos(angle);
+        return Vec2::new(cos, sin);
+    }
+
+    /// Angle from the x axis in (-pi, pi]
+    pub fn angle(&self) -> T {
+        return crate::real::Real::atan2(self.y, self.x);
+    }
+
+    /// Signed angle of the rotation from `self` to `other` in (-pi, pi] (positive is
+    /// counterclockwise)
+    pub fn angle_between(&self, other: &Self) -> T {
+        return crate::real::Real::atan2(Vec2::cross(*self, *other), Vec2::dot(self, other));
+    }
+
+    /// Calculate and return a version of `self` rotated by `angle` (in radians) counterclockwise
+    pub fn rotated(&self, angle: T) -> Self {
+        let (sin, cos) = crate::real::Real::sin_cos(angle);
+        return Vec2::new(self.x * cos - self.y * sin, self.x * sin + self.y * cos);
+    }
+}
+
+//-----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use crate::*;
+
+    const EPS: f64 = 1.0e-10;
+
+    fn int_vec3() -> impl Strategy<Value = Vec3<i64>> {
+        return (-1000..1000i64, -1000..1000i64, -1000..1000i64)
+            .prop_map(|(x, y, z)| Vec3::new(x, y, z));
+    }
+
+    proptest! {
+        #[test]
+        fn prop_cross_int(a in int_vec3(), b in int_vec3()) {
+            /*
+             * Integer vectors ( voxel coordinates ) are exact
+             */
+            prop_assert_eq!(Vec3::cross(&a, &b), -Vec3::cross(&b, &a));
+            prop_assert_eq!(Vec3::cross(&a, &a), Vec3::zero());
+            prop_assert_eq!(Vec3::dot(&Vec3::cross(&a, &b), &a), 0);
+            prop_assert_eq!(Vec3::dot(&Vec3::cross(&a, &b), &b), 0);
+
+            let a = Vec2::new(a.x, a.y);
+            let b = Vec2::new(b.x, b.y);
+
+            prop_assert_eq!(Vec2::cross(a, b), -Vec2::cross(b, a));
+            prop_assert_eq!(Vec2::cross(a, a), 0);
+            prop_assert_eq!(Vec2::dot(&a.perp(), &a), 0);
+            prop_assert_eq!(Vec2::cross(a, a.perp()), a.len2());
+        }
+
+        #[test]
+        fn prop_cross_float(a in strategy::vec3(-5.0..5.0), b in strategy::vec3(-5.0..5.0)) {
+            /*
+             * Floats within the rounding error
+             */
+            prop_assert!((Vec3::cross(&a, &b) + Vec3::cross(&b, &a)).len() < EPS);
+            prop_assert_eq!(Vec3::cross(&a, &a), Vec3::zero());
+            prop_assert!(Vec3::dot(&Vec3::cross(&a, &b), &a).abs() < EPS);
+            prop_assert!(Vec3::dot(&Vec3::cross(&a, &b), &b).abs() < EPS);
+
+            let a = Vec2::new(a.x, a.y);
+            let b = Vec2::new(b.x, b.y);
+
+            prop_assert_eq!(Vec2::cross(a, b), -Vec2::cross(b, a));
+            prop_assert!((Vec2::cross(a, a.perp()) - a.len2()).abs() < EPS);
+        }
+    }
+}
+
 //-----------------------------------------------------------------------------
@@ -24,6 +24,8 @@ impl<T> Vec3<T>
 where
     T: num_traits::Num + Copy,
 {
+    /// Cross product, perpendicular to both vectors ( right handed ). Works for the integer
+    /// types too, so it's exact for the integer coordinates
     #[inline]
     pub fn cross(vec1: &Vec3<T>, vec2: &Vec3<T>) -> Vec3<T> {
         return Vec3 {
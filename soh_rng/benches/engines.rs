@@ -0,0 +1,54 @@
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use soh_rng::{Engine32, Engine64, Lcg, SplitMix, Xoshiro128SS, Xoshiro256SS};
+
+/// Number of values generated per iteration
+const BATCH: usize = 1024;
+
+fn bench_32<TRng: Engine32>(c: &mut Criterion, name: &str) {
+    let mut group = c.benchmark_group("engine32");
+    group.throughput(Throughput::Bytes((BATCH * 4) as u64));
+
+    let mut rng = TRng::new(0xdeadbeef);
+
+    group.bench_function(BenchmarkId::new("next", name), |b| {
+        b.iter(|| (0..BATCH).fold(0u32, |acc, _| acc ^ rng.next()))
+    });
+    group.bench_function(BenchmarkId::new("gen_f32", name), |b| {
+        b.iter(|| (0..BATCH).map(|_| rng.gen::<f32>()).sum::<f32>())
+    });
+    group.bench_function(BenchmarkId::new("gen_to", name), |b| {
+        b.iter(|| (0..BATCH).fold(0u32, |acc, _| acc ^ rng.gen_to(1000u32)))
+    });
+
+    group.finish();
+}
+
+fn bench_64<TRng: Engine64>(c: &mut Criterion, name: &str) {
+    let mut group = c.benchmark_group("engine64");
+    group.throughput(Throughput::Bytes((BATCH * 8) as u64));
+
+    let mut rng = TRng::new(0xdeadbeef);
+
+    group.bench_function(BenchmarkId::new("next", name), |b| {
+        b.iter(|| (0..BATCH).fold(0u64, |acc, _| acc ^ rng.next()))
+    });
+    group.bench_function(BenchmarkId::new("gen_f64", name), |b| {
+        b.iter(|| (0..BATCH).map(|_| rng.gen::<f64>()).sum::<f64>())
+    });
+    group.bench_function(BenchmarkId::new("gen_to", name), |b| {
+        b.iter(|| (0..BATCH).fold(0u64, |acc, _| acc ^ rng.gen_to(1000u64)))
+    });
+
+    group.finish();
+}
+
+fn engines(c: &mut Criterion) {
+    bench_32::<Lcg>(c, "Lcg");
+    bench_32::<Xoshiro128SS>(c, "Xoshiro128SS");
+
+    bench_64::<SplitMix>(c, "SplitMix");
+    bench_64::<Xoshiro256SS>(c, "Xoshiro256SS");
+}
+
+criterion_group!(benches, engines);
+criterion_main!(benches);
@@ -43,5 +43,5 @@ fn main() {
         print!("{} ", i);
     }
 
-    print!("\n");
+    println!();
 }
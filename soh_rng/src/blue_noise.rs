@@ -0,0 +1,196 @@
+//-----------------------------------------------------------------------------
+//! Blue noise tables ( dithering, stochastic transparency )
+//!
+//! Thresholding a table at `t` keeps a fraction `t` of the pixels and they are spread evenly,
+//! with no low frequency clumps. The tables tile seamlessly.
+//!
+//! source:
+//! Robert Ulichney, "The void-and-cluster method for dither array generation", 1993
+//-----------------------------------------------------------------------------
+use crate::{Engine, Word};
+//-----------------------------------------------------------------------------
+
+/// Standard deviation of the Gaussian energy filter in pixels
+const SIGMA: f64 = 1.5;
+
+/// The filter is cut off at this distance in pixels
+const RADIUS: usize = 6;
+
+/// Share of the pixels in the initial pattern
+const INITIAL_DENSITY: f64 = 0.1;
+
+//-----------------------------------------------------------------------------
+
+/// `width` x `height` table of values in (0, 1), row by row, every value appears once
+///
+/// Takes O(n^2) for n pixels, so generate it once ( 64x64 is the usual size )
+pub fn blue_noise<W, E>(rng: &mut E, width: usize, height: usize) -> Vec<f32>
+where
+    W: Word,
+    E: Engine<W>,
+{
+    let count = width * height;
+    if count == 0 {
+        return Vec::new();
+    }
+
+    let mut pattern = Pattern::new(width, height);
+
+    /*
+     * Random initial pattern
+     */
+    let mut indices = (0..count).collect::<Vec<_>>();
+    rng.shuffle(&mut indices);
+
+    let initial = ((count as f64 * INITIAL_DENSITY) as usize).clamp(1, count);
+    for &idx in &indices[..initial] {
+        pattern.toggle(idx);
+    }
+
+    /*
+     * Move the points from the tightest clusters to the largest voids until it's uniform
+     * ( converges long before the iteration limit in practice )
+     */
+    for _ in 0..count {
+        let cluster = pattern.tightest_cluster();
+        pattern.toggle(cluster);
+
+        let void = pattern.largest_void();
+        pattern.toggle(void);
+
+        if void == cluster {
+            break;
+        }
+    }
+
+    let mut ranks = vec![0; count];
+
+    /*
+     * Rank the initial points by removing the tightest clusters
+     */
+    let mut removing = pattern.clone();
+    for rank in (0..initial).rev() {
+        let cluster = removing.tightest_cluster();
+        removing.toggle(cluster);
+        ranks[cluster] = rank;
+    }
+
+    /*
+     * Rank the rest by filling the largest voids
+     */
+    for rank in initial..count {
+        let void = pattern.largest_void();
+        pattern.toggle(void);
+        ranks[void] = rank;
+    }
+
+    return ranks
+        .into_iter()
+        .map(|rank| ((rank as f64 + 0.5) / count as f64) as f32)
+        .collect();
+}
+
+/// [blue_noise] as an image
+#[cfg(feature = "math")]
+pub fn blue_noise_image<W, E>(
+    rng: &mut E,
+    width: usize,
+    height: usize,
+) -> soh_math::image::ImageBuffer<f32>
+where
+    W: Word,
+    E: Engine<W>,
+{
+    let values = blue_noise(rng, width, height);
+    return soh_math::image::ImageBuffer::from_vec(width, height, values);
+}
+
+//-----------------------------------------------------------------------------
+
+/// Binary pattern on a torus with the Gaussian filtered energy of its points
+#[derive(Clone)]
+struct Pattern {
+    width: usize,
+    height: usize,
+    points: Vec<bool>,
+    energy: Vec<f64>,
+    /// Filter weights for the horizontal and vertical offsets
+    offsets_x: Vec<(usize, f64)>,
+    offsets_y: Vec<(usize, f64)>,
+}
+
+impl Pattern {
+    fn new(width: usize, height: usize) -> Self {
+        return Pattern {
+            width,
+            height,
+            points: vec![false; width * height],
+            energy: vec![0.0; width * height],
+            offsets_x: filter_offsets(width),
+            offsets_y: filter_offsets(height),
+        };
+    }
+
+    /// Add or remove the point and update the energy around it
+    fn toggle(&mut self, idx: usize) {
+        self.points[idx] = !self.points[idx];
+        let sign = if self.points[idx] { 1.0 } else { -1.0 };
+
+        let (x, y) = (idx % self.width, idx / self.width);
+        for &(dy, wy) in &self.offsets_y {
+            let row = (y + dy) % self.height * self.width;
+            for &(dx, wx) in &self.offsets_x {
+                self.energy[row + (x + dx) % self.width] += sign * wx * wy;
+            }
+        }
+    }
+
+    fn tightest_cluster(&self) -> usize {
+        return self.extreme(true, |a, b| a > b);
+    }
+
+    fn largest_void(&self) -> usize {
+        return self.extreme(false, |a, b| a < b);
+    }
+
+    /// Point ( or empty pixel ) with the extreme energy, the first one on ties
+    fn extreme(&self, point: bool, is_better: impl Fn(f64, f64) -> bool) -> usize {
+        let mut best = None;
+
+        for (idx, &energy) in self.energy.iter().enumerate() {
+            if self.points[idx] != point {
+                continue;
+            }
+            if best.is_none_or(|best_idx: usize| is_better(energy, self.energy[best_idx])) {
+                best = Some(idx);
+            }
+        }
+
+        return best.expect("the pattern is neither empty nor full");
+    }
+}
+
+/// Offsets within the filter radius wrapped to `[0, size)`, each one once, with their weights
+///
+/// The 2D Gaussian is separable, so the weight of (dx, dy) is the product of the two
+fn filter_offsets(size: usize) -> Vec<(usize, f64)> {
+    let radius = RADIUS.min((size - 1) / 2) as isize;
+    let size = size as isize;
+
+    let mut offsets = (-radius..=radius)
+        .map(|d| {
+            let weight = (-(d * d) as f64 / (2.0 * SIGMA * SIGMA)).exp();
+            return (d.rem_euclid(size) as usize, weight);
+        })
+        .collect::<Vec<_>>();
+
+    // Even sizes smaller than the filter: the opposite offset is the same pixel
+    if size <= 2 * RADIUS as isize && size % 2 == 0 {
+        let d = size / 2;
+        offsets.push((d as usize, (-(d * d) as f64 / (2.0 * SIGMA * SIGMA)).exp()));
+    }
+
+    return offsets;
+}
+
+//-----------------------------------------------------------------------------
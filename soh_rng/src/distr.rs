@@ -0,0 +1,80 @@
+//-----------------------------------------------------------------------------
+//! Transforms from uniformly distributed numbers to other distributions
+//-----------------------------------------------------------------------------
+use num_traits::{Float, FloatConst};
+//-----------------------------------------------------------------------------
+
+/// Standard normal distributed number from two uniform numbers in `[0, 1)`
+/// ( Box-Muller transform )
+pub(crate) fn box_muller<T>(u1: T, u2: T) -> T
+where
+    T: Float + FloatConst,
+{
+    let two = T::one() + T::one();
+
+    // `ln` needs a number in (0, 1]
+    let u1 = (T::one() - u1).max(T::min_positive_value());
+
+    return (-two * u1.ln()).sqrt() * (two * T::PI() * u2).cos();
+}
+
+//-----------------------------------------------------------------------------
+// soh_math types
+#[cfg(feature = "math")]
+use soh_math::{Mat3, Quaternion, Vec2, Vec3};
+
+/// Vector with standard normal distributed components
+#[cfg(feature = "math")]
+pub(crate) fn gaussian_vec2<T, F>(mut gaussian: F) -> Vec2<T>
+where
+    F: FnMut() -> T,
+{
+    let x = gaussian();
+    let y = gaussian();
+
+    return Vec2 { x, y };
+}
+
+/// Vector with standard normal distributed components
+#[cfg(feature = "math")]
+pub(crate) fn gaussian_vec3<T, F>(mut gaussian: F) -> Vec3<T>
+where
+    F: FnMut() -> T,
+{
+    let x = gaussian();
+    let y = gaussian();
+    let z = gaussian();
+
+    return Vec3 { x, y, z };
+}
+
+/// Unit quaternion uniformly distributed over SO(3) from three uniform numbers in `[0, 1)`
+///
+/// source:
+/// Ken Shoemake, "Uniform random rotations", Graphics Gems III
+#[cfg(feature = "math")]
+pub(crate) fn unit_quat<T>(u1: T, u2: T, u3: T) -> Quaternion<T>
+where
+    T: Float + FloatConst,
+{
+    let two_pi = T::PI() + T::PI();
+
+    let r1 = (T::one() - u1).sqrt();
+    let r2 = u1.sqrt();
+
+    let (sin1, cos1) = (two_pi * u2).sin_cos();
+    let (sin2, cos2) = (two_pi * u3).sin_cos();
+
+    return Quaternion::new(r2 * cos2, Vec3::new(r1 * sin1, r1 * cos1, r2 * sin2));
+}
+
+/// Right-handed orthonormal basis taken from the columns of a rotation matrix
+#[cfg(feature = "math")]
+pub(crate) fn orthonormal_basis<T>(rotation: Mat3<T>) -> [Vec3<T>; 3]
+where
+    T: Copy,
+{
+    return [rotation.col(0), rotation.col(1), rotation.col(2)];
+}
+
+//-----------------------------------------------------------------------------
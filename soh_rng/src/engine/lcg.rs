@@ -1,11 +1,11 @@
-use super::Engine32;
+use super::Engine;
 
 #[derive(Default)]
 pub struct Lcg<const MUL: u32, const ADD: u32> {
     state: u32,
 }
 
-impl<const MUL: u32, const ADD: u32> Engine32 for Lcg<MUL, ADD> {
+impl<const MUL: u32, const ADD: u32> Engine<u32> for Lcg<MUL, ADD> {
     fn set_seed(&mut self, seed: u32) {
         self.state = seed;
     }
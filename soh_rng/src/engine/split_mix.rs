@@ -1,11 +1,11 @@
-use super::Engine64;
+use super::Engine;
 
 #[derive(Default)]
 pub struct SplitMix {
     state: u64,
 }
 
-impl Engine64 for SplitMix {
+impl Engine<u64> for SplitMix {
     fn set_seed(&mut self, seed: u64) {
         self.state = seed;
     }
@@ -1,10 +1,59 @@
+use crate::distr;
 use crate::gen_trait::*;
+use num_traits::{Float, FloatConst};
 
-pub trait Engine32: Default {
-    fn set_seed(&mut self, seed: u32);
-    fn next(&mut self) -> u32;
+#[cfg(feature = "math")]
+use soh_math::{real::Real, Mat3, Quaternion, Vec2, Vec3};
 
-    fn new(seed: u32) -> Self {
+//-----------------------------------------------------------------------------
+// Word
+
+/// Output word of an engine ( `u32` or `u64` )
+pub trait Word: RandomlyGenerated<Self> + Copy {
+    /// Truncating conversion from `u64`
+    fn from_u64(value: u64) -> Self;
+
+    /// Truncating conversion from `usize`
+    fn from_usize(value: usize) -> Self;
+
+    /// Truncating conversion to `usize`
+    fn to_usize(self) -> usize;
+}
+
+macro_rules! impl_word {
+    ($($t:ty)*) => {
+        $(
+            impl Word for $t {
+                #[inline(always)]
+                fn from_u64(value: u64) -> Self {
+                    return value as $t;
+                }
+
+                #[inline(always)]
+                fn from_usize(value: usize) -> Self {
+                    return value as $t;
+                }
+
+                #[inline(always)]
+                fn to_usize(self) -> usize {
+                    return self as usize;
+                }
+            }
+        )*
+    }
+}
+
+impl_word!(u32 u64);
+
+//-----------------------------------------------------------------------------
+// Engine
+
+/// Random number engine producing words of type `W`
+pub trait Engine<W: Word>: Default {
+    fn set_seed(&mut self, seed: W);
+    fn next(&mut self) -> W;
+
+    fn new(seed: W) -> Self {
         let mut rng = Self::default();
         rng.set_seed(seed);
         return rng;
@@ -16,66 +65,157 @@ pub trait Engine32: Default {
             .unwrap()
             .as_secs();
 
-        return Self::new(t as u32);
+        return Self::new(W::from_u64(t));
+    }
+
+    fn gen<T: RandomlyGenerated<W>>(&mut self) -> T {
+        return T::from_rand(self.next());
+    }
+
+    fn gen_to<T: RandomlyGenerated<W>>(&mut self, to: T) -> T {
+        return T::from_rand_to(self.next(), to);
+    }
+
+    fn gen_range<T: RandomlyGenerated<W>>(&mut self, from: T, to: T) -> T {
+        return T::from_rand_range(self.next(), from, to);
+    }
+
+    /// Standard normal distributed number ( mean 0, variance 1 )
+    fn gen_gaussian<T>(&mut self) -> T
+    where
+        T: RandomlyGenerated<W> + Float + FloatConst,
+    {
+        let u1 = self.gen();
+        let u2 = self.gen();
+        return distr::box_muller(u1, u2);
+    }
+
+    /// Vector with standard normal distributed components ( isotropic )
+    #[cfg(feature = "math")]
+    fn gen_gaussian_vec2<T>(&mut self) -> Vec2<T>
+    where
+        T: RandomlyGenerated<W> + Float + FloatConst,
+    {
+        return distr::gaussian_vec2(|| self.gen_gaussian());
     }
 
-    fn gen<T: RandomlyGenerated32>(&mut self) -> T {
-        return RandomlyGenerated32::from_rand_32(self.next());
+    /// Vector with standard normal distributed components ( isotropic )
+    #[cfg(feature = "math")]
+    fn gen_gaussian_vec3<T>(&mut self) -> Vec3<T>
+    where
+        T: RandomlyGenerated<W> + Float + FloatConst,
+    {
+        return distr::gaussian_vec3(|| self.gen_gaussian());
     }
 
-    fn gen_to<T: RandomlyGenerated32>(&mut self, to: T) -> T {
-        return RandomlyGenerated32::from_rand_32_to(self.next(), to);
+    /// Unit quaternion uniformly distributed over all rotations
+    #[cfg(feature = "math")]
+    fn gen_unit_quat<T>(&mut self) -> Quaternion<T>
+    where
+        T: RandomlyGenerated<W> + Float + FloatConst,
+    {
+        let u1 = self.gen();
+        let u2 = self.gen();
+        let u3 = self.gen();
+        return distr::unit_quat(u1, u2, u3);
     }
 
-    fn gen_range<T: RandomlyGenerated32>(&mut self, from: T, to: T) -> T {
-        return RandomlyGenerated32::from_rand_32_range(self.next(), from, to);
+    /// Rotation matrix uniformly distributed over all rotations
+    #[cfg(feature = "math")]
+    fn gen_rotation<T>(&mut self) -> Mat3<T>
+    where
+        T: RandomlyGenerated<W> + Float + Real + FloatConst + std::iter::Sum + From<f32>,
+    {
+        return Mat3::from_quat(self.gen_unit_quat());
+    }
+
+    /// Uniformly oriented right-handed orthonormal basis
+    #[cfg(feature = "math")]
+    fn gen_orthonormal_basis<T>(&mut self) -> [Vec3<T>; 3]
+    where
+        T: RandomlyGenerated<W> + Float + Real + FloatConst + std::iter::Sum + From<f32>,
+    {
+        return distr::orthonormal_basis(self.gen_rotation());
     }
 
     /// Fisher-Yates shuffle
     fn shuffle<T>(&mut self, array: &mut [T]) {
         for i in (0..array.len()).rev() {
-            let j = self.gen_to(i as u32 + 1);
-            array.swap(i, j as usize);
+            let j = self.gen_to(W::from_usize(i + 1));
+            array.swap(i, j.to_usize());
         }
     }
 }
 
-pub trait Engine64: Default {
-    fn set_seed(&mut self, seed: u64);
-    fn next(&mut self) -> u64;
+//-----------------------------------------------------------------------------
+// Word size shorthands
+
+/// Engine producing 32 bit words
+///
+/// Implemented for every `Engine<u32>`
+pub trait Engine32: Engine<u32> {}
+impl<E: Engine<u32>> Engine32 for E {}
+
+/// Engine producing 64 bit words
+///
+/// Implemented for every `Engine<u64>`
+pub trait Engine64: Engine<u64> {}
+impl<E: Engine<u64>> Engine64 for E {}
+
+//-----------------------------------------------------------------------------
+// Adapters between word sizes
+//
+// Generic blanket impls in both directions would overlap, so the conversion
+// is explicit through a wrapper type
+
+/// 64 bit engine made of two consecutive outputs of a 32 bit engine
+#[derive(Default)]
+pub struct Widen<E>(E);
+
+impl<E> Widen<E> {
+    pub const fn wrap(engine: E) -> Self {
+        return Widen(engine);
+    }
 
-    fn new(seed: u64) -> Self {
-        let mut rng = Self::default();
-        rng.set_seed(seed);
-        return rng;
+    pub fn into_inner(self) -> E {
+        return self.0;
     }
+}
 
-    fn new_from_time() -> Self {
-        let t = std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .unwrap()
-            .as_secs();
+impl<E: Engine<u32>> Engine<u64> for Widen<E> {
+    fn set_seed(&mut self, seed: u64) {
+        self.0.set_seed((seed ^ (seed >> 32)) as u32);
+    }
 
-        return Self::new(t);
+    fn next(&mut self) -> u64 {
+        let hi = self.0.next() as u64;
+        let lo = self.0.next() as u64;
+        return (hi << 32) | lo;
     }
+}
 
-    fn gen<T: RandomlyGenerated64>(&mut self) -> T {
-        return RandomlyGenerated64::from_rand_64(self.next());
+/// 32 bit engine taking the high bits of a 64 bit engine
+#[derive(Default)]
+pub struct Narrow<E>(E);
+
+impl<E> Narrow<E> {
+    pub const fn wrap(engine: E) -> Self {
+        return Narrow(engine);
     }
 
-    fn gen_to<T: RandomlyGenerated64>(&mut self, to: T) -> T {
-        return RandomlyGenerated64::from_rand_64_to(self.next(), to);
+    pub fn into_inner(self) -> E {
+        return self.0;
     }
+}
 
-    fn gen_range<T: RandomlyGenerated64>(&mut self, from: T, to: T) -> T {
-        return RandomlyGenerated64::from_rand_64_range(self.next(), from, to);
+impl<E: Engine<u64>> Engine<u32> for Narrow<E> {
+    fn set_seed(&mut self, seed: u32) {
+        self.0.set_seed(seed as u64);
     }
 
-    /// Fisher-Yates shuffle
-    fn shuffle<T>(&mut self, array: &mut [T]) {
-        for i in (0..array.len()).rev() {
-            let j = self.gen_to(i + 1);
-            array.swap(i, j);
-        }
+    fn next(&mut self) -> u32 {
+        return (self.0.next() >> 32) as u32;
     }
 }
+
+//-----------------------------------------------------------------------------
@@ -1,11 +1,11 @@
-use super::Engine32;
+use super::Engine;
 
 #[derive(Default)]
 pub struct Xoshiro128SS {
     state: [u32; 4],
 }
 
-impl Engine32 for Xoshiro128SS {
+impl Engine<u32> for Xoshiro128SS {
     fn set_seed(&mut self, seed: u32) {
         let mut lcg = super::Lcg::new(seed);
         self.state.iter_mut().for_each(|s| *s = lcg.gen::<u32>());
@@ -1,11 +1,11 @@
-use super::Engine64;
+use super::Engine;
 
 #[derive(Default)]
 pub struct Xoshiro256SS {
     state: [u64; 4],
 }
 
-impl Engine64 for Xoshiro256SS {
+impl Engine<u64> for Xoshiro256SS {
     fn set_seed(&mut self, seed: u64) {
         let mut sm = super::SplitMix::new(seed);
         self.state.iter_mut().for_each(|s| *s = sm.gen::<u64>());
@@ -187,3 +187,57 @@ impl RandomlyGenerated64 for f64 {
         return f64::from_rand_64(rnum) * to;
     }
 }
+
+//-----------------------------------------------------------------------------
+// Word generic versions
+
+/// A trait for types that can be generated randomly from a random word ( `u32` or `u64` ).
+///
+/// Implemented for every type implementing [`RandomlyGenerated32`] / [`RandomlyGenerated64`].
+pub trait RandomlyGenerated<W>
+where
+    Self: Sized,
+{
+    /// Generates a value of the implementing type from a random word.
+    fn from_rand(rnum: W) -> Self;
+
+    /// Generates a value of the implementing type from a random word, scaled to a maximum value.
+    fn from_rand_to(rnum: W, to: Self) -> Self;
+
+    /// Generates a value of the implementing type from a random word, within a specified range.
+    fn from_rand_range(rnum: W, from: Self, to: Self) -> Self;
+}
+
+impl<T: RandomlyGenerated32> RandomlyGenerated<u32> for T {
+    #[inline(always)]
+    fn from_rand(rnum: u32) -> T {
+        return T::from_rand_32(rnum);
+    }
+
+    #[inline(always)]
+    fn from_rand_to(rnum: u32, to: T) -> T {
+        return T::from_rand_32_to(rnum, to);
+    }
+
+    #[inline(always)]
+    fn from_rand_range(rnum: u32, from: T, to: T) -> T {
+        return T::from_rand_32_range(rnum, from, to);
+    }
+}
+
+impl<T: RandomlyGenerated64> RandomlyGenerated<u64> for T {
+    #[inline(always)]
+    fn from_rand(rnum: u64) -> T {
+        return T::from_rand_64(rnum);
+    }
+
+    #[inline(always)]
+    fn from_rand_to(rnum: u64, to: T) -> T {
+        return T::from_rand_64_to(rnum, to);
+    }
+
+    #[inline(always)]
+    fn from_rand_range(rnum: u64, from: T, to: T) -> T {
+        return T::from_rand_64_range(rnum, from, to);
+    }
+}
@@ -0,0 +1,93 @@
+//-----------------------------------------------------------------------------
+//! Stateless hashes of integers ( noise lattices, procedural placement )
+//!
+//! Unlike the engines they don't carry state: the same coordinates always give the same value
+//! and nearby coordinates give decorrelated ones. The 32 bit hashes only use 32 bit integer
+//! operations, so shaders can reproduce them:
+//!
+//! ```glsl
+//! uint pcg_hash(uint v) {
+//!     uint state = v * 747796405u + 2891336453u;
+//!     uint word = ((state >> ((state >> 28u) + 4u)) ^ state) * 277803737u;
+//!     return (word >> 22u) ^ word;
+//! }
+//!
+//! uint hash32_2d(int x, int y, uint seed) {
+//!     return pcg_hash(uint(x) + pcg_hash(uint(y) + pcg_hash(seed)));
+//! }
+//! ```
+//!
+//! source:
+//! Mark Jarzynski, Marc Olano, "Hash Functions for GPU Rendering", JCGT 2020
+//-----------------------------------------------------------------------------
+
+/// PCG hash, the best quality to speed ratio of the 32 bit hashes
+pub fn pcg_hash(v: u32) -> u32 {
+    let state = v.wrapping_mul(747796405).wrapping_add(2891336453);
+    let word = ((state >> ((state >> 28) + 4)) ^ state).wrapping_mul(277803737);
+    return (word >> 22) ^ word;
+}
+
+/// Thomas Wang's integer hash, cheaper than [pcg_hash] but with visible patterns in low bits
+pub fn wang_hash(mut v: u32) -> u32 {
+    v = (v ^ 61) ^ (v >> 16);
+    v = v.wrapping_mul(9);
+    v ^= v >> 4;
+    v = v.wrapping_mul(0x27d4_eb2d);
+    v ^= v >> 15;
+    return v;
+}
+
+/// SplitMix64 finalizer
+pub fn mix64(mut v: u64) -> u64 {
+    v = (v ^ (v >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    v = (v ^ (v >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    return v ^ (v >> 31);
+}
+
+//-----------------------------------------------------------------------------
+// 32 bit
+
+pub fn hash32(x: u32) -> u32 {
+    return pcg_hash(x);
+}
+
+pub fn hash32_2d(x: i32, y: i32, seed: u32) -> u32 {
+    return pcg_hash((x as u32).wrapping_add(pcg_hash((y as u32).wrapping_add(pcg_hash(seed)))));
+}
+
+pub fn hash32_3d(x: i32, y: i32, z: i32, seed: u32) -> u32 {
+    return hash32_2d(x, y, pcg_hash((z as u32).wrapping_add(pcg_hash(seed))));
+}
+
+//-----------------------------------------------------------------------------
+// 64 bit
+
+pub fn hash64(x: u64) -> u64 {
+    return mix64(x);
+}
+
+pub fn hash64_2d(x: i64, y: i64, seed: u64) -> u64 {
+    let h = mix64(seed ^ (x as u64).wrapping_mul(0x9e37_79b9_7f4a_7c15));
+    return mix64(h ^ (y as u64).wrapping_mul(0xc2b2_ae3d_27d4_eb4f));
+}
+
+pub fn hash64_3d(x: i64, y: i64, z: i64, seed: u64) -> u64 {
+    let h = hash64_2d(x, y, seed);
+    return mix64(h ^ (z as u64).wrapping_mul(0x1656_67b1_9e37_79f9));
+}
+
+//-----------------------------------------------------------------------------
+// Conversions
+
+/// Map a hash to [0, 1)
+pub fn unit_f32(h: u32) -> f32 {
+    return (h >> 8) as f32 / (1u32 << 24) as f32;
+}
+
+/// Map a hash to [0, 1)
+pub fn unit_f64(h: u64) -> f64 {
+    return (h >> 11) as f64 / (1u64 << 53) as f64;
+}
+
+//-----------------------------------------------------------------------------
@@ -1,10 +1,21 @@
+mod distr;
 mod engine;
 mod gen_trait;
 
+pub mod blue_noise;
+pub mod hash;
+pub mod markov;
 pub mod prelude;
+#[cfg(feature = "math")]
+pub mod sample;
+pub mod weighted;
+
+#[cfg(test)]
+mod quality;
 
 pub use engine::generators::*;
-pub use engine::{Engine32, Engine64};
+pub use engine::{Engine, Engine32, Engine64, Narrow, Widen, Word};
+pub use gen_trait::{RandomlyGenerated, RandomlyGenerated32, RandomlyGenerated64};
 
 pub type RNG32 = Lcg;
 pub type RNG64 = SplitMix;
@@ -89,4 +100,224 @@ mod tests {
             test_func::<Xoshiro256SS>();
         }
     }
+
+    #[test]
+    fn test_gaussian() {
+        let mut rng = RNG64::new(0xdeadbeef);
+
+        let samples = (0..NUM_OF_TRIES)
+            .map(|_| rng.gen_gaussian::<f64>())
+            .collect::<Vec<_>>();
+
+        let mean = samples.iter().sum::<f64>() / NUM_OF_TRIES as f64;
+        let var =
+            samples.iter().map(|x| (x - mean) * (x - mean)).sum::<f64>() / NUM_OF_TRIES as f64;
+
+        assert!(samples.iter().all(|x| x.is_finite()));
+        assert!(mean.abs() < 0.05);
+        assert!((var - 1.0).abs() < 0.05);
+    }
+
+    #[cfg(feature = "math")]
+    #[test]
+    fn test_gaussian_vec() {
+        let mut rng = RNG32::new(0xdeadbeef);
+
+        let mut sum = soh_math::Vec3::<f64>::zero();
+        for _ in 0..NUM_OF_TRIES {
+            sum += rng.gen_gaussian_vec3::<f64>();
+        }
+
+        // Isotropic: the mean direction should vanish
+        assert!((sum / NUM_OF_TRIES as f64).len() < 0.05);
+    }
+
+    #[cfg(feature = "math")]
+    #[test]
+    fn test_random_rotation() {
+        let mut rng = RNG64::new(0xdeadbeef);
+
+        for _ in 0..NUM_OF_TRIES {
+            let quat = rng.gen_unit_quat::<f64>();
+            assert!(quat.is_normalized(1e-9));
+
+            let [x, y, z] = rng.gen_orthonormal_basis::<f32>();
+            assert!((x.len() - 1.0).abs() < 1e-5);
+            assert!((y.len() - 1.0).abs() < 1e-5);
+            assert!(soh_math::Vec3::dot(&x, &y).abs() < 1e-5);
+            assert!((soh_math::Vec3::cross(&x, &y) - z).len() < 1e-5);
+        }
+    }
+
+    #[test]
+    fn test_hash() {
+        // Reference values of the GPU versions
+        assert_eq!(hash::pcg_hash(0), 129708002);
+        assert_eq!(hash::pcg_hash(1), 2831084092);
+        assert_eq!(hash::wang_hash(0), 3232319850);
+        assert_eq!(hash::wang_hash(1), 663891101);
+
+        assert_eq!(hash::hash32_2d(-3, 7, 42), hash::hash32_2d(-3, 7, 42));
+        assert_ne!(hash::hash32_2d(-3, 7, 42), hash::hash32_2d(7, -3, 42));
+        assert_ne!(hash::hash32_3d(1, 2, 3, 0), hash::hash32_3d(1, 2, 3, 1));
+        assert_ne!(hash::hash64_2d(1, 2, 0), hash::hash64_2d(2, 1, 0));
+
+        /*
+         * Neighbouring coordinates are decorrelated: uniform mean and about half of the bits
+         * differ
+         */
+        let mut sum = 0.0;
+        let mut flipped = 0;
+        for i in 0..NUM_OF_TRIES as i64 {
+            let h = hash::hash64_3d(i, 0, 0, 7);
+            sum += hash::unit_f64(h);
+            flipped += (h ^ hash::hash64_3d(i + 1, 0, 0, 7)).count_ones();
+
+            let u = hash::unit_f32(hash::hash32_2d(i as i32, 5, 7));
+            assert!((0.0..1.0).contains(&u));
+        }
+
+        assert!((sum / NUM_OF_TRIES as f64 - 0.5).abs() < 0.01);
+        assert!((flipped as f64 / NUM_OF_TRIES as f64 - 32.0).abs() < 0.5);
+    }
+
+    #[test]
+    fn test_weighted() {
+        assert!(weighted::WeightedIndex::new([]).is_none());
+        assert!(weighted::WeightedIndex::new([0.0, 0.0]).is_none());
+        assert!(weighted::WeightedIndex::new([1.0, -1.0]).is_none());
+
+        let weights = weighted::WeightedIndex::new([1.0, 0.0, 3.0]).unwrap();
+        assert_eq!(weights.probability(2), 0.75);
+
+        let mut rng = RNG64::new(0xdeadbeef);
+        let mut counts = [0; 3];
+        for _ in 0..NUM_OF_TRIES {
+            counts[weights.sample(&mut rng)] += 1;
+        }
+
+        assert_eq!(counts[1], 0);
+        assert!((counts[2] as f64 / NUM_OF_TRIES as f64 - 0.75).abs() < 0.02);
+    }
+
+    #[test]
+    fn test_markov_chain() {
+        use markov::MarkovChain;
+
+        // Words start and end at `None`
+        let mut chain = MarkovChain::new();
+        for word in ["abc", "abd", "bcd"] {
+            let chars = word.chars().map(Some);
+            chain.observe_sequence(std::iter::once(None).chain(chars).chain([None]));
+        }
+
+        assert_eq!(chain.probability(&None, &Some('a')), 2.0 / 3.0);
+        assert_eq!(chain.probability(&Some('b'), &Some('c')), 2.0 / 3.0);
+        assert_eq!(chain.probability(&Some('c'), &Some('a')), 0.0);
+        assert_eq!(chain.states().count(), 5);
+
+        let mut rng = RNG32::new(0xdeadbeef);
+        for _ in 0..100 {
+            let word = chain
+                .walk(None, &mut rng)
+                .map_while(|c| c)
+                .collect::<String>();
+            assert!(["abc", "abd", "abcd", "bc", "bcd", "bd"].contains(&word.as_str()));
+        }
+
+        // Weighted state machine
+        let mut machine = MarkovChain::new();
+        machine.add_transition("idle", "walk", 3.0);
+        machine.add_transition("idle", "idle", 1.0);
+        machine.add_transition("walk", "idle", 1.0);
+
+        assert_eq!(machine.probability(&"idle", &"walk"), 0.75);
+        assert_eq!(machine.next(&"walk", &mut rng), Some("idle"));
+        assert_eq!(machine.next(&"run", &mut rng), None);
+        assert_eq!(machine.walk("idle", &mut rng).take(10).count(), 10);
+    }
+
+    #[test]
+    fn test_blue_noise() {
+        let (width, height) = (32, 32);
+        let table = blue_noise::blue_noise(&mut RNG64::new(0xdeadbeef), width, height);
+
+        // Deterministic for the same seed
+        assert_eq!(
+            table,
+            blue_noise::blue_noise(&mut RNG64::new(0xdeadbeef), width, height)
+        );
+
+        // Every value once
+        let mut ranks = table
+            .iter()
+            .map(|v| (v * table.len() as f32) as usize)
+            .collect::<Vec<_>>();
+        ranks.sort();
+        assert_eq!(ranks, (0..table.len()).collect::<Vec<_>>());
+
+        // Sparse thresholds have no neighbouring points ( the table tiles )
+        let is_set = |x: usize, y: usize| table[(y % height) * width + x % width] < 1.0 / 16.0;
+        for y in 0..height {
+            for x in 0..width {
+                let neighbours = [(1, 0), (0, 1), (1, 1), (width - 1, 1)]
+                    .iter()
+                    .filter(|&&(dx, dy)| is_set(x + dx, y + dy))
+                    .count();
+                assert!(!is_set(x, y) || neighbours == 0);
+            }
+        }
+
+        // Small and degenerate sizes
+        assert_eq!(blue_noise::blue_noise(&mut RNG32::new(1), 0, 4), []);
+        assert_eq!(blue_noise::blue_noise(&mut RNG32::new(1), 1, 1), [0.5]);
+        assert_eq!(blue_noise::blue_noise(&mut RNG32::new(1), 2, 3).len(), 6);
+    }
+
+    #[cfg(feature = "math")]
+    #[test]
+    fn test_sample_sets() {
+        use soh_math::Vec2;
+
+        let in_unit_square = |p: &Vec2<f64>| (0.0..1.0).contains(&p.x) && (0.0..1.0).contains(&p.y);
+
+        assert_eq!(sample::halton(1, 2), 0.5);
+        assert_eq!(sample::halton(6, 2), 0.375);
+        assert!((sample::halton(5, 3) - 7.0 / 9.0).abs() < 1e-12);
+
+        // Deterministic for the same seed
+        let halton = sample::halton_jittered(&mut RNG64::new(0xdeadbeef), 64);
+        assert_eq!(
+            halton,
+            sample::halton_jittered(&mut RNG64::new(0xdeadbeef), 64)
+        );
+        assert_eq!(halton.len(), 64);
+        assert!(halton.iter().all(in_unit_square));
+
+        let stratified = sample::stratified(&mut RNG32::new(0xdeadbeef), 4, 3);
+        assert_eq!(stratified.len(), 12);
+        for (i, p) in stratified.iter().enumerate() {
+            let (x, y) = (i % 4, i / 4);
+            assert_eq!(((p.x * 4.0) as usize, (p.y * 3.0) as usize), (x, y));
+        }
+
+        let size = Vec2::new(10.0, 5.0);
+        let min_distance = 0.5;
+        let points = sample::poisson_disk(&mut RNG64::new(0xdeadbeef), size, min_distance, 30);
+        assert!(points
+            .iter()
+            .all(|p| (0.0..size.x).contains(&p.x) && (0.0..size.y).contains(&p.y)));
+        for (i, a) in points.iter().enumerate() {
+            for b in &points[i + 1..] {
+                assert!(a.distance(b) >= min_distance);
+            }
+        }
+
+        // Densely covered: no empty spot fits a disk of the double radius
+        let mut rng = RNG64::new(0xfeedbeef);
+        for _ in 0..NUM_OF_TRIES {
+            let p = Vec2::new(rng.gen::<f64>() * size.x, rng.gen::<f64>() * size.y);
+            assert!(points.iter().any(|q| q.distance(&p) < min_distance * 2.0));
+        }
+    }
 }
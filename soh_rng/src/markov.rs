@@ -0,0 +1,156 @@
+//-----------------------------------------------------------------------------
+//! Markov chains ( procedural names and words, varied AI behavior )
+//-----------------------------------------------------------------------------
+use crate::weighted::WeightedIndex;
+use crate::{Engine, RandomlyGenerated, Word};
+use std::collections::HashMap;
+use std::hash::Hash;
+//-----------------------------------------------------------------------------
+
+/// States with weighted transitions, the next state is sampled with the probability proportional
+/// to the weight of its transition
+///
+/// Built from observed sequences ( every observation adds 1 to the weight ) or from explicit
+/// weights like a state machine
+#[derive(Debug, Clone)]
+pub struct MarkovChain<S> {
+    transitions: HashMap<S, Transitions<S>>,
+}
+
+#[derive(Debug, Clone)]
+struct Transitions<S> {
+    /// Next states with their weights in the order they were added
+    next: Vec<(S, f64)>,
+    sampler: Option<WeightedIndex>,
+}
+
+//-----------------------------------------------------------------------------
+// Constructors
+impl<S> Default for MarkovChain<S> {
+    fn default() -> Self {
+        return MarkovChain {
+            transitions: HashMap::new(),
+        };
+    }
+}
+
+impl<S> MarkovChain<S>
+where
+    S: Eq + Hash + Clone,
+{
+    pub fn new() -> Self {
+        return Self::default();
+    }
+
+    /// Chain with the transitions between the consecutive states of `sequence`
+    pub fn from_sequence(sequence: impl IntoIterator<Item = S>) -> Self {
+        let mut chain = Self::new();
+        chain.observe_sequence(sequence);
+        return chain;
+    }
+}
+
+//-----------------------------------------------------------------------------
+// Building
+impl<S> MarkovChain<S>
+where
+    S: Eq + Hash + Clone,
+{
+    /// Add `weight` to the transition from `from` to `to`, negative and non-finite weights are
+    /// ignored
+    pub fn add_transition(&mut self, from: S, to: S, weight: f64) {
+        if !weight.is_finite() || weight < 0.0 {
+            return;
+        }
+
+        let transitions = self.transitions.entry(from).or_insert(Transitions {
+            next: Vec::new(),
+            sampler: None,
+        });
+
+        match transitions.next.iter_mut().find(|(state, _)| *state == to) {
+            Some((_, w)) => *w += weight,
+            None => transitions.next.push((to, weight)),
+        }
+
+        transitions.sampler = WeightedIndex::new(transitions.next.iter().map(|&(_, w)| w));
+    }
+
+    /// Observed transition from `from` to `to`
+    pub fn observe(&mut self, from: S, to: S) {
+        self.add_transition(from, to, 1.0);
+    }
+
+    /// Observe the transitions between the consecutive states of `sequence`
+    pub fn observe_sequence(&mut self, sequence: impl IntoIterator<Item = S>) {
+        let mut iter = sequence.into_iter();
+        let Some(mut prev) = iter.next() else {
+            return;
+        };
+
+        for state in iter {
+            self.observe(prev, state.clone());
+            prev = state;
+        }
+    }
+}
+
+//-----------------------------------------------------------------------------
+// Sampling
+impl<S> MarkovChain<S>
+where
+    S: Eq + Hash + Clone,
+{
+    /// Random next state, `None` if `state` has no transitions
+    pub fn next<W, E>(&self, state: &S, rng: &mut E) -> Option<S>
+    where
+        W: Word,
+        E: Engine<W>,
+        f64: RandomlyGenerated<W>,
+    {
+        let transitions = self.transitions.get(state)?;
+        let idx = transitions.sampler.as_ref()?.sample(rng);
+
+        return Some(transitions.next[idx].0.clone());
+    }
+
+    /// Random walk from `start` ( not included ) until a state without transitions
+    ///
+    /// Chains with cycles walk forever, limit them with `take`
+    pub fn walk<'a, W, E>(&'a self, start: S, rng: &'a mut E) -> impl Iterator<Item = S> + 'a
+    where
+        W: Word,
+        E: Engine<W>,
+        f64: RandomlyGenerated<W>,
+    {
+        let mut state = start;
+
+        return std::iter::from_fn(move || {
+            state = self.next(&state, rng)?;
+            return Some(state.clone());
+        });
+    }
+
+    /// Probability of the transition from `from` to `to`
+    pub fn probability(&self, from: &S, to: &S) -> f64 {
+        let Some(transitions) = self.transitions.get(from) else {
+            return 0.0;
+        };
+        let Some(sampler) = &transitions.sampler else {
+            return 0.0;
+        };
+
+        return transitions
+            .next
+            .iter()
+            .position(|(state, _)| state == to)
+            .map_or(0.0, |idx| sampler.probability(idx));
+    }
+
+    /// States with at least one transition
+    pub fn states(&self) -> impl Iterator<Item = &S> {
+        return self.transitions.keys();
+    }
+}
+
+//-----------------------------------------------------------------------------
@@ -1,3 +1,3 @@
 pub use crate::RNG32;
 pub use crate::RNG64;
-pub use crate::{Engine32, Engine64};
+pub use crate::{Engine, Engine32, Engine64};
@@ -0,0 +1,175 @@
+//-----------------------------------------------------------------------------
+//! Statistical quality checks run over every engine
+//!
+//! The checks are deliberately simple ( far from TestU01 ), they are here to
+//! catch broken engines and bad output scaling, not to rank good generators.
+//-----------------------------------------------------------------------------
+use crate::{Engine, RandomlyGenerated, Word};
+use crate::{Lcg, Narrow, SplitMix, Widen, Xoshiro128SS, Xoshiro256SS};
+//-----------------------------------------------------------------------------
+
+const SEEDS: [u64; 3] = [0, 0xdeadbeef, 0x0123_4567_89ab_cdef];
+
+/// Number of buckets for the chi-squared test
+const NUM_OF_BUCKETS: usize = 256;
+/// Expected number of samples per bucket
+const SAMPLES_PER_BUCKET: usize = 400;
+
+/// Number of samples for the Kolmogorov-Smirnov test
+const KS_SAMPLES: usize = 10_000;
+
+/// Birthday spacings: `m` birthdays in a year of `2^YEAR_BITS` days.
+/// Duplicate spacings are Poisson distributed with `lambda = m^3 / (4 * n)`
+const BIRTHDAYS: usize = 512;
+const YEAR_BITS: u32 = 24;
+const BIRTHDAY_ROUNDS: usize = 200;
+
+//-----------------------------------------------------------------------------
+// Statistics
+
+/// Pearson's chi-squared statistic for uniformly distributed buckets
+fn chi_squared(counts: &[usize]) -> f64 {
+    let total = counts.iter().sum::<usize>() as f64;
+    let expected = total / counts.len() as f64;
+
+    return counts
+        .iter()
+        .map(|&c| {
+            let d = c as f64 - expected;
+            d * d / expected
+        })
+        .sum();
+}
+
+/// Kolmogorov-Smirnov statistic of the samples against uniform `[0, 1)`
+fn ks_statistic(samples: &mut [f64]) -> f64 {
+    samples.sort_by(f64::total_cmp);
+
+    let n = samples.len() as f64;
+
+    return samples
+        .iter()
+        .enumerate()
+        .map(|(i, &x)| {
+            let above = (i + 1) as f64 / n - x;
+            let below = x - i as f64 / n;
+            above.max(below)
+        })
+        .fold(0.0, f64::max);
+}
+
+/// Number of repeated values among the spacings of sorted birthdays
+fn duplicate_spacings(birthdays: &mut [u32]) -> usize {
+    birthdays.sort_unstable();
+
+    let mut spacings = birthdays
+        .windows(2)
+        .map(|w| w[1] - w[0])
+        .collect::<Vec<_>>();
+    spacings.sort_unstable();
+
+    return spacings.windows(2).filter(|w| w[0] == w[1]).count();
+}
+
+//-----------------------------------------------------------------------------
+// Checks
+
+/// Accept the statistic if it lies within 5 standard deviations of the mean
+fn assert_within(name: &str, value: f64, mean: f64, std_dev: f64) {
+    let lo = mean - 5.0 * std_dev;
+    let hi = mean + 5.0 * std_dev;
+
+    assert!(
+        (lo..=hi).contains(&value),
+        "{name}: {value} is outside of [{lo}, {hi}]"
+    );
+}
+
+fn check_chi_squared(mut gen_bucket: impl FnMut() -> usize) {
+    let mut counts = [0usize; NUM_OF_BUCKETS];
+    for _ in 0..NUM_OF_BUCKETS * SAMPLES_PER_BUCKET {
+        counts[gen_bucket()] += 1;
+    }
+
+    // Chi-squared with k degrees of freedom has mean k and variance 2k
+    let dof = (NUM_OF_BUCKETS - 1) as f64;
+    assert_within("chi-squared", chi_squared(&counts), dof, (2.0 * dof).sqrt());
+}
+
+fn check_ks(mut gen_float: impl FnMut() -> f64) {
+    let mut samples = (0..KS_SAMPLES).map(|_| gen_float()).collect::<Vec<_>>();
+
+    assert!(samples.iter().all(|x| (0.0..1.0).contains(x)));
+
+    // Critical value for alpha = 0.001
+    let critical = 1.95 / (KS_SAMPLES as f64).sqrt();
+    let d = ks_statistic(&mut samples);
+
+    assert!(d < critical, "Kolmogorov-Smirnov: {d} >= {critical}");
+}
+
+fn check_birthday_spacings(mut gen_day: impl FnMut() -> u32) {
+    let mut birthdays = [0u32; BIRTHDAYS];
+
+    let mut duplicates = 0;
+    for _ in 0..BIRTHDAY_ROUNDS {
+        birthdays.iter_mut().for_each(|b| *b = gen_day());
+        duplicates += duplicate_spacings(&mut birthdays);
+    }
+
+    // Sum of Poisson variables is Poisson with the summed lambda
+    let m = BIRTHDAYS as f64;
+    let lambda = m * m * m / (4.0 * (1u64 << YEAR_BITS) as f64) * BIRTHDAY_ROUNDS as f64;
+    assert_within(
+        "birthday spacings",
+        duplicates as f64,
+        lambda,
+        lambda.sqrt(),
+    );
+}
+
+//-----------------------------------------------------------------------------
+
+fn check_engine<W, TRng>()
+where
+    W: Word,
+    TRng: Engine<W>,
+    u32: RandomlyGenerated<W>,
+    f64: RandomlyGenerated<W>,
+{
+    for seed in SEEDS {
+        let mut rng = TRng::new(W::from_u64(seed));
+
+        check_chi_squared(|| rng.gen_to(NUM_OF_BUCKETS as u32) as usize);
+        check_ks(|| rng.gen());
+        check_birthday_spacings(|| rng.gen::<u32>() >> (32 - YEAR_BITS));
+    }
+}
+
+#[test]
+fn test_lcg() {
+    check_engine::<u32, Lcg>();
+}
+
+#[test]
+fn test_xoshiro_128_ss() {
+    check_engine::<u32, Xoshiro128SS>();
+}
+
+#[test]
+fn test_split_mix() {
+    check_engine::<u64, SplitMix>();
+}
+
+#[test]
+fn test_xoshiro_256_ss() {
+    check_engine::<u64, Xoshiro256SS>();
+}
+
+#[test]
+fn test_adapters() {
+    check_engine::<u64, Widen<Xoshiro128SS>>();
+    check_engine::<u32, Narrow<Xoshiro256SS>>();
+}
+
+//-----------------------------------------------------------------------------
@@ -0,0 +1,157 @@
+//-----------------------------------------------------------------------------
+//! 2D sample point sets ( soft shadow and SSAO kernels, scattering )
+//-----------------------------------------------------------------------------
+use crate::{Engine, RandomlyGenerated, Word};
+use soh_math::Vec2;
+//-----------------------------------------------------------------------------
+
+/// Radical inverse of `index` in `base`, the `index`-th element of the Halton sequence
+pub fn halton(mut index: u32, base: u32) -> f64 {
+    let inv_base = 1.0 / base as f64;
+
+    let mut res = 0.0;
+    let mut scale = inv_base;
+
+    while index > 0 {
+        res += (index % base) as f64 * scale;
+        index /= base;
+        scale *= inv_base;
+    }
+
+    return res;
+}
+
+/// `count` points of the Halton sequence ( bases 2 and 3 ) in `[0, 1)^2` shifted by a random
+/// offset with wrap around, so the sets differ between seeds but keep the low discrepancy
+pub fn halton_jittered<W, E>(rng: &mut E, count: u32) -> Vec<Vec2<f64>>
+where
+    W: Word,
+    E: Engine<W>,
+    f64: RandomlyGenerated<W>,
+{
+    let offset = Vec2::new(rng.gen::<f64>(), rng.gen::<f64>());
+
+    return (1..=count)
+        .map(|i| {
+            let p = Vec2::new(halton(i, 2), halton(i, 3)) + offset;
+            return p.map(|x| x.fract());
+        })
+        .collect();
+}
+
+/// One random point in every cell of a `columns` x `rows` grid over `[0, 1)^2`, row by row
+pub fn stratified<W, E>(rng: &mut E, columns: u32, rows: u32) -> Vec<Vec2<f64>>
+where
+    W: Word,
+    E: Engine<W>,
+    f64: RandomlyGenerated<W>,
+{
+    let cell = Vec2::new(1.0 / columns as f64, 1.0 / rows as f64);
+
+    let mut res = Vec::with_capacity(columns as usize * rows as usize);
+    for y in 0..rows {
+        for x in 0..columns {
+            let jitter = Vec2::new(rng.gen::<f64>(), rng.gen::<f64>());
+            let p = Vec2::new(x as f64, y as f64) + jitter;
+
+            res.push(Vec2::mul(&p, &cell));
+        }
+    }
+
+    return res;
+}
+
+/// Points in `[0, size)` no closer than `min_distance` to each other and covering the area
+/// densely ( Poisson disk sampling )
+///
+/// `max_attempts` candidates are tried around every point before it's retired ( 30 is the usual
+/// choice )
+///
+/// source:
+/// Robert Bridson, "Fast Poisson Disk Sampling in Arbitrary Dimensions", SIGGRAPH 2007
+pub fn poisson_disk<W, E>(
+    rng: &mut E,
+    size: Vec2<f64>,
+    min_distance: f64,
+    max_attempts: u32,
+) -> Vec<Vec2<f64>>
+where
+    W: Word,
+    E: Engine<W>,
+    f64: RandomlyGenerated<W>,
+{
+    if size.x <= 0.0 || size.y <= 0.0 || min_distance <= 0.0 {
+        return Vec::new();
+    }
+
+    /*
+     * Background grid with cells small enough to hold at most one point
+     */
+    let cell_size = min_distance / std::f64::consts::SQRT_2;
+    let columns = (size.x / cell_size).ceil() as usize;
+    let rows = (size.y / cell_size).ceil() as usize;
+
+    let mut grid: Vec<Option<usize>> = vec![None; columns * rows];
+    let cell_of = |p: Vec2<f64>| {
+        let x = ((p.x / cell_size) as usize).min(columns - 1);
+        let y = ((p.y / cell_size) as usize).min(rows - 1);
+        return (x, y);
+    };
+
+    let mut points = Vec::new();
+    let mut active = Vec::new();
+
+    let first = Vec2::new(rng.gen::<f64>() * size.x, rng.gen::<f64>() * size.y);
+    let (x, y) = cell_of(first);
+    grid[y * columns + x] = Some(0);
+    points.push(first);
+    active.push(0);
+
+    /*
+     * Try candidates in the annulus [r, 2r) around a random active point
+     */
+    while !active.is_empty() {
+        let active_idx = rng.gen_to(W::from_usize(active.len())).to_usize();
+        let center = points[active[active_idx]];
+
+        let mut found = false;
+        for _ in 0..max_attempts {
+            let angle = rng.gen::<f64>() * std::f64::consts::TAU;
+            let radius = min_distance * (1.0 + rng.gen::<f64>());
+            let candidate = center + Vec2::from_angle(angle) * radius;
+
+            if candidate.x < 0.0
+                || candidate.y < 0.0
+                || candidate.x >= size.x
+                || candidate.y >= size.y
+            {
+                continue;
+            }
+
+            let (cx, cy) = cell_of(candidate);
+            let is_far = (cy.saturating_sub(2)..(cy + 3).min(rows)).all(|y| {
+                return (cx.saturating_sub(2)..(cx + 3).min(columns)).all(|x| {
+                    return grid[y * columns + x].is_none_or(|idx| {
+                        points[idx].distance2(&candidate) >= min_distance * min_distance
+                    });
+                });
+            });
+
+            if is_far {
+                grid[cy * columns + cx] = Some(points.len());
+                active.push(points.len());
+                points.push(candidate);
+                found = true;
+                break;
+            }
+        }
+
+        if !found {
+            active.swap_remove(active_idx);
+        }
+    }
+
+    return points;
+}
+
+//-----------------------------------------------------------------------------
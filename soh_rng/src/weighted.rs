@@ -0,0 +1,79 @@
+//-----------------------------------------------------------------------------
+use crate::{Engine, RandomlyGenerated, Word};
+//-----------------------------------------------------------------------------
+
+/// Sampler of indices with probabilities proportional to their weights
+#[derive(Debug, Clone, PartialEq)]
+pub struct WeightedIndex {
+    /// Running sums of the weights, the last one is the total
+    cumulative: Vec<f64>,
+}
+
+//-----------------------------------------------------------------------------
+
+impl WeightedIndex {
+    /// `None` if there are no weights, one of them is negative or not finite, or all of them are
+    /// zero
+    pub fn new(weights: impl IntoIterator<Item = f64>) -> Option<Self> {
+        let mut total = 0.0;
+        let mut cumulative = Vec::new();
+
+        for weight in weights {
+            if !weight.is_finite() || weight < 0.0 {
+                return None;
+            }
+            total += weight;
+            cumulative.push(total);
+        }
+
+        if total <= 0.0 {
+            return None;
+        }
+
+        return Some(WeightedIndex { cumulative });
+    }
+
+    /// Number of weights
+    pub fn count(&self) -> usize {
+        return self.cumulative.len();
+    }
+
+    pub fn total(&self) -> f64 {
+        return *self.cumulative.last().unwrap();
+    }
+
+    /// Probability of sampling `index`
+    pub fn probability(&self, index: usize) -> f64 {
+        let Some(&sum) = self.cumulative.get(index) else {
+            return 0.0;
+        };
+        let prev = if index == 0 {
+            0.0
+        } else {
+            self.cumulative[index - 1]
+        };
+
+        return (sum - prev) / self.total();
+    }
+
+    /// Index with the probability proportional to its weight ( zero weights are never sampled )
+    pub fn sample<W, E>(&self, rng: &mut E) -> usize
+    where
+        W: Word,
+        E: Engine<W>,
+        f64: RandomlyGenerated<W>,
+    {
+        let target = rng.gen::<f64>() * self.total();
+
+        // First running sum above the target
+        let idx = self.cumulative.partition_point(|&sum| sum <= target);
+        if idx < self.cumulative.len() {
+            return idx;
+        }
+
+        // `gen` can return 1.0, take the last nonzero weight
+        return self.cumulative.partition_point(|&sum| sum < self.total());
+    }
+}
+
+//-----------------------------------------------------------------------------
@@ -0,0 +1,75 @@
+//-----------------------------------------------------------------------------
+use std::sync::{Arc, Condvar, Mutex};
+//-----------------------------------------------------------------------------
+/// Handle of a batch of jobs submitted with
+/// [ThreadPool::submit_batch](crate::ThreadPool::submit_batch).
+///
+/// Dropping the handle doesn't cancel the jobs.
+pub struct BatchHandle {
+    state: Arc<BatchState>,
+}
+
+/// Number of unfinished jobs of the batch
+struct BatchState {
+    remaining: Mutex<usize>,
+    finished: Condvar,
+}
+
+impl BatchHandle {
+    pub(crate) fn new(num_of_jobs: usize) -> Self {
+        return BatchHandle {
+            state: Arc::new(BatchState {
+                remaining: Mutex::new(num_of_jobs),
+                finished: Condvar::new(),
+            }),
+        };
+    }
+
+    /// Creates a guard which marks one job of the batch finished when dropped
+    pub(crate) fn job_guard(&self) -> BatchJobGuard {
+        return BatchJobGuard(self.state.clone());
+    }
+
+    /// Gets the number of jobs of the batch that haven't finished yet
+    pub fn remaining(&self) -> usize {
+        return *self.state.remaining.lock().unwrap();
+    }
+
+    /// Returns true if all of the jobs of the batch have finished
+    pub fn is_done(&self) -> bool {
+        return self.remaining() == 0;
+    }
+
+    /// Blocks until all of the jobs of the batch have finished ( successfully
+    /// or not, errors are reported by the pool ).
+    ///
+    /// Must not be called from inside of a job.
+    pub fn wait_all(&self) {
+        let remaining = self.state.remaining.lock().unwrap();
+
+        drop(
+            self.state
+                .finished
+                .wait_while(remaining, |remaining| *remaining != 0)
+                .unwrap(),
+        );
+    }
+}
+
+//-----------------------------------------------------------------------------
+/// Marks a job of the batch finished once it goes out of scope ( even if the
+/// job panics )
+pub(crate) struct BatchJobGuard(Arc<BatchState>);
+
+impl Drop for BatchJobGuard {
+    fn drop(&mut self) {
+        let mut remaining = self.0.remaining.lock().unwrap();
+
+        *remaining -= 1;
+        if *remaining == 0 {
+            self.0.finished.notify_all();
+        }
+    }
+}
+
+//-----------------------------------------------------------------------------
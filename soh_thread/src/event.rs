@@ -0,0 +1,82 @@
+//-----------------------------------------------------------------------------
+use std::sync::mpsc::{Receiver, Sender};
+//-----------------------------------------------------------------------------
+/// Typed channel for the notifications of the workers ( completion, progress )
+/// which the main loop drains once per frame without blocking.
+///
+/// ```rust
+/// let pool = soh_thread::ThreadPool::new(4);
+/// let bus = soh_thread::EventBus::new();
+///
+/// for i in 0..4 {
+///     let events = bus.sender();
+///     pool.add_job("load", move || {
+///         events.send(i);
+///         Ok(())
+///     });
+/// }
+/// pool.wait();
+///
+/// let mut loaded = bus.drain().collect::<Vec<_>>();
+/// loaded.sort();
+/// assert_eq!(loaded, [0, 1, 2, 3]);
+/// assert_eq!(bus.drain().count(), 0);
+/// ```
+pub struct EventBus<T> {
+    sender: Sender<T>,
+    receiver: Receiver<T>,
+}
+
+/// Sending side of an [EventBus], cheap to clone and move into jobs
+pub struct EventSender<T> {
+    sender: Sender<T>,
+}
+
+//-----------------------------------------------------------------------------
+
+impl<T> EventBus<T> {
+    pub fn new() -> Self {
+        let (sender, receiver) = std::sync::mpsc::channel();
+
+        return EventBus { sender, receiver };
+    }
+
+    /// Creates a sender for a worker
+    pub fn sender(&self) -> EventSender<T> {
+        return EventSender {
+            sender: self.sender.clone(),
+        };
+    }
+
+    /// Takes the events sent so far, doesn't wait for new ones
+    pub fn drain(&self) -> impl Iterator<Item = T> + '_ {
+        return self.receiver.try_iter();
+    }
+}
+
+impl<T> Default for EventBus<T> {
+    fn default() -> Self {
+        return Self::new();
+    }
+}
+
+//-----------------------------------------------------------------------------
+
+impl<T> EventSender<T> {
+    /// Sends an event to the bus, never blocks.
+    ///
+    /// Returns false if the bus was dropped.
+    pub fn send(&self, event: T) -> bool {
+        return self.sender.send(event).is_ok();
+    }
+}
+
+impl<T> Clone for EventSender<T> {
+    fn clone(&self) -> Self {
+        return EventSender {
+            sender: self.sender.clone(),
+        };
+    }
+}
+
+//-----------------------------------------------------------------------------
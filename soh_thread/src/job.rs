@@ -0,0 +1,96 @@
+//-----------------------------------------------------------------------------
+use std::sync::{Arc, Condvar, Mutex};
+//-----------------------------------------------------------------------------
+/// Reason why a job didn't finish successfully
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum JobError {
+    /// The job was removed from the queue before it started
+    #[error("The job was cancelled")]
+    Cancelled,
+
+    /// The job returned an error
+    #[error("The job failed: {0}")]
+    Failed(String),
+
+    /// The job panicked
+    #[error("The job panicked: {0}")]
+    Panicked(String),
+}
+
+//-----------------------------------------------------------------------------
+/// Handle of a job added with [ThreadPool::add_job](crate::ThreadPool::add_job)
+///
+/// Dropping the handle doesn't cancel the job.
+pub struct JobHandle {
+    state: Arc<JobState>,
+}
+
+struct JobState {
+    result: Mutex<Option<Result<(), JobError>>>,
+    finished: Condvar,
+}
+
+impl JobHandle {
+    /// Creates a handle and the completer which resolves it
+    pub(crate) fn new() -> (Self, JobCompleter) {
+        let state = Arc::new(JobState {
+            result: Mutex::new(None),
+            finished: Condvar::new(),
+        });
+
+        return (
+            JobHandle {
+                state: state.clone(),
+            },
+            JobCompleter(state),
+        );
+    }
+
+    /// Returns true if the job has finished or has been cancelled
+    pub fn is_finished(&self) -> bool {
+        return self.state.result.lock().unwrap().is_some();
+    }
+
+    /// Blocks until the job finishes or gets cancelled.
+    ///
+    /// Must not be called from inside of a job.
+    pub fn wait(self) -> Result<(), JobError> {
+        let result = self.state.result.lock().unwrap();
+
+        let mut result = self
+            .state
+            .finished
+            .wait_while(result, |result| result.is_none())
+            .unwrap();
+
+        return result.take().unwrap();
+    }
+}
+
+//-----------------------------------------------------------------------------
+/// Resolves the [JobHandle]. Travels together with the job, if the job is
+/// dropped without running the handle resolves to [JobError::Cancelled].
+pub(crate) struct JobCompleter(Arc<JobState>);
+
+impl JobCompleter {
+    pub fn complete(self, result: Result<(), JobError>) {
+        self.set(result);
+    }
+
+    fn set(&self, result: Result<(), JobError>) {
+        let mut lock = self.0.result.lock().unwrap();
+
+        if lock.is_none() {
+            *lock = Some(result);
+            self.0.finished.notify_all();
+        }
+    }
+}
+
+impl Drop for JobCompleter {
+    fn drop(&mut self) {
+        self.set(Err(JobError::Cancelled));
+    }
+}
+
+//-----------------------------------------------------------------------------
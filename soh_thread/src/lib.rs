@@ -1,9 +1,17 @@
 //-----------------------------------------------------------------------------
-mod job_queue;
+mod batch;
+mod event;
+mod job;
+mod pipeline;
+mod scheduler;
+mod service;
+mod slow;
 mod thread_pool;
 mod worker;
 //-----------------------------------------------------------------------------
-use job_queue::JobQueueHandle;
+use scheduler::Scheduler;
+use service::Service;
+use slow::SlowJobs;
 use worker::Worker;
 //-----------------------------------------------------------------------------
 type Job = (
@@ -11,7 +19,38 @@ type Job = (
     Box<dyn FnOnce() -> anyhow::Result<()> + Send + 'static>,
 );
 //-----------------------------------------------------------------------------
-pub use job_queue::JobQueue;
-pub use job_queue::JOB_QUEUE;
-pub use thread_pool::ThreadPool;
+pub use batch::BatchHandle;
+pub use event::{EventBus, EventSender};
+pub use job::{JobError, JobHandle};
+pub use pipeline::{Pipeline, PipelineBuilder};
+pub use service::ShutdownSignal;
+pub use slow::SlowJobStats;
+pub use thread_pool::{ShutdownPolicy, ThreadPool};
+//-----------------------------------------------------------------------------
+/// Reports an error of a job or a service
+fn report_error(msg: &str) {
+    #[cfg(feature = "log")]
+    soh_log::log_error!("{}", msg);
+
+    #[cfg(not(feature = "log"))]
+    eprintln!("{}", msg);
+}
+
+/// Reports a problem that isn't an error ( slow jobs )
+fn report_warning(msg: &str) {
+    #[cfg(feature = "log")]
+    soh_log::log_warning!("{}", msg);
+
+    #[cfg(not(feature = "log"))]
+    eprintln!("{}", msg);
+}
+
+/// Extracts the message of a panic
+fn panic_reason(payload: &(dyn std::any::Any + Send)) -> &str {
+    return payload
+        .downcast_ref::<&str>()
+        .copied()
+        .or_else(|| payload.downcast_ref::<String>().map(String::as_str))
+        .unwrap_or("unknown reason");
+}
 //-----------------------------------------------------------------------------
@@ -0,0 +1,263 @@
+//-----------------------------------------------------------------------------
+use std::sync::mpsc::{Receiver, SyncSender};
+use std::sync::{Arc, Mutex};
+//-----------------------------------------------------------------------------
+/// Items flowing through stages connected by bounded channels ( decode ->
+/// process -> upload )
+///
+/// Every stage runs on its own threads, so the stages can block without
+/// stalling the jobs of a [ThreadPool](crate::ThreadPool). The channels are
+/// bounded: when a stage can't keep up, the stages before it block, up to
+/// [Pipeline::send].
+///
+/// Errors and panics of a stage are reported and the item is dropped.
+///
+/// ```rust
+/// let pipeline = soh_thread::Pipeline::builder(4)
+///     .stage("parse", 2, |line: String| Ok(line.trim().parse::<i32>()?))
+///     .stage("square", 4, |n| Ok(n * n))
+///     .build()
+///     .unwrap();
+///
+/// let input = ["1", " 2", "3 "];
+///
+/// std::thread::scope(|s| {
+///     s.spawn(|| {
+///         for line in input {
+///             pipeline.send(line.to_owned()).unwrap();
+///         }
+///         pipeline.close();
+///     });
+///
+///     let mut squares = std::iter::from_fn(|| pipeline.recv()).collect::<Vec<_>>();
+///     squares.sort();
+///     assert_eq!(squares, [1, 4, 9]);
+/// });
+/// ```
+pub struct Pipeline<In, Out = ()> {
+    input: Mutex<Option<SyncSender<In>>>,
+    output: Mutex<Option<Receiver<Out>>>,
+    threads: Vec<std::thread::JoinHandle<()>>,
+}
+
+/// Builder of a [Pipeline] taking `In` and whose last stage produces `Out`
+pub struct PipelineBuilder<In, Out> {
+    capacity: usize,
+    input: SyncSender<In>,
+    output: Receiver<Out>,
+    workers: Vec<StageWorker>,
+}
+
+struct StageWorker {
+    name: &'static str,
+    run: Box<dyn FnOnce() + Send + 'static>,
+}
+
+//-----------------------------------------------------------------------------
+
+impl<In: Send + 'static> Pipeline<In> {
+    /// Starts building a pipeline, `capacity` is the number of items buffered
+    /// between the stages
+    pub fn builder(capacity: usize) -> PipelineBuilder<In, In> {
+        let (input, output) = std::sync::mpsc::sync_channel(capacity);
+
+        return PipelineBuilder {
+            capacity,
+            input,
+            output,
+            workers: Vec::new(),
+        };
+    }
+}
+
+impl<In, Out> Pipeline<In, Out> {
+    /// Feeds an item to the first stage, blocks while the stage is behind.
+    ///
+    /// Gives the item back if the pipeline is closed.
+    pub fn send(&self, item: In) -> Result<(), In> {
+        let input = self.input.lock().unwrap().clone();
+        let Some(input) = input else {
+            return Err(item);
+        };
+
+        return input.send(item).map_err(|e| e.0);
+    }
+
+    /// Stops accepting new items. The items already sent still go through the
+    /// stages.
+    pub fn close(&self) {
+        self.input.lock().unwrap().take();
+    }
+
+    /// Waits for an output of the last stage.
+    ///
+    /// Returns `None` once the pipeline is closed and all of the items went
+    /// through, or if the pipeline ends with a sink.
+    pub fn recv(&self) -> Option<Out> {
+        return self.output.lock().unwrap().as_ref()?.recv().ok();
+    }
+
+    /// Closes the pipeline and waits for the stages to finish, the outputs
+    /// which weren't received are dropped
+    pub fn join(mut self) {
+        self.finish();
+    }
+
+    fn finish(&mut self) {
+        self.close();
+
+        // The last stage would block on the full output channel
+        if let Some(output) = self.output.lock().unwrap().take() {
+            for _ in output.iter() {}
+        }
+
+        for thread in self.threads.drain(..) {
+            let _ = thread.join();
+        }
+    }
+}
+
+impl<In, Out> Drop for Pipeline<In, Out> {
+    fn drop(&mut self) {
+        self.finish();
+    }
+}
+
+//-----------------------------------------------------------------------------
+
+impl<In: Send + 'static, Out: Send + 'static> PipelineBuilder<In, Out> {
+    /// Adds a stage running on `workers` threads ( at least one )
+    pub fn stage<Next, F>(
+        self,
+        name: &'static str,
+        workers: usize,
+        stage: F,
+    ) -> PipelineBuilder<In, Next>
+    where
+        Next: Send + 'static,
+        F: Fn(Out) -> anyhow::Result<Next> + Send + Sync + 'static,
+    {
+        let (sender, output) = std::sync::mpsc::sync_channel(self.capacity);
+        let receiver = Arc::new(Mutex::new(self.output));
+        let stage = Arc::new(stage);
+
+        let mut stage_workers = self.workers;
+        for _ in 0..workers.max(1) {
+            let receiver = receiver.clone();
+            let sender = sender.clone();
+            let stage = stage.clone();
+
+            stage_workers.push(StageWorker {
+                name,
+                run: Box::new(move || {
+                    while let Some(item) = next_item(&receiver) {
+                        let Some(item) = run_stage(name, &*stage, item) else {
+                            continue;
+                        };
+
+                        if sender.send(item).is_err() {
+                            break;
+                        }
+                    }
+                }),
+            });
+        }
+
+        return PipelineBuilder {
+            capacity: self.capacity,
+            input: self.input,
+            output,
+            workers: stage_workers,
+        };
+    }
+
+    /// Adds the last stage which consumes the items, running on `workers`
+    /// threads ( at least one )
+    pub fn sink<F>(
+        self,
+        name: &'static str,
+        workers: usize,
+        sink: F,
+    ) -> std::io::Result<Pipeline<In>>
+    where
+        F: Fn(Out) -> anyhow::Result<()> + Send + Sync + 'static,
+    {
+        let receiver = Arc::new(Mutex::new(self.output));
+        let sink = Arc::new(sink);
+
+        let mut stage_workers = self.workers;
+        for _ in 0..workers.max(1) {
+            let receiver = receiver.clone();
+            let sink = sink.clone();
+
+            stage_workers.push(StageWorker {
+                name,
+                run: Box::new(move || {
+                    while let Some(item) = next_item(&receiver) {
+                        run_stage(name, &*sink, item);
+                    }
+                }),
+            });
+        }
+
+        return spawn(self.input, None, stage_workers);
+    }
+
+    /// Starts the stages, the outputs of the last stage are received with
+    /// [Pipeline::recv]
+    pub fn build(self) -> std::io::Result<Pipeline<In, Out>> {
+        return spawn(self.input, Some(self.output), self.workers);
+    }
+}
+
+//-----------------------------------------------------------------------------
+
+fn spawn<In, Out>(
+    input: SyncSender<In>,
+    output: Option<Receiver<Out>>,
+    workers: Vec<StageWorker>,
+) -> std::io::Result<Pipeline<In, Out>> {
+    let mut threads = Vec::with_capacity(workers.len());
+
+    // On error the workers which didn't start drop their channels, so the
+    // started ones exit once the input is dropped
+    for worker in workers {
+        let thread = std::thread::Builder::new()
+            .name(worker.name.to_owned())
+            .spawn(worker.run)?;
+        threads.push(thread);
+    }
+
+    return Ok(Pipeline {
+        input: Mutex::new(Some(input)),
+        output: Mutex::new(output),
+        threads,
+    });
+}
+
+/// Takes the next item of the previous stage, `None` once it's finished
+fn next_item<T>(receiver: &Mutex<Receiver<T>>) -> Option<T> {
+    return receiver.lock().unwrap().recv().ok();
+}
+
+/// Runs the stage on an item and reports its error or panic
+fn run_stage<T, U>(
+    name: &'static str,
+    stage: &dyn Fn(T) -> anyhow::Result<U>,
+    item: T,
+) -> Option<U> {
+    let msg = match std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| stage(item))) {
+        Ok(Ok(item)) => return Some(item),
+        Ok(Err(e)) => format!("Error occured in the stage \"{}\":\n{}", name, e),
+        Err(payload) => format!(
+            "Stage \"{}\" panicked: {}",
+            name,
+            super::panic_reason(payload.as_ref())
+        ),
+    };
+
+    super::report_error(&msg);
+    return None;
+}
+
+//-----------------------------------------------------------------------------
@@ -0,0 +1,450 @@
+//-----------------------------------------------------------------------------
+use super::Job;
+use std::collections::VecDeque as Queue;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Condvar, Mutex};
+//-----------------------------------------------------------------------------
+/// Work-stealing job scheduler shared by the workers of a thread pool.
+///
+/// Every worker owns a deque of jobs. Jobs submitted from a worker thread go
+/// to its own deque, jobs submitted from the outside go to the shared
+/// injector queue. A worker takes jobs from the back of its own deque, then
+/// from the injector and finally steals from the front of the other deques.
+///
+/// Each deque has its own lock, so workers rarely contend with each other.
+pub struct Scheduler {
+    id: usize,
+
+    injector: Mutex<Queue<Job>>,
+    locals: Vec<Mutex<Queue<Job>>>,
+
+    /// Jobs waiting in the queues
+    queued: AtomicUsize,
+    /// Jobs waiting in the queues and jobs being executed
+    pending: AtomicUsize,
+
+    shutdown: AtomicBool,
+    /// Jobs are dropped instead of executed
+    cancelled: AtomicBool,
+
+    /// Idle workers wait here for new jobs
+    sleep: Mutex<()>,
+    wake: Condvar,
+    sleeping: AtomicUsize,
+
+    /// Threads waiting for all of the jobs to finish wait here
+    idle_lock: Mutex<()>,
+    idle: Condvar,
+}
+
+thread_local! {
+    /// ( scheduler id, worker index ) of the current worker thread
+    static CURRENT_WORKER: std::cell::Cell<Option<(usize, usize)>> =
+        const { std::cell::Cell::new(None) };
+}
+
+impl Scheduler {
+    pub fn new(num_of_workers: usize) -> Self {
+        static NEXT_ID: AtomicUsize = AtomicUsize::new(0);
+
+        return Scheduler {
+            id: NEXT_ID.fetch_add(1, Ordering::Relaxed),
+            injector: Mutex::new(Queue::new()),
+            locals: (0..num_of_workers)
+                .map(|_| Mutex::new(Queue::new()))
+                .collect(),
+            queued: AtomicUsize::new(0),
+            pending: AtomicUsize::new(0),
+            shutdown: AtomicBool::new(false),
+            cancelled: AtomicBool::new(false),
+            sleep: Mutex::new(()),
+            wake: Condvar::new(),
+            sleeping: AtomicUsize::new(0),
+            idle_lock: Mutex::new(()),
+            idle: Condvar::new(),
+        };
+    }
+
+    /// Marks the current thread as the worker with the `index`
+    pub fn register_worker(&self, index: usize) {
+        CURRENT_WORKER.set(Some((self.id, index)));
+    }
+
+    /// Index of the current thread if it's a worker of this scheduler
+    fn current_worker(&self) -> Option<usize> {
+        return CURRENT_WORKER
+            .get()
+            .filter(|&(id, _)| id == self.id)
+            .map(|(_, index)| index);
+    }
+
+    //-------------------------------------------------------------------------
+    // Submitting
+
+    /// Adds a new job, it will be executed by one of the workers.
+    pub fn push(&self, job: Job) {
+        // Counters go up first, so they never drop below the real number
+        self.pending.fetch_add(1, Ordering::SeqCst);
+        self.queued.fetch_add(1, Ordering::SeqCst);
+
+        match self.current_worker() {
+            Some(index) => self.locals[index].lock().unwrap().push_back(job),
+            None => self.injector.lock().unwrap().push_back(job),
+        }
+
+        self.wake_workers(false);
+    }
+
+    /// Adds all of the jobs under a single lock of the queue.
+    pub fn push_batch(&self, jobs: Vec<Job>) {
+        if jobs.is_empty() {
+            return;
+        }
+
+        self.pending.fetch_add(jobs.len(), Ordering::SeqCst);
+        self.queued.fetch_add(jobs.len(), Ordering::SeqCst);
+
+        match self.current_worker() {
+            Some(index) => self.locals[index].lock().unwrap().extend(jobs),
+            None => self.injector.lock().unwrap().extend(jobs),
+        }
+
+        self.wake_workers(true);
+    }
+
+    /// Wakes sleeping workers up, skips the lock if nobody sleeps
+    fn wake_workers(&self, all: bool) {
+        if self.sleeping.load(Ordering::SeqCst) == 0 {
+            return;
+        }
+
+        let _lock = self.sleep.lock().unwrap();
+        if all {
+            self.wake.notify_all();
+        } else {
+            self.wake.notify_one();
+        }
+    }
+
+    //-------------------------------------------------------------------------
+    // Executing
+
+    /// Finds a job for the worker with the `index`
+    fn find_job(&self, index: usize) -> Option<Job> {
+        let num_of_workers = self.locals.len();
+
+        // Only one lock is held at a time, otherwise two workers stealing
+        // from each other could deadlock
+        let own = self.locals[index].lock().unwrap().pop_back();
+        let job = own
+            .or_else(|| self.injector.lock().unwrap().pop_front())
+            .or_else(|| {
+                (1..num_of_workers)
+                    .map(|offset| (index + offset) % num_of_workers)
+                    .find_map(|victim| self.locals[victim].lock().unwrap().pop_front())
+            });
+
+        if job.is_some() {
+            self.queued.fetch_sub(1, Ordering::SeqCst);
+        }
+
+        return job;
+    }
+
+    /// Blocks until there is a job for the worker with the `index`.
+    ///
+    /// Returns `None` once the scheduler is shut down and there are no jobs
+    /// left.
+    pub fn wait_for_job(&self, index: usize) -> Option<Job> {
+        loop {
+            if let Some(job) = self.find_job(index) {
+                if !self.cancelled.load(Ordering::SeqCst) {
+                    return Some(job);
+                }
+
+                drop(job);
+                self.signal_finished_many(1);
+                continue;
+            }
+
+            let lock = self.sleep.lock().unwrap();
+
+            // Announce the sleep before rechecking the queues, so either this
+            // worker sees the new job or the submitter sees the sleeper
+            self.sleeping.fetch_add(1, Ordering::SeqCst);
+
+            if self.queued.load(Ordering::SeqCst) == 0 && !self.shutdown.load(Ordering::SeqCst) {
+                drop(self.wake.wait(lock).unwrap());
+                self.sleeping.fetch_sub(1, Ordering::SeqCst);
+                continue;
+            }
+
+            self.sleeping.fetch_sub(1, Ordering::SeqCst);
+            drop(lock);
+
+            if self.queued.load(Ordering::SeqCst) == 0 {
+                // Shut down and there's nothing left to do
+                return None;
+            }
+
+            std::thread::yield_now();
+        }
+    }
+
+    /// Signals that a job taken by a worker has been finished
+    pub fn signal_finished(&self) {
+        self.signal_finished_many(1);
+    }
+
+    fn signal_finished_many(&self, num_of_jobs: usize) {
+        if self.pending.fetch_sub(num_of_jobs, Ordering::SeqCst) == num_of_jobs {
+            let _lock = self.idle_lock.lock().unwrap();
+            self.idle.notify_all();
+        }
+    }
+
+    //-------------------------------------------------------------------------
+    // State
+
+    /// Gets the number of jobs that are waiting in the queues and the jobs
+    /// that are currently being executed.
+    pub fn get_num_of_jobs(&self) -> usize {
+        return self.pending.load(Ordering::SeqCst);
+    }
+
+    /// Blocks until all of the jobs are finished
+    pub fn wait_idle(&self) {
+        let mut lock = self.idle_lock.lock().unwrap();
+        while self.pending.load(Ordering::SeqCst) != 0 {
+            lock = self.idle.wait(lock).unwrap();
+        }
+    }
+
+    /// Blocks until all of the jobs are finished or the `deadline` passes.
+    ///
+    /// Returns true if all of the jobs are finished.
+    pub fn wait_idle_until(&self, deadline: std::time::Instant) -> bool {
+        let mut lock = self.idle_lock.lock().unwrap();
+        while self.pending.load(Ordering::SeqCst) != 0 {
+            let now = std::time::Instant::now();
+            if now >= deadline {
+                return false;
+            }
+
+            lock = self.idle.wait_timeout(lock, deadline - now).unwrap().0;
+        }
+
+        return true;
+    }
+
+    /// Drops all of the queued jobs and every job added from now on. Jobs that
+    /// are already running are not interrupted.
+    pub fn cancel_pending(&self) {
+        self.cancelled.store(true, Ordering::SeqCst);
+
+        let mut jobs = std::mem::take(&mut *self.injector.lock().unwrap());
+        for local in &self.locals {
+            jobs.append(&mut local.lock().unwrap());
+        }
+
+        if jobs.is_empty() {
+            return;
+        }
+
+        self.queued.fetch_sub(jobs.len(), Ordering::SeqCst);
+
+        let num_of_jobs = jobs.len();
+        drop(jobs);
+        self.signal_finished_many(num_of_jobs);
+    }
+
+    /// Lets the workers exit once the queues are empty
+    pub fn shutdown(&self) {
+        self.shutdown.store(true, Ordering::SeqCst);
+
+        let _lock = self.sleep.lock().unwrap();
+        self.wake.notify_all();
+    }
+}
+
+//-----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Worker;
+    use std::collections::HashSet;
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    const NUM_OF_WORKERS: usize = 4;
+
+    fn start() -> (Arc<Scheduler>, Vec<Worker>) {
+        let scheduler = Arc::new(Scheduler::new(NUM_OF_WORKERS));
+        let workers = (0..NUM_OF_WORKERS)
+            .map(|index| Worker::new(scheduler.clone(), index))
+            .collect();
+        return (scheduler, workers);
+    }
+
+    fn stop(scheduler: &Scheduler, workers: Vec<Worker>) {
+        scheduler.shutdown();
+        for mut worker in workers {
+            worker.thread.take().unwrap().join().unwrap();
+        }
+    }
+
+    /// Counter of every job and the threads which ran them
+    struct Record {
+        runs: Vec<AtomicUsize>,
+        threads: Mutex<HashSet<std::thread::ThreadId>>,
+    }
+
+    impl Record {
+        fn new(num_of_jobs: usize) -> Arc<Self> {
+            return Arc::new(Record {
+                runs: (0..num_of_jobs).map(|_| AtomicUsize::new(0)).collect(),
+                threads: Mutex::new(HashSet::new()),
+            });
+        }
+
+        fn job(self: &Arc<Self>, idx: usize, work: Duration) -> Job {
+            let record = self.clone();
+            return (
+                "record",
+                Box::new(move || {
+                    record.runs[idx].fetch_add(1, Ordering::SeqCst);
+                    record
+                        .threads
+                        .lock()
+                        .unwrap()
+                        .insert(std::thread::current().id());
+                    std::thread::sleep(work);
+                    return Ok(());
+                }),
+            );
+        }
+
+        fn assert_ran_once(&self) {
+            for (idx, runs) in self.runs.iter().enumerate() {
+                assert_eq!(runs.load(Ordering::SeqCst), 1, "job {idx}");
+            }
+        }
+
+        fn num_of_threads(&self) -> usize {
+            return self.threads.lock().unwrap().len();
+        }
+    }
+
+    #[test]
+    fn test_many_tiny_jobs() {
+        let (scheduler, workers) = start();
+
+        const NUM_OF_JOBS: usize = 10_000;
+        let record = Record::new(NUM_OF_JOBS);
+
+        // Half one by one, half in batches
+        for idx in 0..NUM_OF_JOBS / 2 {
+            scheduler.push(record.job(idx, Duration::ZERO));
+        }
+        for batch in (NUM_OF_JOBS / 2..NUM_OF_JOBS)
+            .collect::<Vec<_>>()
+            .chunks(100)
+        {
+            let jobs = batch.iter().map(|&idx| record.job(idx, Duration::ZERO));
+            scheduler.push_batch(jobs.collect());
+        }
+
+        scheduler.wait_idle();
+        record.assert_ran_once();
+        assert_eq!(scheduler.get_num_of_jobs(), 0);
+
+        // Slower jobs are spread over the workers
+        let record = Record::new(100);
+        let jobs = (0..100).map(|idx| record.job(idx, Duration::from_millis(1)));
+        scheduler.push_batch(jobs.collect());
+
+        scheduler.wait_idle();
+        record.assert_ran_once();
+        assert!(record.num_of_threads() > 1);
+
+        stop(&scheduler, workers);
+    }
+
+    #[test]
+    fn test_jobs_from_jobs() {
+        let (scheduler, workers) = start();
+
+        const NUM_OF_OUTER: usize = 8;
+        const NUM_OF_INNER: usize = 500;
+        let record = Record::new(NUM_OF_OUTER * NUM_OF_INNER);
+
+        for outer in 0..NUM_OF_OUTER {
+            let scheduler_ref = scheduler.clone();
+            let record = record.clone();
+            scheduler.push((
+                "outer",
+                Box::new(move || {
+                    for inner in 0..NUM_OF_INNER {
+                        let idx = outer * NUM_OF_INNER + inner;
+                        scheduler_ref.push(record.job(idx, Duration::ZERO));
+                    }
+                    return Ok(());
+                }),
+            ));
+        }
+
+        // The outer jobs are pending until they return, so the inner ones are waited for too
+        scheduler.wait_idle();
+        record.assert_ran_once();
+
+        // Jobs pushed by a worker go to its own deque, the others have to steal them
+        let record = Record::new(200);
+        let scheduler_ref = scheduler.clone();
+        let stolen = record.clone();
+        scheduler.push((
+            "outer",
+            Box::new(move || {
+                let jobs = (0..200).map(|idx| stolen.job(idx, Duration::from_millis(1)));
+                scheduler_ref.push_batch(jobs.collect());
+                return Ok(());
+            }),
+        ));
+
+        scheduler.wait_idle();
+        record.assert_ran_once();
+        assert!(record.num_of_threads() > 1);
+
+        stop(&scheduler, workers);
+    }
+
+    #[test]
+    fn test_shutdown_while_stealing() {
+        let (scheduler, workers) = start();
+
+        const NUM_OF_JOBS: usize = 2_000;
+        let record = Record::new(NUM_OF_JOBS);
+
+        let scheduler_ref = scheduler.clone();
+        let inner = record.clone();
+        scheduler.push((
+            "outer",
+            Box::new(move || {
+                for idx in 0..NUM_OF_JOBS {
+                    scheduler_ref.push(inner.job(idx, Duration::from_micros(10)));
+                }
+                return Ok(());
+            }),
+        ));
+
+        // Wait until the others are stealing from the worker running the outer job
+        while record.num_of_threads() < 2 && scheduler.get_num_of_jobs() > 0 {
+            std::thread::yield_now();
+        }
+
+        // The workers drain the queues before exiting
+        stop(&scheduler, workers);
+        record.assert_ran_once();
+        assert_eq!(scheduler.get_num_of_jobs(), 0);
+    }
+}
@@ -0,0 +1,107 @@
+//-----------------------------------------------------------------------------
+use std::sync::{Arc, Condvar, Mutex};
+use std::time::Duration;
+//-----------------------------------------------------------------------------
+/// Signal telling the service loops of a thread pool to stop.
+///
+/// A service should check it regularly, or sleep using
+/// [ShutdownSignal::wait_timeout] which wakes up as soon as the shutdown is
+/// requested.
+#[derive(Clone)]
+pub struct ShutdownSignal {
+    inner: Arc<(Mutex<bool>, Condvar)>,
+}
+
+impl ShutdownSignal {
+    pub(crate) fn new() -> Self {
+        return ShutdownSignal {
+            inner: Arc::new((Mutex::new(false), Condvar::new())),
+        };
+    }
+
+    /// Returns true once the service should stop
+    pub fn is_shutdown(&self) -> bool {
+        return *self.inner.0.lock().unwrap();
+    }
+
+    /// Sleeps for the `timeout` or until the shutdown is requested.
+    ///
+    /// Returns true if the service should stop.
+    pub fn wait_timeout(&self, timeout: Duration) -> bool {
+        let (lock, condvar) = &*self.inner;
+
+        let (shutdown, _) = condvar
+            .wait_timeout_while(lock.lock().unwrap(), timeout, |shutdown| !*shutdown)
+            .unwrap();
+
+        return *shutdown;
+    }
+
+    pub(crate) fn trigger(&self) {
+        let (lock, condvar) = &*self.inner;
+
+        *lock.lock().unwrap() = true;
+        condvar.notify_all();
+    }
+}
+
+//-----------------------------------------------------------------------------
+/// Long-running loop on a dedicated thread ( audio mixer, network poller, ... )
+pub struct Service {
+    name: &'static str,
+    thread: Option<std::thread::JoinHandle<anyhow::Result<()>>>,
+}
+
+impl Service {
+    pub fn new<F>(name: &'static str, signal: ShutdownSignal, service: F) -> std::io::Result<Self>
+    where
+        F: FnOnce(ShutdownSignal) -> anyhow::Result<()> + Send + 'static,
+    {
+        let thread = std::thread::Builder::new()
+            .name(name.to_owned())
+            .spawn(move || service(signal))?;
+
+        return Ok(Service {
+            name,
+            thread: Some(thread),
+        });
+    }
+
+    pub fn name(&self) -> &'static str {
+        return self.name;
+    }
+
+    pub fn is_finished(&self) -> bool {
+        return self.thread.as_ref().is_none_or(|t| t.is_finished());
+    }
+
+    /// Waits until the service exits or the `deadline` passes. Returns true
+    /// if the service has exited.
+    pub fn finished_by(&self, deadline: Option<std::time::Instant>) -> bool {
+        return self
+            .thread
+            .as_ref()
+            .is_none_or(|thread| super::thread_pool::finished_by(thread, deadline));
+    }
+
+    /// Waits for the service to exit and reports its error or panic
+    pub fn join(&mut self) {
+        let Some(thread) = self.thread.take() else {
+            return;
+        };
+
+        let msg = match thread.join() {
+            Ok(Ok(())) => return,
+            Ok(Err(e)) => format!("Error occured in the service \"{}\":\n{}", self.name, e),
+            Err(payload) => format!(
+                "Service \"{}\" panicked: {}",
+                self.name,
+                super::panic_reason(payload.as_ref())
+            ),
+        };
+
+        super::report_error(&msg);
+    }
+}
+
+//-----------------------------------------------------------------------------
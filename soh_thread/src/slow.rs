@@ -0,0 +1,80 @@
+//-----------------------------------------------------------------------------
+use std::sync::Mutex;
+use std::time::Duration;
+//-----------------------------------------------------------------------------
+/// Number of the buckets of [SlowJobStats::overrun_histogram]
+pub const OVERRUN_BUCKETS: usize = 4;
+
+/// Runs of a job that took longer than expected, see
+/// [ThreadPool::add_job_with_deadline](crate::ThreadPool::add_job_with_deadline)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SlowJobStats {
+    pub name: &'static str,
+    /// Number of the slow runs
+    pub count: usize,
+    /// Time spent in the slow runs
+    pub total: Duration,
+    /// The slowest run
+    pub max: Duration,
+    /// Slow runs by how many times longer than expected they took: up to 2x, 4x, 8x and more
+    pub overrun_histogram: [usize; OVERRUN_BUCKETS],
+}
+
+//-----------------------------------------------------------------------------
+/// Statistics of the slow jobs of a pool
+pub(crate) struct SlowJobs {
+    stats: Mutex<Vec<SlowJobStats>>,
+}
+
+impl SlowJobs {
+    pub fn new() -> Self {
+        return SlowJobs {
+            stats: Mutex::new(Vec::new()),
+        };
+    }
+
+    /// Reports and records the run if it took longer than `expected`
+    pub fn record(&self, name: &'static str, expected: Duration, elapsed: Duration) {
+        if elapsed <= expected {
+            return;
+        }
+
+        super::report_warning(&format!(
+            "Task \"{name}\" took {elapsed:.2?}, expected at most {expected:.2?}"
+        ));
+
+        // Bucket of the ratio: [1, 2), [2, 4), [4, 8), [8, inf)
+        let ratio = elapsed.as_secs_f64() / expected.as_secs_f64().max(f64::MIN_POSITIVE);
+        let bucket = (ratio.log2().floor().max(0.0) as usize).min(OVERRUN_BUCKETS - 1);
+
+        let mut stats = self.stats.lock().unwrap();
+        let idx = match stats.iter().position(|s| s.name == name) {
+            Some(idx) => idx,
+            None => {
+                stats.push(SlowJobStats {
+                    name,
+                    count: 0,
+                    total: Duration::ZERO,
+                    max: Duration::ZERO,
+                    overrun_histogram: [0; OVERRUN_BUCKETS],
+                });
+                stats.len() - 1
+            }
+        };
+
+        let job = &mut stats[idx];
+        job.count += 1;
+        job.total += elapsed;
+        job.max = job.max.max(elapsed);
+        job.overrun_histogram[bucket] += 1;
+    }
+
+    /// Statistics of every job name, the most time spent in slow runs first
+    pub fn stats(&self) -> Vec<SlowJobStats> {
+        let mut stats = self.stats.lock().unwrap().clone();
+        stats.sort_by_key(|s| std::cmp::Reverse(s.total));
+        return stats;
+    }
+}
+
+//-----------------------------------------------------------------------------
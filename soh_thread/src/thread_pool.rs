@@ -1,11 +1,64 @@
 //-----------------------------------------------------------------------------
-use super::{Job, Worker};
-use std::sync::{mpsc, Arc, Mutex};
+use super::{
+    BatchHandle, Job, JobError, JobHandle, Scheduler, Service, ShutdownSignal, SlowJobStats,
+    SlowJobs, Worker,
+};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 //-----------------------------------------------------------------------------
+/// What happens to the queued jobs when the pool shuts down
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ShutdownPolicy {
+    /// Execute all of the queued jobs before exiting
+    DrainQueue,
+    /// Cancel the queued jobs, only the running jobs are finished
+    CancelPending,
+    /// Execute the queued jobs until the timeout, then cancel the rest.
+    ///
+    /// Then the threads get another timeout to exit, threads that are still
+    /// busy after it are detached.
+    Timeout(Duration),
+}
 
+/// Pool of worker threads with a work-stealing scheduler.
+///
+/// Jobs added from a worker thread ( from inside of another job ) are put on
+/// that worker's own queue, idle workers steal them from the busy ones.
+///
+/// Long-running loops get their own threads with [ThreadPool::spawn_service],
+/// they are stopped together with the pool.
+///
+/// The pool is stopped with [ThreadPool::shutdown]. Dropping the pool is the
+/// same as shutting it down with [ShutdownPolicy::DrainQueue]: it waits for
+/// all of the queued jobs to finish, then signals the services to stop and
+/// waits for them.
+///
+/// ```rust
+/// use std::sync::atomic::{AtomicUsize, Ordering};
+/// use std::sync::Arc;
+///
+/// let pool = soh_thread::ThreadPool::new(4);
+/// let counter = Arc::new(AtomicUsize::new(0));
+///
+/// for _ in 0..100 {
+///     let counter = counter.clone();
+///     pool.add_job("count", move || {
+///         counter.fetch_add(1, Ordering::Relaxed);
+///         Ok(())
+///     });
+/// }
+///
+/// pool.wait();
+/// assert_eq!(counter.load(Ordering::Relaxed), 100);
+/// ```
 pub struct ThreadPool {
     workers: Vec<Worker>,
-    sender: Option<mpsc::Sender<Job>>,
+    scheduler: Arc<Scheduler>,
+
+    services: Mutex<Vec<Service>>,
+    shutdown_signal: ShutdownSignal,
+
+    slow_jobs: Arc<SlowJobs>,
 }
 
 impl ThreadPool {
@@ -15,59 +68,321 @@ impl ThreadPool {
     pub fn new(size: usize) -> ThreadPool {
         assert!(size > 2);
 
-        let (sender, receiver) = mpsc::channel();
-        let receiver = Arc::new(Mutex::new(receiver));
-
-        let mut workers = Vec::with_capacity(size);
+        let scheduler = Arc::new(Scheduler::new(size));
 
-        for _ in 1..size {
-            workers.push(Worker::new(receiver.clone()));
-        }
+        let workers = (0..size)
+            .map(|index| Worker::new(scheduler.clone(), index))
+            .collect();
 
         ThreadPool {
             workers,
-            sender: Some(sender),
+            scheduler,
+            services: Mutex::new(Vec::new()),
+            shutdown_signal: ShutdownSignal::new(),
+            slow_jobs: Arc::new(SlowJobs::new()),
         }
     }
 
-    /// This functions checks if there are any jobs in the queue. If there are
-    /// jobs that are yet to be submitted to the worker threads it sends it to
-    /// them.
+    /// Adds a new job. It will be executed by one of the worker threads.
+    ///
+    /// Errors of the job are reported by the pool, the returned handle can be
+    /// used to wait for the job and get its result.
+    pub fn add_job<F>(&self, job_name: &'static str, job: F) -> JobHandle
+    where
+        F: FnOnce() -> anyhow::Result<()> + Send + 'static,
+    {
+        return self.push_job(job_name, None, job);
+    }
+
+    /// Adds a new job which is expected to take at most `expected` ( a soft
+    /// deadline, the job isn't interrupted ).
+    ///
+    /// Runs that take longer are reported with the job name and the measured
+    /// time, and collected in [ThreadPool::slow_jobs].
+    ///
+    /// ```rust
+    /// use std::time::Duration;
+    ///
+    /// let pool = soh_thread::ThreadPool::new(4);
+    ///
+    /// let handle = pool.add_job_with_deadline("convert", Duration::ZERO, || {
+    ///     std::thread::sleep(Duration::from_millis(1));
+    ///     Ok(())
+    /// });
+    /// handle.wait().unwrap();
+    ///
+    /// let slow_jobs = pool.slow_jobs();
+    /// assert_eq!(slow_jobs[0].name, "convert");
+    /// assert_eq!(slow_jobs[0].count, 1);
+    /// ```
+    pub fn add_job_with_deadline<F>(
+        &self,
+        job_name: &'static str,
+        expected: Duration,
+        job: F,
+    ) -> JobHandle
+    where
+        F: FnOnce() -> anyhow::Result<()> + Send + 'static,
+    {
+        return self.push_job(job_name, Some(expected), job);
+    }
+
+    /// Gets the jobs that took longer than expected, the most time spent in
+    /// slow runs first
+    pub fn slow_jobs(&self) -> Vec<SlowJobStats> {
+        return self.slow_jobs.stats();
+    }
+
+    fn push_job<F>(&self, job_name: &'static str, expected: Option<Duration>, job: F) -> JobHandle
+    where
+        F: FnOnce() -> anyhow::Result<()> + Send + 'static,
+    {
+        let (handle, completer) = JobHandle::new();
+        let slow_jobs = self.slow_jobs.clone();
+
+        let job = move || {
+            let start = Instant::now();
+            let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(job));
+
+            if let Some(expected) = expected {
+                slow_jobs.record(job_name, expected, start.elapsed());
+            }
+
+            completer.complete(match &result {
+                Ok(Ok(())) => Ok(()),
+                Ok(Err(e)) => Err(JobError::Failed(e.to_string())),
+                Err(payload) => Err(JobError::Panicked(
+                    super::panic_reason(payload.as_ref()).to_owned(),
+                )),
+            });
+
+            match result {
+                Ok(result) => return result,
+                // Let the worker report the panic
+                Err(payload) => std::panic::resume_unwind(payload),
+            }
+        };
+
+        self.scheduler.push((job_name, Box::new(job)));
+
+        return handle;
+    }
+
+    /// Adds many jobs at once. All of the jobs are queued under a single lock,
+    /// which is much cheaper than adding the jobs one by one.
+    ///
+    /// ```rust
+    /// use std::sync::atomic::{AtomicUsize, Ordering};
+    /// use std::sync::Arc;
+    ///
+    /// let pool = soh_thread::ThreadPool::new(4);
+    /// let counter = Arc::new(AtomicUsize::new(0));
+    ///
+    /// let batch = pool.submit_batch(
+    ///     "count",
+    ///     (0..10_000).map(|_| {
+    ///         let counter = counter.clone();
+    ///         move || {
+    ///             counter.fetch_add(1, Ordering::Relaxed);
+    ///             Ok(())
+    ///         }
+    ///     }),
+    /// );
+    ///
+    /// batch.wait_all();
+    /// assert_eq!(counter.load(Ordering::Relaxed), 10_000);
+    /// ```
+    pub fn submit_batch<I, F>(&self, job_name: &'static str, jobs: I) -> BatchHandle
+    where
+        I: IntoIterator<Item = F>,
+        F: FnOnce() -> anyhow::Result<()> + Send + 'static,
+    {
+        let jobs = jobs.into_iter().collect::<Vec<_>>();
+        let handle = BatchHandle::new(jobs.len());
+
+        let jobs = jobs
+            .into_iter()
+            .map(|job| {
+                let guard = handle.job_guard();
+                let job: Job = (
+                    job_name,
+                    Box::new(move || {
+                        let _guard = guard;
+                        job()
+                    }),
+                );
+                job
+            })
+            .collect();
+
+        self.scheduler.push_batch(jobs);
+
+        return handle;
+    }
+
+    /// Starts a service loop on a dedicated thread outside of the job workers.
+    ///
+    /// The service receives the [ShutdownSignal] of the pool and has to return
+    /// once it's triggered. Errors and panics of the service are reported when
+    /// the pool is dropped.
+    ///
+    /// ```rust
+    /// use std::time::Duration;
     ///
-    /// It returns true if the thread pool has finished executing all of the
+    /// let pool = soh_thread::ThreadPool::new(4);
+    ///
+    /// pool.spawn_service("poller", |signal| {
+    ///     while !signal.wait_timeout(Duration::from_millis(10)) {
+    ///         // Poll something
+    ///     }
+    ///     Ok(())
+    /// })
+    /// .unwrap();
+    /// ```
+    pub fn spawn_service<F>(&self, name: &'static str, service: F) -> std::io::Result<()>
+    where
+        F: FnOnce(ShutdownSignal) -> anyhow::Result<()> + Send + 'static,
+    {
+        let service = Service::new(name, self.shutdown_signal.clone(), service)?;
+        self.services.lock().unwrap().push(service);
+
+        return Ok(());
+    }
+
+    /// Gets the names of the services that are still running
+    pub fn running_services(&self) -> Vec<&'static str> {
+        return self
+            .services
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|service| !service.is_finished())
+            .map(Service::name)
+            .collect();
+    }
+
+    /// Gets the number of jobs that are waiting in the queues and the jobs that
+    /// are currently being executed.
+    pub fn get_num_of_jobs(&self) -> usize {
+        return self.scheduler.get_num_of_jobs();
+    }
+
+    /// Returns true if the thread pool has finished executing all of the
     /// jobs, otherwise it returns false.
     pub fn poke(&self) -> bool {
-        let queue = &mut super::JOB_QUEUE.lock().unwrap();
+        return self.get_num_of_jobs() == 0;
+    }
 
-        if queue.get_num_of_jobs() == 0 {
-            return true;
-        }
+    /// Blocks until all of the jobs are finished.
+    ///
+    /// Must not be called from inside of a job.
+    pub fn wait(&self) {
+        self.scheduler.wait_idle();
+    }
+
+    /// Stops the workers and the services.
+    ///
+    /// Handles of the jobs that don't get executed resolve to
+    /// [JobError::Cancelled]. Running jobs are never interrupted.
+    ///
+    /// ```rust
+    /// use soh_thread::{JobError, ShutdownPolicy, ThreadPool};
+    ///
+    /// let pool = ThreadPool::new(4);
+    ///
+    /// let blocker = pool.add_job("blocker", || {
+    ///     std::thread::sleep(std::time::Duration::from_millis(1));
+    ///     Ok(())
+    /// });
+    /// let handles = (0..100)
+    ///     .map(|_| pool.add_job("job", || Ok(())))
+    ///     .collect::<Vec<_>>();
+    ///
+    /// pool.shutdown(ShutdownPolicy::CancelPending);
+    ///
+    /// // Every handle is resolved, either finished or cancelled
+    /// assert!(blocker.is_finished());
+    /// for handle in handles {
+    ///     assert!(matches!(handle.wait(), Ok(()) | Err(JobError::Cancelled)));
+    /// }
+    /// ```
+    pub fn shutdown(mut self, policy: ShutdownPolicy) {
+        self.stop(policy);
+    }
+
+    fn stop(&mut self, policy: ShutdownPolicy) {
+        let deadline = match policy {
+            ShutdownPolicy::DrainQueue => None,
+            ShutdownPolicy::CancelPending => {
+                self.scheduler.cancel_pending();
+                None
+            }
+            ShutdownPolicy::Timeout(timeout) => {
+                if !self.scheduler.wait_idle_until(Instant::now() + timeout) {
+                    self.scheduler.cancel_pending();
+                }
+                Some(Instant::now() + timeout)
+            }
+        };
+
+        self.scheduler.shutdown();
 
-        /*
-         * There may or may not be a job in the queue.
-         *
-         * If there is, send it to worker threads.
-         * If there isn't just return `false` because there is a job that's
-         * currently being executed.
-         */
-        if let Some(job) = queue.take_job() {
-            self.sender.as_ref().unwrap().send(job).unwrap();
+        for worker in self.workers.drain(..) {
+            if let Some(thread) = worker.thread {
+                if !finished_by(&thread, deadline) {
+                    super::report_error(
+                        "Worker is still busy after the shutdown timeout, detaching it",
+                    );
+                    continue;
+                }
+
+                thread.join().unwrap();
+            }
         }
 
-        return false;
+        // Jobs may still rely on the services, so they are stopped last
+        self.shutdown_signal.trigger();
+
+        for mut service in self.services.get_mut().unwrap().drain(..) {
+            if !service.finished_by(deadline) {
+                super::report_error(&format!(
+                    "Service \"{}\" is still running after the shutdown timeout, detaching it",
+                    service.name()
+                ));
+                continue;
+            }
+
+            service.join();
+        }
     }
 }
 
 impl Drop for ThreadPool {
     fn drop(&mut self) {
-        drop(self.sender.take());
+        // Nothing is left after an explicit shutdown
+        self.stop(ShutdownPolicy::DrainQueue);
+    }
+}
 
-        for worker in &mut self.workers {
-            if let Some(thread) = worker.thread.take() {
-                thread.join().unwrap();
-            }
+/// Waits until the thread exits or the `deadline` passes ( no deadline means
+/// waiting forever ). Returns true if the thread has exited.
+pub(crate) fn finished_by<T>(
+    thread: &std::thread::JoinHandle<T>,
+    deadline: Option<Instant>,
+) -> bool {
+    let Some(deadline) = deadline else {
+        return true;
+    };
+
+    while !thread.is_finished() {
+        if Instant::now() >= deadline {
+            return false;
         }
+
+        std::thread::sleep(Duration::from_millis(1));
     }
+
+    return true;
 }
 
 //-----------------------------------------------------------------------------
@@ -1,6 +1,6 @@
 //-----------------------------------------------------------------------------
-use super::Job;
-use std::sync::mpsc;
+use super::Scheduler;
+use std::sync::Arc;
 //-----------------------------------------------------------------------------
 
 pub struct Worker {
@@ -8,32 +8,29 @@ pub struct Worker {
 }
 
 impl Worker {
-    pub fn new(receiver: std::sync::Arc<std::sync::Mutex<mpsc::Receiver<Job>>>) -> Worker {
-        let thread = std::thread::spawn(move || loop {
-            let message = receiver.lock().unwrap().recv();
+    pub fn new(scheduler: Arc<Scheduler>, index: usize) -> Worker {
+        let thread = std::thread::spawn(move || {
+            scheduler.register_worker(index);
 
-            match message {
-                Ok((job_name, job)) => {
-                    let _handle = super::JobQueueHandle;
+            while let Some((job_name, job)) = scheduler.wait_for_job(index) {
+                let _guard = FinishGuard(&scheduler);
 
-                    if let Err(e) = job() {
-                        #[cfg(feature = "log")]
-                        soh_log::log_warning!(
-                            "Error occured when running the task \"{}\":\n{}",
-                            job_name,
-                            e
-                        );
-
-                        #[cfg(not(feature = "log"))]
-                        eprintln!(
+                let msg = match std::panic::catch_unwind(std::panic::AssertUnwindSafe(job)) {
+                    Ok(Ok(())) => continue,
+                    Ok(Err(e)) => {
+                        format!(
                             "Error occured when running the task \"{}\":\n{}",
                             job_name, e
-                        );
+                        )
                     }
-                }
-                Err(_) => {
-                    break;
-                }
+                    Err(payload) => format!(
+                        "Task \"{}\" panicked: {}",
+                        job_name,
+                        super::panic_reason(payload.as_ref())
+                    ),
+                };
+
+                super::report_error(&msg);
             }
         });
 
@@ -44,3 +41,16 @@ impl Worker {
 }
 
 //-----------------------------------------------------------------------------
+/// This structure is used to automatically signal that a job has been finished
+/// once it goes out of scope ( even if the job panics ).
+///
+/// This helps to avoid having to call [Scheduler::signal_finished] manually
+struct FinishGuard<'a>(&'a Scheduler);
+
+impl Drop for FinishGuard<'_> {
+    fn drop(&mut self) {
+        self.0.signal_finished();
+    }
+}
+
+//-----------------------------------------------------------------------------
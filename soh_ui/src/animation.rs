@@ -0,0 +1,149 @@
+//-----------------------------------------------------------------------------
+//! Transitions of widget properties
+//!
+//! A widget with a [`Transition`] (see [`crate::Ui::set_transition`]) animates the changes of its
+//! position and size, fades in and out when shown or hidden and eases its hover state. The
+//! animations advance with [`crate::Ui::update`].
+//-----------------------------------------------------------------------------
+use crate::draw::Color;
+use crate::Rect;
+use soh_math::ease::Ease;
+use soh_math::Vec2;
+//-----------------------------------------------------------------------------
+
+/// How a property changes to a new value
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Transition {
+    /// Duration in seconds
+    pub duration: f32,
+    pub easing: Ease,
+}
+
+/// Value which is animated towards its target
+#[derive(Debug, Clone, PartialEq)]
+pub struct Tween<T> {
+    from: T,
+    to: T,
+    /// Seconds since the start
+    elapsed: f32,
+    transition: Transition,
+}
+
+/// Values which can be interpolated
+pub trait Animatable: Clone + PartialEq {
+    fn lerp(&self, other: &Self, t: f32) -> Self;
+}
+
+//-----------------------------------------------------------------------------
+
+impl Transition {
+    pub fn new(duration: f32) -> Self {
+        return Transition {
+            duration,
+            easing: Ease::CubicOut,
+        };
+    }
+
+    pub fn easing(mut self, easing: Ease) -> Self {
+        self.easing = easing;
+        return self;
+    }
+}
+
+impl Default for Transition {
+    fn default() -> Self {
+        return Transition::new(0.15);
+    }
+}
+
+//-----------------------------------------------------------------------------
+// Constructor
+impl<T: Animatable> Tween<T> {
+    /// Tween resting at the value
+    pub fn new(value: T) -> Self {
+        return Tween {
+            from: value.clone(),
+            to: value,
+            elapsed: 0.0,
+            transition: Transition::new(0.0),
+        };
+    }
+}
+
+//-----------------------------------------------------------------------------
+// Getters
+impl<T: Animatable> Tween<T> {
+    /// Current value
+    pub fn value(&self) -> T {
+        if self.is_finished() {
+            return self.to.clone();
+        }
+
+        let t = self
+            .transition
+            .easing
+            .apply(self.elapsed / self.transition.duration);
+        return self.from.lerp(&self.to, t);
+    }
+    pub fn target(&self) -> &T {
+        return &self.to;
+    }
+    pub fn is_finished(&self) -> bool {
+        return self.elapsed >= self.transition.duration;
+    }
+}
+
+//-----------------------------------------------------------------------------
+// Specific implementation
+impl<T: Animatable> Tween<T> {
+    /// Animate from the current value to the target (nothing changes if the target is the same)
+    pub fn set(&mut self, target: T, transition: Transition) {
+        if self.to == target {
+            return;
+        }
+
+        self.from = self.value();
+        self.to = target;
+        self.elapsed = 0.0;
+        self.transition = transition;
+    }
+
+    /// Jump to the value without animating
+    pub fn set_now(&mut self, value: T) {
+        *self = Tween::new(value);
+    }
+
+    /// Advance by `dt` seconds
+    pub fn update(&mut self, dt: f32) {
+        self.elapsed = (self.elapsed + dt).min(self.transition.duration);
+    }
+}
+
+//-----------------------------------------------------------------------------
+
+impl Animatable for f32 {
+    fn lerp(&self, other: &Self, t: f32) -> Self {
+        return soh_math::lerp(*self, *other, t);
+    }
+}
+
+impl Animatable for Vec2<f32> {
+    fn lerp(&self, other: &Self, t: f32) -> Self {
+        return soh_math::lerp(*self, *other, t);
+    }
+}
+
+impl Animatable for Rect {
+    fn lerp(&self, other: &Self, t: f32) -> Self {
+        return Rect::new(self.min.lerp(&other.min, t), self.max.lerp(&other.max, t));
+    }
+}
+
+impl Animatable for Color {
+    fn lerp(&self, other: &Self, t: f32) -> Self {
+        let rgb = soh_math::color::lerp::lerp_rgb(&self.rgb, &other.rgb, t as f64);
+        return Color::new(rgb, self.alpha.lerp(&other.alpha, t));
+    }
+}
+
+//-----------------------------------------------------------------------------
@@ -0,0 +1,31 @@
+//-----------------------------------------------------------------------------
+//! Clipboard access
+//!
+//! The UI only talks to the [`Clipboard`] trait, so the host application can plug in the system
+//! clipboard (e.g. with `arboard`). By default the clipboard is local to the process.
+//-----------------------------------------------------------------------------
+
+pub trait Clipboard {
+    fn get(&mut self) -> Option<String>;
+    fn set(&mut self, text: &str);
+}
+
+/// Clipboard which is only shared between the widgets of the application
+#[derive(Debug, Default, Clone)]
+pub struct LocalClipboard {
+    text: Option<String>,
+}
+
+//-----------------------------------------------------------------------------
+
+impl Clipboard for LocalClipboard {
+    fn get(&mut self) -> Option<String> {
+        return self.text.clone();
+    }
+
+    fn set(&mut self, text: &str) {
+        self.text = Some(text.to_owned());
+    }
+}
+
+//-----------------------------------------------------------------------------
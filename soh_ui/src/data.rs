@@ -0,0 +1,600 @@
+//-----------------------------------------------------------------------------
+//! Widget trees described in data files ( requires the `serde` feature )
+//!
+//! A [`WidgetDesc`] is a serializable description of a widget with its style and children. The
+//! [`WidgetRegistry`] knows the widget types by name, builds the widgets from their properties
+//! and describes existing widgets. [`UiFile`] loads a RON or JSON file and loads it again when
+//! it changes, so the UI can be edited while the game runs.
+//!
+//! ```
+//! use soh_ui::data::{WidgetDesc, WidgetRegistry};
+//! use soh_ui::{layout::Style, Ui};
+//!
+//! let desc = WidgetDesc::from_ron(r#"(
+//!     type: "Container",
+//!     style: (direction: Column, padding: (left: 4, right: 4, top: 4, bottom: 4)),
+//!     children: [
+//!         (type: "Label", props: { "text": "Name" }),
+//!         (type: "TextInput", name: Some("name"), props: { "placeholder": "Player" }),
+//!     ],
+//! )"#).unwrap();
+//!
+//! let mut ui = Ui::new(Style::default());
+//! let root = ui.root();
+//! let loaded = WidgetRegistry::new().build(&mut ui, root, &desc).unwrap();
+//! assert!(loaded.get("name").is_some());
+//! ```
+//-----------------------------------------------------------------------------
+use crate::draw::Color;
+use crate::error::bail;
+use crate::layout::Style;
+use crate::widget::Widget;
+use crate::{Error, Result};
+use crate::{Ui, WidgetId};
+use serde::{Deserialize, Serialize};
+use soh_math::color::Rgb;
+use std::any::{Any, TypeId};
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+//-----------------------------------------------------------------------------
+
+/// Serializable description of a widget tree
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WidgetDesc {
+    /// Name of the widget type in the registry
+    #[serde(rename = "type")]
+    pub widget: String,
+    /// Used to find the widget after it's built (see [`LoadedTree::get`])
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(default, skip_serializing_if = "Props::is_empty")]
+    pub props: Props,
+    #[serde(default)]
+    pub style: Style,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub children: Vec<WidgetDesc>,
+}
+
+/// Properties of a widget ( the fields which aren't part of the style )
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Props(BTreeMap<String, PropValue>);
+
+/// Value of a property, the same in all file formats
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum PropValue {
+    Bool(bool),
+    Number(f64),
+    String(String),
+    /// Colors are `[r, g, b]` or `[r, g, b, alpha]` with rgb in 0 - 255 and alpha in 0.0 - 1.0
+    List(Vec<PropValue>),
+}
+
+/// Widget which can be built from properties and described by them
+pub trait WidgetData: Widget + Sized {
+    /// Name of the type in the data files
+    const TYPE: &'static str;
+
+    fn from_props(props: &Props) -> Result<Self>;
+
+    fn to_props(&self) -> Props;
+}
+
+/// Widget types known by name
+pub struct WidgetRegistry {
+    builders: HashMap<String, Builder>,
+    describers: HashMap<TypeId, (&'static str, Describer)>,
+}
+
+type Builder = fn(&Props) -> Result<Box<dyn Widget>>;
+type Describer = fn(&dyn Any) -> Props;
+
+/// Widgets built from a description
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LoadedTree {
+    pub root: WidgetId,
+    /// Widgets with a name
+    pub names: HashMap<String, WidgetId>,
+}
+
+/// Data file of a widget tree which is loaded again when it changes
+///
+/// The format is chosen by the extension: `.json` for JSON, anything else for RON.
+#[derive(Debug)]
+pub struct UiFile {
+    path: PathBuf,
+    modified: Option<SystemTime>,
+    loaded: Option<LoadedTree>,
+}
+
+//-----------------------------------------------------------------------------
+
+impl WidgetDesc {
+    pub fn new(widget: impl Into<String>) -> Self {
+        return WidgetDesc {
+            widget: widget.into(),
+            name: None,
+            props: Props::default(),
+            style: Style::default(),
+            children: vec![],
+        };
+    }
+
+    pub fn from_ron(text: &str) -> Result<Self> {
+        return Ok(ron::from_str(text)?);
+    }
+
+    pub fn to_ron(&self) -> Result<String> {
+        let config = ron::ser::PrettyConfig::default();
+        return Ok(ron::ser::to_string_pretty(self, config)?);
+    }
+
+    pub fn from_json(text: &str) -> Result<Self> {
+        return Ok(serde_json::from_str(text)?);
+    }
+
+    pub fn to_json(&self) -> Result<String> {
+        return Ok(serde_json::to_string_pretty(self)?);
+    }
+}
+
+//-----------------------------------------------------------------------------
+
+impl Props {
+    pub fn new() -> Self {
+        return Self::default();
+    }
+
+    pub fn is_empty(&self) -> bool {
+        return self.0.is_empty();
+    }
+
+    pub fn get(&self, key: &str) -> Option<&PropValue> {
+        return self.0.get(key);
+    }
+
+    pub fn set(&mut self, key: impl Into<String>, value: impl Into<PropValue>) -> &mut Self {
+        self.0.insert(key.into(), value.into());
+        return self;
+    }
+
+    pub fn bool(&self, key: &str) -> Result<Option<bool>> {
+        return match self.get(key) {
+            None => Ok(None),
+            Some(PropValue::Bool(value)) => Ok(Some(*value)),
+            Some(value) => Err(wrong_type(key, "a bool", value)),
+        };
+    }
+
+    pub fn number(&self, key: &str) -> Result<Option<f64>> {
+        return match self.get(key) {
+            None => Ok(None),
+            Some(PropValue::Number(value)) => Ok(Some(*value)),
+            Some(value) => Err(wrong_type(key, "a number", value)),
+        };
+    }
+
+    pub fn string(&self, key: &str) -> Result<Option<&str>> {
+        return match self.get(key) {
+            None => Ok(None),
+            Some(PropValue::String(value)) => Ok(Some(value)),
+            Some(value) => Err(wrong_type(key, "a string", value)),
+        };
+    }
+
+    pub fn color(&self, key: &str) -> Result<Option<Color>> {
+        let Some(value) = self.get(key) else {
+            return Ok(None);
+        };
+
+        let components = match value {
+            PropValue::List(list) => list
+                .iter()
+                .map(|value| match value {
+                    PropValue::Number(number) => Some(*number),
+                    _ => None,
+                })
+                .collect::<Option<Vec<_>>>(),
+            _ => None,
+        };
+
+        return match components.as_deref() {
+            Some(&[r, g, b]) => Ok(Some(Color::opaque(rgb(r, g, b)))),
+            Some(&[r, g, b, alpha]) => Ok(Some(Color::new(rgb(r, g, b), alpha as f32))),
+            _ => Err(wrong_type(key, "a color", value)),
+        };
+    }
+}
+
+fn rgb(r: f64, g: f64, b: f64) -> Rgb {
+    let channel = |value: f64| value.clamp(0.0, 255.0).round() as u8;
+    return Rgb::new(channel(r), channel(g), channel(b));
+}
+
+fn wrong_type(key: &str, expected: &str, value: &PropValue) -> Error {
+    return Error::InvalidData(format!(
+        "Property \"{key}\" should be {expected}, got {value:?}"
+    ));
+}
+
+impl From<bool> for PropValue {
+    fn from(value: bool) -> Self {
+        return PropValue::Bool(value);
+    }
+}
+
+impl From<f64> for PropValue {
+    fn from(value: f64) -> Self {
+        return PropValue::Number(value);
+    }
+}
+
+impl From<f32> for PropValue {
+    fn from(value: f32) -> Self {
+        return PropValue::Number(value as f64);
+    }
+}
+
+impl From<&str> for PropValue {
+    fn from(value: &str) -> Self {
+        return PropValue::String(value.to_owned());
+    }
+}
+
+impl From<String> for PropValue {
+    fn from(value: String) -> Self {
+        return PropValue::String(value);
+    }
+}
+
+impl From<Color> for PropValue {
+    fn from(color: Color) -> Self {
+        let Rgb { r, g, b } = color.rgb;
+        let mut list = vec![r as f64, g as f64, b as f64];
+        if color.alpha != 1.0 {
+            list.push(color.alpha as f64);
+        }
+
+        return PropValue::List(list.into_iter().map(PropValue::Number).collect());
+    }
+}
+
+//-----------------------------------------------------------------------------
+
+impl WidgetRegistry {
+    /// Registry with the widgets of this crate which can be described by properties
+    pub fn new() -> Self {
+        let mut registry = WidgetRegistry::empty();
+        registry.register::<crate::widget::Container>();
+        registry.register::<crate::widget::Label>();
+        registry.register::<crate::widget::TextInput>();
+
+        return registry;
+    }
+
+    pub fn empty() -> Self {
+        return WidgetRegistry {
+            builders: HashMap::new(),
+            describers: HashMap::new(),
+        };
+    }
+
+    /// Add a widget type (replaces the type registered with the same name)
+    pub fn register<W: WidgetData>(&mut self) -> &mut Self {
+        self.builders.insert(W::TYPE.to_owned(), |props| {
+            return Ok(Box::new(W::from_props(props)?));
+        });
+        self.describers.insert(
+            TypeId::of::<W>(),
+            (W::TYPE, |widget| {
+                let widget = widget.downcast_ref::<W>().expect("Widget of another type");
+                return widget.to_props();
+            }),
+        );
+
+        return self;
+    }
+
+    pub fn contains(&self, widget: &str) -> bool {
+        return self.builders.contains_key(widget);
+    }
+
+    /// Add the described widgets as the last child of `parent`
+    ///
+    /// Nothing is added if a widget can't be built.
+    pub fn build(&self, ui: &mut Ui, parent: WidgetId, desc: &WidgetDesc) -> Result<LoadedTree> {
+        let mut names = HashMap::new();
+        let widget = self.build_widget(desc)?;
+        let root = ui.add_boxed(parent, widget, desc.style);
+
+        if let Err(err) = self.build_children(ui, root, desc, &mut names) {
+            ui.remove(root);
+            return Err(err);
+        }
+        if let Some(name) = &desc.name {
+            names.insert(name.clone(), root);
+        }
+
+        return Ok(LoadedTree { root, names });
+    }
+
+    /// Describe the widget with its children (fails for widget types which aren't registered)
+    pub fn describe(&self, ui: &Ui, id: WidgetId) -> Result<WidgetDesc> {
+        let widget: &dyn Any = ui.widget_dyn(id);
+        let Some((name, describer)) = self.describers.get(&widget.type_id()) else {
+            bail!(InvalidData, "Widget {id:?} isn't of a registered type");
+        };
+
+        let children = ui
+            .children(id)
+            .iter()
+            .map(|&child| self.describe(ui, child))
+            .collect::<Result<Vec<_>>>()?;
+
+        return Ok(WidgetDesc {
+            widget: (*name).to_owned(),
+            name: None,
+            props: describer(widget),
+            style: *ui.style(id),
+            children,
+        });
+    }
+
+    fn build_widget(&self, desc: &WidgetDesc) -> Result<Box<dyn Widget>> {
+        let Some(builder) = self.builders.get(&desc.widget) else {
+            bail!(InvalidData, "Unknown widget type \"{}\"", desc.widget);
+        };
+
+        return builder(&desc.props).map_err(|err| {
+            return Error::InvalidData(format!("Invalid \"{}\": {err}", desc.widget));
+        });
+    }
+
+    fn build_children(
+        &self,
+        ui: &mut Ui,
+        parent: WidgetId,
+        desc: &WidgetDesc,
+        names: &mut HashMap<String, WidgetId>,
+    ) -> Result<()> {
+        for child_desc in desc.children.iter() {
+            let widget = self.build_widget(child_desc)?;
+            let child = ui.add_boxed(parent, widget, child_desc.style);
+            if let Some(name) = &child_desc.name {
+                names.insert(name.clone(), child);
+            }
+
+            self.build_children(ui, child, child_desc, names)?;
+        }
+
+        return Ok(());
+    }
+}
+
+impl Default for WidgetRegistry {
+    fn default() -> Self {
+        return Self::new();
+    }
+}
+
+impl LoadedTree {
+    /// Widget with the name
+    pub fn get(&self, name: &str) -> Option<WidgetId> {
+        return self.names.get(name).copied();
+    }
+}
+
+//-----------------------------------------------------------------------------
+
+impl UiFile {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        return UiFile {
+            path: path.into(),
+            modified: None,
+            loaded: None,
+        };
+    }
+
+    pub fn path(&self) -> &Path {
+        return &self.path;
+    }
+
+    /// Widgets of the last successful load
+    pub fn loaded(&self) -> Option<&LoadedTree> {
+        return self.loaded.as_ref();
+    }
+
+    /// Load the file if it changed since the last call (or wasn't loaded yet), returns whether
+    /// the widgets were replaced
+    ///
+    /// The new tree takes the place of the old one in `parent`. If the file is invalid the old
+    /// widgets stay (so a typo doesn't break the running UI) and the error is returned.
+    pub fn reload(
+        &mut self,
+        ui: &mut Ui,
+        parent: WidgetId,
+        registry: &WidgetRegistry,
+    ) -> Result<bool> {
+        let file_error = |err: Error| {
+            return Error::File {
+                path: self.path.clone(),
+                source: Box::new(err),
+            };
+        };
+
+        let modified = std::fs::metadata(&self.path)
+            .and_then(|metadata| metadata.modified())
+            .map_err(|err| file_error(err.into()))?;
+        if self.modified == Some(modified) {
+            return Ok(false);
+        }
+        self.modified = Some(modified);
+
+        let text = std::fs::read_to_string(&self.path).map_err(|err| file_error(err.into()))?;
+        let desc = match self.path.extension().is_some_and(|ext| ext == "json") {
+            true => WidgetDesc::from_json(&text),
+            false => WidgetDesc::from_ron(&text),
+        }
+        .map_err(file_error)?;
+
+        let loaded = registry.build(ui, parent, &desc)?;
+
+        /*
+         * Replace the old widgets
+         */
+        if let Some(old) = self.loaded.take() {
+            if ui.contains(old.root) {
+                let index = ui.children(parent).iter().position(|&id| id == old.root);
+                ui.remove(old.root);
+                if let Some(index) = index {
+                    ui.set_index(loaded.root, index);
+                }
+            }
+        }
+        self.loaded = Some(loaded);
+
+        return Ok(true);
+    }
+}
+
+//-----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::layout::{Dimension, Direction};
+    use crate::widget::{Container, Label, TextInput};
+    use std::time::Duration;
+
+    const FORM: &str = r#"(
+        type: "Container",
+        props: { "panel": true, "background": [10, 20, 30, 0.5] },
+        style: (direction: Column, gap: 4, width: Px(200)),
+        children: [
+            (type: "Label", name: Some("title"), props: { "text": "Name", "font_size": 20 }),
+            (type: "TextInput", name: Some("name"), props: { "placeholder": "Player" }),
+        ],
+    )"#;
+
+    #[test]
+    fn test_build_and_describe() {
+        let registry = WidgetRegistry::new();
+        let desc = WidgetDesc::from_ron(FORM).unwrap();
+
+        let mut ui = Ui::new(Style::default());
+        let root = ui.root();
+        let loaded = registry.build(&mut ui, root, &desc).unwrap();
+
+        let form = ui.widget::<Container>(loaded.root).unwrap();
+        assert!(form.panel);
+        assert_eq!(form.background, Some(Color::new(Rgb::new(10, 20, 30), 0.5)));
+        assert_eq!(ui.style(loaded.root).direction, Direction::Column);
+        assert_eq!(ui.style(loaded.root).width, Dimension::Px(200.0));
+        let title = loaded.get("title").unwrap();
+        assert_eq!(ui.widget::<Label>(title).unwrap().text(), "Name");
+        assert!(ui
+            .widget::<TextInput>(loaded.get("name").unwrap())
+            .is_some());
+
+        // Described back (without the names), the same in RON and JSON
+        let described = registry.describe(&ui, loaded.root).unwrap();
+        let unnamed = WidgetDesc {
+            children: desc
+                .children
+                .iter()
+                .map(|child| WidgetDesc {
+                    name: None,
+                    ..child.clone()
+                })
+                .collect(),
+            ..desc.clone()
+        };
+        assert_eq!(described, unnamed);
+        assert_eq!(
+            WidgetDesc::from_ron(&described.to_ron().unwrap()).unwrap(),
+            described
+        );
+        assert_eq!(
+            WidgetDesc::from_json(&described.to_json().unwrap()).unwrap(),
+            described
+        );
+    }
+
+    #[test]
+    fn test_build_errors() {
+        let registry = WidgetRegistry::new();
+        let mut ui = Ui::new(Style::default());
+        let root = ui.root();
+
+        let mut desc = WidgetDesc::new("Container");
+        desc.children.push(WidgetDesc::new("Label"));
+        desc.children.push(WidgetDesc::new("Button"));
+        let err = registry.build(&mut ui, root, &desc).unwrap_err();
+        assert_eq!(err.to_string(), "Unknown widget type \"Button\"");
+        assert!(ui.children(ui.root()).is_empty());
+
+        let mut desc = WidgetDesc::new("Label");
+        desc.props.set("text", 1.0);
+        let err = registry.build(&mut ui, root, &desc).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Invalid \"Label\": Property \"text\" should be a string, got Number(1.0)"
+        );
+
+        // Widgets of unknown types can't be described
+        let registry = WidgetRegistry::empty();
+        let label = ui.add(ui.root(), Label::new("label"), Style::default());
+        assert!(registry.describe(&ui, label).is_err());
+    }
+
+    #[test]
+    fn test_hot_reload() {
+        let path = std::env::temp_dir().join(format!("soh_ui_{}.json", std::process::id()));
+        let write = |text: &str, seconds: u64| {
+            std::fs::write(&path, text).unwrap();
+            let file = std::fs::File::options().write(true).open(&path).unwrap();
+            file.set_modified(SystemTime::UNIX_EPOCH + Duration::from_secs(seconds))
+                .unwrap();
+        };
+        let label = |text: &str| {
+            return format!(
+                r#"{{ "type": "Label", "name": "label", "props": {{ "text": "{text}" }} }}"#
+            );
+        };
+
+        let registry = WidgetRegistry::new();
+        let mut ui = Ui::new(Style::default());
+        let root = ui.root();
+        let before = ui.add(ui.root(), Label::new("before"), Style::default());
+        let after = ui.add(ui.root(), Label::new("after"), Style::default());
+        let mut file = UiFile::new(&path);
+        let text = |ui: &Ui, file: &UiFile| {
+            let id = file.loaded().unwrap().get("label").unwrap();
+            return ui.widget::<Label>(id).unwrap().text().to_owned();
+        };
+
+        write(&label("one"), 1);
+        assert!(file.reload(&mut ui, root, &registry).unwrap());
+        assert!(!file.reload(&mut ui, root, &registry).unwrap());
+        ui.set_index(file.loaded().unwrap().root, 1);
+
+        // Replaced in place
+        write(&label("two"), 2);
+        assert!(file.reload(&mut ui, root, &registry).unwrap());
+        assert_eq!(text(&ui, &file), "two");
+        let loaded = file.loaded().unwrap().root;
+        assert_eq!(ui.children(root), [before, loaded, after]);
+
+        // Invalid files keep the old widgets
+        write("{ \"type\": ", 3);
+        assert!(file.reload(&mut ui, root, &registry).is_err());
+        assert_eq!(text(&ui, &file), "two");
+        assert_eq!(ui.children(ui.root()).len(), 3);
+
+        std::fs::remove_file(&path).unwrap();
+    }
+}
+
+//-----------------------------------------------------------------------------
@@ -0,0 +1,465 @@
+//-----------------------------------------------------------------------------
+use crate::error::{bail, ensure};
+use crate::layout::Direction;
+use crate::Result;
+use std::fmt::Write;
+use std::str::FromStr;
+//-----------------------------------------------------------------------------
+
+/// Arrangement of the docked panels (panels are identified by name)
+///
+/// The layout is serialized to a short text form with `to_string` and restored with `parse`:
+///
+/// ```text
+/// split(row, 0.25, tabs(0, "scene", "assets"), tabs(0, "viewport"))
+/// ```
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct DockLayout {
+    root: Option<DockNode>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum DockNode {
+    /// Two nodes next to each other (`Row`) or on top of each other (`Column`)
+    Split {
+        direction: Direction,
+        /// Fraction of the space given to the first node
+        ratio: f32,
+        children: Box<[DockNode; 2]>,
+    },
+    /// Panels sharing the same space, only the active one is visible
+    Tabs { panels: Vec<String>, active: usize },
+}
+
+/// Where a panel is docked relative to the target
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DockPosition {
+    Left,
+    Right,
+    Top,
+    Bottom,
+    /// Another tab of the target
+    Center,
+}
+
+//-----------------------------------------------------------------------------
+// Getters
+impl DockLayout {
+    pub fn new() -> Self {
+        return Self::default();
+    }
+
+    pub fn root(&self) -> Option<&DockNode> {
+        return self.root.as_ref();
+    }
+
+    pub fn is_empty(&self) -> bool {
+        return self.root.is_none();
+    }
+
+    pub fn contains(&self, panel: &str) -> bool {
+        return self.panels().contains(&panel);
+    }
+
+    /// Names of all docked panels
+    pub fn panels(&self) -> Vec<&str> {
+        let mut panels = vec![];
+        let mut stack = self.root.iter().collect::<Vec<_>>();
+
+        while let Some(node) = stack.pop() {
+            match node {
+                DockNode::Split { children, .. } => stack.extend(children.iter().rev()),
+                DockNode::Tabs { panels: tabs, .. } => {
+                    panels.extend(tabs.iter().map(String::as_str))
+                }
+            }
+        }
+
+        return panels;
+    }
+}
+
+//-----------------------------------------------------------------------------
+// Specific implementation
+impl DockLayout {
+    /// Dock a panel (moving it if it's already docked)
+    ///
+    /// * `target`: the panel to dock next to, `None` (or a panel which isn't docked) docks to the
+    ///   edge of the whole layout
+    pub fn add(&mut self, panel: &str, target: Option<&str>, position: DockPosition) {
+        self.remove(panel);
+
+        let Some(root) = &mut self.root else {
+            self.root = Some(DockNode::tabs([panel]));
+            return;
+        };
+
+        match target.and_then(|target| root.find_tabs_mut(target)) {
+            Some(tabs) => tabs.insert(panel, position, 0.5),
+            None => root.insert(panel, position, 0.25),
+        }
+    }
+
+    /// Undock a panel, returns false if it wasn't docked
+    pub fn remove(&mut self, panel: &str) -> bool {
+        if !self.contains(panel) {
+            return false;
+        }
+
+        self.root = self.root.take().and_then(|root| root.without(panel));
+        return true;
+    }
+
+    /// Make the panel the active tab, returns false if it isn't docked
+    pub fn activate(&mut self, panel: &str) -> bool {
+        let Some(DockNode::Tabs { panels, active }) = self
+            .root
+            .as_mut()
+            .and_then(|root| root.find_tabs_mut(panel))
+        else {
+            return false;
+        };
+
+        *active = panels.iter().position(|p| p == panel).unwrap_or(0);
+        return true;
+    }
+
+    /// Node at the path of child indices (`[]` is the root)
+    pub fn node_at(&self, path: &[usize]) -> Option<&DockNode> {
+        let mut node = self.root.as_ref()?;
+
+        for &index in path {
+            match node {
+                DockNode::Split { children, .. } => node = children.get(index)?,
+                DockNode::Tabs { .. } => return None,
+            }
+        }
+
+        return Some(node);
+    }
+
+    /// Change the ratio of the split at the path (see [`DockLayout::node_at`])
+    pub fn set_ratio(&mut self, path: &[usize], new_ratio: f32) {
+        let mut node = self.root.as_mut();
+
+        for &index in path {
+            node = match node {
+                Some(DockNode::Split { children, .. }) => children.get_mut(index),
+                _ => None,
+            };
+        }
+
+        if let Some(DockNode::Split { ratio, .. }) = node {
+            *ratio = new_ratio.clamp(0.0, 1.0);
+        }
+    }
+}
+
+impl DockNode {
+    pub fn tabs<'a>(panels: impl IntoIterator<Item = &'a str>) -> Self {
+        return DockNode::Tabs {
+            panels: panels.into_iter().map(str::to_owned).collect(),
+            active: 0,
+        };
+    }
+
+    /// Tabs node containing the panel
+    fn find_tabs_mut(&mut self, panel: &str) -> Option<&mut DockNode> {
+        let contains =
+            matches!(self, DockNode::Tabs { panels, .. } if panels.iter().any(|p| p == panel));
+        if contains {
+            return Some(self);
+        }
+
+        let DockNode::Split { children, .. } = self else {
+            return None;
+        };
+        let [first, second] = &mut **children;
+
+        if let Some(tabs) = first.find_tabs_mut(panel) {
+            return Some(tabs);
+        }
+        return second.find_tabs_mut(panel);
+    }
+
+    /// Insert a panel into this node (as a tab) or next to it
+    ///
+    /// * `ratio`: the fraction of the space given to the new panel
+    fn insert(&mut self, panel: &str, position: DockPosition, ratio: f32) {
+        let (direction, first) = match position {
+            DockPosition::Center => {
+                if let DockNode::Tabs { panels, active } = self {
+                    panels.push(panel.to_owned());
+                    *active = panels.len() - 1;
+                    return;
+                }
+                // Can't add tabs to a split, put the panel next to it instead
+                (Direction::Row, false)
+            }
+            DockPosition::Left => (Direction::Row, true),
+            DockPosition::Right => (Direction::Row, false),
+            DockPosition::Top => (Direction::Column, true),
+            DockPosition::Bottom => (Direction::Column, false),
+        };
+
+        let old = std::mem::replace(self, DockNode::tabs([]));
+        let new = DockNode::tabs([panel]);
+
+        *self = match first {
+            true => DockNode::Split {
+                direction,
+                ratio,
+                children: Box::new([new, old]),
+            },
+            false => DockNode::Split {
+                direction,
+                ratio: 1.0 - ratio,
+                children: Box::new([old, new]),
+            },
+        };
+    }
+
+    /// The node without the panel (None if nothing is left)
+    fn without(self, panel: &str) -> Option<DockNode> {
+        match self {
+            DockNode::Tabs { mut panels, active } => {
+                if let Some(index) = panels.iter().position(|p| p == panel) {
+                    panels.remove(index);
+                }
+                if panels.is_empty() {
+                    return None;
+                }
+
+                let active = active.min(panels.len() - 1);
+                return Some(DockNode::Tabs { panels, active });
+            }
+            DockNode::Split {
+                direction,
+                ratio,
+                children,
+            } => {
+                let [first, second] = *children;
+
+                return match (first.without(panel), second.without(panel)) {
+                    (Some(first), Some(second)) => Some(DockNode::Split {
+                        direction,
+                        ratio,
+                        children: Box::new([first, second]),
+                    }),
+                    (Some(node), None) | (None, Some(node)) => Some(node),
+                    (None, None) => None,
+                };
+            }
+        }
+    }
+}
+
+//-----------------------------------------------------------------------------
+// Serialization
+
+impl std::fmt::Display for DockLayout {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        return match &self.root {
+            Some(root) => write!(f, "{root}"),
+            None => write!(f, "empty"),
+        };
+    }
+}
+
+impl std::fmt::Display for DockNode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DockNode::Split {
+                direction,
+                ratio,
+                children,
+            } => {
+                let direction = match direction {
+                    Direction::Row => "row",
+                    Direction::Column => "column",
+                };
+                let [first, second] = &**children;
+                return write!(f, "split({direction}, {ratio}, {first}, {second})");
+            }
+            DockNode::Tabs { panels, active } => {
+                write!(f, "tabs({active}")?;
+                for panel in panels {
+                    f.write_str(", \"")?;
+                    for c in panel.chars() {
+                        if c == '"' || c == '\\' {
+                            f.write_char('\\')?;
+                        }
+                        f.write_char(c)?;
+                    }
+                    f.write_char('"')?;
+                }
+                return f.write_char(')');
+            }
+        }
+    }
+}
+
+impl FromStr for DockLayout {
+    type Err = crate::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let mut parser = Parser { input: s, pos: 0 };
+
+        let root = match parser.peek_word() == "empty" {
+            true => {
+                parser.word()?;
+                None
+            }
+            false => Some(parser.node()?),
+        };
+
+        parser.skip_whitespace();
+        ensure!(
+            parser.pos == s.len(),
+            InvalidLayout,
+            "Unexpected text after the dock layout at {}",
+            parser.pos
+        );
+
+        return Ok(DockLayout { root });
+    }
+}
+
+/// Recursive descent parser of the serialized layout
+struct Parser<'a> {
+    input: &'a str,
+    pos: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn node(&mut self) -> Result<DockNode> {
+        let word = self.word()?;
+        self.expect('(')?;
+
+        let node = match word {
+            "split" => {
+                let direction = match self.word()? {
+                    "row" => Direction::Row,
+                    "column" => Direction::Column,
+                    other => bail!(InvalidLayout, "Unknown split direction \"{other}\""),
+                };
+                self.expect(',')?;
+                let ratio = self.number::<f32>()?;
+                self.expect(',')?;
+                let first = self.node()?;
+                self.expect(',')?;
+                let second = self.node()?;
+
+                DockNode::Split {
+                    direction,
+                    ratio: ratio.clamp(0.0, 1.0),
+                    children: Box::new([first, second]),
+                }
+            }
+            "tabs" => {
+                let active = self.number::<usize>()?;
+                let mut panels = vec![];
+
+                while self.peek() == Some(',') {
+                    self.expect(',')?;
+                    panels.push(self.string()?);
+                }
+                ensure!(
+                    !panels.is_empty(),
+                    InvalidLayout,
+                    "Tabs without panels at {}",
+                    self.pos
+                );
+
+                DockNode::Tabs {
+                    active: active.min(panels.len() - 1),
+                    panels,
+                }
+            }
+            other => bail!(InvalidLayout, "Unknown dock node \"{other}\""),
+        };
+
+        self.expect(')')?;
+        return Ok(node);
+    }
+
+    fn skip_whitespace(&mut self) {
+        let rest = &self.input[self.pos..];
+        self.pos += rest.len() - rest.trim_start().len();
+    }
+
+    fn peek(&mut self) -> Option<char> {
+        self.skip_whitespace();
+        return self.input[self.pos..].chars().next();
+    }
+
+    fn peek_word(&mut self) -> &'a str {
+        self.skip_whitespace();
+        let rest: &'a str = &self.input[self.pos..];
+        let len = rest
+            .find(|c: char| !c.is_ascii_alphanumeric() && c != '.' && c != '_' && c != '-')
+            .unwrap_or(rest.len());
+
+        return &rest[..len];
+    }
+
+    fn word(&mut self) -> Result<&'a str> {
+        let start = {
+            self.skip_whitespace();
+            self.pos
+        };
+        let len = self.peek_word().len();
+        ensure!(len > 0, InvalidLayout, "Expected a word at {start}");
+
+        self.pos += len;
+        return Ok(&self.input[start..start + len]);
+    }
+
+    fn number<T: FromStr>(&mut self) -> Result<T> {
+        let pos = self.pos;
+        let word = self.word()?;
+
+        return match word.parse() {
+            Ok(number) => Ok(number),
+            Err(_) => bail!(InvalidLayout, "Invalid number \"{word}\" at {pos}"),
+        };
+    }
+
+    fn string(&mut self) -> Result<String> {
+        self.expect('"')?;
+
+        let mut string = String::new();
+        let mut chars = self.input[self.pos..].char_indices();
+
+        while let Some((i, c)) = chars.next() {
+            match c {
+                '"' => {
+                    self.pos += i + 1;
+                    return Ok(string);
+                }
+                '\\' => match chars.next() {
+                    Some((_, c)) => string.push(c),
+                    None => break,
+                },
+                c => string.push(c),
+            }
+        }
+
+        bail!(InvalidLayout, "Unterminated string at {}", self.pos);
+    }
+
+    fn expect(&mut self, expected: char) -> Result<()> {
+        let found = self.peek();
+        ensure!(
+            found == Some(expected),
+            InvalidLayout,
+            "Expected '{expected}' at {} (found {found:?})",
+            self.pos
+        );
+
+        self.pos += expected.len_utf8();
+        return Ok(());
+    }
+}
+
+//-----------------------------------------------------------------------------
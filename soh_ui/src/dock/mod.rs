@@ -0,0 +1,633 @@
+//-----------------------------------------------------------------------------
+//! Docking panels
+//!
+//! A [`Dock`] arranges named panels according to a [`DockLayout`]: splits become
+//! [`Splitter`]s, panels sharing a space get a [`TabBar`]. Tabs can be dragged onto another panel
+//! (to its edges or as another tab) or onto the edges of the dock. The layout is saved as text
+//! and can be restored later.
+//-----------------------------------------------------------------------------
+mod layout;
+//-----------------------------------------------------------------------------
+pub use layout::*;
+//-----------------------------------------------------------------------------
+use crate::draw::DrawList;
+use crate::input::{Event, PointerButton};
+use crate::layout::{Dimension, Direction, Display, Style};
+use crate::widget::{Container, Response, Splitter, TabBar};
+use crate::Result;
+use crate::{Rect, Ui, WidgetId};
+use soh_math::Vec2;
+use std::collections::HashMap;
+//-----------------------------------------------------------------------------
+
+pub struct Dock {
+    /// Container of the whole dock
+    root: WidgetId,
+    /// Hidden container keeping the contents of the panels which aren't docked
+    storage: WidgetId,
+
+    layout: DockLayout,
+    panels: HashMap<String, Panel>,
+    /// Layout changed since the widgets were built
+    dirty: bool,
+
+    /*
+     * Widgets built for the current layout
+     */
+    frame: Option<WidgetId>,
+    splitters: Vec<(Vec<usize>, WidgetId)>,
+    tab_stacks: Vec<TabStack>,
+
+    drag: Option<TabDrag>,
+}
+
+struct Panel {
+    title: String,
+    content: WidgetId,
+}
+
+struct TabStack {
+    stack: WidgetId,
+    bar: WidgetId,
+    panels: Vec<String>,
+}
+
+struct TabDrag {
+    panel: String,
+    start: Vec2<f32>,
+    moved: bool,
+    target: Option<DropTarget>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct DropTarget {
+    /// None for the edges of the whole dock
+    panel: Option<String>,
+    position: DockPosition,
+    preview: Rect,
+}
+
+/// Distance the pointer has to move before a tab is dragged
+const DRAG_THRESHOLD: f32 = 8.0;
+/// Width of the zones at the edges of the dock which dock to the whole layout
+const EDGE_ZONE: f32 = 24.0;
+
+//-----------------------------------------------------------------------------
+// Constructor
+impl Dock {
+    /// Create an empty dock as a child of `parent`
+    pub fn new(ui: &mut Ui, parent: WidgetId, style: Style) -> Self {
+        let root = ui.add(parent, Container::new(), style);
+        let storage = ui.add(
+            root,
+            Container::new(),
+            Style {
+                display: Display::None,
+                ..Style::fill()
+            },
+        );
+
+        return Dock {
+            root,
+            storage,
+
+            layout: DockLayout::new(),
+            panels: HashMap::new(),
+            dirty: true,
+
+            frame: None,
+            splitters: vec![],
+            tab_stacks: vec![],
+
+            drag: None,
+        };
+    }
+}
+
+//-----------------------------------------------------------------------------
+// Getters
+impl Dock {
+    pub fn root(&self) -> WidgetId {
+        return self.root;
+    }
+    pub fn layout(&self) -> &DockLayout {
+        return &self.layout;
+    }
+    /// Container of the content of a panel
+    pub fn panel(&self, name: &str) -> Option<WidgetId> {
+        return self.panels.get(name).map(|panel| panel.content);
+    }
+}
+
+//-----------------------------------------------------------------------------
+// Specific implementation
+impl Dock {
+    /// Register a panel, returns the container for its content (the panel isn't docked yet)
+    pub fn add_panel(&mut self, ui: &mut Ui, name: &str, title: &str) -> WidgetId {
+        if let Some(content) = self.panel(name) {
+            return content;
+        }
+
+        let content = ui.add(
+            self.storage,
+            Container::new(),
+            Style {
+                direction: Direction::Column,
+                ..Style::fill()
+            },
+        );
+        self.panels.insert(
+            name.to_owned(),
+            Panel {
+                title: title.to_owned(),
+                content,
+            },
+        );
+
+        return content;
+    }
+
+    /// Dock a panel next to another one (or to the edge of the dock if `target` is None)
+    pub fn dock_panel(&mut self, name: &str, target: Option<&str>, position: DockPosition) {
+        self.layout.add(name, target, position);
+        self.dirty = true;
+    }
+
+    /// Undock a panel (its content is kept)
+    pub fn close_panel(&mut self, name: &str) {
+        self.dirty |= self.layout.remove(name);
+    }
+
+    /// Replace the whole layout
+    pub fn set_layout(&mut self, layout: DockLayout) {
+        self.layout = layout;
+        self.dirty = true;
+    }
+
+    /// Serialize the current layout (including the splitter positions)
+    pub fn save(&mut self, ui: &Ui) -> String {
+        self.sync_ratios(ui);
+        return self.layout.to_string();
+    }
+
+    /// Restore a layout saved with [`Dock::save`]
+    pub fn restore(&mut self, layout: &str) -> Result<()> {
+        self.set_layout(layout.parse()?);
+        return Ok(());
+    }
+
+    /// Rebuild the widgets if the layout changed (call before [`Ui::layout`])
+    pub fn update(&mut self, ui: &mut Ui) {
+        match self.dirty {
+            true => self.rebuild(ui),
+            false => self.sync_ratios(ui),
+        }
+    }
+
+    /// Handle dragging of the tabs (call before [`Ui::handle_event`] and skip it if the event
+    /// is consumed)
+    pub fn handle_event(&mut self, ui: &mut Ui, event: &Event) -> Response {
+        match event {
+            Event::PointerDown {
+                pos,
+                button: PointerButton::Primary,
+            } => {
+                let Some(panel) = self.tab_at(ui, *pos) else {
+                    return Response::Ignored;
+                };
+
+                self.activate(ui, &panel);
+                self.drag = Some(TabDrag {
+                    panel,
+                    start: *pos,
+                    moved: false,
+                    target: None,
+                });
+                return Response::Consumed;
+            }
+            Event::PointerMoved { pos } => {
+                let Some(mut drag) = self.drag.take() else {
+                    return Response::Ignored;
+                };
+
+                drag.moved |= (*pos - drag.start).len() > DRAG_THRESHOLD;
+                if drag.moved {
+                    drag.target = self.drop_target(ui, *pos);
+                }
+
+                self.drag = Some(drag);
+                return Response::Consumed;
+            }
+            Event::PointerUp {
+                button: PointerButton::Primary,
+                ..
+            } => {
+                let Some(drag) = self.drag.take() else {
+                    return Response::Ignored;
+                };
+
+                if let Some(target) = drag.target {
+                    // Dropping a tab onto its own stack changes nothing
+                    let own_stack = target.position == DockPosition::Center
+                        && self.same_stack(&drag.panel, target.panel.as_deref());
+
+                    if target.panel.as_deref() != Some(&drag.panel) && !own_stack {
+                        self.sync_ratios(ui);
+                        self.dock_panel(&drag.panel, target.panel.as_deref(), target.position);
+                    }
+                }
+                return Response::Consumed;
+            }
+            _ => return Response::Ignored,
+        }
+    }
+
+    /// Draw the preview of where the dragged tab would be docked (call after [`Ui::draw`])
+    pub fn draw_overlay(&self, ui: &Ui, list: &mut DrawList) {
+        let Some(target) = self.drag.as_ref().and_then(|drag| drag.target.as_ref()) else {
+            return;
+        };
+        let theme = ui.theme(self.root);
+
+        list.rounded_rect(
+            target.preview,
+            theme.corner_radius,
+            theme.accent.with_alpha(0.3),
+        );
+    }
+
+    fn activate(&mut self, ui: &mut Ui, panel: &str) {
+        self.layout.activate(panel);
+
+        for stack in self.tab_stacks.iter() {
+            let Some(active) = stack.panels.iter().position(|p| p == panel) else {
+                continue;
+            };
+
+            if let Some(bar) = ui.widget_mut::<TabBar>(stack.bar) {
+                bar.set_active(active);
+            }
+            for (i, name) in stack.panels.iter().enumerate() {
+                if let Some(content) = self.panel(name) {
+                    ui.set_visible(content, i == active);
+                }
+            }
+        }
+    }
+
+    fn tab_at(&self, ui: &Ui, pos: Vec2<f32>) -> Option<String> {
+        for stack in self.tab_stacks.iter() {
+            let bar = ui.widget::<TabBar>(stack.bar)?;
+            if let Some(index) = bar.tab_at(ui.rect(stack.bar), pos) {
+                return stack.panels.get(index).cloned();
+            }
+        }
+        return None;
+    }
+
+    fn same_stack(&self, panel: &str, other: Option<&str>) -> bool {
+        return self.tab_stacks.iter().any(|stack| {
+            stack.panels.iter().any(|p| p == panel)
+                && stack.panels.iter().any(|p| Some(p.as_str()) == other)
+        });
+    }
+
+    /// Where a tab dropped at the position would be docked
+    fn drop_target(&self, ui: &Ui, pos: Vec2<f32>) -> Option<DropTarget> {
+        let root = ui.rect(self.root);
+        if !root.contains(pos) {
+            return None;
+        }
+
+        /*
+         * Edges of the whole dock
+         */
+        let edges = [
+            (pos.x - root.min.x, DockPosition::Left),
+            (root.max.x - pos.x, DockPosition::Right),
+            (pos.y - root.min.y, DockPosition::Top),
+            (root.max.y - pos.y, DockPosition::Bottom),
+        ];
+        for (distance, position) in edges {
+            if distance < EDGE_ZONE {
+                return Some(DropTarget {
+                    panel: None,
+                    position,
+                    preview: zone(root, position, 0.25),
+                });
+            }
+        }
+
+        /*
+         * Edges or center of a panel
+         */
+        for stack in self.tab_stacks.iter() {
+            let rect = ui.rect(stack.stack);
+            if !rect.contains(pos) {
+                continue;
+            }
+
+            let relative = Vec2::new(
+                (pos.x - rect.min.x) / rect.width(),
+                (pos.y - rect.min.y) / rect.height(),
+            );
+            let position = match relative {
+                r if r.x < 0.25 => DockPosition::Left,
+                r if r.x > 0.75 => DockPosition::Right,
+                r if r.y < 0.25 => DockPosition::Top,
+                r if r.y > 0.75 => DockPosition::Bottom,
+                _ => DockPosition::Center,
+            };
+
+            return Some(DropTarget {
+                panel: stack.panels.first().cloned(),
+                position,
+                preview: zone(rect, position, 0.5),
+            });
+        }
+
+        return None;
+    }
+
+    /// Copy the splitter positions chosen by the user into the layout
+    fn sync_ratios(&mut self, ui: &Ui) {
+        for (path, splitter) in self.splitters.iter() {
+            if let Some(splitter) = ui.widget::<Splitter>(*splitter) {
+                self.layout.set_ratio(path, splitter.ratio());
+            }
+        }
+    }
+
+    fn rebuild(&mut self, ui: &mut Ui) {
+        /*
+         * Keep the panel contents while the old widgets are removed
+         */
+        for panel in self.panels.values() {
+            ui.reparent(panel.content, self.storage);
+        }
+        if let Some(frame) = self.frame.take() {
+            ui.remove(frame);
+        }
+        self.splitters.clear();
+        self.tab_stacks.clear();
+
+        if let Some(root) = self.layout.root().cloned() {
+            let mut path = vec![];
+            self.frame = Some(self.build(ui, self.root, &root, &mut path));
+        }
+
+        self.dirty = false;
+    }
+
+    fn build(
+        &mut self,
+        ui: &mut Ui,
+        parent: WidgetId,
+        node: &DockNode,
+        path: &mut Vec<usize>,
+    ) -> WidgetId {
+        match node {
+            DockNode::Split {
+                direction,
+                ratio,
+                children,
+            } => {
+                let splitter = ui.add(parent, Splitter::new(*direction, *ratio), Style::fill());
+                self.splitters.push((path.clone(), splitter));
+
+                for (i, child) in children.iter().enumerate() {
+                    path.push(i);
+                    self.build(ui, splitter, child, path);
+                    path.pop();
+                }
+
+                return splitter;
+            }
+            DockNode::Tabs { panels, active } => {
+                let stack = ui.add(
+                    parent,
+                    Container::panel(),
+                    Style {
+                        direction: Direction::Column,
+                        ..Style::fill()
+                    },
+                );
+
+                let titles = panels
+                    .iter()
+                    .map(|name| match self.panels.get(name) {
+                        Some(panel) => panel.title.clone(),
+                        None => name.clone(),
+                    })
+                    .collect();
+                let bar = ui.add(
+                    stack,
+                    TabBar::new(titles, *active),
+                    Style {
+                        width: Dimension::Percent(1.0),
+                        shrink: 0.0,
+                        ..Default::default()
+                    },
+                );
+
+                for (i, name) in panels.iter().enumerate() {
+                    if let Some(content) = self.panel(name) {
+                        ui.reparent(content, stack);
+                        ui.set_visible(content, i == *active);
+                    }
+                }
+
+                self.tab_stacks.push(TabStack {
+                    stack,
+                    bar,
+                    panels: panels.clone(),
+                });
+                return stack;
+            }
+        }
+    }
+}
+
+/// Part of the rect covered by a panel docked at the position
+fn zone(rect: Rect, position: DockPosition, fraction: f32) -> Rect {
+    let size = rect.size();
+
+    return match position {
+        DockPosition::Left => Rect::new(
+            rect.min,
+            Vec2::new(rect.min.x + size.x * fraction, rect.max.y),
+        ),
+        DockPosition::Right => Rect::new(
+            Vec2::new(rect.max.x - size.x * fraction, rect.min.y),
+            rect.max,
+        ),
+        DockPosition::Top => Rect::new(
+            rect.min,
+            Vec2::new(rect.max.x, rect.min.y + size.y * fraction),
+        ),
+        DockPosition::Bottom => Rect::new(
+            Vec2::new(rect.min.x, rect.max.y - size.y * fraction),
+            rect.max,
+        ),
+        DockPosition::Center => rect,
+    };
+}
+
+//-----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_layout_operations() {
+        let mut layout = DockLayout::new();
+
+        layout.add("viewport", None, DockPosition::Center);
+        layout.add("scene", Some("viewport"), DockPosition::Left);
+        layout.add("assets", Some("scene"), DockPosition::Center);
+        layout.add("log", None, DockPosition::Bottom);
+
+        assert_eq!(
+            layout.to_string(),
+            "split(column, 0.75, \
+             split(row, 0.5, tabs(1, \"scene\", \"assets\"), tabs(0, \"viewport\")), \
+             tabs(0, \"log\"))"
+        );
+
+        // Moving a panel removes it from its old place
+        layout.add("assets", Some("log"), DockPosition::Right);
+        layout.activate("scene");
+        layout.remove("viewport");
+        assert_eq!(
+            layout.to_string(),
+            "split(column, 0.75, \
+             tabs(0, \"scene\"), \
+             split(row, 0.5, tabs(0, \"log\"), tabs(0, \"assets\")))"
+        );
+        assert_eq!(layout.panels(), ["scene", "log", "assets"]);
+
+        layout.remove("scene");
+        layout.remove("log");
+        layout.remove("assets");
+        assert!(layout.is_empty());
+        assert_eq!(layout.to_string(), "empty");
+    }
+
+    #[test]
+    fn test_layout_parse() {
+        let text = r#"split(row, 0.3, tabs(1, "a \"quoted\" name", "b\\c"), tabs(0, "d"))"#;
+        let layout = text.parse::<DockLayout>().unwrap();
+
+        assert_eq!(layout.to_string(), text);
+        assert_eq!(layout.panels(), ["a \"quoted\" name", "b\\c", "d"]);
+        assert_eq!("empty".parse::<DockLayout>().unwrap(), DockLayout::new());
+
+        assert!("split(row, 0.5, tabs(0, \"a\"))"
+            .parse::<DockLayout>()
+            .is_err());
+        assert!("tabs(0)".parse::<DockLayout>().is_err());
+        assert!("tabs(0, \"a\") tabs".parse::<DockLayout>().is_err());
+        assert!("window(0, \"a\")".parse::<DockLayout>().is_err());
+    }
+
+    #[test]
+    fn test_dock_widgets() {
+        let viewport_size = Vec2::new(800.0, 600.0);
+        let mut ui = Ui::new(Style::default());
+        let root = ui.root();
+        let mut dock = Dock::new(&mut ui, root, Style::fill());
+
+        let scene = dock.add_panel(&mut ui, "scene", "Scene");
+        let viewport = dock.add_panel(&mut ui, "viewport", "Viewport");
+        let assets = dock.add_panel(&mut ui, "assets", "Assets");
+
+        dock.restore(r#"split(row, 0.25, tabs(0, "scene", "assets"), tabs(0, "viewport"))"#)
+            .unwrap();
+        dock.update(&mut ui);
+        ui.layout(viewport_size);
+
+        // 25% of 800px for the left side, 6px splitter handle
+        assert_eq!(ui.rect(scene).min.x, 0.0);
+        assert!(ui.rect(scene).width() > 195.0 && ui.rect(scene).width() <= 200.0);
+        assert_eq!(ui.rect(viewport).min.x, 206.0);
+        assert!(ui.is_visible(scene) && !ui.is_visible(assets));
+
+        // Clicking a tab activates it
+        let button = PointerButton::Primary;
+        let tab_pos = Vec2::new(crate::widget::TAB_WIDTH + 10.0, 10.0);
+        let event = Event::PointerDown {
+            pos: tab_pos,
+            button,
+        };
+        assert!(dock.handle_event(&mut ui, &event).is_consumed());
+        let event = Event::PointerUp {
+            pos: tab_pos,
+            button,
+        };
+        assert!(dock.handle_event(&mut ui, &event).is_consumed());
+        assert!(!ui.is_visible(scene) && ui.is_visible(assets));
+
+        // Dragging the splitter handle
+        let handle = Vec2::new(203.0, 300.0);
+        ui.handle_event(&Event::PointerDown {
+            pos: handle,
+            button,
+        });
+        ui.handle_event(&Event::PointerMoved {
+            pos: Vec2::new(403.0, 300.0),
+        });
+        ui.handle_event(&Event::PointerUp {
+            pos: handle,
+            button,
+        });
+        dock.update(&mut ui);
+        assert_eq!(
+            dock.save(&ui),
+            r#"split(row, 0.5, tabs(1, "scene", "assets"), tabs(0, "viewport"))"#
+        );
+        ui.layout(viewport_size);
+
+        // Drag the "assets" tab to the bottom edge of the viewport
+        let events = [
+            Event::PointerDown {
+                pos: tab_pos,
+                button,
+            },
+            Event::PointerMoved {
+                pos: Vec2::new(600.0, 300.0),
+            },
+            Event::PointerMoved {
+                pos: Vec2::new(600.0, 550.0),
+            },
+        ];
+        for event in events.iter() {
+            assert!(dock.handle_event(&mut ui, event).is_consumed());
+        }
+
+        let mut list = DrawList::new();
+        dock.draw_overlay(&ui, &mut list);
+        assert_eq!(list.commands().len(), 1);
+
+        let event = Event::PointerUp {
+            pos: Vec2::new(600.0, 550.0),
+            button,
+        };
+        dock.handle_event(&mut ui, &event);
+        dock.update(&mut ui);
+        ui.layout(viewport_size);
+
+        assert_eq!(
+            dock.save(&ui),
+            r#"split(row, 0.5, tabs(0, "scene"), split(column, 0.5, tabs(0, "viewport"), tabs(0, "assets")))"#
+        );
+        assert!(ui.is_visible(assets));
+        assert!(ui.rect(assets).min.y > 300.0);
+        assert_eq!(
+            ui.parent(ui.parent(assets).unwrap()),
+            ui.parent(ui.parent(viewport).unwrap())
+        );
+    }
+}
+
+//-----------------------------------------------------------------------------
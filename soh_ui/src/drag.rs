@@ -0,0 +1,89 @@
+//-----------------------------------------------------------------------------
+//! Drag and drop between widgets
+//!
+//! A widget starts a drag from its event handler with [`EventCtx::start_drag`] ( usually once
+//! the pointer moved far enough after a press ). While the drag is active the [`crate::Ui`]
+//! draws its preview at the pointer and finds the drop target: the deepest widget under the
+//! pointer, or its closest parent, which accepts the payload ( [`Widget::accepts_drop`] ). The
+//! target is highlighted and receives the payload in [`Widget::on_drop`] when the pointer is
+//! released. Escape cancels the drag.
+//!
+//! [`EventCtx::start_drag`]: crate::widget::EventCtx::start_drag
+//! [`Widget::accepts_drop`]: crate::widget::Widget::accepts_drop
+//! [`Widget::on_drop`]: crate::widget::Widget::on_drop
+//-----------------------------------------------------------------------------
+use crate::draw::DrawList;
+use crate::layout::Edges;
+use crate::text::TextMeasure;
+use crate::theme::Theme;
+use crate::{Rect, RectExt, WidgetId};
+use soh_math::Vec2;
+use std::any::Any;
+//-----------------------------------------------------------------------------
+
+/// Distance the pointer should move after a press before a drag starts (so clicks don't drag)
+pub const DRAG_THRESHOLD: f32 = 4.0;
+
+/// Dragged payload with the widget which started the drag
+pub struct DragData {
+    payload: Box<dyn Any>,
+    source: WidgetId,
+}
+
+/// Drag which is in progress
+pub(crate) struct ActiveDrag {
+    pub data: DragData,
+    /// Draw commands relative to the pointer
+    pub preview: DrawList,
+    pub pos: Vec2<f32>,
+    pub target: Option<WidgetId>,
+}
+
+//-----------------------------------------------------------------------------
+
+impl DragData {
+    pub(crate) fn new(payload: Box<dyn Any>, source: WidgetId) -> Self {
+        return DragData { payload, source };
+    }
+
+    pub fn source(&self) -> WidgetId {
+        return self.source;
+    }
+
+    /// Whether the payload is a `T`
+    pub fn is<T: Any>(&self) -> bool {
+        return self.payload.is::<T>();
+    }
+
+    pub fn payload<T: Any>(&self) -> Option<&T> {
+        return self.payload.downcast_ref();
+    }
+
+    /// Take the payload out of the drag
+    pub fn take<T: Any>(self) -> Result<T, DragData> {
+        let source = self.source;
+
+        return match self.payload.downcast() {
+            Ok(payload) => Ok(*payload),
+            Err(payload) => Err(DragData { payload, source }),
+        };
+    }
+}
+
+//-----------------------------------------------------------------------------
+
+/// Preview with a label in a rounded box, placed to the bottom right of the pointer
+pub fn label_preview(label: &str, text: &dyn TextMeasure, theme: &Theme) -> DrawList {
+    let mut list = DrawList::new();
+    let size = text.text_size(label, theme.font_size);
+    let offset = Vec2::new(12.0, 12.0);
+
+    let rect = Rect::from_pos_size(offset, size).expand(Edges::all(theme.padding));
+    list.rounded_rect(rect, theme.corner_radius, theme.surface.with_alpha(0.9));
+    list.outline(rect, theme.border_width, theme.border);
+    list.text(offset, label, theme.font_size, theme.text);
+
+    return list;
+}
+
+//-----------------------------------------------------------------------------
@@ -0,0 +1,323 @@
+//-----------------------------------------------------------------------------
+//! Backend independent draw commands produced by the widgets
+//-----------------------------------------------------------------------------
+use crate::Rect;
+use ash::vk;
+use soh_math::color::Rgb;
+use soh_math::Vec2;
+//-----------------------------------------------------------------------------
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Color {
+    pub rgb: Rgb,
+    /// Opacity (0.0 - transparent, 1.0 - opaque)
+    pub alpha: f32,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum DrawCmd {
+    /// Filled rectangle (with rounded corners if `radius` isn't zero)
+    Rect {
+        rect: Rect,
+        radius: f32,
+        color: Color,
+    },
+    /// Outline drawn inside the rect (with rounded corners if `radius` isn't zero)
+    Border {
+        rect: Rect,
+        radius: f32,
+        width: f32,
+        color: Color,
+    },
+    /// Soft shadow of a rounded rect which fades out over `blur` pixels on both sides of its edge
+    Shadow {
+        rect: Rect,
+        radius: f32,
+        blur: f32,
+        color: Color,
+    },
+    /// Straight line of the given width
+    Line {
+        from: Vec2<f32>,
+        to: Vec2<f32>,
+        width: f32,
+        color: Color,
+    },
+    /// Single line of text ( `pos` is the top left corner )
+    Text {
+        pos: Vec2<f32>,
+        text: String,
+        size: f32,
+        color: Color,
+    },
+    /// Textured rectangle, `uv` is the part of the texture in normalized coordinates and `tint`
+    /// multiplies its colors
+    Image {
+        rect: Rect,
+        texture: TextureHandle,
+        uv: Rect,
+        tint: Color,
+    },
+    /// Limit the following commands to the intersection of `rect` and the current clip rect
+    PushClip(Rect),
+    /// Restore the clip rect from before the matching `PushClip`
+    PopClip,
+}
+
+/// Texture shown by the image commands ( the image view and the sampler of a `soh_vk::Texture` )
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TextureHandle {
+    pub image_view: vk::ImageView,
+    pub sampler: vk::Sampler,
+}
+
+/// List of draw commands in back to front order
+#[derive(Debug, Default, Clone)]
+pub struct DrawList {
+    cmds: Vec<DrawCmd>,
+}
+
+//-----------------------------------------------------------------------------
+
+impl Color {
+    pub const fn new(rgb: Rgb, alpha: f32) -> Self {
+        return Color { rgb, alpha };
+    }
+
+    pub const fn opaque(rgb: Rgb) -> Self {
+        return Color { rgb, alpha: 1.0 };
+    }
+
+    pub fn with_alpha(self, alpha: f32) -> Self {
+        return Color { alpha, ..self };
+    }
+}
+
+impl From<Rgb> for Color {
+    fn from(value: Rgb) -> Self {
+        return Color::opaque(value);
+    }
+}
+
+//-----------------------------------------------------------------------------
+
+impl TextureHandle {
+    pub const fn new(image_view: vk::ImageView, sampler: vk::Sampler) -> Self {
+        return TextureHandle {
+            image_view,
+            sampler,
+        };
+    }
+}
+
+//-----------------------------------------------------------------------------
+
+impl DrawList {
+    pub fn new() -> Self {
+        return DrawList { cmds: vec![] };
+    }
+
+    pub fn commands(&self) -> &[DrawCmd] {
+        return &self.cmds;
+    }
+
+    pub fn clear(&mut self) {
+        self.cmds.clear();
+    }
+
+    pub fn push(&mut self, cmd: DrawCmd) {
+        self.cmds.push(cmd);
+    }
+
+    /// Move the commands starting at the index `first` (including their clip rects)
+    pub fn translate(&mut self, first: usize, offset: Vec2<f32>) {
+        if offset == Vec2::new(0.0, 0.0) {
+            return;
+        }
+
+        for cmd in self.cmds[first..].iter_mut() {
+            match cmd {
+                DrawCmd::Rect { rect, .. }
+                | DrawCmd::Border { rect, .. }
+                | DrawCmd::Shadow { rect, .. }
+                | DrawCmd::Image { rect, .. }
+                | DrawCmd::PushClip(rect) => *rect = rect.translate(offset),
+                DrawCmd::Line { from, to, .. } => {
+                    *from += offset;
+                    *to += offset;
+                }
+                DrawCmd::Text { pos, .. } => *pos += offset,
+                DrawCmd::PopClip => {}
+            }
+        }
+    }
+
+    /// Convert the commands starting at the index `first` from logical to physical pixels
+    ///
+    /// Rect edges and text origins are snapped to whole pixels and borders are at least a pixel
+    /// wide, so the UI stays crisp at fractional scales. Font sizes become physical, the renderer
+    /// rasterizes the glyphs at the resolution of the monitor.
+    pub fn scale(&mut self, first: usize, factor: f32) {
+        if factor == 1.0 {
+            return;
+        }
+
+        let snap = |v: Vec2<f32>| Vec2::new((v.x * factor).round(), (v.y * factor).round());
+        let snap_rect = |rect: &Rect| Rect::new(snap(rect.min), snap(rect.max));
+
+        for cmd in self.cmds[first..].iter_mut() {
+            match cmd {
+                DrawCmd::Rect { rect, radius, .. } => {
+                    *rect = snap_rect(rect);
+                    *radius *= factor;
+                }
+                DrawCmd::Border {
+                    rect,
+                    radius,
+                    width,
+                    ..
+                } => {
+                    *rect = snap_rect(rect);
+                    *radius *= factor;
+                    *width = (*width * factor).round().max(1.0);
+                }
+                DrawCmd::Shadow {
+                    rect, radius, blur, ..
+                } => {
+                    *rect = snap_rect(rect);
+                    *radius *= factor;
+                    *blur *= factor;
+                }
+                DrawCmd::Line {
+                    from, to, width, ..
+                } => {
+                    *from *= factor;
+                    *to *= factor;
+                    *width *= factor;
+                }
+                DrawCmd::Text { pos, size, .. } => {
+                    *pos = snap(*pos);
+                    *size *= factor;
+                }
+                DrawCmd::Image { rect, .. } | DrawCmd::PushClip(rect) => *rect = snap_rect(rect),
+                DrawCmd::PopClip => {}
+            }
+        }
+    }
+
+    /// Multiply the opacity of the commands starting at the index `first`
+    pub fn fade(&mut self, first: usize, opacity: f32) {
+        if opacity >= 1.0 {
+            return;
+        }
+
+        for cmd in self.cmds[first..].iter_mut() {
+            match cmd {
+                DrawCmd::Rect { color, .. }
+                | DrawCmd::Border { color, .. }
+                | DrawCmd::Shadow { color, .. }
+                | DrawCmd::Line { color, .. }
+                | DrawCmd::Text { color, .. } => color.alpha *= opacity,
+                DrawCmd::Image { tint, .. } => tint.alpha *= opacity,
+                DrawCmd::PushClip(_) | DrawCmd::PopClip => {}
+            }
+        }
+    }
+
+    /// Move all commands of `other` to the end of this list
+    pub fn append(&mut self, other: &mut DrawList) {
+        self.cmds.append(&mut other.cmds);
+    }
+
+    pub fn rect(&mut self, rect: Rect, color: impl Into<Color>) {
+        self.rounded_rect(rect, 0.0, color);
+    }
+
+    pub fn rounded_rect(&mut self, rect: Rect, radius: f32, color: impl Into<Color>) {
+        self.cmds.push(DrawCmd::Rect {
+            rect,
+            radius,
+            color: color.into(),
+        });
+    }
+
+    /// Border with rounded corners drawn inside the rect
+    pub fn border(&mut self, rect: Rect, radius: f32, width: f32, color: impl Into<Color>) {
+        self.cmds.push(DrawCmd::Border {
+            rect,
+            radius,
+            width,
+            color: color.into(),
+        });
+    }
+
+    pub fn shadow(&mut self, rect: Rect, radius: f32, blur: f32, color: impl Into<Color>) {
+        self.cmds.push(DrawCmd::Shadow {
+            rect,
+            radius,
+            blur,
+            color: color.into(),
+        });
+    }
+
+    pub fn line(&mut self, from: Vec2<f32>, to: Vec2<f32>, width: f32, color: impl Into<Color>) {
+        self.cmds.push(DrawCmd::Line {
+            from,
+            to,
+            width,
+            color: color.into(),
+        });
+    }
+
+    /// Outline drawn inside the rect
+    pub fn outline(&mut self, rect: Rect, width: f32, color: impl Into<Color>) {
+        let color = color.into();
+        let (min, max) = (rect.min, rect.max);
+        let width = width.min(rect.width() * 0.5).min(rect.height() * 0.5);
+
+        self.rect(Rect::new(min, Vec2::new(max.x, min.y + width)), color);
+        self.rect(Rect::new(Vec2::new(min.x, max.y - width), max), color);
+        self.rect(
+            Rect::new(
+                Vec2::new(min.x, min.y + width),
+                Vec2::new(min.x + width, max.y - width),
+            ),
+            color,
+        );
+        self.rect(
+            Rect::new(
+                Vec2::new(max.x - width, min.y + width),
+                Vec2::new(max.x, max.y - width),
+            ),
+            color,
+        );
+    }
+
+    pub fn text(&mut self, pos: Vec2<f32>, text: &str, size: f32, color: impl Into<Color>) {
+        self.cmds.push(DrawCmd::Text {
+            pos,
+            text: text.to_owned(),
+            size,
+            color: color.into(),
+        });
+    }
+
+    pub fn image(&mut self, rect: Rect, texture: TextureHandle, uv: Rect, tint: impl Into<Color>) {
+        self.cmds.push(DrawCmd::Image {
+            rect,
+            texture,
+            uv,
+            tint: tint.into(),
+        });
+    }
+
+    pub fn push_clip(&mut self, rect: Rect) {
+        self.cmds.push(DrawCmd::PushClip(rect));
+    }
+
+    pub fn pop_clip(&mut self) {
+        self.cmds.push(DrawCmd::PopClip);
+    }
+}
+
+//-----------------------------------------------------------------------------
@@ -0,0 +1,64 @@
+//-----------------------------------------------------------------------------
+//! Error type of the library
+//-----------------------------------------------------------------------------
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+
+    /// A data file isn't valid RON
+    #[cfg(feature = "serde")]
+    #[error("RON error: {0}")]
+    RonParse(#[from] ron::error::SpannedError),
+
+    /// A description couldn't be written as RON
+    #[cfg(feature = "serde")]
+    #[error("RON error: {0}")]
+    Ron(#[from] ron::Error),
+
+    #[cfg(feature = "serde")]
+    #[error("JSON error: {0}")]
+    Json(#[from] serde_json::Error),
+
+    /// A data file couldn't be read or parsed
+    #[error("Can't load {}: {source}", path.display())]
+    File {
+        path: std::path::PathBuf,
+        source: Box<Error>,
+    },
+
+    /// A widget description is wrong (unknown widget type, property of the wrong type)
+    #[error("{0}")]
+    InvalidData(String),
+
+    /// A saved dock layout can't be parsed
+    #[error("{0}")]
+    InvalidLayout(String),
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+//-----------------------------------------------------------------------------
+// Like the anyhow macros, with the kind of the error first
+
+/// Return an error of the kind with a formatted message
+macro_rules! bail {
+    ($kind:ident, $($arg:tt)*) => {
+        return Err($crate::Error::$kind(format!($($arg)*)))
+    };
+}
+
+/// Return an error of the kind with a formatted message if the condition is false
+macro_rules! ensure {
+    ($cond:expr, $kind:ident, $($arg:tt)*) => {
+        if !$cond {
+            $crate::error::bail!($kind, $($arg)*);
+        }
+    };
+}
+
+pub(crate) use bail;
+pub(crate) use ensure;
+
+//-----------------------------------------------------------------------------
@@ -0,0 +1,317 @@
+//-----------------------------------------------------------------------------
+//! Immediate mode debug overlay
+//!
+//! A [`DebugUi`] is a panel of simple controls which are declared again every frame, so values can
+//! be tuned in game without building a widget tree. The panel produces a [`DrawList`] like the
+//! retained widgets, which the renderer draws on top of the frame.
+//!
+//! ```
+//! use soh_math::Vec2;
+//! use soh_ui::immediate::DebugUi;
+//!
+//! let mut ui = DebugUi::new();
+//! let (mut speed, mut wireframe) = (1.0, false);
+//!
+//! // Every frame (after passing the input events to `ui.handle_event`)
+//! ui.begin(Vec2::new(10.0, 10.0));
+//! ui.label("Tuning");
+//! ui.slider("speed", &mut speed, 0.0..=10.0);
+//! ui.checkbox("wireframe", &mut wireframe);
+//! ui.plot_line("frame time", &[16.6, 17.1, 16.4]);
+//! let list = ui.end();
+//! ```
+//-----------------------------------------------------------------------------
+mod widgets;
+//-----------------------------------------------------------------------------
+use crate::draw::DrawList;
+use crate::input::{Event, PointerButton};
+use crate::layout::Edges;
+use crate::text::{MonospaceMetrics, TextMeasure};
+use crate::theme::Theme;
+use crate::widget::Response;
+use crate::{Rect, RectExt};
+use soh_math::Vec2;
+//-----------------------------------------------------------------------------
+
+pub struct DebugUi {
+    text: Box<dyn TextMeasure>,
+    theme: Theme,
+    width: f32,
+    /// Physical pixels per logical pixel (the panel is laid out in logical pixels)
+    scale_factor: f32,
+
+    /*
+     * Input (collected between the frames)
+     */
+    pointer: Option<Vec2<f32>>,
+    down: bool,
+    /// The pointer button went down since the last frame
+    pressed: bool,
+    /// Control which is being dragged
+    active: Option<usize>,
+
+    /*
+     * Current frame
+     */
+    origin: Vec2<f32>,
+    cursor: f32,
+    next_id: usize,
+    /// Rect of the panel in the last frame (used to route the events)
+    rect: Rect,
+    content: DrawList,
+    list: DrawList,
+}
+
+//-----------------------------------------------------------------------------
+// Constructor
+impl DebugUi {
+    pub fn new() -> Self {
+        return DebugUi {
+            text: Box::new(MonospaceMetrics::default()),
+            theme: Theme::default(),
+            width: 280.0,
+            scale_factor: 1.0,
+
+            pointer: None,
+            down: false,
+            pressed: false,
+            active: None,
+
+            origin: Vec2::new(0.0, 0.0),
+            cursor: 0.0,
+            next_id: 0,
+            rect: Rect::default(),
+            content: DrawList::new(),
+            list: DrawList::new(),
+        };
+    }
+
+    pub fn with_theme(mut self, theme: Theme) -> Self {
+        self.theme = theme;
+        return self;
+    }
+
+    /// Use the font metrics of the renderer
+    pub fn with_text_measure(mut self, text: impl TextMeasure + 'static) -> Self {
+        self.text = Box::new(text);
+        return self;
+    }
+
+    /// Width of the panel
+    pub fn with_width(mut self, width: f32) -> Self {
+        self.width = width;
+        return self;
+    }
+
+    pub fn with_scale_factor(mut self, scale_factor: f32) -> Self {
+        self.scale_factor = scale_factor;
+        return self;
+    }
+}
+
+impl Default for DebugUi {
+    fn default() -> Self {
+        return Self::new();
+    }
+}
+
+//-----------------------------------------------------------------------------
+// Getters
+impl DebugUi {
+    pub fn theme(&self) -> &Theme {
+        return &self.theme;
+    }
+    /// Rect of the panel in the last finished frame
+    pub fn rect(&self) -> Rect {
+        return self.rect;
+    }
+    /// Whether a control is being dragged
+    pub fn is_active(&self) -> bool {
+        return self.active.is_some();
+    }
+}
+
+//-----------------------------------------------------------------------------
+// Specific implementation
+impl DebugUi {
+    /// Track the pointer, the events over the panel are consumed (don't pass them to the game)
+    pub fn handle_event(&mut self, event: &Event) -> Response {
+        let over = |pos: &Vec2<f32>| self.rect.contains(*pos);
+
+        let consumed = match event {
+            Event::PointerMoved { pos } => {
+                self.pointer = Some(*pos);
+                self.active.is_some() || over(pos)
+            }
+            Event::PointerDown {
+                pos,
+                button: PointerButton::Primary,
+            } => {
+                self.pointer = Some(*pos);
+                let consumed = over(pos);
+                if consumed {
+                    self.down = true;
+                    self.pressed = true;
+                }
+                consumed
+            }
+            Event::PointerUp {
+                button: PointerButton::Primary,
+                ..
+            } => std::mem::replace(&mut self.down, false),
+            Event::PointerDown { pos, .. }
+            | Event::PointerUp { pos, .. }
+            | Event::Scroll { pos, .. } => over(pos),
+            Event::ScaleFactorChanged { scale_factor } => {
+                self.scale_factor = *scale_factor;
+                false
+            }
+            _ => false,
+        };
+
+        return match consumed {
+            true => Response::Consumed,
+            false => Response::Ignored,
+        };
+    }
+
+    /// Start a frame, the panel is placed with its top left corner at `pos`
+    pub fn begin(&mut self, pos: Vec2<f32>) {
+        self.origin = pos;
+        self.cursor = pos.y + self.theme.padding;
+        self.next_id = 0;
+        self.content.clear();
+    }
+
+    /// Finish the frame and return its draw commands (in physical pixels)
+    pub fn end(&mut self) -> &DrawList {
+        if !self.down {
+            self.active = None;
+        }
+        self.pressed = false;
+
+        self.rect = Rect::new(
+            self.origin,
+            Vec2::new(self.origin.x + self.width, self.cursor),
+        );
+
+        /*
+         * The size of the background is only known now
+         */
+        let theme = &self.theme;
+        self.list.clear();
+        self.list
+            .rounded_rect(self.rect, theme.corner_radius, theme.border);
+        self.list.rounded_rect(
+            self.rect.shrink(Edges::all(theme.border_width)),
+            theme.corner_radius,
+            theme.background.with_alpha(0.9),
+        );
+        self.list.append(&mut self.content);
+        self.list.scale(0, self.scale_factor);
+
+        return &self.list;
+    }
+
+    /// Allocate the next row of the panel
+    fn row(&mut self, height: f32) -> (usize, Rect) {
+        let id = self.next_id;
+        self.next_id += 1;
+
+        let padding = self.theme.padding;
+        let min = Vec2::new(self.origin.x + padding, self.cursor);
+        let size = Vec2::new(self.width - 2.0 * padding, height);
+        self.cursor += height + padding;
+
+        return (id, Rect::from_pos_size(min, size));
+    }
+
+    fn line_height(&self) -> f32 {
+        return self.text.line_height(self.theme.font_size);
+    }
+
+    /// The pointer went down inside the rect in this frame
+    fn clicked(&self, rect: Rect) -> bool {
+        return self.pressed && self.pointer.is_some_and(|pos| rect.contains(pos));
+    }
+}
+
+//-----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::draw::DrawCmd;
+
+    fn click(ui: &mut DebugUi, pos: Vec2<f32>) {
+        let button = PointerButton::Primary;
+        ui.handle_event(&Event::PointerDown { pos, button });
+        ui.handle_event(&Event::PointerUp { pos, button });
+    }
+
+    fn texts(list: &DrawList) -> Vec<String> {
+        return list
+            .commands()
+            .iter()
+            .filter_map(|cmd| match cmd {
+                DrawCmd::Text { text, .. } => Some(text.clone()),
+                _ => None,
+            })
+            .collect();
+    }
+
+    #[test]
+    fn test_debug_ui() {
+        let mut ui = DebugUi::new().with_width(200.0);
+        let mut enabled = false;
+        let mut value = 5.0;
+
+        let frame = |ui: &mut DebugUi, enabled: &mut bool, value: &mut f32| {
+            ui.begin(Vec2::new(0.0, 0.0));
+            ui.label("Debug");
+            let toggled = ui.checkbox("enabled", enabled);
+            let changed = ui.slider("value", value, 0.0..=10.0);
+            ui.plot_line("plot", &[1.0, 3.0, 2.0]);
+            ui.end();
+            return (toggled, changed);
+        };
+
+        // Default metrics: 20px lines, 4px padding
+        frame(&mut ui, &mut enabled, &mut value);
+        assert_eq!(ui.rect().max.y, 4.0 + (20.0 + 4.0) * 3.0 + 60.0 + 4.0);
+        assert_eq!(
+            texts(&ui.list),
+            ["Debug", "enabled", "value", "5.000", "plot", "2.000"]
+        );
+
+        // Events outside of the panel are left to the game
+        let outside = Event::PointerMoved {
+            pos: Vec2::new(300.0, 10.0),
+        };
+        assert!(!ui.handle_event(&outside).is_consumed());
+
+        click(&mut ui, Vec2::new(10.0, 4.0 + 24.0 + 10.0));
+        assert_eq!(frame(&mut ui, &mut enabled, &mut value), (true, false));
+        assert!(enabled);
+
+        // Drag the slider (the track is the right half of the row) past its end
+        let button = PointerButton::Primary;
+        let pos = Vec2::new(150.0, 4.0 + 48.0 + 10.0);
+        assert!(ui
+            .handle_event(&Event::PointerDown { pos, button })
+            .is_consumed());
+        frame(&mut ui, &mut enabled, &mut value);
+        assert!(ui.is_active());
+
+        let pos = Vec2::new(500.0, 0.0);
+        assert!(ui.handle_event(&Event::PointerMoved { pos }).is_consumed());
+        assert_eq!(frame(&mut ui, &mut enabled, &mut value), (false, true));
+        assert_eq!(value, 10.0);
+
+        ui.handle_event(&Event::PointerUp { pos, button });
+        frame(&mut ui, &mut enabled, &mut value);
+        assert!(!ui.is_active());
+    }
+}
+
+//-----------------------------------------------------------------------------
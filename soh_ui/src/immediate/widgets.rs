@@ -0,0 +1,153 @@
+//-----------------------------------------------------------------------------
+use super::DebugUi;
+use crate::layout::Edges;
+use crate::{Rect, RectExt};
+use soh_math::Vec2;
+use std::ops::RangeInclusive;
+//-----------------------------------------------------------------------------
+
+impl DebugUi {
+    pub fn label(&mut self, text: &str) {
+        let (_, rect) = self.row(self.line_height());
+        let theme = &self.theme;
+
+        self.content
+            .text(rect.min, text, theme.font_size, theme.text);
+    }
+
+    /// Toggle `value` on click, returns true if it changed
+    pub fn checkbox(&mut self, label: &str, value: &mut bool) -> bool {
+        let line_height = self.line_height();
+        let (_, rect) = self.row(line_height);
+
+        let changed = self.clicked(rect);
+        if changed {
+            *value = !*value;
+        }
+
+        /*
+         * Box followed by the label
+         */
+        let theme = &self.theme;
+        let size = line_height * 0.8;
+        let min = Vec2::new(rect.min.x, rect.center().y - size * 0.5);
+        let check = Rect::from_pos_size(min, Vec2::new(size, size));
+
+        self.content
+            .rounded_rect(check, theme.corner_radius, theme.border);
+        self.content.rounded_rect(
+            check.shrink(Edges::all(theme.border_width)),
+            theme.corner_radius,
+            theme.surface,
+        );
+        if *value {
+            self.content
+                .rect(check.shrink(Edges::all(size * 0.25)), theme.accent);
+        }
+
+        let pos = Vec2::new(check.max.x + theme.padding, rect.min.y);
+        self.content.text(pos, label, theme.font_size, theme.text);
+
+        return changed;
+    }
+
+    /// Drag to set `value` inside the range, returns true if it changed
+    pub fn slider(&mut self, label: &str, value: &mut f32, range: RangeInclusive<f32>) -> bool {
+        let (id, rect) = self.row(self.line_height());
+        let (start, end) = (*range.start(), *range.end());
+
+        /*
+         * Label on the left half, track on the right half
+         */
+        let track = Rect::new(Vec2::new(rect.center().x, rect.min.y), rect.max);
+
+        if self.clicked(track) {
+            self.active = Some(id);
+        }
+
+        let mut changed = false;
+        if let (Some(pos), true) = (self.pointer, self.active == Some(id) && self.down) {
+            let t = ((pos.x - track.min.x) / track.width()).clamp(0.0, 1.0);
+            let new_value = start + (end - start) * t;
+
+            changed = new_value != *value;
+            *value = new_value;
+        }
+
+        let theme = &self.theme;
+        let t = match end != start {
+            true => ((*value - start) / (end - start)).clamp(0.0, 1.0),
+            false => 0.0,
+        };
+        let fill = Rect::new(
+            track.min,
+            Vec2::new(track.min.x + t * track.width(), track.max.y),
+        );
+        let text_pos = Vec2::new(track.min.x + theme.padding, track.min.y);
+
+        self.content
+            .text(rect.min, label, theme.font_size, theme.text);
+        self.content
+            .rounded_rect(track, theme.corner_radius, theme.surface);
+        self.content
+            .rounded_rect(fill, theme.corner_radius, theme.selection);
+        self.content.text(
+            text_pos,
+            &format!("{value:.3}"),
+            theme.font_size,
+            theme.text,
+        );
+
+        return changed;
+    }
+
+    /// Line graph of the values scaled to fit, with the last value printed
+    pub fn plot_line(&mut self, label: &str, values: &[f32]) {
+        let line_height = self.line_height();
+        let (_, rect) = self.row(line_height * 3.0);
+        let theme = &self.theme;
+
+        self.content
+            .text(rect.min, label, theme.font_size, theme.text_muted);
+        if let Some(last) = values.last() {
+            let text = format!("{last:.3}");
+            let width = self.text.text_width(&text, theme.font_size);
+            let pos = Vec2::new(rect.max.x - width, rect.min.y);
+            self.content.text(pos, &text, theme.font_size, theme.text);
+        }
+
+        let area = Rect::new(Vec2::new(rect.min.x, rect.min.y + line_height), rect.max);
+        self.content.rect(area, theme.surface);
+
+        if values.len() < 2 {
+            return;
+        }
+
+        /*
+         * Scale to the range of the values
+         */
+        let (min, max) = values
+            .iter()
+            .fold((f32::INFINITY, f32::NEG_INFINITY), |(min, max), &v| {
+                (min.min(v), max.max(v))
+            });
+        let (min, max) = match max > min {
+            true => (min, max),
+            false => (min - 1.0, max + 1.0),
+        };
+
+        let step = area.width() / (values.len() - 1) as f32;
+        let y = |v: f32| area.max.y - (v - min) / (max - min) * area.height();
+
+        self.content.push_clip(area);
+        for (i, pair) in values.windows(2).enumerate() {
+            let x = area.min.x + i as f32 * step;
+            let from = Vec2::new(x, y(pair[0]));
+            let to = Vec2::new(x + step, y(pair[1]));
+            self.content.line(from, to, 1.0, theme.accent);
+        }
+        self.content.pop_clip();
+    }
+}
+
+//-----------------------------------------------------------------------------
@@ -0,0 +1,291 @@
+//-----------------------------------------------------------------------------
+//! Input events
+//!
+//! Widgets receive backend independent [`Event`]s. [`WinitInput`] translates the window events
+//! of winit into them.
+//!
+//! Positions and distances are in logical pixels ( physical pixels divided by the scale factor
+//! of the monitor ), so the UI keeps its size on high DPI monitors.
+//-----------------------------------------------------------------------------
+use soh_math::Vec2;
+use winit::dpi::PhysicalSize;
+use winit::event::{ElementState, MouseButton, MouseScrollDelta, WindowEvent};
+use winit::keyboard::{Key as WinitKey, NamedKey};
+//-----------------------------------------------------------------------------
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Event {
+    PointerMoved {
+        pos: Vec2<f32>,
+    },
+    PointerDown {
+        pos: Vec2<f32>,
+        button: PointerButton,
+    },
+    PointerUp {
+        pos: Vec2<f32>,
+        button: PointerButton,
+    },
+    /// Scroll by `delta` pixels
+    Scroll {
+        pos: Vec2<f32>,
+        delta: Vec2<f32>,
+    },
+    KeyDown {
+        key: Key,
+        modifiers: Modifiers,
+    },
+    KeyUp {
+        key: Key,
+        modifiers: Modifiers,
+    },
+    /// Text typed by the user (already processed by the keyboard layout)
+    Text(String),
+    /// The window moved to a monitor with another scale factor (or the setting changed)
+    ScaleFactorChanged {
+        scale_factor: f32,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PointerButton {
+    Primary,
+    Secondary,
+    Middle,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Key {
+    Left,
+    Right,
+    Up,
+    Down,
+    Home,
+    End,
+    PageUp,
+    PageDown,
+    Backspace,
+    Delete,
+    Enter,
+    Escape,
+    Tab,
+    Space,
+    /// Character key (lowercase for letters)
+    Char(char),
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Modifiers {
+    pub shift: bool,
+    pub ctrl: bool,
+    pub alt: bool,
+    pub logo: bool,
+}
+
+/// Translates winit window events into UI events
+#[derive(Debug)]
+pub struct WinitInput {
+    modifiers: Modifiers,
+    /// In logical pixels
+    pointer_pos: Vec2<f32>,
+    /// In physical pixels (to move the pointer when the scale factor changes)
+    physical_pointer_pos: Vec2<f32>,
+    scale_factor: f32,
+}
+
+//-----------------------------------------------------------------------------
+
+impl Modifiers {
+    pub const NONE: Modifiers = Modifiers {
+        shift: false,
+        ctrl: false,
+        alt: false,
+        logo: false,
+    };
+
+    pub fn is_empty(&self) -> bool {
+        return *self == Modifiers::NONE;
+    }
+
+    /// Modifier used for shortcuts (Cmd on macOS, Ctrl everywhere else)
+    pub fn command(&self) -> bool {
+        if cfg!(target_os = "macos") {
+            return self.logo;
+        }
+        return self.ctrl;
+    }
+}
+
+impl std::ops::BitOr for Modifiers {
+    type Output = Modifiers;
+
+    fn bitor(self, rhs: Self) -> Self::Output {
+        return Modifiers {
+            shift: self.shift || rhs.shift,
+            ctrl: self.ctrl || rhs.ctrl,
+            alt: self.alt || rhs.alt,
+            logo: self.logo || rhs.logo,
+        };
+    }
+}
+
+//-----------------------------------------------------------------------------
+// Getters
+impl WinitInput {
+    pub fn modifiers(&self) -> Modifiers {
+        return self.modifiers;
+    }
+    pub fn pointer_pos(&self) -> Vec2<f32> {
+        return self.pointer_pos;
+    }
+    /// Physical pixels per logical pixel
+    pub fn scale_factor(&self) -> f32 {
+        return self.scale_factor;
+    }
+}
+
+//-----------------------------------------------------------------------------
+// Specific implementation
+impl WinitInput {
+    pub fn new() -> Self {
+        return Self::default();
+    }
+
+    /// Start with the scale factor of the window ( `Window::scale_factor`, winit only sends an
+    /// event when it changes )
+    pub fn with_scale_factor(mut self, scale_factor: f64) -> Self {
+        self.scale_factor = scale_factor as f32;
+        return self;
+    }
+
+    /// Size of the window in logical pixels (the viewport of the UI layout)
+    pub fn logical_size(&self, size: PhysicalSize<u32>) -> Vec2<f32> {
+        return Vec2::new(size.width as f32, size.height as f32) / self.scale_factor;
+    }
+
+    /// Translate a window event (might produce multiple UI events, e.g. key press and text)
+    pub fn translate(&mut self, event: &WindowEvent) -> Vec<Event> {
+        let mut events = vec![];
+
+        match event {
+            WindowEvent::ModifiersChanged(modifiers) => {
+                let state = modifiers.state();
+                self.modifiers = Modifiers {
+                    shift: state.shift_key(),
+                    ctrl: state.control_key(),
+                    alt: state.alt_key(),
+                    logo: state.super_key(),
+                };
+            }
+            WindowEvent::ScaleFactorChanged { scale_factor, .. } => {
+                self.scale_factor = *scale_factor as f32;
+                self.pointer_pos = self.physical_pointer_pos / self.scale_factor;
+                events.push(Event::ScaleFactorChanged {
+                    scale_factor: self.scale_factor,
+                });
+            }
+            WindowEvent::CursorMoved { position, .. } => {
+                self.physical_pointer_pos = Vec2::new(position.x as f32, position.y as f32);
+                self.pointer_pos = self.physical_pointer_pos / self.scale_factor;
+                events.push(Event::PointerMoved {
+                    pos: self.pointer_pos,
+                });
+            }
+            WindowEvent::MouseInput { state, button, .. } => {
+                let button = match button {
+                    MouseButton::Left => PointerButton::Primary,
+                    MouseButton::Right => PointerButton::Secondary,
+                    MouseButton::Middle => PointerButton::Middle,
+                    _ => return events,
+                };
+                let pos = self.pointer_pos;
+                events.push(match state {
+                    ElementState::Pressed => Event::PointerDown { pos, button },
+                    ElementState::Released => Event::PointerUp { pos, button },
+                });
+            }
+            WindowEvent::MouseWheel { delta, .. } => {
+                const LINE_HEIGHT: f32 = 40.0;
+
+                let delta = match delta {
+                    MouseScrollDelta::LineDelta(x, y) => Vec2::new(*x, *y) * LINE_HEIGHT,
+                    MouseScrollDelta::PixelDelta(delta) => {
+                        Vec2::new(delta.x as f32, delta.y as f32) / self.scale_factor
+                    }
+                };
+                events.push(Event::Scroll {
+                    pos: self.pointer_pos,
+                    delta,
+                });
+            }
+            WindowEvent::KeyboardInput { event, .. } => {
+                let pressed = event.state == ElementState::Pressed;
+
+                if let Some(key) = translate_key(&event.logical_key) {
+                    let modifiers = self.modifiers;
+                    events.push(match pressed {
+                        true => Event::KeyDown { key, modifiers },
+                        false => Event::KeyUp { key, modifiers },
+                    });
+                }
+
+                /*
+                 * Shortcuts don't produce text
+                 */
+                if pressed && !self.modifiers.command() {
+                    if let Some(text) = &event.text {
+                        if text.chars().any(|c| !c.is_control()) {
+                            events.push(Event::Text(text.to_string()));
+                        }
+                    }
+                }
+            }
+            _ => {}
+        }
+
+        return events;
+    }
+}
+
+impl Default for WinitInput {
+    fn default() -> Self {
+        return WinitInput {
+            modifiers: Modifiers::NONE,
+            pointer_pos: Vec2::new(0.0, 0.0),
+            physical_pointer_pos: Vec2::new(0.0, 0.0),
+            scale_factor: 1.0,
+        };
+    }
+}
+
+fn translate_key(key: &WinitKey) -> Option<Key> {
+    return match key {
+        WinitKey::Named(named) => match named {
+            NamedKey::ArrowLeft => Some(Key::Left),
+            NamedKey::ArrowRight => Some(Key::Right),
+            NamedKey::ArrowUp => Some(Key::Up),
+            NamedKey::ArrowDown => Some(Key::Down),
+            NamedKey::Home => Some(Key::Home),
+            NamedKey::End => Some(Key::End),
+            NamedKey::PageUp => Some(Key::PageUp),
+            NamedKey::PageDown => Some(Key::PageDown),
+            NamedKey::Backspace => Some(Key::Backspace),
+            NamedKey::Delete => Some(Key::Delete),
+            NamedKey::Enter => Some(Key::Enter),
+            NamedKey::Escape => Some(Key::Escape),
+            NamedKey::Tab => Some(Key::Tab),
+            NamedKey::Space => Some(Key::Space),
+            _ => None,
+        },
+        WinitKey::Character(text) => {
+            let mut chars = text.chars();
+            match (chars.next(), chars.next()) {
+                (Some(c), None) => Some(Key::Char(c.to_ascii_lowercase())),
+                _ => None,
+            }
+        }
+        _ => None,
+    };
+}
+
+//-----------------------------------------------------------------------------
@@ -0,0 +1,492 @@
+//-----------------------------------------------------------------------------
+// Simplified version of the CSS flexbox algorithm
+// https://www.w3.org/TR/css-flexbox-1/#layout-algorithm
+//-----------------------------------------------------------------------------
+use super::{
+    Align, Dimension, Direction, Display, Justify, LayoutTree, Overflow, Position, Style, Wrap,
+};
+use crate::{Rect, RectExt};
+use soh_math::Vec2;
+//-----------------------------------------------------------------------------
+
+/// Compute the layout of the `root` node (placed at `rect`) and all of its descendants
+pub fn compute<T: LayoutTree>(tree: &mut T, root: T::Id, rect: Rect) {
+    tree.set_rect(root, rect);
+    layout_children(tree, root, rect);
+}
+
+//-----------------------------------------------------------------------------
+
+/// Flex item state during the layout of a container
+struct Item<Id> {
+    id: Id,
+    style: Style,
+
+    /*
+     * Main axis
+     */
+    margin_main: (f32, f32),
+    base: f32,
+    min_main: f32,
+    max_main: f32,
+    target_main: f32,
+    frozen: bool,
+
+    /*
+     * Cross axis
+     */
+    margin_cross: (f32, f32),
+    cross: f32,
+    stretch: bool,
+}
+
+impl<Id> Item<Id> {
+    fn outer_main(&self) -> f32 {
+        return self.margin_main.0 + self.target_main + self.margin_main.1;
+    }
+
+    fn outer_cross(&self) -> f32 {
+        return self.margin_cross.0 + self.cross + self.margin_cross.1;
+    }
+}
+
+//-----------------------------------------------------------------------------
+
+fn layout_children<T: LayoutTree>(tree: &mut T, node: T::Id, rect: Rect) {
+    let style = *tree.style(node);
+    let dir = style.direction;
+
+    let content = scroll_content(tree, node, &style, rect.shrink(style.padding));
+    let content_main = main(content.size(), dir);
+    let content_cross = cross(content.size(), dir);
+
+    /*
+     * Collect flex items ( absolutely positioned children are placed right away )
+     */
+    let children = tree.children(node).to_vec();
+    let mut items = Vec::with_capacity(children.len());
+
+    for child in children {
+        let child_style = *tree.style(child);
+
+        if child_style.display == Display::None {
+            tree.set_rect(child, Rect::from_pos_size(content.min, Vec2::new(0.0, 0.0)));
+            continue;
+        }
+
+        if let Position::Absolute(offset) = child_style.position {
+            let size = intrinsic_size(tree, child, content.size());
+            let child_rect = Rect::from_pos_size(content.min + offset, size);
+
+            tree.set_rect(child, child_rect);
+            layout_children(tree, child, child_rect);
+            continue;
+        }
+
+        items.push(make_item(tree, child, child_style, content.size(), dir));
+    }
+
+    /*
+     * Split items into lines
+     */
+    let mut lines: Vec<std::ops::Range<usize>> = vec![];
+    let mut line_start = 0;
+    let mut line_main = 0.0;
+
+    for (idx, item) in items.iter().enumerate() {
+        let outer = item.outer_main();
+        let gap = if idx > line_start { style.gap } else { 0.0 };
+
+        if style.wrap == Wrap::Wrap && idx > line_start && line_main + gap + outer > content_main {
+            lines.push(line_start..idx);
+            line_start = idx;
+            line_main = outer;
+        } else {
+            line_main += gap + outer;
+        }
+    }
+    if line_start < items.len() {
+        lines.push(line_start..items.len());
+    }
+
+    let single_line = style.wrap == Wrap::NoWrap;
+
+    /*
+     * Lay out every line
+     */
+    let mut line_cross_pos = cross(content.min, dir);
+
+    for line in lines {
+        let line_items = &mut items[line];
+
+        resolve_flexible_lengths(line_items, content_main, style.gap);
+
+        /*
+         * Cross sizes
+         */
+        for item in line_items.iter_mut() {
+            let available = from_axes(item.target_main, content_cross, dir);
+            let cross_dim = cross_dimension(&item.style, dir);
+            let align = item.style.align_self.unwrap_or(style.align_items);
+
+            let cross_size = match cross_dim.resolve(content_cross) {
+                Some(size) => size,
+                None => cross(intrinsic_size(tree, item.id, available), dir),
+            };
+
+            item.stretch = align == Align::Stretch && cross_dim == Dimension::Auto;
+            item.cross = clamp_cross(&item.style, dir, cross_size);
+        }
+
+        let line_cross = if single_line {
+            content_cross
+        } else {
+            line_items
+                .iter()
+                .map(|item| item.outer_cross())
+                .fold(0.0, f32::max)
+        };
+
+        for item in line_items.iter_mut().filter(|item| item.stretch) {
+            let stretched = line_cross - item.margin_cross.0 - item.margin_cross.1;
+            item.cross = clamp_cross(&item.style, dir, stretched);
+        }
+
+        /*
+         * Main axis positions
+         */
+        let num_of_items = line_items.len() as f32;
+        let used_main = line_items.iter().map(|item| item.outer_main()).sum::<f32>()
+            + style.gap * (num_of_items - 1.0);
+        let remaining = content_main - used_main;
+
+        let (offset, spacing) = justify_offsets(style.justify, remaining, num_of_items);
+
+        let mut main_pos = main(content.min, dir) + offset;
+
+        for item in line_items.iter() {
+            let align = item.style.align_self.unwrap_or(style.align_items);
+            let cross_offset = match align {
+                Align::Start | Align::Stretch => 0.0,
+                Align::End => line_cross - item.outer_cross(),
+                Align::Center => (line_cross - item.outer_cross()) * 0.5,
+            };
+
+            let pos = from_axes(
+                main_pos + item.margin_main.0,
+                line_cross_pos + cross_offset + item.margin_cross.0,
+                dir,
+            );
+            let size = from_axes(item.target_main, item.cross, dir);
+            let item_rect = Rect::from_pos_size(pos, size);
+
+            tree.set_rect(item.id, item_rect);
+            layout_children(tree, item.id, item_rect);
+
+            main_pos += item.outer_main() + style.gap + spacing;
+        }
+
+        line_cross_pos += line_cross + style.gap;
+    }
+}
+
+//-----------------------------------------------------------------------------
+
+fn make_item<T: LayoutTree>(
+    tree: &T,
+    id: T::Id,
+    style: Style,
+    parent_content: Vec2<f32>,
+    dir: Direction,
+) -> Item<T::Id> {
+    let parent_main = main(parent_content, dir);
+
+    let base = style
+        .basis
+        .resolve(parent_main)
+        .or_else(|| main_dimension(&style, dir).resolve(parent_main))
+        .unwrap_or_else(|| main(intrinsic_size(tree, id, parent_content), dir));
+
+    let min_main = main(style.min_size, dir);
+    let max_main = main(style.max_size, dir).max(min_main);
+
+    let (margin_main, margin_cross) = match dir {
+        Direction::Row => (
+            (style.margin.left, style.margin.right),
+            (style.margin.top, style.margin.bottom),
+        ),
+        Direction::Column => (
+            (style.margin.top, style.margin.bottom),
+            (style.margin.left, style.margin.right),
+        ),
+    };
+
+    return Item {
+        id,
+        style,
+
+        margin_main,
+        base,
+        min_main,
+        max_main,
+        target_main: base.clamp(min_main, max_main),
+        frozen: false,
+
+        margin_cross,
+        cross: 0.0,
+        stretch: false,
+    };
+}
+
+/// Grow or shrink the items of a line so that they fill the container
+fn resolve_flexible_lengths<Id>(items: &mut [Item<Id>], container_main: f32, gap: f32) {
+    let gaps = gap * (items.len() as f32 - 1.0).max(0.0);
+    let margins = items
+        .iter()
+        .map(|item| item.margin_main.0 + item.margin_main.1)
+        .sum::<f32>();
+
+    let initial_free =
+        container_main - gaps - margins - items.iter().map(|item| item.base).sum::<f32>();
+    let growing = initial_free > 0.0;
+
+    /*
+     * Freeze inflexible items
+     */
+    for item in items.iter_mut() {
+        let hypothetical = item.base.clamp(item.min_main, item.max_main);
+        item.target_main = hypothetical;
+
+        item.frozen = if growing {
+            item.style.grow <= 0.0 || item.base > hypothetical
+        } else {
+            item.style.shrink <= 0.0 || item.base < hypothetical
+        };
+    }
+
+    /*
+     * Distribute the free space until no item violates its min/max constraints
+     */
+    while items.iter().any(|item| !item.frozen) {
+        let used = items
+            .iter()
+            .map(|item| {
+                if item.frozen {
+                    item.target_main
+                } else {
+                    item.base
+                }
+            })
+            .sum::<f32>();
+        let free = container_main - gaps - margins - used;
+
+        let unfrozen = || items.iter().filter(|item| !item.frozen);
+        let sum_of_factors = if growing {
+            unfrozen().map(|item| item.style.grow).sum::<f32>()
+        } else {
+            unfrozen()
+                .map(|item| item.style.shrink * item.base)
+                .sum::<f32>()
+        };
+
+        let mut violations = Vec::with_capacity(items.len());
+
+        for item in items.iter_mut().filter(|item| !item.frozen) {
+            let factor = if growing {
+                item.style.grow
+            } else {
+                item.style.shrink * item.base
+            };
+
+            let unclamped = if sum_of_factors > 0.0 {
+                item.base + free * factor / sum_of_factors
+            } else {
+                item.base
+            };
+
+            item.target_main = unclamped.clamp(item.min_main, item.max_main);
+            violations.push(item.target_main - unclamped);
+        }
+
+        /*
+         * Freeze all items if there were no violations, otherwise freeze the violating ones
+         */
+        let total_violation = violations.iter().sum::<f32>();
+
+        for (item, violation) in items.iter_mut().filter(|item| !item.frozen).zip(violations) {
+            item.frozen = if total_violation > 0.0 {
+                violation > 0.0
+            } else if total_violation < 0.0 {
+                violation < 0.0
+            } else {
+                true
+            };
+        }
+    }
+}
+
+/// Get the offset of the first item and the extra spacing between items
+fn justify_offsets(justify: Justify, remaining: f32, num_of_items: f32) -> (f32, f32) {
+    let positive = remaining.max(0.0);
+
+    return match justify {
+        Justify::Start => (0.0, 0.0),
+        Justify::End => (remaining, 0.0),
+        Justify::Center => (remaining * 0.5, 0.0),
+        Justify::SpaceBetween if num_of_items > 1.0 => (0.0, positive / (num_of_items - 1.0)),
+        Justify::SpaceBetween => (0.0, 0.0),
+        Justify::SpaceAround => {
+            let spacing = positive / num_of_items;
+            (spacing * 0.5, spacing)
+        }
+        Justify::SpaceEvenly => {
+            let spacing = positive / (num_of_items + 1.0);
+            (spacing, spacing)
+        }
+    };
+}
+
+//-----------------------------------------------------------------------------
+
+/// Content rect of a node which is big enough for the children along the scrolled axes
+fn scroll_content<T: LayoutTree>(tree: &T, node: T::Id, style: &Style, content: Rect) -> Rect {
+    let scroll_x = style.overflow_x == Overflow::Scroll;
+    let scroll_y = style.overflow_y == Overflow::Scroll;
+
+    if !scroll_x && !scroll_y {
+        return content;
+    }
+
+    let needed = content_size(tree, node, content.size());
+    let size = Vec2::new(
+        match scroll_x {
+            true => content.width().max(needed.x),
+            false => content.width(),
+        },
+        match scroll_y {
+            true => content.height().max(needed.y),
+            false => content.height(),
+        },
+    );
+
+    return Rect::from_pos_size(content.min, size);
+}
+
+/// Size of a node determined by its own style and its content (ignoring grow and shrink)
+pub fn intrinsic_size<T: LayoutTree>(tree: &T, node: T::Id, available: Vec2<f32>) -> Vec2<f32> {
+    let style = tree.style(node);
+
+    let width = style.width.resolve(available.x);
+    let height = style.height.resolve(available.y);
+
+    let size = match (width, height) {
+        (Some(width), Some(height)) => Vec2::new(width, height),
+        _ => {
+            let padding = style.padding.sum();
+            let content_available = Vec2::new(
+                (width.unwrap_or(available.x) - padding.x).max(0.0),
+                (height.unwrap_or(available.y) - padding.y).max(0.0),
+            );
+
+            let content = content_size(tree, node, content_available) + padding;
+
+            Vec2::new(width.unwrap_or(content.x), height.unwrap_or(content.y))
+        }
+    };
+
+    return clamp_size(size, style);
+}
+
+/// Size of the children of a node (laid out in a single line) or the measured content size
+fn content_size<T: LayoutTree>(tree: &T, node: T::Id, available: Vec2<f32>) -> Vec2<f32> {
+    let style = tree.style(node);
+    let children = tree.children(node);
+
+    if children.is_empty() {
+        return tree.measure(node, available);
+    }
+
+    let dir = style.direction;
+
+    let mut sum_main = 0.0;
+    let mut max_cross: f32 = 0.0;
+    let mut num_of_items = 0;
+
+    for &child in children.iter() {
+        let child_style = tree.style(child);
+
+        if child_style.display == Display::None {
+            continue;
+        }
+        if let Position::Absolute(_) = child_style.position {
+            continue;
+        }
+
+        let outer = intrinsic_size(tree, child, available) + child_style.margin.sum();
+
+        sum_main += main(outer, dir);
+        max_cross = max_cross.max(cross(outer, dir));
+        num_of_items += 1;
+    }
+
+    if num_of_items > 1 {
+        sum_main += style.gap * (num_of_items - 1) as f32;
+    }
+
+    return from_axes(sum_main, max_cross, dir);
+}
+
+fn clamp_size(size: Vec2<f32>, style: &Style) -> Vec2<f32> {
+    return Vec2::new(
+        size.x
+            .clamp(style.min_size.x, style.max_size.x.max(style.min_size.x)),
+        size.y
+            .clamp(style.min_size.y, style.max_size.y.max(style.min_size.y)),
+    );
+}
+
+fn clamp_cross(style: &Style, dir: Direction, size: f32) -> f32 {
+    let min = cross(style.min_size, dir);
+    let max = cross(style.max_size, dir).max(min);
+
+    return size.clamp(min, max);
+}
+
+//-----------------------------------------------------------------------------
+// Axis helpers
+fn main(v: Vec2<f32>, dir: Direction) -> f32 {
+    return match dir {
+        Direction::Row => v.x,
+        Direction::Column => v.y,
+    };
+}
+
+fn cross(v: Vec2<f32>, dir: Direction) -> f32 {
+    return match dir {
+        Direction::Row => v.y,
+        Direction::Column => v.x,
+    };
+}
+
+fn from_axes(main: f32, cross: f32, dir: Direction) -> Vec2<f32> {
+    return match dir {
+        Direction::Row => Vec2::new(main, cross),
+        Direction::Column => Vec2::new(cross, main),
+    };
+}
+
+fn main_dimension(style: &Style, dir: Direction) -> Dimension {
+    return match dir {
+        Direction::Row => style.width,
+        Direction::Column => style.height,
+    };
+}
+
+fn cross_dimension(style: &Style, dir: Direction) -> Dimension {
+    return match dir {
+        Direction::Row => style.height,
+        Direction::Column => style.width,
+    };
+}
+
+//-----------------------------------------------------------------------------
@@ -0,0 +1,447 @@
+//-----------------------------------------------------------------------------
+//! Flexbox-like layout
+//!
+//! Every node has a [`Style`] describing how it's sized and how it arranges its children. The
+//! layout is recomputed every frame (before drawing) with [`compute`], so it follows window
+//! resizes.
+//-----------------------------------------------------------------------------
+mod flex;
+#[cfg(feature = "serde")]
+mod serde_vec2;
+mod tree;
+//-----------------------------------------------------------------------------
+pub use flex::{compute, intrinsic_size};
+pub use tree::*;
+//-----------------------------------------------------------------------------
+use soh_math::Vec2;
+//-----------------------------------------------------------------------------
+
+/// Main axis of a container
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Direction {
+    #[default]
+    Row,
+    Column,
+}
+
+/// Whether the children of a container may be split into multiple lines
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Wrap {
+    #[default]
+    NoWrap,
+    Wrap,
+}
+
+/// Distribution of the free space along the main axis
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Justify {
+    #[default]
+    Start,
+    End,
+    Center,
+    SpaceBetween,
+    SpaceAround,
+    SpaceEvenly,
+}
+
+/// Alignment of children along the cross axis
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Align {
+    Start,
+    End,
+    Center,
+    #[default]
+    Stretch,
+}
+
+/// Size along one axis
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Dimension {
+    /// Sized by the content (or by grow/shrink/stretch)
+    #[default]
+    Auto,
+    /// Size in pixels
+    Px(f32),
+    /// Fraction (0.0 - 1.0) of the parent's content size
+    Percent(f32),
+}
+
+/// How the node is positioned inside its parent
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Position {
+    /// Placed by the parent's flex layout
+    #[default]
+    Relative,
+    /// Placed at an offset from the parent's content origin, ignored by the flex layout
+    Absolute(#[cfg_attr(feature = "serde", serde(with = "serde_vec2"))] Vec2<f32>),
+}
+
+/// Whether the node takes part in the layout
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Display {
+    #[default]
+    Flex,
+    /// The node is hidden and takes no space (neither do its children)
+    None,
+}
+
+/// What happens to the content which doesn't fit into the node (along one axis)
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Overflow {
+    /// The children are shrunk to fit (or overflow if they can't shrink)
+    #[default]
+    Visible,
+    /// The children keep their size and are scrolled (see [`crate::widget::ScrollArea`])
+    Scroll,
+}
+
+/// Padding or margin sizes
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Edges {
+    pub left: f32,
+    pub right: f32,
+    pub top: f32,
+    pub bottom: f32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct Style {
+    /*
+     * Container properties
+     */
+    pub direction: Direction,
+    pub wrap: Wrap,
+    pub justify: Justify,
+    pub align_items: Align,
+    /// Space between children along the main axis (and between lines along the cross axis)
+    pub gap: f32,
+    pub padding: Edges,
+    pub overflow_x: Overflow,
+    pub overflow_y: Overflow,
+
+    /*
+     * Item properties
+     */
+    pub display: Display,
+    pub position: Position,
+    /// Overrides the parent's `align_items`
+    pub align_self: Option<Align>,
+    pub grow: f32,
+    pub shrink: f32,
+    /// Initial main size before growing or shrinking ( `Auto` uses width/height )
+    pub basis: Dimension,
+    pub margin: Edges,
+
+    pub width: Dimension,
+    pub height: Dimension,
+    #[cfg_attr(feature = "serde", serde(with = "serde_vec2"))]
+    pub min_size: Vec2<f32>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(
+            with = "serde_vec2::max_size",
+            skip_serializing_if = "serde_vec2::max_size::is_unbounded"
+        )
+    )]
+    pub max_size: Vec2<f32>,
+}
+
+//-----------------------------------------------------------------------------
+
+/// Tree of nodes which can be laid out
+pub trait LayoutTree {
+    type Id: Copy;
+
+    fn style(&self, node: Self::Id) -> &Style;
+    fn children(&self, node: Self::Id) -> &[Self::Id];
+
+    /// Size of the content of a node (used for `Auto` sizes of nodes without children)
+    ///
+    /// * `available`: the space available for the content
+    fn measure(&self, node: Self::Id, available: Vec2<f32>) -> Vec2<f32>;
+
+    /// Store the computed rectangle of a node
+    fn set_rect(&mut self, node: Self::Id, rect: crate::Rect);
+}
+
+//-----------------------------------------------------------------------------
+
+impl Edges {
+    pub const ZERO: Edges = Edges::all(0.0);
+
+    pub const fn all(value: f32) -> Self {
+        return Edges {
+            left: value,
+            right: value,
+            top: value,
+            bottom: value,
+        };
+    }
+
+    pub const fn symmetric(horizontal: f32, vertical: f32) -> Self {
+        return Edges {
+            left: horizontal,
+            right: horizontal,
+            top: vertical,
+            bottom: vertical,
+        };
+    }
+
+    pub fn horizontal(&self) -> f32 {
+        return self.left + self.right;
+    }
+
+    pub fn vertical(&self) -> f32 {
+        return self.top + self.bottom;
+    }
+
+    pub fn sum(&self) -> Vec2<f32> {
+        return Vec2::new(self.horizontal(), self.vertical());
+    }
+}
+
+impl Dimension {
+    /// Get the size in pixels (None for `Auto`)
+    pub fn resolve(&self, parent_size: f32) -> Option<f32> {
+        return match *self {
+            Dimension::Auto => None,
+            Dimension::Px(px) => Some(px),
+            Dimension::Percent(fraction) => Some(fraction * parent_size),
+        };
+    }
+}
+
+impl Style {
+    /// Style which makes a node fill the remaining space of its parent
+    pub fn fill() -> Self {
+        return Style {
+            grow: 1.0,
+            ..Default::default()
+        };
+    }
+
+    /// Style of a node with fixed size
+    pub fn fixed(width: f32, height: f32) -> Self {
+        return Style {
+            width: Dimension::Px(width),
+            height: Dimension::Px(height),
+            shrink: 0.0,
+            ..Default::default()
+        };
+    }
+}
+
+impl Default for Style {
+    fn default() -> Self {
+        return Style {
+            direction: Direction::default(),
+            wrap: Wrap::default(),
+            justify: Justify::default(),
+            align_items: Align::default(),
+            gap: 0.0,
+            padding: Edges::ZERO,
+            overflow_x: Overflow::default(),
+            overflow_y: Overflow::default(),
+
+            display: Display::default(),
+            position: Position::default(),
+            align_self: None,
+            grow: 0.0,
+            shrink: 1.0,
+            basis: Dimension::Auto,
+            margin: Edges::ZERO,
+
+            width: Dimension::Auto,
+            height: Dimension::Auto,
+            min_size: Vec2::new(0.0, 0.0),
+            max_size: Vec2::new(f32::INFINITY, f32::INFINITY),
+        };
+    }
+}
+
+//-----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vec2(x: f32, y: f32) -> Vec2<f32> {
+        return Vec2::new(x, y);
+    }
+
+    #[test]
+    fn test_row_grow() {
+        let mut tree = Tree::new(Style {
+            padding: Edges::all(10.0),
+            gap: 10.0,
+            ..Default::default()
+        });
+        let root = tree.root();
+
+        let fixed = tree.add(root, Style::fixed(100.0, 50.0));
+        let grow_1 = tree.add(root, Style::fill());
+        let grow_3 = tree.add(
+            root,
+            Style {
+                grow: 3.0,
+                ..Default::default()
+            },
+        );
+
+        tree.compute(vec2(540.0, 200.0));
+
+        // 540 - 2 * 10 padding - 2 * 10 gap - 100 fixed = 400 free space
+        assert_eq!(tree.rect(fixed).min, vec2(10.0, 10.0));
+        assert_eq!(tree.rect(fixed).size(), vec2(100.0, 50.0));
+        assert_eq!(tree.rect(grow_1).min, vec2(120.0, 10.0));
+        assert_eq!(tree.rect(grow_1).size(), vec2(100.0, 180.0));
+        assert_eq!(tree.rect(grow_3).min, vec2(230.0, 10.0));
+        assert_eq!(tree.rect(grow_3).size(), vec2(300.0, 180.0));
+    }
+
+    #[test]
+    fn test_column_justify_align() {
+        let mut tree = Tree::new(Style {
+            direction: Direction::Column,
+            justify: Justify::Center,
+            align_items: Align::Center,
+            ..Default::default()
+        });
+        let root = tree.root();
+
+        let a = tree.add(root, Style::fixed(40.0, 20.0));
+        let b = tree.add(root, Style::fixed(60.0, 20.0));
+
+        tree.compute(vec2(100.0, 100.0));
+
+        assert_eq!(tree.rect(a).min, vec2(30.0, 30.0));
+        assert_eq!(tree.rect(b).min, vec2(20.0, 50.0));
+    }
+
+    #[test]
+    fn test_shrink_min_max() {
+        let mut tree = Tree::new(Style::default());
+        let root = tree.root();
+
+        let a = tree.add(
+            root,
+            Style {
+                width: Dimension::Px(100.0),
+                min_size: vec2(80.0, 0.0),
+                ..Default::default()
+            },
+        );
+        let b = tree.add(
+            root,
+            Style {
+                width: Dimension::Px(100.0),
+                ..Default::default()
+            },
+        );
+        let c = tree.add(
+            root,
+            Style {
+                grow: 1.0,
+                max_size: vec2(10.0, f32::INFINITY),
+                ..Default::default()
+            },
+        );
+
+        tree.compute(vec2(150.0, 10.0));
+
+        // 50px overflow: `b` absorbs what `a` can't because of its min size
+        assert_eq!(tree.rect(a).width(), 80.0);
+        assert_eq!(tree.rect(b).width(), 70.0);
+        assert_eq!(tree.rect(c).width(), 0.0);
+
+        tree.compute(vec2(300.0, 10.0));
+
+        // Growing is limited by the max size
+        assert_eq!(tree.rect(a).width(), 100.0);
+        assert_eq!(tree.rect(b).width(), 100.0);
+        assert_eq!(tree.rect(c).width(), 10.0);
+    }
+
+    #[test]
+    fn test_wrap_margin_percent() {
+        let mut tree = Tree::new(Style {
+            wrap: Wrap::Wrap,
+            align_items: Align::Start,
+            ..Default::default()
+        });
+        let root = tree.root();
+
+        let items = (0..3)
+            .map(|_| {
+                tree.add(
+                    root,
+                    Style {
+                        width: Dimension::Percent(0.4),
+                        height: Dimension::Px(10.0),
+                        margin: Edges::all(5.0),
+                        ..Default::default()
+                    },
+                )
+            })
+            .collect::<Vec<_>>();
+
+        tree.compute(vec2(100.0, 100.0));
+
+        // Two items of 50px (with margins) fit into the first line
+        assert_eq!(tree.rect(items[0]).min, vec2(5.0, 5.0));
+        assert_eq!(tree.rect(items[1]).min, vec2(55.0, 5.0));
+        assert_eq!(tree.rect(items[2]).min, vec2(5.0, 25.0));
+        assert_eq!(tree.rect(items[2]).size(), vec2(40.0, 10.0));
+    }
+
+    #[test]
+    fn test_auto_size_from_content() {
+        let mut tree = Tree::new(Style {
+            direction: Direction::Column,
+            align_items: Align::Start,
+            ..Default::default()
+        });
+        let root = tree.root();
+
+        let row = tree.add(
+            root,
+            Style {
+                padding: Edges::all(2.0),
+                gap: 4.0,
+                ..Default::default()
+            },
+        );
+        let label = tree.add(row, Style::default());
+        tree.set_content_size(label, vec2(30.0, 12.0));
+        let button = tree.add(row, Style::fixed(20.0, 20.0));
+
+        let absolute = tree.add(
+            root,
+            Style {
+                position: Position::Absolute(vec2(50.0, 60.0)),
+                ..Style::fixed(5.0, 5.0)
+            },
+        );
+
+        tree.compute(vec2(200.0, 200.0));
+
+        assert_eq!(tree.rect(row).size(), vec2(58.0, 24.0));
+        assert_eq!(tree.rect(label).min, vec2(2.0, 2.0));
+        assert_eq!(tree.rect(label).size(), vec2(30.0, 20.0));
+        assert_eq!(tree.rect(button).min, vec2(36.0, 2.0));
+        assert_eq!(tree.rect(absolute).min, vec2(50.0, 60.0));
+    }
+}
+
+//-----------------------------------------------------------------------------
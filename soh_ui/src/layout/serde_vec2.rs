@@ -0,0 +1,42 @@
+//-----------------------------------------------------------------------------
+// Vectors of the styles are written as `(x, y)` tuples
+//-----------------------------------------------------------------------------
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use soh_math::Vec2;
+//-----------------------------------------------------------------------------
+
+pub fn serialize<S: Serializer>(v: &Vec2<f32>, serializer: S) -> Result<S::Ok, S::Error> {
+    return (v.x, v.y).serialize(serializer);
+}
+
+pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec2<f32>, D::Error> {
+    let (x, y) = <(f32, f32)>::deserialize(deserializer)?;
+    return Ok(Vec2::new(x, y));
+}
+
+//-----------------------------------------------------------------------------
+
+/// Maximum sizes are `(Option<f32>, Option<f32>)` tuples, `None` is unbounded (JSON has no
+/// infinity)
+pub mod max_size {
+    use super::*;
+
+    pub fn is_unbounded(v: &Vec2<f32>) -> bool {
+        return v.x == f32::INFINITY && v.y == f32::INFINITY;
+    }
+
+    pub fn serialize<S: Serializer>(v: &Vec2<f32>, serializer: S) -> Result<S::Ok, S::Error> {
+        let bound = |value: f32| value.is_finite().then_some(value);
+        return (bound(v.x), bound(v.y)).serialize(serializer);
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec2<f32>, D::Error> {
+        let (x, y) = <(Option<f32>, Option<f32>)>::deserialize(deserializer)?;
+        return Ok(Vec2::new(
+            x.unwrap_or(f32::INFINITY),
+            y.unwrap_or(f32::INFINITY),
+        ));
+    }
+}
+
+//-----------------------------------------------------------------------------
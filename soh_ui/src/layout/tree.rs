@@ -0,0 +1,107 @@
+//-----------------------------------------------------------------------------
+use super::{LayoutTree, Style};
+use crate::Rect;
+use soh_math::Vec2;
+//-----------------------------------------------------------------------------
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct NodeId(usize);
+
+/// Standalone layout tree (for laying out things which aren't widgets)
+pub struct Tree {
+    nodes: Vec<Node>,
+}
+
+struct Node {
+    style: Style,
+    children: Vec<NodeId>,
+
+    content_size: Vec2<f32>,
+    rect: Rect,
+}
+
+//-----------------------------------------------------------------------------
+// Constructor
+impl Tree {
+    pub fn new(root_style: Style) -> Self {
+        return Tree {
+            nodes: vec![Node::new(root_style)],
+        };
+    }
+}
+
+//-----------------------------------------------------------------------------
+// Getters
+impl Tree {
+    pub fn root(&self) -> NodeId {
+        return NodeId(0);
+    }
+    pub fn rect(&self, node: NodeId) -> Rect {
+        return self.nodes[node.0].rect;
+    }
+    pub fn style_mut(&mut self, node: NodeId) -> &mut Style {
+        return &mut self.nodes[node.0].style;
+    }
+}
+
+//-----------------------------------------------------------------------------
+// Specific implementation
+impl Tree {
+    /// Add a new node as the last child of `parent`
+    pub fn add(&mut self, parent: NodeId, style: Style) -> NodeId {
+        let id = NodeId(self.nodes.len());
+
+        self.nodes.push(Node::new(style));
+        self.nodes[parent.0].children.push(id);
+
+        return id;
+    }
+
+    /// Set the content size of a leaf node (e.g. the size of a text)
+    pub fn set_content_size(&mut self, node: NodeId, size: Vec2<f32>) {
+        self.nodes[node.0].content_size = size;
+    }
+
+    /// Lay out the whole tree inside a viewport of the given size
+    pub fn compute(&mut self, viewport_size: Vec2<f32>) {
+        let root = self.root();
+        let viewport = Rect::from_pos_size(Vec2::new(0.0, 0.0), viewport_size);
+
+        super::compute(self, root, viewport);
+    }
+}
+
+impl Node {
+    fn new(style: Style) -> Self {
+        return Node {
+            style,
+            children: vec![],
+            content_size: Vec2::new(0.0, 0.0),
+            rect: Rect::default(),
+        };
+    }
+}
+
+//-----------------------------------------------------------------------------
+
+impl LayoutTree for Tree {
+    type Id = NodeId;
+
+    fn style(&self, node: NodeId) -> &Style {
+        return &self.nodes[node.0].style;
+    }
+
+    fn children(&self, node: NodeId) -> &[NodeId] {
+        return &self.nodes[node.0].children;
+    }
+
+    fn measure(&self, node: NodeId, _available: Vec2<f32>) -> Vec2<f32> {
+        return self.nodes[node.0].content_size;
+    }
+
+    fn set_rect(&mut self, node: NodeId, rect: Rect) {
+        self.nodes[node.0].rect = rect;
+    }
+}
+
+//-----------------------------------------------------------------------------
@@ -1,14 +1,44 @@
-pub fn add(left: u64, right: u64) -> u64 {
-    left + right
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn it_works() {
-        let result = add(2, 2);
-        assert_eq!(result, 4);
-    }
-}
+//-----------------------------------------------------------------------------
+//! Retained mode UI
+//-----------------------------------------------------------------------------
+// Private modules
+mod rect;
+mod ui;
+//-----------------------------------------------------------------------------
+// Public imports
+pub use rect::*;
+pub use ui::*;
+//-----------------------------------------------------------------------------
+
+// Error type of the library
+pub mod error;
+pub use error::{Error, Result};
+
+// Flexbox-like layout
+pub mod layout;
+
+// Docking panels
+pub mod dock;
+
+// Immediate mode debug overlay
+pub mod immediate;
+
+// Widget trees described in data files
+#[cfg(feature = "serde")]
+pub mod data;
+
+// Widgets and everything they need
+pub mod animation;
+pub mod clipboard;
+pub mod drag;
+pub mod draw;
+pub mod input;
+pub mod nav;
+pub mod observable;
+pub mod overlay;
+pub mod panel;
+pub mod text;
+pub mod theme;
+pub mod widget;
+
+//-----------------------------------------------------------------------------
@@ -0,0 +1,302 @@
+//-----------------------------------------------------------------------------
+//! Navigation without a pointer ( gamepads, keyboard only menus )
+//!
+//! Navigation actions are turned into the key events the widgets already understand: the
+//! directions become arrow keys, accept becomes Enter, cancel becomes Escape ( closes popups and
+//! dialogs ) and next / previous become Tab / Shift+Tab. With spatial navigation enabled (see
+//! [`crate::Ui::set_spatial_navigation`]) the arrow keys move the focus to the closest widget in
+//! their direction.
+//!
+//! [`GamepadInput`] is independent of the gamepad library: the application passes it the button
+//! and stick state and gets the UI events, held directions repeat.
+//!
+//! ```
+//! use soh_ui::nav::{GamepadButton, GamepadInput};
+//! use soh_ui::{layout::Style, Ui};
+//!
+//! let mut ui = Ui::new(Style::default()).with_spatial_navigation(true);
+//! let mut gamepad = GamepadInput::new();
+//!
+//! // Gamepad events of the frame
+//! let mut events = gamepad.button(GamepadButton::DPadDown, true);
+//! events.extend(gamepad.left_stick(0.0, 0.0));
+//! // Once per frame ( repeats of the held directions )
+//! events.extend(gamepad.update(1.0 / 60.0));
+//!
+//! for event in events.iter() {
+//!     ui.handle_event(event);
+//! }
+//! ```
+//-----------------------------------------------------------------------------
+use crate::input::{Event, Key, Modifiers};
+//-----------------------------------------------------------------------------
+
+/// Seconds a direction is held before it starts repeating
+pub const REPEAT_DELAY: f32 = 0.4;
+
+/// Seconds between the repeats of a held direction
+pub const REPEAT_INTERVAL: f32 = 0.1;
+
+/// Stick deflection which presses a direction
+pub const STICK_PRESS: f32 = 0.5;
+
+/// Stick deflection under which the direction is released (lower than [`STICK_PRESS`], so a
+/// stick held at the threshold doesn't flicker)
+pub const STICK_RELEASE: f32 = 0.3;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum NavAction {
+    Up,
+    Down,
+    Left,
+    Right,
+    /// Activate the focused widget
+    Accept,
+    /// Close the popup or the dialog, leave the text input
+    Cancel,
+    /// Focus the next widget
+    Next,
+    /// Focus the previous widget
+    Previous,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GamepadButton {
+    DPadUp,
+    DPadDown,
+    DPadLeft,
+    DPadRight,
+    /// A on Xbox, Cross on PlayStation
+    South,
+    /// B on Xbox, Circle on PlayStation
+    East,
+    LeftShoulder,
+    RightShoulder,
+    /// Buttons which don't navigate
+    Other,
+}
+
+/// Repeats the held directions
+#[derive(Debug, Default, Clone)]
+pub struct NavRepeat {
+    /// Held direction with the time until its next repeat
+    held: Option<(NavAction, f32)>,
+}
+
+/// Translates the gamepad state into UI events
+#[derive(Debug, Default, Clone)]
+pub struct GamepadInput {
+    repeat: NavRepeat,
+    /// Direction pressed by the left stick
+    stick: Option<NavAction>,
+}
+
+//-----------------------------------------------------------------------------
+
+impl NavAction {
+    /// Key event which performs the action
+    pub fn to_event(&self) -> Event {
+        let (key, modifiers) = match self {
+            NavAction::Up => (Key::Up, Modifiers::NONE),
+            NavAction::Down => (Key::Down, Modifiers::NONE),
+            NavAction::Left => (Key::Left, Modifiers::NONE),
+            NavAction::Right => (Key::Right, Modifiers::NONE),
+            NavAction::Accept => (Key::Enter, Modifiers::NONE),
+            NavAction::Cancel => (Key::Escape, Modifiers::NONE),
+            NavAction::Next => (Key::Tab, Modifiers::NONE),
+            NavAction::Previous => (
+                Key::Tab,
+                Modifiers {
+                    shift: true,
+                    ..Modifiers::NONE
+                },
+            ),
+        };
+
+        return Event::KeyDown { key, modifiers };
+    }
+
+    pub fn is_direction(&self) -> bool {
+        return matches!(
+            self,
+            NavAction::Up | NavAction::Down | NavAction::Left | NavAction::Right
+        );
+    }
+}
+
+impl GamepadButton {
+    pub fn action(&self) -> Option<NavAction> {
+        return match self {
+            GamepadButton::DPadUp => Some(NavAction::Up),
+            GamepadButton::DPadDown => Some(NavAction::Down),
+            GamepadButton::DPadLeft => Some(NavAction::Left),
+            GamepadButton::DPadRight => Some(NavAction::Right),
+            GamepadButton::South => Some(NavAction::Accept),
+            GamepadButton::East => Some(NavAction::Cancel),
+            GamepadButton::LeftShoulder => Some(NavAction::Previous),
+            GamepadButton::RightShoulder => Some(NavAction::Next),
+            GamepadButton::Other => None,
+        };
+    }
+}
+
+//-----------------------------------------------------------------------------
+
+impl NavRepeat {
+    pub fn new() -> Self {
+        return Self::default();
+    }
+
+    /// Event of the pressed action (a direction starts repeating after [`REPEAT_DELAY`])
+    pub fn press(&mut self, action: NavAction) -> Event {
+        if action.is_direction() {
+            self.held = Some((action, REPEAT_DELAY));
+        }
+        return action.to_event();
+    }
+
+    pub fn release(&mut self, action: NavAction) {
+        if self.held.is_some_and(|(held, _)| held == action) {
+            self.held = None;
+        }
+    }
+
+    /// Advance the time by `dt` seconds, returns the repeated event
+    ///
+    /// At most one event is repeated per update, so a long frame doesn't move the focus many
+    /// times at once.
+    pub fn update(&mut self, dt: f32) -> Vec<Event> {
+        let mut events = vec![];
+        let Some((action, remaining)) = &mut self.held else {
+            return events;
+        };
+
+        *remaining -= dt;
+        if *remaining <= 0.0 {
+            events.push(action.to_event());
+            *remaining = REPEAT_INTERVAL;
+        }
+
+        return events;
+    }
+}
+
+impl GamepadInput {
+    pub fn new() -> Self {
+        return Self::default();
+    }
+
+    /// Button pressed or released
+    pub fn button(&mut self, button: GamepadButton, pressed: bool) -> Vec<Event> {
+        let Some(action) = button.action() else {
+            return vec![];
+        };
+
+        if !pressed {
+            self.repeat.release(action);
+            return vec![];
+        }
+        return vec![self.repeat.press(action)];
+    }
+
+    /// Position of the left stick ( -1.0 - 1.0, y points up ), the dominant axis presses a
+    /// direction
+    pub fn left_stick(&mut self, x: f32, y: f32) -> Vec<Event> {
+        let threshold = match self.stick {
+            Some(_) => STICK_RELEASE,
+            None => STICK_PRESS,
+        };
+
+        let direction = match x.abs() > y.abs() {
+            true if x >= threshold => Some(NavAction::Right),
+            true if x <= -threshold => Some(NavAction::Left),
+            false if y >= threshold => Some(NavAction::Up),
+            false if y <= -threshold => Some(NavAction::Down),
+            _ => None,
+        };
+        if direction == self.stick {
+            return vec![];
+        }
+
+        if let Some(old) = self.stick {
+            self.repeat.release(old);
+        }
+        self.stick = direction;
+
+        return direction
+            .map(|direction| self.repeat.press(direction))
+            .into_iter()
+            .collect();
+    }
+
+    /// Advance the time by `dt` seconds, returns the repeats of the held direction
+    pub fn update(&mut self, dt: f32) -> Vec<Event> {
+        return self.repeat.update(dt);
+    }
+}
+
+//-----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(key: Key) -> Event {
+        return Event::KeyDown {
+            key,
+            modifiers: Modifiers::NONE,
+        };
+    }
+
+    #[test]
+    fn test_repeat() {
+        let mut gamepad = GamepadInput::new();
+
+        assert_eq!(
+            gamepad.button(GamepadButton::South, true),
+            [key(Key::Enter)]
+        );
+        assert_eq!(
+            gamepad.button(GamepadButton::DPadDown, true),
+            [key(Key::Down)]
+        );
+
+        // Repeats after the delay
+        assert!(gamepad.update(REPEAT_DELAY * 0.5).is_empty());
+        assert_eq!(gamepad.update(REPEAT_DELAY * 0.5), [key(Key::Down)]);
+        assert_eq!(gamepad.update(REPEAT_INTERVAL * 2.5), [key(Key::Down)]);
+
+        // Long frames repeat only once
+        assert_eq!(gamepad.update(5.0), [key(Key::Down)]);
+        assert_eq!(gamepad.update(f32::INFINITY), [key(Key::Down)]);
+        assert!(gamepad.update(REPEAT_INTERVAL * 0.5).is_empty());
+        assert_eq!(gamepad.update(REPEAT_INTERVAL * 0.5), [key(Key::Down)]);
+
+        // Accept doesn't repeat and doesn't stop the direction
+        gamepad.button(GamepadButton::South, false);
+        assert_eq!(gamepad.update(REPEAT_INTERVAL), [key(Key::Down)]);
+
+        gamepad.button(GamepadButton::DPadDown, false);
+        assert!(gamepad.update(1.0).is_empty());
+    }
+
+    #[test]
+    fn test_stick() {
+        let mut gamepad = GamepadInput::new();
+
+        assert!(gamepad.left_stick(0.4, 0.1).is_empty());
+        assert_eq!(gamepad.left_stick(0.6, 0.1), [key(Key::Right)]);
+
+        // Hysteresis: stays pressed until the stick is almost back
+        assert!(gamepad.left_stick(0.4, 0.1).is_empty());
+        assert_eq!(gamepad.update(REPEAT_DELAY), [key(Key::Right)]);
+        assert!(gamepad.left_stick(0.2, 0.1).is_empty());
+        assert!(gamepad.update(1.0).is_empty());
+
+        // Switching the direction presses the new one right away
+        gamepad.left_stick(-0.8, 0.0);
+        assert_eq!(gamepad.left_stick(0.0, -0.9), [key(Key::Down)]);
+    }
+}
+
+//-----------------------------------------------------------------------------
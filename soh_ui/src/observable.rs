@@ -0,0 +1,189 @@
+//-----------------------------------------------------------------------------
+//! Observable state
+//!
+//! An [`Observable`] is a shared value which notifies its subscribers when it changes. Widgets are
+//! bound to observables with [`crate::Ui::bind`], so they follow the state without manual
+//! synchronization.
+//-----------------------------------------------------------------------------
+use std::cell::{Cell, Ref, RefCell};
+use std::rc::{Rc, Weak};
+//-----------------------------------------------------------------------------
+
+/// Shared value (cloning creates another handle to the same value)
+pub struct Observable<T> {
+    inner: Rc<Inner<T>>,
+}
+
+/// Keeps a subscriber registered (unsubscribes on drop)
+#[must_use = "The subscriber is removed when the subscription is dropped"]
+pub struct Subscription {
+    unsubscribe: Option<Box<dyn FnOnce()>>,
+}
+
+struct Inner<T> {
+    value: RefCell<T>,
+    /// Incremented on every change
+    version: Cell<u64>,
+
+    subscribers: RefCell<Vec<(u64, Subscriber<T>)>>,
+    next_subscriber_id: Cell<u64>,
+
+    /// Subscriptions to the sources of a derived observable
+    sources: RefCell<Vec<Subscription>>,
+}
+
+type Subscriber<T> = Rc<RefCell<dyn FnMut(&T)>>;
+
+//-----------------------------------------------------------------------------
+// Constructor
+impl<T: 'static> Observable<T> {
+    pub fn new(value: T) -> Self {
+        return Observable {
+            inner: Rc::new(Inner {
+                value: RefCell::new(value),
+                version: Cell::new(0),
+                subscribers: RefCell::new(vec![]),
+                next_subscriber_id: Cell::new(0),
+                sources: RefCell::new(vec![]),
+            }),
+        };
+    }
+}
+
+impl<T> Clone for Observable<T> {
+    fn clone(&self) -> Self {
+        return Observable {
+            inner: self.inner.clone(),
+        };
+    }
+}
+
+impl<T: Default + 'static> Default for Observable<T> {
+    fn default() -> Self {
+        return Observable::new(T::default());
+    }
+}
+
+//-----------------------------------------------------------------------------
+// Getters
+impl<T: 'static> Observable<T> {
+    pub fn get(&self) -> T
+    where
+        T: Clone,
+    {
+        return self.inner.value.borrow().clone();
+    }
+
+    /// Borrow the value (it can't be changed while the borrow is alive)
+    pub fn borrow(&self) -> Ref<'_, T> {
+        return self.inner.value.borrow();
+    }
+
+    /// Number of changes so far (used to detect changes without subscribing)
+    pub fn version(&self) -> u64 {
+        return self.inner.version.get();
+    }
+}
+
+//-----------------------------------------------------------------------------
+// Specific implementation
+impl<T: 'static> Observable<T> {
+    /// Replace the value and notify the subscribers
+    pub fn set(&self, value: T) {
+        *self.inner.value.borrow_mut() = value;
+        self.changed();
+    }
+
+    /// Replace the value only if it differs from the current one
+    pub fn set_if_changed(&self, value: T)
+    where
+        T: PartialEq,
+    {
+        if *self.inner.value.borrow() != value {
+            self.set(value);
+        }
+    }
+
+    /// Modify the value in place and notify the subscribers
+    pub fn update(&self, f: impl FnOnce(&mut T)) {
+        f(&mut self.inner.value.borrow_mut());
+        self.changed();
+    }
+
+    /// Call `f` with the new value after every change
+    ///
+    /// The subscriber must not change the observable it's subscribed to.
+    pub fn subscribe(&self, f: impl FnMut(&T) + 'static) -> Subscription {
+        let id = self.inner.next_subscriber_id.get();
+        self.inner.next_subscriber_id.set(id + 1);
+
+        let subscriber: Subscriber<T> = Rc::new(RefCell::new(f));
+        self.inner.subscribers.borrow_mut().push((id, subscriber));
+
+        let inner: Weak<Inner<T>> = Rc::downgrade(&self.inner);
+        return Subscription {
+            unsubscribe: Some(Box::new(move || {
+                if let Some(inner) = inner.upgrade() {
+                    inner
+                        .subscribers
+                        .borrow_mut()
+                        .retain(|(other, _)| *other != id);
+                }
+            })),
+        };
+    }
+
+    /// Observable which is recomputed from this one after every change
+    pub fn map<U: 'static>(&self, f: impl Fn(&T) -> U + 'static) -> Observable<U> {
+        let derived = Observable::new(f(&self.borrow()));
+
+        let target = Rc::downgrade(&derived.inner);
+        let subscription = self.subscribe(move |value| {
+            if let Some(inner) = target.upgrade() {
+                Observable { inner }.set(f(value));
+            }
+        });
+        derived.inner.sources.borrow_mut().push(subscription);
+
+        return derived;
+    }
+
+    fn changed(&self) {
+        self.inner.version.set(self.inner.version.get() + 1);
+
+        /*
+         * Subscribers may subscribe or unsubscribe, so iterate over a copy
+         */
+        let subscribers = self
+            .inner
+            .subscribers
+            .borrow()
+            .iter()
+            .map(|(_, subscriber)| subscriber.clone())
+            .collect::<Vec<_>>();
+
+        let value = self.inner.value.borrow();
+        for subscriber in subscribers {
+            (subscriber.borrow_mut())(&value);
+        }
+    }
+}
+
+//-----------------------------------------------------------------------------
+
+impl Subscription {
+    /// Keep the subscriber registered for as long as the observable lives
+    pub fn detach(mut self) {
+        self.unsubscribe = None;
+    }
+}
+
+impl Drop for Subscription {
+    fn drop(&mut self) {
+        if let Some(unsubscribe) = self.unsubscribe.take() {
+            unsubscribe();
+        }
+    }
+}
+
+//-----------------------------------------------------------------------------
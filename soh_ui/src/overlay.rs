@@ -0,0 +1,103 @@
+//-----------------------------------------------------------------------------
+//! Modal dialogs, popups and tooltips
+//!
+//! Overlays are widget trees drawn on top of the main tree, each in its own layer ( the last
+//! opened layer is on top ). [`crate::Ui::open_modal`] and [`crate::Ui::open_popup`] create the
+//! root widget of a layer, its content is added to it as usual.
+//!
+//! Input routing:
+//! * Pointer events go to the topmost layer under the pointer. A modal consumes the pointer
+//!   events outside of it, so nothing below it reacts.
+//! * Pressing the pointer outside of the popups on top closes them, the press is consumed.
+//! * Keyboard events go to the focused widget. Opening a layer focuses its first focusable
+//!   widget, closing it gives the focus back. Tab and Shift+Tab stay inside the topmost modal or
+//!   popup.
+//! * Escape which isn't handled by the focused widget closes the topmost modal or popup.
+//! * Tooltips never receive events, any event other than a pointer move hides them until the
+//!   pointer leaves the widget.
+//-----------------------------------------------------------------------------
+use crate::{Rect, WidgetId};
+use soh_math::Vec2;
+//-----------------------------------------------------------------------------
+
+/// Seconds the pointer should stay over a widget before its tooltip is shown
+pub const TOOLTIP_DELAY: f32 = 0.5;
+
+/// Distance of the tooltip below the pointer
+pub const TOOLTIP_OFFSET: f32 = 20.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LayerKind {
+    /// Blocks the input to everything below it
+    Modal,
+    /// Closed by a press outside of it (menus, dropdowns)
+    Popup,
+    /// Shown after hovering a widget, doesn't receive input
+    Tooltip,
+}
+
+/// Where a layer is placed in the viewport (it's moved to stay inside of the viewport)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Placement {
+    Center,
+    /// Below the widget, or above it if there is no space below
+    Below(WidgetId),
+    /// Top left corner at the position, or bottom left if there is no space below
+    At(Vec2<f32>),
+}
+
+/// Overlay opened in the [`crate::Ui`]
+pub(crate) struct Layer {
+    pub root: WidgetId,
+    pub kind: LayerKind,
+    pub placement: Placement,
+    /// Focused widget before the layer was opened
+    pub restore_focus: Option<WidgetId>,
+    /// The first focusable widget is focused during the next layout (once the content is added)
+    pub focus_pending: bool,
+}
+
+/// Hover timer of the tooltips
+#[derive(Debug, Default)]
+pub(crate) struct TooltipState {
+    pub elapsed: f32,
+    /// Root of the tooltip layer while it's shown
+    pub layer: Option<WidgetId>,
+    /// Hidden by an event until the pointer leaves the widget
+    pub suppressed: bool,
+}
+
+//-----------------------------------------------------------------------------
+
+/// Rect of a layer of `size` next to `anchor` ( below if it fits, above otherwise ), moved
+/// inside of the viewport
+pub(crate) fn place_next_to(anchor: Rect, size: Vec2<f32>, viewport: Rect) -> Rect {
+    let below = anchor.max.y + size.y <= viewport.max.y;
+    let above = anchor.min.y - size.y >= viewport.min.y;
+
+    let y = match below || !above {
+        true => anchor.max.y,
+        false => anchor.min.y - size.y,
+    };
+    let pos = Vec2::new(anchor.min.x, y);
+
+    return Rect::from_pos_size(clamp_pos(pos, size, viewport), size);
+}
+
+/// Rect of a layer of `size` in the center of the viewport
+pub(crate) fn place_center(size: Vec2<f32>, viewport: Rect) -> Rect {
+    let pos = viewport.center() - size / 2.0;
+    return Rect::from_pos_size(clamp_pos(pos, size, viewport), size);
+}
+
+/// Move the rect inside of the viewport (the top left corner stays visible if it's too big)
+fn clamp_pos(pos: Vec2<f32>, size: Vec2<f32>, viewport: Rect) -> Vec2<f32> {
+    let max = viewport.max - size;
+
+    return Vec2::new(
+        pos.x.min(max.x).max(viewport.min.x),
+        pos.y.min(max.y).max(viewport.min.y),
+    );
+}
+
+//-----------------------------------------------------------------------------
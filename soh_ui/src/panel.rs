@@ -0,0 +1,291 @@
+//-----------------------------------------------------------------------------
+//! Styled panels: rounded corners, borders, drop shadows and 9-slice textures
+//!
+//! Rounded rects, borders and shadows are single draw commands which the renderer shades with
+//! signed distance functions. The coverage functions of this module are the reference for the
+//! shaders ( the distance is [`soh_math::sdf::rounded_rect`] ):
+//!
+//! ```glsl
+//! float rounded_rect(vec2 p, vec2 half_size, float radius) {
+//!     radius = min(radius, min(half_size.x, half_size.y));
+//!     vec2 d = abs(p) - half_size + radius;
+//!     return length(max(d, 0.0)) + min(max(d.x, d.y), 0.0) - radius;
+//! }
+//!
+//! // p is relative to the center of the rect, pixel_size is 1.0 without scaling
+//! float fill_alpha(float d)                { return clamp(0.5 - d / pixel_size, 0.0, 1.0); }
+//! float border_alpha(float d, float width) { return fill_alpha(abs(d + width * 0.5) - width * 0.5); }
+//! float shadow_alpha(float d, float blur)  { return 1.0 - smoothstep(-blur, blur, d); }
+//! ```
+//-----------------------------------------------------------------------------
+use crate::draw::{Color, DrawList, TextureHandle};
+use crate::layout::Edges;
+use crate::theme::Theme;
+use crate::Rect;
+use soh_math::sdf;
+use soh_math::Vec2;
+//-----------------------------------------------------------------------------
+
+/// Drop shadow cast by a panel
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Shadow {
+    pub offset: Vec2<f32>,
+    /// Distance over which the shadow fades out
+    pub blur: f32,
+    /// Grow the shadow beyond the panel
+    pub spread: f32,
+    pub color: Color,
+}
+
+/// Look of a panel
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PanelStyle {
+    pub background: Color,
+    pub radius: f32,
+    pub border_width: f32,
+    pub border: Color,
+    pub shadow: Option<Shadow>,
+}
+
+/// Texture whose corners keep their size while the edges and the center stretch ( frames,
+/// buttons drawn by an artist )
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct NineSlice {
+    pub texture: TextureHandle,
+    /// Size of the texture in pixels
+    pub texture_size: (u32, u32),
+    /// Size of the corners in texture pixels
+    pub insets: Edges,
+    /// Size of a texture pixel on the screen
+    pub scale: f32,
+}
+
+//-----------------------------------------------------------------------------
+
+impl PanelStyle {
+    /// Panel look of the theme
+    pub fn from_theme(theme: &Theme) -> Self {
+        return PanelStyle {
+            background: theme.background,
+            radius: theme.corner_radius,
+            border_width: theme.border_width,
+            border: theme.border,
+            shadow: theme.shadow,
+        };
+    }
+
+    pub fn background(mut self, color: impl Into<Color>) -> Self {
+        self.background = color.into();
+        return self;
+    }
+}
+
+impl NineSlice {
+    pub fn new(texture: TextureHandle, texture_size: (u32, u32), insets: Edges) -> Self {
+        return NineSlice {
+            texture,
+            texture_size,
+            insets,
+            scale: 1.0,
+        };
+    }
+}
+
+//-----------------------------------------------------------------------------
+
+impl DrawList {
+    /// Shadow, background and border of a panel
+    pub fn panel(&mut self, rect: Rect, style: &PanelStyle) {
+        if let Some(shadow) = &style.shadow {
+            let spread = Vec2::splat(shadow.spread);
+            self.shadow(
+                rect.translate(shadow.offset).inflate(spread),
+                style.radius + shadow.spread,
+                shadow.blur,
+                shadow.color,
+            );
+        }
+
+        self.rounded_rect(rect, style.radius, style.background);
+
+        if style.border_width > 0.0 {
+            self.border(rect, style.radius, style.border_width, style.border);
+        }
+    }
+
+    /// Draw the texture stretched to the rect without stretching its corners
+    pub fn nine_slice(&mut self, rect: Rect, slice: &NineSlice, tint: impl Into<Color>) {
+        let tint = tint.into();
+        let texture_size = Vec2::new(slice.texture_size.0 as f32, slice.texture_size.1 as f32);
+        if texture_size.x <= 0.0 || texture_size.y <= 0.0 {
+            return;
+        }
+
+        /*
+         * Corners shrink if the rect is smaller than them
+         */
+        let insets = slice.insets;
+        let fit = |start: f32, end: f32, length: f32| {
+            let scale = (length / ((start + end) * slice.scale)).min(1.0) * slice.scale;
+            return (start * scale, end * scale);
+        };
+        let (left, right) = fit(insets.left, insets.right, rect.width());
+        let (top, bottom) = fit(insets.top, insets.bottom, rect.height());
+
+        let xs = [
+            rect.min.x,
+            rect.min.x + left,
+            rect.max.x - right,
+            rect.max.x,
+        ];
+        let ys = [
+            rect.min.y,
+            rect.min.y + top,
+            rect.max.y - bottom,
+            rect.max.y,
+        ];
+        let us = [
+            0.0,
+            insets.left / texture_size.x,
+            1.0 - insets.right / texture_size.x,
+            1.0,
+        ];
+        let vs = [
+            0.0,
+            insets.top / texture_size.y,
+            1.0 - insets.bottom / texture_size.y,
+            1.0,
+        ];
+
+        for row in 0..3 {
+            for column in 0..3 {
+                let part = Rect::new(
+                    Vec2::new(xs[column], ys[row]),
+                    Vec2::new(xs[column + 1], ys[row + 1]),
+                );
+                if part.is_empty() {
+                    continue;
+                }
+
+                let uv = Rect::new(
+                    Vec2::new(us[column], vs[row]),
+                    Vec2::new(us[column + 1], vs[row + 1]),
+                );
+                self.image(part, slice.texture, uv, tint);
+            }
+        }
+    }
+}
+
+//-----------------------------------------------------------------------------
+// Reference shading
+
+/// Signed distance from the edge of the rounded rect ( negative inside )
+pub fn rounded_rect_distance(rect: Rect, radius: f32, p: Vec2<f32>) -> f32 {
+    return sdf::rounded_rect(p - rect.center(), rect.size() / 2.0, radius);
+}
+
+/// Opacity of a rounded rect at the point
+pub fn fill_alpha(rect: Rect, radius: f32, p: Vec2<f32>) -> f32 {
+    return sdf::coverage(rounded_rect_distance(rect, radius, p), 1.0);
+}
+
+/// Opacity of a border drawn inside of the rounded rect at the point
+pub fn border_alpha(rect: Rect, radius: f32, width: f32, p: Vec2<f32>) -> f32 {
+    let d = rounded_rect_distance(rect, radius, p);
+    return sdf::coverage(sdf::annular(d + width * 0.5, width * 0.5), 1.0);
+}
+
+/// Opacity of the shadow of a rounded rect at the point
+pub fn shadow_alpha(rect: Rect, radius: f32, blur: f32, p: Vec2<f32>) -> f32 {
+    let d = rounded_rect_distance(rect, radius, p);
+    if blur <= 0.0 {
+        return sdf::coverage(d, 1.0);
+    }
+
+    let t = ((d + blur) / (2.0 * blur)).clamp(0.0, 1.0);
+    return 1.0 - t * t * (3.0 - 2.0 * t);
+}
+
+//-----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::draw::DrawCmd;
+    use ash::vk::{self, Handle};
+
+    #[test]
+    fn test_panel() {
+        let theme = Theme::dark();
+        let shadow = theme.shadow.unwrap();
+        let rect = Rect::new(Vec2::new(10.0, 10.0), Vec2::new(110.0, 60.0));
+
+        let mut list = DrawList::new();
+        list.panel(rect, &PanelStyle::from_theme(&theme));
+        match list.commands() {
+            [DrawCmd::Shadow {
+                rect: shadow_rect, ..
+            }, DrawCmd::Rect { .. }, DrawCmd::Border { width, .. }] => {
+                assert_eq!(*shadow_rect, rect.translate(shadow.offset));
+                assert_eq!(*width, theme.border_width);
+            }
+            cmds => panic!("Unexpected commands: {cmds:?}"),
+        }
+
+        // Antialiased edges, the corners are cut off
+        assert_eq!(fill_alpha(rect, 10.0, Vec2::new(60.0, 10.0)), 0.5);
+        assert_eq!(fill_alpha(rect, 10.0, Vec2::new(10.5, 10.5)), 0.0);
+        assert_eq!(border_alpha(rect, 10.0, 2.0, Vec2::new(60.0, 11.0)), 1.0);
+        assert_eq!(border_alpha(rect, 10.0, 2.0, Vec2::new(60.0, 35.0)), 0.0);
+        assert_eq!(shadow_alpha(rect, 0.0, 4.0, Vec2::new(60.0, 10.0)), 0.5);
+        assert_eq!(shadow_alpha(rect, 0.0, 4.0, Vec2::new(60.0, 5.0)), 0.0);
+    }
+
+    #[test]
+    fn test_nine_slice() {
+        let texture = TextureHandle::new(vk::ImageView::from_raw(1), vk::Sampler::from_raw(2));
+        let slice = NineSlice::new(texture, (32, 32), Edges::all(8.0));
+        let images = |rect: Rect| {
+            let mut list = DrawList::new();
+            list.nine_slice(rect, &slice, Color::opaque(soh_math::color::WHITE));
+
+            return list
+                .commands()
+                .iter()
+                .map(|cmd| match cmd {
+                    DrawCmd::Image { rect, uv, .. } => (*rect, *uv),
+                    _ => panic!("Unexpected command: {cmd:?}"),
+                })
+                .collect::<Vec<_>>();
+        };
+
+        // The corners keep their size, the center stretches
+        let parts = images(Rect::new(Vec2::new(0.0, 0.0), Vec2::new(100.0, 50.0)));
+        assert_eq!(parts.len(), 9);
+        assert_eq!(
+            parts[0],
+            (
+                Rect::new(Vec2::new(0.0, 0.0), Vec2::new(8.0, 8.0)),
+                Rect::new(Vec2::new(0.0, 0.0), Vec2::new(0.25, 0.25))
+            )
+        );
+        assert_eq!(
+            parts[4],
+            (
+                Rect::new(Vec2::new(8.0, 8.0), Vec2::new(92.0, 42.0)),
+                Rect::new(Vec2::new(0.25, 0.25), Vec2::new(0.75, 0.75))
+            )
+        );
+
+        // Too small for the corners: they shrink and the middle parts disappear
+        let parts = images(Rect::new(Vec2::new(0.0, 0.0), Vec2::new(8.0, 8.0)));
+        assert_eq!(parts.len(), 4);
+        assert_eq!(
+            parts[3].0,
+            Rect::new(Vec2::new(4.0, 4.0), Vec2::new(8.0, 8.0))
+        );
+    }
+}
+
+//-----------------------------------------------------------------------------
@@ -0,0 +1,39 @@
+//-----------------------------------------------------------------------------
+use crate::layout::Edges;
+use soh_math::Vec2;
+//-----------------------------------------------------------------------------
+
+/// Axis aligned rectangle in UI coordinates (y axis points down)
+pub type Rect = soh_math::Rect<f32>;
+
+/// Operations on rects with the UI edges
+pub trait RectExt {
+    /// Move the edges inwards (the size never becomes negative)
+    fn shrink(&self, edges: Edges) -> Self;
+
+    /// Move the edges outwards
+    fn expand(&self, edges: Edges) -> Self;
+}
+
+//-----------------------------------------------------------------------------
+
+impl RectExt for Rect {
+    fn shrink(&self, edges: Edges) -> Self {
+        let min = Vec2::new(self.min.x + edges.left, self.min.y + edges.top);
+        let max = Vec2::new(self.max.x - edges.right, self.max.y - edges.bottom);
+
+        return Rect {
+            min,
+            max: Vec2::new(max.x.max(min.x), max.y.max(min.y)),
+        };
+    }
+
+    fn expand(&self, edges: Edges) -> Self {
+        return Rect {
+            min: Vec2::new(self.min.x - edges.left, self.min.y - edges.top),
+            max: Vec2::new(self.max.x + edges.right, self.max.y + edges.bottom),
+        };
+    }
+}
+
+//-----------------------------------------------------------------------------
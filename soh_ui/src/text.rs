@@ -0,0 +1,49 @@
+//-----------------------------------------------------------------------------
+//! Text measurement (provided by the renderer which knows the fonts)
+//-----------------------------------------------------------------------------
+use soh_math::Vec2;
+//-----------------------------------------------------------------------------
+
+pub trait TextMeasure {
+    /// Width of a single line of text in pixels
+    fn text_width(&self, text: &str, size: f32) -> f32;
+
+    /// Height of a line of text in pixels
+    fn line_height(&self, size: f32) -> f32;
+
+    fn text_size(&self, text: &str, size: f32) -> Vec2<f32> {
+        return Vec2::new(self.text_width(text, size), self.line_height(size));
+    }
+}
+
+/// Approximate metrics of a monospace font (used until the renderer provides real ones)
+#[derive(Debug, Clone, Copy)]
+pub struct MonospaceMetrics {
+    /// Advance of a character relative to the font size
+    pub advance: f32,
+    /// Line height relative to the font size
+    pub line_height: f32,
+}
+
+//-----------------------------------------------------------------------------
+
+impl Default for MonospaceMetrics {
+    fn default() -> Self {
+        return MonospaceMetrics {
+            advance: 0.5,
+            line_height: 1.25,
+        };
+    }
+}
+
+impl TextMeasure for MonospaceMetrics {
+    fn text_width(&self, text: &str, size: f32) -> f32 {
+        return text.chars().count() as f32 * self.advance * size;
+    }
+
+    fn line_height(&self, size: f32) -> f32 {
+        return self.line_height * size;
+    }
+}
+
+//-----------------------------------------------------------------------------
@@ -0,0 +1,106 @@
+//-----------------------------------------------------------------------------
+//! Colors and sizes shared by the widgets
+//!
+//! A theme applies to a widget and all of its children until a child sets another one (see
+//! [`crate::Ui::set_theme`]). Widgets can still override single properties (e.g. the color of a
+//! label).
+//-----------------------------------------------------------------------------
+use crate::draw::Color;
+use crate::panel::Shadow;
+use soh_math::color::{Rgb, BLACK};
+use soh_math::Vec2;
+//-----------------------------------------------------------------------------
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Theme {
+    /*
+     * Colors
+     */
+    /// Background of windows and panels
+    pub background: Color,
+    /// Background of widgets placed on top of panels (text inputs, buttons)
+    pub surface: Color,
+    pub text: Color,
+    /// Placeholders and disabled text
+    pub text_muted: Color,
+    /// Focused and active elements
+    pub accent: Color,
+    pub border: Color,
+    pub selection: Color,
+    /// Dims the UI behind modal dialogs
+    pub backdrop: Color,
+
+    /*
+     * Sizes
+     */
+    pub font_size: f32,
+    /// Space between the border of a widget and its content
+    pub padding: f32,
+    pub border_width: f32,
+    pub corner_radius: f32,
+
+    /*
+     * Effects
+     */
+    /// Drop shadow of the panels
+    pub shadow: Option<Shadow>,
+}
+
+//-----------------------------------------------------------------------------
+
+impl Theme {
+    pub fn dark() -> Self {
+        return Theme {
+            background: Color::opaque(Rgb::new(24, 24, 27)),
+            surface: Color::opaque(Rgb::new(39, 39, 44)),
+            text: Color::opaque(Rgb::new(230, 230, 232)),
+            text_muted: Color::opaque(Rgb::new(128, 128, 136)),
+            accent: Color::opaque(Rgb::new(70, 130, 220)),
+            border: Color::opaque(Rgb::new(72, 72, 80)),
+            selection: Color::new(Rgb::new(70, 130, 220), 0.45),
+            backdrop: Color::new(BLACK, 0.5),
+
+            font_size: 16.0,
+            padding: 4.0,
+            border_width: 1.0,
+            corner_radius: 3.0,
+
+            shadow: Some(Shadow {
+                offset: Vec2::new(0.0, 2.0),
+                blur: 6.0,
+                spread: 0.0,
+                color: Color::new(BLACK, 0.35),
+            }),
+        };
+    }
+
+    pub fn light() -> Self {
+        return Theme {
+            background: Color::opaque(Rgb::new(242, 242, 245)),
+            surface: Color::opaque(Rgb::new(255, 255, 255)),
+            text: Color::opaque(Rgb::new(28, 28, 32)),
+            text_muted: Color::opaque(Rgb::new(140, 140, 148)),
+            accent: Color::opaque(Rgb::new(38, 110, 210)),
+            border: Color::opaque(Rgb::new(196, 196, 204)),
+            selection: Color::new(Rgb::new(38, 110, 210), 0.3),
+            backdrop: Color::new(BLACK, 0.3),
+
+            shadow: Some(Shadow {
+                offset: Vec2::new(0.0, 1.0),
+                blur: 4.0,
+                spread: 0.0,
+                color: Color::new(BLACK, 0.15),
+            }),
+
+            ..Theme::dark()
+        };
+    }
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        return Theme::dark();
+    }
+}
+
+//-----------------------------------------------------------------------------
@@ -0,0 +1,1589 @@
+//-----------------------------------------------------------------------------
+use crate::animation::{Transition, Tween};
+use crate::clipboard::{Clipboard, LocalClipboard};
+use crate::drag::{ActiveDrag, DragData};
+use crate::draw::DrawList;
+use crate::input::{Event, Key, Modifiers, PointerButton};
+use crate::layout::{Display, Edges, LayoutTree, Overflow, Position, Style};
+use crate::observable::Observable;
+use crate::overlay::{
+    place_center, place_next_to, Layer, LayerKind, Placement, TooltipState, TOOLTIP_DELAY,
+    TOOLTIP_OFFSET,
+};
+use crate::text::{MonospaceMetrics, TextMeasure};
+use crate::theme::Theme;
+use crate::widget::{
+    Container, DrawCtx, EventCtx, FocusRequest, Label, MeasureCtx, Menu, Response, ScrollArea,
+    Widget,
+};
+use crate::Rect;
+use soh_math::Vec2;
+use std::any::Any;
+use std::rc::Rc;
+//-----------------------------------------------------------------------------
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct WidgetId(usize);
+
+/// Tree of widgets
+///
+/// Owns the widgets, lays them out, routes the input events to them and collects their draw
+/// commands.
+///
+/// The layout and the events are in logical pixels, the draw commands in physical pixels (see
+/// [`Ui::set_scale_factor`]).
+pub struct Ui {
+    nodes: Vec<Option<Node>>,
+    free: Vec<usize>,
+
+    focused: Option<WidgetId>,
+    /// The focus was moved with the keyboard (the focused widget is highlighted)
+    focus_visible: bool,
+    /// The arrow keys move the focus in the whole UI, not only in the focus groups
+    spatial_navigation: bool,
+    hovered: Option<WidgetId>,
+    captured: Option<WidgetId>,
+    drag: Option<ActiveDrag>,
+    /// Overlays from the bottom to the top
+    layers: Vec<Layer>,
+    tooltip: TooltipState,
+    /// Last position of the pointer
+    pointer: Vec2<f32>,
+    /// Physical pixels per logical pixel
+    scale_factor: f32,
+
+    text: Box<dyn TextMeasure>,
+    clipboard: Box<dyn Clipboard>,
+    /// Theme of the widgets which don't have a theme set on themselves or their parents
+    theme: Rc<Theme>,
+
+    bindings: Vec<(WidgetId, Binding)>,
+}
+
+/// Applies the value of an observable to a widget if the value changed
+type Binding = Box<dyn FnMut(&mut Ui)>;
+
+/// Creates the context menu of a widget each time it's opened
+type MenuFactory = Rc<dyn Fn() -> Menu>;
+
+struct Node {
+    widget: Box<dyn Widget>,
+    style: Style,
+    theme: Option<Rc<Theme>>,
+    parent: Option<WidgetId>,
+    children: Vec<WidgetId>,
+    rect: Rect,
+    animation: Option<Box<Animation>>,
+    tooltip: Option<String>,
+    context_menu: Option<MenuFactory>,
+}
+
+/// Animated properties of a widget with a transition
+struct Animation {
+    transition: Transition,
+    /// None until the widget is laid out while visible (it appears without moving)
+    rect: Option<Tween<Rect>>,
+    opacity: Tween<f32>,
+    /// 1.0 when hovered
+    hover: Tween<f32>,
+    /// Fading out, the widget is hidden at the end
+    hiding: bool,
+}
+
+//-----------------------------------------------------------------------------
+// Constructor
+impl Ui {
+    /// Create a UI with an empty container as the root
+    pub fn new(root_style: Style) -> Self {
+        return Ui {
+            nodes: vec![Some(Node::new(
+                Box::new(Container::new()),
+                root_style,
+                None,
+            ))],
+            free: vec![],
+
+            focused: None,
+            focus_visible: false,
+            spatial_navigation: false,
+            hovered: None,
+            captured: None,
+            drag: None,
+            layers: vec![],
+            tooltip: TooltipState::default(),
+            pointer: Vec2::new(0.0, 0.0),
+            scale_factor: 1.0,
+
+            text: Box::new(MonospaceMetrics::default()),
+            clipboard: Box::new(LocalClipboard::default()),
+            theme: Rc::new(Theme::default()),
+
+            bindings: vec![],
+        };
+    }
+
+    pub fn with_theme(mut self, theme: Theme) -> Self {
+        self.theme = Rc::new(theme);
+        return self;
+    }
+
+    /// Use the font metrics of the renderer
+    pub fn with_text_measure(mut self, text: impl TextMeasure + 'static) -> Self {
+        self.text = Box::new(text);
+        return self;
+    }
+
+    /// Let the arrow keys move the focus in the whole UI (see [`Ui::set_spatial_navigation`])
+    pub fn with_spatial_navigation(mut self, enabled: bool) -> Self {
+        self.spatial_navigation = enabled;
+        return self;
+    }
+
+    /// Start with the scale factor of the window (see [`Ui::set_scale_factor`])
+    pub fn with_scale_factor(mut self, scale_factor: f32) -> Self {
+        self.scale_factor = scale_factor;
+        return self;
+    }
+
+    /// Use another clipboard (e.g. the system clipboard)
+    pub fn with_clipboard(mut self, clipboard: impl Clipboard + 'static) -> Self {
+        self.clipboard = Box::new(clipboard);
+        return self;
+    }
+}
+
+//-----------------------------------------------------------------------------
+// Getters
+impl Ui {
+    pub fn root(&self) -> WidgetId {
+        return WidgetId(0);
+    }
+    pub fn focused(&self) -> Option<WidgetId> {
+        return self.focused;
+    }
+    /// Whether the focused widget is highlighted (the focus was moved with the keyboard)
+    pub fn focus_visible(&self) -> bool {
+        return self.focus_visible;
+    }
+    pub fn hovered(&self) -> Option<WidgetId> {
+        return self.hovered;
+    }
+    /// Physical pixels per logical pixel
+    pub fn scale_factor(&self) -> f32 {
+        return self.scale_factor;
+    }
+    pub fn rect(&self, id: WidgetId) -> Rect {
+        return self.node(id).rect;
+    }
+    pub fn style(&self, id: WidgetId) -> &Style {
+        return &self.node(id).style;
+    }
+    pub fn style_mut(&mut self, id: WidgetId) -> &mut Style {
+        return &mut self.node_mut(id).style;
+    }
+    pub fn parent(&self, id: WidgetId) -> Option<WidgetId> {
+        return self.node(id).parent;
+    }
+    pub fn children(&self, id: WidgetId) -> &[WidgetId] {
+        return &self.node(id).children;
+    }
+    /// Theme used by the widget (set on itself or on the closest parent)
+    pub fn theme(&self, id: WidgetId) -> &Theme {
+        return self.theme_rc(id);
+    }
+    /// Rect where the widget is drawn (differs from [`Ui::rect`] during a transition)
+    pub fn animated_rect(&self, id: WidgetId) -> Rect {
+        let node = self.node(id);
+        return match &node.animation {
+            Some(animation) => animation.rect(node.rect),
+            None => node.rect,
+        };
+    }
+    pub fn is_visible(&self, id: WidgetId) -> bool {
+        return self.node(id).style.display != Display::None;
+    }
+    pub fn contains(&self, id: WidgetId) -> bool {
+        return self.nodes.get(id.0).is_some_and(Option::is_some);
+    }
+    pub fn is_dragging(&self) -> bool {
+        return self.drag.is_some();
+    }
+    /// Payload of the drag in progress
+    pub fn drag_data(&self) -> Option<&DragData> {
+        return self.drag.as_ref().map(|drag| &drag.data);
+    }
+    /// Widget which would receive the drag if it was dropped now
+    pub fn drop_target(&self) -> Option<WidgetId> {
+        return self.drag.as_ref().and_then(|drag| drag.target);
+    }
+    /// Roots of the open overlays from the bottom to the top
+    pub fn overlays(&self) -> impl Iterator<Item = (WidgetId, LayerKind)> + '_ {
+        return self.layers.iter().map(|layer| (layer.root, layer.kind));
+    }
+}
+
+//-----------------------------------------------------------------------------
+// Specific implementation
+impl Ui {
+    /// Add a widget as the last child of `parent`
+    pub fn add(&mut self, parent: WidgetId, widget: impl Widget, style: Style) -> WidgetId {
+        return self.add_boxed(parent, Box::new(widget), style);
+    }
+
+    pub fn add_boxed(
+        &mut self,
+        parent: WidgetId,
+        widget: Box<dyn Widget>,
+        style: Style,
+    ) -> WidgetId {
+        let mut style = style;
+
+        /*
+         * Scroll areas need a layout which doesn't shrink their children
+         */
+        if let Some(area) = (widget.as_ref() as &dyn Any).downcast_ref::<ScrollArea>() {
+            let overflow = |scroll: bool| match scroll {
+                true => Overflow::Scroll,
+                false => Overflow::Visible,
+            };
+            style.overflow_x = overflow(area.horizontal());
+            style.overflow_y = overflow(area.vertical());
+        }
+
+        let id = self.insert_node(Node::new(widget, style, Some(parent)));
+        self.node_mut(parent).children.push(id);
+
+        return id;
+    }
+
+    fn insert_node(&mut self, node: Node) -> WidgetId {
+        return match self.free.pop() {
+            Some(index) => {
+                self.nodes[index] = Some(node);
+                WidgetId(index)
+            }
+            None => {
+                self.nodes.push(Some(node));
+                WidgetId(self.nodes.len() - 1)
+            }
+        };
+    }
+
+    /// Move a widget (with its children) to the end of another parent
+    pub fn reparent(&mut self, id: WidgetId, new_parent: WidgetId) {
+        assert!(id != self.root(), "Can't move the root widget");
+
+        let mut ancestor = Some(new_parent);
+        while let Some(current) = ancestor {
+            assert!(current != id, "Can't move a widget into its own child");
+            ancestor = self.node(current).parent;
+        }
+
+        if let Some(parent) = self.node(id).parent {
+            self.node_mut(parent).children.retain(|&child| child != id);
+        }
+        self.node_mut(new_parent).children.push(id);
+        self.node_mut(id).parent = Some(new_parent);
+    }
+
+    /// Move a widget to the index among its siblings (clamped to the last index)
+    pub fn set_index(&mut self, id: WidgetId, index: usize) {
+        let Some(parent) = self.node(id).parent else {
+            return;
+        };
+
+        let children = &mut self.node_mut(parent).children;
+        children.retain(|&child| child != id);
+        children.insert(index.min(children.len()), id);
+    }
+
+    /// Remove a widget with all of its children (the root can't be removed, removing the root of
+    /// an overlay closes it)
+    pub fn remove(&mut self, id: WidgetId) {
+        assert!(id != self.root(), "Can't remove the root widget");
+
+        if self.layers.iter().any(|layer| layer.root == id) {
+            self.close_overlay(id);
+            return;
+        }
+        self.remove_subtree(id);
+    }
+
+    fn remove_subtree(&mut self, id: WidgetId) {
+        if let Some(parent) = self.node(id).parent {
+            self.node_mut(parent).children.retain(|&child| child != id);
+        }
+
+        let mut stack = vec![id];
+        while let Some(id) = stack.pop() {
+            let node = self.nodes[id.0].take().expect("Widget was already removed");
+            stack.extend(node.children);
+            self.free.push(id.0);
+            self.bindings.retain(|(bound, _)| *bound != id);
+
+            let drop_target = self.drag.as_mut().map(|drag| &mut drag.target);
+            for state in [&mut self.focused, &mut self.hovered, &mut self.captured]
+                .into_iter()
+                .chain(drop_target)
+            {
+                if *state == Some(id) {
+                    *state = None;
+                }
+            }
+        }
+    }
+
+    pub fn widget<T: Widget>(&self, id: WidgetId) -> Option<&T> {
+        let widget: &dyn Any = self.nodes.get(id.0)?.as_ref()?.widget.as_ref();
+        return widget.downcast_ref();
+    }
+
+    /// Widget of any type
+    pub fn widget_dyn(&self, id: WidgetId) -> &dyn Widget {
+        return self.node(id).widget.as_ref();
+    }
+
+    pub fn widget_mut<T: Widget>(&mut self, id: WidgetId) -> Option<&mut T> {
+        let widget: &mut dyn Any = self.nodes.get_mut(id.0)?.as_mut()?.widget.as_mut();
+        return widget.downcast_mut();
+    }
+
+    /// Use a theme for the widget and all of its children (`None` uses the theme of the parent)
+    pub fn set_theme(&mut self, id: WidgetId, theme: Option<Theme>) {
+        self.node_mut(id).theme = theme.map(Rc::new);
+    }
+
+    /// Change the theme of the widgets which don't have their own
+    pub fn set_default_theme(&mut self, theme: Theme) {
+        self.theme = Rc::new(theme);
+    }
+
+    /// Show or hide a widget (hidden widgets take no space)
+    ///
+    /// Widgets with a transition fade in and out.
+    pub fn set_visible(&mut self, id: WidgetId, visible: bool) {
+        let Node {
+            style, animation, ..
+        } = self.node_mut(id);
+        let hidden = style.display == Display::None;
+
+        match (animation.as_deref_mut(), visible) {
+            (Some(animation), true) => {
+                if hidden {
+                    animation.opacity.set_now(0.0);
+                }
+                animation.opacity.set(1.0, animation.transition);
+                animation.hiding = false;
+                style.display = Display::Flex;
+            }
+            // Stays in the layout until it fades out
+            (Some(animation), false) if !hidden => {
+                animation.opacity.set(0.0, animation.transition);
+                animation.hiding = true;
+            }
+            (_, true) => style.display = Display::Flex,
+            (_, false) => style.display = Display::None,
+        }
+    }
+
+    /// Animate the changes of the widget's position, size, visibility and hover state (`None`
+    /// disables the animations)
+    pub fn set_transition(&mut self, id: WidgetId, transition: Option<Transition>) {
+        self.node_mut(id).animation = transition.map(|transition| {
+            Box::new(Animation {
+                transition,
+                rect: None,
+                opacity: Tween::new(1.0),
+                hover: Tween::new(0.0),
+                hiding: false,
+            })
+        });
+    }
+
+    /// Open a modal dialog in the center of the viewport, returns its root widget
+    ///
+    /// Everything below the dialog is dimmed and doesn't receive input until it's closed (see
+    /// [`crate::overlay`] for the input routing).
+    pub fn open_modal(&mut self, widget: impl Widget, style: Style) -> WidgetId {
+        return self.open_layer(LayerKind::Modal, Placement::Center, Box::new(widget), style);
+    }
+
+    /// Open a popup ( menus, dropdowns ), returns its root widget
+    ///
+    /// The popup is closed by pressing the pointer outside of it or by Escape.
+    pub fn open_popup(
+        &mut self,
+        widget: impl Widget,
+        style: Style,
+        placement: Placement,
+    ) -> WidgetId {
+        return self.open_layer(LayerKind::Popup, placement, Box::new(widget), style);
+    }
+
+    /// Close the overlay and all overlays opened after it, the focus goes back to the widget
+    /// which was focused before it was opened
+    pub fn close_overlay(&mut self, root: WidgetId) {
+        let Some(index) = self.layers.iter().position(|layer| layer.root == root) else {
+            return;
+        };
+
+        let closed = self.layers.split_off(index);
+        for layer in closed.iter().rev() {
+            if self.tooltip.layer == Some(layer.root) {
+                self.tooltip.layer = None;
+            }
+            self.remove_subtree(layer.root);
+        }
+
+        let closed = &closed[0];
+        if closed.kind != LayerKind::Tooltip {
+            self.focused = closed.restore_focus.filter(|&id| self.contains(id));
+        }
+    }
+
+    /// Text shown after the pointer rests on the widget (or on one of its children without a
+    /// tooltip) for [`TOOLTIP_DELAY`] seconds
+    pub fn set_tooltip(&mut self, id: WidgetId, tooltip: Option<String>) {
+        self.node_mut(id).tooltip = tooltip;
+    }
+
+    /// Open the menu at the pointer when the widget (or one of its children) is pressed with the
+    /// secondary button and doesn't handle the press
+    ///
+    /// * `menu`: creates the menu each time it's opened
+    pub fn set_context_menu(&mut self, id: WidgetId, menu: impl Fn() -> Menu + 'static) {
+        self.node_mut(id).context_menu = Some(Rc::new(menu));
+    }
+
+    fn open_layer(
+        &mut self,
+        kind: LayerKind,
+        placement: Placement,
+        widget: Box<dyn Widget>,
+        style: Style,
+    ) -> WidgetId {
+        let is_tooltip = kind == LayerKind::Tooltip;
+        if !is_tooltip {
+            self.hide_tooltip();
+        }
+
+        let root = self.insert_node(Node::new(widget, style, None));
+        self.layers.push(Layer {
+            root,
+            kind,
+            placement,
+            restore_focus: self.focused,
+            focus_pending: !is_tooltip,
+        });
+
+        /*
+         * Keyboard events don't reach the widgets below the layer
+         */
+        if !is_tooltip {
+            self.focused = None;
+        }
+
+        return root;
+    }
+
+    /// Close the topmost modal or popup, returns false if there is none
+    fn close_top_overlay(&mut self) -> bool {
+        let top = self
+            .layers
+            .iter()
+            .rev()
+            .find(|layer| layer.kind != LayerKind::Tooltip)
+            .map(|layer| layer.root);
+
+        let Some(root) = top else {
+            return false;
+        };
+        self.close_overlay(root);
+        return true;
+    }
+
+    /// Close the popups on top which don't contain the position, returns whether any was closed
+    fn dismiss_popups(&mut self, pos: Vec2<f32>) -> bool {
+        let mut first_closed = None;
+
+        for layer in self.layers.iter().rev() {
+            match layer.kind {
+                LayerKind::Tooltip => continue,
+                LayerKind::Popup if !self.node(layer.root).rect.contains(pos) => {
+                    first_closed = Some(layer.root);
+                }
+                _ => break,
+            }
+        }
+
+        let Some(root) = first_closed else {
+            return false;
+        };
+        self.close_overlay(root);
+        return true;
+    }
+
+    /// Root of the overlay containing the widget
+    fn overlay_of(&self, id: WidgetId) -> Option<WidgetId> {
+        let mut top = id;
+        while let Some(parent) = self.node(top).parent {
+            top = parent;
+        }
+
+        return self
+            .layers
+            .iter()
+            .any(|layer| layer.root == top)
+            .then_some(top);
+    }
+
+    /// Root of the topmost modal or popup (Tab stays inside of it), or the root of the UI
+    fn focus_scope(&self) -> WidgetId {
+        return self
+            .layers
+            .iter()
+            .rev()
+            .find(|layer| layer.kind != LayerKind::Tooltip)
+            .map_or(self.root(), |layer| layer.root);
+    }
+
+    /// Open the context menu of the target or of its closest parent which has one
+    fn show_context_menu(&mut self, target: Option<WidgetId>, pos: Vec2<f32>) -> Response {
+        let mut current = target;
+
+        while let Some(id) = current {
+            if let Some(menu) = self.node(id).context_menu.clone() {
+                self.open_popup(menu(), Style::default(), Placement::At(pos));
+                return Response::Consumed;
+            }
+            current = self.node(id).parent;
+        }
+
+        return Response::Ignored;
+    }
+
+    /// Show the tooltip of the hovered widget once the pointer rested on it long enough, returns
+    /// true while waiting for it or when it was shown
+    fn update_tooltip(&mut self, dt: f32) -> bool {
+        if self.tooltip.layer.is_some() || self.tooltip.suppressed || self.drag.is_some() {
+            return false;
+        }
+        let Some((owner, text)) = self.tooltip_text() else {
+            return false;
+        };
+
+        self.tooltip.elapsed += dt;
+        if self.tooltip.elapsed < TOOLTIP_DELAY {
+            return true;
+        }
+
+        let theme = self.theme_rc(owner).clone();
+        let style = Style {
+            padding: Edges::all(theme.padding),
+            ..Style::default()
+        };
+        let pos = self.pointer + Vec2::new(0.0, TOOLTIP_OFFSET);
+
+        let root = self.open_layer(
+            LayerKind::Tooltip,
+            Placement::At(pos),
+            Box::new(Container::panel()),
+            style,
+        );
+        self.node_mut(root).theme = Some(theme);
+        self.add(root, Label::new(text), Style::default());
+        self.tooltip.layer = Some(root);
+
+        return true;
+    }
+
+    /// Tooltip of the hovered widget or of its closest parent which has one
+    fn tooltip_text(&self) -> Option<(WidgetId, String)> {
+        let mut current = self.hovered;
+
+        while let Some(id) = current {
+            let node = self.node(id);
+            if let Some(tooltip) = &node.tooltip {
+                return Some((id, tooltip.clone()));
+            }
+            current = node.parent;
+        }
+
+        return None;
+    }
+
+    fn hide_tooltip(&mut self) {
+        if let Some(root) = self.tooltip.layer.take() {
+            self.close_overlay(root);
+        }
+    }
+
+    /// Advance the animations by `dt` seconds, returns true while some of them are running (the
+    /// UI should be redrawn)
+    ///
+    /// Also shows the tooltips, so it should be called while the tooltip delay runs.
+    pub fn update(&mut self, dt: f32) -> bool {
+        let hovered = self.hovered;
+        let mut running = false;
+
+        for (index, node) in self.nodes.iter_mut().enumerate() {
+            let Some(Node {
+                style,
+                animation: Some(animation),
+                ..
+            }) = node
+            else {
+                continue;
+            };
+
+            let hover = match hovered == Some(WidgetId(index)) {
+                true => 1.0,
+                false => 0.0,
+            };
+            animation.hover.set(hover, animation.transition);
+
+            if let Some(rect) = &mut animation.rect {
+                rect.update(dt);
+            }
+            animation.opacity.update(dt);
+            animation.hover.update(dt);
+
+            if animation.hiding && animation.opacity.is_finished() {
+                animation.hiding = false;
+                style.display = Display::None;
+            }
+
+            running |= !animation.is_finished();
+        }
+
+        running |= self.update_tooltip(dt);
+        return running;
+    }
+
+    /// Update the widget whenever the observable changes (the first update happens during the
+    /// next layout)
+    ///
+    /// * `apply`: copies the value into the widget
+    pub fn bind<W: Widget, T: 'static>(
+        &mut self,
+        id: WidgetId,
+        source: &Observable<T>,
+        mut apply: impl FnMut(&mut W, &T) + 'static,
+    ) {
+        let source = source.clone();
+        let mut applied_version = None;
+
+        let binding = move |ui: &mut Ui| {
+            if applied_version == Some(source.version()) {
+                return;
+            }
+            applied_version = Some(source.version());
+
+            if let Some(widget) = ui.widget_mut::<W>(id) {
+                apply(widget, &source.borrow());
+            }
+        };
+        self.bindings.push((id, Box::new(binding)));
+    }
+
+    /// Update the style of the widget whenever the observable changes (e.g. the visibility)
+    pub fn bind_style<T: 'static>(
+        &mut self,
+        id: WidgetId,
+        source: &Observable<T>,
+        mut apply: impl FnMut(&mut Style, &T) + 'static,
+    ) {
+        let source = source.clone();
+        let mut applied_version = None;
+
+        let binding = move |ui: &mut Ui| {
+            if applied_version == Some(source.version()) {
+                return;
+            }
+            applied_version = Some(source.version());
+
+            apply(ui.style_mut(id), &source.borrow());
+        };
+        self.bindings.push((id, Box::new(binding)));
+    }
+
+    /// Apply the observables which changed since the last update to the bound widgets
+    pub fn update_bindings(&mut self) {
+        let mut bindings = std::mem::take(&mut self.bindings);
+
+        for (_, binding) in bindings.iter_mut() {
+            binding(self);
+        }
+
+        /*
+         * Keep the bindings added during the update
+         */
+        bindings.append(&mut self.bindings);
+        self.bindings = bindings;
+    }
+
+    pub fn set_focus(&mut self, id: Option<WidgetId>) {
+        self.focused = id;
+    }
+
+    /// Let the arrow keys which aren't handled by the focused widget move the focus to the
+    /// closest widget in their direction in the whole UI, not only inside of focus groups ( menus
+    /// driven by a gamepad or the keyboard only, see [`crate::nav`] )
+    ///
+    /// If nothing is focused, an arrow key focuses the first focusable widget.
+    pub fn set_spatial_navigation(&mut self, enabled: bool) {
+        self.spatial_navigation = enabled;
+    }
+
+    /// Move the focus to the next focusable widget (wraps around)
+    pub fn focus_next(&mut self) {
+        self.move_focus(true);
+    }
+
+    /// Move the focus to the previous focusable widget (wraps around)
+    pub fn focus_previous(&mut self) {
+        self.move_focus(false);
+    }
+
+    /// Visible focusable widgets in the order of the tree (only inside of the topmost modal or
+    /// popup if one is open)
+    pub fn focus_order(&self) -> Vec<WidgetId> {
+        let mut order = vec![];
+        self.collect_focusable(self.focus_scope(), &mut order);
+        return order;
+    }
+
+    fn move_focus(&mut self, forward: bool) {
+        let order = self.focus_order();
+        if order.is_empty() {
+            return;
+        }
+
+        let current = self
+            .focused
+            .and_then(|focused| order.iter().position(|&id| id == focused));
+        let index = match (current, forward) {
+            (Some(index), true) => (index + 1) % order.len(),
+            (Some(index), false) => (index + order.len() - 1) % order.len(),
+            (None, true) => 0,
+            (None, false) => order.len() - 1,
+        };
+
+        self.focused = Some(order[index]);
+        self.focus_visible = true;
+    }
+
+    /// Move the focus to the closest focusable widget of the focus group (or of the whole UI with
+    /// spatial navigation) in the direction of the arrow key, returns false if there is none
+    fn move_focus_in_direction(&mut self, key: Key) -> bool {
+        let direction = match key {
+            Key::Left => Vec2::new(-1.0, 0.0),
+            Key::Right => Vec2::new(1.0, 0.0),
+            Key::Up => Vec2::new(0.0, -1.0),
+            Key::Down => Vec2::new(0.0, 1.0),
+            _ => return false,
+        };
+        let Some(focused) = self.focused else {
+            let first = self.focus_order().first().copied();
+            if !self.spatial_navigation || first.is_none() {
+                return false;
+            }
+
+            self.focused = first;
+            self.focus_visible = true;
+            return true;
+        };
+
+        /*
+         * Find the closest group containing the focused widget
+         */
+        let mut group = self.node(focused).parent;
+        while let Some(id) = group {
+            if self.node(id).widget.is_focus_group() {
+                break;
+            }
+            group = self.node(id).parent;
+        }
+        let mut candidates = vec![];
+        match group {
+            Some(group) => {
+                for &child in self.node(group).children.iter() {
+                    if self.is_visible(child) {
+                        self.collect_focusable(child, &mut candidates);
+                    }
+                }
+            }
+            None if self.spatial_navigation => candidates = self.focus_order(),
+            None => return false,
+        }
+
+        /*
+         * Prefer widgets which are straight in the direction of the key
+         */
+        let from = self.node(focused).rect.center();
+        let closest = candidates
+            .into_iter()
+            .filter(|&id| id != focused)
+            .filter_map(|id| {
+                let delta = self.node(id).rect.center() - from;
+                let along = delta.x * direction.x + delta.y * direction.y;
+                let across = (delta.x * direction.y - delta.y * direction.x).abs();
+
+                return (along > 0.0).then_some((id, along + 2.0 * across));
+            })
+            .min_by(|(_, a), (_, b)| a.total_cmp(b));
+
+        let Some((id, _)) = closest else {
+            return false;
+        };
+        self.focused = Some(id);
+        self.focus_visible = true;
+        return true;
+    }
+
+    /// Keyboard navigation for the key events which weren't handled by the focused widget
+    fn navigate(&mut self, key: Key, modifiers: Modifiers) -> Response {
+        let moved = match key {
+            Key::Tab if !modifiers.ctrl && !modifiers.alt && !modifiers.logo => {
+                self.move_focus(!modifiers.shift);
+                true
+            }
+            Key::Escape if modifiers.is_empty() => self.close_top_overlay(),
+            _ if modifiers.is_empty() => self.move_focus_in_direction(key),
+            _ => false,
+        };
+
+        return match moved {
+            true => Response::Consumed,
+            false => Response::Ignored,
+        };
+    }
+
+    fn collect_focusable(&self, id: WidgetId, order: &mut Vec<WidgetId>) {
+        let node = self.node(id);
+        if node.widget.is_focusable() {
+            order.push(id);
+        }
+
+        for &child in node.children.iter() {
+            if self.is_visible(child) {
+                self.collect_focusable(child, order);
+            }
+        }
+    }
+
+    /// Change the number of physical pixels per logical pixel (also done by
+    /// [`Event::ScaleFactorChanged`])
+    ///
+    /// The layout doesn't change, the draw commands are scaled and snapped to the physical
+    /// pixels. Text is drawn with the physical font size, so the renderer should rasterize the
+    /// glyphs again for the new sizes.
+    pub fn set_scale_factor(&mut self, scale_factor: f32) {
+        self.scale_factor = scale_factor;
+    }
+
+    /// Lay out all widgets inside a viewport of the given size (in logical pixels)
+    pub fn layout(&mut self, viewport_size: Vec2<f32>) {
+        let root = self.root();
+        let viewport = Rect::from_pos_size(Vec2::new(0.0, 0.0), viewport_size);
+
+        self.update_bindings();
+        self.adjust_styles();
+        crate::layout::compute(self, root, viewport);
+        self.layout_scroll_areas(root);
+        self.layout_overlays(viewport);
+        self.start_transitions();
+    }
+
+    /// Place the overlays with their intrinsic size
+    fn layout_overlays(&mut self, viewport: Rect) {
+        for index in 0..self.layers.len() {
+            let root = self.layers[index].root;
+            let size = crate::layout::intrinsic_size(self, root, viewport.size());
+
+            let rect = match self.layers[index].placement {
+                Placement::Below(anchor) if self.contains(anchor) => {
+                    place_next_to(self.rect(anchor), size, viewport)
+                }
+                Placement::At(pos) => {
+                    let anchor = Rect::from_pos_size(pos, Vec2::new(0.0, 0.0));
+                    place_next_to(anchor, size, viewport)
+                }
+                Placement::Center | Placement::Below(_) => place_center(size, viewport),
+            };
+            crate::layout::compute(self, root, rect);
+            self.layout_scroll_areas(root);
+
+            if std::mem::take(&mut self.layers[index].focus_pending) {
+                let mut order = vec![];
+                self.collect_focusable(root, &mut order);
+                self.focused = order.first().copied();
+            }
+        }
+    }
+
+    /// Animate the widgets with a transition to their new rects
+    fn start_transitions(&mut self) {
+        for node in self.nodes.iter_mut().flatten() {
+            let Some(animation) = node.animation.as_deref_mut() else {
+                continue;
+            };
+
+            if node.style.display == Display::None {
+                animation.rect = None;
+                continue;
+            }
+
+            let transition = animation.transition;
+            match &mut animation.rect {
+                Some(rect) => rect.set(node.rect, transition),
+                None => animation.rect = Some(Tween::new(node.rect)),
+            }
+        }
+    }
+
+    /// Append the draw commands of all widgets (parents before children) in physical pixels
+    pub fn draw(&self, list: &mut DrawList) {
+        let first_cmd = list.commands().len();
+        self.draw_node(self.root(), &self.theme, None, list);
+
+        for layer in self.layers.iter() {
+            if layer.kind == LayerKind::Modal {
+                list.rect(self.node(self.root()).rect, self.theme.backdrop);
+            }
+            self.draw_node(layer.root, &self.theme, None, list);
+        }
+
+        /*
+         * Drag preview on top of everything
+         */
+        if let Some(drag) = &self.drag {
+            let first_preview_cmd = list.commands().len();
+            for cmd in drag.preview.commands() {
+                list.push(cmd.clone());
+            }
+            list.translate(first_preview_cmd, drag.pos);
+        }
+
+        list.scale(first_cmd, self.scale_factor);
+    }
+
+    fn draw_node(
+        &self,
+        id: WidgetId,
+        parent_theme: &Theme,
+        clip: Option<Rect>,
+        list: &mut DrawList,
+    ) {
+        let node = self.node(id);
+        let theme = node.theme.as_deref().unwrap_or(parent_theme);
+        let hovered = self.hovered == Some(id);
+
+        /*
+         * The widget is drawn with the animated size, the whole subtree is moved to the animated
+         * position and faded afterwards
+         */
+        let animation = node.animation.as_deref();
+        let animated = animation.map_or(node.rect, |animation| animation.rect(node.rect));
+        let first_cmd = list.commands().len();
+
+        let mut ctx = DrawCtx {
+            rect: Rect::from_pos_size(node.rect.min, animated.size()),
+            list,
+            text: self.text.as_ref(),
+            theme,
+            focused: self.focused == Some(id),
+            hovered,
+            hover: animation.map_or(hovered as u8 as f32, |animation| animation.hover.value()),
+            drop_hover: self.drop_target() == Some(id),
+        };
+        node.widget.draw(&mut ctx);
+
+        /*
+         * Children outside of the clip rect are skipped
+         */
+        let clips_children = node.widget.clips_children();
+        let clip = match clips_children {
+            true => {
+                ctx.list.push_clip(node.rect);
+                Some(clip.map_or(node.rect, |clip| clip.intersection(&node.rect)))
+            }
+            false => clip,
+        };
+
+        for &child in node.children.iter() {
+            if !self.is_visible(child) {
+                continue;
+            }
+            if clip.is_none_or(|clip| self.node(child).rect.intersects(&clip)) {
+                self.draw_node(child, theme, clip, ctx.list);
+            }
+        }
+
+        if clips_children {
+            ctx.list.pop_clip();
+        }
+        node.widget.draw_overlay(&mut ctx);
+
+        if (ctx.focused && self.focus_visible) || ctx.drop_hover {
+            ctx.list
+                .outline(node.rect, 2.0 * theme.border_width, theme.accent);
+        }
+
+        if let Some(animation) = animation {
+            ctx.list.translate(first_cmd, animated.min - node.rect.min);
+            ctx.list.fade(first_cmd, animation.opacity.value());
+        }
+    }
+
+    /// Let the widgets adjust their styles and the styles of their children
+    fn adjust_styles(&mut self) {
+        for index in 0..self.nodes.len() {
+            let Some(node) = &self.nodes[index] else {
+                continue;
+            };
+
+            let mut style = node.style;
+            let children = node.children.clone();
+            let mut child_styles = children
+                .iter()
+                .map(|&child| self.node(child).style)
+                .collect::<Vec<_>>();
+
+            self.node(WidgetId(index))
+                .widget
+                .adjust_styles(&mut style, &mut child_styles);
+
+            self.node_mut(WidgetId(index)).style = style;
+            for (child, child_style) in children.into_iter().zip(child_styles) {
+                self.node_mut(child).style = child_style;
+            }
+        }
+    }
+
+    /// Create the visible rows of virtualized scroll areas and shift the scrolled children
+    fn layout_scroll_areas(&mut self, id: WidgetId) {
+        let rect = self.node(id).rect;
+        let padding = self.node(id).style.padding;
+
+        if self.widget::<ScrollArea>(id).is_some() {
+            /*
+             * Virtualized rows are absolutely positioned, so the content size is known before
+             * they are created
+             */
+            if self.sync_virtual_rows(id) {
+                crate::layout::compute(self, id, rect);
+            }
+
+            let extent = match self.virtual_rows_extent(id) {
+                Some(extent) => extent,
+                None => self.children_extent(id),
+            };
+            let area = self
+                .widget_mut::<ScrollArea>(id)
+                .expect("Not a scroll area");
+            area.set_extent(
+                rect.size(),
+                extent + Vec2::new(padding.right, padding.bottom),
+            );
+
+            let offset = area.offset();
+            if offset != Vec2::new(0.0, 0.0) {
+                self.translate_descendants(id, -offset);
+            }
+        }
+
+        let children = self.node(id).children.clone();
+        for child in children {
+            self.layout_scroll_areas(child);
+        }
+    }
+
+    /// Remove the rows which are no longer visible and create the ones which became visible
+    /// (returns whether anything changed)
+    fn sync_virtual_rows(&mut self, id: WidgetId) -> bool {
+        let Some(extent) = self.virtual_rows_extent(id) else {
+            return false;
+        };
+        let padding = self.node(id).style.padding;
+        let rect = self.node(id).rect;
+
+        let area = self
+            .widget_mut::<ScrollArea>(id)
+            .expect("Not a scroll area");
+        area.set_extent(
+            rect.size(),
+            extent + Vec2::new(padding.right, padding.bottom),
+        );
+        let visible = area.visible_rows().unwrap_or(0..0);
+        let rows = area.rows_mut().expect("Not a virtualized scroll area");
+
+        let dirty = std::mem::take(&mut rows.dirty);
+        let (keep, remove): (Vec<_>, Vec<_>) = std::mem::take(&mut rows.widgets)
+            .into_iter()
+            .partition(|(row, _)| !dirty && visible.contains(row));
+
+        let new_rows = visible
+            .clone()
+            .filter(|row| !keep.iter().any(|(kept, _)| kept == row))
+            .map(|row| (row, (rows.factory)(row)))
+            .collect::<Vec<_>>();
+        let height = rows.height;
+        let changed = !remove.is_empty() || !new_rows.is_empty();
+        rows.widgets = keep;
+
+        for (_, widget) in remove {
+            self.remove(widget);
+        }
+
+        let mut created = Vec::with_capacity(new_rows.len());
+        for (row, widget) in new_rows {
+            let style = Style {
+                position: Position::Absolute(Vec2::new(0.0, row as f32 * height)),
+                width: crate::layout::Dimension::Percent(1.0),
+                height: crate::layout::Dimension::Px(height),
+                ..Default::default()
+            };
+            created.push((row, self.add_boxed(id, widget, style)));
+        }
+
+        if let Some(rows) = self
+            .widget_mut::<ScrollArea>(id)
+            .and_then(ScrollArea::rows_mut)
+        {
+            rows.widgets.extend(created);
+        }
+
+        return changed;
+    }
+
+    /// Content size of a virtualized scroll area (excluding the right and bottom padding)
+    fn virtual_rows_extent(&mut self, id: WidgetId) -> Option<Vec2<f32>> {
+        let node = self.node(id);
+        let (rect, padding) = (node.rect, node.style.padding);
+
+        let rows = self.widget_mut::<ScrollArea>(id)?.rows_mut()?;
+
+        return Some(Vec2::new(
+            rect.width() - padding.right,
+            padding.top + rows.count as f32 * rows.height,
+        ));
+    }
+
+    /// Size from the top left corner to the furthest child (excluding the right and bottom
+    /// padding)
+    fn children_extent(&self, id: WidgetId) -> Vec2<f32> {
+        let node = self.node(id);
+        let mut extent: Vec2<f32> = Vec2::new(0.0, 0.0);
+
+        for &child in node.children.iter() {
+            let child = self.node(child);
+            let max = child.rect.max - node.rect.min
+                + Vec2::new(child.style.margin.right, child.style.margin.bottom);
+
+            extent = Vec2::new(extent.x.max(max.x), extent.y.max(max.y));
+        }
+
+        return extent;
+    }
+
+    fn translate_descendants(&mut self, id: WidgetId, offset: Vec2<f32>) {
+        let mut stack = self.node(id).children.clone();
+
+        while let Some(id) = stack.pop() {
+            let node = self.node_mut(id);
+            node.rect = node.rect.translate(offset);
+            stack.extend(node.children.iter().copied());
+        }
+    }
+
+    /// Route an input event to the widgets
+    ///
+    /// Pointer events go to the widget under the pointer (or to the widget which captured the
+    /// pointer), keyboard events go to the focused widget. Ignored events bubble up to the
+    /// parents.
+    pub fn handle_event(&mut self, event: &Event) -> Response {
+        // Not consumed, every UI of the window needs it
+        if let Event::ScaleFactorChanged { scale_factor } = event {
+            self.set_scale_factor(*scale_factor);
+            return Response::Ignored;
+        }
+
+        if let Some(pos) = pointer_pos(event) {
+            self.pointer = pos;
+        }
+        if matches!(
+            event,
+            Event::PointerDown { .. }
+                | Event::Scroll { .. }
+                | Event::KeyDown { .. }
+                | Event::Text(_)
+        ) {
+            self.hide_tooltip();
+            self.tooltip.suppressed = true;
+        }
+
+        if let Some(response) = self.handle_drag_event(event) {
+            return response;
+        }
+        if let Some(response) = self.handle_overlay_event(event) {
+            return response;
+        }
+
+        let target = match event {
+            Event::PointerMoved { pos } => {
+                let hovered = self.hit_test(*pos);
+                if hovered != self.hovered {
+                    self.hide_tooltip();
+                    self.tooltip = TooltipState::default();
+                }
+
+                self.hovered = hovered;
+                self.captured.or(self.hovered)
+            }
+            Event::PointerDown { pos, .. } => {
+                let target = self.captured.or(self.hit_test(*pos));
+                self.focused = self.focusable_ancestor(target);
+                self.focus_visible = false;
+                target
+            }
+            Event::PointerUp { pos, .. } | Event::Scroll { pos, .. } => {
+                self.captured.or(self.hit_test(*pos))
+            }
+            Event::KeyDown { .. } | Event::KeyUp { .. } | Event::Text(_) => self.focused,
+            Event::ScaleFactorChanged { .. } => return Response::Ignored,
+        };
+
+        let mut response = self.dispatch(target, event);
+
+        if let Event::KeyDown { key, modifiers } = event {
+            if !response.is_consumed() {
+                response = self.navigate(*key, *modifiers);
+            }
+        }
+
+        if let Event::PointerDown {
+            pos,
+            button: PointerButton::Secondary,
+        } = event
+        {
+            if !response.is_consumed() {
+                response = self.show_context_menu(target, *pos);
+            }
+        }
+
+        if matches!(event, Event::PointerUp { .. }) {
+            self.captured = None;
+        }
+
+        return response;
+    }
+
+    /// Deepest widget under the point (later children and overlays are on top, a modal hides
+    /// everything below it)
+    pub fn hit_test(&self, pos: Vec2<f32>) -> Option<WidgetId> {
+        for layer in self.layers.iter().rev() {
+            if layer.kind == LayerKind::Tooltip {
+                continue;
+            }
+            if let Some(id) = self.hit_test_from(layer.root, pos) {
+                return Some(id);
+            }
+            if layer.kind == LayerKind::Modal {
+                return None;
+            }
+        }
+
+        return self.hit_test_from(self.root(), pos);
+    }
+
+    fn hit_test_from(&self, root: WidgetId, pos: Vec2<f32>) -> Option<WidgetId> {
+        let mut id = root;
+        if !self.node(id).rect.contains(pos) {
+            return None;
+        }
+
+        'outer: loop {
+            for &child in self.node(id).children.iter().rev() {
+                if self.is_visible(child) && self.node(child).rect.contains(pos) {
+                    id = child;
+                    continue 'outer;
+                }
+            }
+            return Some(id);
+        }
+    }
+
+    /// Give the event to the target and then to its parents until it's consumed
+    fn dispatch(&mut self, target: Option<WidgetId>, event: &Event) -> Response {
+        let mut current = target;
+
+        while let Some(id) = current {
+            let response = self.with_event_ctx(id, pointer_pos(event), |widget, ctx| {
+                return widget.on_event(ctx, event);
+            });
+
+            if response.is_consumed() {
+                return response;
+            }
+            current = self.node(id).parent;
+        }
+
+        return Response::Ignored;
+    }
+
+    /// Call the widget with an event context and apply the requests it made
+    fn with_event_ctx<R>(
+        &mut self,
+        id: WidgetId,
+        pointer_pos: Option<Vec2<f32>>,
+        f: impl FnOnce(&mut dyn Widget, &mut EventCtx) -> R,
+    ) -> R {
+        let theme = self.theme_rc(id).clone();
+        let node = self.nodes[id.0].as_mut().expect("Widget was removed");
+
+        let mut ctx = EventCtx {
+            rect: node.rect,
+            text: self.text.as_ref(),
+            theme: &theme,
+            clipboard: self.clipboard.as_mut(),
+            focused: self.focused == Some(id),
+            focus_request: None,
+            capture_pointer: false,
+            drag_request: None,
+            close_request: false,
+        };
+        let result = f(node.widget.as_mut(), &mut ctx);
+
+        let EventCtx {
+            focus_request,
+            capture_pointer,
+            drag_request,
+            close_request,
+            ..
+        } = ctx;
+        match focus_request {
+            Some(FocusRequest::Take) => self.focused = Some(id),
+            Some(FocusRequest::Release) if self.focused == Some(id) => self.focused = None,
+            Some(FocusRequest::Next) => self.move_focus(true),
+            Some(FocusRequest::Previous) => self.move_focus(false),
+            _ => {}
+        }
+        if capture_pointer {
+            self.captured = Some(id);
+        }
+        if let Some((payload, preview)) = drag_request {
+            let pos = pointer_pos.unwrap_or(Vec2::new(0.0, 0.0));
+            self.drag = Some(ActiveDrag {
+                data: DragData::new(payload, id),
+                preview,
+                pos,
+                target: None,
+            });
+            self.update_drop_target(pos);
+        }
+        if let Some(root) = self.overlay_of(id).filter(|_| close_request) {
+            self.close_overlay(root);
+        }
+
+        return result;
+    }
+
+    /// Routing of the pointer events around the overlays, returns `None` for the events which
+    /// are handled as usual
+    fn handle_overlay_event(&mut self, event: &Event) -> Option<Response> {
+        let pos = match event {
+            Event::PointerDown { pos, .. }
+            | Event::PointerUp { pos, .. }
+            | Event::Scroll { pos, .. }
+                if self.captured.is_none() =>
+            {
+                *pos
+            }
+            _ => return None,
+        };
+
+        /*
+         * Pressing outside of the popups closes them
+         */
+        if matches!(event, Event::PointerDown { .. }) && self.dismiss_popups(pos) {
+            return Some(Response::Consumed);
+        }
+
+        /*
+         * Nothing below a modal reacts
+         */
+        let modal = self
+            .layers
+            .iter()
+            .any(|layer| layer.kind == LayerKind::Modal);
+        if modal && self.hit_test(pos).is_none() {
+            return Some(Response::Consumed);
+        }
+
+        return None;
+    }
+
+    /// Move the drag in progress and drop it, returns `None` for the events which are handled as
+    /// usual
+    fn handle_drag_event(&mut self, event: &Event) -> Option<Response> {
+        self.drag.as_ref()?;
+
+        match event {
+            Event::PointerMoved { pos } => {
+                self.hovered = self.hit_test(*pos);
+                self.update_drop_target(*pos);
+            }
+            Event::PointerUp { pos, .. } => {
+                self.update_drop_target(*pos);
+
+                let drag = self.drag.take()?;
+                if let Some(target) = drag.target {
+                    self.with_event_ctx(target, Some(*pos), |widget, ctx| {
+                        widget.on_drop(ctx, *pos, drag.data);
+                    });
+                }
+
+                // The release still goes to the widget which captured the pointer
+                return None;
+            }
+            Event::KeyDown {
+                key: Key::Escape, ..
+            } => self.cancel_drag(),
+            _ => return None,
+        }
+
+        return Some(Response::Consumed);
+    }
+
+    /// Stop the drag in progress without dropping it
+    pub fn cancel_drag(&mut self) {
+        self.drag = None;
+        self.captured = None;
+    }
+
+    /// Find the widget under the position which accepts the drag
+    fn update_drop_target(&mut self, pos: Vec2<f32>) {
+        let Some(drag) = &self.drag else {
+            return;
+        };
+
+        let mut target = self.hit_test(pos);
+        while let Some(id) = target {
+            let node = self.node(id);
+            if node.widget.accepts_drop(&drag.data) {
+                break;
+            }
+            target = node.parent;
+        }
+
+        if let Some(drag) = &mut self.drag {
+            drag.pos = pos;
+            drag.target = target;
+        }
+    }
+
+    fn theme_rc(&self, id: WidgetId) -> &Rc<Theme> {
+        let mut current = Some(id);
+
+        while let Some(id) = current {
+            let node = self.node(id);
+            if let Some(theme) = &node.theme {
+                return theme;
+            }
+            current = node.parent;
+        }
+
+        return &self.theme;
+    }
+
+    fn focusable_ancestor(&self, id: Option<WidgetId>) -> Option<WidgetId> {
+        let mut current = id;
+
+        while let Some(id) = current {
+            let node = self.node(id);
+            if node.widget.is_focusable() {
+                return Some(id);
+            }
+            current = node.parent;
+        }
+
+        return None;
+    }
+
+    fn node(&self, id: WidgetId) -> &Node {
+        return self.nodes[id.0].as_ref().expect("Widget was removed");
+    }
+
+    fn node_mut(&mut self, id: WidgetId) -> &mut Node {
+        return self.nodes[id.0].as_mut().expect("Widget was removed");
+    }
+}
+
+fn pointer_pos(event: &Event) -> Option<Vec2<f32>> {
+    return match event {
+        Event::PointerMoved { pos }
+        | Event::PointerDown { pos, .. }
+        | Event::PointerUp { pos, .. }
+        | Event::Scroll { pos, .. } => Some(*pos),
+        Event::KeyDown { .. }
+        | Event::KeyUp { .. }
+        | Event::Text(_)
+        | Event::ScaleFactorChanged { .. } => None,
+    };
+}
+
+impl Animation {
+    fn rect(&self, layout_rect: Rect) -> Rect {
+        return self.rect.as_ref().map_or(layout_rect, Tween::value);
+    }
+
+    fn is_finished(&self) -> bool {
+        let rect_finished = self.rect.as_ref().is_none_or(Tween::is_finished);
+        return rect_finished && self.opacity.is_finished() && self.hover.is_finished();
+    }
+}
+
+impl Node {
+    fn new(widget: Box<dyn Widget>, style: Style, parent: Option<WidgetId>) -> Self {
+        return Node {
+            widget,
+            style,
+            theme: None,
+            parent,
+            children: vec![],
+            rect: Rect::default(),
+            animation: None,
+            tooltip: None,
+            context_menu: None,
+        };
+    }
+}
+
+//-----------------------------------------------------------------------------
+
+impl LayoutTree for Ui {
+    type Id = WidgetId;
+
+    fn style(&self, node: WidgetId) -> &Style {
+        return &self.node(node).style;
+    }
+
+    fn children(&self, node: WidgetId) -> &[WidgetId] {
+        return &self.node(node).children;
+    }
+
+    fn measure(&self, node: WidgetId, available: Vec2<f32>) -> Vec2<f32> {
+        let ctx = MeasureCtx {
+            text: self.text.as_ref(),
+            theme: self.theme(node),
+        };
+        return self.node(node).widget.measure(&ctx, available);
+    }
+
+    fn set_rect(&mut self, node: WidgetId, rect: Rect) {
+        self.node_mut(node).rect = rect;
+    }
+}
+
+//-----------------------------------------------------------------------------
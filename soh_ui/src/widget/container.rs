@@ -0,0 +1,91 @@
+//-----------------------------------------------------------------------------
+use super::{DrawCtx, Widget};
+use crate::draw::Color;
+use crate::panel::PanelStyle;
+//-----------------------------------------------------------------------------
+
+/// Widget which only groups its children (optionally with a background)
+#[derive(Debug, Default, Clone)]
+pub struct Container {
+    /// Draw the background, the border and the shadow of the theme
+    pub panel: bool,
+    /// Overrides the background color of the theme (also for non panels)
+    pub background: Option<Color>,
+    /// The arrow keys move the focus between the children
+    pub focus_group: bool,
+}
+
+//-----------------------------------------------------------------------------
+
+impl Container {
+    pub fn new() -> Self {
+        return Self::default();
+    }
+
+    /// Container with the panel look of the theme
+    pub fn panel() -> Self {
+        return Container {
+            panel: true,
+            ..Self::default()
+        };
+    }
+
+    pub fn background(mut self, color: impl Into<Color>) -> Self {
+        self.background = Some(color.into());
+        return self;
+    }
+
+    /// Let the arrow keys move the focus between the children
+    pub fn focus_group(mut self) -> Self {
+        self.focus_group = true;
+        return self;
+    }
+}
+
+impl Widget for Container {
+    fn draw(&self, ctx: &mut DrawCtx) {
+        if self.panel {
+            let mut style = PanelStyle::from_theme(ctx.theme);
+            if let Some(background) = self.background {
+                style = style.background(background);
+            }
+
+            ctx.list.panel(ctx.rect, &style);
+        } else if let Some(background) = self.background {
+            ctx.list.rect(ctx.rect, background);
+        }
+    }
+
+    fn is_focus_group(&self) -> bool {
+        return self.focus_group;
+    }
+}
+
+#[cfg(feature = "serde")]
+impl crate::data::WidgetData for Container {
+    const TYPE: &'static str = "Container";
+
+    fn from_props(props: &crate::data::Props) -> crate::Result<Self> {
+        return Ok(Container {
+            panel: props.bool("panel")?.unwrap_or(false),
+            background: props.color("background")?,
+            focus_group: props.bool("focus_group")?.unwrap_or(false),
+        });
+    }
+
+    fn to_props(&self) -> crate::data::Props {
+        let mut props = crate::data::Props::new();
+        if self.panel {
+            props.set("panel", true);
+        }
+        if let Some(background) = self.background {
+            props.set("background", background);
+        }
+        if self.focus_group {
+            props.set("focus_group", true);
+        }
+        return props;
+    }
+}
+
+//-----------------------------------------------------------------------------
@@ -0,0 +1,195 @@
+//-----------------------------------------------------------------------------
+use super::plot::{data_range, tick_label, ticks, LABEL_SCALE};
+use super::{DrawCtx, MeasureCtx, Widget};
+use crate::draw::Color;
+use crate::layout::Edges;
+use crate::{Rect, RectExt};
+use soh_math::color::Gradient;
+use soh_math::Vec2;
+//-----------------------------------------------------------------------------
+
+/// Grid of values colored with a color map, with a color bar as the legend
+#[derive(Debug, Clone)]
+pub struct Heatmap {
+    columns: usize,
+    rows: usize,
+    /// Row by row, starting at the top
+    values: Vec<f32>,
+    gradient: Gradient,
+    /// Values mapped to the ends of the gradient (fitted to the values if not set)
+    range: Option<(f32, f32)>,
+    /// Draw the color bar
+    pub legend: bool,
+}
+
+/// Width of the color bar
+const BAR_WIDTH: f32 = 12.0;
+
+//-----------------------------------------------------------------------------
+// Constructor
+impl Heatmap {
+    pub fn new(columns: usize, rows: usize) -> Self {
+        return Heatmap {
+            columns,
+            rows,
+            values: vec![0.0; columns * rows],
+            gradient: Gradient::viridis(),
+            range: None,
+            legend: true,
+        };
+    }
+
+    pub fn gradient(mut self, gradient: Gradient) -> Self {
+        self.gradient = gradient;
+        return self;
+    }
+
+    /// Fixed range of the color map
+    pub fn range(mut self, min: f32, max: f32) -> Self {
+        self.range = Some((min, max));
+        return self;
+    }
+}
+
+//-----------------------------------------------------------------------------
+// Getters
+impl Heatmap {
+    pub fn columns(&self) -> usize {
+        return self.columns;
+    }
+    pub fn rows(&self) -> usize {
+        return self.rows;
+    }
+    pub fn values(&self) -> &[f32] {
+        return &self.values;
+    }
+    pub fn value_range(&self) -> (f32, f32) {
+        return self
+            .range
+            .unwrap_or_else(|| data_range(self.values.iter().copied()));
+    }
+
+    /// Color of the value according to the color map
+    pub fn value_color(&self, value: f32) -> Color {
+        let (min, max) = self.value_range();
+        let t = (value - min) / (max - min);
+        return Color::opaque(self.gradient.sample(t as f64));
+    }
+}
+
+//-----------------------------------------------------------------------------
+// Specific implementation
+impl Heatmap {
+    /// Replace the values (`columns * rows` values, row by row)
+    pub fn set_values(&mut self, values: &[f32]) {
+        assert_eq!(
+            values.len(),
+            self.columns * self.rows,
+            "Heatmap expects {} x {} values",
+            self.columns,
+            self.rows
+        );
+        self.values.clear();
+        self.values.extend_from_slice(values);
+    }
+
+    /// Change the size of the grid and replace the values
+    pub fn set_grid(&mut self, columns: usize, rows: usize, values: &[f32]) {
+        self.columns = columns;
+        self.rows = rows;
+        self.set_values(values);
+    }
+}
+
+impl Widget for Heatmap {
+    fn measure(&self, ctx: &MeasureCtx, _available: Vec2<f32>) -> Vec2<f32> {
+        let line_height = ctx.text.line_height(ctx.theme.font_size);
+        return Vec2::new(line_height * 10.0, line_height * 8.0);
+    }
+
+    fn draw(&self, ctx: &mut DrawCtx) {
+        let theme = ctx.theme;
+        let size = theme.font_size * LABEL_SCALE;
+        let line_height = ctx.text.line_height(size);
+        let inner = ctx.rect.shrink(Edges::all(theme.padding));
+
+        /*
+         * Color bar with its labels on the right
+         */
+        let value_range = self.value_range();
+        let (labels, step) = ticks(
+            value_range,
+            ((inner.height() / (line_height * 2.0)) as usize).max(2),
+        );
+        let labels = labels
+            .into_iter()
+            .map(|v| (v, tick_label(v, step)))
+            .collect::<Vec<_>>();
+
+        let legend_width = match self.legend {
+            true => {
+                let label_width = labels
+                    .iter()
+                    .map(|(_, label)| ctx.text.text_width(label, size))
+                    .fold(0.0, f32::max);
+                BAR_WIDTH + label_width + 2.0 * theme.padding
+            }
+            false => 0.0,
+        };
+
+        let grid = inner.shrink(Edges {
+            right: legend_width,
+            ..Edges::ZERO
+        });
+
+        /*
+         * Cells
+         */
+        if self.columns > 0 && self.rows > 0 {
+            let cell = Vec2::new(
+                grid.width() / self.columns as f32,
+                grid.height() / self.rows as f32,
+            );
+
+            for (i, &value) in self.values.iter().enumerate() {
+                let (column, row) = (i % self.columns, i / self.columns);
+                let min = Vec2::new(
+                    grid.min.x + column as f32 * cell.x,
+                    grid.min.y + row as f32 * cell.y,
+                );
+                ctx.list
+                    .rect(Rect::from_pos_size(min, cell), self.value_color(value));
+            }
+        }
+
+        if !self.legend {
+            return;
+        }
+
+        let bar = Rect::new(
+            Vec2::new(grid.max.x + theme.padding, grid.min.y),
+            Vec2::new(grid.max.x + theme.padding + BAR_WIDTH, grid.max.y),
+        );
+        let slices = (bar.height() as usize / 2).max(1);
+        let slice_height = bar.height() / slices as f32;
+
+        for i in 0..slices {
+            let t = 1.0 - (i as f32 + 0.5) / slices as f32;
+            let min = Vec2::new(bar.min.x, bar.min.y + i as f32 * slice_height);
+            let color = Color::opaque(self.gradient.sample(t as f64));
+            ctx.list.rect(
+                Rect::from_pos_size(min, Vec2::new(BAR_WIDTH, slice_height)),
+                color,
+            );
+        }
+
+        let (min, max) = value_range;
+        for (value, label) in labels.iter() {
+            let y = bar.max.y - (value - min) / (max - min) * bar.height();
+            let pos = Vec2::new(bar.max.x + theme.padding, y - line_height * 0.5);
+            ctx.list.text(pos, label, size, theme.text_muted);
+        }
+    }
+}
+
+//-----------------------------------------------------------------------------
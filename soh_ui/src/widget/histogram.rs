@@ -0,0 +1,124 @@
+//-----------------------------------------------------------------------------
+use super::plot::{data_range, draw_axes, map_point, nice_range};
+use super::{DrawCtx, MeasureCtx, Widget};
+use crate::draw::Color;
+use crate::Rect;
+use soh_math::Vec2;
+//-----------------------------------------------------------------------------
+
+/// Distribution of values counted in bins of equal width
+#[derive(Debug, Clone)]
+pub struct Histogram {
+    values: Vec<f32>,
+    bins: usize,
+    /// Range of the bins (fitted to the values if not set)
+    range: Option<(f32, f32)>,
+    /// Overrides the accent color of the theme
+    pub color: Option<Color>,
+}
+
+//-----------------------------------------------------------------------------
+// Constructor
+impl Histogram {
+    pub fn new(bins: usize) -> Self {
+        return Histogram {
+            values: vec![],
+            bins: bins.max(1),
+            range: None,
+            color: None,
+        };
+    }
+
+    /// Fixed range of the bins (values outside of it aren't counted)
+    pub fn range(mut self, min: f32, max: f32) -> Self {
+        self.range = Some((min, max));
+        return self;
+    }
+
+    pub fn color(mut self, color: impl Into<Color>) -> Self {
+        self.color = Some(color.into());
+        return self;
+    }
+}
+
+//-----------------------------------------------------------------------------
+// Getters
+impl Histogram {
+    pub fn values(&self) -> &[f32] {
+        return &self.values;
+    }
+
+    /// Range of the bins
+    pub fn bin_range(&self) -> (f32, f32) {
+        return self
+            .range
+            .unwrap_or_else(|| data_range(self.values.iter().copied()));
+    }
+
+    /// Number of values in each bin
+    pub fn counts(&self) -> Vec<usize> {
+        let (min, max) = self.bin_range();
+        let mut counts = vec![0; self.bins];
+
+        for &value in self.values.iter() {
+            if !(min..=max).contains(&value) {
+                continue;
+            }
+
+            // The maximum belongs to the last bin
+            let bin = ((value - min) / (max - min) * self.bins as f32) as usize;
+            counts[bin.min(self.bins - 1)] += 1;
+        }
+
+        return counts;
+    }
+}
+
+//-----------------------------------------------------------------------------
+// Specific implementation
+impl Histogram {
+    pub fn set_values(&mut self, values: &[f32]) {
+        self.values.clear();
+        self.values.extend_from_slice(values);
+    }
+
+    pub fn set_bins(&mut self, bins: usize) {
+        self.bins = bins.max(1);
+    }
+}
+
+impl Widget for Histogram {
+    fn measure(&self, ctx: &MeasureCtx, _available: Vec2<f32>) -> Vec2<f32> {
+        let line_height = ctx.text.line_height(ctx.theme.font_size);
+        return Vec2::new(line_height * 12.0, line_height * 8.0);
+    }
+
+    fn draw(&self, ctx: &mut DrawCtx) {
+        let counts = self.counts();
+        let max_count = counts.iter().copied().max().unwrap_or(0).max(1);
+
+        let x_range = self.bin_range();
+        let y_range = nice_range((0.0, max_count as f32));
+        let area = draw_axes(ctx, x_range, y_range);
+
+        let color = self.color.unwrap_or(ctx.theme.accent);
+        let bin_width = (x_range.1 - x_range.0) / self.bins as f32;
+
+        for (i, &count) in counts.iter().enumerate() {
+            if count == 0 {
+                continue;
+            }
+
+            let x = x_range.0 + i as f32 * bin_width;
+            let top_left = map_point(area, x_range, y_range, Vec2::new(x, count as f32));
+            let bottom_right = map_point(area, x_range, y_range, Vec2::new(x + bin_width, 0.0));
+
+            // 1px gap between the bars
+            let right = (bottom_right.x - 1.0).max(top_left.x + 1.0);
+            let bar = Rect::new(top_left, Vec2::new(right, bottom_right.y));
+            ctx.list.rect(bar, color);
+        }
+    }
+}
+
+//-----------------------------------------------------------------------------
@@ -0,0 +1,156 @@
+//-----------------------------------------------------------------------------
+use super::{DrawCtx, MeasureCtx, Widget};
+use crate::draw::{Color, TextureHandle};
+use crate::Rect;
+use soh_math::Vec2;
+use std::collections::HashMap;
+//-----------------------------------------------------------------------------
+
+/// Named icons packed into a single texture
+#[derive(Debug, Clone)]
+pub struct IconAtlas {
+    texture: TextureHandle,
+    /// Size of the texture in pixels
+    texture_size: Vec2<f32>,
+    /// Rects of the icons in pixels
+    icons: HashMap<String, Rect>,
+}
+
+/// Small square image ( toolbar buttons, file types ) taken from an [IconAtlas] or a glyph of an
+/// icon font. Tinted with the text color of the theme unless a color is set.
+#[derive(Debug, Clone)]
+pub struct Icon {
+    source: IconSource,
+    /// Overrides the font size of the theme
+    size: Option<f32>,
+    /// Overrides the text color of the theme
+    color: Option<Color>,
+}
+
+#[derive(Debug, Clone)]
+enum IconSource {
+    Atlas { texture: TextureHandle, uv: Rect },
+    Glyph(char),
+}
+
+//-----------------------------------------------------------------------------
+// Constructor
+impl IconAtlas {
+    pub fn new(texture: TextureHandle, texture_size: (u32, u32)) -> Self {
+        return IconAtlas {
+            texture,
+            texture_size: Vec2::new(texture_size.0 as f32, texture_size.1 as f32),
+            icons: HashMap::new(),
+        };
+    }
+
+    /// Atlas of icons of the same size, named row by row
+    pub fn grid<'a>(
+        texture: TextureHandle,
+        texture_size: (u32, u32),
+        icon_size: (u32, u32),
+        names: impl IntoIterator<Item = &'a str>,
+    ) -> Self {
+        let mut atlas = IconAtlas::new(texture, texture_size);
+        let columns = (texture_size.0 / icon_size.0.max(1)).max(1);
+        let size = Vec2::new(icon_size.0 as f32, icon_size.1 as f32);
+
+        for (i, name) in names.into_iter().enumerate() {
+            let (column, row) = (i as u32 % columns, i as u32 / columns);
+            let pos = Vec2::new(column as f32 * size.x, row as f32 * size.y);
+            atlas.add(name, Rect::from_pos_size(pos, size));
+        }
+
+        return atlas;
+    }
+}
+
+impl Icon {
+    /// Icon of the atlas, `None` if the atlas doesn't have it
+    pub fn from_atlas(atlas: &IconAtlas, name: &str) -> Option<Self> {
+        return Some(Icon {
+            source: IconSource::Atlas {
+                texture: atlas.texture,
+                uv: atlas.uv(name)?,
+            },
+            size: None,
+            color: None,
+        });
+    }
+
+    /// Glyph of an icon font
+    pub fn glyph(glyph: char) -> Self {
+        return Icon {
+            source: IconSource::Glyph(glyph),
+            size: None,
+            color: None,
+        };
+    }
+
+    pub fn size(mut self, size: f32) -> Self {
+        self.size = Some(size);
+        return self;
+    }
+
+    pub fn color(mut self, color: impl Into<Color>) -> Self {
+        self.color = Some(color.into());
+        return self;
+    }
+}
+
+//-----------------------------------------------------------------------------
+// Getters
+impl IconAtlas {
+    pub fn texture(&self) -> TextureHandle {
+        return self.texture;
+    }
+
+    pub fn contains(&self, name: &str) -> bool {
+        return self.icons.contains_key(name);
+    }
+
+    /// Part of the texture with the icon in normalized coordinates
+    pub fn uv(&self, name: &str) -> Option<Rect> {
+        let rect = self.icons.get(name)?;
+        let to_uv = |pos: Vec2<f32>| {
+            return Vec2::new(pos.x / self.texture_size.x, pos.y / self.texture_size.y);
+        };
+
+        return Some(Rect::new(to_uv(rect.min), to_uv(rect.max)));
+    }
+}
+
+//-----------------------------------------------------------------------------
+// Specific implementation
+impl IconAtlas {
+    /// Add an icon, `rect` is in pixels
+    pub fn add(&mut self, name: impl Into<String>, rect: Rect) {
+        self.icons.insert(name.into(), rect);
+    }
+}
+
+impl Widget for Icon {
+    fn measure(&self, ctx: &MeasureCtx, _available: Vec2<f32>) -> Vec2<f32> {
+        let size = self.size.unwrap_or(ctx.theme.font_size);
+        return Vec2::new(size, size);
+    }
+
+    fn draw(&self, ctx: &mut DrawCtx) {
+        let size = self.size.unwrap_or(ctx.theme.font_size);
+        let color = self.color.unwrap_or(ctx.theme.text);
+        let rect = Rect::from_center_size(ctx.rect.center(), Vec2::new(size, size));
+
+        match &self.source {
+            IconSource::Atlas { texture, uv } => ctx.list.image(rect, *texture, *uv, color),
+            IconSource::Glyph(glyph) => {
+                let mut buf = [0; 4];
+                let glyph = glyph.encode_utf8(&mut buf);
+                let glyph_size = ctx.text.text_size(glyph, size);
+                let pos = ctx.rect.center() - glyph_size / 2.0;
+                ctx.list.text(pos, glyph, size, color);
+            }
+        }
+    }
+}
+
+//-----------------------------------------------------------------------------
@@ -0,0 +1,142 @@
+//-----------------------------------------------------------------------------
+use super::{DrawCtx, MeasureCtx, Widget};
+use crate::draw::{Color, TextureHandle};
+use crate::Rect;
+use soh_math::color::WHITE;
+use soh_math::Vec2;
+//-----------------------------------------------------------------------------
+
+/// How an image is fitted into the rect of the widget
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum ImageFit {
+    /// Stretch to the whole rect
+    Fill,
+    /// Scale to fit inside the rect keeping the aspect ratio ( letterboxed )
+    #[default]
+    Contain,
+    /// Scale to cover the whole rect keeping the aspect ratio ( cropped )
+    Cover,
+    /// Keep the size of the image, centered and cropped to the rect
+    Original,
+}
+
+/// Texture ( thumbnails, previews, render targets )
+#[derive(Debug, Clone)]
+pub struct Image {
+    texture: TextureHandle,
+    /// Size of the texture in pixels
+    texture_size: Vec2<f32>,
+    /// Shown part of the texture in normalized coordinates
+    uv: Rect,
+    tint: Color,
+    fit: ImageFit,
+}
+
+//-----------------------------------------------------------------------------
+// Constructor
+impl Image {
+    /// * `texture_size`: size of the texture in pixels ( `soh_vk::Texture::size` )
+    pub fn new(texture: TextureHandle, texture_size: (u32, u32)) -> Self {
+        return Image {
+            texture,
+            texture_size: Vec2::new(texture_size.0 as f32, texture_size.1 as f32),
+            uv: Rect::new(Vec2::new(0.0, 0.0), Vec2::new(1.0, 1.0)),
+            tint: Color::opaque(WHITE),
+            fit: ImageFit::default(),
+        };
+    }
+
+    /// Show only a part of the texture ( normalized coordinates )
+    pub fn uv(mut self, uv: Rect) -> Self {
+        self.uv = uv;
+        return self;
+    }
+
+    pub fn tint(mut self, tint: impl Into<Color>) -> Self {
+        self.tint = tint.into();
+        return self;
+    }
+
+    pub fn fit(mut self, fit: ImageFit) -> Self {
+        self.fit = fit;
+        return self;
+    }
+}
+
+//-----------------------------------------------------------------------------
+// Getters
+impl Image {
+    pub fn texture(&self) -> TextureHandle {
+        return self.texture;
+    }
+
+    /// Size of the shown part of the texture in pixels
+    pub fn image_size(&self) -> Vec2<f32> {
+        return Vec2::new(
+            self.uv.width().abs() * self.texture_size.x,
+            self.uv.height().abs() * self.texture_size.y,
+        );
+    }
+}
+
+//-----------------------------------------------------------------------------
+// Specific implementation
+impl Image {
+    /// Replace the texture ( e.g. after a thumbnail has been loaded )
+    pub fn set_texture(&mut self, texture: TextureHandle, texture_size: (u32, u32)) {
+        self.texture = texture;
+        self.texture_size = Vec2::new(texture_size.0 as f32, texture_size.1 as f32);
+    }
+
+    /// Rect of the image and the part of the texture shown in it
+    pub fn placement(&self, rect: Rect) -> (Rect, Rect) {
+        let size = self.image_size();
+        if size.x <= 0.0 || size.y <= 0.0 {
+            return (rect, self.uv);
+        }
+
+        let scale = Vec2::new(rect.width() / size.x, rect.height() / size.y);
+        let scale = match self.fit {
+            ImageFit::Fill => return (rect, self.uv),
+            ImageFit::Contain => scale.x.min(scale.y),
+            ImageFit::Cover => scale.x.max(scale.y),
+            ImageFit::Original => 1.0,
+        };
+
+        /*
+         * Center the scaled image and crop it to the rect
+         */
+        let image = Rect::from_center_size(rect.center(), size * scale);
+        let shown = image.intersection(&rect);
+
+        let uv_at = |pos: Vec2<f32>| {
+            let t = Vec2::new(
+                (pos.x - image.min.x) / image.width(),
+                (pos.y - image.min.y) / image.height(),
+            );
+            return Vec2::new(
+                self.uv.min.x + t.x * self.uv.width(),
+                self.uv.min.y + t.y * self.uv.height(),
+            );
+        };
+
+        return (shown, Rect::new(uv_at(shown.min), uv_at(shown.max)));
+    }
+}
+
+impl Widget for Image {
+    fn measure(&self, _ctx: &MeasureCtx, _available: Vec2<f32>) -> Vec2<f32> {
+        return self.image_size();
+    }
+
+    fn draw(&self, ctx: &mut DrawCtx) {
+        let (rect, uv) = self.placement(ctx.rect);
+        if rect.is_empty() {
+            return;
+        }
+
+        ctx.list.image(rect, self.texture, uv, self.tint);
+    }
+}
+
+//-----------------------------------------------------------------------------
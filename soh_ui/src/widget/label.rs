@@ -0,0 +1,94 @@
+//-----------------------------------------------------------------------------
+use super::{DrawCtx, MeasureCtx, Widget};
+use crate::draw::Color;
+use soh_math::Vec2;
+//-----------------------------------------------------------------------------
+
+/// Single line of text
+#[derive(Debug, Clone)]
+pub struct Label {
+    text: String,
+    /// Overrides the font size of the theme
+    font_size: Option<f32>,
+    /// Overrides the text color of the theme
+    color: Option<Color>,
+}
+
+//-----------------------------------------------------------------------------
+// Constructor
+impl Label {
+    pub fn new(text: impl Into<String>) -> Self {
+        return Label {
+            text: text.into(),
+            font_size: None,
+            color: None,
+        };
+    }
+
+    pub fn font_size(mut self, size: f32) -> Self {
+        self.font_size = Some(size);
+        return self;
+    }
+
+    pub fn color(mut self, color: impl Into<Color>) -> Self {
+        self.color = Some(color.into());
+        return self;
+    }
+}
+
+//-----------------------------------------------------------------------------
+// Getters
+impl Label {
+    pub fn text(&self) -> &str {
+        return &self.text;
+    }
+}
+
+//-----------------------------------------------------------------------------
+// Specific implementation
+impl Label {
+    pub fn set_text(&mut self, text: impl Into<String>) {
+        self.text = text.into();
+    }
+}
+
+impl Widget for Label {
+    fn measure(&self, ctx: &MeasureCtx, _available: Vec2<f32>) -> Vec2<f32> {
+        let font_size = self.font_size.unwrap_or(ctx.theme.font_size);
+        return ctx.text.text_size(&self.text, font_size);
+    }
+
+    fn draw(&self, ctx: &mut DrawCtx) {
+        let font_size = self.font_size.unwrap_or(ctx.theme.font_size);
+        let color = self.color.unwrap_or(ctx.theme.text);
+
+        ctx.list.text(ctx.rect.min, &self.text, font_size, color);
+    }
+}
+
+#[cfg(feature = "serde")]
+impl crate::data::WidgetData for Label {
+    const TYPE: &'static str = "Label";
+
+    fn from_props(props: &crate::data::Props) -> crate::Result<Self> {
+        return Ok(Label {
+            text: props.string("text")?.unwrap_or_default().to_owned(),
+            font_size: props.number("font_size")?.map(|size| size as f32),
+            color: props.color("color")?,
+        });
+    }
+
+    fn to_props(&self) -> crate::data::Props {
+        let mut props = crate::data::Props::new();
+        props.set("text", self.text.as_str());
+        if let Some(size) = self.font_size {
+            props.set("font_size", size);
+        }
+        if let Some(color) = self.color {
+            props.set("color", color);
+        }
+        return props;
+    }
+}
+
+//-----------------------------------------------------------------------------
@@ -0,0 +1,173 @@
+//-----------------------------------------------------------------------------
+use super::rows::row_height;
+use super::{DrawCtx, EventCtx, MeasureCtx, Response, Widget};
+use crate::input::{Event, Key, PointerButton};
+use crate::panel::PanelStyle;
+use crate::Rect;
+use soh_math::Vec2;
+//-----------------------------------------------------------------------------
+
+/// List of actions, usually shown in a popup (see [`crate::Ui::set_context_menu`])
+///
+/// Choosing an item calls its action and closes the popup containing the menu.
+pub struct Menu {
+    items: Vec<MenuItem>,
+    highlighted: Option<usize>,
+}
+
+struct MenuItem {
+    label: String,
+    action: Box<dyn FnMut()>,
+}
+
+//-----------------------------------------------------------------------------
+// Constructor
+impl Menu {
+    pub fn new() -> Self {
+        return Menu {
+            items: vec![],
+            highlighted: None,
+        };
+    }
+
+    pub fn item(mut self, label: impl Into<String>, action: impl FnMut() + 'static) -> Self {
+        self.items.push(MenuItem {
+            label: label.into(),
+            action: Box::new(action),
+        });
+        return self;
+    }
+}
+
+impl Default for Menu {
+    fn default() -> Self {
+        return Self::new();
+    }
+}
+
+//-----------------------------------------------------------------------------
+// Getters
+impl Menu {
+    pub fn len(&self) -> usize {
+        return self.items.len();
+    }
+    pub fn is_empty(&self) -> bool {
+        return self.items.is_empty();
+    }
+    pub fn label(&self, index: usize) -> &str {
+        return &self.items[index].label;
+    }
+    /// Item under the pointer or chosen with the arrow keys
+    pub fn highlighted(&self) -> Option<usize> {
+        return self.highlighted;
+    }
+}
+
+//-----------------------------------------------------------------------------
+// Specific implementation
+impl Menu {
+    /// Call the action of the item and close the popup
+    fn activate(&mut self, ctx: &mut EventCtx, index: usize) {
+        (self.items[index].action)();
+        ctx.close_overlay();
+    }
+
+    fn item_at(&self, ctx: &EventCtx, pos: Vec2<f32>) -> Option<usize> {
+        if !ctx.rect.contains(pos) {
+            return None;
+        }
+
+        let row_height = row_height(ctx.text, ctx.theme);
+        let index = ((pos.y - ctx.rect.min.y - ctx.theme.padding) / row_height).floor();
+        return (index >= 0.0 && (index as usize) < self.items.len()).then_some(index as usize);
+    }
+
+    fn on_key(&mut self, ctx: &mut EventCtx, key: Key) -> Response {
+        let last = self.items.len().checked_sub(1);
+        let Some(last) = last else {
+            return Response::Ignored;
+        };
+
+        self.highlighted = match (key, self.highlighted) {
+            (Key::Down, Some(index)) if index < last => Some(index + 1),
+            (Key::Down, _) | (Key::Home, _) => Some(0),
+            (Key::Up, Some(index)) if index > 0 => Some(index - 1),
+            (Key::Up, _) | (Key::End, _) => Some(last),
+            (Key::Enter | Key::Space, Some(index)) => {
+                self.activate(ctx, index);
+                Some(index)
+            }
+            _ => return Response::Ignored,
+        };
+        return Response::Consumed;
+    }
+}
+
+impl Widget for Menu {
+    fn measure(&self, ctx: &MeasureCtx, _available: Vec2<f32>) -> Vec2<f32> {
+        let theme = ctx.theme;
+        let width = self
+            .items
+            .iter()
+            .map(|item| ctx.text.text_size(&item.label, theme.font_size).x)
+            .fold(0.0, f32::max);
+
+        return Vec2::new(
+            width + 4.0 * theme.padding,
+            self.items.len() as f32 * row_height(ctx.text, theme) + 2.0 * theme.padding,
+        );
+    }
+
+    fn draw(&self, ctx: &mut DrawCtx) {
+        let theme = ctx.theme;
+        let row_height = row_height(ctx.text, theme);
+        let line_height = ctx.text.line_height(theme.font_size);
+
+        let style = PanelStyle::from_theme(theme).background(theme.surface);
+        ctx.list.panel(ctx.rect, &style);
+
+        for (i, item) in self.items.iter().enumerate() {
+            let y = ctx.rect.min.y + theme.padding + i as f32 * row_height;
+            let row = Rect::new(
+                Vec2::new(ctx.rect.min.x, y),
+                Vec2::new(ctx.rect.max.x, y + row_height),
+            );
+
+            if self.highlighted == Some(i) {
+                ctx.list.rect(row, theme.selection);
+            }
+
+            let pos = Vec2::new(
+                row.min.x + 2.0 * theme.padding,
+                row.center().y - line_height * 0.5,
+            );
+            ctx.list.text(pos, &item.label, theme.font_size, theme.text);
+        }
+    }
+
+    fn on_event(&mut self, ctx: &mut EventCtx, event: &Event) -> Response {
+        return match event {
+            Event::PointerMoved { pos } => {
+                self.highlighted = self.item_at(ctx, *pos);
+                Response::Consumed
+            }
+            Event::PointerDown {
+                pos,
+                button: PointerButton::Primary,
+            } => {
+                if let Some(index) = self.item_at(ctx, *pos) {
+                    self.activate(ctx, index);
+                }
+                Response::Consumed
+            }
+            Event::KeyDown { key, .. } if ctx.focused => self.on_key(ctx, *key),
+            _ => Response::Ignored,
+        };
+    }
+
+    fn is_focusable(&self) -> bool {
+        return true;
+    }
+}
+
+//-----------------------------------------------------------------------------
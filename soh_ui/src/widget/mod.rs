@@ -0,0 +1,1486 @@
+//-----------------------------------------------------------------------------
+//! Widgets
+//!
+//! A widget only knows how to measure, draw itself and react to events. The [`crate::Ui`] owns
+//! the widgets, lays them out and routes the events to them.
+//-----------------------------------------------------------------------------
+mod container;
+mod heatmap;
+mod histogram;
+mod icon;
+mod image;
+mod label;
+mod menu;
+mod plot;
+mod rows;
+mod scroll_area;
+mod splitter;
+mod tab_bar;
+mod table;
+mod text_input;
+mod tree_view;
+//-----------------------------------------------------------------------------
+pub use container::*;
+pub use heatmap::*;
+pub use histogram::*;
+pub use icon::*;
+pub use image::*;
+pub use label::*;
+pub use menu::*;
+pub use plot::{LinePlot, Series, PALETTE};
+pub use scroll_area::*;
+pub use splitter::*;
+pub use tab_bar::*;
+pub use table::*;
+pub use text_input::*;
+pub use tree_view::*;
+//-----------------------------------------------------------------------------
+use crate::clipboard::Clipboard;
+use crate::drag::DragData;
+use crate::draw::DrawList;
+use crate::input::Event;
+use crate::layout::Style;
+use crate::text::TextMeasure;
+use crate::theme::Theme;
+use crate::Rect;
+use soh_math::Vec2;
+use std::any::Any;
+//-----------------------------------------------------------------------------
+
+pub trait Widget: Any {
+    /// Size of the content (used by the layout for `Auto` sizes of leaf nodes)
+    fn measure(&self, _ctx: &MeasureCtx, _available: Vec2<f32>) -> Vec2<f32> {
+        return Vec2::new(0.0, 0.0);
+    }
+
+    /// Draw the widget (the children are drawn after it)
+    fn draw(&self, ctx: &mut DrawCtx);
+
+    /// Draw on top of the children (e.g. scrollbars)
+    fn draw_overlay(&self, _ctx: &mut DrawCtx) {}
+
+    /// Adjust the style of the widget and of its children before the layout (e.g. to apply sizes
+    /// chosen by the user)
+    fn adjust_styles(&self, _style: &mut Style, _children: &mut [Style]) {}
+
+    /// Whether the children are only visible inside the rect of the widget
+    fn clips_children(&self) -> bool {
+        return false;
+    }
+
+    /// React to an event, unhandled events are passed to the parent
+    fn on_event(&mut self, _ctx: &mut EventCtx, _event: &Event) -> Response {
+        return Response::Ignored;
+    }
+
+    /// Whether the widget can receive keyboard focus (focusable widgets are reached with Tab and
+    /// Shift+Tab in the order of the tree)
+    fn is_focusable(&self) -> bool {
+        return false;
+    }
+
+    /// Whether the arrow keys move the focus between the focusable widgets inside this one (if
+    /// the focused widget doesn't handle them)
+    fn is_focus_group(&self) -> bool {
+        return false;
+    }
+
+    /// Whether the widget can receive the dragged payload (see [`crate::drag`])
+    fn accepts_drop(&self, _drag: &DragData) -> bool {
+        return false;
+    }
+
+    /// Receive the payload dropped on the widget (only called if it accepts the drop)
+    fn on_drop(&mut self, _ctx: &mut EventCtx, _pos: Vec2<f32>, _drag: DragData) {}
+}
+
+/// Whether the event was handled by the widget
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Response {
+    Ignored,
+    Consumed,
+}
+
+pub struct MeasureCtx<'a> {
+    pub text: &'a dyn TextMeasure,
+    pub theme: &'a Theme,
+}
+
+pub struct DrawCtx<'a> {
+    pub rect: Rect,
+    pub list: &'a mut DrawList,
+    pub text: &'a dyn TextMeasure,
+    pub theme: &'a Theme,
+    pub focused: bool,
+    pub hovered: bool,
+    /// Hover state eased by the transition of the widget (0.0 - 1.0, without a transition it's
+    /// `hovered`), used to blend the hover colors
+    pub hover: f32,
+    /// A drag which the widget accepts is over it
+    pub drop_hover: bool,
+}
+
+pub struct EventCtx<'a> {
+    pub rect: Rect,
+    pub text: &'a dyn TextMeasure,
+    pub theme: &'a Theme,
+    pub clipboard: &'a mut dyn Clipboard,
+    pub focused: bool,
+
+    pub(crate) focus_request: Option<FocusRequest>,
+    pub(crate) capture_pointer: bool,
+    pub(crate) drag_request: Option<(Box<dyn Any>, DrawList)>,
+    pub(crate) close_request: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum FocusRequest {
+    Take,
+    Release,
+    Next,
+    Previous,
+}
+
+//-----------------------------------------------------------------------------
+
+impl Response {
+    pub fn is_consumed(&self) -> bool {
+        return *self == Response::Consumed;
+    }
+}
+
+impl EventCtx<'_> {
+    /// Move the keyboard focus to this widget
+    pub fn request_focus(&mut self) {
+        self.focus_request = Some(FocusRequest::Take);
+    }
+
+    /// Remove the keyboard focus from this widget
+    pub fn release_focus(&mut self) {
+        self.focus_request = Some(FocusRequest::Release);
+    }
+
+    /// Move the keyboard focus to the next focusable widget (like Tab)
+    pub fn focus_next(&mut self) {
+        self.focus_request = Some(FocusRequest::Next);
+    }
+
+    /// Move the keyboard focus to the previous focusable widget (like Shift+Tab)
+    pub fn focus_previous(&mut self) {
+        self.focus_request = Some(FocusRequest::Previous);
+    }
+
+    /// Receive all pointer events until the pointer button is released
+    pub fn capture_pointer(&mut self) {
+        self.capture_pointer = true;
+    }
+
+    /// Start dragging the payload, `preview` is drawn relative to the pointer
+    pub fn start_drag<T: Any>(&mut self, payload: T, preview: DrawList) {
+        self.drag_request = Some((Box::new(payload), preview));
+    }
+
+    /// Start dragging the payload with a label as the preview
+    pub fn start_drag_with_label<T: Any>(&mut self, payload: T, label: &str) {
+        let preview = crate::drag::label_preview(label, self.text, self.theme);
+        self.start_drag(payload, preview);
+    }
+
+    /// Close the modal or popup containing this widget (see [`crate::overlay`])
+    pub fn close_overlay(&mut self) {
+        self.close_request = true;
+    }
+}
+
+//-----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::input::{Key, Modifiers, PointerButton};
+    use crate::Ui;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    const CTRL: Modifiers = Modifiers {
+        ctrl: true,
+        ..Modifiers::NONE
+    };
+    const SHIFT: Modifiers = Modifiers {
+        shift: true,
+        ..Modifiers::NONE
+    };
+
+    fn key(ui: &mut Ui, key: Key, modifiers: Modifiers) {
+        ui.handle_event(&Event::KeyDown { key, modifiers });
+    }
+
+    fn click(ui: &mut Ui, pos: Vec2<f32>) {
+        let button = PointerButton::Primary;
+        ui.handle_event(&Event::PointerDown { pos, button });
+        ui.handle_event(&Event::PointerUp { pos, button });
+    }
+
+    fn setup() -> (Ui, crate::WidgetId) {
+        let mut ui = Ui::new(Style::default());
+        let input = ui.add(ui.root(), TextInput::new(), Style::fixed(200.0, 24.0));
+        ui.layout(Vec2::new(400.0, 300.0));
+
+        return (ui, input);
+    }
+
+    fn text(ui: &Ui, id: crate::WidgetId) -> String {
+        return ui.widget::<TextInput>(id).unwrap().text().to_owned();
+    }
+
+    #[test]
+    fn test_text_input_typing() {
+        let (mut ui, input) = setup();
+
+        // Not focused yet
+        ui.handle_event(&Event::Text("a".into()));
+        assert_eq!(text(&ui, input), "");
+
+        click(&mut ui, Vec2::new(10.0, 10.0));
+        assert_eq!(ui.focused(), Some(input));
+
+        ui.handle_event(&Event::Text("hello wörld".into()));
+        key(&mut ui, Key::Backspace, Modifiers::NONE);
+        key(&mut ui, Key::Left, Modifiers::NONE);
+        key(&mut ui, Key::Left, Modifiers::NONE);
+        ui.handle_event(&Event::Text("\u{8}x".into()));
+        assert_eq!(text(&ui, input), "hello wöxrl");
+
+        key(&mut ui, Key::Backspace, CTRL);
+        assert_eq!(text(&ui, input), "hello rl");
+
+        key(&mut ui, Key::Home, Modifiers::NONE);
+        key(&mut ui, Key::Delete, CTRL);
+        assert_eq!(text(&ui, input), " rl");
+
+        key(&mut ui, Key::Escape, Modifiers::NONE);
+        assert_eq!(ui.focused(), None);
+    }
+
+    #[test]
+    fn test_text_input_selection_clipboard() {
+        let (mut ui, input) = setup();
+        let changes = Rc::new(RefCell::new(vec![]));
+
+        {
+            let changes = changes.clone();
+            ui.widget_mut::<TextInput>(input)
+                .unwrap()
+                .set_text("one two three")
+                .on_change(move |text| changes.borrow_mut().push(text.to_owned()));
+        }
+
+        click(&mut ui, Vec2::new(10.0, 10.0));
+
+        // Select "two" and cut it
+        key(&mut ui, Key::Home, Modifiers::NONE);
+        key(&mut ui, Key::Right, CTRL);
+        key(&mut ui, Key::Right, Modifiers::NONE);
+        key(&mut ui, Key::Right, CTRL | SHIFT);
+        assert_eq!(
+            ui.widget::<TextInput>(input).unwrap().selected_text(),
+            "two"
+        );
+
+        key(&mut ui, Key::Char('x'), CTRL);
+        assert_eq!(text(&ui, input), "one  three");
+
+        key(&mut ui, Key::End, Modifiers::NONE);
+        key(&mut ui, Key::Char('v'), CTRL);
+        assert_eq!(text(&ui, input), "one  threetwo");
+
+        // Select all and replace with typed text
+        key(&mut ui, Key::Char('a'), CTRL);
+        ui.handle_event(&Event::Text("z".into()));
+        assert_eq!(text(&ui, input), "z");
+
+        assert_eq!(*changes.borrow(), ["one  three", "one  threetwo", "z"]);
+    }
+
+    #[test]
+    fn test_text_input_pointer() {
+        let (mut ui, input) = setup();
+        ui.widget_mut::<TextInput>(input)
+            .unwrap()
+            .set_text("abcdef");
+
+        // Default metrics: 16px font with 8px advance, 4px padding
+        click(&mut ui, Vec2::new(4.0 + 8.0 * 2.0 + 1.0, 10.0));
+        assert_eq!(ui.widget::<TextInput>(input).unwrap().cursor(), 2);
+
+        // Drag to select
+        let button = PointerButton::Primary;
+        ui.handle_event(&Event::PointerDown {
+            pos: Vec2::new(4.0 + 8.0, 10.0),
+            button,
+        });
+        ui.handle_event(&Event::PointerMoved {
+            pos: Vec2::new(300.0, 10.0),
+        });
+        ui.handle_event(&Event::PointerUp {
+            pos: Vec2::new(300.0, 10.0),
+            button,
+        });
+        assert_eq!(
+            ui.widget::<TextInput>(input).unwrap().selected_text(),
+            "bcdef"
+        );
+
+        // Clicking elsewhere removes the focus
+        click(&mut ui, Vec2::new(300.0, 200.0));
+        assert_eq!(ui.focused(), None);
+    }
+
+    #[test]
+    fn test_scroll_area() {
+        use crate::layout::Direction;
+
+        let mut ui = Ui::new(Style::default());
+        let area = ui.add(
+            ui.root(),
+            ScrollArea::new(),
+            Style {
+                direction: Direction::Column,
+                ..Style::fixed(100.0, 100.0)
+            },
+        );
+        let rows = (0..10)
+            .map(|i| {
+                ui.add(
+                    area,
+                    Label::new(format!("row {i}")),
+                    Style::fixed(50.0, 20.0),
+                )
+            })
+            .collect::<Vec<_>>();
+        ui.layout(Vec2::new(400.0, 300.0));
+
+        // Rows keep their size instead of shrinking into the area
+        let scroll = ui.widget::<ScrollArea>(area).unwrap();
+        assert_eq!(scroll.content_size(), Vec2::new(50.0, 200.0));
+        assert_eq!(ui.rect(rows[9]).min, Vec2::new(0.0, 180.0));
+
+        // Wheel scrolling (clamped to the content)
+        let pos = Vec2::new(10.0, 10.0);
+        let delta = Vec2::new(0.0, -50.0);
+        assert!(ui.handle_event(&Event::Scroll { pos, delta }).is_consumed());
+        ui.layout(Vec2::new(400.0, 300.0));
+        assert_eq!(ui.rect(rows[0]).min, Vec2::new(0.0, -50.0));
+
+        let delta = Vec2::new(0.0, -500.0);
+        ui.handle_event(&Event::Scroll { pos, delta });
+        ui.layout(Vec2::new(400.0, 300.0));
+        assert_eq!(ui.rect(rows[9]).max, Vec2::new(50.0, 100.0));
+
+        // Already at the end: the event isn't consumed
+        assert!(!ui.handle_event(&Event::Scroll { pos, delta }).is_consumed());
+
+        // Dragging the thumb (half of the track: 100px viewport of 200px content)
+        let button = PointerButton::Primary;
+        ui.handle_event(&Event::PointerDown {
+            pos: Vec2::new(96.0, 75.0),
+            button,
+        });
+        ui.handle_event(&Event::PointerMoved {
+            pos: Vec2::new(96.0, 50.0),
+        });
+        ui.handle_event(&Event::PointerUp {
+            pos: Vec2::new(96.0, 50.0),
+            button,
+        });
+        let scroll = ui.widget::<ScrollArea>(area).unwrap();
+        assert_eq!(scroll.offset(), Vec2::new(0.0, 50.0));
+
+        // Only the visible rows are drawn
+        ui.layout(Vec2::new(400.0, 300.0));
+        let mut list = crate::draw::DrawList::new();
+        ui.draw(&mut list);
+        let num_of_texts = list
+            .commands()
+            .iter()
+            .filter(|cmd| matches!(cmd, crate::draw::DrawCmd::Text { .. }))
+            .count();
+        assert_eq!(num_of_texts, 6);
+    }
+
+    #[test]
+    fn test_virtualized_scroll_area() {
+        let created = Rc::new(RefCell::new(0));
+
+        let mut ui = Ui::new(Style::default());
+        let factory = {
+            let created = created.clone();
+            move |row: usize| -> Box<dyn Widget> {
+                *created.borrow_mut() += 1;
+                return Box::new(Label::new(format!("row {row}")));
+            }
+        };
+        let area = ui.add(
+            ui.root(),
+            ScrollArea::virtualized(100_000, 20.0, factory),
+            Style::fixed(200.0, 100.0),
+        );
+        ui.layout(Vec2::new(400.0, 300.0));
+
+        // 5 visible rows and 2 overscan rows after them
+        assert_eq!(ui.children(area).len(), 7);
+        assert_eq!(*created.borrow(), 7);
+        let scroll = ui.widget::<ScrollArea>(area).unwrap();
+        assert_eq!(scroll.content_size(), Vec2::new(200.0, 2_000_000.0));
+
+        ui.widget_mut::<ScrollArea>(area)
+            .unwrap()
+            .scroll_to_row(50_000);
+        ui.layout(Vec2::new(400.0, 300.0));
+        assert_eq!(ui.children(area).len(), 9);
+
+        let row = |ui: &Ui, text: &str| {
+            return ui.children(area).iter().copied().find(|&id| {
+                return ui.widget::<Label>(id).unwrap().text() == text;
+            });
+        };
+        let first = row(&ui, "row 50000").unwrap();
+        assert_eq!(
+            ui.rect(first),
+            Rect::from_pos_size(Vec2::new(0.0, 0.0), Vec2::new(200.0, 20.0))
+        );
+        assert!(row(&ui, "row 49998").is_some());
+        assert!(row(&ui, "row 49997").is_none());
+
+        // Scrolling by one row only creates one widget
+        let count = *created.borrow();
+        let delta = Vec2::new(0.0, -20.0);
+        ui.handle_event(&Event::Scroll {
+            pos: Vec2::new(10.0, 10.0),
+            delta,
+        });
+        ui.layout(Vec2::new(400.0, 300.0));
+        assert_eq!(*created.borrow(), count + 1);
+        assert_eq!(ui.children(area).len(), 9);
+
+        ui.widget_mut::<ScrollArea>(area).unwrap().set_row_count(3);
+        ui.layout(Vec2::new(400.0, 300.0));
+        assert_eq!(ui.children(area).len(), 3);
+        assert_eq!(
+            ui.rect(row(&ui, "row 2").unwrap()).min,
+            Vec2::new(0.0, 40.0)
+        );
+    }
+
+    #[test]
+    fn test_observable() {
+        use crate::observable::Observable;
+
+        let count = Observable::new(1);
+        let doubled = count.map(|count| count * 2);
+        let seen = Rc::new(RefCell::new(vec![]));
+
+        let subscription = {
+            let seen = seen.clone();
+            doubled.subscribe(move |value| seen.borrow_mut().push(*value))
+        };
+
+        count.set(2);
+        count.update(|count| *count += 1);
+        count.set_if_changed(3);
+        assert_eq!(doubled.get(), 6);
+        assert_eq!(count.version(), 2);
+
+        drop(subscription);
+        count.set(10);
+        assert_eq!(doubled.get(), 20);
+        assert_eq!(*seen.borrow(), [4, 6]);
+    }
+
+    #[test]
+    fn test_bindings() {
+        use crate::observable::Observable;
+
+        let name = Observable::new(String::from("Alice"));
+        let show_details = Observable::new(false);
+
+        let mut ui = Ui::new(Style::default());
+        let label = ui.add(ui.root(), Label::new(""), Style::default());
+        let details = ui.add(ui.root(), Label::new("details"), Style::default());
+        let input = ui.add(ui.root(), TextInput::new(), Style::fixed(200.0, 24.0));
+
+        ui.bind(label, &name, |label: &mut Label, name: &String| {
+            label.set_text(format!("Hello, {name}"));
+        });
+        ui.bind(input, &name, |input: &mut TextInput, name: &String| {
+            if input.text() != name {
+                input.set_text(name.clone());
+            }
+        });
+        ui.bind_style(details, &show_details, |style, show| {
+            style.display = match show {
+                true => crate::layout::Display::Flex,
+                false => crate::layout::Display::None,
+            };
+        });
+        {
+            let name = name.clone();
+            ui.widget_mut::<TextInput>(input)
+                .unwrap()
+                .on_change(move |text| name.set(text.to_owned()));
+        }
+        ui.layout(Vec2::new(400.0, 300.0));
+
+        assert_eq!(ui.widget::<Label>(label).unwrap().text(), "Hello, Alice");
+        assert!(!ui.is_visible(details));
+        assert_eq!(ui.rect(details).size(), Vec2::new(0.0, 0.0));
+
+        // Typing updates the state, which updates the label
+        let input_pos = ui.rect(input).center();
+        click(&mut ui, input_pos);
+        ui.handle_event(&Event::Text("!".into()));
+        show_details.set(true);
+        ui.layout(Vec2::new(400.0, 300.0));
+
+        assert_eq!(name.get(), "Alice!");
+        assert_eq!(ui.widget::<Label>(label).unwrap().text(), "Hello, Alice!");
+        assert!(ui.is_visible(details));
+        assert!(ui.rect(details).width() > 0.0);
+
+        // Removed widgets drop their bindings
+        ui.remove(label);
+        name.set(String::from("Bob"));
+        ui.layout(Vec2::new(400.0, 300.0));
+        assert_eq!(ui.widget::<TextInput>(input).unwrap().text(), "Bob");
+    }
+
+    #[test]
+    fn test_theme_hierarchy() {
+        use crate::draw::{Color, DrawCmd, DrawList};
+        use crate::theme::Theme;
+        use soh_math::color::Rgb;
+
+        let mut ui = Ui::new(Style::default());
+        let panel = ui.add(ui.root(), Container::panel(), Style::fill());
+        let themed = ui.add(panel, Label::new("themed"), Style::default());
+        let red = Color::opaque(Rgb::new(255, 0, 0));
+        ui.add(panel, Label::new("custom").color(red), Style::default());
+        let big = ui.add(ui.root(), Label::new("big"), Style::default());
+
+        ui.set_theme(panel, Some(Theme::light()));
+        ui.set_theme(
+            big,
+            Some(Theme {
+                font_size: 32.0,
+                ..Theme::dark()
+            }),
+        );
+        ui.layout(Vec2::new(400.0, 300.0));
+
+        assert_eq!(*ui.theme(ui.root()), Theme::dark());
+        assert_eq!(*ui.theme(themed), Theme::light());
+        // Monospace metrics: the advance is half of the font size
+        assert_eq!(ui.rect(big).width(), 3.0 * 16.0);
+
+        let mut list = DrawList::new();
+        ui.draw(&mut list);
+
+        let text_color = |text: &str| {
+            return list.commands().iter().find_map(|cmd| match cmd {
+                DrawCmd::Text { text: t, color, .. } if t == text => Some(*color),
+                _ => None,
+            });
+        };
+        assert_eq!(text_color("themed"), Some(Theme::light().text));
+        assert_eq!(text_color("custom"), Some(red));
+        assert_eq!(text_color("big"), Some(Theme::dark().text));
+
+        ui.set_theme(panel, None);
+        assert_eq!(*ui.theme(themed), Theme::dark());
+        assert_eq!(ui.rect(themed).width(), 6.0 * 8.0);
+    }
+
+    #[test]
+    fn test_focus_navigation() {
+        use crate::layout::{Direction, Wrap};
+
+        struct Focusable;
+        impl Widget for Focusable {
+            fn draw(&self, _ctx: &mut DrawCtx) {}
+            fn is_focusable(&self) -> bool {
+                return true;
+            }
+        }
+
+        let mut ui = Ui::new(Style::default());
+        let root = ui.root();
+        let input = ui.add(root, TextInput::new(), Style::fixed(100.0, 24.0));
+
+        // 2x2 grid
+        let group = ui.add(
+            root,
+            Container::new().focus_group(),
+            Style {
+                wrap: Wrap::Wrap,
+                ..Style::fixed(40.0, 40.0)
+            },
+        );
+        let cells = (0..4)
+            .map(|_| ui.add(group, Focusable, Style::fixed(20.0, 20.0)))
+            .collect::<Vec<_>>();
+
+        let hidden = ui.add(
+            root,
+            TextInput::new(),
+            Style {
+                direction: Direction::Row,
+                ..Style::fixed(100.0, 24.0)
+            },
+        );
+        ui.set_visible(hidden, false);
+        ui.layout(Vec2::new(400.0, 300.0));
+
+        // Tab goes through the widgets in the order of the tree
+        key(&mut ui, Key::Tab, Modifiers::NONE);
+        assert_eq!(ui.focused(), Some(input));
+        assert!(ui.focus_visible());
+        key(&mut ui, Key::Tab, Modifiers::NONE);
+        assert_eq!(ui.focused(), Some(cells[0]));
+
+        // Arrow keys move inside the group
+        key(&mut ui, Key::Right, Modifiers::NONE);
+        assert_eq!(ui.focused(), Some(cells[1]));
+        key(&mut ui, Key::Down, Modifiers::NONE);
+        assert_eq!(ui.focused(), Some(cells[3]));
+        key(&mut ui, Key::Left, Modifiers::NONE);
+        assert_eq!(ui.focused(), Some(cells[2]));
+        key(&mut ui, Key::Up, Modifiers::NONE);
+        assert_eq!(ui.focused(), Some(cells[0]));
+        let response = ui.handle_event(&Event::KeyDown {
+            key: Key::Left,
+            modifiers: Modifiers::NONE,
+        });
+        assert!(!response.is_consumed());
+        assert_eq!(ui.focused(), Some(cells[0]));
+
+        // The focused widget is highlighted
+        let mut list = DrawList::new();
+        ui.draw(&mut list);
+        let accent = ui.theme(cells[0]).accent;
+        let outline = list
+            .commands()
+            .iter()
+            .filter(
+                |cmd| matches!(cmd, crate::draw::DrawCmd::Rect { color, .. } if *color == accent),
+            )
+            .count();
+        assert_eq!(outline, 4);
+
+        // Shift+Tab goes back and wraps around (skipping hidden widgets)
+        key(&mut ui, Key::Tab, SHIFT);
+        assert_eq!(ui.focused(), Some(input));
+        key(&mut ui, Key::Tab, SHIFT);
+        assert_eq!(ui.focused(), Some(cells[3]));
+
+        ui.set_visible(hidden, true);
+        key(&mut ui, Key::Tab, Modifiers::NONE);
+        assert_eq!(ui.focused(), Some(hidden));
+
+        // Focusing with the pointer doesn't highlight
+        click(&mut ui, Vec2::new(10.0, 10.0));
+        assert_eq!(ui.focused(), Some(input));
+        assert!(!ui.focus_visible());
+    }
+
+    #[test]
+    fn test_gamepad_navigation() {
+        use crate::layout::Direction;
+        use crate::nav::{GamepadButton, GamepadInput, REPEAT_DELAY};
+        use crate::overlay::Placement;
+
+        #[derive(Default)]
+        struct Button {
+            presses: usize,
+        }
+        impl Widget for Button {
+            fn draw(&self, _ctx: &mut DrawCtx) {}
+            fn on_event(&mut self, _ctx: &mut EventCtx, event: &Event) -> Response {
+                if let Event::KeyDown {
+                    key: Key::Enter, ..
+                } = event
+                {
+                    self.presses += 1;
+                    return Response::Consumed;
+                }
+                return Response::Ignored;
+            }
+            fn is_focusable(&self) -> bool {
+                return true;
+            }
+        }
+
+        let mut ui = Ui::new(Style {
+            direction: Direction::Column,
+            ..Style::default()
+        })
+        .with_spatial_navigation(true);
+        let buttons = (0..3)
+            .map(|_| ui.add(ui.root(), Button::default(), Style::fixed(100.0, 20.0)))
+            .collect::<Vec<_>>();
+        ui.layout(Vec2::new(400.0, 300.0));
+
+        let mut gamepad = GamepadInput::new();
+        let send = |ui: &mut Ui, events: Vec<Event>| {
+            for event in events.iter() {
+                ui.handle_event(event);
+            }
+        };
+
+        // The first direction focuses the first widget, holding it repeats
+        send(&mut ui, gamepad.button(GamepadButton::DPadDown, true));
+        assert_eq!(ui.focused(), Some(buttons[0]));
+        send(&mut ui, gamepad.update(REPEAT_DELAY));
+        assert_eq!(ui.focused(), Some(buttons[1]));
+        assert!(ui.focus_visible());
+        send(&mut ui, gamepad.button(GamepadButton::DPadDown, false));
+
+        send(&mut ui, gamepad.button(GamepadButton::South, true));
+        assert_eq!(ui.widget::<Button>(buttons[1]).unwrap().presses, 1);
+
+        send(&mut ui, gamepad.button(GamepadButton::RightShoulder, true));
+        assert_eq!(ui.focused(), Some(buttons[2]));
+        send(&mut ui, gamepad.left_stick(0.0, 1.0));
+        assert_eq!(ui.focused(), Some(buttons[1]));
+
+        // Cancel closes the popup and gives the focus back
+        let popup = ui.open_popup(
+            Menu::new().item("Quit", || {}),
+            Style::default(),
+            Placement::Below(buttons[1]),
+        );
+        ui.layout(Vec2::new(400.0, 300.0));
+        assert_eq!(ui.focused(), Some(popup));
+        send(&mut ui, gamepad.button(GamepadButton::East, true));
+        assert!(!ui.contains(popup));
+        assert_eq!(ui.focused(), Some(buttons[1]));
+    }
+
+    #[test]
+    fn test_transitions() {
+        use crate::animation::Transition;
+        use crate::draw::{DrawCmd, DrawList};
+        use soh_math::ease::Ease;
+
+        let mut ui = Ui::new(Style::default());
+        let root = ui.root();
+        let first = ui.add(root, Container::new(), Style::fixed(100.0, 20.0));
+        let second = ui.add(
+            root,
+            Container::new().background(soh_math::color::Rgb::new(255, 0, 0)),
+            Style::fixed(50.0, 20.0),
+        );
+        ui.set_transition(second, Some(Transition::new(1.0).easing(Ease::Linear)));
+        ui.layout(Vec2::new(400.0, 300.0));
+
+        // Appears without moving
+        assert_eq!(ui.animated_rect(second).min, Vec2::new(100.0, 0.0));
+        assert!(!ui.update(0.1));
+
+        // Moves to the new position
+        ui.set_visible(first, false);
+        ui.layout(Vec2::new(400.0, 300.0));
+        assert_eq!(ui.rect(second).min, Vec2::new(0.0, 0.0));
+        assert!(ui.update(0.25));
+        assert_eq!(ui.animated_rect(second).min, Vec2::new(75.0, 0.0));
+
+        let mut list = DrawList::new();
+        ui.draw(&mut list);
+        assert!(matches!(
+            list.commands(),
+            [DrawCmd::Rect { rect, .. }] if rect.min == Vec2::new(75.0, 0.0)
+        ));
+
+        // Fades out before it's hidden
+        ui.update(1.0);
+        ui.set_visible(second, false);
+        ui.update(0.5);
+        assert!(ui.is_visible(second));
+
+        let mut list = DrawList::new();
+        ui.draw(&mut list);
+        assert!(matches!(
+            list.commands(),
+            [DrawCmd::Rect { color, .. }] if color.alpha == 0.5
+        ));
+
+        assert!(!ui.update(0.5));
+        assert!(!ui.is_visible(second));
+
+        // Hover is eased
+        ui.set_visible(second, true);
+        ui.layout(Vec2::new(400.0, 300.0));
+        ui.handle_event(&Event::PointerMoved {
+            pos: Vec2::new(10.0, 10.0),
+        });
+        assert!(ui.update(0.5));
+        assert_eq!(ui.hovered(), Some(second));
+    }
+
+    #[test]
+    fn test_plots() {
+        use crate::draw::{DrawCmd, DrawList};
+        use soh_math::color::Gradient;
+
+        // Nice ticks
+        assert_eq!(plot::nice_step(10.0, 5), 2.0);
+        assert_eq!(plot::nice_step(0.7, 5), 0.2);
+        assert_eq!(plot::nice_range((0.3, 9.1)), (0.0, 10.0));
+        assert_eq!(plot::ticks((-1.0, 1.0), 4).0, [-1.0, -0.5, 0.0, 0.5, 1.0]);
+        assert_eq!(plot::tick_label(0.5, 0.5), "0.5");
+        assert_eq!(plot::tick_label(20.0, 10.0), "20");
+
+        let mut ui = Ui::new(Style::default());
+        let root = ui.root();
+        let line_plot = ui.add(
+            root,
+            LinePlot::new().series(Series::from_values("frame", &[1.0, 3.0, 2.0])),
+            Style::fixed(300.0, 200.0),
+        );
+        let histogram = ui.add(root, Histogram::new(4), Style::fixed(300.0, 200.0));
+        let heatmap = ui.add(
+            root,
+            Heatmap::new(2, 2).gradient(Gradient::grayscale()),
+            Style::fixed(200.0, 200.0),
+        );
+
+        // Autoscaled axes
+        let plot = ui.widget_mut::<LinePlot>(line_plot).unwrap();
+        assert_eq!(plot.ranges(), ((0.0, 2.0), (1.0, 3.0)));
+        plot.set_values("budget", &[16.0, 16.0, 16.0]);
+        assert_eq!(plot.ranges(), ((0.0, 2.0), (0.0, 20.0)));
+
+        let histogram = ui.widget_mut::<Histogram>(histogram).unwrap();
+        histogram.set_values(&[0.0, 0.1, 0.5, 0.9, 1.0, 1.0]);
+        assert_eq!(histogram.counts(), [2, 0, 1, 3]);
+
+        let heatmap = ui.widget_mut::<Heatmap>(heatmap).unwrap();
+        heatmap.set_values(&[0.0, 1.0, 2.0, 4.0]);
+        assert_eq!(heatmap.value_color(4.0), soh_math::color::WHITE.into());
+        assert_eq!(
+            heatmap.value_color(2.0),
+            soh_math::color::Rgb::new(128, 128, 128).into()
+        );
+
+        ui.layout(Vec2::new(800.0, 300.0));
+        let mut list = DrawList::new();
+        ui.draw(&mut list);
+
+        // Two lines of two segments each, with a legend
+        let texts = list
+            .commands()
+            .iter()
+            .filter_map(|cmd| match cmd {
+                DrawCmd::Text { text, .. } => Some(text.as_str()),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        assert!(texts.contains(&"frame") && texts.contains(&"budget"));
+        let series_lines = list
+            .commands()
+            .iter()
+            .filter(|cmd| matches!(cmd, DrawCmd::Line { width, .. } if *width == 1.5))
+            .count();
+        assert_eq!(series_lines, 4);
+    }
+    #[test]
+    fn test_images() {
+        use crate::draw::{DrawCmd, DrawList, TextureHandle};
+        use ash::vk::{self, Handle};
+
+        let texture = TextureHandle::new(vk::ImageView::from_raw(1), vk::Sampler::from_raw(2));
+        let rect = Rect::new(Vec2::new(0.0, 0.0), Vec2::new(200.0, 100.0));
+        let full = Rect::new(Vec2::new(0.0, 0.0), Vec2::new(1.0, 1.0));
+
+        let image = Image::new(texture, (100, 100));
+        assert_eq!(
+            image.placement(rect),
+            (
+                Rect::new(Vec2::new(50.0, 0.0), Vec2::new(150.0, 100.0)),
+                full
+            )
+        );
+        let image = image.fit(ImageFit::Cover);
+        assert_eq!(
+            image.placement(rect),
+            (rect, Rect::new(Vec2::new(0.0, 0.25), Vec2::new(1.0, 0.75)))
+        );
+        let image = image.fit(ImageFit::Fill);
+        assert_eq!(image.placement(rect), (rect, full));
+
+        // 2 x 2 icons of 16 px
+        let atlas = IconAtlas::grid(texture, (32, 32), (16, 16), ["open", "save", "close"]);
+        assert_eq!(
+            atlas.uv("close"),
+            Some(Rect::new(Vec2::new(0.0, 0.5), Vec2::new(0.5, 1.0)))
+        );
+        assert!(Icon::from_atlas(&atlas, "missing").is_none());
+
+        let mut ui = Ui::new(Style::default());
+        let root = ui.root();
+        let icon = Icon::from_atlas(&atlas, "save").unwrap().size(16.0);
+        ui.add(root, icon, Style::fixed(32.0, 32.0));
+        ui.layout(Vec2::new(100.0, 100.0));
+        let mut list = DrawList::new();
+        ui.draw(&mut list);
+
+        let text_color = ui.theme(root).text;
+        assert!(list.commands().contains(&DrawCmd::Image {
+            rect: Rect::new(Vec2::new(8.0, 8.0), Vec2::new(24.0, 24.0)),
+            texture,
+            uv: Rect::new(Vec2::new(0.5, 0.0), Vec2::new(1.0, 0.5)),
+            tint: text_color,
+        }));
+    }
+    #[test]
+    fn test_tree_view() {
+        use crate::draw::DrawCmd;
+
+        let loads = Rc::new(RefCell::new(vec![]));
+        let selections = Rc::new(RefCell::new(vec![]));
+
+        let mut tree = TreeView::new().loader({
+            let loads = loads.clone();
+            move |_, label| {
+                loads.borrow_mut().push(label.to_owned());
+                return vec![
+                    TreeItem::new(format!("{label}/a")),
+                    TreeItem::lazy(format!("{label}/b")),
+                ];
+            }
+        });
+        let scene = tree.add_lazy(None, "scene");
+        tree.add(None, "assets");
+        {
+            let selections = selections.clone();
+            tree.on_select(move |id| selections.borrow_mut().push(id));
+        }
+
+        let mut ui = Ui::new(Style::default());
+        let id = ui.add(ui.root(), tree, Style::fixed(200.0, 100.0));
+        ui.layout(Vec2::new(400.0, 300.0));
+
+        let labels = |ui: &Ui| {
+            let tree = ui.widget::<TreeView>(id).unwrap();
+            return tree
+                .rows()
+                .iter()
+                .map(|&(node, depth)| format!("{depth} {}", tree.label(node)))
+                .collect::<Vec<_>>();
+        };
+        let selected = |ui: &Ui| {
+            let tree = ui.widget::<TreeView>(id).unwrap();
+            return tree.selected().map(|node| tree.label(node).to_owned());
+        };
+
+        // Rows are 24px high ( 20px line and 4px padding ), the chevron starts after the padding
+        click(&mut ui, Vec2::new(10.0, 12.0));
+        assert_eq!(
+            labels(&ui),
+            ["0 scene", "1 scene/a", "1 scene/b", "0 assets"]
+        );
+        assert_eq!(*loads.borrow(), ["scene"]);
+        assert_eq!(selected(&ui), None);
+
+        click(&mut ui, Vec2::new(100.0, 36.0));
+        assert_eq!(selected(&ui).as_deref(), Some("scene/a"));
+
+        key(&mut ui, Key::Down, Modifiers::NONE);
+        key(&mut ui, Key::Right, Modifiers::NONE);
+        key(&mut ui, Key::Right, Modifiers::NONE);
+        assert_eq!(selected(&ui).as_deref(), Some("scene/b/a"));
+        assert_eq!(*loads.borrow(), ["scene", "scene/b"]);
+        assert_eq!(labels(&ui).len(), 6);
+
+        // Collapsing an ancestor selects it instead of the hidden node
+        ui.widget_mut::<TreeView>(id).unwrap().collapse(scene);
+        assert_eq!(selected(&ui).as_deref(), Some("scene"));
+        assert_eq!(labels(&ui), ["0 scene", "0 assets"]);
+        assert_eq!(selections.borrow().len(), 3);
+
+        // Expanding again doesn't load the children again
+        key(&mut ui, Key::Right, Modifiers::NONE);
+        assert_eq!(labels(&ui).len(), 6);
+        assert_eq!(loads.borrow().len(), 2);
+
+        // Only the visible rows are drawn
+        let mut list = DrawList::new();
+        ui.draw(&mut list);
+        let texts = list
+            .commands()
+            .iter()
+            .filter(|cmd| matches!(cmd, DrawCmd::Text { .. }))
+            .count();
+        assert_eq!(texts, 5);
+    }
+
+    #[test]
+    fn test_table() {
+        let requested = Rc::new(RefCell::new(0));
+
+        let columns = vec![
+            TableColumn::new("id", 100.0),
+            TableColumn::new("name", 100.0).sortable(false),
+        ];
+        let names = ["10", "9", "100", "b", "a"];
+        let widget = Table::new(columns, names.len(), move |row, column| {
+            return match column {
+                0 => row.to_string(),
+                _ => names[row].to_owned(),
+            };
+        });
+
+        let mut ui = Ui::new(Style::default());
+        ui.add(ui.root(), widget, Style::fixed(300.0, 200.0));
+        ui.layout(Vec2::new(400.0, 300.0));
+        fn table(ui: &mut Ui) -> &mut Table {
+            let id = ui.children(ui.root())[0];
+            return ui.widget_mut::<Table>(id).unwrap();
+        }
+
+        // Numbers are sorted by value and before the text
+        table(&mut ui).sort_by(1, SortOrder::Ascending);
+        let rows = (0..5).map(|row| table(&mut ui).data_row(row));
+        assert_eq!(rows.collect::<Vec<_>>(), [1, 0, 2, 4, 3]);
+
+        // Clicking the header of a sortable column sorts by it, then reverses the order
+        click(&mut ui, Vec2::new(50.0, 12.0));
+        assert_eq!(table(&mut ui).sort(), Some((0, SortOrder::Ascending)));
+        click(&mut ui, Vec2::new(50.0, 12.0));
+        assert_eq!(table(&mut ui).sort(), Some((0, SortOrder::Descending)));
+        click(&mut ui, Vec2::new(150.0, 12.0));
+        assert_eq!(table(&mut ui).sort(), Some((0, SortOrder::Descending)));
+
+        // Rows below the 24px header select their data row
+        click(&mut ui, Vec2::new(50.0, 24.0 + 12.0));
+        assert_eq!(table(&mut ui).selected(), Some(4));
+        key(&mut ui, Key::Down, Modifiers::NONE);
+        assert_eq!(table(&mut ui).selected(), Some(3));
+
+        // Dragging the edge of a column resizes it
+        let button = PointerButton::Primary;
+        ui.handle_event(&Event::PointerDown {
+            pos: Vec2::new(101.0, 12.0),
+            button,
+        });
+        ui.handle_event(&Event::PointerMoved {
+            pos: Vec2::new(151.0, 12.0),
+        });
+        ui.handle_event(&Event::PointerUp {
+            pos: Vec2::new(151.0, 12.0),
+            button,
+        });
+        assert_eq!(table(&mut ui).columns()[0].width(), 150.0);
+        assert_eq!(table(&mut ui).sort(), Some((0, SortOrder::Descending)));
+
+        // Columns mixing numbers and text are sorted without cycles ( "2" < "10" < "1a" < "2"
+        // would be one if numbers and text were mixed )
+        let cells = (0..100)
+            .map(|i| match i % 3 {
+                0 => format!("{}", i * 7 % 50),
+                1 => format!("{}a", i % 20),
+                _ => format!("{}.5", 99 - i),
+            })
+            .collect::<Vec<_>>();
+        let mut mixed = Table::new(vec![TableColumn::new("n", 100.0)], cells.len(), {
+            let cells = cells.clone();
+            move |row, _| {
+                return cells[row].clone();
+            }
+        });
+        mixed.sort_by(0, SortOrder::Ascending);
+        let sorted = (0..cells.len())
+            .map(|row| cells[mixed.data_row(row)].as_str())
+            .collect::<Vec<_>>();
+        let numbers = cells
+            .iter()
+            .filter(|cell| cell.parse::<f64>().is_ok())
+            .count();
+        assert!(sorted[..numbers]
+            .iter()
+            .all(|cell| cell.parse::<f64>().is_ok()));
+        assert!(sorted[..numbers]
+            .windows(2)
+            .all(|w| w[0].parse::<f64>().unwrap() <= w[1].parse::<f64>().unwrap()));
+        assert!(sorted[numbers..].windows(2).all(|w| w[0] <= w[1]));
+
+        // Only the cells of the visible rows are requested
+        let big = Table::new(vec![TableColumn::new("n", 100.0)], 1_000_000, {
+            let requested = requested.clone();
+            move |row, _| {
+                *requested.borrow_mut() += 1;
+                return row.to_string();
+            }
+        });
+        let mut ui = Ui::new(Style::default());
+        ui.add(ui.root(), big, Style::fixed(300.0, 200.0));
+        ui.layout(Vec2::new(400.0, 300.0));
+        ui.draw(&mut DrawList::new());
+        assert_eq!(*requested.borrow(), 8);
+    }
+    #[test]
+    fn test_drag_and_drop() {
+        use crate::drag::{DragData, DRAG_THRESHOLD};
+        use crate::draw::{DrawCmd, DrawList};
+
+        /// Starts dragging its asset name once the pointer moves after a press
+        struct Asset {
+            name: &'static str,
+            press: Option<Vec2<f32>>,
+        }
+
+        impl Widget for Asset {
+            fn draw(&self, _ctx: &mut DrawCtx) {}
+
+            fn on_event(&mut self, ctx: &mut EventCtx, event: &Event) -> Response {
+                match event {
+                    Event::PointerDown { pos, .. } => {
+                        self.press = Some(*pos);
+                        ctx.capture_pointer();
+                    }
+                    Event::PointerMoved { pos } => {
+                        let Some(press) = self.press else {
+                            return Response::Ignored;
+                        };
+                        if (*pos - press).len() > DRAG_THRESHOLD {
+                            ctx.start_drag_with_label(self.name.to_owned(), self.name);
+                            self.press = None;
+                        }
+                    }
+                    Event::PointerUp { .. } => self.press = None,
+                    _ => return Response::Ignored,
+                }
+                return Response::Consumed;
+            }
+        }
+
+        /// Accepts the dragged asset names
+        #[derive(Default)]
+        struct Viewport {
+            dropped: Vec<(String, Vec2<f32>)>,
+        }
+
+        impl Widget for Viewport {
+            fn draw(&self, _ctx: &mut DrawCtx) {}
+
+            fn accepts_drop(&self, drag: &DragData) -> bool {
+                return drag.is::<String>();
+            }
+
+            fn on_drop(&mut self, _ctx: &mut EventCtx, pos: Vec2<f32>, drag: DragData) {
+                if let Ok(name) = drag.take::<String>() {
+                    self.dropped.push((name, pos));
+                }
+            }
+        }
+
+        let mut ui = Ui::new(Style::default());
+        let root = ui.root();
+        let asset = Asset {
+            name: "rock.mesh",
+            press: None,
+        };
+        let asset = ui.add(root, asset, Style::fixed(100.0, 100.0));
+        let viewport = ui.add(root, Viewport::default(), Style::fixed(100.0, 100.0));
+        let label = ui.add(viewport, Label::new("viewport"), Style::fixed(50.0, 20.0));
+        ui.layout(Vec2::new(400.0, 300.0));
+
+        let button = PointerButton::Primary;
+        let moved = |ui: &mut Ui, x: f32, y: f32| {
+            ui.handle_event(&Event::PointerMoved {
+                pos: Vec2::new(x, y),
+            });
+        };
+
+        // Small moves don't start a drag
+        ui.handle_event(&Event::PointerDown {
+            pos: Vec2::new(50.0, 50.0),
+            button,
+        });
+        moved(&mut ui, 52.0, 50.0);
+        assert!(!ui.is_dragging());
+
+        moved(&mut ui, 60.0, 50.0);
+        assert_eq!(ui.drag_data().unwrap().source(), asset);
+        assert_eq!(ui.drop_target(), None);
+
+        // The parent of the label accepts the drop and is highlighted
+        moved(&mut ui, 110.0, 10.0);
+        assert_eq!(ui.drop_target(), Some(viewport));
+
+        let mut list = DrawList::new();
+        ui.draw(&mut list);
+        let accent = ui.theme(viewport).accent;
+        let cmds = list.commands();
+        assert!(cmds
+            .iter()
+            .any(|cmd| matches!(cmd, DrawCmd::Rect { color, .. } if *color == accent)));
+        assert!(matches!(
+            cmds.last(),
+            Some(DrawCmd::Text { text, pos, .. })
+                if text == "rock.mesh" && *pos == Vec2::new(122.0, 22.0)
+        ));
+
+        ui.handle_event(&Event::PointerUp {
+            pos: Vec2::new(150.0, 50.0),
+            button,
+        });
+        assert!(!ui.is_dragging());
+        let dropped = &ui.widget::<Viewport>(viewport).unwrap().dropped;
+        assert_eq!(*dropped, [("rock.mesh".to_owned(), Vec2::new(150.0, 50.0))]);
+        assert!(ui.widget::<Asset>(asset).unwrap().press.is_none());
+
+        // Escape cancels the drag
+        ui.handle_event(&Event::PointerDown {
+            pos: Vec2::new(50.0, 50.0),
+            button,
+        });
+        moved(&mut ui, 150.0, 50.0);
+        assert!(ui.is_dragging());
+        key(&mut ui, Key::Escape, Modifiers::NONE);
+        assert!(!ui.is_dragging());
+        ui.handle_event(&Event::PointerUp {
+            pos: Vec2::new(150.0, 50.0),
+            button,
+        });
+        assert_eq!(ui.widget::<Viewport>(viewport).unwrap().dropped.len(), 1);
+        assert!(ui.contains(label));
+    }
+
+    #[test]
+    fn test_scale_factor() {
+        use crate::draw::DrawCmd;
+        use soh_math::color::Rgb;
+
+        let mut ui = Ui::new(Style::default());
+        let red = Rgb::new(255, 0, 0);
+        let block = ui.add(
+            ui.root(),
+            Container::new().background(red),
+            Style::fixed(10.5, 10.0),
+        );
+        let label = ui.add(ui.root(), Label::new("hi"), Style::default());
+
+        // The layout and the events stay in logical pixels
+        let response = ui.handle_event(&Event::ScaleFactorChanged { scale_factor: 1.5 });
+        assert!(!response.is_consumed());
+        assert_eq!(ui.scale_factor(), 1.5);
+        ui.layout(Vec2::new(400.0, 300.0));
+        assert_eq!(ui.rect(block).max, Vec2::new(10.5, 10.0));
+        assert_eq!(ui.hit_test(Vec2::new(12.0, 5.0)), Some(label));
+
+        // Drawn in physical pixels snapped to the pixel grid
+        let mut list = DrawList::new();
+        ui.draw(&mut list);
+        match list.commands() {
+            [DrawCmd::Rect { rect, .. }, DrawCmd::Text { pos, size, .. }] => {
+                assert_eq!(*rect, Rect::new(Vec2::new(0.0, 0.0), Vec2::new(16.0, 15.0)));
+                assert_eq!(*pos, Vec2::new(16.0, 0.0));
+                assert_eq!(*size, 1.5 * ui.theme(label).font_size);
+            }
+            cmds => panic!("Unexpected commands: {cmds:?}"),
+        }
+    }
+
+    #[test]
+    fn test_modal() {
+        use crate::layout::Direction;
+        use crate::overlay::LayerKind;
+
+        let mut ui = Ui::new(Style::default());
+        let input = ui.add(ui.root(), TextInput::new(), Style::fixed(200.0, 24.0));
+        ui.layout(Vec2::new(400.0, 300.0));
+        click(&mut ui, Vec2::new(10.0, 10.0));
+        assert_eq!(ui.focused(), Some(input));
+
+        let dialog = ui.open_modal(
+            Container::panel(),
+            Style {
+                direction: Direction::Column,
+                ..Style::fixed(200.0, 100.0)
+            },
+        );
+        let fields = [
+            ui.add(dialog, TextInput::new(), Style::fixed(100.0, 24.0)),
+            ui.add(dialog, TextInput::new(), Style::fixed(100.0, 24.0)),
+        ];
+        ui.layout(Vec2::new(400.0, 300.0));
+
+        // Centered, the first field takes the focus
+        assert_eq!(
+            ui.overlays().collect::<Vec<_>>(),
+            [(dialog, LayerKind::Modal)]
+        );
+        assert_eq!(ui.rect(dialog).min, Vec2::new(100.0, 100.0));
+        assert_eq!(ui.focused(), Some(fields[0]));
+
+        // The focus stays inside of the dialog
+        assert_eq!(ui.focus_order(), fields);
+        key(&mut ui, Key::Tab, Modifiers::NONE);
+        key(&mut ui, Key::Tab, Modifiers::NONE);
+        assert_eq!(ui.focused(), Some(fields[0]));
+
+        // Nothing below the dialog reacts
+        assert_eq!(ui.hit_test(Vec2::new(10.0, 10.0)), None);
+        let button = PointerButton::Primary;
+        let pos = Vec2::new(10.0, 10.0);
+        assert!(ui
+            .handle_event(&Event::PointerDown { pos, button })
+            .is_consumed());
+        ui.handle_event(&Event::Text("a".into()));
+        assert_eq!(ui.focused(), Some(fields[0]));
+        assert_eq!(text(&ui, input), "");
+        assert_eq!(text(&ui, fields[0]), "a");
+
+        let mut list = DrawList::new();
+        ui.draw(&mut list);
+        let backdrop = ui.theme(ui.root()).backdrop;
+        assert!(list.commands().iter().any(
+            |cmd| matches!(cmd, crate::draw::DrawCmd::Rect { color, .. } if *color == backdrop)
+        ));
+
+        // The input handles the first Escape, the second one closes the dialog
+        key(&mut ui, Key::Escape, Modifiers::NONE);
+        assert!(ui.contains(dialog));
+        key(&mut ui, Key::Escape, Modifiers::NONE);
+        assert!(!ui.contains(dialog));
+        assert_eq!(ui.overlays().count(), 0);
+        assert_eq!(ui.focused(), Some(input));
+    }
+
+    #[test]
+    fn test_popups() {
+        use crate::layout::Position;
+        use crate::overlay::{LayerKind, Placement};
+
+        let chosen = Rc::new(RefCell::new(vec![]));
+        let menu = {
+            let chosen = chosen.clone();
+            move || {
+                let (copy, paste) = (chosen.clone(), chosen.clone());
+                return Menu::new()
+                    .item("Copy", move || copy.borrow_mut().push("copy"))
+                    .item("Paste", move || paste.borrow_mut().push("paste"));
+            }
+        };
+
+        let mut ui = Ui::new(Style::default());
+        let input = ui.add(ui.root(), TextInput::new(), Style::fixed(200.0, 24.0));
+        let anchor = ui.add(
+            ui.root(),
+            Label::new("bottom"),
+            Style {
+                position: Position::Absolute(Vec2::new(0.0, 280.0)),
+                ..Style::fixed(100.0, 20.0)
+            },
+        );
+        ui.set_context_menu(input, menu);
+        ui.layout(Vec2::new(400.0, 300.0));
+
+        // The context menu opens at the pointer and is used with the keyboard
+        let pos = Vec2::new(20.0, 10.0);
+        let secondary = PointerButton::Secondary;
+        ui.handle_event(&Event::PointerDown {
+            pos,
+            button: secondary,
+        });
+        ui.handle_event(&Event::PointerUp {
+            pos,
+            button: secondary,
+        });
+        ui.layout(Vec2::new(400.0, 300.0));
+
+        let (popup, kind) = ui.overlays().next().unwrap();
+        assert_eq!(kind, LayerKind::Popup);
+        assert_eq!(ui.rect(popup).min, pos);
+        assert_eq!(ui.focused(), Some(popup));
+
+        key(&mut ui, Key::Down, Modifiers::NONE);
+        key(&mut ui, Key::Down, Modifiers::NONE);
+        assert_eq!(ui.widget::<Menu>(popup).unwrap().highlighted(), Some(1));
+        key(&mut ui, Key::Enter, Modifiers::NONE);
+        assert_eq!(*chosen.borrow(), ["paste"]);
+        assert!(!ui.contains(popup));
+        assert_eq!(ui.focused(), Some(input));
+
+        // Pressing outside closes the menu without reaching the widget below
+        ui.handle_event(&Event::PointerDown {
+            pos,
+            button: secondary,
+        });
+        ui.layout(Vec2::new(400.0, 300.0));
+        click(&mut ui, Vec2::new(300.0, 10.0));
+        assert_eq!(ui.overlays().count(), 0);
+        assert_eq!(ui.focused(), Some(input));
+        assert_eq!(chosen.borrow().len(), 1);
+
+        // No space below the anchor: the popup is placed above it
+        let popup = ui.open_popup(
+            Menu::new(),
+            Style::fixed(80.0, 50.0),
+            Placement::Below(anchor),
+        );
+        ui.layout(Vec2::new(400.0, 300.0));
+        assert_eq!(
+            ui.rect(popup),
+            Rect::new(Vec2::new(0.0, 230.0), Vec2::new(80.0, 280.0))
+        );
+        key(&mut ui, Key::Escape, Modifiers::NONE);
+        assert!(!ui.contains(popup));
+    }
+
+    #[test]
+    fn test_tooltip() {
+        use crate::overlay::{LayerKind, TOOLTIP_DELAY, TOOLTIP_OFFSET};
+
+        let mut ui = Ui::new(Style::default());
+        let label = ui.add(ui.root(), Label::new("save"), Style::fixed(100.0, 20.0));
+        ui.set_tooltip(label, Some("Save the file".into()));
+        ui.layout(Vec2::new(400.0, 300.0));
+
+        let hover = |ui: &mut Ui, pos: Vec2<f32>| {
+            ui.handle_event(&Event::PointerMoved { pos });
+            ui.update(TOOLTIP_DELAY * 0.6);
+            assert_eq!(ui.overlays().count(), 0);
+            assert!(ui.update(TOOLTIP_DELAY * 0.6));
+            ui.layout(Vec2::new(400.0, 300.0));
+        };
+
+        // Shown below the pointer after the delay
+        let pos = Vec2::new(10.0, 10.0);
+        hover(&mut ui, pos);
+        let (tooltip, kind) = ui.overlays().next().unwrap();
+        assert_eq!(kind, LayerKind::Tooltip);
+        assert_eq!(ui.rect(tooltip).min, pos + Vec2::new(0.0, TOOLTIP_OFFSET));
+        assert_eq!(ui.hit_test(ui.rect(tooltip).center()), Some(ui.root()));
+
+        let mut list = DrawList::new();
+        ui.draw(&mut list);
+        assert!(list.commands().iter().any(
+            |cmd| matches!(cmd, crate::draw::DrawCmd::Text { text, .. } if text == "Save the file")
+        ));
+
+        // Hidden by a key press until the pointer leaves the widget
+        key(&mut ui, Key::Char('s'), Modifiers::NONE);
+        assert_eq!(ui.overlays().count(), 0);
+        assert!(!ui.update(TOOLTIP_DELAY * 2.0));
+        assert_eq!(ui.overlays().count(), 0);
+
+        ui.handle_event(&Event::PointerMoved {
+            pos: Vec2::new(300.0, 10.0),
+        });
+        hover(&mut ui, pos);
+        assert_eq!(ui.overlays().count(), 1);
+    }
+}
+
+//-----------------------------------------------------------------------------
@@ -0,0 +1,372 @@
+//-----------------------------------------------------------------------------
+use super::{DrawCtx, MeasureCtx, Widget};
+use crate::draw::Color;
+use crate::layout::Edges;
+use crate::{Rect, RectExt};
+use soh_math::color::{self, Rgb};
+use soh_math::Vec2;
+//-----------------------------------------------------------------------------
+
+/// Plot of one or more series of points connected with lines
+///
+/// The axes fit the data unless a range is set.
+#[derive(Debug, Default, Clone)]
+pub struct LinePlot {
+    series: Vec<Series>,
+    x_range: Option<(f32, f32)>,
+    y_range: Option<(f32, f32)>,
+    /// Draw the names of the series
+    pub legend: bool,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Series {
+    pub name: String,
+    /// None picks a color from the palette
+    pub color: Option<Color>,
+    pub points: Vec<Vec2<f32>>,
+}
+
+/// Colors of the series without a color
+pub const PALETTE: [Rgb; 6] = [
+    color::BLUE,
+    color::ORANGE,
+    color::GREEN,
+    color::RED,
+    color::LIGHT_BLUE,
+    color::YELLOW,
+];
+
+/// Scale of the tick labels relative to the font size of the theme
+pub(crate) const LABEL_SCALE: f32 = 0.75;
+
+//-----------------------------------------------------------------------------
+// Constructor
+impl LinePlot {
+    pub fn new() -> Self {
+        return LinePlot {
+            legend: true,
+            ..Default::default()
+        };
+    }
+
+    pub fn series(mut self, series: Series) -> Self {
+        self.series.push(series);
+        return self;
+    }
+
+    /// Fixed range of the x axis
+    pub fn x_range(mut self, min: f32, max: f32) -> Self {
+        self.x_range = Some((min, max));
+        return self;
+    }
+
+    /// Fixed range of the y axis
+    pub fn y_range(mut self, min: f32, max: f32) -> Self {
+        self.y_range = Some((min, max));
+        return self;
+    }
+}
+
+impl Series {
+    pub fn new(name: impl Into<String>, points: impl IntoIterator<Item = Vec2<f32>>) -> Self {
+        return Series {
+            name: name.into(),
+            color: None,
+            points: points.into_iter().collect(),
+        };
+    }
+
+    /// Series of values at x = 0, 1, 2, ...
+    pub fn from_values(name: impl Into<String>, values: &[f32]) -> Self {
+        let points = values
+            .iter()
+            .enumerate()
+            .map(|(i, &v)| Vec2::new(i as f32, v));
+        return Series::new(name, points);
+    }
+
+    pub fn color(mut self, color: impl Into<Color>) -> Self {
+        self.color = Some(color.into());
+        return self;
+    }
+}
+
+//-----------------------------------------------------------------------------
+// Getters
+impl LinePlot {
+    pub fn all_series(&self) -> &[Series] {
+        return &self.series;
+    }
+
+    /// Ranges of the axes (fitted to the data if not set)
+    pub fn ranges(&self) -> ((f32, f32), (f32, f32)) {
+        let points = self.series.iter().flat_map(|series| series.points.iter());
+
+        let x_range = self
+            .x_range
+            .unwrap_or_else(|| data_range(points.clone().map(|p| p.x)));
+        let y_range = self
+            .y_range
+            .unwrap_or_else(|| nice_range(data_range(points.map(|p| p.y))));
+
+        return (x_range, y_range);
+    }
+}
+
+//-----------------------------------------------------------------------------
+// Specific implementation
+impl LinePlot {
+    /// Replace the points of a series (it's added if there is none with the name)
+    pub fn set_points(&mut self, name: &str, points: impl IntoIterator<Item = Vec2<f32>>) {
+        match self.series.iter_mut().find(|series| series.name == name) {
+            Some(series) => {
+                series.points.clear();
+                series.points.extend(points);
+            }
+            None => self.series.push(Series::new(name, points)),
+        }
+    }
+
+    /// Replace the points of a series with values at x = 0, 1, 2, ...
+    pub fn set_values(&mut self, name: &str, values: &[f32]) {
+        let points = values
+            .iter()
+            .enumerate()
+            .map(|(i, &v)| Vec2::new(i as f32, v));
+        self.set_points(name, points);
+    }
+
+    pub fn remove_series(&mut self, name: &str) {
+        self.series.retain(|series| series.name != name);
+    }
+
+    fn series_color(&self, index: usize) -> Color {
+        return self.series[index]
+            .color
+            .unwrap_or(Color::opaque(PALETTE[index % PALETTE.len()]));
+    }
+}
+
+impl Widget for LinePlot {
+    fn measure(&self, ctx: &MeasureCtx, _available: Vec2<f32>) -> Vec2<f32> {
+        let line_height = ctx.text.line_height(ctx.theme.font_size);
+        return Vec2::new(line_height * 12.0, line_height * 8.0);
+    }
+
+    fn draw(&self, ctx: &mut DrawCtx) {
+        let (x_range, y_range) = self.ranges();
+        let area = draw_axes(ctx, x_range, y_range);
+        let to_screen = |p: Vec2<f32>| map_point(area, x_range, y_range, p);
+
+        ctx.list.push_clip(area);
+        for (i, series) in self.series.iter().enumerate() {
+            let color = self.series_color(i);
+
+            for pair in series.points.windows(2) {
+                ctx.list
+                    .line(to_screen(pair[0]), to_screen(pair[1]), 1.5, color);
+            }
+        }
+        ctx.list.pop_clip();
+
+        if self.legend && !self.series.is_empty() {
+            let entries = self
+                .series
+                .iter()
+                .enumerate()
+                .map(|(i, series)| (series.name.as_str(), self.series_color(i)));
+            draw_legend(ctx, area, entries);
+        }
+    }
+}
+
+//-----------------------------------------------------------------------------
+// Axes shared by the plots
+
+/// Range of the values (an empty range is widened so it can be drawn)
+pub(crate) fn data_range(values: impl Iterator<Item = f32>) -> (f32, f32) {
+    let (min, max) = values
+        .filter(|v| v.is_finite())
+        .fold((f32::INFINITY, f32::NEG_INFINITY), |(min, max), v| {
+            (min.min(v), max.max(v))
+        });
+
+    if min > max {
+        return (0.0, 1.0);
+    }
+    if min == max {
+        return (min - 0.5, max + 0.5);
+    }
+    return (min, max);
+}
+
+/// Round the step between ticks to 1, 2 or 5 times a power of ten
+pub(crate) fn nice_step(range: f32, max_ticks: usize) -> f32 {
+    let rough = range / max_ticks.max(1) as f32;
+    let magnitude = 10.0f32.powf(rough.log10().floor());
+
+    let step = [1.0, 2.0, 5.0, 10.0]
+        .into_iter()
+        .map(|m| m * magnitude)
+        .find(|&step| step >= rough)
+        .unwrap_or(10.0 * magnitude);
+
+    return step;
+}
+
+/// Extend the range to the closest ticks
+pub(crate) fn nice_range((min, max): (f32, f32)) -> (f32, f32) {
+    let step = nice_step(max - min, 5);
+    return ((min / step).floor() * step, (max / step).ceil() * step);
+}
+
+/// Ticks at multiples of a nice step inside the range
+pub(crate) fn ticks((min, max): (f32, f32), max_ticks: usize) -> (Vec<f32>, f32) {
+    let step = nice_step(max - min, max_ticks);
+    if !step.is_finite() || step <= 0.0 {
+        return (vec![], step);
+    }
+
+    let first = (min / step).ceil() as i64;
+    let last = (max / step).floor() as i64;
+    let ticks = (first..=last).map(|i| i as f32 * step).collect();
+
+    return (ticks, step);
+}
+
+/// Tick label with as many decimals as the step needs
+pub(crate) fn tick_label(value: f32, step: f32) -> String {
+    let decimals = (-step.log10().floor()).max(0.0) as usize;
+    let value = match value == 0.0 {
+        true => 0.0, // No "-0"
+        false => value,
+    };
+    return format!("{value:.decimals$}");
+}
+
+pub(crate) fn map_point(
+    area: Rect,
+    (x_min, x_max): (f32, f32),
+    (y_min, y_max): (f32, f32),
+    p: Vec2<f32>,
+) -> Vec2<f32> {
+    let x = area.min.x + (p.x - x_min) / (x_max - x_min) * area.width();
+    let y = area.max.y - (p.y - y_min) / (y_max - y_min) * area.height();
+    return Vec2::new(x, y);
+}
+
+/// Draw the background, gridlines and tick labels, returns the area of the data
+pub(crate) fn draw_axes(ctx: &mut DrawCtx, x_range: (f32, f32), y_range: (f32, f32)) -> Rect {
+    let theme = ctx.theme;
+    let size = theme.font_size * LABEL_SCALE;
+    let line_height = ctx.text.line_height(size);
+
+    /*
+     * Room for the labels on the left and at the bottom
+     */
+    let max_y_ticks = ((ctx.rect.height() / (line_height * 2.0)) as usize).max(2);
+    let (y_ticks, y_step) = ticks(y_range, max_y_ticks);
+    let y_labels = y_ticks
+        .iter()
+        .map(|&v| tick_label(v, y_step))
+        .collect::<Vec<_>>();
+    let label_width = y_labels
+        .iter()
+        .map(|label| ctx.text.text_width(label, size))
+        .fold(0.0, f32::max);
+
+    let inner = ctx.rect.shrink(Edges::all(theme.padding));
+    let area = inner.shrink(Edges {
+        left: label_width + theme.padding,
+        bottom: line_height + theme.padding,
+        ..Edges::ZERO
+    });
+
+    let max_x_ticks = ((area.width() / (line_height * 4.0)) as usize).max(2);
+    let (x_ticks, x_step) = ticks(x_range, max_x_ticks);
+
+    ctx.list.rect(area, theme.surface);
+
+    let grid = theme.border.with_alpha(0.5);
+    for (&v, label) in y_ticks.iter().zip(y_labels.iter()) {
+        let y = map_point(area, x_range, y_range, Vec2::new(x_range.0, v)).y;
+        ctx.list.line(
+            Vec2::new(area.min.x, y),
+            Vec2::new(area.max.x, y),
+            1.0,
+            grid,
+        );
+
+        let width = ctx.text.text_width(label, size);
+        let pos = Vec2::new(area.min.x - theme.padding - width, y - line_height * 0.5);
+        ctx.list.text(pos, label, size, theme.text_muted);
+    }
+    for &v in x_ticks.iter() {
+        let x = map_point(area, x_range, y_range, Vec2::new(v, y_range.0)).x;
+        ctx.list.line(
+            Vec2::new(x, area.min.y),
+            Vec2::new(x, area.max.y),
+            1.0,
+            grid,
+        );
+
+        let label = tick_label(v, x_step);
+        let width = ctx.text.text_width(&label, size);
+        let pos = Vec2::new(x - width * 0.5, area.max.y + theme.padding);
+        ctx.list.text(pos, &label, size, theme.text_muted);
+    }
+
+    return area;
+}
+
+/// Names with color markers in the top right corner of the area
+pub(crate) fn draw_legend<'a>(
+    ctx: &mut DrawCtx,
+    area: Rect,
+    entries: impl Iterator<Item = (&'a str, Color)> + Clone,
+) {
+    let theme = ctx.theme;
+    let size = theme.font_size * LABEL_SCALE;
+    let line_height = ctx.text.line_height(size);
+    let marker = line_height * 0.5;
+
+    let text_width = entries
+        .clone()
+        .map(|(name, _)| ctx.text.text_width(name, size))
+        .fold(0.0, f32::max);
+    let count = entries.clone().count() as f32;
+
+    let box_size = Vec2::new(
+        marker + text_width + 3.0 * theme.padding,
+        count * line_height + 2.0 * theme.padding,
+    );
+    let min = Vec2::new(
+        area.max.x - theme.padding - box_size.x,
+        area.min.y + theme.padding,
+    );
+    let legend = Rect::from_pos_size(min, box_size);
+
+    ctx.list.rounded_rect(
+        legend,
+        theme.corner_radius,
+        theme.background.with_alpha(0.8),
+    );
+
+    for (i, (name, color)) in entries.enumerate() {
+        let y = legend.min.y + theme.padding + i as f32 * line_height;
+        let marker_min = Vec2::new(
+            legend.min.x + theme.padding,
+            y + (line_height - marker) * 0.5,
+        );
+
+        ctx.list.rect(
+            Rect::from_pos_size(marker_min, Vec2::new(marker, marker)),
+            color,
+        );
+        let pos = Vec2::new(marker_min.x + marker + theme.padding, y);
+        ctx.list.text(pos, name, size, theme.text);
+    }
+}
+
+//-----------------------------------------------------------------------------
@@ -0,0 +1,107 @@
+//-----------------------------------------------------------------------------
+use crate::draw::DrawList;
+use crate::text::TextMeasure;
+use crate::theme::Theme;
+use crate::Rect;
+use soh_math::Vec2;
+use std::ops::Range;
+//-----------------------------------------------------------------------------
+
+/// Vertical scroll of rows of equal height which the widget draws itself (only the visible rows
+/// are drawn, so the number of rows doesn't matter)
+#[derive(Debug, Default, Clone, Copy)]
+pub(super) struct RowScroll {
+    pub offset: f32,
+}
+
+const SCROLLBAR_WIDTH: f32 = 6.0;
+const MIN_THUMB_LENGTH: f32 = 16.0;
+
+//-----------------------------------------------------------------------------
+
+pub(super) fn row_height(text: &dyn TextMeasure, theme: &Theme) -> f32 {
+    return text.line_height(theme.font_size) + theme.padding;
+}
+
+impl RowScroll {
+    /// Rows intersecting the viewport
+    pub fn visible(&self, count: usize, row_height: f32, viewport: Rect) -> Range<usize> {
+        if row_height <= 0.0 {
+            return 0..0;
+        }
+
+        let first = (self.offset / row_height).floor() as usize;
+        let last = ((self.offset + viewport.height()) / row_height).ceil() as usize;
+        return first.min(count)..last.min(count);
+    }
+
+    /// Top of the row in the viewport
+    pub fn row_y(&self, row: usize, row_height: f32, viewport: Rect) -> f32 {
+        return viewport.min.y + row as f32 * row_height - self.offset;
+    }
+
+    pub fn row_at(&self, y: f32, count: usize, row_height: f32, viewport: Rect) -> Option<usize> {
+        if y < viewport.min.y || y >= viewport.max.y || row_height <= 0.0 {
+            return None;
+        }
+
+        let row = ((y - viewport.min.y + self.offset) / row_height).floor() as usize;
+        return (row < count).then_some(row);
+    }
+
+    /// Scroll by `delta` pixels, returns whether the offset changed
+    pub fn scroll(&mut self, delta: f32, count: usize, row_height: f32, viewport: Rect) -> bool {
+        let old = self.offset;
+        self.offset -= delta;
+        self.clamp(count, row_height, viewport);
+
+        return self.offset != old;
+    }
+
+    /// Scroll as little as possible to make the row visible
+    pub fn scroll_to(&mut self, row: usize, count: usize, row_height: f32, viewport: Rect) {
+        let top = row as f32 * row_height;
+        let bottom = top + row_height;
+
+        if top < self.offset {
+            self.offset = top;
+        } else if bottom > self.offset + viewport.height() {
+            self.offset = bottom - viewport.height();
+        }
+        self.clamp(count, row_height, viewport);
+    }
+
+    pub fn clamp(&mut self, count: usize, row_height: f32, viewport: Rect) {
+        let max = (count as f32 * row_height - viewport.height()).max(0.0);
+        self.offset = self.offset.clamp(0.0, max);
+    }
+
+    /// Thin thumb on the right edge of the viewport (only if the rows don't fit)
+    pub fn draw_scrollbar(
+        &self,
+        list: &mut DrawList,
+        count: usize,
+        row_height: f32,
+        viewport: Rect,
+        theme: &Theme,
+    ) {
+        let content = count as f32 * row_height;
+        let length = viewport.height();
+        if content <= length {
+            return;
+        }
+
+        let thumb_length = (length / content * length)
+            .max(MIN_THUMB_LENGTH)
+            .min(length);
+        let start = self.offset / (content - length) * (length - thumb_length);
+        let thumb = Rect::from_pos_size(
+            Vec2::new(viewport.max.x - SCROLLBAR_WIDTH, viewport.min.y + start),
+            Vec2::new(SCROLLBAR_WIDTH, thumb_length),
+        );
+
+        list.rounded_rect(thumb, SCROLLBAR_WIDTH * 0.5, theme.border);
+    }
+}
+
+//-----------------------------------------------------------------------------
@@ -0,0 +1,391 @@
+//-----------------------------------------------------------------------------
+use super::{DrawCtx, EventCtx, Response, Widget};
+use crate::input::{Event, PointerButton};
+use crate::{Rect, WidgetId};
+use soh_math::Vec2;
+use std::ops::Range;
+//-----------------------------------------------------------------------------
+
+/// Container which scrolls its children
+///
+/// The children are laid out without being shrunk along the scrolled axes and are shifted by the
+/// scroll offset. Scrolling is done with the mouse wheel, by dragging the scrollbars or by
+/// dragging the content.
+///
+/// A virtualized scroll area (see [`ScrollArea::virtualized`]) has rows of equal height which are
+/// created on demand, so only the visible rows exist as widgets.
+pub struct ScrollArea {
+    horizontal: bool,
+    vertical: bool,
+
+    offset: Vec2<f32>,
+    viewport: Vec2<f32>,
+    content: Vec2<f32>,
+
+    drag: Option<Drag>,
+    rows: Option<VirtualRows>,
+}
+
+pub(crate) struct VirtualRows {
+    pub(crate) count: usize,
+    pub(crate) height: f32,
+    pub(crate) factory: RowFactory,
+    /// Row index and widget of the rows which currently exist
+    pub(crate) widgets: Vec<(usize, WidgetId)>,
+    pub(crate) dirty: bool,
+}
+
+type RowFactory = Box<dyn FnMut(usize) -> Box<dyn Widget>>;
+
+#[derive(Debug, Clone, Copy)]
+struct Drag {
+    kind: DragKind,
+    start_pos: Vec2<f32>,
+    start_offset: Vec2<f32>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DragKind {
+    Content,
+    Thumb { vertical: bool },
+}
+
+const SCROLLBAR_WIDTH: f32 = 8.0;
+const MIN_THUMB_LENGTH: f32 = 16.0;
+/// Rows created outside of the visible part (so small scrolls don't create widgets)
+const OVERSCAN_ROWS: usize = 2;
+
+//-----------------------------------------------------------------------------
+// Constructor
+impl ScrollArea {
+    /// Vertically scrolling area
+    pub fn new() -> Self {
+        return ScrollArea {
+            horizontal: false,
+            vertical: true,
+
+            offset: Vec2::new(0.0, 0.0),
+            viewport: Vec2::new(0.0, 0.0),
+            content: Vec2::new(0.0, 0.0),
+
+            drag: None,
+            rows: None,
+        };
+    }
+
+    /// Vertically scrolling list of `row_count` rows
+    ///
+    /// * `factory`: creates the widget of a row when it becomes visible
+    pub fn virtualized(
+        row_count: usize,
+        row_height: f32,
+        factory: impl FnMut(usize) -> Box<dyn Widget> + 'static,
+    ) -> Self {
+        return ScrollArea {
+            rows: Some(VirtualRows {
+                count: row_count,
+                height: row_height,
+                factory: Box::new(factory),
+                widgets: vec![],
+                dirty: false,
+            }),
+            ..ScrollArea::new()
+        };
+    }
+
+    /// Select the scrolled axes (ignored for virtualized areas)
+    pub fn axes(mut self, horizontal: bool, vertical: bool) -> Self {
+        if self.rows.is_none() {
+            self.horizontal = horizontal;
+            self.vertical = vertical;
+        }
+        return self;
+    }
+}
+
+impl Default for ScrollArea {
+    fn default() -> Self {
+        return Self::new();
+    }
+}
+
+//-----------------------------------------------------------------------------
+// Getters
+impl ScrollArea {
+    pub fn horizontal(&self) -> bool {
+        return self.horizontal;
+    }
+    pub fn vertical(&self) -> bool {
+        return self.vertical;
+    }
+    pub fn offset(&self) -> Vec2<f32> {
+        return self.offset;
+    }
+    /// Size of the visible part (known after the layout)
+    pub fn viewport_size(&self) -> Vec2<f32> {
+        return self.viewport;
+    }
+    /// Size of the scrolled content (known after the layout)
+    pub fn content_size(&self) -> Vec2<f32> {
+        return self.content;
+    }
+    pub fn row_count(&self) -> Option<usize> {
+        return self.rows.as_ref().map(|rows| rows.count);
+    }
+    pub fn max_offset(&self) -> Vec2<f32> {
+        return Vec2::new(
+            (self.content.x - self.viewport.x).max(0.0),
+            (self.content.y - self.viewport.y).max(0.0),
+        );
+    }
+}
+
+//-----------------------------------------------------------------------------
+// Specific implementation
+impl ScrollArea {
+    /// Scroll to the offset (clamped to the content size of the last layout, applied during the
+    /// next layout)
+    pub fn set_offset(&mut self, offset: Vec2<f32>) {
+        let max = self.max_offset();
+
+        self.offset = Vec2::new(
+            match self.horizontal {
+                true => offset.x.clamp(0.0, max.x),
+                false => 0.0,
+            },
+            match self.vertical {
+                true => offset.y.clamp(0.0, max.y),
+                false => 0.0,
+            },
+        );
+    }
+
+    /// Scroll so that the row is at the top of the viewport
+    pub fn scroll_to_row(&mut self, row: usize) {
+        if let Some(height) = self.rows.as_ref().map(|rows| rows.height) {
+            self.set_offset(Vec2::new(self.offset.x, row as f32 * height));
+        }
+    }
+
+    /// Change the number of rows of a virtualized area
+    pub fn set_row_count(&mut self, count: usize) {
+        if let Some(rows) = &mut self.rows {
+            rows.count = count;
+        }
+    }
+
+    /// Recreate the widgets of all rows during the next layout (e.g. after the data changed)
+    pub fn refresh_rows(&mut self) {
+        if let Some(rows) = &mut self.rows {
+            rows.dirty = true;
+        }
+    }
+
+    /// Rows which should exist for the current offset
+    pub(crate) fn visible_rows(&self) -> Option<Range<usize>> {
+        let rows = self.rows.as_ref()?;
+        if rows.height <= 0.0 {
+            return Some(0..0);
+        }
+
+        let first = (self.offset.y / rows.height).floor() as usize;
+        let last = ((self.offset.y + self.viewport.y) / rows.height).ceil() as usize;
+
+        return Some(first.saturating_sub(OVERSCAN_ROWS)..(last + OVERSCAN_ROWS).min(rows.count));
+    }
+
+    pub(crate) fn rows_mut(&mut self) -> Option<&mut VirtualRows> {
+        return self.rows.as_mut();
+    }
+
+    /// Store the sizes computed by the layout (and clamp the offset)
+    pub(crate) fn set_extent(&mut self, viewport: Vec2<f32>, content: Vec2<f32>) {
+        self.viewport = viewport;
+        self.content = content;
+        self.set_offset(self.offset);
+    }
+
+    /// Track and thumb rects of a scrollbar (None if the axis doesn't scroll)
+    fn scrollbar(&self, rect: Rect, vertical: bool) -> Option<(Rect, Rect)> {
+        let (enabled, viewport, content, offset) = match vertical {
+            true => (
+                self.vertical,
+                self.viewport.y,
+                self.content.y,
+                self.offset.y,
+            ),
+            false => (
+                self.horizontal,
+                self.viewport.x,
+                self.content.x,
+                self.offset.x,
+            ),
+        };
+        if !enabled || content <= viewport {
+            return None;
+        }
+
+        let track = match vertical {
+            true => Rect::new(
+                Vec2::new(rect.max.x - SCROLLBAR_WIDTH, rect.min.y),
+                rect.max,
+            ),
+            false => Rect::new(
+                Vec2::new(rect.min.x, rect.max.y - SCROLLBAR_WIDTH),
+                rect.max,
+            ),
+        };
+        let track_length = viewport;
+        let thumb_length = (viewport / content * track_length)
+            .max(MIN_THUMB_LENGTH)
+            .min(track_length);
+        let start = offset / (content - viewport) * (track_length - thumb_length);
+
+        let thumb = match vertical {
+            true => Rect::from_pos_size(
+                track.min + Vec2::new(0.0, start),
+                Vec2::new(SCROLLBAR_WIDTH, thumb_length),
+            ),
+            false => Rect::from_pos_size(
+                track.min + Vec2::new(start, 0.0),
+                Vec2::new(thumb_length, SCROLLBAR_WIDTH),
+            ),
+        };
+
+        return Some((track, thumb));
+    }
+
+    /// Offset change per pixel of thumb movement
+    fn thumb_ratio(&self, rect: Rect, vertical: bool) -> f32 {
+        let Some((track, thumb)) = self.scrollbar(rect, vertical) else {
+            return 0.0;
+        };
+        let (track_length, thumb_length, max_offset) = match vertical {
+            true => (track.height(), thumb.height(), self.max_offset().y),
+            false => (track.width(), thumb.width(), self.max_offset().x),
+        };
+
+        if track_length <= thumb_length {
+            return 0.0;
+        }
+        return max_offset / (track_length - thumb_length);
+    }
+
+    fn start_drag(&mut self, rect: Rect, pos: Vec2<f32>) -> DragKind {
+        for vertical in [true, false] {
+            let Some((track, thumb)) = self.scrollbar(rect, vertical) else {
+                continue;
+            };
+            if !track.contains(pos) {
+                continue;
+            }
+
+            /*
+             * Clicking the track outside of the thumb jumps there
+             */
+            if !thumb.contains(pos) {
+                let ratio = self.thumb_ratio(rect, vertical);
+                let delta = pos - thumb.center();
+                let offset = match vertical {
+                    true => Vec2::new(self.offset.x, self.offset.y + delta.y * ratio),
+                    false => Vec2::new(self.offset.x + delta.x * ratio, self.offset.y),
+                };
+                self.set_offset(offset);
+            }
+
+            return DragKind::Thumb { vertical };
+        }
+
+        return DragKind::Content;
+    }
+}
+
+impl Widget for ScrollArea {
+    fn draw(&self, _ctx: &mut DrawCtx) {}
+
+    fn draw_overlay(&self, ctx: &mut DrawCtx) {
+        let theme = ctx.theme;
+
+        for vertical in [true, false] {
+            let Some((track, thumb)) = self.scrollbar(ctx.rect, vertical) else {
+                continue;
+            };
+
+            let active = self
+                .drag
+                .is_some_and(|drag| drag.kind == DragKind::Thumb { vertical });
+            let thumb_color = match active || ctx.hovered {
+                true => theme.accent,
+                false => theme.border,
+            };
+
+            ctx.list.rect(track, theme.surface.with_alpha(0.5));
+            ctx.list
+                .rounded_rect(thumb, SCROLLBAR_WIDTH * 0.5, thumb_color);
+        }
+    }
+
+    fn clips_children(&self) -> bool {
+        return true;
+    }
+
+    fn on_event(&mut self, ctx: &mut EventCtx, event: &Event) -> Response {
+        match event {
+            Event::Scroll { delta, .. } => {
+                let old = self.offset;
+                self.set_offset(self.offset - *delta);
+
+                if self.offset == old {
+                    // Let the outer scroll area scroll
+                    return Response::Ignored;
+                }
+                return Response::Consumed;
+            }
+            Event::PointerDown {
+                pos,
+                button: PointerButton::Primary,
+            } => {
+                let kind = self.start_drag(ctx.rect, *pos);
+                self.drag = Some(Drag {
+                    kind,
+                    start_pos: *pos,
+                    start_offset: self.offset,
+                });
+
+                ctx.capture_pointer();
+                return Response::Consumed;
+            }
+            Event::PointerMoved { pos } => {
+                let Some(drag) = self.drag else {
+                    return Response::Ignored;
+                };
+                let delta = *pos - drag.start_pos;
+
+                let offset = match drag.kind {
+                    DragKind::Content => drag.start_offset - delta,
+                    DragKind::Thumb { vertical: true } => {
+                        let ratio = self.thumb_ratio(ctx.rect, true);
+                        drag.start_offset + Vec2::new(0.0, delta.y * ratio)
+                    }
+                    DragKind::Thumb { vertical: false } => {
+                        let ratio = self.thumb_ratio(ctx.rect, false);
+                        drag.start_offset + Vec2::new(delta.x * ratio, 0.0)
+                    }
+                };
+                self.set_offset(offset);
+
+                return Response::Consumed;
+            }
+            Event::PointerUp {
+                button: PointerButton::Primary,
+                ..
+            } if self.drag.is_some() => {
+                self.drag = None;
+                return Response::Consumed;
+            }
+            _ => return Response::Ignored,
+        }
+    }
+}
+
+//-----------------------------------------------------------------------------
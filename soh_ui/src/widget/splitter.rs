@@ -0,0 +1,173 @@
+//-----------------------------------------------------------------------------
+use super::{DrawCtx, EventCtx, Response, Widget};
+use crate::input::{Event, PointerButton};
+use crate::layout::{Dimension, Direction, Edges, Style};
+use crate::{Rect, RectExt};
+use soh_math::Vec2;
+//-----------------------------------------------------------------------------
+
+/// Container which splits its space between the first two children with a draggable handle
+///
+/// The splitter controls the direction, padding and gap of its own style and the sizes of the
+/// children (further children are not sized).
+#[derive(Debug, Clone)]
+pub struct Splitter {
+    direction: Direction,
+    /// Fraction of the space given to the first child
+    ratio: f32,
+    min_ratio: f32,
+    max_ratio: f32,
+    dragging: bool,
+}
+
+const HANDLE_WIDTH: f32 = 6.0;
+
+//-----------------------------------------------------------------------------
+// Constructor
+impl Splitter {
+    /// * `direction`: `Row` places the children side by side, `Column` on top of each other
+    pub fn new(direction: Direction, ratio: f32) -> Self {
+        return Splitter {
+            direction,
+            ratio: ratio.clamp(0.0, 1.0),
+            min_ratio: 0.05,
+            max_ratio: 0.95,
+            dragging: false,
+        };
+    }
+
+    /// Limit the ratio the user can drag the handle to
+    pub fn limits(mut self, min_ratio: f32, max_ratio: f32) -> Self {
+        self.min_ratio = min_ratio;
+        self.max_ratio = max_ratio.max(min_ratio);
+        self.ratio = self.ratio.clamp(self.min_ratio, self.max_ratio);
+        return self;
+    }
+}
+
+//-----------------------------------------------------------------------------
+// Getters
+impl Splitter {
+    pub fn direction(&self) -> Direction {
+        return self.direction;
+    }
+    pub fn ratio(&self) -> f32 {
+        return self.ratio;
+    }
+    pub fn is_dragging(&self) -> bool {
+        return self.dragging;
+    }
+}
+
+//-----------------------------------------------------------------------------
+// Specific implementation
+impl Splitter {
+    pub fn set_ratio(&mut self, ratio: f32) {
+        self.ratio = ratio.clamp(self.min_ratio, self.max_ratio);
+    }
+
+    /// Rect of the handle between the children
+    pub fn handle_rect(&self, rect: Rect) -> Rect {
+        return match self.direction {
+            Direction::Row => {
+                let x = rect.min.x + self.ratio * rect.width();
+                Rect::new(
+                    Vec2::new(x, rect.min.y),
+                    Vec2::new(x + HANDLE_WIDTH, rect.max.y),
+                )
+            }
+            Direction::Column => {
+                let y = rect.min.y + self.ratio * rect.height();
+                Rect::new(
+                    Vec2::new(rect.min.x, y),
+                    Vec2::new(rect.max.x, y + HANDLE_WIDTH),
+                )
+            }
+        };
+    }
+}
+
+impl Widget for Splitter {
+    fn draw(&self, _ctx: &mut DrawCtx) {}
+
+    fn draw_overlay(&self, ctx: &mut DrawCtx) {
+        let handle = self.handle_rect(ctx.rect);
+        let color = match self.dragging {
+            true => ctx.theme.accent,
+            false => ctx.theme.border,
+        };
+
+        /*
+         * Thin line in the middle of the handle
+         */
+        let line = match self.direction {
+            Direction::Row => handle.shrink(Edges::symmetric(HANDLE_WIDTH * 0.5 - 0.5, 0.0)),
+            Direction::Column => handle.shrink(Edges::symmetric(0.0, HANDLE_WIDTH * 0.5 - 0.5)),
+        };
+        ctx.list.rect(line, color);
+    }
+
+    fn adjust_styles(&self, style: &mut Style, children: &mut [Style]) {
+        style.direction = self.direction;
+        style.gap = HANDLE_WIDTH;
+        style.padding = Edges::ZERO;
+        style.wrap = Default::default();
+
+        let set_main = |style: &mut Style, dimension: Dimension| match self.direction {
+            Direction::Row => style.width = dimension,
+            Direction::Column => style.height = dimension,
+        };
+
+        if let [first, second, ..] = children {
+            // Percentages resolve against the whole space (including the handle)
+            first.basis = Dimension::Percent(self.ratio);
+            first.grow = 0.0;
+            first.shrink = 0.0;
+            set_main(first, Dimension::Auto);
+
+            second.basis = Dimension::Px(0.0);
+            second.grow = 1.0;
+            second.shrink = 0.0;
+            set_main(second, Dimension::Auto);
+
+            for child in [first, second] {
+                child.min_size = Vec2::new(0.0, 0.0);
+            }
+        }
+    }
+
+    fn on_event(&mut self, ctx: &mut EventCtx, event: &Event) -> Response {
+        match event {
+            Event::PointerDown {
+                pos,
+                button: PointerButton::Primary,
+            } if self.handle_rect(ctx.rect).contains(*pos) => {
+                self.dragging = true;
+                ctx.capture_pointer();
+                return Response::Consumed;
+            }
+            Event::PointerMoved { pos } if self.dragging => {
+                let rect = ctx.rect;
+                let ratio = match self.direction {
+                    Direction::Row => (pos.x - rect.min.x - HANDLE_WIDTH * 0.5) / rect.width(),
+                    Direction::Column => (pos.y - rect.min.y - HANDLE_WIDTH * 0.5) / rect.height(),
+                };
+
+                if ratio.is_finite() {
+                    self.set_ratio(ratio);
+                }
+                return Response::Consumed;
+            }
+            Event::PointerUp {
+                button: PointerButton::Primary,
+                ..
+            } if self.dragging => {
+                self.dragging = false;
+                return Response::Consumed;
+            }
+            _ => return Response::Ignored,
+        }
+    }
+}
+
+//-----------------------------------------------------------------------------
@@ -0,0 +1,114 @@
+//-----------------------------------------------------------------------------
+use super::{DrawCtx, EventCtx, MeasureCtx, Response, Widget};
+use crate::input::{Event, PointerButton};
+use crate::layout::Edges;
+use crate::{Rect, RectExt};
+use soh_math::Vec2;
+//-----------------------------------------------------------------------------
+
+/// Row of tabs of equal width (the active tab is highlighted)
+#[derive(Debug, Clone)]
+pub struct TabBar {
+    titles: Vec<String>,
+    active: usize,
+}
+
+pub const TAB_WIDTH: f32 = 120.0;
+
+//-----------------------------------------------------------------------------
+// Constructor
+impl TabBar {
+    pub fn new(titles: Vec<String>, active: usize) -> Self {
+        return TabBar { titles, active };
+    }
+}
+
+//-----------------------------------------------------------------------------
+// Getters
+impl TabBar {
+    pub fn titles(&self) -> &[String] {
+        return &self.titles;
+    }
+    pub fn active(&self) -> usize {
+        return self.active;
+    }
+}
+
+//-----------------------------------------------------------------------------
+// Specific implementation
+impl TabBar {
+    pub fn set_active(&mut self, active: usize) {
+        self.active = active.min(self.titles.len().saturating_sub(1));
+    }
+
+    /// Index of the tab at the position
+    pub fn tab_at(&self, rect: Rect, pos: Vec2<f32>) -> Option<usize> {
+        if !rect.contains(pos) {
+            return None;
+        }
+
+        let index = ((pos.x - rect.min.x) / TAB_WIDTH) as usize;
+        return (index < self.titles.len()).then_some(index);
+    }
+
+    pub fn tab_rect(&self, rect: Rect, index: usize) -> Rect {
+        let min = Vec2::new(rect.min.x + index as f32 * TAB_WIDTH, rect.min.y);
+        return Rect::new(min, Vec2::new(min.x + TAB_WIDTH, rect.max.y));
+    }
+}
+
+impl Widget for TabBar {
+    fn measure(&self, ctx: &MeasureCtx, _available: Vec2<f32>) -> Vec2<f32> {
+        let height = ctx.text.line_height(ctx.theme.font_size) + 2.0 * ctx.theme.padding;
+        return Vec2::new(self.titles.len() as f32 * TAB_WIDTH, height);
+    }
+
+    fn draw(&self, ctx: &mut DrawCtx) {
+        let theme = ctx.theme;
+        let line_height = ctx.text.line_height(theme.font_size);
+
+        ctx.list.push_clip(ctx.rect);
+
+        for (i, title) in self.titles.iter().enumerate() {
+            let rect = self.tab_rect(ctx.rect, i);
+            let (background, color) = match i == self.active {
+                true => (theme.surface, theme.text),
+                false => (theme.background, theme.text_muted),
+            };
+
+            ctx.list
+                .rect(rect.shrink(Edges::symmetric(0.5, 0.0)), background);
+            if i == self.active {
+                let underline = Rect::new(Vec2::new(rect.min.x, rect.max.y - 2.0), rect.max);
+                ctx.list.rect(underline, theme.accent);
+            }
+
+            let pos = Vec2::new(
+                rect.min.x + theme.padding,
+                rect.center().y - line_height * 0.5,
+            );
+            ctx.list.push_clip(rect);
+            ctx.list.text(pos, title, theme.font_size, color);
+            ctx.list.pop_clip();
+        }
+
+        ctx.list.pop_clip();
+    }
+
+    fn on_event(&mut self, ctx: &mut EventCtx, event: &Event) -> Response {
+        if let Event::PointerDown {
+            pos,
+            button: PointerButton::Primary,
+        } = event
+        {
+            if let Some(index) = self.tab_at(ctx.rect, *pos) {
+                self.active = index;
+                return Response::Consumed;
+            }
+        }
+
+        return Response::Ignored;
+    }
+}
+
+//-----------------------------------------------------------------------------
@@ -0,0 +1,468 @@
+//-----------------------------------------------------------------------------
+use super::rows::{row_height, RowScroll};
+use super::{DrawCtx, EventCtx, MeasureCtx, Response, Widget};
+use crate::input::{Event, Key, PointerButton};
+use crate::layout::Edges;
+use crate::{Rect, RectExt};
+use soh_math::Vec2;
+use std::cmp::Ordering;
+//-----------------------------------------------------------------------------
+
+/// Rows of text cells under a header of sortable and resizable columns
+///
+/// The cells are provided by a function of the row and column index and only the cells of the
+/// visible rows are requested, so the table can have millions of rows. Sorting requests the cells
+/// of the sorted column once.
+pub struct Table {
+    columns: Vec<TableColumn>,
+    row_count: usize,
+    cell: CellFn,
+
+    sort: Option<(usize, SortOrder)>,
+    /// Data row of each displayed row ( `None` if not sorted )
+    order: Option<Vec<usize>>,
+    /// Data row
+    selected: Option<usize>,
+    scroll: RowScroll,
+    resize: Option<Resize>,
+
+    on_select: Option<Box<dyn FnMut(usize)>>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TableColumn {
+    title: String,
+    width: f32,
+    min_width: f32,
+    sortable: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SortOrder {
+    Ascending,
+    Descending,
+}
+
+type CellFn = Box<dyn Fn(usize, usize) -> String>;
+
+#[derive(Debug, Clone, Copy)]
+struct Resize {
+    column: usize,
+    start_x: f32,
+    start_width: f32,
+}
+
+/// Distance from the edge of a header cell at which dragging resizes the column
+const RESIZE_MARGIN: f32 = 4.0;
+
+//-----------------------------------------------------------------------------
+// Constructor
+impl Table {
+    /// * `cell`: text of the cell at the data row and column
+    pub fn new(
+        columns: Vec<TableColumn>,
+        row_count: usize,
+        cell: impl Fn(usize, usize) -> String + 'static,
+    ) -> Self {
+        return Table {
+            columns,
+            row_count,
+            cell: Box::new(cell),
+
+            sort: None,
+            order: None,
+            selected: None,
+            scroll: RowScroll::default(),
+            resize: None,
+
+            on_select: None,
+        };
+    }
+}
+
+impl TableColumn {
+    pub fn new(title: impl Into<String>, width: f32) -> Self {
+        return TableColumn {
+            title: title.into(),
+            width,
+            min_width: 24.0,
+            sortable: true,
+        };
+    }
+
+    pub fn min_width(mut self, min_width: f32) -> Self {
+        self.min_width = min_width;
+        self.width = self.width.max(min_width);
+        return self;
+    }
+
+    pub fn sortable(mut self, sortable: bool) -> Self {
+        self.sortable = sortable;
+        return self;
+    }
+}
+
+//-----------------------------------------------------------------------------
+// Getters
+impl Table {
+    pub fn columns(&self) -> &[TableColumn] {
+        return &self.columns;
+    }
+    pub fn row_count(&self) -> usize {
+        return self.row_count;
+    }
+    pub fn sort(&self) -> Option<(usize, SortOrder)> {
+        return self.sort;
+    }
+    /// Data row of the selected row
+    pub fn selected(&self) -> Option<usize> {
+        return self.selected;
+    }
+
+    /// Data row shown at the position ( after sorting )
+    pub fn data_row(&self, display_row: usize) -> usize {
+        return match &self.order {
+            Some(order) => order[display_row],
+            None => display_row,
+        };
+    }
+
+    /// Position at which the data row is shown
+    pub fn display_row(&self, data_row: usize) -> Option<usize> {
+        return match &self.order {
+            Some(order) => order.iter().position(|&row| row == data_row),
+            None => (data_row < self.row_count).then_some(data_row),
+        };
+    }
+
+    pub fn cell(&self, data_row: usize, column: usize) -> String {
+        return (self.cell)(data_row, column);
+    }
+}
+
+impl TableColumn {
+    pub fn title(&self) -> &str {
+        return &self.title;
+    }
+    pub fn width(&self) -> f32 {
+        return self.width;
+    }
+    pub fn is_sortable(&self) -> bool {
+        return self.sortable;
+    }
+}
+
+//-----------------------------------------------------------------------------
+// Specific implementation
+impl Table {
+    /// Change the number of rows ( the data changed, the sort order is recomputed )
+    pub fn set_row_count(&mut self, count: usize) {
+        self.row_count = count;
+        self.selected = self.selected.filter(|&row| row < count);
+        self.refresh();
+    }
+
+    /// Sort again after the data changed
+    pub fn refresh(&mut self) {
+        match self.sort {
+            Some((column, order)) => self.sort_by(column, order),
+            None => self.order = None,
+        }
+    }
+
+    /// Sort the rows by the cells of the column ( numerically if both cells are numbers )
+    pub fn sort_by(&mut self, column: usize, order: SortOrder) {
+        let keys = (0..self.row_count)
+            .map(|row| self.cell(row, column))
+            .collect::<Vec<_>>();
+
+        let mut rows = (0..self.row_count).collect::<Vec<_>>();
+        rows.sort_by(|&a, &b| {
+            let ordering = compare_cells(&keys[a], &keys[b]);
+            return match order {
+                SortOrder::Ascending => ordering,
+                SortOrder::Descending => ordering.reverse(),
+            };
+        });
+
+        self.sort = Some((column, order));
+        self.order = Some(rows);
+    }
+
+    /// Show the rows in the order of the data
+    pub fn clear_sort(&mut self) {
+        self.sort = None;
+        self.order = None;
+    }
+
+    pub fn set_column_width(&mut self, column: usize, width: f32) {
+        let column = &mut self.columns[column];
+        column.width = width.max(column.min_width);
+    }
+
+    /// Set the callback which is called with the data row when the user selects a row
+    pub fn on_select(&mut self, callback: impl FnMut(usize) + 'static) -> &mut Self {
+        self.on_select = Some(Box::new(callback));
+        return self;
+    }
+
+    /// Select the data row (doesn't call the `on_select` callback)
+    pub fn select(&mut self, data_row: Option<usize>) {
+        self.selected = data_row;
+    }
+
+    fn select_by_user(&mut self, data_row: usize) {
+        if self.selected == Some(data_row) {
+            return;
+        }
+
+        self.selected = Some(data_row);
+        if let Some(on_select) = &mut self.on_select {
+            on_select(data_row);
+        }
+    }
+
+    /// Horizontal span of each column
+    fn column_spans(&self, rect: Rect) -> impl Iterator<Item = (f32, f32)> + '_ {
+        let mut x = rect.min.x;
+        return self.columns.iter().map(move |column| {
+            let span = (x, x + column.width);
+            x += column.width;
+            return span;
+        });
+    }
+
+    /// Header and the rows below it
+    fn split(&self, rect: Rect, row_height: f32) -> (Rect, Rect) {
+        let y = (rect.min.y + row_height).min(rect.max.y);
+        return (
+            Rect::new(rect.min, Vec2::new(rect.max.x, y)),
+            Rect::new(Vec2::new(rect.min.x, y), rect.max),
+        );
+    }
+
+    fn on_header_click(&mut self, rect: Rect, pos: Vec2<f32>) {
+        let spans = self.column_spans(rect).collect::<Vec<_>>();
+
+        /*
+         * Dragging the right edge of a column resizes it
+         */
+        let edge = spans
+            .iter()
+            .position(|&(_, end)| (pos.x - end).abs() <= RESIZE_MARGIN);
+        if let Some(column) = edge {
+            self.resize = Some(Resize {
+                column,
+                start_x: pos.x,
+                start_width: self.columns[column].width,
+            });
+            return;
+        }
+
+        /*
+         * Clicking a column sorts by it, clicking it again reverses the order
+         */
+        let Some(column) = spans
+            .iter()
+            .position(|&(start, end)| (start..end).contains(&pos.x))
+        else {
+            return;
+        };
+        if !self.columns[column].sortable {
+            return;
+        }
+
+        let order = match self.sort {
+            Some((sorted, SortOrder::Ascending)) if sorted == column => SortOrder::Descending,
+            _ => SortOrder::Ascending,
+        };
+        self.sort_by(column, order);
+    }
+
+    fn on_key(&mut self, key: Key, page: usize) -> Response {
+        if self.row_count == 0 {
+            return Response::Ignored;
+        }
+
+        let last = self.row_count - 1;
+        let current = self.selected.and_then(|row| self.display_row(row));
+        let row = match (key, current) {
+            (Key::Up | Key::Down, None) | (Key::Home, _) => 0,
+            (Key::End, _) => last,
+            (Key::Up, Some(row)) => row.saturating_sub(1),
+            (Key::Down, Some(row)) => (row + 1).min(last),
+            (Key::PageUp, Some(row)) => row.saturating_sub(page),
+            (Key::PageDown, Some(row)) => (row + page).min(last),
+            _ => return Response::Ignored,
+        };
+
+        self.select_by_user(self.data_row(row));
+        return Response::Consumed;
+    }
+}
+
+impl Widget for Table {
+    fn measure(&self, ctx: &MeasureCtx, _available: Vec2<f32>) -> Vec2<f32> {
+        let width = self.columns.iter().map(|column| column.width).sum();
+        return Vec2::new(width, row_height(ctx.text, ctx.theme) * 8.0);
+    }
+
+    fn draw(&self, ctx: &mut DrawCtx) {
+        let theme = ctx.theme;
+        let row_height = row_height(ctx.text, theme);
+        let line_height = ctx.text.line_height(theme.font_size);
+        let (header, body) = self.split(ctx.rect, row_height);
+        let spans = self.column_spans(ctx.rect).collect::<Vec<_>>();
+
+        ctx.list.push_clip(ctx.rect);
+
+        /*
+         * Header with the sort direction and the column separators
+         */
+        ctx.list.rect(header, theme.surface);
+        for (i, (column, &(start, end))) in self.columns.iter().zip(&spans).enumerate() {
+            let cell = Rect::new(Vec2::new(start, header.min.y), Vec2::new(end, header.max.y));
+            let mut title = column.title.clone();
+            match self.sort {
+                Some((sorted, SortOrder::Ascending)) if sorted == i => title.push_str(" ^"),
+                Some((sorted, SortOrder::Descending)) if sorted == i => title.push_str(" v"),
+                _ => {}
+            }
+
+            let color = match self.resize.is_some_and(|resize| resize.column == i) {
+                true => theme.accent,
+                false => theme.border,
+            };
+            ctx.list
+                .rect(Rect::new(Vec2::new(end - 1.0, cell.min.y), cell.max), color);
+
+            let pos = Vec2::new(start + theme.padding, cell.center().y - line_height * 0.5);
+            ctx.list.push_clip(cell.shrink(Edges::symmetric(1.0, 0.0)));
+            ctx.list.text(pos, &title, theme.font_size, theme.text);
+            ctx.list.pop_clip();
+        }
+
+        /*
+         * Visible rows
+         */
+        ctx.list.push_clip(body);
+        for i in self.scroll.visible(self.row_count, row_height, body) {
+            let data_row = self.data_row(i);
+            let y = self.scroll.row_y(i, row_height, body);
+            let row = Rect::new(
+                Vec2::new(body.min.x, y),
+                Vec2::new(body.max.x, y + row_height),
+            );
+
+            if self.selected == Some(data_row) {
+                let color = match ctx.focused {
+                    true => theme.selection,
+                    false => theme.surface,
+                };
+                ctx.list.rect(row, color);
+            }
+
+            for (column, &(start, end)) in spans.iter().enumerate() {
+                let cell = Rect::new(Vec2::new(start, row.min.y), Vec2::new(end, row.max.y));
+                let pos = Vec2::new(start + theme.padding, cell.center().y - line_height * 0.5);
+
+                ctx.list.push_clip(cell);
+                ctx.list.text(
+                    pos,
+                    &self.cell(data_row, column),
+                    theme.font_size,
+                    theme.text,
+                );
+                ctx.list.pop_clip();
+            }
+        }
+        ctx.list.pop_clip();
+
+        self.scroll
+            .draw_scrollbar(ctx.list, self.row_count, row_height, body, theme);
+        ctx.list.pop_clip();
+    }
+
+    fn on_event(&mut self, ctx: &mut EventCtx, event: &Event) -> Response {
+        let row_height = row_height(ctx.text, ctx.theme);
+        let (header, body) = self.split(ctx.rect, row_height);
+
+        match event {
+            Event::Scroll { delta, .. } => {
+                return match self
+                    .scroll
+                    .scroll(delta.y, self.row_count, row_height, body)
+                {
+                    true => Response::Consumed,
+                    // Let the outer scroll area scroll
+                    false => Response::Ignored,
+                };
+            }
+            Event::PointerDown {
+                pos,
+                button: PointerButton::Primary,
+            } => {
+                ctx.request_focus();
+
+                if header.contains(*pos) {
+                    self.on_header_click(ctx.rect, *pos);
+                    if self.resize.is_some() {
+                        ctx.capture_pointer();
+                    }
+                } else if let Some(row) =
+                    self.scroll.row_at(pos.y, self.row_count, row_height, body)
+                {
+                    self.select_by_user(self.data_row(row));
+                }
+                return Response::Consumed;
+            }
+            Event::PointerMoved { pos } => {
+                let Some(resize) = self.resize else {
+                    return Response::Ignored;
+                };
+
+                self.set_column_width(resize.column, resize.start_width + pos.x - resize.start_x);
+                return Response::Consumed;
+            }
+            Event::PointerUp {
+                button: PointerButton::Primary,
+                ..
+            } if self.resize.is_some() => {
+                self.resize = None;
+                return Response::Consumed;
+            }
+            Event::KeyDown { key, .. } if ctx.focused => {
+                let page = ((body.height() / row_height) as usize).max(1);
+                let response = self.on_key(*key, page);
+
+                /*
+                 * Keep the selected row visible
+                 */
+                if let Some(row) = self.selected.and_then(|row| self.display_row(row)) {
+                    self.scroll.scroll_to(row, self.row_count, row_height, body);
+                }
+                return response;
+            }
+            _ => return Response::Ignored,
+        }
+    }
+
+    fn is_focusable(&self) -> bool {
+        return true;
+    }
+}
+
+//-----------------------------------------------------------------------------
+
+/// Numbers are compared by value and come before the text, which is compared as text ( so the
+/// order is total even for columns mixing both )
+fn compare_cells(a: &str, b: &str) -> Ordering {
+    return match (a.trim().parse::<f64>(), b.trim().parse::<f64>()) {
+        (Ok(a), Ok(b)) => a.total_cmp(&b),
+        (Ok(_), Err(_)) => Ordering::Less,
+        (Err(_), Ok(_)) => Ordering::Greater,
+        (Err(_), Err(_)) => a.cmp(b),
+    };
+}
+
+//-----------------------------------------------------------------------------
@@ -0,0 +1,454 @@
+//-----------------------------------------------------------------------------
+use super::{DrawCtx, EventCtx, MeasureCtx, Response, Widget};
+use crate::input::{Event, Key, Modifiers, PointerButton};
+use crate::layout::Edges;
+use crate::text::TextMeasure;
+use crate::theme::Theme;
+use crate::{Rect, RectExt};
+use soh_math::Vec2;
+//-----------------------------------------------------------------------------
+
+/// Single line text field
+///
+/// Cursor and anchor are byte indices into the text, the selection is the range between them.
+pub struct TextInput {
+    text: String,
+    placeholder: String,
+    /// Overrides the font size of the theme
+    font_size: Option<f32>,
+
+    cursor: usize,
+    anchor: usize,
+    /// Horizontal scroll of the text (in pixels) which keeps the cursor visible
+    scroll: f32,
+    dragging: bool,
+
+    on_change: Option<Callback>,
+    on_submit: Option<Callback>,
+}
+
+type Callback = Box<dyn FnMut(&str)>;
+
+const CURSOR_WIDTH: f32 = 1.0;
+
+//-----------------------------------------------------------------------------
+// Constructor
+impl TextInput {
+    pub fn new() -> Self {
+        return TextInput {
+            text: String::new(),
+            placeholder: String::new(),
+            font_size: None,
+
+            cursor: 0,
+            anchor: 0,
+            scroll: 0.0,
+            dragging: false,
+
+            on_change: None,
+            on_submit: None,
+        };
+    }
+
+    pub fn placeholder(mut self, placeholder: impl Into<String>) -> Self {
+        self.placeholder = placeholder.into();
+        return self;
+    }
+
+    pub fn font_size(mut self, size: f32) -> Self {
+        self.font_size = Some(size);
+        return self;
+    }
+}
+
+impl Default for TextInput {
+    fn default() -> Self {
+        return Self::new();
+    }
+}
+
+//-----------------------------------------------------------------------------
+// Getters
+impl TextInput {
+    pub fn text(&self) -> &str {
+        return &self.text;
+    }
+    pub fn cursor(&self) -> usize {
+        return self.cursor;
+    }
+    pub fn selection(&self) -> std::ops::Range<usize> {
+        return self.cursor.min(self.anchor)..self.cursor.max(self.anchor);
+    }
+    pub fn selected_text(&self) -> &str {
+        return &self.text[self.selection()];
+    }
+}
+
+//-----------------------------------------------------------------------------
+// Specific implementation
+impl TextInput {
+    /// Replace the text (doesn't call the `on_change` callback)
+    pub fn set_text(&mut self, text: impl Into<String>) -> &mut Self {
+        self.text = text.into();
+        self.cursor = self.text.len();
+        self.anchor = self.cursor;
+        return self;
+    }
+
+    /// Set the callback which is called with the new text after every edit
+    pub fn on_change(&mut self, callback: impl FnMut(&str) + 'static) -> &mut Self {
+        self.on_change = Some(Box::new(callback));
+        return self;
+    }
+
+    /// Set the callback which is called when Enter is pressed
+    pub fn on_submit(&mut self, callback: impl FnMut(&str) + 'static) -> &mut Self {
+        self.on_submit = Some(Box::new(callback));
+        return self;
+    }
+
+    pub fn select_all(&mut self) {
+        self.anchor = 0;
+        self.cursor = self.text.len();
+    }
+
+    fn resolved_font_size(&self, theme: &Theme) -> f32 {
+        return self.font_size.unwrap_or(theme.font_size);
+    }
+
+    fn changed(&mut self) {
+        if let Some(on_change) = &mut self.on_change {
+            on_change(&self.text);
+        }
+    }
+
+    fn move_cursor(&mut self, pos: usize, select: bool) {
+        self.cursor = pos;
+        if !select {
+            self.anchor = pos;
+        }
+    }
+
+    /// Replace the selection with `text`, returns false if nothing changed
+    fn insert(&mut self, text: &str) -> bool {
+        let range = self.selection();
+        if range.is_empty() && text.is_empty() {
+            return false;
+        }
+
+        self.text.replace_range(range.clone(), text);
+        self.move_cursor(range.start + text.len(), false);
+
+        return true;
+    }
+
+    /// Delete the selection or the text between the cursor and `pos`
+    fn delete_to(&mut self, pos: usize) -> bool {
+        if self.cursor == self.anchor {
+            self.anchor = pos;
+        }
+        return self.insert("");
+    }
+
+    fn prev_char(&self, pos: usize) -> usize {
+        return self.text[..pos]
+            .char_indices()
+            .next_back()
+            .map_or(0, |(i, _)| i);
+    }
+
+    fn next_char(&self, pos: usize) -> usize {
+        return self.text[pos..]
+            .chars()
+            .next()
+            .map_or(pos, |c| pos + c.len_utf8());
+    }
+
+    /// Start of the word before `pos` (skipping the whitespace before it)
+    fn prev_word(&self, pos: usize) -> usize {
+        let mut pos = pos;
+
+        while pos > 0 && self.char_before(pos).is_some_and(char::is_whitespace) {
+            pos = self.prev_char(pos);
+        }
+        while pos > 0 && self.char_before(pos).is_some_and(|c| !c.is_whitespace()) {
+            pos = self.prev_char(pos);
+        }
+
+        return pos;
+    }
+
+    /// End of the word after `pos` (skipping the whitespace before it)
+    fn next_word(&self, pos: usize) -> usize {
+        let mut pos = pos;
+        let char_at = |pos: usize| self.text[pos..].chars().next();
+
+        while char_at(pos).is_some_and(char::is_whitespace) {
+            pos = self.next_char(pos);
+        }
+        while char_at(pos).is_some_and(|c| !c.is_whitespace()) {
+            pos = self.next_char(pos);
+        }
+
+        return pos;
+    }
+
+    fn char_before(&self, pos: usize) -> Option<char> {
+        return self.text[..pos].chars().next_back();
+    }
+
+    /// Byte index of the character boundary closest to the x coordinate
+    fn index_at(&self, ctx: &EventCtx, x: f32) -> usize {
+        let (text, rect) = (ctx.text, ctx.rect);
+        let font_size = self.resolved_font_size(ctx.theme);
+        let x = x - rect.min.x - ctx.theme.padding + self.scroll;
+
+        let mut best = (0, x.abs());
+        for (i, c) in self.text.char_indices() {
+            let end = i + c.len_utf8();
+            let distance = (text.text_width(&self.text[..end], font_size) - x).abs();
+            if distance < best.1 {
+                best = (end, distance);
+            }
+        }
+
+        return best.0;
+    }
+
+    /// Update the scroll so that the cursor is inside the visible part of the text
+    fn scroll_to_cursor(&mut self, ctx: &EventCtx) {
+        let text: &dyn TextMeasure = ctx.text;
+        let font_size = self.resolved_font_size(ctx.theme);
+
+        let visible = (ctx.rect.width() - 2.0 * ctx.theme.padding - CURSOR_WIDTH).max(0.0);
+        let cursor_x = text.text_width(&self.text[..self.cursor], font_size);
+        let text_width = text.text_width(&self.text, font_size);
+
+        self.scroll = self
+            .scroll
+            .min(cursor_x)
+            .max(cursor_x - visible)
+            .min((text_width - visible).max(0.0));
+    }
+
+    fn on_key(&mut self, ctx: &mut EventCtx, key: Key, modifiers: Modifiers) -> Response {
+        let select = modifiers.shift;
+        let word = modifiers.ctrl || modifiers.alt;
+        let mut changed = false;
+
+        match key {
+            Key::Left => {
+                let pos = match (word, self.cursor != self.anchor && !select) {
+                    (_, true) => self.selection().start,
+                    (true, false) => self.prev_word(self.cursor),
+                    (false, false) => self.prev_char(self.cursor),
+                };
+                self.move_cursor(pos, select);
+            }
+            Key::Right => {
+                let pos = match (word, self.cursor != self.anchor && !select) {
+                    (_, true) => self.selection().end,
+                    (true, false) => self.next_word(self.cursor),
+                    (false, false) => self.next_char(self.cursor),
+                };
+                self.move_cursor(pos, select);
+            }
+            Key::Home => self.move_cursor(0, select),
+            Key::End => self.move_cursor(self.text.len(), select),
+            Key::Backspace => {
+                let pos = match word {
+                    true => self.prev_word(self.cursor),
+                    false => self.prev_char(self.cursor),
+                };
+                changed = self.delete_to(pos);
+            }
+            Key::Delete => {
+                let pos = match word {
+                    true => self.next_word(self.cursor),
+                    false => self.next_char(self.cursor),
+                };
+                changed = self.delete_to(pos);
+            }
+            Key::Enter => {
+                if let Some(on_submit) = &mut self.on_submit {
+                    on_submit(&self.text);
+                }
+            }
+            Key::Escape => ctx.release_focus(),
+            Key::Char('a') if modifiers.command() => self.select_all(),
+            Key::Char('c') if modifiers.command() => {
+                if self.cursor != self.anchor {
+                    ctx.clipboard.set(self.selected_text());
+                }
+            }
+            Key::Char('x') if modifiers.command() => {
+                if self.cursor != self.anchor {
+                    ctx.clipboard.set(self.selected_text());
+                    changed = self.insert("");
+                }
+            }
+            Key::Char('v') if modifiers.command() => {
+                if let Some(text) = ctx.clipboard.get() {
+                    // Single line: newlines become spaces
+                    let text = text
+                        .chars()
+                        .map(|c| if c == '\n' { ' ' } else { c })
+                        .filter(|c| !c.is_control())
+                        .collect::<String>();
+                    changed = self.insert(&text);
+                }
+            }
+            _ => return Response::Ignored,
+        }
+
+        if changed {
+            self.changed();
+        }
+
+        return Response::Consumed;
+    }
+}
+
+impl Widget for TextInput {
+    fn measure(&self, ctx: &MeasureCtx, _available: Vec2<f32>) -> Vec2<f32> {
+        const MIN_WIDTH: f32 = 100.0;
+
+        let font_size = self.resolved_font_size(ctx.theme);
+        let width = ctx.text.text_width(&self.text, font_size) + CURSOR_WIDTH;
+        let height = ctx.text.line_height(font_size);
+
+        return Vec2::new(width.max(MIN_WIDTH), height) + Vec2::new(2.0, 2.0) * ctx.theme.padding;
+    }
+
+    fn draw(&self, ctx: &mut DrawCtx) {
+        let theme = ctx.theme;
+        let border = match ctx.focused {
+            true => theme.accent,
+            false => theme.border,
+        };
+        let font_size = self.resolved_font_size(theme);
+
+        let rect = ctx.rect;
+        let inner = rect.shrink(Edges::all(theme.border_width));
+        let line_height = ctx.text.line_height(font_size);
+        let origin = Vec2::new(
+            rect.min.x + theme.padding - self.scroll,
+            rect.center().y - line_height * 0.5,
+        );
+        let x_at = |pos: usize| origin.x + ctx.text.text_width(&self.text[..pos], font_size);
+
+        ctx.list.rounded_rect(rect, theme.corner_radius, border);
+        ctx.list
+            .rounded_rect(inner, theme.corner_radius, theme.surface);
+        ctx.list.push_clip(inner);
+
+        if ctx.focused && self.cursor != self.anchor {
+            let range = self.selection();
+            ctx.list.rect(
+                Rect::new(
+                    Vec2::new(x_at(range.start), origin.y),
+                    Vec2::new(x_at(range.end), origin.y + line_height),
+                ),
+                theme.selection,
+            );
+        }
+
+        if self.text.is_empty() {
+            ctx.list
+                .text(origin, &self.placeholder, font_size, theme.text_muted);
+        } else {
+            ctx.list.text(origin, &self.text, font_size, theme.text);
+        }
+
+        if ctx.focused {
+            let x = x_at(self.cursor);
+            ctx.list.rect(
+                Rect::new(
+                    Vec2::new(x, origin.y),
+                    Vec2::new(x + CURSOR_WIDTH, origin.y + line_height),
+                ),
+                theme.text,
+            );
+        }
+
+        ctx.list.pop_clip();
+    }
+
+    fn on_event(&mut self, ctx: &mut EventCtx, event: &Event) -> Response {
+        let response = match event {
+            Event::PointerDown {
+                pos,
+                button: PointerButton::Primary,
+            } => {
+                let index = self.index_at(ctx, pos.x);
+                self.move_cursor(index, false);
+                self.dragging = true;
+
+                ctx.request_focus();
+                ctx.capture_pointer();
+                Response::Consumed
+            }
+            Event::PointerMoved { pos } if self.dragging => {
+                let index = self.index_at(ctx, pos.x);
+                self.move_cursor(index, true);
+                Response::Consumed
+            }
+            Event::PointerUp {
+                button: PointerButton::Primary,
+                ..
+            } if self.dragging => {
+                self.dragging = false;
+                Response::Consumed
+            }
+            Event::KeyDown { key, modifiers } if ctx.focused => self.on_key(ctx, *key, *modifiers),
+            Event::Text(text) if ctx.focused => {
+                let text = text.chars().filter(|c| !c.is_control()).collect::<String>();
+                if self.insert(&text) {
+                    self.changed();
+                }
+                Response::Consumed
+            }
+            _ => Response::Ignored,
+        };
+
+        if response.is_consumed() {
+            self.scroll_to_cursor(ctx);
+        }
+
+        return response;
+    }
+
+    fn is_focusable(&self) -> bool {
+        return true;
+    }
+}
+
+#[cfg(feature = "serde")]
+impl crate::data::WidgetData for TextInput {
+    const TYPE: &'static str = "TextInput";
+
+    fn from_props(props: &crate::data::Props) -> crate::Result<Self> {
+        let mut input = TextInput::new();
+        input.set_text(props.string("text")?.unwrap_or_default());
+        input.placeholder = props.string("placeholder")?.unwrap_or_default().to_owned();
+        input.font_size = props.number("font_size")?.map(|size| size as f32);
+
+        return Ok(input);
+    }
+
+    fn to_props(&self) -> crate::data::Props {
+        let mut props = crate::data::Props::new();
+        if !self.text.is_empty() {
+            props.set("text", self.text.as_str());
+        }
+        if !self.placeholder.is_empty() {
+            props.set("placeholder", self.placeholder.as_str());
+        }
+        if let Some(size) = self.font_size {
+            props.set("font_size", size);
+        }
+        return props;
+    }
+}
+
+//-----------------------------------------------------------------------------
@@ -0,0 +1,420 @@
+//-----------------------------------------------------------------------------
+use super::rows::{row_height, RowScroll};
+use super::{DrawCtx, EventCtx, MeasureCtx, Response, Widget};
+use crate::input::{Event, Key, PointerButton};
+use crate::Rect;
+use soh_math::Vec2;
+//-----------------------------------------------------------------------------
+
+/// Hierarchy of labels which can be expanded and collapsed ( scene graphs, file browsers )
+///
+/// Only the visible rows are drawn. Children of lazy nodes are loaded by the loader (see
+/// [`TreeView::loader`]) when the node is expanded for the first time.
+pub struct TreeView {
+    nodes: Vec<Node>,
+    roots: Vec<TreeNodeId>,
+    selected: Option<TreeNodeId>,
+    scroll: RowScroll,
+
+    loader: Option<Loader>,
+    on_select: Option<Box<dyn FnMut(TreeNodeId)>>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TreeNodeId(usize);
+
+/// Child node returned by the loader
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TreeItem {
+    pub label: String,
+    /// Whether the node has children to load when it's expanded
+    pub lazy: bool,
+}
+
+#[derive(Debug, Clone)]
+struct Node {
+    label: String,
+    parent: Option<TreeNodeId>,
+    children: Vec<TreeNodeId>,
+    expanded: bool,
+    /// The children haven't been loaded yet
+    lazy: bool,
+}
+
+type Loader = Box<dyn FnMut(TreeNodeId, &str) -> Vec<TreeItem>>;
+
+/// Horizontal offset of each level
+const INDENT: f32 = 16.0;
+
+//-----------------------------------------------------------------------------
+// Constructor
+impl TreeView {
+    pub fn new() -> Self {
+        return TreeView {
+            nodes: vec![],
+            roots: vec![],
+            selected: None,
+            scroll: RowScroll::default(),
+
+            loader: None,
+            on_select: None,
+        };
+    }
+
+    /// Set the function which loads the children of a lazy node ( called with the node and its
+    /// label )
+    pub fn loader(
+        mut self,
+        loader: impl FnMut(TreeNodeId, &str) -> Vec<TreeItem> + 'static,
+    ) -> Self {
+        self.loader = Some(Box::new(loader));
+        return self;
+    }
+}
+
+impl Default for TreeView {
+    fn default() -> Self {
+        return Self::new();
+    }
+}
+
+impl TreeItem {
+    pub fn new(label: impl Into<String>) -> Self {
+        return TreeItem {
+            label: label.into(),
+            lazy: false,
+        };
+    }
+
+    pub fn lazy(label: impl Into<String>) -> Self {
+        return TreeItem {
+            label: label.into(),
+            lazy: true,
+        };
+    }
+}
+
+//-----------------------------------------------------------------------------
+// Getters
+impl TreeView {
+    pub fn roots(&self) -> &[TreeNodeId] {
+        return &self.roots;
+    }
+    pub fn label(&self, id: TreeNodeId) -> &str {
+        return &self.nodes[id.0].label;
+    }
+    pub fn parent(&self, id: TreeNodeId) -> Option<TreeNodeId> {
+        return self.nodes[id.0].parent;
+    }
+    /// Loaded children of the node
+    pub fn children(&self, id: TreeNodeId) -> &[TreeNodeId] {
+        return &self.nodes[id.0].children;
+    }
+    pub fn is_expanded(&self, id: TreeNodeId) -> bool {
+        return self.nodes[id.0].expanded;
+    }
+    pub fn selected(&self) -> Option<TreeNodeId> {
+        return self.selected;
+    }
+
+    /// Whether the node can be expanded
+    pub fn has_children(&self, id: TreeNodeId) -> bool {
+        let node = &self.nodes[id.0];
+        return node.lazy || !node.children.is_empty();
+    }
+
+    /// Rows of the expanded part of the tree with their depth, top to bottom
+    pub fn rows(&self) -> Vec<(TreeNodeId, usize)> {
+        let mut rows = vec![];
+        let mut stack = self
+            .roots
+            .iter()
+            .rev()
+            .map(|&id| (id, 0))
+            .collect::<Vec<_>>();
+
+        while let Some((id, depth)) = stack.pop() {
+            rows.push((id, depth));
+
+            let node = &self.nodes[id.0];
+            if node.expanded {
+                stack.extend(node.children.iter().rev().map(|&child| (child, depth + 1)));
+            }
+        }
+
+        return rows;
+    }
+}
+
+//-----------------------------------------------------------------------------
+// Specific implementation
+impl TreeView {
+    /// Add a node at the end of the children of `parent` ( a root if `None` )
+    pub fn add(&mut self, parent: Option<TreeNodeId>, label: impl Into<String>) -> TreeNodeId {
+        return self.add_item(parent, TreeItem::new(label));
+    }
+
+    /// Add a node whose children are loaded when it's expanded
+    pub fn add_lazy(&mut self, parent: Option<TreeNodeId>, label: impl Into<String>) -> TreeNodeId {
+        return self.add_item(parent, TreeItem::lazy(label));
+    }
+
+    /// Remove all nodes
+    pub fn clear(&mut self) {
+        self.nodes.clear();
+        self.roots.clear();
+        self.selected = None;
+        self.scroll = RowScroll::default();
+    }
+
+    /// Set the callback which is called when the user selects a node
+    pub fn on_select(&mut self, callback: impl FnMut(TreeNodeId) + 'static) -> &mut Self {
+        self.on_select = Some(Box::new(callback));
+        return self;
+    }
+
+    /// Select the node (doesn't call the `on_select` callback)
+    pub fn select(&mut self, id: Option<TreeNodeId>) {
+        self.selected = id;
+    }
+
+    /// Expand the node and its ancestors, loading the children of lazy nodes
+    pub fn expand(&mut self, id: TreeNodeId) {
+        let mut node = Some(id);
+        while let Some(id) = node {
+            self.load_children(id);
+            self.nodes[id.0].expanded = true;
+            node = self.nodes[id.0].parent;
+        }
+    }
+
+    pub fn collapse(&mut self, id: TreeNodeId) {
+        self.nodes[id.0].expanded = false;
+
+        // Don't keep a hidden node selected
+        let mut selected = self.selected;
+        while let Some(node) = selected {
+            selected = self.nodes[node.0].parent;
+            if selected == Some(id) {
+                self.selected = Some(id);
+                break;
+            }
+        }
+    }
+
+    pub fn toggle(&mut self, id: TreeNodeId) {
+        match self.is_expanded(id) {
+            true => self.collapse(id),
+            false => self.expand(id),
+        }
+    }
+
+    fn add_item(&mut self, parent: Option<TreeNodeId>, item: TreeItem) -> TreeNodeId {
+        let id = TreeNodeId(self.nodes.len());
+        self.nodes.push(Node {
+            label: item.label,
+            parent,
+            children: vec![],
+            expanded: false,
+            lazy: item.lazy,
+        });
+
+        match parent {
+            Some(parent) => self.nodes[parent.0].children.push(id),
+            None => self.roots.push(id),
+        }
+
+        return id;
+    }
+
+    fn load_children(&mut self, id: TreeNodeId) {
+        if !self.nodes[id.0].lazy {
+            return;
+        }
+        self.nodes[id.0].lazy = false;
+
+        let Some(loader) = &mut self.loader else {
+            return;
+        };
+        let items = loader(id, &self.nodes[id.0].label);
+
+        for item in items {
+            self.add_item(Some(id), item);
+        }
+    }
+
+    fn select_by_user(&mut self, id: TreeNodeId) {
+        if self.selected == Some(id) {
+            return;
+        }
+
+        self.selected = Some(id);
+        if let Some(on_select) = &mut self.on_select {
+            on_select(id);
+        }
+    }
+
+    fn on_key(&mut self, key: Key, rows: &[(TreeNodeId, usize)]) -> Response {
+        let current = self
+            .selected
+            .and_then(|selected| rows.iter().position(|&(id, _)| id == selected));
+
+        let Some(current) = current else {
+            if matches!(key, Key::Up | Key::Down | Key::Home | Key::End) && !rows.is_empty() {
+                self.select_by_user(rows[0].0);
+                return Response::Consumed;
+            }
+            return Response::Ignored;
+        };
+        let id = rows[current].0;
+
+        match key {
+            Key::Up => self.select_by_user(rows[current.saturating_sub(1)].0),
+            Key::Down => self.select_by_user(rows[(current + 1).min(rows.len() - 1)].0),
+            Key::Home => self.select_by_user(rows[0].0),
+            Key::End => self.select_by_user(rows[rows.len() - 1].0),
+            Key::Right if self.has_children(id) && !self.is_expanded(id) => self.expand(id),
+            // Move to the first child
+            Key::Right if self.is_expanded(id) => {
+                if let Some(&child) = self.children(id).first() {
+                    self.select_by_user(child);
+                }
+            }
+            Key::Left if self.is_expanded(id) => self.collapse(id),
+            Key::Left => {
+                if let Some(parent) = self.parent(id) {
+                    self.select_by_user(parent);
+                }
+            }
+            Key::Enter | Key::Space if self.has_children(id) => self.toggle(id),
+            _ => return Response::Ignored,
+        }
+
+        return Response::Consumed;
+    }
+}
+
+impl Widget for TreeView {
+    fn measure(&self, ctx: &MeasureCtx, _available: Vec2<f32>) -> Vec2<f32> {
+        let row_height = row_height(ctx.text, ctx.theme);
+        return Vec2::new(row_height * 12.0, row_height * 8.0);
+    }
+
+    fn draw(&self, ctx: &mut DrawCtx) {
+        let theme = ctx.theme;
+        let rect = ctx.rect;
+        let row_height = row_height(ctx.text, theme);
+        let line_height = ctx.text.line_height(theme.font_size);
+        let rows = self.rows();
+
+        ctx.list.push_clip(rect);
+
+        for i in self.scroll.visible(rows.len(), row_height, rect) {
+            let (id, depth) = rows[i];
+            let y = self.scroll.row_y(i, row_height, rect);
+            let row = Rect::new(
+                Vec2::new(rect.min.x, y),
+                Vec2::new(rect.max.x, y + row_height),
+            );
+
+            if self.selected == Some(id) {
+                let color = match ctx.focused {
+                    true => theme.selection,
+                    false => theme.surface,
+                };
+                ctx.list.rect(row, color);
+            }
+
+            /*
+             * Chevron pointing right ( collapsed ) or down ( expanded )
+             */
+            let x = rect.min.x + theme.padding + depth as f32 * INDENT;
+            if self.has_children(id) {
+                let center = Vec2::new(x + INDENT * 0.5, row.center().y);
+                let s = INDENT * 0.2;
+                let (a, b, c) = match self.is_expanded(id) {
+                    true => (
+                        Vec2::new(-s, -s * 0.5),
+                        Vec2::new(0.0, s * 0.5),
+                        Vec2::new(s, -s * 0.5),
+                    ),
+                    false => (
+                        Vec2::new(-s * 0.5, -s),
+                        Vec2::new(s * 0.5, 0.0),
+                        Vec2::new(-s * 0.5, s),
+                    ),
+                };
+                ctx.list.line(center + a, center + b, 1.5, theme.text_muted);
+                ctx.list.line(center + b, center + c, 1.5, theme.text_muted);
+            }
+
+            let pos = Vec2::new(x + INDENT, row.center().y - line_height * 0.5);
+            ctx.list
+                .text(pos, self.label(id), theme.font_size, theme.text);
+        }
+
+        self.scroll
+            .draw_scrollbar(ctx.list, rows.len(), row_height, rect, theme);
+        ctx.list.pop_clip();
+    }
+
+    fn on_event(&mut self, ctx: &mut EventCtx, event: &Event) -> Response {
+        let row_height = row_height(ctx.text, ctx.theme);
+        let rows = self.rows();
+
+        let response = match event {
+            Event::Scroll { delta, .. } => {
+                match self
+                    .scroll
+                    .scroll(delta.y, rows.len(), row_height, ctx.rect)
+                {
+                    true => Response::Consumed,
+                    // Let the outer scroll area scroll
+                    false => Response::Ignored,
+                }
+            }
+            Event::PointerDown {
+                pos,
+                button: PointerButton::Primary,
+            } => {
+                ctx.request_focus();
+
+                let Some(row) = self.scroll.row_at(pos.y, rows.len(), row_height, ctx.rect) else {
+                    return Response::Consumed;
+                };
+                let (id, depth) = rows[row];
+
+                let chevron = ctx.rect.min.x + ctx.theme.padding + depth as f32 * INDENT;
+                if self.has_children(id) && (chevron..chevron + INDENT).contains(&pos.x) {
+                    self.toggle(id);
+                } else {
+                    self.select_by_user(id);
+                }
+                return Response::Consumed;
+            }
+            Event::KeyDown { key, .. } if ctx.focused => self.on_key(*key, &rows),
+            _ => Response::Ignored,
+        };
+
+        /*
+         * Keep the node selected with the keyboard visible
+         */
+        if response.is_consumed() && matches!(event, Event::KeyDown { .. }) {
+            let rows = self.rows();
+            let selected = self
+                .selected
+                .and_then(|selected| rows.iter().position(|&(id, _)| id == selected));
+            if let Some(row) = selected {
+                self.scroll.scroll_to(row, rows.len(), row_height, ctx.rect);
+            }
+        }
+
+        return response;
+    }
+
+    fn is_focusable(&self) -> bool {
+        return true;
+    }
+}
+
+//-----------------------------------------------------------------------------
@@ -80,3 +80,176 @@ pub fn vertex_derive(item: TokenStream) -> TokenStream {
 }
 
 //-----------------------------------------------------------------------------
+
+/// Parsed `#[binding(0, uniform, stages = "fragment")]` of a field
+struct Binding {
+    binding_num: syn::LitInt,
+    descriptor_type: proc_macro2::TokenStream,
+    stages: proc_macro2::TokenStream,
+}
+
+fn parse_binding(attr: &syn::Attribute) -> syn::Result<Binding> {
+    return attr.parse_args_with(|input: syn::parse::ParseStream| {
+        let binding_num = input.parse::<syn::LitInt>()?;
+        input.parse::<syn::Token![,]>()?;
+
+        let kind = input.parse::<syn::Ident>()?;
+        let descriptor_type = match kind.to_string().as_str() {
+            "uniform" => quote! { UNIFORM_BUFFER },
+            "storage" => quote! { STORAGE_BUFFER },
+            "combined_image_sampler" => quote! { COMBINED_IMAGE_SAMPLER },
+            _ => {
+                return Err(syn::Error::new(
+                    kind.span(),
+                    "expected `uniform`, `storage` or `combined_image_sampler`",
+                ))
+            }
+        };
+
+        /*
+         * Optional stages ( all graphics stages by default )
+         */
+        let mut stages = quote! { soh::vk::ShaderStageFlags::ALL_GRAPHICS };
+        if input.parse::<Option<syn::Token![,]>>()?.is_some() {
+            let key = input.parse::<syn::Ident>()?;
+            if key != "stages" {
+                return Err(syn::Error::new(key.span(), "expected `stages = \"...\"`"));
+            }
+            input.parse::<syn::Token![=]>()?;
+            let value = input.parse::<syn::LitStr>()?;
+
+            let flags = value
+                .value()
+                .split('|')
+                .map(|stage| {
+                    let flag = match stage.trim() {
+                        "vertex" => quote! { VERTEX },
+                        "fragment" => quote! { FRAGMENT },
+                        "tessellation_control" => quote! { TESSELLATION_CONTROL },
+                        "tessellation_evaluation" => quote! { TESSELLATION_EVALUATION },
+                        "geometry" => quote! { GEOMETRY },
+                        "compute" => quote! { COMPUTE },
+                        "all_graphics" => quote! { ALL_GRAPHICS },
+                        "all" => quote! { ALL },
+                        other => {
+                            return Err(syn::Error::new(
+                                value.span(),
+                                format!("unknown shader stage `{other}`"),
+                            ))
+                        }
+                    };
+                    return Ok(quote! { soh::vk::ShaderStageFlags::#flag });
+                })
+                .collect::<syn::Result<Vec<_>>>()?;
+            stages = quote! { #(#flags)|* };
+        }
+
+        return Ok(Binding {
+            binding_num,
+            descriptor_type: quote! { soh::vk::DescriptorType::#descriptor_type },
+            stages,
+        });
+    });
+}
+
+/// Fields annotated with `#[binding(N, uniform | storage | combined_image_sampler, stages = "...")]`
+/// become the bindings of the set layout, see `soh::vk::descriptor::DescriptorSet`
+#[proc_macro_derive(DescriptorSet, attributes(binding))]
+pub fn descriptor_set_derive(item: TokenStream) -> TokenStream {
+    let input = syn::parse_macro_input!(item as syn::ItemStruct);
+
+    return match descriptor_set_impl(&input) {
+        Ok(tokens) => TokenStream::from(tokens),
+        Err(err) => TokenStream::from(err.to_compile_error()),
+    };
+}
+
+fn descriptor_set_impl(input: &syn::ItemStruct) -> syn::Result<proc_macro2::TokenStream> {
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let syn::Fields::Named(fields) = &input.fields else {
+        return Err(syn::Error::new_spanned(input, "fields must be named"));
+    };
+
+    /*
+     * Bindings of the annotated fields
+     */
+    let mut field_names = vec![];
+    let mut field_types = vec![];
+    let mut bindings = vec![];
+    let mut binding_nums = std::collections::HashSet::new();
+
+    for field in fields.named.iter() {
+        let Some(attr) = field
+            .attrs
+            .iter()
+            .find(|attr| attr.path().is_ident("binding"))
+        else {
+            continue;
+        };
+        let binding = parse_binding(attr)?;
+
+        if !binding_nums.insert(binding.binding_num.base10_parse::<u32>()?) {
+            return Err(syn::Error::new(
+                binding.binding_num.span(),
+                "binding number is used twice",
+            ));
+        }
+
+        field_names.push(field.ident.clone());
+        field_types.push(&field.ty);
+        bindings.push(binding);
+    }
+
+    let binding_nums = bindings.iter().map(|b| &b.binding_num).collect::<Vec<_>>();
+    let descriptor_types = bindings
+        .iter()
+        .map(|b| &b.descriptor_type)
+        .collect::<Vec<_>>();
+    let stages = bindings.iter().map(|b| &b.stages).collect::<Vec<_>>();
+    let type_errors = field_names.iter().map(|field| {
+        format!(
+            "the type of the field `{}` doesn't match its descriptor type",
+            field.as_ref().unwrap()
+        )
+    });
+
+    /*
+     * Build the implementation
+     */
+    let a = quote! {
+        impl #impl_generics soh::vk::descriptor::DescriptorSet for #name #ty_generics #where_clause {
+            fn bindings() -> Vec<soh::vk::descriptor::SetLayoutBinding> {
+                #(
+                    const {
+                        assert!(
+                            <#field_types as soh::vk::descriptor::DescriptorResource>::DESCRIPTOR_TYPE.as_raw()
+                                == #descriptor_types.as_raw(),
+                            #type_errors
+                        );
+                    }
+                )*
+
+                return vec![
+                    #(
+                        soh::vk::descriptor::SetLayoutBinding {
+                            binding_num: #binding_nums,
+                            descriptor_type: #descriptor_types,
+                            count: <#field_types as soh::vk::descriptor::DescriptorResource>::COUNT,
+                            state_flags: #stages,
+                        },
+                    )*
+                ];
+            }
+
+            fn write(&self, set: &mut soh::vk::descriptor::Set) {
+                #( set.write_resource(#binding_nums, &self.#field_names); )*
+            }
+        }
+    };
+
+    return Ok(a);
+}
+
+//-----------------------------------------------------------------------------
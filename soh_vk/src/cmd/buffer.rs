@@ -1,6 +1,9 @@
 //-----------------------------------------------------------------------------
-use anyhow::Result;
+use super::RecordingState;
+use crate::error::{bail, ensure};
+use crate::{Result, ToVk};
 use ash::vk::{self, Handle};
+use std::sync::atomic::{AtomicU8, Ordering};
 //-----------------------------------------------------------------------------
 
 pub struct Buffer {
@@ -9,6 +12,20 @@ pub struct Buffer {
     cmd_buffer: vk::CommandBuffer,
     level: super::BufferLevel,
     queue_family_index: u32,
+
+    // [RecordingState] tracked on the CPU to catch misuse early
+    state: AtomicU8,
+}
+
+//-----------------------------------------------------------------------------
+// Getters
+impl Buffer {
+    pub fn level(&self) -> super::BufferLevel {
+        return self.level;
+    }
+    pub fn state(&self) -> RecordingState {
+        return RecordingState::from_u8(self.state.load(Ordering::Relaxed));
+    }
 }
 
 //-----------------------------------------------------------------------------
@@ -19,6 +36,8 @@ impl Buffer {
             self.device
                 .reset_command_buffer(**self, vk::CommandBufferResetFlags::default())?;
         }
+        self.set_state(RecordingState::Initial);
+
         return Ok(());
     }
 
@@ -26,18 +45,42 @@ impl Buffer {
     // Recording functions
     // ========================================================================
 
+    /// Begins recording, an executable buffer is reset implicitly ( the pool
+    /// has to allow resetting the buffers )
     pub fn begin(&self, flags: vk::CommandBufferUsageFlags) -> Result<()> {
+        let state = self.state();
+        ensure!(
+            matches!(state, RecordingState::Initial | RecordingState::Executable),
+            InvalidUsage,
+            "Can't begin a command buffer in the {state:?} state"
+        );
+
         let begin_info = vk::CommandBufferBeginInfo::default().flags(flags);
 
         unsafe { self.device.begin_command_buffer(**self, &begin_info)? };
+        self.set_state(RecordingState::Recording);
 
         return Ok(());
     }
 
     pub fn end(&self) -> Result<()> {
+        let state = self.state();
+        ensure!(
+            state == RecordingState::Recording,
+            InvalidUsage,
+            "Can't end a command buffer in the {state:?} state{}",
+            if state == RecordingState::RenderPassActive {
+                " ( the render pass wasn't ended )"
+            } else {
+                ""
+            }
+        );
+
         unsafe {
             self.device.end_command_buffer(**self)?;
         }
+        self.set_state(RecordingState::Executable);
+
         return Ok(());
     }
 
@@ -54,6 +97,22 @@ impl Buffer {
             },
         };
 
+        self.begin_render_pass_with_clear(
+            framebuffer,
+            render_pass,
+            std::slice::from_ref(&CLEAR_VALUE),
+        );
+    }
+
+    /// Begin the render pass with a clear value for each of the attachments
+    pub fn begin_render_pass_with_clear(
+        &self,
+        framebuffer: &crate::Framebuffer,
+        render_pass: &crate::RenderPass,
+        clear_values: &[vk::ClearValue],
+    ) {
+        self.debug_check_outside_render_pass("begin a render pass");
+
         let render_pass_info = vk::RenderPassBeginInfo::default()
             .render_pass(**render_pass)
             .framebuffer(**framebuffer)
@@ -61,7 +120,7 @@ impl Buffer {
                 offset: vk::Offset2D { x: 0, y: 0 },
                 extent: framebuffer.extent(),
             })
-            .clear_values(std::slice::from_ref(&CLEAR_VALUE));
+            .clear_values(clear_values);
 
         unsafe {
             self.device.cmd_begin_render_pass(
@@ -70,17 +129,103 @@ impl Buffer {
                 vk::SubpassContents::INLINE,
             );
         }
+        self.set_state(RecordingState::RenderPassActive);
+    }
+
+    /// Begin the render pass with an image-less framebuffer, the `image_views`
+    /// are used as the attachments
+    #[cfg(feature = "imageless_framebuffer")]
+    pub fn begin_render_pass_imageless(
+        &self,
+        framebuffer: &crate::Framebuffer,
+        render_pass: &crate::RenderPass,
+        image_views: &[vk::ImageView],
+        clear_values: &[vk::ClearValue],
+    ) {
+        self.debug_check_outside_render_pass("begin a render pass");
+        debug_assert!(
+            framebuffer.is_imageless(),
+            "Framebuffer has its own image views"
+        );
+
+        let mut attachment_info =
+            vk::RenderPassAttachmentBeginInfo::default().attachments(image_views);
+
+        let render_pass_info = vk::RenderPassBeginInfo::default()
+            .render_pass(**render_pass)
+            .framebuffer(**framebuffer)
+            .render_area(vk::Rect2D {
+                offset: vk::Offset2D { x: 0, y: 0 },
+                extent: framebuffer.extent(),
+            })
+            .clear_values(clear_values)
+            .push_next(&mut attachment_info);
+
+        unsafe {
+            self.device.cmd_begin_render_pass(
+                **self,
+                &render_pass_info,
+                vk::SubpassContents::INLINE,
+            );
+        }
+        self.set_state(RecordingState::RenderPassActive);
     }
 
     pub fn end_render_pass(&self) {
+        debug_assert!(
+            self.state() == RecordingState::RenderPassActive,
+            "Can't end a render pass, command buffer is in the {:?} state",
+            self.state()
+        );
+
         unsafe {
             self.device.cmd_end_render_pass(**self);
         }
+        self.set_state(RecordingState::Recording);
     }
 
     //-------------------------------------------------------------------------
 
+    /// Sets the viewport to the `rect` with the full depth range
+    pub fn set_viewport_rect(&self, rect: soh_math::Rect<f32>) {
+        self.set_viewport(soh_math::Viewport::new(rect));
+    }
+
+    pub fn set_viewport(&self, viewport: soh_math::Viewport) {
+        self.debug_check_recording("set the viewport");
+
+        let viewport = viewport.to_vk();
+
+        unsafe {
+            self.device
+                .cmd_set_viewport(**self, 0, std::slice::from_ref(&viewport));
+        }
+    }
+
+    pub fn set_scissor_rect(&self, rect: soh_math::Rect<u32>) {
+        self.debug_check_recording("set the scissor");
+
+        let scissor = rect.to_vk();
+
+        unsafe {
+            self.device
+                .cmd_set_scissor(**self, 0, std::slice::from_ref(&scissor));
+        }
+    }
+
+    /// Sets both the viewport and the scissor to the `rect`. Useful together
+    /// with [crate::split_screen]
+    pub fn set_viewport_scissor_rect(&self, rect: soh_math::Rect<u32>) {
+        self.set_viewport_rect(soh_math::Rect::new(
+            soh_math::Vec2::new(rect.min.x as f32, rect.min.y as f32),
+            soh_math::Vec2::new(rect.max.x as f32, rect.max.y as f32),
+        ));
+        self.set_scissor_rect(rect);
+    }
+
     pub fn set_fb_viewport_scissor(&self, framebuffer: &crate::Framebuffer) {
+        self.debug_check_recording("set the viewport");
+
         let (viewport, scissor) = framebuffer.get_viewport_scissor();
 
         unsafe {
@@ -91,9 +236,34 @@ impl Buffer {
         }
     }
 
+    /// Reference value of the stencil test, see [crate::StencilState]
+    pub fn set_stencil_reference(&self, reference: u32) {
+        self.debug_check_recording("set the stencil reference");
+
+        unsafe {
+            self.device.cmd_set_stencil_reference(
+                **self,
+                vk::StencilFaceFlags::FRONT_AND_BACK,
+                reference,
+            );
+        }
+    }
+
+    /// Sets the width of the lines. The bound pipeline has to be created with
+    /// a dynamic line width ( see [crate::Pipeline::has_dynamic_line_width] )
+    pub fn set_line_width(&self, width: f32) {
+        self.debug_check_recording("set the line width");
+
+        unsafe {
+            self.device.cmd_set_line_width(**self, width);
+        }
+    }
+
     //-------------------------------------------------------------------------
 
     pub fn bind_pipeline(&self, graphics_pipeline: &crate::Pipeline) {
+        self.debug_check_recording("bind a pipeline");
+
         unsafe {
             self.device.cmd_bind_pipeline(
                 **self,
@@ -104,6 +274,8 @@ impl Buffer {
     }
 
     pub fn bind_vertex_buffer(&self, vertex_buffer: &crate::vertex::Buffer) {
+        self.debug_check_recording("bind a vertex buffer");
+
         unsafe {
             self.device.cmd_bind_vertex_buffers(
                 **self,
@@ -114,7 +286,20 @@ impl Buffer {
         }
     }
 
+    /// Bind a raw `buffer` to the vertex input `binding` ( for example per
+    /// instance data written by a compute shader )
+    pub fn bind_vertex_buffer_at(&self, binding: u32, buffer: &crate::Buffer, offset: u64) {
+        self.debug_check_recording("bind a vertex buffer");
+
+        unsafe {
+            self.device
+                .cmd_bind_vertex_buffers(**self, binding, &[**buffer], &[offset]);
+        }
+    }
+
     pub fn bind_index_buffer(&self, index_buffer: &crate::index::Buffer) {
+        self.debug_check_recording("bind an index buffer");
+
         unsafe {
             self.device.cmd_bind_index_buffer(
                 **self,
@@ -125,11 +310,39 @@ impl Buffer {
         }
     }
 
+    pub fn bind_transient_vertices(&self, vertices: &crate::transient::TransientVertices) {
+        self.debug_check_recording("bind a vertex buffer");
+
+        unsafe {
+            self.device.cmd_bind_vertex_buffers(
+                **self,
+                0,
+                &[vertices.buffer()],
+                &[vertices.offset()],
+            );
+        }
+    }
+
+    pub fn bind_transient_indices(&self, indexes: &crate::transient::TransientIndices) {
+        self.debug_check_recording("bind an index buffer");
+
+        unsafe {
+            self.device.cmd_bind_index_buffer(
+                **self,
+                indexes.buffer(),
+                indexes.offset(),
+                indexes.index_type(),
+            );
+        }
+    }
+
     pub fn bind_descriptor_sets(
         &self,
         pipeline: &crate::Pipeline,
         descriptor_sets: &[&crate::descriptor::Set],
     ) {
+        self.debug_check_recording("bind descriptor sets");
+
         let descriptor_sets = crate::get_handles_vec(descriptor_sets);
 
         unsafe {
@@ -151,6 +364,8 @@ impl Buffer {
         first_vertex: u32,
         first_instance: u32,
     ) {
+        self.debug_check_inside_render_pass("draw");
+
         unsafe {
             self.device.cmd_draw(
                 **self,
@@ -170,6 +385,8 @@ impl Buffer {
         vertex_offset: i32,
         first_instance: u32,
     ) {
+        self.debug_check_inside_render_pass("draw");
+
         unsafe {
             self.device.cmd_draw_indexed(
                 **self,
@@ -182,6 +399,510 @@ impl Buffer {
         }
     }
 
+    /// Binds the buffers of the mesh and draws all of its submeshes
+    pub fn draw_mesh(&self, mesh: &crate::Mesh) {
+        self.bind_mesh(mesh);
+
+        for submesh in mesh.submeshes() {
+            self.draw_submesh(submesh);
+        }
+    }
+
+    /// Binds the buffers of the mesh, the submeshes then can be drawn one by
+    /// one with [Buffer::draw_submesh] ( binding the material in between )
+    pub fn bind_mesh(&self, mesh: &crate::Mesh) {
+        self.bind_vertex_buffer(mesh.vertex_buffer());
+        self.bind_index_buffer(mesh.index_buffer());
+    }
+
+    pub fn draw_submesh(&self, submesh: &crate::Submesh) {
+        self.draw_indexed(
+            submesh.num_of_indexes,
+            1,
+            submesh.first_index,
+            submesh.vertex_offset,
+            0,
+        );
+    }
+
+    pub fn draw_indirect(&self, buffer: &crate::Buffer, offset: u64, draw_count: u32) {
+        self.debug_check_inside_render_pass("draw");
+
+        unsafe {
+            self.device.cmd_draw_indirect(
+                **self,
+                **buffer,
+                offset,
+                draw_count,
+                size_of::<vk::DrawIndirectCommand>() as u32,
+            );
+        }
+    }
+
+    //-------------------------------------------------------------------------
+
+    pub fn bind_compute_pipeline(&self, pipeline: &crate::ComputePipeline) {
+        self.debug_check_recording("bind a pipeline");
+
+        unsafe {
+            self.device
+                .cmd_bind_pipeline(**self, vk::PipelineBindPoint::COMPUTE, **pipeline);
+        }
+    }
+
+    pub fn bind_compute_descriptor_sets(
+        &self,
+        pipeline: &crate::ComputePipeline,
+        descriptor_sets: &[&crate::descriptor::Set],
+    ) {
+        self.debug_check_recording("bind descriptor sets");
+
+        let descriptor_sets = crate::get_handles_vec(descriptor_sets);
+
+        unsafe {
+            self.device.cmd_bind_descriptor_sets(
+                **self,
+                vk::PipelineBindPoint::COMPUTE,
+                pipeline.layout(),
+                0,
+                &descriptor_sets,
+                &[],
+            );
+        }
+    }
+
+    /// Sets the push constants of the compute pipeline to the `data`
+    pub fn push_compute_constants<T: Copy>(&self, pipeline: &crate::ComputePipeline, data: &T) {
+        self.debug_check_recording("push constants");
+
+        assert!(size_of::<T>() as u32 <= pipeline.push_constants_size());
+
+        let bytes =
+            unsafe { std::slice::from_raw_parts((data as *const T).cast::<u8>(), size_of::<T>()) };
+
+        unsafe {
+            self.device.cmd_push_constants(
+                **self,
+                pipeline.layout(),
+                vk::ShaderStageFlags::COMPUTE,
+                0,
+                bytes,
+            );
+        }
+    }
+
+    pub fn dispatch(&self, group_count_x: u32, group_count_y: u32, group_count_z: u32) {
+        self.debug_check_outside_render_pass("dispatch");
+
+        unsafe {
+            self.device
+                .cmd_dispatch(**self, group_count_x, group_count_y, group_count_z);
+        }
+    }
+
+    //-------------------------------------------------------------------------
+
+    /// Write small `data` ( up to 64 KiB ) to the buffer from the command
+    /// buffer, outside of render passes
+    pub fn update_buffer<T: Copy>(&self, buffer: &crate::Buffer, offset: u64, data: &[T]) {
+        self.debug_check_outside_render_pass("update a buffer");
+
+        let bytes =
+            unsafe { std::slice::from_raw_parts(data.as_ptr().cast::<u8>(), size_of_val(data)) };
+
+        unsafe {
+            self.device
+                .cmd_update_buffer(**self, **buffer, offset, bytes);
+        }
+    }
+
+    /// Record a global memory barrier: the `dst_access` in the `dst_stage` of
+    /// the following commands waits for the `src_access` in the `src_stage` of
+    /// the previous commands
+    pub fn memory_barrier(
+        &self,
+        src_stage: vk::PipelineStageFlags,
+        src_access: vk::AccessFlags,
+        dst_stage: vk::PipelineStageFlags,
+        dst_access: vk::AccessFlags,
+    ) {
+        self.debug_check_outside_render_pass("record a barrier");
+
+        let barrier = vk::MemoryBarrier::default()
+            .src_access_mask(src_access)
+            .dst_access_mask(dst_access);
+
+        unsafe {
+            self.device.cmd_pipeline_barrier(
+                **self,
+                src_stage,
+                dst_stage,
+                vk::DependencyFlags::empty(),
+                std::slice::from_ref(&barrier),
+                &[],
+                &[],
+            );
+        }
+    }
+
+    /// Release the ownership of the buffer to the `dst_family` queue family,
+    /// the first half of a queue family ownership transfer ( recorded after
+    /// the `src_access` in the `src_stage` which wrote the buffer ). Does
+    /// nothing if the command buffer is of the same family
+    pub fn release_buffer_ownership(
+        &self,
+        buffer: &crate::Buffer,
+        dst_family: u32,
+        src_stage: vk::PipelineStageFlags,
+        src_access: vk::AccessFlags,
+    ) {
+        self.debug_check_outside_render_pass("release a buffer");
+
+        if dst_family == self.queue_family_index {
+            return;
+        }
+
+        let barrier = vk::BufferMemoryBarrier::default()
+            .src_access_mask(src_access)
+            .dst_access_mask(vk::AccessFlags::empty())
+            .src_queue_family_index(self.queue_family_index)
+            .dst_queue_family_index(dst_family)
+            .buffer(**buffer)
+            .offset(0)
+            .size(vk::WHOLE_SIZE);
+
+        unsafe {
+            self.device.cmd_pipeline_barrier(
+                **self,
+                src_stage,
+                vk::PipelineStageFlags::BOTTOM_OF_PIPE,
+                vk::DependencyFlags::empty(),
+                &[],
+                std::slice::from_ref(&barrier),
+                &[],
+            );
+        }
+    }
+
+    /// Acquire the ownership of the buffer released by the `src_family` queue
+    /// family ( see [Buffer::release_buffer_ownership] ) before the
+    /// `dst_access` in the `dst_stage`. If the command buffer is of the same
+    /// family it's a barrier after the transfer writes instead
+    pub fn acquire_buffer_ownership(
+        &self,
+        buffer: &crate::Buffer,
+        src_family: u32,
+        dst_stage: vk::PipelineStageFlags,
+        dst_access: vk::AccessFlags,
+    ) {
+        self.debug_check_outside_render_pass("acquire a buffer");
+
+        let (src_stage, src_access, src_family, dst_family) =
+            match src_family == self.queue_family_index {
+                true => (
+                    vk::PipelineStageFlags::TRANSFER,
+                    vk::AccessFlags::TRANSFER_WRITE,
+                    vk::QUEUE_FAMILY_IGNORED,
+                    vk::QUEUE_FAMILY_IGNORED,
+                ),
+                false => (
+                    vk::PipelineStageFlags::TOP_OF_PIPE,
+                    vk::AccessFlags::empty(),
+                    src_family,
+                    self.queue_family_index,
+                ),
+            };
+
+        let barrier = vk::BufferMemoryBarrier::default()
+            .src_access_mask(src_access)
+            .dst_access_mask(dst_access)
+            .src_queue_family_index(src_family)
+            .dst_queue_family_index(dst_family)
+            .buffer(**buffer)
+            .offset(0)
+            .size(vk::WHOLE_SIZE);
+
+        unsafe {
+            self.device.cmd_pipeline_barrier(
+                **self,
+                src_stage,
+                dst_stage,
+                vk::DependencyFlags::empty(),
+                &[],
+                std::slice::from_ref(&barrier),
+                &[],
+            );
+        }
+    }
+
+    //-------------------------------------------------------------------------
+
+    /// Record an image layout transition using a pipeline barrier
+    ///
+    /// * `image`: the image to transition
+    /// * `old_layout`: the current layout of the subresource range
+    /// * `new_layout`: the layout to transition to
+    /// * `range`: the subresource range affected by the transition
+    pub fn transition_image_layout(
+        &self,
+        image: &crate::Image,
+        old_layout: vk::ImageLayout,
+        new_layout: vk::ImageLayout,
+        range: vk::ImageSubresourceRange,
+    ) {
+        self.debug_check_outside_render_pass("transition an image");
+
+        let (src_access_mask, src_stage) = layout_access_and_stage(old_layout);
+        let (dst_access_mask, dst_stage) = layout_access_and_stage(new_layout);
+
+        let barrier = vk::ImageMemoryBarrier::default()
+            .image(**image)
+            .old_layout(old_layout)
+            .new_layout(new_layout)
+            .src_access_mask(src_access_mask)
+            .dst_access_mask(dst_access_mask)
+            .src_queue_family_index(vk::QUEUE_FAMILY_IGNORED)
+            .dst_queue_family_index(vk::QUEUE_FAMILY_IGNORED)
+            .subresource_range(range);
+
+        unsafe {
+            self.device.cmd_pipeline_barrier(
+                **self,
+                src_stage,
+                dst_stage,
+                vk::DependencyFlags::empty(),
+                &[],
+                &[],
+                std::slice::from_ref(&barrier),
+            );
+        }
+    }
+
+    /// Blit regions of one image into another (with scaling and format conversion)
+    ///
+    /// `src` should be in the `TRANSFER_SRC_OPTIMAL` layout and `dst` should be in the
+    /// `TRANSFER_DST_OPTIMAL` layout. Only graphics queues support blitting.
+    pub fn blit_image(
+        &self,
+        src: &crate::Image,
+        dst: &crate::Image,
+        regions: &[vk::ImageBlit],
+        filter: vk::Filter,
+    ) {
+        self.debug_check_outside_render_pass("blit an image");
+
+        unsafe {
+            self.device.cmd_blit_image(
+                **self,
+                **src,
+                vk::ImageLayout::TRANSFER_SRC_OPTIMAL,
+                **dst,
+                vk::ImageLayout::TRANSFER_DST_OPTIMAL,
+                regions,
+                filter,
+            );
+        }
+    }
+
+    /// Copy regions of one image into another
+    ///
+    /// `src` should be in the `TRANSFER_SRC_OPTIMAL` layout and `dst` should be in the
+    /// `TRANSFER_DST_OPTIMAL` layout.
+    pub fn copy_image(&self, src: &crate::Image, dst: &crate::Image, regions: &[vk::ImageCopy]) {
+        self.debug_check_outside_render_pass("copy an image");
+
+        unsafe {
+            self.device.cmd_copy_image(
+                **self,
+                **src,
+                vk::ImageLayout::TRANSFER_SRC_OPTIMAL,
+                **dst,
+                vk::ImageLayout::TRANSFER_DST_OPTIMAL,
+                regions,
+            );
+        }
+    }
+
+    /// Copy regions of a buffer into an image
+    ///
+    /// `dst` should be in the `TRANSFER_DST_OPTIMAL` layout.
+    pub fn copy_buffer_to_image(
+        &self,
+        src: &crate::Buffer,
+        dst: &crate::Image,
+        regions: &[vk::BufferImageCopy],
+    ) {
+        self.debug_check_outside_render_pass("copy a buffer to an image");
+
+        unsafe {
+            self.device.cmd_copy_buffer_to_image(
+                **self,
+                **src,
+                **dst,
+                vk::ImageLayout::TRANSFER_DST_OPTIMAL,
+                regions,
+            );
+        }
+    }
+
+    /// Copy regions of an image into a buffer
+    ///
+    /// `src` should be in the `TRANSFER_SRC_OPTIMAL` layout.
+    pub fn copy_image_to_buffer(
+        &self,
+        src: &crate::Image,
+        dst: &crate::Buffer,
+        regions: &[vk::BufferImageCopy],
+    ) {
+        self.debug_check_outside_render_pass("copy an image to a buffer");
+
+        unsafe {
+            self.device.cmd_copy_image_to_buffer(
+                **self,
+                **src,
+                vk::ImageLayout::TRANSFER_SRC_OPTIMAL,
+                **dst,
+                regions,
+            );
+        }
+    }
+
+    // ========================================================================
+    // Query functions
+    // ========================================================================
+
+    /// Reset the queries of the frame, outside of render passes before they
+    /// are used again
+    pub fn reset_queries(&self, pool: &crate::OcclusionQueryPool, frame_idx: usize) {
+        self.debug_check_outside_render_pass("reset queries");
+
+        unsafe {
+            self.device.cmd_reset_query_pool(
+                **self,
+                **pool,
+                pool.query_index(frame_idx, 0),
+                pool.queries_per_frame(),
+            );
+        }
+    }
+
+    /// Start counting the samples of the following draws which pass the depth
+    /// test into the query of the frame
+    pub fn begin_query(&self, pool: &crate::OcclusionQueryPool, frame_idx: usize, query: u32) {
+        self.debug_check_inside_render_pass("begin a query");
+
+        let flags = match pool.is_precise() {
+            true => vk::QueryControlFlags::PRECISE,
+            false => vk::QueryControlFlags::empty(),
+        };
+
+        unsafe {
+            self.device
+                .cmd_begin_query(**self, **pool, pool.query_index(frame_idx, query), flags);
+        }
+    }
+
+    pub fn end_query(&self, pool: &crate::OcclusionQueryPool, frame_idx: usize, query: u32) {
+        self.debug_check_inside_render_pass("end a query");
+
+        unsafe {
+            self.device
+                .cmd_end_query(**self, **pool, pool.query_index(frame_idx, query));
+        }
+    }
+
+    /// Copy the results of the first `query_count` queries of the frame to the
+    /// predicates of the conditional rendering, outside of render passes. Waits
+    /// for the queries on the GPU, so all of them should have been ended
+    pub fn copy_query_predicates(
+        &self,
+        pool: &crate::OcclusionQueryPool,
+        frame_idx: usize,
+        query_count: u32,
+    ) -> Result<()> {
+        self.debug_check_outside_render_pass("copy query results");
+
+        let Some(predicates) = pool.predicates() else {
+            bail!(
+                Unsupported,
+                "Conditional rendering isn't supported by the device"
+            );
+        };
+
+        ensure!(
+            query_count <= pool.queries_per_frame(),
+            InvalidUsage,
+            "Copying {query_count} queries from a pool with {} queries per frame",
+            pool.queries_per_frame()
+        );
+
+        let first = pool.query_index(frame_idx, 0);
+
+        unsafe {
+            self.device.cmd_copy_query_pool_results(
+                **self,
+                **pool,
+                first,
+                query_count,
+                **predicates,
+                (first as usize * size_of::<u32>()) as u64,
+                size_of::<u32>() as u64,
+                vk::QueryResultFlags::WAIT,
+            );
+        }
+
+        self.memory_barrier(
+            vk::PipelineStageFlags::TRANSFER,
+            vk::AccessFlags::TRANSFER_WRITE,
+            vk::PipelineStageFlags::CONDITIONAL_RENDERING_EXT,
+            vk::AccessFlags::CONDITIONAL_RENDERING_READ_EXT,
+        );
+
+        return Ok(());
+    }
+
+    /// Skip the following draws if no samples passed for the query of the
+    /// frame, see [Buffer::copy_query_predicates]
+    pub fn begin_conditional_rendering(
+        &self,
+        pool: &crate::OcclusionQueryPool,
+        frame_idx: usize,
+        query: u32,
+    ) -> Result<()> {
+        self.debug_check_recording("begin conditional rendering");
+
+        let (Some(ext), Some(predicates)) = (
+            self.device.device_conditional_rendering(),
+            pool.predicates(),
+        ) else {
+            bail!(
+                Unsupported,
+                "Conditional rendering isn't supported by the device"
+            );
+        };
+
+        let begin_info = vk::ConditionalRenderingBeginInfoEXT::default()
+            .buffer(**predicates)
+            .offset((pool.query_index(frame_idx, query) as usize * size_of::<u32>()) as u64);
+
+        unsafe {
+            // ash has no wrappers for the extension commands
+            (ext.fp().cmd_begin_conditional_rendering_ext)(**self, &begin_info);
+        }
+
+        return Ok(());
+    }
+
+    pub fn end_conditional_rendering(&self) {
+        self.debug_check_recording("end conditional rendering");
+
+        if let Some(ext) = self.device.device_conditional_rendering() {
+            unsafe {
+                (ext.fp().cmd_end_conditional_rendering_ext)(**self);
+            }
+        }
+    }
+
     // ========================================================================
     // Submit functions
     // ========================================================================
@@ -197,6 +918,8 @@ impl Buffer {
         signal_semaphore: &crate::sync::Semaphore,
         fence: Option<&crate::sync::Fence>,
     ) -> Result<()> {
+        self.check_executable()?;
+
         let queue = self.get_queue_handle();
 
         // This means that the pipeline is going to wait for the color attachment to be available
@@ -220,6 +943,8 @@ impl Buffer {
     }
 
     pub fn submit_and_wait(&self) -> Result<()> {
+        self.check_executable()?;
+
         let queue = self.get_queue_handle();
 
         let submit_info = vk::SubmitInfo::default().command_buffers(std::slice::from_ref(self));
@@ -236,6 +961,36 @@ impl Buffer {
         return Ok(());
     }
 
+    /// Submit the command buffer to its queue, signaling the timeline
+    /// semaphore to the `value` once the execution completes
+    #[cfg(feature = "timeline_semaphore")]
+    pub fn submit_signal_timeline(
+        &self,
+        timeline: &crate::sync::TimelineSemaphore,
+        value: u64,
+    ) -> Result<()> {
+        self.check_executable()?;
+
+        let queue = self.get_queue_handle();
+
+        let mut timeline_info = vk::TimelineSemaphoreSubmitInfo::default()
+            .signal_semaphore_values(std::slice::from_ref(&value));
+        let submit_info = vk::SubmitInfo::default()
+            .signal_semaphores(std::slice::from_ref(timeline))
+            .command_buffers(std::slice::from_ref(self))
+            .push_next(&mut timeline_info);
+
+        unsafe {
+            self.device.queue_submit(
+                queue,
+                std::slice::from_ref(&submit_info),
+                vk::Fence::null(),
+            )?;
+        }
+
+        return Ok(());
+    }
+
     #[inline(always)]
     pub(super) fn from_handle(
         device: crate::DeviceRef,
@@ -250,9 +1005,62 @@ impl Buffer {
             cmd_buffer: buffer,
             level,
             queue_family_index,
+            state: AtomicU8::new(RecordingState::Initial as u8),
         };
     }
 
+    fn set_state(&self, state: RecordingState) {
+        self.state.store(state as u8, Ordering::Relaxed);
+    }
+
+    fn check_executable(&self) -> Result<()> {
+        let state = self.state();
+        ensure!(
+            state == RecordingState::Executable,
+            InvalidUsage,
+            "Can't submit a command buffer in the {state:?} state ( it has to be ended first )"
+        );
+
+        return Ok(());
+    }
+
+    /// State commands ( binds, dynamic state ) may be recorded both inside and
+    /// outside of a render pass
+    fn debug_check_recording(&self, action: &str) {
+        debug_assert!(
+            matches!(
+                self.state(),
+                RecordingState::Recording | RecordingState::RenderPassActive
+            ),
+            "Can't {action}, command buffer is in the {:?} state",
+            self.state()
+        );
+    }
+
+    /// Secondary buffers are recorded for the render pass of the primary one
+    fn debug_check_inside_render_pass(&self, action: &str) {
+        debug_assert!(
+            self.state() == RecordingState::RenderPassActive
+                || (self.level == super::BufferLevel::Secondary
+                    && self.state() == RecordingState::Recording),
+            "Can't {action} outside of a render pass, command buffer is in the {:?} state",
+            self.state()
+        );
+    }
+
+    fn debug_check_outside_render_pass(&self, action: &str) {
+        debug_assert!(
+            self.state() == RecordingState::Recording,
+            "Can't {action}, command buffer is in the {:?} state{}",
+            self.state(),
+            if self.state() == RecordingState::RenderPassActive {
+                " ( only allowed outside of a render pass )"
+            } else {
+                ""
+            }
+        );
+    }
+
     fn get_queue_handle(&self) -> vk::Queue {
         let queue = self.device.get_queue(self.queue_family_index);
 
@@ -265,6 +1073,51 @@ impl Buffer {
     }
 }
 
+//-----------------------------------------------------------------------------
+// Get the access mask and the pipeline stage which use an image in a given layout
+fn layout_access_and_stage(layout: vk::ImageLayout) -> (vk::AccessFlags, vk::PipelineStageFlags) {
+    return match layout {
+        vk::ImageLayout::UNDEFINED => (
+            vk::AccessFlags::empty(),
+            vk::PipelineStageFlags::TOP_OF_PIPE,
+        ),
+        vk::ImageLayout::PREINITIALIZED => {
+            (vk::AccessFlags::HOST_WRITE, vk::PipelineStageFlags::HOST)
+        }
+        vk::ImageLayout::TRANSFER_SRC_OPTIMAL => (
+            vk::AccessFlags::TRANSFER_READ,
+            vk::PipelineStageFlags::TRANSFER,
+        ),
+        vk::ImageLayout::TRANSFER_DST_OPTIMAL => (
+            vk::AccessFlags::TRANSFER_WRITE,
+            vk::PipelineStageFlags::TRANSFER,
+        ),
+        vk::ImageLayout::SHADER_READ_ONLY_OPTIMAL => (
+            vk::AccessFlags::SHADER_READ,
+            vk::PipelineStageFlags::FRAGMENT_SHADER,
+        ),
+        vk::ImageLayout::COLOR_ATTACHMENT_OPTIMAL => (
+            vk::AccessFlags::COLOR_ATTACHMENT_READ | vk::AccessFlags::COLOR_ATTACHMENT_WRITE,
+            vk::PipelineStageFlags::COLOR_ATTACHMENT_OUTPUT,
+        ),
+        vk::ImageLayout::DEPTH_STENCIL_ATTACHMENT_OPTIMAL => (
+            vk::AccessFlags::DEPTH_STENCIL_ATTACHMENT_READ
+                | vk::AccessFlags::DEPTH_STENCIL_ATTACHMENT_WRITE,
+            vk::PipelineStageFlags::EARLY_FRAGMENT_TESTS
+                | vk::PipelineStageFlags::LATE_FRAGMENT_TESTS,
+        ),
+        vk::ImageLayout::PRESENT_SRC_KHR => (
+            vk::AccessFlags::empty(),
+            vk::PipelineStageFlags::BOTTOM_OF_PIPE,
+        ),
+        // Be conservative for everything else
+        _ => (
+            vk::AccessFlags::MEMORY_READ | vk::AccessFlags::MEMORY_WRITE,
+            vk::PipelineStageFlags::ALL_COMMANDS,
+        ),
+    };
+}
+
 //-----------------------------------------------------------------------------
 // Deref
 impl std::ops::Deref for Buffer {
@@ -23,4 +23,32 @@ impl From<BufferLevel> for ash::vk::CommandBufferLevel {
     }
 }
 
+/// Lifecycle of a command buffer as tracked by [Buffer].
+///
+/// Recording functions check the state with debug assertions, `begin`, `end`
+/// and the submit functions return an error in the wrong state.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum RecordingState {
+    /// Allocated or reset
+    Initial,
+    /// Between `begin` and `end`, outside of a render pass
+    Recording,
+    /// Between `begin_render_pass` and `end_render_pass`
+    RenderPassActive,
+    /// Ended, can be submitted ( or begun again, which resets it )
+    Executable,
+}
+
+impl RecordingState {
+    fn from_u8(value: u8) -> Self {
+        return match value {
+            0 => RecordingState::Initial,
+            1 => RecordingState::Recording,
+            2 => RecordingState::RenderPassActive,
+            _ => RecordingState::Executable,
+        };
+    }
+}
+
 //-----------------------------------------------------------------------------
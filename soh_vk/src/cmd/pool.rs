@@ -1,5 +1,6 @@
 //-----------------------------------------------------------------------------
-use anyhow::Result;
+use crate::error::{bail, ensure};
+use crate::Result;
 use ash::vk::{self, Handle};
 //-----------------------------------------------------------------------------
 
@@ -37,6 +38,7 @@ impl Pool {
             .queue_family_index(graphics_family);
 
         let cmd_pool = unsafe { device.create_command_pool(&create_info, None)? };
+        crate::debug::leaks::track("cmd::Pool", cmd_pool);
 
         return Ok(Pool {
             device: device.clone(),
@@ -57,6 +59,7 @@ impl Pool {
             .queue_family_index(transfer_family);
 
         let cmd_pool = unsafe { device.create_command_pool(&create_info, None)? };
+        crate::debug::leaks::track("cmd::Pool", cmd_pool);
 
         return Ok(Pool {
             device: device.clone(),
@@ -79,7 +82,7 @@ impl Pool {
         let cmd_buffers = unsafe { self.device.allocate_command_buffers(&alloc_info)? };
 
         let Some(&cmd_buffer) = cmd_buffers.first() else {
-            anyhow::bail!("No command buffers were allocated");
+            bail!(Unexpected, "No command buffers were allocated");
         };
 
         return Ok(super::Buffer::from_handle(
@@ -102,8 +105,9 @@ impl Pool {
 
         let cmd_buffers = unsafe { self.device.allocate_command_buffers(&alloc_info)? };
 
-        anyhow::ensure!(
+        ensure!(
             cmd_buffers.len() == count as usize,
+            Unexpected,
             "Number of allocated buffers doesn't match the requested count"
         );
 
@@ -121,6 +125,29 @@ impl Pool {
 
         return Ok(res);
     }
+
+    /// Record a one time command buffer, submit it and wait for it to finish executing
+    ///
+    /// * `record`: function which records the commands into the command buffer
+    pub fn execute_one_time<F>(&self, record: F) -> Result<()>
+    where
+        F: FnOnce(&super::Buffer) -> Result<()>,
+    {
+        let cmd_buf = self.allocate_buffer(super::BufferLevel::Primary)?;
+
+        let res = cmd_buf
+            .begin(vk::CommandBufferUsageFlags::ONE_TIME_SUBMIT)
+            .and_then(|_| record(&cmd_buf))
+            .and_then(|_| cmd_buf.end())
+            .and_then(|_| cmd_buf.submit_and_wait());
+
+        unsafe {
+            self.device
+                .free_command_buffers(**self, std::slice::from_ref(&cmd_buf));
+        }
+
+        return res;
+    }
 }
 
 //-----------------------------------------------------------------------------
@@ -138,6 +165,7 @@ impl Drop for Pool {
             self.cmd_pool.as_raw()
         );
 
+        crate::debug::leaks::untrack("cmd::Pool", self.cmd_pool);
         unsafe {
             self.device.destroy_command_pool(self.cmd_pool, None);
         }
@@ -0,0 +1,122 @@
+//-----------------------------------------------------------------------------
+use crate::Result;
+use ash::vk;
+//-----------------------------------------------------------------------------
+
+pub struct ComputePipeline {
+    device: crate::DeviceRef,
+
+    pipeline: vk::Pipeline,
+    pipeline_layout: vk::PipelineLayout,
+    push_constants_size: u32,
+}
+
+//-----------------------------------------------------------------------------
+// Getters
+impl ComputePipeline {
+    pub fn layout(&self) -> vk::PipelineLayout {
+        return self.pipeline_layout;
+    }
+    pub fn push_constants_size(&self) -> u32 {
+        return self.push_constants_size;
+    }
+}
+
+//-----------------------------------------------------------------------------
+// Constructor
+impl ComputePipeline {
+    /// * `push_constants_size`: size of the push constants block of the shader
+    ///   in bytes ( 0 if there is none )
+    pub fn new(
+        device: &crate::DeviceRef,
+        descriptor_set_layouts: &[&crate::descriptor::SetLayout],
+        compute_shader: &crate::Shader,
+        push_constants_size: u32,
+    ) -> Result<Self> {
+        let stage_info = vk::PipelineShaderStageCreateInfo::default()
+            .stage(vk::ShaderStageFlags::COMPUTE)
+            .module(**compute_shader)
+            .name(c"main");
+
+        /*
+         * Pipeline layout
+         */
+        let descriptor_set_layouts = crate::get_handles_vec(descriptor_set_layouts);
+
+        let push_constant_range = vk::PushConstantRange::default()
+            .stage_flags(vk::ShaderStageFlags::COMPUTE)
+            .offset(0)
+            .size(push_constants_size);
+
+        let mut pipeline_layout_create_info =
+            vk::PipelineLayoutCreateInfo::default().set_layouts(&descriptor_set_layouts);
+
+        if push_constants_size > 0 {
+            pipeline_layout_create_info = pipeline_layout_create_info
+                .push_constant_ranges(std::slice::from_ref(&push_constant_range));
+        }
+
+        let pipeline_layout =
+            unsafe { device.create_pipeline_layout(&pipeline_layout_create_info, None)? };
+
+        /*
+         * Pipeline
+         */
+        let create_info = vk::ComputePipelineCreateInfo::default()
+            .stage(stage_info)
+            .layout(pipeline_layout);
+
+        let pipeline = unsafe {
+            device
+                .create_compute_pipelines(
+                    device.pipeline_cache(),
+                    std::slice::from_ref(&create_info),
+                    None,
+                )
+                .map_err(|(_, e)| e)
+        };
+
+        let pipeline = match pipeline {
+            Ok(pipelines) => pipelines[0],
+            Err(e) => {
+                unsafe { device.destroy_pipeline_layout(pipeline_layout, None) };
+                return Err(e.into());
+            }
+        };
+
+        crate::debug::stats::record_pipelines_created(1);
+        crate::debug::leaks::track("Pipeline", pipeline);
+
+        return Ok(ComputePipeline {
+            device: device.clone(),
+            pipeline,
+            pipeline_layout,
+            push_constants_size,
+        });
+    }
+}
+
+//-----------------------------------------------------------------------------
+// Drop
+impl Drop for ComputePipeline {
+    fn drop(&mut self) {
+        crate::debug::leaks::untrack("Pipeline", self.pipeline);
+        unsafe {
+            self.device.destroy_pipeline(self.pipeline, None);
+            self.device
+                .destroy_pipeline_layout(self.pipeline_layout, None);
+        }
+    }
+}
+
+//-----------------------------------------------------------------------------
+// Deref
+impl std::ops::Deref for ComputePipeline {
+    type Target = vk::Pipeline;
+
+    fn deref(&self) -> &Self::Target {
+        return &self.pipeline;
+    }
+}
+
+//-----------------------------------------------------------------------------
@@ -1,5 +1,6 @@
 //-----------------------------------------------------------------------------
-use anyhow::Result;
+use crate::error::bail;
+use crate::Result;
 use soh_log::LogError;
 //-----------------------------------------------------------------------------
 
@@ -21,6 +22,7 @@ pub struct ContextBootstrapInfo<'a> {
      * Frame info
      */
     pub num_of_frames_in_flight: usize,
+    pub preferred_color_space: crate::wsi::ColorSpace,
 
     /*
      * Shader info
@@ -28,8 +30,19 @@ pub struct ContextBootstrapInfo<'a> {
     pub shader_manager_mode: crate::shader::Mode,
     pub recompile_shaders: bool,
     pub shader_directory: &'a str,
+
+    /*
+     * Statistics
+     */
+    /// Write the `debug::stats` JSON report to this file when the context is dropped
+    pub stats_report_path: Option<&'a str>,
 }
 
+//-----------------------------------------------------------------------------
+/// Recreates user resources on the new device after the device was lost, see
+/// [VulkanContext::register_recreate_callback]
+pub type RecreateCallback = Box<dyn FnMut(&VulkanContext) -> Result<()> + Send>;
+
 //-----------------------------------------------------------------------------
 /// Struct which contains the vulkan context information
 ///
@@ -73,6 +86,23 @@ pub struct VulkanContext {
      * Shader manager
      */
     shader_manager: crate::shader::Manager,
+
+    /*
+     * RenderDoc API (None if not running under RenderDoc)
+     */
+    #[cfg(feature = "renderdoc")]
+    renderdoc: Option<crate::renderdoc::RenderDoc>,
+
+    /*
+     * Where to write the statistics report
+     */
+    stats_report_path: Option<std::path::PathBuf>,
+
+    /*
+     * Called after the device is recreated
+     */
+    recreate_callbacks: Vec<(usize, RecreateCallback)>,
+    next_recreate_callback_id: usize,
 }
 
 /// Structure containing data needed to render a frame
@@ -86,6 +116,14 @@ pub struct PerFrameData<'a> {
     pub cmd_buffer: &'a crate::cmd::Buffer,
 }
 
+/// Frame acquired by [VulkanContext::acquire_frame] which has to be submitted
+/// with [VulkanContext::submit_frame]
+#[derive(Debug, Clone, Copy)]
+pub struct AcquiredFrame {
+    pub frame_idx: usize,
+    pub image_idx: usize,
+}
+
 //-----------------------------------------------------------------------------
 // Getters
 impl VulkanContext {
@@ -129,6 +167,11 @@ impl VulkanContext {
     pub fn shader_manager(&self) -> &crate::shader::Manager {
         &self.shader_manager
     }
+
+    #[cfg(feature = "renderdoc")]
+    pub fn renderdoc(&self) -> Option<&crate::renderdoc::RenderDoc> {
+        return self.renderdoc.as_ref();
+    }
 }
 
 //-----------------------------------------------------------------------------
@@ -140,6 +183,16 @@ impl VulkanContext {
 
         crate::debug::setup_messenger(bootstrap_info.debug_messenger_callback);
 
+        // RenderDoc has to be attached before the instance is created
+        #[cfg(feature = "renderdoc")]
+        let renderdoc = match crate::renderdoc::RenderDoc::new() {
+            Ok(renderdoc) => Some(renderdoc),
+            Err(e) => {
+                soh_log::log_info!("RenderDoc isn't available: {}", e);
+                None
+            }
+        };
+
         let instance = Self::create_instance(&bootstrap_info)?;
         let debug_messenger = crate::debug::Messenger::new(&instance).ok();
 
@@ -147,7 +200,11 @@ impl VulkanContext {
 
         let device = crate::Device::new(&instance, &surface)?;
 
-        let swapchain = crate::Swapchain::new(&device, (win_size.width, win_size.height))?;
+        let swapchain = crate::Swapchain::new_with_color_space(
+            &device,
+            (win_size.width, win_size.height),
+            bootstrap_info.preferred_color_space,
+        )?;
         let render_pass = crate::RenderPass::new_simple(&device, swapchain.image_format())?;
         let framebuffers =
             crate::Framebuffer::new_from_swapchain(&device, &swapchain, &render_pass)?;
@@ -192,6 +249,14 @@ impl VulkanContext {
             in_flight_fences,
 
             shader_manager,
+
+            #[cfg(feature = "renderdoc")]
+            renderdoc,
+
+            stats_report_path: bootstrap_info.stats_report_path.map(Into::into),
+
+            recreate_callbacks: Vec::new(),
+            next_recreate_callback_id: 0,
         });
     }
 }
@@ -204,15 +269,28 @@ impl VulkanContext {
     where
         F: FnOnce(PerFrameData<'_>) -> Result<()>,
     {
+        let Some(frame) = self.acquire_frame(frame_num)? else {
+            return Ok(true);
+        };
+
         /*
-         * Get current frame index
+         * Draw the frame
          */
-        let frame_idx = frame_num % self.num_of_frames_in_flight();
+        user_draw_func(self.frame_data(frame))?;
+
+        return self.submit_frame(frame);
+    }
 
+    /// First half of [VulkanContext::on_frame]: waits for the frame to finish
+    /// rendering and acquires an image from the swapchain.
+    ///
+    /// Returns None if the swapchain should be recreated
+    pub fn acquire_frame(&self, frame_num: usize) -> Result<Option<AcquiredFrame>> {
         /*
-         * Get object references
+         * Get current frame index
          */
-        let cmd_buffer = &self.cmd_buffers[frame_idx];
+        let frame_idx = frame_num % self.num_of_frames_in_flight();
+
         let image_available_semaphore = &self.image_available_semaphores[frame_idx];
         let in_flight_fence = &self.in_flight_fences[frame_idx];
 
@@ -233,7 +311,7 @@ impl VulkanContext {
             Ok((image_idx, false)) => image_idx as usize,
             // Swapchain should be resized
             Ok((_, true)) | Err(ash::vk::Result::ERROR_OUT_OF_DATE_KHR) => {
-                return Ok(true);
+                return Ok(None);
             }
             // Error occured
             Err(e) => {
@@ -249,22 +327,37 @@ impl VulkanContext {
          */
         in_flight_fence.reset();
 
-        /*
-         * Prepare the frame data
-         */
-        let per_frame_data = PerFrameData {
-            context: self,
+        return Ok(Some(AcquiredFrame {
             frame_idx,
             image_idx,
+        }));
+    }
+
+    /// Data to record the acquired frame with
+    pub fn frame_data(&self, frame: AcquiredFrame) -> PerFrameData<'_> {
+        return PerFrameData {
+            context: self,
+            frame_idx: frame.frame_idx,
+            image_idx: frame.image_idx,
 
-            framebuffer: &self.framebuffers[image_idx],
-            cmd_buffer,
+            framebuffer: &self.framebuffers[frame.image_idx],
+            cmd_buffer: &self.cmd_buffers[frame.frame_idx],
         };
+    }
 
-        /*
-         * Draw the frame
-         */
-        user_draw_func(per_frame_data)?;
+    /// Second half of [VulkanContext::on_frame]: submits the recorded command
+    /// buffer of the frame and presents the image.
+    ///
+    /// Returns true if swapchain should be recreated
+    pub fn submit_frame(&self, frame: AcquiredFrame) -> Result<bool> {
+        let AcquiredFrame {
+            frame_idx,
+            image_idx,
+        } = frame;
+
+        let cmd_buffer = &self.cmd_buffers[frame_idx];
+        let image_available_semaphore = &self.image_available_semaphores[frame_idx];
+        let in_flight_fence = &self.in_flight_fences[frame_idx];
 
         /*
          * Use image different semaphore per image.
@@ -298,6 +391,8 @@ impl VulkanContext {
             .present_image(render_finished_semaphore, image_idx as u32);
 
         return match present_result {
+            // The device has to be recreated, see `recover_device_lost`
+            Err(e) if e.is_device_lost() => Err(e),
             // Need to recreate swapchain if Error or suboptimal
             Ok(true) | Err(_) => Ok(true),
             // Don't need to recreate swapchain
@@ -314,8 +409,20 @@ impl VulkanContext {
         /*
          * Recreate the swapchain
          */
+        let old_format = self.swapchain.image_format();
         self.swapchain.recreate(window_size)?;
 
+        /*
+         * Recreate the render pass if the format of the swapchain changed
+         * ( color space switch, moving to another monitor ). Pipelines made
+         * for the old render pass have to be recreated, see
+         * [crate::PipelineRegistry]
+         */
+        if self.swapchain.image_format() != old_format {
+            self.render_pass =
+                crate::RenderPass::new_simple(&self.device, self.swapchain.image_format())?;
+        }
+
         /*
          * Recreate framebuffers
          */
@@ -332,6 +439,173 @@ impl VulkanContext {
         return Ok(());
     }
 
+    /// Registers a callback recreating user resources ( buffers, images,
+    /// pipelines, ... ) after the device was lost. Returns the id of the
+    /// callback for [VulkanContext::unregister_recreate_callback].
+    pub fn register_recreate_callback<F>(&mut self, callback: F) -> usize
+    where
+        F: FnMut(&VulkanContext) -> Result<()> + Send + 'static,
+    {
+        let id = self.next_recreate_callback_id;
+        self.next_recreate_callback_id += 1;
+
+        self.recreate_callbacks.push((id, Box::new(callback)));
+
+        return id;
+    }
+
+    pub fn unregister_recreate_callback(&mut self, id: usize) {
+        self.recreate_callbacks
+            .retain(|(callback_id, _)| *callback_id != id);
+    }
+
+    /// Rebuilds the device and everything the context created with it after
+    /// an error with [crate::Error::is_device_lost], then calls the recreate
+    /// callbacks in the order they were registered.
+    ///
+    /// Objects created with the old device stay valid to drop ( they keep the
+    /// old device alive ), but can't be used with the new one. The pipeline
+    /// cache of the new device is seeded with the one of the old device, so
+    /// the recreated pipelines aren't compiled from scratch.
+    pub fn recover_device_lost(&mut self) -> Result<()> {
+        soh_log::log_warning!("GPU device was lost, recreating it");
+
+        let num_of_frames = self.num_of_frames_in_flight() as u32;
+        let extent = self.swapchain.extent();
+
+        /*
+         * Keep the compiled pipelines, so the recreated ones don't have to be compiled from
+         * scratch. The cache lives on the host, its data is still readable
+         */
+        let pipeline_cache_data = match self.device.pipeline_cache_data() {
+            Ok(data) => data,
+            Err(e) => {
+                soh_log::log_warning!("Can't read the pipeline cache of the lost device: {e}");
+                Vec::new()
+            }
+        };
+
+        /*
+         * Tear down the objects which can't coexist with the new ones
+         */
+        self.device.wait_idle();
+
+        for framebuffer in self.framebuffers.iter() {
+            framebuffer.destroy();
+        }
+        self.framebuffers.clear();
+
+        // Only one swapchain can use the surface
+        self.swapchain.destroy();
+
+        /*
+         * Device and WSI
+         */
+        let device = crate::Device::new_with_pipeline_cache(
+            &self.instance,
+            &self.surface,
+            &pipeline_cache_data,
+        )?;
+
+        let swapchain = crate::Swapchain::new_with_color_space(
+            &device,
+            (extent.width, extent.height),
+            self.swapchain.preferred_color_space(),
+        )?;
+        let render_pass = crate::RenderPass::new_simple(&device, swapchain.image_format())?;
+        let framebuffers =
+            crate::Framebuffer::new_from_swapchain(&device, &swapchain, &render_pass)?;
+
+        /*
+         * Command pools, buffers and synchronization objects
+         */
+        let cmd_pool_graphics = crate::cmd::Pool::new_graphics(&device)?;
+        let cmd_pool_transfer = crate::cmd::Pool::new_transfer(&device)?;
+        let cmd_buffers =
+            cmd_pool_graphics.allocate_buffers(crate::cmd::BufferLevel::Primary, num_of_frames)?;
+
+        let image_available_semaphores = (0..num_of_frames)
+            .map(|_| crate::sync::Semaphore::new(&device))
+            .collect::<Result<Vec<_>>>()?;
+        let render_finished_semaphores = (0..swapchain.num_of_images())
+            .map(|_| crate::sync::Semaphore::new(&device))
+            .collect::<Result<Vec<_>>>()?;
+        let in_flight_fences = (0..num_of_frames)
+            .map(|_| crate::sync::Fence::new(&device, true))
+            .collect::<Result<Vec<_>>>()?;
+
+        /*
+         * Replace the old objects ( command buffers before their pool )
+         */
+        self.cmd_buffers = cmd_buffers;
+        self.cmd_pool_graphics = cmd_pool_graphics;
+        self.cmd_pool_transfer = cmd_pool_transfer;
+
+        self.image_available_semaphores = image_available_semaphores;
+        self.render_finished_semaphores = render_finished_semaphores;
+        self.in_flight_fences = in_flight_fences;
+
+        self.framebuffers = framebuffers;
+        self.render_pass = render_pass;
+        self.swapchain = swapchain;
+        self.device = device;
+
+        /*
+         * User resources
+         */
+        let mut callbacks = std::mem::take(&mut self.recreate_callbacks);
+
+        let res = callbacks
+            .iter_mut()
+            .try_for_each(|(_, callback)| callback(self));
+
+        // Callbacks can't register new ones, nothing to merge
+        self.recreate_callbacks = callbacks;
+
+        return res;
+    }
+
+    /// Recreates the swapchain with the preferred color space ( for example to
+    /// toggle HDR ). The surface may not support it, check
+    /// [crate::Swapchain::color_space] afterwards.
+    pub fn set_preferred_color_space(
+        &mut self,
+        preferred_color_space: crate::wsi::ColorSpace,
+        window_size: (u32, u32),
+    ) -> Result<()> {
+        self.swapchain
+            .set_preferred_color_space(preferred_color_space);
+
+        return self.on_window_resize(window_size);
+    }
+
+    /// Start a RenderDoc capture (does nothing if RenderDoc isn't attached)
+    #[cfg(feature = "renderdoc")]
+    pub fn start_capture(&self) {
+        if let Some(renderdoc) = &self.renderdoc {
+            renderdoc.start_capture();
+        }
+    }
+
+    /// End a RenderDoc capture started with `start_capture`
+    ///
+    /// Returns false if RenderDoc isn't attached or the capture failed
+    #[cfg(feature = "renderdoc")]
+    pub fn end_capture(&self) -> bool {
+        return self
+            .renderdoc
+            .as_ref()
+            .is_some_and(|renderdoc| renderdoc.end_capture());
+    }
+
+    /// Capture the next presented frame (does nothing if RenderDoc isn't attached)
+    #[cfg(feature = "renderdoc")]
+    pub fn trigger_capture(&self) {
+        if let Some(renderdoc) = &self.renderdoc {
+            renderdoc.trigger_capture();
+        }
+    }
+
     fn create_instance(bootstrap_info: &ContextBootstrapInfo) -> Result<crate::InstanceRef> {
         /*
          * Helper functions
@@ -402,10 +676,13 @@ impl VulkanContext {
                 return Ok(crate::wsi::Platform::Wayland);
             }
 
-            anyhow::bail!("Weird platform on linux: neither X11 nor wayland");
+            bail!(
+                Unsupported,
+                "Weird platform on linux: neither X11 nor wayland"
+            );
         }
 
-        anyhow::bail!("Unsupported WSI platform");
+        bail!(Unsupported, "Unsupported WSI platform");
     }
 }
 
@@ -420,6 +697,21 @@ impl Drop for VulkanContext {
         }
 
         self.debug_messenger = None;
+
+        if let Some(path) = &self.stats_report_path {
+            let report = crate::debug::stats::report();
+
+            soh_log::log_info!(
+                "Writing stats report to {:?} ({} validation warnings, {} validation errors)",
+                path,
+                report.num_of_warnings(),
+                report.num_of_errors()
+            );
+
+            if let Err(e) = report.write(path) {
+                soh_log::log_error!("Failed to write stats report: {}", e);
+            }
+        }
     }
 }
 
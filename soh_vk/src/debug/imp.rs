@@ -47,6 +47,23 @@ pub extern "system" fn debug_messenger_callback(
         return vk::FALSE;
     };
 
+    /*
+     * Count validation warnings and errors by message id
+     */
+    let stats_severity = match message_severity {
+        vk::DebugUtilsMessageSeverityFlagsEXT::WARNING => Some(super::stats::Severity::Warning),
+        vk::DebugUtilsMessageSeverityFlagsEXT::ERROR => Some(super::stats::Severity::Error),
+        _ => None,
+    };
+
+    if let Some(severity) = stats_severity {
+        let message_id = unsafe { callback_data.message_id_name_as_c_str() }
+            .map(|id| id.to_string_lossy().into_owned())
+            .unwrap_or_else(|| format!("0x{:x}", callback_data.message_id_number));
+
+        super::stats::record_validation_message(severity, &message_id);
+    }
+
     /*
      * Compile args
      */
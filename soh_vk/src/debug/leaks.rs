@@ -0,0 +1,119 @@
+//-----------------------------------------------------------------------------
+//! Tracker of the wrapper objects which are still alive ( debug builds only )
+//!
+//! Every tracked wrapper is registered with the backtrace of its creation when
+//! it's created and removed when it's destroyed. Objects which are still alive
+//! when the instance is destroyed are reported through `soh_log`.
+//!
+//! Backtraces are captured like [std::backtrace::Backtrace::capture] does, so
+//! `RUST_BACKTRACE=1` has to be set to get them. In release builds nothing is
+//! tracked.
+//-----------------------------------------------------------------------------
+use ash::vk::Handle;
+#[cfg(debug_assertions)]
+use std::backtrace::Backtrace;
+#[cfg(debug_assertions)]
+use std::collections::BTreeMap;
+#[cfg(debug_assertions)]
+use std::sync::Mutex;
+//-----------------------------------------------------------------------------
+#[cfg(debug_assertions)]
+static LIVE_OBJECTS: Mutex<BTreeMap<(&'static str, u64), Backtrace>> = Mutex::new(BTreeMap::new());
+//-----------------------------------------------------------------------------
+
+/// A tracked object which hasn't been destroyed
+#[derive(Debug, Clone)]
+pub struct LiveObject {
+    /// Name of the wrapper type
+    pub kind: &'static str,
+    /// Raw Vulkan handle
+    pub handle: u64,
+    /// Where the object was created ( empty if backtraces are disabled )
+    pub backtrace: String,
+}
+
+//-----------------------------------------------------------------------------
+// Recording (called from the wrappers)
+#[cfg(debug_assertions)]
+pub(crate) fn track<H: Handle>(kind: &'static str, handle: H) {
+    let Ok(mut objects) = LIVE_OBJECTS.lock() else {
+        return;
+    };
+
+    objects.insert((kind, handle.as_raw()), Backtrace::capture());
+}
+
+#[cfg(debug_assertions)]
+pub(crate) fn untrack<H: Handle>(kind: &'static str, handle: H) {
+    let Ok(mut objects) = LIVE_OBJECTS.lock() else {
+        return;
+    };
+
+    objects.remove(&(kind, handle.as_raw()));
+}
+
+#[cfg(not(debug_assertions))]
+#[inline(always)]
+pub(crate) fn track<H: Handle>(_kind: &'static str, _handle: H) {}
+
+#[cfg(not(debug_assertions))]
+#[inline(always)]
+pub(crate) fn untrack<H: Handle>(_kind: &'static str, _handle: H) {}
+
+//-----------------------------------------------------------------------------
+
+/// Objects which were created and not destroyed yet ( always empty in release
+/// builds )
+pub fn live_objects() -> Vec<LiveObject> {
+    #[cfg(debug_assertions)]
+    {
+        let Ok(objects) = LIVE_OBJECTS.lock() else {
+            return Vec::new();
+        };
+
+        return objects
+            .iter()
+            .map(|(&(kind, handle), backtrace)| LiveObject {
+                kind,
+                handle,
+                backtrace: match backtrace.status() {
+                    std::backtrace::BacktraceStatus::Captured => backtrace.to_string(),
+                    _ => String::new(),
+                },
+            })
+            .collect();
+    }
+
+    #[cfg(not(debug_assertions))]
+    return Vec::new();
+}
+
+/// Log every object which is still alive
+pub(crate) fn report_leaks() {
+    let objects = live_objects();
+
+    if objects.is_empty() {
+        return;
+    }
+
+    soh_log::log_warning!("{} Vulkan objects were not destroyed:", objects.len());
+
+    for object in objects.iter() {
+        if object.backtrace.is_empty() {
+            soh_log::log_warning!("{} (0x{:x})", object.kind, object.handle);
+        } else {
+            soh_log::log_warning!(
+                "{} (0x{:x}) created at:\n{}",
+                object.kind,
+                object.handle,
+                object.backtrace
+            );
+        }
+    }
+
+    if objects.iter().all(|object| object.backtrace.is_empty()) {
+        soh_log::log_warning!("Set RUST_BACKTRACE=1 to see where the objects were created");
+    }
+}
+
+//-----------------------------------------------------------------------------
@@ -2,8 +2,14 @@
 // Implementation details
 mod imp;
 //-----------------------------------------------------------------------------
+// Validation and allocation statistics
+pub mod stats;
+// Wrapper objects which weren't destroyed ( debug builds )
+pub mod leaks;
+//-----------------------------------------------------------------------------
 
-use anyhow::Result;
+use crate::error::ensure;
+use crate::Result;
 use ash::vk;
 
 //-----------------------------------------------------------------------------
@@ -51,8 +57,9 @@ impl Messenger {
     pub fn new(instance: &crate::InstanceRef) -> Result<Self> {
         use soh_log::LogError;
 
-        anyhow::ensure!(
+        ensure!(
             crate::Instance::are_validation_layers_enabled(),
+            InvalidUsage,
             "Cannot create debug messenger! Validation layers are not enabled"
         );
 
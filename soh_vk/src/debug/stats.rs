@@ -0,0 +1,201 @@
+//-----------------------------------------------------------------------------
+//! Run statistics (validation messages, object allocations, GPU memory usage)
+//!
+//! The statistics are collected for the whole process and can be written as a JSON report at the
+//! end of a run (e.g. to let CI fail when new validation errors appear).
+//-----------------------------------------------------------------------------
+use crate::Result;
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+//-----------------------------------------------------------------------------
+static VALIDATION_MESSAGES: Mutex<BTreeMap<(Severity, String), u64>> = Mutex::new(BTreeMap::new());
+
+static PIPELINES_CREATED: AtomicU64 = AtomicU64::new(0);
+static DESCRIPTOR_SETS_ALLOCATED: AtomicU64 = AtomicU64::new(0);
+
+static MEMORY_ALLOCATIONS: AtomicU64 = AtomicU64::new(0);
+static MEMORY_CURRENT: AtomicU64 = AtomicU64::new(0);
+static MEMORY_PEAK: AtomicU64 = AtomicU64::new(0);
+//-----------------------------------------------------------------------------
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    Warning,
+    Error,
+}
+
+/// Snapshot of the collected statistics
+#[derive(Debug, Clone, Default)]
+pub struct Report {
+    /// Number of validation messages for every (severity, message id) pair
+    pub validation_messages: BTreeMap<(Severity, String), u64>,
+
+    pub pipelines_created: u64,
+    pub descriptor_sets_allocated: u64,
+
+    pub memory_allocations: u64,
+    pub memory_current_bytes: u64,
+    pub memory_peak_bytes: u64,
+}
+
+//-----------------------------------------------------------------------------
+// Recording (called from the wrappers)
+pub(crate) fn record_validation_message(severity: Severity, message_id: &str) {
+    let Ok(mut messages) = VALIDATION_MESSAGES.lock() else {
+        return;
+    };
+
+    *messages
+        .entry((severity, message_id.to_owned()))
+        .or_default() += 1;
+}
+
+pub(crate) fn record_pipelines_created(count: u64) {
+    PIPELINES_CREATED.fetch_add(count, Ordering::Relaxed);
+}
+
+pub(crate) fn record_descriptor_sets_allocated(count: u64) {
+    DESCRIPTOR_SETS_ALLOCATED.fetch_add(count, Ordering::Relaxed);
+}
+
+pub(crate) fn record_memory_alloc(size: u64) {
+    MEMORY_ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+
+    let current = MEMORY_CURRENT.fetch_add(size, Ordering::Relaxed) + size;
+    MEMORY_PEAK.fetch_max(current, Ordering::Relaxed);
+}
+
+pub(crate) fn record_memory_free(size: u64) {
+    MEMORY_CURRENT.fetch_sub(size, Ordering::Relaxed);
+}
+
+//-----------------------------------------------------------------------------
+
+/// Get the statistics collected so far
+pub fn report() -> Report {
+    let validation_messages = VALIDATION_MESSAGES
+        .lock()
+        .map(|messages| messages.clone())
+        .unwrap_or_default();
+
+    return Report {
+        validation_messages,
+
+        pipelines_created: PIPELINES_CREATED.load(Ordering::Relaxed),
+        descriptor_sets_allocated: DESCRIPTOR_SETS_ALLOCATED.load(Ordering::Relaxed),
+
+        memory_allocations: MEMORY_ALLOCATIONS.load(Ordering::Relaxed),
+        memory_current_bytes: MEMORY_CURRENT.load(Ordering::Relaxed),
+        memory_peak_bytes: MEMORY_PEAK.load(Ordering::Relaxed),
+    };
+}
+
+//-----------------------------------------------------------------------------
+// Specific implementation
+impl Report {
+    pub fn num_of_warnings(&self) -> u64 {
+        return self.count(Severity::Warning);
+    }
+
+    pub fn num_of_errors(&self) -> u64 {
+        return self.count(Severity::Error);
+    }
+
+    /// Serialize the report as JSON
+    pub fn to_json(&self) -> String {
+        use std::fmt::Write;
+
+        let mut json = String::new();
+
+        // Writing into a String can't fail
+        let _ = (|| -> std::fmt::Result {
+            writeln!(json, "{{")?;
+            writeln!(json, "  \"validation\": {{")?;
+            writeln!(json, "    \"warnings\": {},", self.num_of_warnings())?;
+            writeln!(json, "    \"errors\": {},", self.num_of_errors())?;
+            writeln!(json, "    \"messages\": [")?;
+
+            for (idx, ((severity, id), count)) in self.validation_messages.iter().enumerate() {
+                let severity = match severity {
+                    Severity::Warning => "warning",
+                    Severity::Error => "error",
+                };
+                let separator = if idx + 1 < self.validation_messages.len() {
+                    ","
+                } else {
+                    ""
+                };
+
+                writeln!(
+                    json,
+                    "      {{ \"id\": \"{}\", \"severity\": \"{}\", \"count\": {} }}{}",
+                    escape_json(id),
+                    severity,
+                    count,
+                    separator
+                )?;
+            }
+
+            writeln!(json, "    ]")?;
+            writeln!(json, "  }},")?;
+            writeln!(json, "  \"pipelines_created\": {},", self.pipelines_created)?;
+            writeln!(
+                json,
+                "  \"descriptor_sets_allocated\": {},",
+                self.descriptor_sets_allocated
+            )?;
+            writeln!(json, "  \"memory\": {{")?;
+            writeln!(json, "    \"allocations\": {},", self.memory_allocations)?;
+            writeln!(
+                json,
+                "    \"current_bytes\": {},",
+                self.memory_current_bytes
+            )?;
+            writeln!(json, "    \"peak_bytes\": {}", self.memory_peak_bytes)?;
+            writeln!(json, "  }}")?;
+            writeln!(json, "}}")?;
+
+            return Ok(());
+        })();
+
+        return json;
+    }
+
+    /// Write the report as JSON to the file
+    pub fn write<P: AsRef<std::path::Path>>(&self, path: P) -> Result<()> {
+        std::fs::write(path, self.to_json())?;
+        return Ok(());
+    }
+
+    fn count(&self, severity: Severity) -> u64 {
+        return self
+            .validation_messages
+            .iter()
+            .filter(|((s, _), _)| *s == severity)
+            .map(|(_, count)| count)
+            .sum();
+    }
+}
+
+//-----------------------------------------------------------------------------
+
+fn escape_json(s: &str) -> String {
+    let mut res = String::with_capacity(s.len());
+
+    for c in s.chars() {
+        match c {
+            '"' => res.push_str("\\\""),
+            '\\' => res.push_str("\\\\"),
+            '\n' => res.push_str("\\n"),
+            '\r' => res.push_str("\\r"),
+            '\t' => res.push_str("\\t"),
+            c if (c as u32) < 0x20 => res.push_str(&format!("\\u{:04x}", c as u32)),
+            c => res.push(c),
+        }
+    }
+
+    return res;
+}
+
+//-----------------------------------------------------------------------------
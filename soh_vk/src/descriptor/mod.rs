@@ -2,12 +2,14 @@
 mod pool;
 mod set;
 mod set_layout;
+mod typed;
 //-----------------------------------------------------------------------------
 pub mod uniform;
 //-----------------------------------------------------------------------------
 pub use pool::*;
 pub use set::*;
 pub use set_layout::*;
+pub use typed::*;
 //-----------------------------------------------------------------------------
 
 use ash::vk;
@@ -1,5 +1,6 @@
 //-----------------------------------------------------------------------------
-use anyhow::Result;
+use crate::error::{bail, ensure};
+use crate::Result;
 use ash::vk;
 //-----------------------------------------------------------------------------
 
@@ -86,6 +87,7 @@ impl PoolBuilder {
             .max_sets(self.max_num_of_sets);
 
         let pool = unsafe { device.create_descriptor_pool(&create_info, None)? };
+        crate::debug::leaks::track("descriptor::Pool", pool);
 
         return Ok(Pool {
             device: device.clone(),
@@ -111,9 +113,11 @@ impl Pool {
         let sets = unsafe { self.device.allocate_descriptor_sets(&alloc_info)? };
 
         let Some(&set) = sets.first() else {
-            anyhow::bail!("No descriptor sets were allocated");
+            bail!(Unexpected, "No descriptor sets were allocated");
         };
 
+        crate::debug::stats::record_descriptor_sets_allocated(1);
+
         return Ok(super::Set::from_handle(self.device.clone(), set));
     }
 
@@ -130,11 +134,14 @@ impl Pool {
 
         let sets = unsafe { self.device.allocate_descriptor_sets(&alloc_info)? };
 
-        anyhow::ensure!(
+        ensure!(
             sets.len() == layouts.len(),
+            Unexpected,
             "Number of allocated descriptor sets doesn't match the requested count"
         );
 
+        crate::debug::stats::record_descriptor_sets_allocated(sets.len() as u64);
+
         let res = sets
             .iter()
             .map(|set| {
@@ -150,6 +157,7 @@ impl Pool {
 // Drop
 impl Drop for Pool {
     fn drop(&mut self) {
+        crate::debug::leaks::untrack("descriptor::Pool", self.pool);
         unsafe {
             self.device.destroy_descriptor_pool(self.pool, None);
         }
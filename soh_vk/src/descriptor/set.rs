@@ -19,17 +19,41 @@ impl Set {
     pub fn update_uniform_buffers(
         &mut self,
         uniform_buffers: &[(u32, &[&crate::uniform::Buffer])],
+    ) {
+        let buffers = uniform_buffers
+            .iter()
+            .map(|(binding, ubs)| (*binding, ubs.iter().map(|ub| ub.buffer()).collect()))
+            .collect::<Vec<_>>();
+
+        self.update_buffers(vk::DescriptorType::UNIFORM_BUFFER, &buffers);
+    }
+
+    /// Write each storage buffer to it's binding, like
+    /// [Set::update_uniform_buffers]
+    pub fn update_storage_buffers(&mut self, storage_buffers: &[(u32, &[&crate::Buffer])]) {
+        let buffers = storage_buffers
+            .iter()
+            .map(|(binding, buffers)| (*binding, buffers.to_vec()))
+            .collect::<Vec<_>>();
+
+        self.update_buffers(vk::DescriptorType::STORAGE_BUFFER, &buffers);
+    }
+
+    fn update_buffers(
+        &mut self,
+        descriptor_type: vk::DescriptorType,
+        buffers: &[(u32, Vec<&crate::Buffer>)],
     ) {
         /*
-         * Write info for each uniform buffer
+         * Write info for each buffer
          */
-        let buffer_infos = uniform_buffers
+        let buffer_infos = buffers
             .iter()
-            .map(|(_, ubs)| {
-                return ubs
+            .map(|(_, buffers)| {
+                return buffers
                     .iter()
-                    .map(|&ub| {
-                        return Self::get_buffer_descriptor_info(ub.buffer());
+                    .map(|&buffer| {
+                        return Self::get_buffer_descriptor_info(buffer);
                     })
                     .collect::<Vec<_>>();
             })
@@ -38,14 +62,14 @@ impl Set {
         /*
          * Descriptor write instruction for each binding
          */
-        let descriptor_writes = uniform_buffers
+        let descriptor_writes = buffers
             .iter()
             .enumerate()
             .map(|(idx, &(binding, _))| {
                 return vk::WriteDescriptorSet::default()
                     .dst_set(**self)
                     .dst_binding(binding)
-                    .descriptor_type(vk::DescriptorType::UNIFORM_BUFFER)
+                    .descriptor_type(descriptor_type)
                     .buffer_info(&buffer_infos[idx]);
             })
             .collect::<Vec<_>>();
@@ -58,6 +82,76 @@ impl Set {
         }
     }
 
+    /// Write each texture to it's binding ( combined image samplers in the
+    /// `SHADER_READ_ONLY_OPTIMAL` layout ), like [Set::update_uniform_buffers]
+    pub fn update_textures(&mut self, textures: &[(u32, &[&crate::Texture])]) {
+        let image_infos = textures
+            .iter()
+            .map(|(_, textures)| {
+                return textures
+                    .iter()
+                    .map(|&texture| {
+                        return vk::DescriptorImageInfo::default()
+                            .sampler(texture.sampler())
+                            .image_view(texture.image_view())
+                            .image_layout(vk::ImageLayout::SHADER_READ_ONLY_OPTIMAL);
+                    })
+                    .collect::<Vec<_>>();
+            })
+            .collect::<Vec<_>>();
+
+        let image_infos = textures
+            .iter()
+            .zip(image_infos.iter())
+            .map(|(&(binding, _), infos)| (binding, infos.as_slice()))
+            .collect::<Vec<_>>();
+
+        self.update_combined_image_samplers(&image_infos);
+    }
+
+    /// Write combined image samplers given by their raw infos to each binding
+    pub fn update_combined_image_samplers(
+        &mut self,
+        image_infos: &[(u32, &[vk::DescriptorImageInfo])],
+    ) {
+        let descriptor_writes = image_infos
+            .iter()
+            .map(|&(binding, infos)| {
+                return vk::WriteDescriptorSet::default()
+                    .dst_set(**self)
+                    .dst_binding(binding)
+                    .descriptor_type(vk::DescriptorType::COMBINED_IMAGE_SAMPLER)
+                    .image_info(infos);
+            })
+            .collect::<Vec<_>>();
+
+        unsafe {
+            self.device.update_descriptor_sets(&descriptor_writes, &[]);
+        }
+    }
+
+    /// Write a resource ( or an array of them ) to the binding, see [super::DescriptorSet]
+    pub fn write_resource<R>(&mut self, binding: u32, resource: &R)
+    where
+        R: super::DescriptorResource,
+    {
+        let mut infos = super::DescriptorInfos::default();
+        resource.descriptor_infos(&mut infos);
+
+        let write = vk::WriteDescriptorSet::default()
+            .dst_set(**self)
+            .dst_binding(binding)
+            .descriptor_type(R::DESCRIPTOR_TYPE);
+        let write = match infos.images.is_empty() {
+            true => write.buffer_info(&infos.buffers),
+            false => write.image_info(&infos.images),
+        };
+
+        unsafe {
+            self.device.update_descriptor_sets(&[write], &[]);
+        }
+    }
+
     pub(super) fn from_handle(device: crate::DeviceRef, set: vk::DescriptorSet) -> Self {
         return Set { device, set };
     }
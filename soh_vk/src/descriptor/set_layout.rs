@@ -1,5 +1,5 @@
 //-----------------------------------------------------------------------------
-use anyhow::Result;
+use crate::Result;
 use ash::vk;
 //-----------------------------------------------------------------------------
 
@@ -36,6 +36,7 @@ impl SetLayout {
         let create_info = vk::DescriptorSetLayoutCreateInfo::default().bindings(&vk_bindings);
 
         let layout = unsafe { device.create_descriptor_set_layout(&create_info, None)? };
+        crate::debug::leaks::track("SetLayout", layout);
 
         return Ok(SetLayout {
             device: device.clone(),
@@ -49,6 +50,7 @@ impl SetLayout {
 // Drop
 impl Drop for SetLayout {
     fn drop(&mut self) {
+        crate::debug::leaks::untrack("SetLayout", self.layout);
         unsafe {
             self.device.destroy_descriptor_set_layout(self.layout, None);
         }
@@ -0,0 +1,120 @@
+//-----------------------------------------------------------------------------
+use crate::Result;
+use ash::vk;
+//-----------------------------------------------------------------------------
+
+/// Descriptor set layout described by a struct, usually derived:
+///
+/// ```ignore
+/// #[derive(soh::vk::DescriptorSet)]
+/// struct MaterialSet<'a> {
+///     #[binding(0, uniform)]
+///     camera: &'a soh::vk::uniform::Buffer,
+///     #[binding(1, combined_image_sampler, stages = "fragment")]
+///     albedo: &'a soh::vk::Texture,
+///     #[binding(2, storage, stages = "vertex | fragment")]
+///     lights: [&'a soh::vk::Buffer; 4],
+/// }
+///
+/// let layout = MaterialSet::set_layout(&device)?;
+/// // ...
+/// MaterialSet { camera: &camera, albedo: &albedo, lights }.write(&mut set);
+/// ```
+///
+/// The descriptor types are `uniform`, `storage` and `combined_image_sampler`, the field type
+/// should be a [DescriptorResource] of the same type ( checked at compile time ). Arrays of
+/// resources make bindings with several descriptors. The stages default to all graphics stages,
+/// the names are `vertex`, `fragment`, `tessellation_control`, `tessellation_evaluation`,
+/// `geometry`, `compute`, `all_graphics` and `all` joined by `|`.
+pub trait DescriptorSet {
+    /// Bindings of the set layout
+    fn bindings() -> Vec<super::SetLayoutBinding>;
+
+    /// Write the resources of the fields to their bindings
+    fn write(&self, set: &mut super::Set);
+
+    fn set_layout(device: &crate::DeviceRef) -> Result<super::SetLayout> {
+        return super::SetLayout::new(device, &Self::bindings());
+    }
+}
+
+/// Resource which can be written to a descriptor
+pub trait DescriptorResource {
+    const DESCRIPTOR_TYPE: vk::DescriptorType;
+    /// Number of descriptors in the binding
+    const COUNT: u32 = 1;
+
+    /// Append the infos of the descriptors
+    fn descriptor_infos(&self, infos: &mut DescriptorInfos);
+}
+
+/// Infos of the descriptors written to a binding ( buffers or images )
+#[derive(Debug, Default)]
+pub struct DescriptorInfos {
+    pub buffers: Vec<vk::DescriptorBufferInfo>,
+    pub images: Vec<vk::DescriptorImageInfo>,
+}
+
+//-----------------------------------------------------------------------------
+
+impl DescriptorResource for super::uniform::Buffer {
+    const DESCRIPTOR_TYPE: vk::DescriptorType = vk::DescriptorType::UNIFORM_BUFFER;
+
+    fn descriptor_infos(&self, infos: &mut DescriptorInfos) {
+        self.buffer().descriptor_infos(infos);
+    }
+}
+
+impl DescriptorResource for crate::Buffer {
+    const DESCRIPTOR_TYPE: vk::DescriptorType = vk::DescriptorType::STORAGE_BUFFER;
+
+    fn descriptor_infos(&self, infos: &mut DescriptorInfos) {
+        infos.buffers.push(
+            vk::DescriptorBufferInfo::default()
+                .buffer(**self)
+                .offset(0)
+                .range(self.buffer_size()),
+        );
+    }
+}
+
+impl DescriptorResource for crate::Texture {
+    const DESCRIPTOR_TYPE: vk::DescriptorType = vk::DescriptorType::COMBINED_IMAGE_SAMPLER;
+
+    fn descriptor_infos(&self, infos: &mut DescriptorInfos) {
+        infos.images.push(
+            vk::DescriptorImageInfo::default()
+                .sampler(self.sampler())
+                .image_view(self.image_view())
+                .image_layout(vk::ImageLayout::SHADER_READ_ONLY_OPTIMAL),
+        );
+    }
+}
+
+impl<T> DescriptorResource for &T
+where
+    T: DescriptorResource,
+{
+    const DESCRIPTOR_TYPE: vk::DescriptorType = T::DESCRIPTOR_TYPE;
+    const COUNT: u32 = T::COUNT;
+
+    fn descriptor_infos(&self, infos: &mut DescriptorInfos) {
+        (**self).descriptor_infos(infos);
+    }
+}
+
+impl<T, const N: usize> DescriptorResource for [T; N]
+where
+    T: DescriptorResource,
+{
+    const DESCRIPTOR_TYPE: vk::DescriptorType = T::DESCRIPTOR_TYPE;
+    const COUNT: u32 = T::COUNT * N as u32;
+
+    fn descriptor_infos(&self, infos: &mut DescriptorInfos) {
+        for resource in self.iter() {
+            resource.descriptor_infos(infos);
+        }
+    }
+}
+
+//-----------------------------------------------------------------------------
@@ -1,5 +1,5 @@
 //-----------------------------------------------------------------------------
-use anyhow::Result;
+use crate::Result;
 //-----------------------------------------------------------------------------
 
 pub struct Buffer {
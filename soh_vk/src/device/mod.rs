@@ -2,7 +2,7 @@
 pub mod physical;
 //-----------------------------------------------------------------------------
 
-use anyhow::Result;
+use crate::Result;
 use ash::vk::{self, Handle};
 
 //-----------------------------------------------------------------------------
@@ -19,11 +19,21 @@ pub struct Device {
 
     // EXT, KHR devices
     device_swapchain: ash::khr::swapchain::Device,
+    #[cfg(feature = "buffer_device_address")]
+    device_buffer_device_address: ash::khr::buffer_device_address::Device,
+    #[cfg(feature = "timeline_semaphore")]
+    device_timeline_semaphore: ash::khr::timeline_semaphore::Device,
+    // Optional, enabled when the physical device supports it
+    device_conditional_rendering: Option<ash::ext::conditional_rendering::Device>,
 
     // Queues
     graphics_queue: vk::Queue,
     present_queue: vk::Queue,
     transfer_queue: vk::Queue,
+
+    // Used by every pipeline created with the device, its data seeds the cache of the device
+    // recreated after the device was lost
+    pipeline_cache: vk::PipelineCache,
 }
 
 //-----------------------------------------------------------------------------
@@ -31,7 +41,7 @@ pub struct Device {
 ///
 /// (This is needed because the vulkan handles are implicitly bound to a specific device. Therefore
 /// it's redundant to have to provide devices everywhere)
-pub type DeviceRef = std::rc::Rc<Device>;
+pub type DeviceRef = std::sync::Arc<Device>;
 //-----------------------------------------------------------------------------
 // According to the vulkan documentation this should be OK
 unsafe impl Sync for Device {}
@@ -50,6 +60,18 @@ impl Device {
     pub fn device_swapchain(&self) -> &ash::khr::swapchain::Device {
         return &self.device_swapchain;
     }
+    #[cfg(feature = "buffer_device_address")]
+    pub fn device_buffer_device_address(&self) -> &ash::khr::buffer_device_address::Device {
+        return &self.device_buffer_device_address;
+    }
+    #[cfg(feature = "timeline_semaphore")]
+    pub fn device_timeline_semaphore(&self) -> &ash::khr::timeline_semaphore::Device {
+        return &self.device_timeline_semaphore;
+    }
+    /// `VK_EXT_conditional_rendering`, `None` if it isn't supported
+    pub fn device_conditional_rendering(&self) -> Option<&ash::ext::conditional_rendering::Device> {
+        return self.device_conditional_rendering.as_ref();
+    }
 
     pub fn graphics_queue(&self) -> vk::Queue {
         return self.graphics_queue;
@@ -60,12 +82,25 @@ impl Device {
     pub fn transfer_queue(&self) -> vk::Queue {
         return self.transfer_queue;
     }
+    pub fn pipeline_cache(&self) -> vk::PipelineCache {
+        return self.pipeline_cache;
+    }
 }
 
 //-----------------------------------------------------------------------------
 // Constructor
 impl Device {
     pub fn new(instance: &crate::InstanceRef, surface: &crate::SurfaceRef) -> Result<DeviceRef> {
+        return Self::new_with_pipeline_cache(instance, surface, &[]);
+    }
+
+    /// Create the device with its pipeline cache seeded with the `pipeline_cache_data` of
+    /// [Device::pipeline_cache_data] ( data of another driver or GPU is ignored by the driver )
+    pub fn new_with_pipeline_cache(
+        instance: &crate::InstanceRef,
+        surface: &crate::SurfaceRef,
+        pipeline_cache_data: &[u8],
+    ) -> Result<DeviceRef> {
         soh_log::log_info!("Creating logical device");
 
         /*
@@ -91,25 +126,75 @@ impl Device {
         /*
          * Specify extensions
          */
-        let swapchain_extension_name = ash::khr::swapchain::NAME;
-        let extensions = [swapchain_extension_name.as_ptr()];
+        let conditional_rendering =
+            physical.supports_extension(ash::ext::conditional_rendering::NAME);
+
+        let mut extensions = physical::Device::required_extensions()
+            .iter()
+            .map(|ext| ext.as_ptr())
+            .collect::<Vec<_>>();
+        if conditional_rendering {
+            extensions.push(ash::ext::conditional_rendering::NAME.as_ptr());
+        }
 
         let device_features = vk::PhysicalDeviceFeatures::default()
             .depth_clamp(true)
             .fill_mode_non_solid(true) // For lines
-            .wide_lines(true); // For wide lines
+            .wide_lines(true) // For wide lines
+            .occlusion_query_precise(physical.info().features.occlusion_query_precise == vk::TRUE)
+            .tessellation_shader(physical.info().features.tessellation_shader == vk::TRUE)
+            .geometry_shader(physical.info().features.geometry_shader == vk::TRUE);
 
         /*
          * Create logical device
          */
-        let create_info = vk::DeviceCreateInfo::default()
+        let mut create_info = vk::DeviceCreateInfo::default()
             .queue_create_infos(&queue_create_infos)
             .enabled_features(&device_features)
             .enabled_extension_names(&extensions);
 
+        let mut conditional_rendering_features =
+            vk::PhysicalDeviceConditionalRenderingFeaturesEXT::default()
+                .conditional_rendering(true);
+        if conditional_rendering {
+            create_info = create_info.push_next(&mut conditional_rendering_features);
+        }
+
+        #[cfg(feature = "buffer_device_address")]
+        let mut buffer_device_address_features =
+            vk::PhysicalDeviceBufferDeviceAddressFeatures::default().buffer_device_address(true);
+        #[cfg(feature = "buffer_device_address")]
+        {
+            create_info = create_info.push_next(&mut buffer_device_address_features);
+        }
+
+        #[cfg(feature = "imageless_framebuffer")]
+        let mut imageless_framebuffer_features =
+            vk::PhysicalDeviceImagelessFramebufferFeatures::default().imageless_framebuffer(true);
+        #[cfg(feature = "imageless_framebuffer")]
+        {
+            create_info = create_info.push_next(&mut imageless_framebuffer_features);
+        }
+
+        #[cfg(feature = "timeline_semaphore")]
+        let mut timeline_semaphore_features =
+            vk::PhysicalDeviceTimelineSemaphoreFeatures::default().timeline_semaphore(true);
+        #[cfg(feature = "timeline_semaphore")]
+        {
+            create_info = create_info.push_next(&mut timeline_semaphore_features);
+        }
+
         let device = unsafe { instance.create_device(*physical, &create_info, None)? };
 
         let device_swapchain = ash::khr::swapchain::Device::new(instance, &device);
+        #[cfg(feature = "buffer_device_address")]
+        let device_buffer_device_address =
+            ash::khr::buffer_device_address::Device::new(instance, &device);
+        #[cfg(feature = "timeline_semaphore")]
+        let device_timeline_semaphore =
+            ash::khr::timeline_semaphore::Device::new(instance, &device);
+        let device_conditional_rendering = conditional_rendering
+            .then(|| ash::ext::conditional_rendering::Device::new(instance, &device));
 
         /*
          * Get queues
@@ -127,15 +212,35 @@ impl Device {
             physical.queue_family_idx(crate::QueueType::Transfer),
         );
 
+        /*
+         * Pipeline cache
+         */
+        let cache_create_info =
+            vk::PipelineCacheCreateInfo::default().initial_data(pipeline_cache_data);
+        let pipeline_cache = match unsafe { device.create_pipeline_cache(&cache_create_info, None) }
+        {
+            Ok(pipeline_cache) => pipeline_cache,
+            Err(e) => {
+                unsafe { device.destroy_device(None) };
+                return Err(e.into());
+            }
+        };
+
         return Ok(DeviceRef::new(Device {
             instance: instance.clone(),
             surface: surface.clone(),
             physical,
             logical: device,
             device_swapchain,
+            #[cfg(feature = "buffer_device_address")]
+            device_buffer_device_address,
+            #[cfg(feature = "timeline_semaphore")]
+            device_timeline_semaphore,
+            device_conditional_rendering,
             graphics_queue,
             present_queue,
             transfer_queue,
+            pipeline_cache,
         }));
     }
 }
@@ -149,6 +254,11 @@ impl Device {
         }
     }
 
+    /// Contents of the pipeline cache, to seed the cache of a new device with
+    pub fn pipeline_cache_data(&self) -> Result<Vec<u8>> {
+        return Ok(unsafe { self.logical.get_pipeline_cache_data(self.pipeline_cache)? });
+    }
+
     /// Get a queue handle for specified queue family index
     pub fn get_queue(&self, queue_family_index: u32) -> vk::Queue {
         return Self::__get_queue(&self.logical, queue_family_index);
@@ -168,7 +278,11 @@ impl Drop for Device {
             self.physical.info().name
         );
 
-        unsafe { self.logical.destroy_device(None) };
+        unsafe {
+            self.logical
+                .destroy_pipeline_cache(self.pipeline_cache, None);
+            self.logical.destroy_device(None);
+        }
     }
 }
 
@@ -1,5 +1,6 @@
 //-----------------------------------------------------------------------------
-use anyhow::Result;
+use crate::error::ensure;
+use crate::Result;
 use ash::vk::{self, Handle};
 //-----------------------------------------------------------------------------
 
@@ -96,8 +97,9 @@ impl Device {
             .collect::<Vec<_>>();
 
         // Throw error if no suitable devices
-        anyhow::ensure!(
+        ensure!(
             !suitable_devices.is_empty(),
+            Unsupported,
             "Coudn't find suitable physical device"
         );
 
@@ -122,6 +124,22 @@ impl Device {
         let gpu_info =
             PhysicalDeviceInfo::query_info(instance, *selected_device.1, surface).unwrap();
 
+        {
+            let props = &gpu_info.device_props;
+            let api_version = format!(
+                "{}.{}.{}",
+                vk::api_version_major(props.api_version),
+                vk::api_version_minor(props.api_version),
+                vk::api_version_patch(props.api_version)
+            );
+
+            // Bug reports need the GPU, the errors are only the poisoned logger
+            let _ = soh_log::LOGGER.set_header_entry("soh_vk", env!("CARGO_PKG_VERSION"));
+            let _ = soh_log::LOGGER.set_header_entry("gpu", &gpu_info.name);
+            let _ = soh_log::LOGGER.set_header_entry("driver", &gpu_info.driver_version());
+            let _ = soh_log::LOGGER.set_header_entry("vulkan", &api_version);
+        }
+
         {
             soh_log::log_info!("Choose GPU {}", selected_device.0);
             soh_log::log_debug!("GPU Info: \"{:#?}\"", gpu_info);
@@ -154,6 +172,49 @@ impl Device {
         );
     }
 
+    /// Device extensions which are required for the physical device to be suitable
+    pub fn required_extensions() -> Vec<&'static std::ffi::CStr> {
+        #[allow(unused_mut)]
+        let mut extensions = vec![ash::khr::swapchain::NAME];
+
+        #[cfg(feature = "buffer_device_address")]
+        extensions.push(ash::khr::buffer_device_address::NAME);
+
+        #[cfg(feature = "imageless_framebuffer")]
+        extensions.extend([
+            ash::khr::imageless_framebuffer::NAME,
+            ash::khr::maintenance2::NAME,
+            ash::khr::image_format_list::NAME,
+        ]);
+
+        #[cfg(feature = "timeline_semaphore")]
+        extensions.push(ash::khr::timeline_semaphore::NAME);
+
+        return extensions;
+    }
+
+    /// Whether the physical device supports an optional device extension
+    pub fn supports_extension(&self, name: &std::ffi::CStr) -> bool {
+        let Ok(available_extensions) = (unsafe {
+            self.instance
+                .enumerate_device_extension_properties(self.physical_device)
+        }) else {
+            return false;
+        };
+
+        return available_extensions
+            .iter()
+            .any(|ext| ext.extension_name_as_c_str() == Ok(name));
+    }
+
+    /// Query which features the physical device supports for a given format
+    pub fn format_properties(&self, format: vk::Format) -> vk::FormatProperties {
+        return unsafe {
+            self.instance
+                .get_physical_device_format_properties(self.physical_device, format)
+        };
+    }
+
     /// Find the index for the physical device memory type that supports the given properties
     ///
     /// * `type_filter`: the vk::MemoryRequirements::memory_type_bits field
@@ -186,13 +247,11 @@ impl Device {
             instance: &crate::Instance,
             physical_device: vk::PhysicalDevice,
         ) -> bool {
-            const DEVICE_EXTENSIONS: &[&std::ffi::CStr] = &[ash::khr::swapchain::NAME];
-
             let available_extensions =
                 unsafe { instance.enumerate_device_extension_properties(physical_device) }
                     .expect("Failed to enumerate device extension properties");
 
-            for &required_extension_name in DEVICE_EXTENSIONS.iter() {
+            for &required_extension_name in Device::required_extensions().iter() {
                 let mut found = false;
 
                 for available_extension in available_extensions.iter() {
@@ -237,6 +296,31 @@ impl Device {
 //-----------------------------------------------------------------------------
 
 impl PhysicalDeviceInfo {
+    /// Driver version in the vendor's format
+    pub fn driver_version(&self) -> String {
+        const NVIDIA: u32 = 0x10de;
+
+        let version = self.device_props.driver_version;
+
+        if self.device_props.vendor_id == NVIDIA {
+            return format!(
+                "{}.{}.{}.{}",
+                version >> 22,
+                (version >> 14) & 0xff,
+                (version >> 6) & 0xff,
+                version & 0x3f
+            );
+        }
+
+        // Most of the other vendors use the Vulkan version encoding
+        return format!(
+            "{}.{}.{}",
+            vk::api_version_major(version),
+            vk::api_version_minor(version),
+            vk::api_version_patch(version)
+        );
+    }
+
     fn query_info(
         instance: &crate::Instance,
         physical_device: vk::PhysicalDevice,
@@ -330,7 +414,11 @@ impl PhysicalDeviceInfo {
          * Iterate over queues and find the appropriate queue indices
          */
         for (i, qf) in queue_families.iter().enumerate() {
-            if qf.queue_flags.intersects(vk::QueueFlags::GRAPHICS) {
+            // Compute work ( particles ) is recorded together with the graphics
+            if qf
+                .queue_flags
+                .contains(vk::QueueFlags::GRAPHICS | vk::QueueFlags::COMPUTE)
+            {
                 res.graphics_family = Some(i as u32);
             } else if qf.queue_flags.intersects(vk::QueueFlags::TRANSFER) {
                 res.transfer_family = Some(i as u32);
@@ -351,8 +439,9 @@ impl PhysicalDeviceInfo {
             }
         }
 
-        anyhow::ensure!(
+        ensure!(
             res.is_complete(),
+            Unexpected,
             "The queue family indices are not complete!"
         );
 
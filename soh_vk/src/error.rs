@@ -0,0 +1,83 @@
+//-----------------------------------------------------------------------------
+//! Error type of the library
+//-----------------------------------------------------------------------------
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    /// A Vulkan call returned an error code
+    #[error("Vulkan error: {0}")]
+    Vulkan(#[from] ash::vk::Result),
+
+    /// The Vulkan library couldn't be loaded
+    #[error("Failed to load Vulkan: {0}")]
+    Loading(#[from] ash::LoadingError),
+
+    #[error("Shader compilation failed: {0}")]
+    Shader(#[from] shaderc::Error),
+
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+
+    /// The window doesn't provide its handles
+    #[error("Window handle error: {0}")]
+    Window(#[from] winit::raw_window_handle::HandleError),
+
+    /// A name returned by Vulkan isn't a valid C string
+    #[error("Invalid C string: {0}")]
+    CStr(#[from] std::ffi::FromBytesUntilNulError),
+
+    #[cfg(feature = "renderdoc")]
+    #[error("Failed to load a library: {0}")]
+    Library(#[from] libloading::Error),
+
+    #[cfg(feature = "gltf")]
+    #[error("Failed to load a glTF file: {0}")]
+    Gltf(#[from] gltf::Error),
+
+    /// The hardware, driver or platform lacks something (a memory type, an extension, a window
+    /// system)
+    #[error("{0}")]
+    Unsupported(String),
+
+    /// The object can't be used like that (e.g. writing to unmapped memory)
+    #[error("{0}")]
+    InvalidUsage(String),
+
+    /// The driver returned something it shouldn't have
+    #[error("{0}")]
+    Unexpected(String),
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+impl Error {
+    /// Whether the GPU device was lost ( driver crash, GPU reset, removed
+    /// GPU ), see [crate::VulkanContext::recover_device_lost]
+    pub fn is_device_lost(&self) -> bool {
+        return matches!(self, Error::Vulkan(ash::vk::Result::ERROR_DEVICE_LOST));
+    }
+}
+
+//-----------------------------------------------------------------------------
+// Like the anyhow macros, with the kind of the error first
+
+/// Return an error of the kind with a formatted message
+macro_rules! bail {
+    ($kind:ident, $($arg:tt)*) => {
+        return Err($crate::Error::$kind(format!($($arg)*)))
+    };
+}
+
+/// Return an error of the kind with a formatted message if the condition is false
+macro_rules! ensure {
+    ($cond:expr, $kind:ident, $($arg:tt)*) => {
+        if !$cond {
+            $crate::error::bail!($kind, $($arg)*);
+        }
+    };
+}
+
+pub(crate) use bail;
+pub(crate) use ensure;
+
+//-----------------------------------------------------------------------------
@@ -0,0 +1,201 @@
+//-----------------------------------------------------------------------------
+use crate::error::bail;
+use crate::Result;
+use std::sync::mpsc;
+//-----------------------------------------------------------------------------
+
+/// Records the commands of a frame on the render thread
+pub type RecordFn = Box<dyn FnOnce(crate::PerFrameData<'_>) -> Result<()> + Send>;
+
+/// Something that happened on the render thread, see
+/// [FrameSubmitter::poll_events]
+#[derive(Debug)]
+pub enum FrameEvent {
+    /// The swapchain has to be recreated, call [FrameSubmitter::resize] with
+    /// the current size of the window
+    SwapchainOutOfDate,
+    /// The device was lost and recreated, the frame was skipped. The recreate
+    /// callbacks of the context have been called
+    DeviceRecovered,
+    /// Rendering a frame failed, the frame was skipped
+    Error(crate::Error),
+}
+
+enum Request {
+    Frame(RecordFn),
+    Resize((u32, u32)),
+}
+
+/// Runs acquire, record, submit and present of the frames on a separate render
+/// thread, so that the game thread isn't limited by the render rate.
+///
+/// The game thread sends a closure recording each frame, it runs on the render
+/// thread with the [crate::PerFrameData] of that frame. At most
+/// [crate::VulkanContext::num_of_frames_in_flight] frames are queued:
+/// [FrameSubmitter::submit] waits for a free slot, [FrameSubmitter::try_submit]
+/// drops the frame instead.
+///
+/// Everything the closures use has to be `Send` ( resources shared with the
+/// game thread are kept in an `Arc` ).
+pub struct FrameSubmitter {
+    requests: Option<mpsc::SyncSender<Request>>,
+    events: mpsc::Receiver<FrameEvent>,
+
+    thread: Option<std::thread::JoinHandle<crate::VulkanContext>>,
+}
+
+//-----------------------------------------------------------------------------
+// Constructor
+impl FrameSubmitter {
+    /// Moves the `context` to a new render thread
+    pub fn new(context: crate::VulkanContext) -> Result<Self> {
+        let (request_sender, request_receiver) =
+            mpsc::sync_channel(context.num_of_frames_in_flight());
+        let (event_sender, event_receiver) = mpsc::channel();
+
+        let thread = std::thread::Builder::new()
+            .name("soh_vk render".to_owned())
+            .spawn(move || Self::render_thread(context, request_receiver, event_sender))?;
+
+        return Ok(FrameSubmitter {
+            requests: Some(request_sender),
+            events: event_receiver,
+            thread: Some(thread),
+        });
+    }
+}
+
+//-----------------------------------------------------------------------------
+// Specific implementation
+impl FrameSubmitter {
+    /// Queues a frame, waits if there are too many frames queued already
+    pub fn submit<F>(&self, record: F) -> Result<()>
+    where
+        F: FnOnce(crate::PerFrameData<'_>) -> Result<()> + Send + 'static,
+    {
+        return self.send(Request::Frame(Box::new(record)));
+    }
+
+    /// Queues a frame if there is room for it. Returns false if the frame was
+    /// dropped because the render thread is behind.
+    pub fn try_submit<F>(&self, record: F) -> Result<bool>
+    where
+        F: FnOnce(crate::PerFrameData<'_>) -> Result<()> + Send + 'static,
+    {
+        let Some(requests) = &self.requests else {
+            bail!(InvalidUsage, "Render thread has stopped");
+        };
+
+        return match requests.try_send(Request::Frame(Box::new(record))) {
+            Ok(()) => Ok(true),
+            Err(mpsc::TrySendError::Full(_)) => Ok(false),
+            Err(mpsc::TrySendError::Disconnected(_)) => {
+                bail!(Unexpected, "Render thread has stopped")
+            }
+        };
+    }
+
+    /// Recreates the swapchain for the `window_size` after the queued frames
+    pub fn resize(&self, window_size: (u32, u32)) -> Result<()> {
+        return self.send(Request::Resize(window_size));
+    }
+
+    /// Events which happened on the render thread since the last call
+    pub fn poll_events(&self) -> Vec<FrameEvent> {
+        return self.events.try_iter().collect();
+    }
+
+    /// Renders the queued frames, stops the render thread and returns the
+    /// context
+    pub fn shutdown(mut self) -> Result<crate::VulkanContext> {
+        return self.join();
+    }
+
+    fn send(&self, request: Request) -> Result<()> {
+        let Some(requests) = &self.requests else {
+            bail!(InvalidUsage, "Render thread has stopped");
+        };
+
+        if requests.send(request).is_err() {
+            bail!(Unexpected, "Render thread has stopped");
+        }
+
+        return Ok(());
+    }
+
+    fn join(&mut self) -> Result<crate::VulkanContext> {
+        // Closing the channel ends the loop of the render thread
+        self.requests = None;
+
+        let Some(thread) = self.thread.take() else {
+            bail!(InvalidUsage, "Render thread has been joined already");
+        };
+
+        return match thread.join() {
+            Ok(context) => Ok(context),
+            Err(_) => bail!(Unexpected, "Render thread panicked"),
+        };
+    }
+
+    fn render_thread(
+        mut context: crate::VulkanContext,
+        requests: mpsc::Receiver<Request>,
+        events: mpsc::Sender<FrameEvent>,
+    ) -> crate::VulkanContext {
+        let mut frame_num = 0;
+
+        for request in requests {
+            let res = match request {
+                Request::Frame(record) => {
+                    context.on_frame(frame_num, record).map(|should_recreate| {
+                        frame_num += 1;
+
+                        if should_recreate {
+                            // The game thread may be gone already
+                            let _ = events.send(FrameEvent::SwapchainOutOfDate);
+                        }
+                    })
+                }
+                Request::Resize(window_size) => context.on_window_resize(window_size),
+            };
+
+            let Err(err) = res else {
+                continue;
+            };
+
+            /*
+             * The device can be recreated right away, the other errors are
+             * left to the game thread
+             */
+            let event = if err.is_device_lost() {
+                context
+                    .recover_device_lost()
+                    .map_or_else(FrameEvent::Error, |_| FrameEvent::DeviceRecovered)
+            } else {
+                FrameEvent::Error(err)
+            };
+
+            if let FrameEvent::Error(err) = &event {
+                soh_log::log_error!("Render thread: {}", err);
+            }
+
+            let _ = events.send(event);
+        }
+
+        context.device().wait_idle();
+
+        return context;
+    }
+}
+
+//-----------------------------------------------------------------------------
+// Drop
+impl Drop for FrameSubmitter {
+    fn drop(&mut self) {
+        if self.thread.is_some() {
+            let _ = self.join();
+        }
+    }
+}
+
+//-----------------------------------------------------------------------------
@@ -1,6 +1,7 @@
 //-----------------------------------------------------------------------------
-use anyhow::Result;
+use crate::{Result, ToVk};
 use ash::vk;
+use soh_math::{Rect, Vec2, Viewport};
 //-----------------------------------------------------------------------------
 
 pub struct Framebuffer {
@@ -10,6 +11,17 @@ pub struct Framebuffer {
 
     image_views: Vec<vk::ImageView>,
     framebuffer: vk::Framebuffer,
+    is_imageless: bool,
+}
+
+//-----------------------------------------------------------------------------
+/// Description of an attachment of an image-less framebuffer, the views
+/// passed when beginning the render pass have to match it
+#[cfg(feature = "imageless_framebuffer")]
+#[derive(Debug, Clone, Copy)]
+pub struct ImagelessAttachment {
+    pub format: crate::Format,
+    pub usage: vk::ImageUsageFlags,
 }
 
 //-----------------------------------------------------------------------------
@@ -18,6 +30,11 @@ impl Framebuffer {
     pub fn extent(&self) -> vk::Extent2D {
         return self.extent;
     }
+    /// Whether the image views are given when beginning the render pass, see
+    /// [cmd::Buffer::begin_render_pass_imageless](crate::cmd::Buffer::begin_render_pass_imageless)
+    pub fn is_imageless(&self) -> bool {
+        return self.is_imageless;
+    }
 }
 
 //-----------------------------------------------------------------------------
@@ -46,12 +63,14 @@ impl Framebuffer {
                 create_info = create_info.attachments(std::slice::from_ref(image_view));
 
                 let framebuffer = unsafe { device.create_framebuffer(&create_info, None).unwrap() };
+                crate::debug::leaks::track("Framebuffer", framebuffer);
 
                 return Framebuffer {
                     device: device.clone(),
                     extent,
                     image_views: vec![*image_view],
                     framebuffer,
+                    is_imageless: false,
                 };
             })
             .collect::<Vec<_>>();
@@ -59,8 +78,91 @@ impl Framebuffer {
         return Ok(framebuffers);
     }
 
+    /// Creates a framebuffer from the image views of the attachments. The
+    /// framebuffer takes the ownership of the views, they are destroyed by
+    /// [Framebuffer::destroy]
+    pub fn new_from_views(
+        device: &crate::DeviceRef,
+        render_pass: &crate::RenderPass,
+        image_views: Vec<vk::ImageView>,
+        extent: vk::Extent2D,
+    ) -> Result<Self> {
+        let create_info = vk::FramebufferCreateInfo::default()
+            .render_pass(**render_pass)
+            .attachments(&image_views)
+            .width(extent.width)
+            .height(extent.height)
+            .layers(1);
+
+        let framebuffer = unsafe { device.create_framebuffer(&create_info, None)? };
+        crate::debug::leaks::track("Framebuffer", framebuffer);
+
+        return Ok(Framebuffer {
+            device: device.clone(),
+            extent,
+            image_views,
+            framebuffer,
+            is_imageless: false,
+        });
+    }
+
+    /// Creates a framebuffer without image views, only the formats and the
+    /// usages of the attachments are fixed. The views are given every time
+    /// the render pass begins, so one framebuffer can be used with all of the
+    /// swapchain images and doesn't depend on the lifetime of the images.
+    #[cfg(feature = "imageless_framebuffer")]
+    pub fn new_imageless(
+        device: &crate::DeviceRef,
+        render_pass: &crate::RenderPass,
+        attachments: &[ImagelessAttachment],
+        extent: vk::Extent2D,
+    ) -> Result<Self> {
+        let formats = attachments
+            .iter()
+            .map(|attachment| attachment.format)
+            .collect::<Vec<_>>();
+
+        let image_infos = attachments
+            .iter()
+            .zip(formats.iter())
+            .map(|(attachment, format)| {
+                return vk::FramebufferAttachmentImageInfo::default()
+                    .usage(attachment.usage)
+                    .width(extent.width)
+                    .height(extent.height)
+                    .layer_count(1)
+                    .view_formats(std::slice::from_ref(format));
+            })
+            .collect::<Vec<_>>();
+
+        let mut attachments_info =
+            vk::FramebufferAttachmentsCreateInfo::default().attachment_image_infos(&image_infos);
+
+        let mut create_info = vk::FramebufferCreateInfo::default()
+            .flags(vk::FramebufferCreateFlags::IMAGELESS)
+            .render_pass(**render_pass)
+            .width(extent.width)
+            .height(extent.height)
+            .layers(1)
+            .push_next(&mut attachments_info);
+        // The count is taken from the attachments, not from the views
+        create_info.attachment_count = image_infos.len() as u32;
+
+        let framebuffer = unsafe { device.create_framebuffer(&create_info, None)? };
+        crate::debug::leaks::track("Framebuffer", framebuffer);
+
+        return Ok(Framebuffer {
+            device: device.clone(),
+            extent,
+            image_views: Vec::new(),
+            framebuffer,
+            is_imageless: true,
+        });
+    }
+
     pub fn destroy(&self) {
         // soh_log::log_debug!("Destroying framebuffer (0x{:x})", self.framebuffer.as_raw());
+        crate::debug::leaks::untrack("Framebuffer", self.framebuffer);
         unsafe {
             self.device.destroy_framebuffer(self.framebuffer, None);
 
@@ -74,22 +176,27 @@ impl Framebuffer {
 //-----------------------------------------------------------------------------
 // Specific implementation
 impl Framebuffer {
+    /// Rect covering the whole framebuffer
+    pub fn rect(&self) -> Rect<u32> {
+        return Rect::new(
+            Vec2::zero(),
+            Vec2::new(self.extent.width, self.extent.height),
+        );
+    }
+
     pub fn get_viewport_scissor(&self) -> (vk::Viewport, vk::Rect2D) {
-        let viewport = vk::Viewport {
-            x: 0.0,
-            y: 0.0,
-            width: self.extent.width as f32,
-            height: self.extent.height as f32,
-            min_depth: 0.0,
-            max_depth: 1.0,
-        };
-
-        let scissor = vk::Rect2D {
-            offset: vk::Offset2D { x: 0, y: 0 },
-            extent: self.extent,
-        };
-
-        return (viewport, scissor);
+        let rect = self.rect();
+        let viewport = Viewport::new(Rect::new(
+            Vec2::zero(),
+            Vec2::new(rect.max.x as f32, rect.max.y as f32),
+        ));
+
+        return (viewport.to_vk(), rect.to_vk());
+    }
+
+    /// Splits the framebuffer between the players, see [split_screen]
+    pub fn split_screen(&self, num_of_players: u32) -> Vec<Rect<u32>> {
+        return split_screen(self.rect(), num_of_players);
     }
 
     fn create_image_views(
@@ -125,6 +232,49 @@ impl Framebuffer {
     }
 }
 
+//-----------------------------------------------------------------------------
+/// Splits the `area` into a rect for every player.
+///
+/// The players are laid out in a grid with as many columns as rows ( or one
+/// column more ), row by row. The last row may have fewer players, they share
+/// its whole width. Neighbouring rects share edges, so there are no gaps.
+///
+/// * 1 player: the whole area
+/// * 2 players: side by side
+/// * 3 players: two on top, one at the bottom
+/// * 4 players: 2x2
+pub fn split_screen(area: Rect<u32>, num_of_players: u32) -> Vec<Rect<u32>> {
+    if num_of_players == 0 {
+        return Vec::new();
+    }
+
+    let cols = (1..).find(|cols| cols * cols >= num_of_players).unwrap();
+    let rows = num_of_players.div_ceil(cols);
+
+    // Position of the `idx`-th of the `count` edges along the range
+    let edge = |min: u32, max: u32, idx: u32, count: u32| {
+        return min + ((max - min) as u64 * idx as u64 / count as u64) as u32;
+    };
+
+    let mut res = Vec::with_capacity(num_of_players as usize);
+
+    for row in 0..rows {
+        let cols_in_row = cols.min(num_of_players - row * cols);
+
+        let min_y = edge(area.min.y, area.max.y, row, rows);
+        let max_y = edge(area.min.y, area.max.y, row + 1, rows);
+
+        for col in 0..cols_in_row {
+            let min_x = edge(area.min.x, area.max.x, col, cols_in_row);
+            let max_x = edge(area.min.x, area.max.x, col + 1, cols_in_row);
+
+            res.push(Rect::new(Vec2::new(min_x, min_y), Vec2::new(max_x, max_y)));
+        }
+    }
+
+    return res;
+}
+
 //-----------------------------------------------------------------------------
 // Deref
 impl std::ops::Deref for Framebuffer {
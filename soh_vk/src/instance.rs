@@ -1,5 +1,6 @@
 //-----------------------------------------------------------------------------
-use anyhow::Result;
+use crate::error::ensure;
+use crate::Result;
 use ash::vk;
 use std::ffi::CStr;
 //-----------------------------------------------------------------------------
@@ -15,7 +16,7 @@ pub struct Instance {
 
 //-----------------------------------------------------------------------------
 /// Instance reference stored inside the dependant types (which is the logical device mainly)
-pub type InstanceRef = std::rc::Rc<Instance>;
+pub type InstanceRef = std::sync::Arc<Instance>;
 //-----------------------------------------------------------------------------
 // Getters
 impl Instance {
@@ -53,7 +54,7 @@ impl Instance {
         /*
          * Get the required extensions and layers
          */
-        let required_extensions = Self::get_extensions(surface_platform);
+        let mut required_extensions = Self::get_extensions(surface_platform);
         let required_layers = Self::get_validation_layers(&entry)?;
 
         // Log stuff
@@ -86,7 +87,21 @@ impl Instance {
                 }
             }
 
-            anyhow::ensure!(found, "Extension {:?} not supported!", r_name);
+            ensure!(found, Unsupported, "Extension {:?} not supported!", r_name);
+        }
+
+        /*
+         * Enable optional extensions if they are supported
+         */
+        for &o_name in Self::get_optional_extensions().iter() {
+            let supported = supported_extensions
+                .iter()
+                .any(|supported_ext| supported_ext.extension_name_as_c_str() == Ok(o_name));
+
+            if supported {
+                soh_log::log_info!("Enabling optional extension {:?}", o_name);
+                required_extensions.push(o_name);
+            }
         }
 
         /*
@@ -153,6 +168,17 @@ impl Instance {
             }
         }
 
+        /*
+         * VK_KHR_buffer_device_address, VK_KHR_imageless_framebuffer and
+         * VK_KHR_timeline_semaphore depend on VK_KHR_get_physical_device_properties2
+         */
+        if cfg!(feature = "buffer_device_address")
+            || cfg!(feature = "imageless_framebuffer")
+            || cfg!(feature = "timeline_semaphore")
+        {
+            extensions.push(ash::khr::get_physical_device_properties2::NAME);
+        }
+
         /*
          * Require validation layer extension
          */
@@ -163,6 +189,13 @@ impl Instance {
         return extensions;
     }
 
+    fn get_optional_extensions() -> Vec<&'static CStr> {
+        /*
+         * Needed for HDR and wide gamut swapchain color spaces
+         */
+        return vec![ash::ext::swapchain_colorspace::NAME];
+    }
+
     fn get_validation_layers(entry: &ash::Entry) -> Result<Vec<&'static CStr>> {
         static REQUIRED_VALIDATION_LAYERS: &[&CStr] = &[c"VK_LAYER_KHRONOS_validation"];
 
@@ -211,6 +244,8 @@ impl Drop for Instance {
     fn drop(&mut self) {
         soh_log::log_info!("Destroying instance");
 
+        crate::debug::leaks::report_leaks();
+
         unsafe { self.instance.destroy_instance(None) };
     }
 }
@@ -2,21 +2,29 @@
 //! Convenient Vulkan wrappers
 //-----------------------------------------------------------------------------
 // Private modules
+mod compute_pipeline;
 mod device;
 mod framebuffer;
 mod instance;
 mod pipeline;
+mod pipeline_registry;
 mod render_pass;
 //-----------------------------------------------------------------------------
 // Public imports
+pub use compute_pipeline::*;
 pub use device::*;
 pub use framebuffer::*;
 pub use instance::*;
 pub use pipeline::*;
+pub use pipeline_registry::*;
 pub use render_pass::*;
 //-----------------------------------------------------------------------------
 
 //-----------------------------------------------------------------------------
+// Error type
+pub mod error;
+pub use error::{Error, Result};
+
 // Typedefs
 pub mod types;
 pub use types::*;
@@ -25,6 +33,10 @@ pub use types::*;
 pub mod context;
 pub use context::*;
 
+// Acquire, record, submit and present on a render thread
+pub mod frame_submitter;
+pub use frame_submitter::FrameSubmitter;
+
 // Shader related structures
 pub mod shader;
 pub use shader::Shader;
@@ -32,6 +44,10 @@ pub use shader::Shader;
 // Debug messenger
 pub mod debug;
 
+// RenderDoc frame captures
+#[cfg(feature = "renderdoc")]
+pub mod renderdoc;
+
 // Window system integration
 pub mod wsi;
 pub use wsi::{Surface, SurfaceRef, Swapchain};
@@ -54,9 +70,50 @@ pub use vertex::Vertex;
 // Index buffer
 pub mod index;
 
+// Vertex and index buffers with submeshes
+pub mod mesh;
+pub use mesh::{Mesh, MeshBuilder, Submesh};
+
+// glTF scene loading
+#[cfg(feature = "gltf")]
+pub mod scene;
+#[cfg(feature = "gltf")]
+pub use scene::Scene;
+
+// Depth-only render target for shadows
+pub mod shadow;
+pub use shadow::ShadowMap;
+
+// Chain of fullscreen post-processing passes
+pub mod post_process;
+pub use post_process::PostProcess;
+
+// Resources for each frame in flight
+pub mod per_frame;
+pub use per_frame::PerFrame;
+
+// Per-frame arena for the geometry that lives only for one frame
+pub mod transient;
+pub use transient::TransientGeometry;
+
+// GPU particles simulated by a compute shader
+pub mod particles;
+pub use particles::ParticleSystem;
+
+// Background uploads on the transfer queue
+#[cfg(feature = "timeline_semaphore")]
+pub mod transfer;
+#[cfg(feature = "timeline_semaphore")]
+pub use transfer::AsyncTransfer;
+
+// Occlusion queries
+pub mod query;
+pub use query::OcclusionQueryPool;
+
 // Descripor stuff
 pub mod descriptor;
 pub use descriptor::uniform;
+pub use soh_vk_derive::DescriptorSet;
 
 //-----------------------------------------------------------------------------
 // Helps to easily get a handle from a Option<&WrapperType>
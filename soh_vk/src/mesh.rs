@@ -0,0 +1,152 @@
+//-----------------------------------------------------------------------------
+use crate::error::ensure;
+use crate::Result;
+//-----------------------------------------------------------------------------
+
+/// Vertex and index buffers with the submeshes that are drawn from them.
+///
+/// Built on the CPU side with [MeshBuilder] and drawn with
+/// [cmd::Buffer::draw_mesh](crate::cmd::Buffer::draw_mesh).
+pub struct Mesh {
+    vertex_buffer: crate::vertex::Buffer,
+    index_buffer: crate::index::Buffer,
+
+    submeshes: Vec<Submesh>,
+}
+
+/// Range of the index buffer drawn with one material
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Submesh {
+    pub first_index: u32,
+    pub num_of_indexes: u32,
+    /// Added to the indexes of the submesh
+    pub vertex_offset: i32,
+    /// Index of the material, its meaning is up to the user
+    pub material: u32,
+}
+
+//-----------------------------------------------------------------------------
+// Builder
+pub struct MeshBuilder<T> {
+    vertices: Vec<T>,
+    indexes: Vec<u32>,
+
+    submeshes: Vec<Submesh>,
+}
+
+impl<T> MeshBuilder<T>
+where
+    T: crate::Vertex,
+{
+    pub fn new() -> Self {
+        return MeshBuilder {
+            vertices: Vec::new(),
+            indexes: Vec::new(),
+            submeshes: Vec::new(),
+        };
+    }
+
+    /// Adds a submesh. The `indexes` point into its own `vertices`
+    pub fn submesh(mut self, vertices: &[T], indexes: &[u32], material: u32) -> Self {
+        self.submeshes.push(Submesh {
+            first_index: self.indexes.len() as u32,
+            num_of_indexes: indexes.len() as u32,
+            vertex_offset: self.vertices.len() as i32,
+            material,
+        });
+
+        self.vertices.extend_from_slice(vertices);
+        self.indexes.extend_from_slice(indexes);
+
+        return self;
+    }
+
+    pub fn vertices(&self) -> &[T] {
+        return &self.vertices;
+    }
+    pub fn indexes(&self) -> &[u32] {
+        return &self.indexes;
+    }
+    pub fn submeshes(&self) -> &[Submesh] {
+        return &self.submeshes;
+    }
+
+    /// Uploads the mesh to the GPU. The indexes are stored as `u16` when all
+    /// of them fit.
+    pub fn build(self, context: &crate::VulkanContext) -> Result<Mesh> {
+        ensure!(
+            !self.vertices.is_empty() && !self.indexes.is_empty(),
+            InvalidUsage,
+            "Trying to build an empty mesh"
+        );
+
+        let vertex_buffer = crate::vertex::Buffer::new(context, &self.vertices)?;
+
+        let index_buffer = if self.indexes.iter().all(|&i| i <= u16::MAX as u32) {
+            let indexes = self.indexes.iter().map(|&i| i as u16).collect::<Vec<_>>();
+            crate::index::Buffer::new_u16(context, &indexes)?
+        } else {
+            crate::index::Buffer::new_u32(context, &self.indexes)?
+        };
+
+        return Ok(Mesh {
+            vertex_buffer,
+            index_buffer,
+            submeshes: self.submeshes,
+        });
+    }
+}
+
+impl<T> Default for MeshBuilder<T>
+where
+    T: crate::Vertex,
+{
+    fn default() -> Self {
+        return Self::new();
+    }
+}
+
+//-----------------------------------------------------------------------------
+// Getters
+impl Mesh {
+    pub fn vertex_buffer(&self) -> &crate::vertex::Buffer {
+        return &self.vertex_buffer;
+    }
+    pub fn index_buffer(&self) -> &crate::index::Buffer {
+        return &self.index_buffer;
+    }
+    pub fn submeshes(&self) -> &[Submesh] {
+        return &self.submeshes;
+    }
+}
+
+//-----------------------------------------------------------------------------
+// Constructor
+impl Mesh {
+    /// Creates a mesh from existing buffers. Without `submeshes` the whole index
+    /// buffer is one submesh with material 0.
+    pub fn new(
+        vertex_buffer: crate::vertex::Buffer,
+        index_buffer: crate::index::Buffer,
+        submeshes: Vec<Submesh>,
+    ) -> Self {
+        let submeshes = if submeshes.is_empty() {
+            vec![Submesh {
+                first_index: 0,
+                num_of_indexes: index_buffer.num_of_indexes() as u32,
+                vertex_offset: 0,
+                material: 0,
+            }]
+        } else {
+            submeshes
+        };
+
+        return Mesh {
+            vertex_buffer,
+            index_buffer,
+            submeshes,
+        };
+    }
+}
+
+//-----------------------------------------------------------------------------
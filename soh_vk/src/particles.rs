@@ -0,0 +1,453 @@
+//-----------------------------------------------------------------------------
+use crate::error::ensure;
+use crate::Result;
+use ash::vk;
+use soh_math::{Vec3, Vec4};
+//-----------------------------------------------------------------------------
+/// Local size of the simulation shader
+const WORK_GROUP_SIZE: u32 = 64;
+
+/// Every particle is drawn as a quad of two triangles
+const VERTICES_PER_PARTICLE: u32 = 6;
+
+/// Simulates the particles of the input state and writes the survivors and
+/// the spawned particles to the output state, counting them in the indirect
+/// draw command of the output.
+const SIMULATE_COMP: &str = r#"
+#version 450
+
+layout(local_size_x = 64) in;
+
+struct Particle {
+    vec4 position;
+    vec4 velocity;
+    vec4 color;
+};
+
+layout(std430, set = 0, binding = 0) readonly buffer InParticles {
+    Particle in_particles[];
+};
+layout(std430, set = 0, binding = 1) readonly buffer InCommand {
+    uint vertex_count;
+    uint instance_count;
+    uint first_vertex;
+    uint first_instance;
+} in_cmd;
+layout(std430, set = 0, binding = 2) writeonly buffer OutParticles {
+    Particle out_particles[];
+};
+layout(std430, set = 0, binding = 3) buffer OutCommand {
+    uint vertex_count;
+    uint instance_count;
+    uint first_vertex;
+    uint first_instance;
+} out_cmd;
+
+layout(std430, set = 1, binding = 0) readonly buffer Spawned {
+    Particle spawned[];
+};
+
+layout(push_constant) uniform Params {
+    vec4 gravity_dt;
+    uint spawn_count;
+    uint max_particles;
+} params;
+
+void main() {
+    uint idx = gl_GlobalInvocationID.x;
+    uint num_alive = in_cmd.instance_count;
+
+    Particle p;
+    if (idx < num_alive) {
+        float dt = params.gravity_dt.w;
+
+        p = in_particles[idx];
+        p.velocity.xyz += params.gravity_dt.xyz * dt;
+        p.position.xyz += p.velocity.xyz * dt;
+        p.position.w -= dt;
+    } else if (idx - num_alive < params.spawn_count) {
+        p = spawned[idx - num_alive];
+    } else {
+        return;
+    }
+
+    if (p.position.w <= 0.0) {
+        return;
+    }
+
+    uint dst = atomicAdd(out_cmd.instance_count, 1);
+    if (dst >= params.max_particles) {
+        atomicAdd(out_cmd.instance_count, uint(-1));
+        return;
+    }
+
+    out_particles[dst] = p;
+}
+"#;
+//-----------------------------------------------------------------------------
+
+/// A particle as it is stored on the GPU
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Particle {
+    /// `w` is the remaining life in seconds, the particle dies at 0
+    pub position: Vec4<f32>,
+    /// `w` is the size of the quad
+    pub velocity: Vec4<f32>,
+    pub color: Vec4<f32>,
+}
+
+/// GPU particle system: the simulation runs in a compute shader over a
+/// storage buffer and the particles are drawn as instanced quads with an
+/// indirect draw, so the CPU never reads the number of alive particles.
+///
+/// The state is double-buffered, every [ParticleSystem::update] reads one
+/// state and writes the other. New particles are queued with
+/// [ParticleSystem::emit] and uploaded on the next update.
+///
+/// The pipeline drawing the particles is created by the user with
+/// [ParticleSystem::vertex_description] as the vertex input. The attributes
+/// of [Particle] are per instance ( locations 0, 1, 2 ) and the vertex shader
+/// gets 6 vertices per instance, which it has to turn into a quad from
+/// `gl_VertexIndex`.
+pub struct ParticleSystem {
+    max_particles: u32,
+    max_spawn_per_frame: u32,
+
+    pipeline: crate::ComputePipeline,
+    _set_layouts: [crate::descriptor::SetLayout; 2],
+    // Keeps the sets alive
+    _descriptor_pool: crate::descriptor::Pool,
+    // Set `i` reads the state `i` and writes the other one
+    state_sets: [crate::descriptor::Set; 2],
+    spawn_sets: crate::PerFrame<crate::descriptor::Set>,
+
+    states: [ParticleState; 2],
+    // State written by the last update
+    current: usize,
+
+    spawn_buffers: crate::PerFrame<crate::Buffer>,
+    pending: Vec<Particle>,
+}
+
+struct ParticleState {
+    particles: crate::Buffer,
+    // Single `vk::DrawIndirectCommand`
+    command: crate::Buffer,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy)]
+struct PushConstants {
+    gravity_dt: Vec4<f32>,
+    spawn_count: u32,
+    max_particles: u32,
+}
+
+//-----------------------------------------------------------------------------
+// Vertex
+impl crate::Vertex for Particle {
+    fn get_attribute_description() -> Vec<crate::vertex::AttributeDescription> {
+        return vec![
+            crate::vertex::AttributeDescription {
+                location: 0,
+                format: crate::Format::R32G32B32A32_SFLOAT,
+                offset: std::mem::offset_of!(Self, position) as u32,
+            },
+            crate::vertex::AttributeDescription {
+                location: 1,
+                format: crate::Format::R32G32B32A32_SFLOAT,
+                offset: std::mem::offset_of!(Self, velocity) as u32,
+            },
+            crate::vertex::AttributeDescription {
+                location: 2,
+                format: crate::Format::R32G32B32A32_SFLOAT,
+                offset: std::mem::offset_of!(Self, color) as u32,
+            },
+        ];
+    }
+}
+
+//-----------------------------------------------------------------------------
+// Getters
+impl ParticleSystem {
+    pub fn max_particles(&self) -> u32 {
+        return self.max_particles;
+    }
+    pub fn max_spawn_per_frame(&self) -> u32 {
+        return self.max_spawn_per_frame;
+    }
+    /// Number of particles waiting for the next update
+    pub fn num_of_pending(&self) -> usize {
+        return self.pending.len();
+    }
+    /// Vertex input of the pipeline drawing the particles
+    pub fn vertex_description() -> crate::vertex::VertexDescription {
+        return <Particle as crate::Vertex>::get_vertex_description().per_instance();
+    }
+}
+
+//-----------------------------------------------------------------------------
+// Constructor
+impl ParticleSystem {
+    /// Creates the system for up to `max_particles` alive particles, of which
+    /// up to `max_spawn_per_frame` can be spawned by a single update.
+    pub fn new(
+        context: &crate::VulkanContext,
+        max_particles: u32,
+        max_spawn_per_frame: u32,
+    ) -> Result<Self> {
+        ensure!(
+            max_particles > 0 && max_spawn_per_frame > 0,
+            InvalidUsage,
+            "Particle system needs room for at least one particle"
+        );
+
+        let device = context.device();
+        let num_of_frames = context.num_of_frames_in_flight();
+
+        /*
+         * Buffers
+         */
+        // Both pools may only be used from the main thread
+        let cmd_pool = unsafe { context.cmd_pool_graphics() };
+
+        let states = [
+            ParticleState::new(device, cmd_pool, max_particles)?,
+            ParticleState::new(device, cmd_pool, max_particles)?,
+        ];
+
+        let spawn_buffers = crate::PerFrame::try_new(num_of_frames, |_| {
+            let mut buffer = crate::Buffer::new(
+                device,
+                (max_spawn_per_frame as usize * size_of::<Particle>()) as u64,
+                crate::BufferUsageFlags::STORAGE_BUFFER,
+                crate::MemoryPropertyFlags::HOST_VISIBLE
+                    | crate::MemoryPropertyFlags::HOST_COHERENT,
+            )?;
+
+            // Stays mapped for the whole lifetime of the buffer
+            buffer.memory_mut().map()?;
+
+            return Ok(buffer);
+        })?;
+
+        /*
+         * Descriptor sets
+         */
+        let storage_binding = |binding_num| crate::descriptor::SetLayoutBinding {
+            binding_num,
+            descriptor_type: vk::DescriptorType::STORAGE_BUFFER,
+            count: 1,
+            state_flags: vk::ShaderStageFlags::COMPUTE,
+        };
+
+        let set_layouts = [
+            crate::descriptor::SetLayout::new(
+                device,
+                &[
+                    storage_binding(0),
+                    storage_binding(1),
+                    storage_binding(2),
+                    storage_binding(3),
+                ],
+            )?,
+            crate::descriptor::SetLayout::new(device, &[storage_binding(0)])?,
+        ];
+
+        let descriptor_pool = crate::descriptor::PoolBuilder::new()
+            .max_num_of_sets(2 + num_of_frames as u32)
+            .storage_descriptor_count(8 + num_of_frames as u32)
+            .build(device)?;
+
+        let mut state_sets = [
+            descriptor_pool.allocate_set(&set_layouts[0])?,
+            descriptor_pool.allocate_set(&set_layouts[0])?,
+        ];
+
+        for (idx, set) in state_sets.iter_mut().enumerate() {
+            let src = &states[idx];
+            let dst = &states[1 - idx];
+
+            set.update_storage_buffers(&[
+                (0, &[&src.particles]),
+                (1, &[&src.command]),
+                (2, &[&dst.particles]),
+                (3, &[&dst.command]),
+            ]);
+        }
+
+        let spawn_sets = crate::PerFrame::try_new(num_of_frames, |frame_idx| {
+            let mut set = descriptor_pool.allocate_set(&set_layouts[1])?;
+            set.update_storage_buffers(&[(0, &[&spawn_buffers[frame_idx]])]);
+
+            return Ok(set);
+        })?;
+
+        /*
+         * Pipeline
+         */
+        let shader = crate::Shader::from_glsl(
+            device,
+            context.shader_manager(),
+            SIMULATE_COMP,
+            shaderc::ShaderKind::Compute,
+            "particles.comp",
+        )?;
+
+        let pipeline = crate::ComputePipeline::new(
+            device,
+            &[&set_layouts[0], &set_layouts[1]],
+            &shader,
+            size_of::<PushConstants>() as u32,
+        )?;
+
+        return Ok(ParticleSystem {
+            max_particles,
+            max_spawn_per_frame,
+            pipeline,
+            _set_layouts: set_layouts,
+            _descriptor_pool: descriptor_pool,
+            state_sets,
+            spawn_sets,
+            states,
+            current: 0,
+            spawn_buffers,
+            pending: Vec::new(),
+        });
+    }
+}
+
+//-----------------------------------------------------------------------------
+// Specific implementation
+impl ParticleSystem {
+    /// Queues the `particles` to be spawned. At most
+    /// [ParticleSystem::max_spawn_per_frame] of them are spawned by an
+    /// update, the rest waits for the next ones.
+    pub fn emit(&mut self, particles: &[Particle]) {
+        self.pending.extend_from_slice(particles);
+    }
+
+    /// Records the simulation step of `dt` seconds. Has to be recorded
+    /// outside of a render pass, before [ParticleSystem::draw].
+    ///
+    /// `frame_idx` selects the spawn buffer, the fence of that frame must have
+    /// signaled ( see [crate::PerFrameData::frame_idx] ).
+    pub fn update(
+        &mut self,
+        cmd_buffer: &crate::cmd::Buffer,
+        frame_idx: usize,
+        dt: f32,
+        gravity: Vec3<f32>,
+    ) -> Result<()> {
+        /*
+         * Upload the spawned particles
+         */
+        let spawn_count = self.pending.len().min(self.max_spawn_per_frame as usize);
+
+        self.spawn_buffers[frame_idx]
+            .memory_mut()
+            .write_at(0, &self.pending[..spawn_count])?;
+        self.pending.drain(..spawn_count);
+
+        let src = self.current;
+        let dst = 1 - src;
+
+        /*
+         * Reset the output count. The previous frame may still read the output
+         * state as its input or draw from it
+         */
+        cmd_buffer.memory_barrier(
+            vk::PipelineStageFlags::COMPUTE_SHADER
+                | vk::PipelineStageFlags::DRAW_INDIRECT
+                | vk::PipelineStageFlags::VERTEX_INPUT,
+            vk::AccessFlags::empty(),
+            vk::PipelineStageFlags::TRANSFER,
+            vk::AccessFlags::empty(),
+        );
+
+        let command = vk::DrawIndirectCommand {
+            vertex_count: VERTICES_PER_PARTICLE,
+            instance_count: 0,
+            first_vertex: 0,
+            first_instance: 0,
+        };
+        cmd_buffer.update_buffer(&self.states[dst].command, 0, &[command]);
+
+        cmd_buffer.memory_barrier(
+            vk::PipelineStageFlags::TRANSFER | vk::PipelineStageFlags::COMPUTE_SHADER,
+            vk::AccessFlags::TRANSFER_WRITE | vk::AccessFlags::SHADER_WRITE,
+            vk::PipelineStageFlags::COMPUTE_SHADER,
+            vk::AccessFlags::SHADER_READ | vk::AccessFlags::SHADER_WRITE,
+        );
+
+        /*
+         * Simulate, a thread for every possibly alive or spawned particle
+         */
+        let push_constants = PushConstants {
+            gravity_dt: Vec4::new(gravity.x, gravity.y, gravity.z, dt),
+            spawn_count: spawn_count as u32,
+            max_particles: self.max_particles,
+        };
+
+        let num_of_threads = self.max_particles + spawn_count as u32;
+
+        cmd_buffer.bind_compute_pipeline(&self.pipeline);
+        cmd_buffer.bind_compute_descriptor_sets(
+            &self.pipeline,
+            &[&self.state_sets[src], &self.spawn_sets[frame_idx]],
+        );
+        cmd_buffer.push_compute_constants(&self.pipeline, &push_constants);
+        cmd_buffer.dispatch(num_of_threads.div_ceil(WORK_GROUP_SIZE), 1, 1);
+
+        cmd_buffer.memory_barrier(
+            vk::PipelineStageFlags::COMPUTE_SHADER,
+            vk::AccessFlags::SHADER_WRITE,
+            vk::PipelineStageFlags::DRAW_INDIRECT | vk::PipelineStageFlags::VERTEX_INPUT,
+            vk::AccessFlags::INDIRECT_COMMAND_READ | vk::AccessFlags::VERTEX_ATTRIBUTE_READ,
+        );
+
+        self.current = dst;
+
+        return Ok(());
+    }
+
+    /// Draws the alive particles, the particle pipeline has to be bound
+    pub fn draw(&self, cmd_buffer: &crate::cmd::Buffer) {
+        let state = &self.states[self.current];
+
+        cmd_buffer.bind_vertex_buffer_at(0, &state.particles, 0);
+        cmd_buffer.draw_indirect(&state.command, 0, 1);
+    }
+}
+
+impl ParticleState {
+    fn new(
+        device: &crate::DeviceRef,
+        cmd_pool: &crate::cmd::Pool,
+        max_particles: u32,
+    ) -> Result<Self> {
+        let particles = crate::Buffer::new(
+            device,
+            (max_particles as usize * size_of::<Particle>()) as u64,
+            crate::BufferUsageFlags::STORAGE_BUFFER | crate::BufferUsageFlags::VERTEX_BUFFER,
+            crate::MemoryPropertyFlags::DEVICE_LOCAL,
+        )?;
+
+        let command = crate::Buffer::new_staged(
+            device,
+            cmd_pool,
+            &[vk::DrawIndirectCommand {
+                vertex_count: VERTICES_PER_PARTICLE,
+                instance_count: 0,
+                first_vertex: 0,
+                first_instance: 0,
+            }],
+            crate::BufferUsageFlags::STORAGE_BUFFER | crate::BufferUsageFlags::INDIRECT_BUFFER,
+        )?;
+
+        return Ok(ParticleState { particles, command });
+    }
+}
+
+//-----------------------------------------------------------------------------
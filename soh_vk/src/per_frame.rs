@@ -0,0 +1,114 @@
+//-----------------------------------------------------------------------------
+use crate::Result;
+//-----------------------------------------------------------------------------
+
+/// A value for each frame in flight ( uniform buffers, descriptor sets,
+/// arenas ), indexed by [crate::PerFrameData::frame_idx]
+///
+/// ```ignore
+/// let mut uniforms = PerFrame::from_context(&context, |_| {
+///     soh::vk::uniform::Buffer::new(context.device(), size_of::<Camera>() as u64)
+/// })?;
+/// // ...
+/// uniforms.get_mut(frame.frame_idx).write(&[camera])?;
+/// ```
+#[derive(Debug, Clone)]
+pub struct PerFrame<T> {
+    frames: Vec<T>,
+}
+
+//-----------------------------------------------------------------------------
+// Constructor
+impl<T> PerFrame<T> {
+    /// Create the value of each frame with `f( frame_idx )`
+    pub fn new<F>(num_of_frames: usize, f: F) -> Self
+    where
+        F: FnMut(usize) -> T,
+    {
+        return PerFrame {
+            frames: (0..num_of_frames).map(f).collect(),
+        };
+    }
+
+    /// Create the value of each frame with `f( frame_idx )`, stops at the
+    /// first error
+    pub fn try_new<F>(num_of_frames: usize, f: F) -> Result<Self>
+    where
+        F: FnMut(usize) -> Result<T>,
+    {
+        return Ok(PerFrame {
+            frames: (0..num_of_frames).map(f).collect::<Result<_>>()?,
+        });
+    }
+
+    /// Create a value for each frame in flight of the context, like
+    /// [PerFrame::try_new]
+    pub fn from_context<F>(context: &crate::VulkanContext, f: F) -> Result<Self>
+    where
+        F: FnMut(usize) -> Result<T>,
+    {
+        return Self::try_new(context.num_of_frames_in_flight(), f);
+    }
+}
+
+//-----------------------------------------------------------------------------
+// Specific implementation
+impl<T> PerFrame<T> {
+    pub fn num_of_frames(&self) -> usize {
+        return self.frames.len();
+    }
+
+    pub fn get(&self, frame_idx: usize) -> &T {
+        return &self.frames[frame_idx];
+    }
+
+    pub fn get_mut(&mut self, frame_idx: usize) -> &mut T {
+        return &mut self.frames[frame_idx];
+    }
+
+    pub fn iter(&self) -> std::slice::Iter<'_, T> {
+        return self.frames.iter();
+    }
+
+    pub fn iter_mut(&mut self) -> std::slice::IterMut<'_, T> {
+        return self.frames.iter_mut();
+    }
+}
+
+//-----------------------------------------------------------------------------
+// Index
+impl<T> std::ops::Index<usize> for PerFrame<T> {
+    type Output = T;
+
+    fn index(&self, frame_idx: usize) -> &Self::Output {
+        return self.get(frame_idx);
+    }
+}
+
+impl<T> std::ops::IndexMut<usize> for PerFrame<T> {
+    fn index_mut(&mut self, frame_idx: usize) -> &mut Self::Output {
+        return self.get_mut(frame_idx);
+    }
+}
+
+//-----------------------------------------------------------------------------
+// Iterators
+impl<'a, T> IntoIterator for &'a PerFrame<T> {
+    type Item = &'a T;
+    type IntoIter = std::slice::Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        return self.iter();
+    }
+}
+
+impl<'a, T> IntoIterator for &'a mut PerFrame<T> {
+    type Item = &'a mut T;
+    type IntoIter = std::slice::IterMut<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        return self.iter_mut();
+    }
+}
+
+//-----------------------------------------------------------------------------
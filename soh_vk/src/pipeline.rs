@@ -1,5 +1,6 @@
 //-----------------------------------------------------------------------------
-use anyhow::Result;
+use crate::error::ensure;
+use crate::Result;
 use ash::vk;
 //-----------------------------------------------------------------------------
 const DYNAMIC_STATES: &[vk::DynamicState] =
@@ -12,11 +13,151 @@ pub struct Pipeline {
     pipeline: vk::Pipeline,
     pipeline_layout: vk::PipelineLayout,
     blend_mode: BlendMode,
+    preset: PipelinePreset,
+    dynamic_line_width: bool,
+    stencil: Option<StencilState>,
+    allows_derivatives: bool,
+}
+
+/// Description of a pipeline created with [Pipeline::new_batch], the
+/// arguments of [Pipeline::new_with_preset], [Pipeline::new_with_stencil]
+/// and [Pipeline::new_with_stages] together
+#[derive(Clone, Copy)]
+pub struct PipelineInfo<'a> {
+    pub descriptor_set_layouts: &'a [&'a crate::descriptor::SetLayout],
+    pub vertex_descriptions: &'a [crate::vertex::VertexDescription],
+    pub vertex_shader: &'a crate::Shader,
+    pub fragment_shader: &'a crate::Shader,
+    pub blend_mode: BlendMode,
+    pub preset: PipelinePreset,
+    /// Needs a render pass with a stencil attachment
+    pub stencil: Option<StencilState>,
+    pub stages: ExtraStages<'a>,
+}
+
+// How the pipelines of a batch derive from each other
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Derivatives {
+    None,
+    // The first pipeline of the batch is the base
+    FromFirst,
+    // Every pipeline derives from an existing one
+    FromBase(vk::Pipeline),
+}
+
+// State of a pipeline which the create infos point into
+struct PipelineState {
+    shader_stages: Vec<vk::PipelineShaderStageCreateInfo<'static>>,
+    dynamic_states: Vec<vk::DynamicState>,
+    binding_descriptions: Vec<vk::VertexInputBindingDescription>,
+    attribute_descriptions: Vec<vk::VertexInputAttributeDescription>,
+    topology: vk::PrimitiveTopology,
+    // Only with the tessellation
+    patch_control_points: Option<u32>,
+    rasterizer: vk::PipelineRasterizationStateCreateInfo<'static>,
+    color_blend_attachment: vk::PipelineColorBlendAttachmentState,
+    depth_stencil: vk::PipelineDepthStencilStateCreateInfo<'static>,
+    dynamic_line_width: bool,
+}
+
+//-----------------------------------------------------------------------------
+/// Ready-made rasterization setups
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum PipelinePreset {
+    /// Filled triangles
+    #[default]
+    Fill,
+    /// Edges of the triangles
+    Wireframe,
+    /// Every vertex is a point. The vertex shader has to write `gl_PointSize`
+    Points,
+    /// Every pair of vertices is a line of the given width
+    Lines { width: f32 },
+}
+
+//-----------------------------------------------------------------------------
+/// Depth bias of the depth-only pipelines, pushes the depth away from the
+/// light to avoid shadow acne
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct DepthBias {
+    /// Constant depth added to each fragment
+    pub constant_factor: f32,
+    /// Added depth scaled by the slope of the polygon
+    pub slope_factor: f32,
+    /// Maximum bias ( 0 means no clamping )
+    pub clamp: f32,
+}
+
+impl Default for DepthBias {
+    fn default() -> Self {
+        return DepthBias {
+            constant_factor: 1.25,
+            slope_factor: 1.75,
+            clamp: 0.0,
+        };
+    }
+}
+
+//-----------------------------------------------------------------------------
+/// Stencil test of the pipelines drawing to a render pass with a stencil
+/// attachment ( see [RenderPass::new_with_stencil](crate::RenderPass::new_with_stencil) ),
+/// the same for the front and the back faces.
+///
+/// The reference value is dynamic and set with
+/// [cmd::Buffer::set_stencil_reference](crate::cmd::Buffer::set_stencil_reference),
+/// so nested masks ( UI clip rects ) share one pipeline for every level.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct StencilState {
+    /// Comparison of `reference & compare_mask` with `stencil & compare_mask`
+    pub compare_op: vk::CompareOp,
+    pub compare_mask: u32,
+    /// Bits of the stencil which are written
+    pub write_mask: u32,
+    /// Applied when both the stencil and the depth tests pass
+    pub pass_op: vk::StencilOp,
+    /// Applied when the stencil test fails
+    pub fail_op: vk::StencilOp,
+    /// Applied when the stencil test passes but the depth test fails
+    pub depth_fail_op: vk::StencilOp,
+}
+
+impl Default for StencilState {
+    /// Everything passes, nothing is written
+    fn default() -> Self {
+        return StencilState {
+            compare_op: vk::CompareOp::ALWAYS,
+            compare_mask: 0xff,
+            write_mask: 0xff,
+            pass_op: vk::StencilOp::KEEP,
+            fail_op: vk::StencilOp::KEEP,
+            depth_fail_op: vk::StencilOp::KEEP,
+        };
+    }
+}
+
+//-----------------------------------------------------------------------------
+/// Optional programmable stages between the vertex and the fragment shaders,
+/// see [Pipeline::new_with_stages]
+#[derive(Clone, Copy, Default)]
+pub struct ExtraStages<'a> {
+    pub tessellation: Option<TessellationStages<'a>>,
+    /// Needs the `geometryShader` device feature
+    pub geometry: Option<&'a crate::Shader>,
+}
+
+/// Tessellation control and evaluation shaders. The pipeline draws patches of
+/// `patch_control_points` vertices instead of triangles, needs the
+/// `tessellationShader` device feature
+#[derive(Clone, Copy)]
+pub struct TessellationStages<'a> {
+    pub control: &'a crate::Shader,
+    pub evaluation: &'a crate::Shader,
+    pub patch_control_points: u32,
 }
 
 //-----------------------------------------------------------------------------
 /// Common blending modes
-#[derive(Clone, Copy, Debug, Default)]
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
 pub enum BlendMode {
     /// No blending
     #[default]
@@ -47,6 +188,22 @@ impl Pipeline {
     pub fn blend_mode(&self) -> BlendMode {
         return self.blend_mode;
     }
+    pub fn preset(&self) -> PipelinePreset {
+        return self.preset;
+    }
+    /// Whether the line width can be changed with
+    /// [cmd::Buffer::set_line_width](crate::cmd::Buffer::set_line_width)
+    /// ( only for line presets when the device supports wide lines )
+    pub fn has_dynamic_line_width(&self) -> bool {
+        return self.dynamic_line_width;
+    }
+    pub fn stencil(&self) -> Option<StencilState> {
+        return self.stencil;
+    }
+    /// Whether the pipeline can be the base of [Pipeline::new_batch_derived]
+    pub fn allows_derivatives(&self) -> bool {
+        return self.allows_derivatives;
+    }
 }
 
 //-----------------------------------------------------------------------------
@@ -61,29 +218,364 @@ impl Pipeline {
         fragment_shader: &crate::Shader,
         blend_mode: BlendMode,
     ) -> Result<Self> {
+        return Self::new_with_preset(
+            device,
+            render_pass,
+            descriptor_set_layouts,
+            vertex_descriptions,
+            vertex_shader,
+            fragment_shader,
+            blend_mode,
+            PipelinePreset::Fill,
+        );
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn new_with_preset(
+        device: &crate::DeviceRef,
+        render_pass: &crate::RenderPass,
+        descriptor_set_layouts: &[&crate::descriptor::SetLayout],
+        vertex_descriptions: &[crate::vertex::VertexDescription],
+        vertex_shader: &crate::Shader,
+        fragment_shader: &crate::Shader,
+        blend_mode: BlendMode,
+        preset: PipelinePreset,
+    ) -> Result<Self> {
+        return Self::create(
+            device,
+            render_pass,
+            PipelineInfo {
+                blend_mode,
+                preset,
+                ..PipelineInfo::new(
+                    descriptor_set_layouts,
+                    vertex_descriptions,
+                    vertex_shader,
+                    fragment_shader,
+                )
+            },
+        );
+    }
+
+    /// Pipeline with tessellation and / or geometry shaders. With the
+    /// tessellation the vertex buffers hold the control points of the patches
+    /// and the preset should be [PipelinePreset::Fill] or
+    /// [PipelinePreset::Wireframe]
+    #[allow(clippy::too_many_arguments)]
+    pub fn new_with_stages(
+        device: &crate::DeviceRef,
+        render_pass: &crate::RenderPass,
+        descriptor_set_layouts: &[&crate::descriptor::SetLayout],
+        vertex_descriptions: &[crate::vertex::VertexDescription],
+        vertex_shader: &crate::Shader,
+        fragment_shader: &crate::Shader,
+        blend_mode: BlendMode,
+        preset: PipelinePreset,
+        stages: ExtraStages,
+    ) -> Result<Self> {
+        return Self::create(
+            device,
+            render_pass,
+            PipelineInfo {
+                blend_mode,
+                preset,
+                stages,
+                ..PipelineInfo::new(
+                    descriptor_set_layouts,
+                    vertex_descriptions,
+                    vertex_shader,
+                    fragment_shader,
+                )
+            },
+        );
+    }
+
+    /// Pipeline with the stencil test, for a render pass with a stencil
+    /// attachment
+    #[allow(clippy::too_many_arguments)]
+    pub fn new_with_stencil(
+        device: &crate::DeviceRef,
+        render_pass: &crate::RenderPass,
+        descriptor_set_layouts: &[&crate::descriptor::SetLayout],
+        vertex_descriptions: &[crate::vertex::VertexDescription],
+        vertex_shader: &crate::Shader,
+        fragment_shader: &crate::Shader,
+        blend_mode: BlendMode,
+        preset: PipelinePreset,
+        stencil: StencilState,
+    ) -> Result<Self> {
+        return Self::create(
+            device,
+            render_pass,
+            PipelineInfo {
+                blend_mode,
+                preset,
+                stencil: Some(stencil),
+                ..PipelineInfo::new(
+                    descriptor_set_layouts,
+                    vertex_descriptions,
+                    vertex_shader,
+                    fragment_shader,
+                )
+            },
+        );
+    }
+
+    /// Create all of the pipelines with one call, which lets the driver
+    /// compile them together. The first pipeline is the base and the others
+    /// are its derivatives, so the most common setup should come first
+    pub fn new_batch(
+        device: &crate::DeviceRef,
+        render_pass: &crate::RenderPass,
+        infos: &[PipelineInfo],
+    ) -> Result<Vec<Self>> {
+        return Self::create_many(device, render_pass, infos, Derivatives::FromFirst);
+    }
+
+    /// Create all of the pipelines with one call as derivatives of the `base`
+    /// ( a pipeline of an earlier [Pipeline::new_batch] )
+    pub fn new_batch_derived(
+        device: &crate::DeviceRef,
+        render_pass: &crate::RenderPass,
+        base: &Pipeline,
+        infos: &[PipelineInfo],
+    ) -> Result<Vec<Self>> {
+        ensure!(
+            base.allows_derivatives,
+            InvalidUsage,
+            "The base pipeline wasn't created with Pipeline::new_batch"
+        );
+
+        return Self::create_many(
+            device,
+            render_pass,
+            infos,
+            Derivatives::FromBase(base.pipeline),
+        );
+    }
+
+    fn create(
+        device: &crate::DeviceRef,
+        render_pass: &crate::RenderPass,
+        info: PipelineInfo,
+    ) -> Result<Self> {
+        let mut pipelines = Self::create_many(
+            device,
+            render_pass,
+            std::slice::from_ref(&info),
+            Derivatives::None,
+        )?;
+
+        return Ok(pipelines.remove(0));
+    }
+
+    pub(crate) fn create_many(
+        device: &crate::DeviceRef,
+        render_pass: &crate::RenderPass,
+        infos: &[PipelineInfo],
+        derivatives: Derivatives,
+    ) -> Result<Vec<Self>> {
+        if infos.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        for info in infos {
+            info.validate(device, render_pass)?;
+        }
+
         /*
-         * Describe the programmable stages
+         * State of each pipeline, the create infos point into it
+         */
+        let states = infos
+            .iter()
+            .map(|info| PipelineState::new(device, info))
+            .collect::<Vec<_>>();
+
+        let dynamic_states = states
+            .iter()
+            .map(|state| {
+                return vk::PipelineDynamicStateCreateInfo::default()
+                    .dynamic_states(&state.dynamic_states);
+            })
+            .collect::<Vec<_>>();
+        let vertex_inputs = states
+            .iter()
+            .map(|state| {
+                return vk::PipelineVertexInputStateCreateInfo::default()
+                    .vertex_binding_descriptions(&state.binding_descriptions)
+                    .vertex_attribute_descriptions(&state.attribute_descriptions);
+            })
+            .collect::<Vec<_>>();
+        let input_assemblies = states
+            .iter()
+            .map(|state| {
+                return vk::PipelineInputAssemblyStateCreateInfo::default()
+                    .topology(state.topology)
+                    .primitive_restart_enable(false);
+            })
+            .collect::<Vec<_>>();
+        let tessellation_states = states
+            .iter()
+            .map(|state| {
+                return vk::PipelineTessellationStateCreateInfo::default()
+                    .patch_control_points(state.patch_control_points.unwrap_or(0));
+            })
+            .collect::<Vec<_>>();
+        let color_blendings = states
+            .iter()
+            .map(|state| {
+                return vk::PipelineColorBlendStateCreateInfo::default()
+                    .logic_op_enable(false)
+                    .attachments(std::slice::from_ref(&state.color_blend_attachment))
+                    .blend_constants([0.0; 4]);
+            })
+            .collect::<Vec<_>>();
+
+        /*
+         * Fixed state shared by every pipeline
          */
+        let viewport_state = vk::PipelineViewportStateCreateInfo::default()
+            .viewport_count(1)
+            .scissor_count(1);
+
+        let multisampling = vk::PipelineMultisampleStateCreateInfo::default()
+            .sample_shading_enable(false)
+            .rasterization_samples(vk::SampleCountFlags::TYPE_1);
+
+        /*
+         * Pipeline layouts
+         */
+        let mut pipeline_layouts = Vec::with_capacity(infos.len());
+        for info in infos {
+            let descriptor_set_layouts = crate::get_handles_vec(info.descriptor_set_layouts);
+
+            let pipeline_layout_create_info =
+                vk::PipelineLayoutCreateInfo::default().set_layouts(&descriptor_set_layouts);
+
+            match unsafe { device.create_pipeline_layout(&pipeline_layout_create_info, None) } {
+                Ok(pipeline_layout) => pipeline_layouts.push(pipeline_layout),
+                Err(e) => {
+                    Self::destroy_layouts(device, &pipeline_layouts);
+                    return Err(e.into());
+                }
+            }
+        }
+
+        /*
+         * Create the pipelines
+         */
+        let pipeline_create_infos = (0..infos.len())
+            .map(|idx| {
+                let state = &states[idx];
+
+                let mut pipeline_create_info = vk::GraphicsPipelineCreateInfo::default()
+                    .flags(derivatives.flags(idx))
+                    .stages(&state.shader_stages)
+                    .vertex_input_state(&vertex_inputs[idx])
+                    .input_assembly_state(&input_assemblies[idx])
+                    .viewport_state(&viewport_state)
+                    .rasterization_state(&state.rasterizer)
+                    .multisample_state(&multisampling)
+                    .color_blend_state(&color_blendings[idx])
+                    .dynamic_state(&dynamic_states[idx])
+                    .layout(pipeline_layouts[idx])
+                    .render_pass(**render_pass)
+                    .subpass(0);
+                if render_pass.depth_stencil_format().is_some() {
+                    pipeline_create_info =
+                        pipeline_create_info.depth_stencil_state(&state.depth_stencil);
+                }
+                if state.patch_control_points.is_some() {
+                    pipeline_create_info =
+                        pipeline_create_info.tessellation_state(&tessellation_states[idx]);
+                }
+                pipeline_create_info = match derivatives {
+                    Derivatives::None => pipeline_create_info,
+                    Derivatives::FromFirst => pipeline_create_info.base_pipeline_index(0),
+                    Derivatives::FromBase(base) => pipeline_create_info
+                        .base_pipeline_handle(base)
+                        .base_pipeline_index(-1),
+                };
+
+                return pipeline_create_info;
+            })
+            .collect::<Vec<_>>();
+
+        let graphics_pipelines = unsafe {
+            device.create_graphics_pipelines(device.pipeline_cache(), &pipeline_create_infos, None)
+        };
+
+        let graphics_pipelines = match graphics_pipelines {
+            Ok(pipelines) => pipelines,
+            Err((pipelines, e)) => {
+                // Some of the pipelines may have been created
+                for pipeline in pipelines {
+                    if pipeline != vk::Pipeline::null() {
+                        unsafe { device.destroy_pipeline(pipeline, None) };
+                    }
+                }
+                Self::destroy_layouts(device, &pipeline_layouts);
+                return Err(e.into());
+            }
+        };
+
+        crate::debug::stats::record_pipelines_created(graphics_pipelines.len() as u64);
+
+        return Ok(graphics_pipelines
+            .into_iter()
+            .enumerate()
+            .map(|(idx, graphics_pipeline)| {
+                crate::debug::leaks::track("Pipeline", graphics_pipeline);
+
+                return Pipeline {
+                    device: device.clone(),
+                    pipeline: graphics_pipeline,
+                    pipeline_layout: pipeline_layouts[idx],
+                    blend_mode: infos[idx].blend_mode,
+                    preset: infos[idx].preset,
+                    dynamic_line_width: states[idx].dynamic_line_width,
+                    stencil: infos[idx].stencil,
+                    allows_derivatives: derivatives != Derivatives::None,
+                };
+            })
+            .collect());
+    }
+
+    fn destroy_layouts(device: &crate::DeviceRef, pipeline_layouts: &[vk::PipelineLayout]) {
+        for &pipeline_layout in pipeline_layouts {
+            unsafe { device.destroy_pipeline_layout(pipeline_layout, None) };
+        }
+    }
+}
+
+impl Pipeline {
+    /// Pipeline writing only the depth ( shadow maps, depth pre-pass ) for a
+    /// render pass created with
+    /// [RenderPass::new_depth_only](crate::RenderPass::new_depth_only).
+    ///
+    /// There is no fragment shader and no culling, the depth bias is applied to
+    /// every polygon. Geometry outside of the near and far planes is clamped
+    /// when the device supports it.
+    pub fn new_depth_only(
+        device: &crate::DeviceRef,
+        render_pass: &crate::RenderPass,
+        descriptor_set_layouts: &[&crate::descriptor::SetLayout],
+        vertex_descriptions: &[crate::vertex::VertexDescription],
+        vertex_shader: &crate::Shader,
+        depth_bias: DepthBias,
+    ) -> Result<Self> {
+        crate::vertex::validate_vertex_input(vertex_descriptions, vertex_shader)?;
+
+        let depth_clamp = device.physical().info().features.depth_clamp == vk::TRUE;
+
         let vertex_shader_stage_info = vk::PipelineShaderStageCreateInfo::default()
             .stage(vk::ShaderStageFlags::VERTEX)
             .module(**vertex_shader)
             .name(c"main");
-        let fragment_shader_stage_info = vk::PipelineShaderStageCreateInfo::default()
-            .stage(vk::ShaderStageFlags::FRAGMENT)
-            .module(**fragment_shader)
-            .name(c"main");
 
-        let shader_stages = [vertex_shader_stage_info, fragment_shader_stage_info];
-
-        /*
-         * Describe the dynamic state
-         */
         let dynamic_state =
             vk::PipelineDynamicStateCreateInfo::default().dynamic_states(DYNAMIC_STATES);
 
-        /*
-         * Describe the layout of the input vertex data
-         */
         let (binding_descriptions, attribute_descriptions) =
             crate::vertex::get_vk_vertex_description(vertex_descriptions);
 
@@ -91,9 +583,6 @@ impl Pipeline {
             .vertex_binding_descriptions(&binding_descriptions)
             .vertex_attribute_descriptions(&attribute_descriptions);
 
-        /*
-         * Input assembly info
-         */
         let input_assembly = vk::PipelineInputAssemblyStateCreateInfo::default()
             .topology(vk::PrimitiveTopology::TRIANGLE_LIST)
             .primitive_restart_enable(false);
@@ -103,33 +592,31 @@ impl Pipeline {
             .scissor_count(1);
 
         /*
-         * Rasterizer
+         * Rasterizer with the depth bias
          */
         let rasterizer = vk::PipelineRasterizationStateCreateInfo::default()
-            .depth_clamp_enable(false) // Discard fragments beyond near and far planes
-            .rasterizer_discard_enable(false) // Do not disable output to frame buffer
+            .depth_clamp_enable(depth_clamp)
+            .rasterizer_discard_enable(false)
             .polygon_mode(vk::PolygonMode::FILL)
             .line_width(1.0)
-            .cull_mode(vk::CullModeFlags::FRONT) // Backface culling
+            .cull_mode(vk::CullModeFlags::NONE)
             .front_face(vk::FrontFace::CLOCKWISE)
-            .depth_bias_enable(false);
+            .depth_bias_enable(true)
+            .depth_bias_constant_factor(depth_bias.constant_factor)
+            .depth_bias_slope_factor(depth_bias.slope_factor)
+            .depth_bias_clamp(depth_bias.clamp);
 
-        /*
-         * Multisampling
-         */
         let multisampling = vk::PipelineMultisampleStateCreateInfo::default()
             .sample_shading_enable(false)
             .rasterization_samples(vk::SampleCountFlags::TYPE_1);
 
-        /*
-         * Color blending
-         */
-        let color_blend_attachment = blend_mode.to_vk_attachment();
+        let depth_stencil = vk::PipelineDepthStencilStateCreateInfo::default()
+            .depth_test_enable(true)
+            .depth_write_enable(true)
+            .depth_compare_op(vk::CompareOp::LESS_OR_EQUAL);
 
-        let color_blending = vk::PipelineColorBlendStateCreateInfo::default()
-            .logic_op_enable(false)
-            .attachments(std::slice::from_ref(&color_blend_attachment))
-            .blend_constants([0.0; 4]);
+        // No color attachments
+        let color_blending = vk::PipelineColorBlendStateCreateInfo::default();
 
         /*
          * Pipeline layout
@@ -143,12 +630,13 @@ impl Pipeline {
             unsafe { device.create_pipeline_layout(&pipeline_layout_create_info, None)? };
 
         let pipeline_create_info = vk::GraphicsPipelineCreateInfo::default()
-            .stages(&shader_stages)
+            .stages(std::slice::from_ref(&vertex_shader_stage_info))
             .vertex_input_state(&vertex_input)
             .input_assembly_state(&input_assembly)
             .viewport_state(&viewport_state)
             .rasterization_state(&rasterizer)
             .multisample_state(&multisampling)
+            .depth_stencil_state(&depth_stencil)
             .color_blend_state(&color_blending)
             .dynamic_state(&dynamic_state)
             .layout(pipeline_layout)
@@ -158,24 +646,336 @@ impl Pipeline {
         let graphics_pipeline = unsafe {
             device
                 .create_graphics_pipelines(
-                    vk::PipelineCache::null(),
+                    device.pipeline_cache(),
                     std::slice::from_ref(&pipeline_create_info),
                     None,
                 )
-                .map_err(|(_, e)| e)?
-        }[0];
+                .map_err(|(_, e)| e)
+        };
+
+        let graphics_pipeline = match graphics_pipeline {
+            Ok(pipelines) => pipelines[0],
+            Err(e) => {
+                unsafe { device.destroy_pipeline_layout(pipeline_layout, None) };
+                return Err(e.into());
+            }
+        };
+
+        crate::debug::stats::record_pipelines_created(1);
+        crate::debug::leaks::track("Pipeline", graphics_pipeline);
 
         return Ok(Pipeline {
             device: device.clone(),
             pipeline: graphics_pipeline,
             pipeline_layout,
-            blend_mode,
+            blend_mode: BlendMode::None,
+            preset: PipelinePreset::Fill,
+            dynamic_line_width: false,
+            stencil: None,
+            allows_derivatives: false,
         });
     }
 }
 
+//-----------------------------------------------------------------------------
+// Constructor
+impl<'a> PipelineInfo<'a> {
+    /// Filled triangles without blending, the stencil test and extra stages
+    pub fn new(
+        descriptor_set_layouts: &'a [&'a crate::descriptor::SetLayout],
+        vertex_descriptions: &'a [crate::vertex::VertexDescription],
+        vertex_shader: &'a crate::Shader,
+        fragment_shader: &'a crate::Shader,
+    ) -> Self {
+        return PipelineInfo {
+            descriptor_set_layouts,
+            vertex_descriptions,
+            vertex_shader,
+            fragment_shader,
+            blend_mode: BlendMode::None,
+            preset: PipelinePreset::Fill,
+            stencil: None,
+            stages: ExtraStages::default(),
+        };
+    }
+}
+
 //-----------------------------------------------------------------------------
 // Specific implementation
+impl PipelineInfo<'_> {
+    fn validate(&self, device: &crate::DeviceRef, render_pass: &crate::RenderPass) -> Result<()> {
+        let physical_info = device.physical().info();
+
+        ensure!(
+            self.preset != PipelinePreset::Wireframe
+                || physical_info.features.fill_mode_non_solid == vk::TRUE,
+            Unsupported,
+            "Wireframe pipelines require the fillModeNonSolid feature"
+        );
+
+        ensure!(
+            self.stencil.is_none() || render_pass.has_stencil(),
+            InvalidUsage,
+            "Stencil test needs a render pass with a stencil attachment"
+        );
+
+        if let Some(tessellation) = &self.stages.tessellation {
+            ensure!(
+                physical_info.features.tessellation_shader == vk::TRUE,
+                Unsupported,
+                "Tessellation stages require the tessellationShader feature"
+            );
+
+            let max_patch_size = physical_info
+                .device_props
+                .limits
+                .max_tessellation_patch_size;
+            ensure!(
+                (1..=max_patch_size).contains(&tessellation.patch_control_points),
+                InvalidUsage,
+                "Patches of {} control points, the device supports 1 to {max_patch_size}",
+                tessellation.patch_control_points
+            );
+
+            ensure!(
+                matches!(
+                    self.preset,
+                    PipelinePreset::Fill | PipelinePreset::Wireframe
+                ),
+                InvalidUsage,
+                "Tessellation pipelines draw patches, the preset should be Fill or Wireframe"
+            );
+        }
+
+        ensure!(
+            self.stages.geometry.is_none() || physical_info.features.geometry_shader == vk::TRUE,
+            Unsupported,
+            "Geometry stage requires the geometryShader feature"
+        );
+
+        crate::vertex::validate_vertex_input(self.vertex_descriptions, self.vertex_shader)?;
+
+        return Ok(());
+    }
+}
+
+impl PipelineState {
+    fn new(device: &crate::DeviceRef, info: &PipelineInfo) -> Self {
+        let physical_info = device.physical().info();
+        let preset = info.preset;
+
+        let wide_lines = physical_info.features.wide_lines == vk::TRUE;
+        let dynamic_line_width = wide_lines && preset.is_lines();
+
+        // Without wide lines the only valid width is 1.0
+        let line_width = if wide_lines {
+            let [min, max] = physical_info.device_props.limits.line_width_range;
+            preset.line_width().clamp(min, max)
+        } else {
+            1.0
+        };
+
+        /*
+         * Describe the programmable stages
+         */
+        let mut shader_stages = vec![vk::PipelineShaderStageCreateInfo::default()
+            .stage(vk::ShaderStageFlags::VERTEX)
+            .module(**info.vertex_shader)
+            .name(c"main")];
+        if let Some(tessellation) = &info.stages.tessellation {
+            shader_stages.push(
+                vk::PipelineShaderStageCreateInfo::default()
+                    .stage(vk::ShaderStageFlags::TESSELLATION_CONTROL)
+                    .module(**tessellation.control)
+                    .name(c"main"),
+            );
+            shader_stages.push(
+                vk::PipelineShaderStageCreateInfo::default()
+                    .stage(vk::ShaderStageFlags::TESSELLATION_EVALUATION)
+                    .module(**tessellation.evaluation)
+                    .name(c"main"),
+            );
+        }
+        if let Some(geometry_shader) = info.stages.geometry {
+            shader_stages.push(
+                vk::PipelineShaderStageCreateInfo::default()
+                    .stage(vk::ShaderStageFlags::GEOMETRY)
+                    .module(**geometry_shader)
+                    .name(c"main"),
+            );
+        }
+        shader_stages.push(
+            vk::PipelineShaderStageCreateInfo::default()
+                .stage(vk::ShaderStageFlags::FRAGMENT)
+                .module(**info.fragment_shader)
+                .name(c"main"),
+        );
+
+        /*
+         * Describe the dynamic state
+         */
+        let mut dynamic_states = DYNAMIC_STATES.to_vec();
+        if dynamic_line_width {
+            dynamic_states.push(vk::DynamicState::LINE_WIDTH);
+        }
+        if info.stencil.is_some() {
+            dynamic_states.push(vk::DynamicState::STENCIL_REFERENCE);
+        }
+
+        /*
+         * Describe the layout of the input vertex data
+         */
+        let (binding_descriptions, attribute_descriptions) =
+            crate::vertex::get_vk_vertex_description(info.vertex_descriptions);
+
+        /*
+         * Input assembly ( the tessellation takes patches )
+         */
+        let patch_control_points = info
+            .stages
+            .tessellation
+            .map(|tessellation| tessellation.patch_control_points);
+        let topology = match patch_control_points {
+            Some(_) => vk::PrimitiveTopology::PATCH_LIST,
+            None => preset.topology(),
+        };
+
+        /*
+         * Rasterizer
+         */
+        let rasterizer = vk::PipelineRasterizationStateCreateInfo::default()
+            .depth_clamp_enable(false) // Discard fragments beyond near and far planes
+            .rasterizer_discard_enable(false) // Do not disable output to frame buffer
+            .polygon_mode(preset.polygon_mode())
+            .line_width(line_width)
+            .cull_mode(vk::CullModeFlags::FRONT) // Backface culling
+            .front_face(vk::FrontFace::CLOCKWISE)
+            .depth_bias_enable(false);
+
+        /*
+         * Stencil test ( the state is needed for any render pass with a
+         * depth / stencil attachment, the depth isn't tested )
+         */
+        let stencil = info.stencil;
+        let depth_stencil = vk::PipelineDepthStencilStateCreateInfo::default()
+            .depth_test_enable(false)
+            .depth_write_enable(false)
+            .stencil_test_enable(stencil.is_some())
+            .front(stencil.unwrap_or_default().to_vk_op_state())
+            .back(stencil.unwrap_or_default().to_vk_op_state());
+
+        return PipelineState {
+            shader_stages,
+            dynamic_states,
+            binding_descriptions,
+            attribute_descriptions,
+            topology,
+            patch_control_points,
+            rasterizer,
+            color_blend_attachment: info.blend_mode.to_vk_attachment(),
+            depth_stencil,
+            dynamic_line_width,
+        };
+    }
+}
+
+impl Derivatives {
+    fn flags(self, idx: usize) -> vk::PipelineCreateFlags {
+        return match self {
+            Derivatives::None => vk::PipelineCreateFlags::empty(),
+            Derivatives::FromFirst if idx == 0 => vk::PipelineCreateFlags::ALLOW_DERIVATIVES,
+            Derivatives::FromFirst | Derivatives::FromBase(_) => {
+                vk::PipelineCreateFlags::ALLOW_DERIVATIVES | vk::PipelineCreateFlags::DERIVATIVE
+            }
+        };
+    }
+}
+
+impl PipelinePreset {
+    fn is_lines(self) -> bool {
+        return matches!(
+            self,
+            PipelinePreset::Wireframe | PipelinePreset::Lines { .. }
+        );
+    }
+
+    fn topology(self) -> vk::PrimitiveTopology {
+        return match self {
+            PipelinePreset::Fill | PipelinePreset::Wireframe => {
+                vk::PrimitiveTopology::TRIANGLE_LIST
+            }
+            PipelinePreset::Points => vk::PrimitiveTopology::POINT_LIST,
+            PipelinePreset::Lines { .. } => vk::PrimitiveTopology::LINE_LIST,
+        };
+    }
+
+    fn polygon_mode(self) -> vk::PolygonMode {
+        return match self {
+            PipelinePreset::Wireframe => vk::PolygonMode::LINE,
+            _ => vk::PolygonMode::FILL,
+        };
+    }
+
+    fn line_width(self) -> f32 {
+        return match self {
+            PipelinePreset::Lines { width } => width,
+            _ => 1.0,
+        };
+    }
+}
+
+//-----------------------------------------------------------------------------
+impl StencilState {
+    /// Write the reference wherever is drawn ( the mask of a portal )
+    pub fn write() -> Self {
+        return StencilState {
+            pass_op: vk::StencilOp::REPLACE,
+            ..Default::default()
+        };
+    }
+
+    /// Draw only where the stencil equals the reference, without writing it
+    pub fn test_equal() -> Self {
+        return StencilState {
+            compare_op: vk::CompareOp::EQUAL,
+            write_mask: 0,
+            ..Default::default()
+        };
+    }
+
+    /// Increment the stencil where it equals the reference: with the
+    /// reference at the nesting level of the parent, a clip rect is pushed
+    /// inside of the parent one
+    pub fn increment() -> Self {
+        return StencilState {
+            compare_op: vk::CompareOp::EQUAL,
+            pass_op: vk::StencilOp::INCREMENT_AND_CLAMP,
+            ..Default::default()
+        };
+    }
+
+    /// Decrement the stencil where it equals the reference, pops the clip
+    /// rect pushed with [StencilState::increment]
+    pub fn decrement() -> Self {
+        return StencilState {
+            compare_op: vk::CompareOp::EQUAL,
+            pass_op: vk::StencilOp::DECREMENT_AND_CLAMP,
+            ..Default::default()
+        };
+    }
+
+    fn to_vk_op_state(self) -> vk::StencilOpState {
+        return vk::StencilOpState::default()
+            .fail_op(self.fail_op)
+            .pass_op(self.pass_op)
+            .depth_fail_op(self.depth_fail_op)
+            .compare_op(self.compare_op)
+            .compare_mask(self.compare_mask)
+            .write_mask(self.write_mask);
+    }
+}
+
+//-----------------------------------------------------------------------------
 impl BlendMode {
     /// Convert BlendMode to Vulkan blend state
     fn to_vk_attachment(self) -> vk::PipelineColorBlendAttachmentState {
@@ -250,6 +1050,7 @@ impl BlendMode {
 // Drop
 impl Drop for Pipeline {
     fn drop(&mut self) {
+        crate::debug::leaks::untrack("Pipeline", self.pipeline);
         unsafe {
             self.device.destroy_pipeline(self.pipeline, None);
             self.device
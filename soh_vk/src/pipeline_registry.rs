@@ -0,0 +1,433 @@
+//-----------------------------------------------------------------------------
+use crate::Result;
+use std::sync::Arc;
+#[cfg(feature = "thread")]
+use std::sync::Mutex;
+//-----------------------------------------------------------------------------
+
+/// Everything needed to (re)create a graphics pipeline for the render pass of
+/// the context. Shaders and set layouts are shared, so that the registry can
+/// keep them alive for the rebuilds.
+#[derive(Clone)]
+pub struct PipelineDesc {
+    pub set_layouts: Vec<Arc<crate::descriptor::SetLayout>>,
+    pub vertex_descriptions: Vec<crate::vertex::VertexDescription>,
+    pub vertex_shader: Arc<crate::Shader>,
+    pub fragment_shader: Arc<crate::Shader>,
+    pub blend_mode: crate::BlendMode,
+    pub preset: crate::PipelinePreset,
+}
+
+/// Index of a pipeline in the [PipelineRegistry]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PipelineId(usize);
+
+/// Owns the pipelines drawing to the render pass of the context and recreates
+/// them when the render pass changes ( the swapchain format changed after a
+/// color space switch or moving the window to another monitor ).
+///
+/// Pipelines are requested with [PipelineRegistry::get_or_create], equal
+/// descriptions share one pipeline. Many pipelines ( the materials of a scene )
+/// are better requested together with [PipelineRegistry::get_or_create_many],
+/// which compiles them in one call. Call [PipelineRegistry::sync] after
+/// [crate::VulkanContext::on_window_resize] and get the pipelines by their id
+/// every frame instead of keeping references.
+///
+/// After [crate::VulkanContext::recover_device_lost] the shaders and the set
+/// layouts of the descriptions are recreated on the new device as well, the
+/// new ones are returned by [PipelineRegistry::desc]. The pipelines come from
+/// the pipeline cache of the new device, seeded with the one of the lost
+/// device.
+pub struct PipelineRegistry {
+    device: crate::DeviceRef,
+
+    // Render pass the pipelines were created for ( shared with the
+    // compilation jobs )
+    render_pass: Arc<crate::RenderPass>,
+    entries: Vec<Entry>,
+}
+
+struct Entry {
+    desc: PipelineDesc,
+    pipeline: crate::Pipeline,
+}
+
+//-----------------------------------------------------------------------------
+// PartialEq
+impl PartialEq for PipelineDesc {
+    fn eq(&self, other: &Self) -> bool {
+        return self.set_layouts.len() == other.set_layouts.len()
+            && self
+                .set_layouts
+                .iter()
+                .zip(other.set_layouts.iter())
+                .all(|(a, b)| Arc::ptr_eq(a, b))
+            && self.vertex_descriptions == other.vertex_descriptions
+            && Arc::ptr_eq(&self.vertex_shader, &other.vertex_shader)
+            && Arc::ptr_eq(&self.fragment_shader, &other.fragment_shader)
+            && self.blend_mode == other.blend_mode
+            && self.preset == other.preset;
+    }
+}
+
+//-----------------------------------------------------------------------------
+// Getters
+impl PipelineRegistry {
+    pub fn len(&self) -> usize {
+        return self.entries.len();
+    }
+    pub fn is_empty(&self) -> bool {
+        return self.entries.is_empty();
+    }
+    pub fn get(&self, id: PipelineId) -> &crate::Pipeline {
+        return &self.entries[id.0].pipeline;
+    }
+    pub fn desc(&self, id: PipelineId) -> &PipelineDesc {
+        return &self.entries[id.0].desc;
+    }
+}
+
+//-----------------------------------------------------------------------------
+// Constructor
+impl PipelineRegistry {
+    pub fn new(context: &crate::VulkanContext) -> Result<Self> {
+        return Ok(PipelineRegistry {
+            device: context.device().clone(),
+            render_pass: Arc::new(Self::make_render_pass(context)?),
+            entries: Vec::new(),
+        });
+    }
+}
+
+//-----------------------------------------------------------------------------
+// Specific implementation
+impl PipelineRegistry {
+    /// Returns the pipeline with the `desc`, creating it if there isn't one
+    pub fn get_or_create(&mut self, desc: &PipelineDesc) -> Result<PipelineId> {
+        if let Some(idx) = self.entries.iter().position(|entry| entry.desc == *desc) {
+            return Ok(PipelineId(idx));
+        }
+
+        let pipeline = self.create_pipeline(desc)?;
+
+        self.entries.push(Entry {
+            desc: desc.clone(),
+            pipeline,
+        });
+
+        return Ok(PipelineId(self.entries.len() - 1));
+    }
+
+    /// Returns the pipelines with the `descs`, the missing ones are created
+    /// with one call as derivatives of the first of them
+    pub fn get_or_create_many(&mut self, descs: &[PipelineDesc]) -> Result<Vec<PipelineId>> {
+        return self.get_or_create_with(descs, |registry, new_descs| {
+            return Self::create_pipelines(
+                &registry.device,
+                &registry.render_pass,
+                new_descs,
+                crate::pipeline::Derivatives::FromFirst,
+            );
+        });
+    }
+
+    /// Like [PipelineRegistry::get_or_create_many], but the missing pipelines
+    /// are compiled on the workers of the `pool` in jobs of up to
+    /// `pipelines_per_job` pipelines. The first one is compiled on this
+    /// thread, the others derive from it. Waits until all of them are done.
+    #[cfg(feature = "thread")]
+    pub fn get_or_create_parallel(
+        &mut self,
+        pool: &soh_thread::ThreadPool,
+        descs: &[PipelineDesc],
+        pipelines_per_job: usize,
+    ) -> Result<Vec<PipelineId>> {
+        crate::error::ensure!(
+            pipelines_per_job != 0,
+            InvalidUsage,
+            "A compilation job needs at least one pipeline"
+        );
+
+        return self.get_or_create_with(descs, |registry, new_descs| {
+            return registry.create_pipelines_parallel(pool, new_descs, pipelines_per_job);
+        });
+    }
+
+    /// Recreates all of the pipelines if the render pass of the `context`
+    /// isn't compatible with the one they were created for, or the device of
+    /// the `context` was recreated. Returns whether the pipelines were
+    /// recreated.
+    pub fn sync(&mut self, context: &crate::VulkanContext) -> Result<bool> {
+        if !Arc::ptr_eq(&self.device, context.device()) {
+            self.move_to_device(context)?;
+            return Ok(true);
+        }
+
+        if context.render_pass().is_compatible_with(&self.render_pass) {
+            return Ok(false);
+        }
+
+        soh_log::log_debug!(
+            "Render pass changed, recreating {} pipelines",
+            self.entries.len()
+        );
+
+        /*
+         * The old pipelines may still be used by the frames in flight
+         */
+        self.device.wait_idle();
+
+        self.render_pass = Arc::new(Self::make_render_pass(context)?);
+        self.recreate_pipelines()?;
+
+        return Ok(true);
+    }
+
+    /// Recreates everything on the new device of the `context`, the objects
+    /// shared by the descriptions stay shared
+    fn move_to_device(&mut self, context: &crate::VulkanContext) -> Result<()> {
+        soh_log::log_debug!(
+            "Device changed, recreating {} pipelines",
+            self.entries.len()
+        );
+
+        let device = context.device().clone();
+
+        let mut shaders: Vec<(Arc<crate::Shader>, Arc<crate::Shader>)> = Vec::new();
+        let mut set_layouts: Vec<(
+            Arc<crate::descriptor::SetLayout>,
+            Arc<crate::descriptor::SetLayout>,
+        )> = Vec::new();
+
+        let mut recreate_shader = |old: &Arc<crate::Shader>| -> Result<Arc<crate::Shader>> {
+            if let Some((_, new)) = shaders.iter().find(|(o, _)| Arc::ptr_eq(o, old)) {
+                return Ok(new.clone());
+            }
+
+            let new = Arc::new(old.recreate(&device)?);
+            shaders.push((old.clone(), new.clone()));
+
+            return Ok(new);
+        };
+
+        for entry in self.entries.iter_mut() {
+            let desc = &mut entry.desc;
+
+            desc.vertex_shader = recreate_shader(&desc.vertex_shader)?;
+            desc.fragment_shader = recreate_shader(&desc.fragment_shader)?;
+
+            for layout in desc.set_layouts.iter_mut() {
+                if let Some((_, new)) = set_layouts.iter().find(|(o, _)| Arc::ptr_eq(o, layout)) {
+                    *layout = new.clone();
+                    continue;
+                }
+
+                let new = Arc::new(crate::descriptor::SetLayout::new(
+                    &device,
+                    layout.bindings(),
+                )?);
+                set_layouts.push((layout.clone(), new.clone()));
+                *layout = new;
+            }
+        }
+
+        self.device = device;
+        self.render_pass = Arc::new(Self::make_render_pass(context)?);
+        self.recreate_pipelines()?;
+
+        return Ok(());
+    }
+
+    /// Recreates the pipelines of all the entries with one call
+    fn recreate_pipelines(&mut self) -> Result<()> {
+        let descs = self
+            .entries
+            .iter()
+            .map(|entry| entry.desc.clone())
+            .collect::<Vec<_>>();
+
+        let pipelines = Self::create_pipelines(
+            &self.device,
+            &self.render_pass,
+            &descs,
+            crate::pipeline::Derivatives::FromFirst,
+        )?;
+
+        for (entry, pipeline) in self.entries.iter_mut().zip(pipelines) {
+            entry.pipeline = pipeline;
+        }
+
+        return Ok(());
+    }
+
+    /// Creates the pipelines of the `descs` which aren't in the registry yet
+    /// with `create` and returns the ids of all the `descs`
+    fn get_or_create_with<F>(
+        &mut self,
+        descs: &[PipelineDesc],
+        create: F,
+    ) -> Result<Vec<PipelineId>>
+    where
+        F: FnOnce(&Self, &[PipelineDesc]) -> Result<Vec<crate::Pipeline>>,
+    {
+        let find = |entries: &[Entry], desc: &PipelineDesc| {
+            return entries.iter().position(|entry| entry.desc == *desc);
+        };
+
+        /*
+         * Each missing description once
+         */
+        let mut new_descs: Vec<PipelineDesc> = Vec::new();
+        for desc in descs {
+            if find(&self.entries, desc).is_none() && !new_descs.contains(desc) {
+                new_descs.push(desc.clone());
+            }
+        }
+
+        if !new_descs.is_empty() {
+            let pipelines = create(self, &new_descs)?;
+
+            self.entries.extend(
+                new_descs
+                    .into_iter()
+                    .zip(pipelines)
+                    .map(|(desc, pipeline)| Entry { desc, pipeline }),
+            );
+        }
+
+        return Ok(descs
+            .iter()
+            .map(|desc| PipelineId(find(&self.entries, desc).unwrap()))
+            .collect());
+    }
+
+    /// Compiles the first pipeline on this thread and the others in jobs of
+    /// the `pool`, as its derivatives
+    #[cfg(feature = "thread")]
+    fn create_pipelines_parallel(
+        &self,
+        pool: &soh_thread::ThreadPool,
+        descs: &[PipelineDesc],
+        pipelines_per_job: usize,
+    ) -> Result<Vec<crate::Pipeline>> {
+        let mut pipelines = Self::create_pipelines(
+            &self.device,
+            &self.render_pass,
+            &descs[..1],
+            crate::pipeline::Derivatives::FromFirst,
+        )?;
+        // Stays alive until all of the jobs are done
+        let base = *pipelines[0];
+
+        /*
+         * Each job writes the pipelines to its own slot
+         */
+        let chunks = descs[1..]
+            .chunks(pipelines_per_job)
+            .map(|chunk| chunk.to_vec())
+            .collect::<Vec<_>>();
+        let results = Arc::new(Mutex::new(
+            (0..chunks.len()).map(|_| None).collect::<Vec<_>>(),
+        ));
+
+        let batch = pool.submit_batch(
+            "compile_pipelines",
+            chunks.into_iter().enumerate().map(|(idx, chunk)| {
+                let device = self.device.clone();
+                let render_pass = self.render_pass.clone();
+                let results = results.clone();
+
+                move || {
+                    let res = Self::create_pipelines(
+                        &device,
+                        &render_pass,
+                        &chunk,
+                        crate::pipeline::Derivatives::FromBase(base),
+                    );
+                    results.lock().unwrap()[idx] = Some(res);
+
+                    return Ok(());
+                }
+            }),
+        );
+
+        batch.wait_all();
+
+        /*
+         * Collect the pipelines in the order of the descriptions
+         */
+        let results = std::mem::take(&mut *results.lock().unwrap());
+        for res in results {
+            let Some(res) = res else {
+                crate::error::bail!(Unexpected, "A pipeline compilation job didn't run");
+            };
+            pipelines.extend(res?);
+        }
+
+        return Ok(pipelines);
+    }
+
+    fn create_pipelines(
+        device: &crate::DeviceRef,
+        render_pass: &crate::RenderPass,
+        descs: &[PipelineDesc],
+        derivatives: crate::pipeline::Derivatives,
+    ) -> Result<Vec<crate::Pipeline>> {
+        let set_layouts = descs
+            .iter()
+            .map(|desc| {
+                return desc
+                    .set_layouts
+                    .iter()
+                    .map(|layout| layout.as_ref())
+                    .collect::<Vec<_>>();
+            })
+            .collect::<Vec<_>>();
+
+        let infos = descs
+            .iter()
+            .zip(set_layouts.iter())
+            .map(|(desc, set_layouts)| {
+                return crate::PipelineInfo {
+                    blend_mode: desc.blend_mode,
+                    preset: desc.preset,
+                    ..crate::PipelineInfo::new(
+                        set_layouts,
+                        &desc.vertex_descriptions,
+                        &desc.vertex_shader,
+                        &desc.fragment_shader,
+                    )
+                };
+            })
+            .collect::<Vec<_>>();
+
+        return crate::Pipeline::create_many(device, render_pass, &infos, derivatives);
+    }
+
+    fn create_pipeline(&self, desc: &PipelineDesc) -> Result<crate::Pipeline> {
+        let set_layouts = desc
+            .set_layouts
+            .iter()
+            .map(|layout| layout.as_ref())
+            .collect::<Vec<_>>();
+
+        return crate::Pipeline::new_with_preset(
+            &self.device,
+            &self.render_pass,
+            &set_layouts,
+            &desc.vertex_descriptions,
+            &desc.vertex_shader,
+            &desc.fragment_shader,
+            desc.blend_mode,
+            desc.preset,
+        );
+    }
+
+    /// A compatible copy of the render pass of the `context`, the pipelines
+    /// can be used with the original one
+    fn make_render_pass(context: &crate::VulkanContext) -> Result<crate::RenderPass> {
+        return crate::RenderPass::new_simple(context.device(), context.swapchain().image_format());
+    }
+}
+
+//-----------------------------------------------------------------------------
@@ -0,0 +1,309 @@
+//-----------------------------------------------------------------------------
+use crate::error::ensure;
+use crate::Result;
+use ash::vk;
+//-----------------------------------------------------------------------------
+/// Format of the offscreen targets ( HDR, so that tonemapping has something to
+/// work with )
+const TARGET_FORMAT: vk::Format = vk::Format::R16G16B16A16_SFLOAT;
+
+/// Generates a triangle covering the whole screen from `gl_VertexIndex`.
+///
+/// The triangle is counter-clockwise in framebuffer coordinates, so it
+/// survives the back-face culling of [crate::Pipeline::new].
+const FULLSCREEN_VERT: &str = r#"
+#version 450
+
+layout(location = 0) out vec2 out_uv;
+
+void main() {
+    out_uv = vec2(gl_VertexIndex & 2, (gl_VertexIndex << 1) & 2);
+    gl_Position = vec4(out_uv * 2.0 - 1.0, 0.0, 1.0);
+}
+"#;
+//-----------------------------------------------------------------------------
+
+/// Chain of fullscreen passes applied to the rendered scene ( tonemapping,
+/// FXAA, vignette, ... ).
+///
+/// The scene is rendered into an offscreen target with
+/// [PostProcess::begin_scene], then [PostProcess::apply] runs every fragment
+/// shader of the chain, ping-ponging between two offscreen targets. The last
+/// pass writes to the swapchain framebuffer.
+///
+/// Fragment shaders of the passes get the texture coordinate at location 0
+/// and the output of the previous pass as a `sampler2D` at set 0, binding 0.
+pub struct PostProcess {
+    device: crate::DeviceRef,
+
+    render_pass: crate::RenderPass,
+    targets: [OffscreenTarget; 2],
+    sampler: vk::Sampler,
+
+    set_layout: crate::descriptor::SetLayout,
+    // Keeps the sets alive
+    _descriptor_pool: crate::descriptor::Pool,
+    // Set `i` samples the target `i`
+    sets: [crate::descriptor::Set; 2],
+
+    // The last pass draws to the swapchain render pass, the others to the
+    // offscreen render pass
+    passes: Vec<crate::Pipeline>,
+}
+
+struct OffscreenTarget {
+    // Destroyed after the framebuffer
+    _image: crate::Image,
+    // Owned by the framebuffer
+    image_view: vk::ImageView,
+    framebuffer: crate::Framebuffer,
+}
+
+//-----------------------------------------------------------------------------
+// Getters
+impl PostProcess {
+    /// Render pass of the scene, the scene pipelines have to be created with it
+    pub fn scene_render_pass(&self) -> &crate::RenderPass {
+        return &self.render_pass;
+    }
+    /// Layout of the set with the input of a pass
+    pub fn set_layout(&self) -> &crate::descriptor::SetLayout {
+        return &self.set_layout;
+    }
+    pub fn num_of_passes(&self) -> usize {
+        return self.passes.len();
+    }
+}
+
+//-----------------------------------------------------------------------------
+// Constructor
+impl PostProcess {
+    /// Creates the chain with a pass for each of the `fragment_shaders` ( at
+    /// least one ), the output goes to the render pass of the `context`.
+    pub fn new(
+        context: &crate::VulkanContext,
+        fragment_shaders: &[&crate::Shader],
+    ) -> Result<Self> {
+        ensure!(
+            !fragment_shaders.is_empty(),
+            InvalidUsage,
+            "Post-processing chain needs at least one pass"
+        );
+
+        let device = context.device();
+        let extent = context.swapchain().extent();
+
+        let render_pass = crate::RenderPass::new_offscreen(device, TARGET_FORMAT)?;
+        let targets = [
+            OffscreenTarget::new(device, &render_pass, extent)?,
+            OffscreenTarget::new(device, &render_pass, extent)?,
+        ];
+
+        /*
+         * Sampler and descriptor sets reading the targets
+         */
+        let sampler_create_info = vk::SamplerCreateInfo::default()
+            .mag_filter(vk::Filter::LINEAR)
+            .min_filter(vk::Filter::LINEAR)
+            .address_mode_u(vk::SamplerAddressMode::CLAMP_TO_EDGE)
+            .address_mode_v(vk::SamplerAddressMode::CLAMP_TO_EDGE)
+            .address_mode_w(vk::SamplerAddressMode::CLAMP_TO_EDGE);
+
+        let sampler = unsafe { device.create_sampler(&sampler_create_info, None)? };
+
+        let set_layout = crate::descriptor::SetLayout::new(
+            device,
+            &[crate::descriptor::SetLayoutBinding {
+                binding_num: 0,
+                descriptor_type: vk::DescriptorType::COMBINED_IMAGE_SAMPLER,
+                count: 1,
+                state_flags: vk::ShaderStageFlags::FRAGMENT,
+            }],
+        )?;
+
+        let descriptor_pool = crate::descriptor::PoolBuilder::new()
+            .max_num_of_sets(2)
+            .combined_sampler_descriptor_count(2)
+            .build(device)?;
+
+        let sets = [
+            descriptor_pool.allocate_set(&set_layout)?,
+            descriptor_pool.allocate_set(&set_layout)?,
+        ];
+
+        /*
+         * A pipeline for every pass
+         */
+        let vertex_shader = crate::Shader::from_glsl(
+            device,
+            context.shader_manager(),
+            FULLSCREEN_VERT,
+            shaderc::ShaderKind::Vertex,
+            "fullscreen.vert",
+        )?;
+
+        let passes = fragment_shaders
+            .iter()
+            .enumerate()
+            .map(|(idx, fragment_shader)| {
+                let is_last = idx + 1 == fragment_shaders.len();
+
+                return crate::Pipeline::new(
+                    device,
+                    if is_last {
+                        context.render_pass()
+                    } else {
+                        &render_pass
+                    },
+                    &[&set_layout],
+                    &[],
+                    &vertex_shader,
+                    fragment_shader,
+                    crate::BlendMode::None,
+                );
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let mut res = PostProcess {
+            device: device.clone(),
+            render_pass,
+            targets,
+            sampler,
+            set_layout,
+            _descriptor_pool: descriptor_pool,
+            sets,
+            passes,
+        };
+
+        res.update_sets();
+
+        return Ok(res);
+    }
+}
+
+//-----------------------------------------------------------------------------
+// Specific implementation
+impl PostProcess {
+    /// Recreates the offscreen targets, has to be called when the swapchain is
+    /// resized ( after [crate::VulkanContext::on_window_resize] ).
+    pub fn on_resize(&mut self, context: &crate::VulkanContext) -> Result<()> {
+        let extent = context.swapchain().extent();
+
+        for target in self.targets.iter_mut() {
+            *target = OffscreenTarget::new(&self.device, &self.render_pass, extent)?;
+        }
+
+        self.update_sets();
+
+        return Ok(());
+    }
+
+    /// Begins the render pass of the scene. The viewport and the scissor are
+    /// set to the whole target.
+    pub fn begin_scene(&self, cmd_buffer: &crate::cmd::Buffer) {
+        let framebuffer = &self.targets[0].framebuffer;
+
+        cmd_buffer.begin_render_pass(framebuffer, &self.render_pass);
+        cmd_buffer.set_fb_viewport_scissor(framebuffer);
+    }
+
+    /// Runs the chain on the rendered scene, the result is drawn to the
+    /// `framebuffer` of the swapchain ( the render pass of the context is
+    /// begun and ended inside ).
+    pub fn apply(
+        &self,
+        cmd_buffer: &crate::cmd::Buffer,
+        framebuffer: &crate::Framebuffer,
+        context: &crate::VulkanContext,
+    ) {
+        for (idx, pipeline) in self.passes.iter().enumerate() {
+            let is_last = idx + 1 == self.passes.len();
+            let src = idx % 2;
+
+            let (dst_framebuffer, dst_render_pass) = if is_last {
+                (framebuffer, context.render_pass())
+            } else {
+                (&self.targets[1 - src].framebuffer, &self.render_pass)
+            };
+
+            cmd_buffer.begin_render_pass(dst_framebuffer, dst_render_pass);
+            cmd_buffer.set_fb_viewport_scissor(dst_framebuffer);
+
+            cmd_buffer.bind_pipeline(pipeline);
+            cmd_buffer.bind_descriptor_sets(pipeline, &[&self.sets[src]]);
+            cmd_buffer.draw(3, 1, 0, 0);
+
+            cmd_buffer.end_render_pass();
+        }
+    }
+
+    fn update_sets(&mut self) {
+        for (set, target) in self.sets.iter_mut().zip(self.targets.iter()) {
+            let info = vk::DescriptorImageInfo::default()
+                .sampler(self.sampler)
+                .image_view(target.image_view)
+                .image_layout(vk::ImageLayout::SHADER_READ_ONLY_OPTIMAL);
+
+            set.update_combined_image_samplers(&[(0, std::slice::from_ref(&info))]);
+        }
+    }
+}
+
+impl OffscreenTarget {
+    fn new(
+        device: &crate::DeviceRef,
+        render_pass: &crate::RenderPass,
+        extent: vk::Extent2D,
+    ) -> Result<Self> {
+        let mut image = crate::ImageBuilder::new()
+            .format(TARGET_FORMAT)
+            .size((extent.width, extent.height))
+            .usage(vk::ImageUsageFlags::COLOR_ATTACHMENT | vk::ImageUsageFlags::SAMPLED)
+            .tiling(vk::ImageTiling::OPTIMAL)
+            .build(device)?;
+
+        image.allocate_memory(vk::MemoryPropertyFlags::DEVICE_LOCAL)?;
+
+        let view_create_info = vk::ImageViewCreateInfo::default()
+            .image(*image)
+            .view_type(vk::ImageViewType::TYPE_2D)
+            .format(TARGET_FORMAT)
+            .subresource_range(image.full_range());
+
+        let image_view = unsafe { device.create_image_view(&view_create_info, None)? };
+
+        let framebuffer =
+            match crate::Framebuffer::new_from_views(device, render_pass, vec![image_view], extent)
+            {
+                Ok(framebuffer) => framebuffer,
+                Err(e) => {
+                    unsafe { device.destroy_image_view(image_view, None) };
+                    return Err(e);
+                }
+            };
+
+        return Ok(OffscreenTarget {
+            _image: image,
+            image_view,
+            framebuffer,
+        });
+    }
+}
+
+//-----------------------------------------------------------------------------
+// Drop
+impl Drop for PostProcess {
+    fn drop(&mut self) {
+        unsafe {
+            self.device.destroy_sampler(self.sampler, None);
+        }
+    }
+}
+
+impl Drop for OffscreenTarget {
+    fn drop(&mut self) {
+        self.framebuffer.destroy();
+    }
+}
+
+//-----------------------------------------------------------------------------
@@ -0,0 +1,162 @@
+//-----------------------------------------------------------------------------
+use crate::Result;
+use ash::vk;
+//-----------------------------------------------------------------------------
+
+/// Occlusion queries ( how many samples of the draws between
+/// [crate::cmd::Buffer::begin_query] and [crate::cmd::Buffer::end_query]
+/// passed the depth test ), a set of queries per frame in flight.
+///
+/// Each frame:
+/// 1. [crate::cmd::Buffer::reset_queries] outside of the render pass
+/// 2. `begin_query` / `end_query` around the draws of the bounding boxes
+/// 3. Either read the results with [OcclusionQueryPool::results] once the
+///    frame has finished ( frames in flight later ), or skip the draws on the
+///    GPU with [crate::cmd::Buffer::copy_query_predicates] and
+///    [crate::cmd::Buffer::begin_conditional_rendering] when the device
+///    supports `VK_EXT_conditional_rendering`
+pub struct OcclusionQueryPool {
+    device: crate::DeviceRef,
+
+    pool: vk::QueryPool,
+    num_of_frames: u32,
+    queries_per_frame: u32,
+    precise: bool,
+
+    // Visibility of each query for the conditional rendering ( u32 per query )
+    predicates: Option<crate::Buffer>,
+}
+
+//-----------------------------------------------------------------------------
+// Getters
+impl OcclusionQueryPool {
+    pub fn num_of_frames(&self) -> u32 {
+        return self.num_of_frames;
+    }
+    pub fn queries_per_frame(&self) -> u32 {
+        return self.queries_per_frame;
+    }
+    /// Whether the results are sample counts ( otherwise only zero or non zero )
+    pub fn is_precise(&self) -> bool {
+        return self.precise;
+    }
+    pub fn supports_conditional_rendering(&self) -> bool {
+        return self.predicates.is_some();
+    }
+    pub(crate) fn predicates(&self) -> Option<&crate::Buffer> {
+        return self.predicates.as_ref();
+    }
+}
+
+//-----------------------------------------------------------------------------
+// Constructor
+impl OcclusionQueryPool {
+    /// * `num_of_frames`: frames in flight
+    /// * `queries_per_frame`: occlusion queries available in a frame
+    /// * `precise`: count the samples, if the device supports it
+    pub fn new(
+        device: &crate::DeviceRef,
+        num_of_frames: u32,
+        queries_per_frame: u32,
+        precise: bool,
+    ) -> Result<Self> {
+        let query_count = num_of_frames * queries_per_frame;
+
+        let create_info = vk::QueryPoolCreateInfo::default()
+            .query_type(vk::QueryType::OCCLUSION)
+            .query_count(query_count);
+
+        let pool = unsafe { device.create_query_pool(&create_info, None)? };
+        crate::debug::leaks::track("OcclusionQueryPool", pool);
+
+        let precise =
+            precise && device.physical().info().features.occlusion_query_precise == vk::TRUE;
+
+        let predicates = match device.device_conditional_rendering() {
+            Some(_) => Some(crate::Buffer::new(
+                device,
+                (query_count as usize * size_of::<u32>()) as u64,
+                crate::BufferUsageFlags::CONDITIONAL_RENDERING_EXT
+                    | crate::BufferUsageFlags::TRANSFER_DST,
+                crate::MemoryPropertyFlags::DEVICE_LOCAL,
+            )?),
+            None => None,
+        };
+
+        return Ok(OcclusionQueryPool {
+            device: device.clone(),
+            pool,
+            num_of_frames,
+            queries_per_frame,
+            precise,
+            predicates,
+        });
+    }
+}
+
+//-----------------------------------------------------------------------------
+// Specific implementation
+impl OcclusionQueryPool {
+    /// Index of the `query` of the frame in the pool
+    pub fn query_index(&self, frame_idx: usize, query: u32) -> u32 {
+        assert!(
+            (frame_idx as u32) < self.num_of_frames && query < self.queries_per_frame,
+            "Query {query} of the frame {frame_idx} is out of range ( {} frames of {} queries )",
+            self.num_of_frames,
+            self.queries_per_frame
+        );
+
+        return frame_idx as u32 * self.queries_per_frame + query;
+    }
+
+    /// Samples which passed for each query of the frame, `None` if the query
+    /// wasn't used since the last reset or it's still running on the GPU.
+    /// Doesn't wait.
+    pub fn results(&self, frame_idx: usize) -> Result<Vec<Option<u64>>> {
+        let first = self.query_index(frame_idx, 0);
+
+        // Result and availability of each query
+        let mut data = vec![[0u64; 2]; self.queries_per_frame as usize];
+
+        let res = unsafe {
+            self.device.get_query_pool_results(
+                self.pool,
+                first,
+                &mut data,
+                vk::QueryResultFlags::TYPE_64 | vk::QueryResultFlags::WITH_AVAILABILITY,
+            )
+        };
+        match res {
+            Ok(()) | Err(vk::Result::NOT_READY) => {}
+            Err(err) => return Err(err.into()),
+        }
+
+        return Ok(data
+            .iter()
+            .map(|&[samples, available]| (available != 0).then_some(samples))
+            .collect());
+    }
+}
+
+//-----------------------------------------------------------------------------
+// Drop
+impl Drop for OcclusionQueryPool {
+    fn drop(&mut self) {
+        crate::debug::leaks::untrack("OcclusionQueryPool", self.pool);
+        unsafe {
+            self.device.destroy_query_pool(self.pool, None);
+        }
+    }
+}
+
+//-----------------------------------------------------------------------------
+// Deref
+impl std::ops::Deref for OcclusionQueryPool {
+    type Target = vk::QueryPool;
+
+    fn deref(&self) -> &Self::Target {
+        return &self.pool;
+    }
+}
+
+//-----------------------------------------------------------------------------
@@ -1,7 +1,8 @@
 //-----------------------------------------------------------------------------
 // https://developer.samsung.com/galaxy-gamedev/resources/articles/renderpasses.html#Using-a-VkRenderPass
 //-----------------------------------------------------------------------------
-use anyhow::Result;
+use crate::error::ensure;
+use crate::Result;
 use ash::vk;
 //-----------------------------------------------------------------------------
 
@@ -9,6 +10,9 @@ pub struct RenderPass {
     device: crate::DeviceRef,
 
     render_pass: vk::RenderPass,
+    // Format and number of samples of every attachment
+    attachments: Vec<(crate::Format, u8)>,
+    depth_stencil_format: Option<crate::Format>,
 }
 
 //-----------------------------------------------------------------------------
@@ -72,6 +76,27 @@ impl Default for Attachment {
     }
 }
 
+//-----------------------------------------------------------------------------
+// Getters
+impl RenderPass {
+    /// Whether pipelines created for one render pass can be used with the
+    /// other ( all render passes here have a single subpass, so only the
+    /// formats and the sample counts of the attachments matter )
+    pub fn is_compatible_with(&self, other: &RenderPass) -> bool {
+        return self.attachments == other.attachments;
+    }
+    /// Format of the depth / stencil attachment, if there is one
+    pub fn depth_stencil_format(&self) -> Option<crate::Format> {
+        return self.depth_stencil_format;
+    }
+    /// Whether the pipelines can use the stencil test
+    pub fn has_stencil(&self) -> bool {
+        return self.depth_stencil_format.is_some_and(|format| {
+            crate::format_aspect_mask(format).contains(vk::ImageAspectFlags::STENCIL)
+        });
+    }
+}
+
 //-----------------------------------------------------------------------------
 // Constructor
 impl RenderPass {
@@ -89,14 +114,166 @@ impl RenderPass {
         return Self::new(device, color_attachments);
     }
 
+    /// Create render pass with one color attachment which is sampled by the
+    /// following passes ( offscreen targets ). The color is cleared and ends
+    /// up in `SHADER_READ_ONLY_OPTIMAL` layout.
+    pub fn new_offscreen(device: &crate::DeviceRef, format: crate::Format) -> Result<Self> {
+        let color_attachment: vk::AttachmentDescription = Attachment {
+            format,
+            load_op: LoadOp::CLEAR,
+            store_op: StoreOp::STORE,
+            initial_layout: crate::ImageLayout::UNDEFINED,
+            final_layout: crate::ImageLayout::SHADER_READ_ONLY_OPTIMAL,
+            ..Default::default()
+        }
+        .into();
+
+        let color_attachment_ref = vk::AttachmentReference::default()
+            .attachment(0)
+            .layout(vk::ImageLayout::COLOR_ATTACHMENT_OPTIMAL);
+
+        let subpass = vk::SubpassDescription::default()
+            .pipeline_bind_point(vk::PipelineBindPoint::GRAPHICS)
+            .color_attachments(std::slice::from_ref(&color_attachment_ref));
+
+        /*
+         * Writing has to wait for the previous reads of the image, the
+         * following reads have to wait for the writing
+         */
+        let dependencies = [
+            vk::SubpassDependency::default()
+                .src_subpass(vk::SUBPASS_EXTERNAL)
+                .dst_subpass(0)
+                .src_stage_mask(vk::PipelineStageFlags::FRAGMENT_SHADER)
+                .src_access_mask(vk::AccessFlags::SHADER_READ)
+                .dst_stage_mask(vk::PipelineStageFlags::COLOR_ATTACHMENT_OUTPUT)
+                .dst_access_mask(vk::AccessFlags::COLOR_ATTACHMENT_WRITE),
+            vk::SubpassDependency::default()
+                .src_subpass(0)
+                .dst_subpass(vk::SUBPASS_EXTERNAL)
+                .src_stage_mask(vk::PipelineStageFlags::COLOR_ATTACHMENT_OUTPUT)
+                .src_access_mask(vk::AccessFlags::COLOR_ATTACHMENT_WRITE)
+                .dst_stage_mask(vk::PipelineStageFlags::FRAGMENT_SHADER)
+                .dst_access_mask(vk::AccessFlags::SHADER_READ),
+        ];
+
+        let create_info = vk::RenderPassCreateInfo::default()
+            .attachments(std::slice::from_ref(&color_attachment))
+            .subpasses(std::slice::from_ref(&subpass))
+            .dependencies(&dependencies);
+
+        let render_pass = unsafe { device.create_render_pass(&create_info, None)? };
+        crate::debug::leaks::track("RenderPass", render_pass);
+
+        return Ok(RenderPass {
+            device: device.clone(),
+            render_pass,
+            attachments: vec![(format, 1)],
+            depth_stencil_format: None,
+        });
+    }
+
+    /// Create render pass with only one depth attachment ( shadow maps ). The
+    /// depth is cleared to 1.0 and ends up in `SHADER_READ_ONLY_OPTIMAL`
+    /// layout to be sampled by the following passes.
+    pub fn new_depth_only(device: &crate::DeviceRef, format: crate::Format) -> Result<Self> {
+        let depth_attachment: vk::AttachmentDescription = Attachment {
+            format,
+            load_op: LoadOp::CLEAR,
+            store_op: StoreOp::STORE,
+            initial_layout: crate::ImageLayout::UNDEFINED,
+            final_layout: crate::ImageLayout::SHADER_READ_ONLY_OPTIMAL,
+            ..Default::default()
+        }
+        .into();
+
+        let depth_attachment_ref = vk::AttachmentReference::default()
+            .attachment(0)
+            .layout(vk::ImageLayout::DEPTH_STENCIL_ATTACHMENT_OPTIMAL);
+
+        let subpass = vk::SubpassDescription::default()
+            .pipeline_bind_point(vk::PipelineBindPoint::GRAPHICS)
+            .depth_stencil_attachment(&depth_attachment_ref);
+
+        /*
+         * Writing has to wait for the previous reads of the depth, the
+         * following reads have to wait for the writing
+         */
+        let dependencies = [
+            vk::SubpassDependency::default()
+                .src_subpass(vk::SUBPASS_EXTERNAL)
+                .dst_subpass(0)
+                .src_stage_mask(vk::PipelineStageFlags::FRAGMENT_SHADER)
+                .src_access_mask(vk::AccessFlags::SHADER_READ)
+                .dst_stage_mask(vk::PipelineStageFlags::EARLY_FRAGMENT_TESTS)
+                .dst_access_mask(vk::AccessFlags::DEPTH_STENCIL_ATTACHMENT_WRITE),
+            vk::SubpassDependency::default()
+                .src_subpass(0)
+                .dst_subpass(vk::SUBPASS_EXTERNAL)
+                .src_stage_mask(vk::PipelineStageFlags::LATE_FRAGMENT_TESTS)
+                .src_access_mask(vk::AccessFlags::DEPTH_STENCIL_ATTACHMENT_WRITE)
+                .dst_stage_mask(vk::PipelineStageFlags::FRAGMENT_SHADER)
+                .dst_access_mask(vk::AccessFlags::SHADER_READ),
+        ];
+
+        let create_info = vk::RenderPassCreateInfo::default()
+            .attachments(std::slice::from_ref(&depth_attachment))
+            .subpasses(std::slice::from_ref(&subpass))
+            .dependencies(&dependencies);
+
+        let render_pass = unsafe { device.create_render_pass(&create_info, None)? };
+        crate::debug::leaks::track("RenderPass", render_pass);
+
+        return Ok(RenderPass {
+            device: device.clone(),
+            render_pass,
+            attachments: vec![(format, 1)],
+            depth_stencil_format: Some(format),
+        });
+    }
+
     pub fn new(device: &crate::DeviceRef, color_attachments: &[Attachment]) -> Result<Self> {
+        return Self::create(device, color_attachments, None);
+    }
+
+    /// Create render pass with a depth / stencil attachment after the color
+    /// ones ( the framebuffer has its image view last ). Clear the stencil
+    /// with the `stencil_load_op` to start the masks over every frame
+    pub fn new_with_stencil(
+        device: &crate::DeviceRef,
+        color_attachments: &[Attachment],
+        stencil_attachment: Attachment,
+    ) -> Result<Self> {
+        ensure!(
+            crate::format_aspect_mask(stencil_attachment.format)
+                .contains(vk::ImageAspectFlags::STENCIL),
+            InvalidUsage,
+            "Format {:?} of the stencil attachment has no stencil",
+            stencil_attachment.format
+        );
+
+        return Self::create(device, color_attachments, Some(stencil_attachment));
+    }
+
+    fn create(
+        device: &crate::DeviceRef,
+        color_attachments: &[Attachment],
+        stencil_attachment: Option<Attachment>,
+    ) -> Result<Self> {
         /*
          * Declare all of the attachments in the render pass
          * (attachment is a render target and corresponds to an image view in
          * the framebuffer)
          */
-        let color_attachments = color_attachments
+        let attachments = color_attachments
             .iter()
+            .chain(stencil_attachment.iter())
+            .map(|attachment| (attachment.format, attachment.num_of_samples))
+            .collect::<Vec<_>>();
+
+        let vk_attachments = color_attachments
+            .iter()
+            .chain(stencil_attachment.iter())
             .map(|attachment| (*attachment).into())
             .collect::<Vec<_>>();
         /*
@@ -106,18 +283,24 @@ impl RenderPass {
         let color_attachment_ref = vk::AttachmentReference::default()
             .attachment(0)
             .layout(vk::ImageLayout::COLOR_ATTACHMENT_OPTIMAL); // Layout DURING the subpass
+        let stencil_attachment_ref = vk::AttachmentReference::default()
+            .attachment(color_attachments.len() as u32)
+            .layout(vk::ImageLayout::DEPTH_STENCIL_ATTACHMENT_OPTIMAL);
 
         /*
          * Declare the subpasses
          */
-        let subpass = vk::SubpassDescription::default()
+        let mut subpass = vk::SubpassDescription::default()
             .pipeline_bind_point(vk::PipelineBindPoint::GRAPHICS)
             .color_attachments(std::slice::from_ref(&color_attachment_ref));
+        if stencil_attachment.is_some() {
+            subpass = subpass.depth_stencil_attachment(&stencil_attachment_ref);
+        }
 
         /*
          * Dependencies between subpasses
          */
-        let dependency = vk::SubpassDependency::default()
+        let mut dependency = vk::SubpassDependency::default()
             .src_subpass(vk::SUBPASS_EXTERNAL)
             .dst_subpass(0)
             .src_stage_mask(vk::PipelineStageFlags::COLOR_ATTACHMENT_OUTPUT)
@@ -125,19 +308,35 @@ impl RenderPass {
             .dst_stage_mask(vk::PipelineStageFlags::COLOR_ATTACHMENT_OUTPUT)
             .dst_access_mask(vk::AccessFlags::COLOR_ATTACHMENT_WRITE);
 
+        // The stencil of the previous frame has to be done before it's cleared
+        if stencil_attachment.is_some() {
+            let fragment_tests = vk::PipelineStageFlags::EARLY_FRAGMENT_TESTS
+                | vk::PipelineStageFlags::LATE_FRAGMENT_TESTS;
+
+            dependency = dependency
+                .src_stage_mask(dependency.src_stage_mask | fragment_tests)
+                .dst_stage_mask(dependency.dst_stage_mask | fragment_tests)
+                .dst_access_mask(
+                    dependency.dst_access_mask | vk::AccessFlags::DEPTH_STENCIL_ATTACHMENT_WRITE,
+                );
+        }
+
         /*
          * Create render pass
          */
         let create_info = vk::RenderPassCreateInfo::default()
-            .attachments(&color_attachments)
+            .attachments(&vk_attachments)
             .subpasses(std::slice::from_ref(&subpass))
             .dependencies(std::slice::from_ref(&dependency));
 
         let render_pass = unsafe { device.create_render_pass(&create_info, None)? };
+        crate::debug::leaks::track("RenderPass", render_pass);
 
         return Ok(RenderPass {
             device: device.clone(),
             render_pass,
+            attachments,
+            depth_stencil_format: stencil_attachment.map(|attachment| attachment.format),
         });
     }
 }
@@ -146,6 +345,7 @@ impl RenderPass {
 // Drop
 impl Drop for RenderPass {
     fn drop(&mut self) {
+        crate::debug::leaks::untrack("RenderPass", self.render_pass);
         unsafe {
             self.device.destroy_render_pass(self.render_pass, None);
         }
@@ -0,0 +1,176 @@
+//-----------------------------------------------------------------------------
+//! RenderDoc in-application API
+//!
+//! Only attaches if the application was launched (injected) by RenderDoc.
+//! See https://renderdoc.org/docs/in_application_api.html
+//-----------------------------------------------------------------------------
+#[cfg(not(any(target_os = "linux", target_os = "windows")))]
+use crate::error::bail;
+use crate::error::ensure;
+use crate::Result;
+use std::ffi::{c_int, c_void};
+//-----------------------------------------------------------------------------
+
+/// `eRENDERDOC_API_Version_1_1_2`
+const API_VERSION_1_1_2: c_int = 10102;
+
+#[cfg(target_os = "windows")]
+const LIB_NAME: &str = "renderdoc.dll";
+#[cfg(not(target_os = "windows"))]
+const LIB_NAME: &str = "librenderdoc.so";
+
+// `RTLD_NOLOAD` isn't exposed by `libloading`
+#[cfg(target_os = "linux")]
+const RTLD_NOLOAD: c_int = 0x4;
+
+type DevicePointer = *mut c_void;
+type WindowHandle = *mut c_void;
+
+type PfnGetApi = unsafe extern "C" fn(version: c_int, out_api: *mut *mut c_void) -> c_int;
+type PfnGetApiVersion =
+    unsafe extern "C" fn(major: *mut c_int, minor: *mut c_int, patch: *mut c_int);
+type PfnTriggerCapture = unsafe extern "C" fn();
+type PfnTriggerMultiFrameCapture = unsafe extern "C" fn(num_frames: u32);
+type PfnStartFrameCapture = unsafe extern "C" fn(device: DevicePointer, window: WindowHandle);
+type PfnIsFrameCapturing = unsafe extern "C" fn() -> u32;
+type PfnEndFrameCapture = unsafe extern "C" fn(device: DevicePointer, window: WindowHandle) -> u32;
+
+/// Layout of `RENDERDOC_API_1_1_2` (only the used entries are typed)
+#[repr(C)]
+struct ApiTable {
+    get_api_version: PfnGetApiVersion,
+    // SetCaptureOptionU32 .. GetCapture
+    _unused_0: [*const c_void; 14],
+    trigger_capture: PfnTriggerCapture,
+    // IsTargetControlConnected, LaunchReplayUI, SetActiveWindow
+    _unused_1: [*const c_void; 3],
+    start_frame_capture: PfnStartFrameCapture,
+    is_frame_capturing: PfnIsFrameCapturing,
+    end_frame_capture: PfnEndFrameCapture,
+    trigger_multi_frame_capture: PfnTriggerMultiFrameCapture,
+}
+
+//-----------------------------------------------------------------------------
+
+pub struct RenderDoc {
+    // Keep the library loaded while the function table is used
+    _library: libloading::Library,
+
+    api: &'static ApiTable,
+}
+
+// The in-application API may be called from any thread, the table itself is
+// never written
+unsafe impl Send for RenderDoc {}
+unsafe impl Sync for RenderDoc {}
+
+//-----------------------------------------------------------------------------
+// Constructor
+impl RenderDoc {
+    /// Get the RenderDoc API if the application is running under RenderDoc
+    ///
+    /// Should be called before the vulkan instance is created.
+    pub fn new() -> Result<Self> {
+        let library = Self::open_library()?;
+
+        let api = unsafe {
+            let get_api = library.get::<PfnGetApi>(b"RENDERDOC_GetAPI\0")?;
+
+            let mut api_ptr: *mut c_void = std::ptr::null_mut();
+            let res = get_api(API_VERSION_1_1_2, &mut api_ptr);
+
+            ensure!(
+                res == 1 && !api_ptr.is_null(),
+                Unsupported,
+                "RenderDoc doesn't support the requested API version"
+            );
+
+            &*(api_ptr as *const ApiTable)
+        };
+
+        let renderdoc = RenderDoc {
+            _library: library,
+            api,
+        };
+
+        let (major, minor, patch) = renderdoc.api_version();
+        soh_log::log_info!("Attached to RenderDoc {}.{}.{}", major, minor, patch);
+
+        return Ok(renderdoc);
+    }
+
+    #[cfg(target_os = "linux")]
+    fn open_library() -> Result<libloading::Library> {
+        use libloading::os::unix;
+
+        let library = unsafe { unix::Library::open(Some(LIB_NAME), unix::RTLD_NOW | RTLD_NOLOAD)? };
+
+        return Ok(library.into());
+    }
+
+    #[cfg(target_os = "windows")]
+    fn open_library() -> Result<libloading::Library> {
+        let library = libloading::os::windows::Library::open_already_loaded(LIB_NAME)?;
+
+        return Ok(library.into());
+    }
+
+    #[cfg(not(any(target_os = "linux", target_os = "windows")))]
+    fn open_library() -> Result<libloading::Library> {
+        bail!(
+            Unsupported,
+            "RenderDoc ({}) isn't supported on this platform",
+            LIB_NAME
+        );
+    }
+}
+
+//-----------------------------------------------------------------------------
+// Specific implementation
+impl RenderDoc {
+    pub fn api_version(&self) -> (i32, i32, i32) {
+        let (mut major, mut minor, mut patch) = (0, 0, 0);
+
+        unsafe {
+            (self.api.get_api_version)(&mut major, &mut minor, &mut patch);
+        }
+
+        return (major, minor, patch);
+    }
+
+    /// Start capturing the commands of any device and window
+    pub fn start_capture(&self) {
+        unsafe {
+            (self.api.start_frame_capture)(std::ptr::null_mut(), std::ptr::null_mut());
+        }
+    }
+
+    /// Finish the capture started with `start_capture`
+    ///
+    /// Returns false if the capture failed
+    pub fn end_capture(&self) -> bool {
+        return unsafe {
+            (self.api.end_frame_capture)(std::ptr::null_mut(), std::ptr::null_mut()) == 1
+        };
+    }
+
+    /// Capture the next presented frame
+    pub fn trigger_capture(&self) {
+        unsafe {
+            (self.api.trigger_capture)();
+        }
+    }
+
+    /// Capture the next `num_of_frames` presented frames
+    pub fn trigger_multi_frame_capture(&self, num_of_frames: u32) {
+        unsafe {
+            (self.api.trigger_multi_frame_capture)(num_of_frames);
+        }
+    }
+
+    pub fn is_capturing(&self) -> bool {
+        return unsafe { (self.api.is_frame_capturing)() == 1 };
+    }
+}
+
+//-----------------------------------------------------------------------------
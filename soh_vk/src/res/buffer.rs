@@ -1,5 +1,5 @@
 //-----------------------------------------------------------------------------
-use anyhow::Result;
+use crate::Result;
 use ash::vk;
 //-----------------------------------------------------------------------------
 
@@ -38,6 +38,30 @@ impl Buffer {
     }
 }
 
+//-----------------------------------------------------------------------------
+// Buffer device address
+#[cfg(feature = "buffer_device_address")]
+impl Buffer {
+    /// Get the GPU address of the buffer (usable as a pointer inside shaders)
+    ///
+    /// The buffer must have been created with the `SHADER_DEVICE_ADDRESS` usage.
+    pub fn device_address(&self) -> vk::DeviceAddress {
+        assert!(
+            self.usage
+                .contains(crate::BufferUsageFlags::SHADER_DEVICE_ADDRESS),
+            "Trying to get the device address of a buffer without SHADER_DEVICE_ADDRESS usage"
+        );
+
+        let address_info = vk::BufferDeviceAddressInfo::default().buffer(self.buffer);
+
+        return unsafe {
+            self.device
+                .device_buffer_device_address()
+                .get_buffer_device_address(&address_info)
+        };
+    }
+}
+
 //-----------------------------------------------------------------------------
 // Constructors
 impl Buffer {
@@ -56,6 +80,7 @@ impl Buffer {
             .sharing_mode(vk::SharingMode::EXCLUSIVE);
 
         let buffer = unsafe { device.create_buffer(&create_info, None)? };
+        crate::debug::leaks::track("Buffer", buffer);
 
         /*
          * Get memory requirements
@@ -64,8 +89,18 @@ impl Buffer {
 
         /*
          * Allocate memory
+         *
+         * Buffers which are referenced by their GPU address need memory allocated with the
+         * `DEVICE_ADDRESS` flag
          */
-        let memory = super::Memory::alloc(device, memory_requirements, properties)?;
+        let alloc_flags = if usage.contains(crate::BufferUsageFlags::SHADER_DEVICE_ADDRESS) {
+            vk::MemoryAllocateFlags::DEVICE_ADDRESS
+        } else {
+            vk::MemoryAllocateFlags::empty()
+        };
+
+        let memory =
+            super::Memory::alloc_with_flags(device, memory_requirements, properties, alloc_flags)?;
 
         /*
          * Bind allocted memory to buffer
@@ -155,6 +190,7 @@ impl Buffer {
 // Drop
 impl Drop for Buffer {
     fn drop(&mut self) {
+        crate::debug::leaks::untrack("Buffer", self.buffer);
         unsafe {
             self.device.destroy_buffer(self.buffer, None);
         }
@@ -1,5 +1,6 @@
 //-----------------------------------------------------------------------------
-use anyhow::Result;
+use crate::error::ensure;
+use crate::Result;
 use ash::vk;
 //-----------------------------------------------------------------------------
 
@@ -8,6 +9,13 @@ pub struct Image {
 
     image: vk::Image,
     format: vk::Format,
+    size: (u32, u32),
+
+    usage: vk::ImageUsageFlags,
+    tiling: vk::ImageTiling,
+
+    num_of_mip_levels: u32,
+    num_of_layers: u32,
 
     memory: Option<super::Memory>,
 }
@@ -139,16 +147,58 @@ impl ImageBuilder {
          * Create image
          */
         let image = unsafe { device.create_image(&create_info, None)? };
+        crate::debug::leaks::track("Image", image);
 
         return Ok(Image {
             device: device.clone(),
             image,
             format: self.format,
+            size: self.size,
+
+            usage: self.usage,
+            tiling: self.tiling,
+
+            num_of_mip_levels: self.num_of_mip_levels,
+            num_of_layers: self.num_of_layers,
+
             memory: None,
         });
     }
 }
 
+//-----------------------------------------------------------------------------
+// Constructor
+impl Image {
+    /// Creates an attachment which only lives during a render pass ( depth
+    /// buffers, multisampled color resolved at the end of the pass ). Its
+    /// memory is lazily allocated when the GPU supports it, see
+    /// [Image::allocate_transient_memory].
+    pub fn new_transient_attachment(
+        device: &crate::DeviceRef,
+        format: vk::Format,
+        size: (u32, u32),
+        samples: vk::SampleCountFlags,
+    ) -> Result<Image> {
+        let attachment_usage = if super::format_aspect_mask(format) == vk::ImageAspectFlags::COLOR {
+            vk::ImageUsageFlags::COLOR_ATTACHMENT
+        } else {
+            vk::ImageUsageFlags::DEPTH_STENCIL_ATTACHMENT
+        };
+
+        let mut image = ImageBuilder::new()
+            .format(format)
+            .size(size)
+            .usage(attachment_usage | vk::ImageUsageFlags::TRANSIENT_ATTACHMENT)
+            .samples(samples)
+            .tiling(vk::ImageTiling::OPTIMAL)
+            .build(device)?;
+
+        image.allocate_transient_memory()?;
+
+        return Ok(image);
+    }
+}
+
 impl Default for ImageBuilder {
     fn default() -> Self {
         return Self::new();
@@ -164,6 +214,34 @@ impl Image {
     pub fn format(&self) -> vk::Format {
         return self.format;
     }
+    pub fn size(&self) -> (u32, u32) {
+        return self.size;
+    }
+    pub fn usage(&self) -> vk::ImageUsageFlags {
+        return self.usage;
+    }
+    pub fn tiling(&self) -> vk::ImageTiling {
+        return self.tiling;
+    }
+    pub fn mip_levels(&self) -> u32 {
+        return self.num_of_mip_levels;
+    }
+    pub fn layers(&self) -> u32 {
+        return self.num_of_layers;
+    }
+    pub fn aspect_mask(&self) -> vk::ImageAspectFlags {
+        return super::format_aspect_mask(self.format);
+    }
+    /// Subresource range covering all mip levels and layers of the image
+    pub fn full_range(&self) -> vk::ImageSubresourceRange {
+        return vk::ImageSubresourceRange {
+            aspect_mask: self.aspect_mask(),
+            base_mip_level: 0,
+            level_count: self.num_of_mip_levels,
+            base_array_layer: 0,
+            layer_count: self.num_of_layers,
+        };
+    }
     pub fn memory(&self) -> Option<&super::Memory> {
         return self.memory.as_ref();
     }
@@ -198,9 +276,248 @@ impl Image {
         return Ok(());
     }
 
+    /// Allocate `LAZILY_ALLOCATED` memory for an image with the
+    /// `TRANSIENT_ATTACHMENT` usage. On tiled GPUs such attachments may never
+    /// get backing memory. Falls back to regular device local memory if there
+    /// is no lazily allocated memory type.
+    ///
+    /// Returns whether the memory is lazily allocated.
+    pub fn allocate_transient_memory(&mut self) -> Result<bool> {
+        ensure!(
+            self.usage
+                .contains(vk::ImageUsageFlags::TRANSIENT_ATTACHMENT),
+            InvalidUsage,
+            "Lazily allocated memory requires the image to have TRANSIENT_ATTACHMENT usage"
+        );
+
+        let memory_requirements = unsafe { self.device.get_image_memory_requirements(self.image) };
+        let lazy_properties =
+            vk::MemoryPropertyFlags::DEVICE_LOCAL | vk::MemoryPropertyFlags::LAZILY_ALLOCATED;
+
+        let is_lazy = self
+            .device
+            .physical()
+            .find_memory_type(memory_requirements.memory_type_bits, lazy_properties)
+            .is_some();
+
+        self.allocate_memory(if is_lazy {
+            lazy_properties
+        } else {
+            vk::MemoryPropertyFlags::DEVICE_LOCAL
+        })?;
+
+        return Ok(is_lazy);
+    }
+
     pub fn free_memory(&mut self) {
         self.memory = None;
     }
+
+    /// Fill all mip levels of the image by repeatedly blitting each level into the next one
+    ///
+    /// The whole image is expected to be in `old_layout` with the level 0 containing the data.
+    /// After the call all of the levels are in `new_layout`.
+    ///
+    /// * `cmd_pool`: graphics command pool (blitting isn't supported on transfer queues)
+    /// * `old_layout`: the current layout of the image
+    /// * `new_layout`: the layout the image should end up in
+    pub fn generate_mipmaps(
+        &self,
+        cmd_pool: &crate::cmd::Pool,
+        old_layout: vk::ImageLayout,
+        new_layout: vk::ImageLayout,
+    ) -> Result<()> {
+        ensure!(
+            matches!(cmd_pool.queue_type(), crate::QueueType::Graphics),
+            InvalidUsage,
+            "Generating mipmaps requires a graphics command pool"
+        );
+        ensure!(
+            self.usage
+                .contains(vk::ImageUsageFlags::TRANSFER_SRC | vk::ImageUsageFlags::TRANSFER_DST),
+            InvalidUsage,
+            "Generating mipmaps requires the image to have TRANSFER_SRC and TRANSFER_DST usage"
+        );
+
+        /*
+         * Check if the format supports linear filtering
+         */
+        let format_properties = self.device.physical().format_properties(self.format);
+        let format_features = match self.tiling {
+            vk::ImageTiling::LINEAR => format_properties.linear_tiling_features,
+            _ => format_properties.optimal_tiling_features,
+        };
+
+        ensure!(
+            format_features.contains(vk::FormatFeatureFlags::SAMPLED_IMAGE_FILTER_LINEAR),
+            Unsupported,
+            "Image format {:?} doesn't support linear blitting",
+            self.format
+        );
+
+        let aspect_mask = self.aspect_mask();
+        let level_range = |level: u32, level_count: u32| vk::ImageSubresourceRange {
+            aspect_mask,
+            base_mip_level: level,
+            level_count,
+            base_array_layer: 0,
+            layer_count: self.num_of_layers,
+        };
+        let level_layers = |level: u32| vk::ImageSubresourceLayers {
+            aspect_mask,
+            mip_level: level,
+            base_array_layer: 0,
+            layer_count: self.num_of_layers,
+        };
+        let level_extent = |level: u32| vk::Offset3D {
+            x: (self.size.0 >> level).max(1) as i32,
+            y: (self.size.1 >> level).max(1) as i32,
+            z: 1,
+        };
+
+        let last_level = self.num_of_mip_levels - 1;
+
+        return cmd_pool.execute_one_time(|cmd_buf| {
+            cmd_buf.transition_image_layout(
+                self,
+                old_layout,
+                vk::ImageLayout::TRANSFER_DST_OPTIMAL,
+                self.full_range(),
+            );
+
+            for level in 1..self.num_of_mip_levels {
+                /*
+                 * Previous level becomes the blit source
+                 */
+                cmd_buf.transition_image_layout(
+                    self,
+                    vk::ImageLayout::TRANSFER_DST_OPTIMAL,
+                    vk::ImageLayout::TRANSFER_SRC_OPTIMAL,
+                    level_range(level - 1, 1),
+                );
+
+                let blit = vk::ImageBlit {
+                    src_subresource: level_layers(level - 1),
+                    src_offsets: [vk::Offset3D::default(), level_extent(level - 1)],
+                    dst_subresource: level_layers(level),
+                    dst_offsets: [vk::Offset3D::default(), level_extent(level)],
+                };
+
+                cmd_buf.blit_image(self, self, std::slice::from_ref(&blit), vk::Filter::LINEAR);
+            }
+
+            /*
+             * All levels except the last one were used as a blit source
+             */
+            if last_level > 0 {
+                cmd_buf.transition_image_layout(
+                    self,
+                    vk::ImageLayout::TRANSFER_SRC_OPTIMAL,
+                    new_layout,
+                    level_range(0, last_level),
+                );
+            }
+            cmd_buf.transition_image_layout(
+                self,
+                vk::ImageLayout::TRANSFER_DST_OPTIMAL,
+                new_layout,
+                level_range(last_level, 1),
+            );
+
+            return Ok(());
+        });
+    }
+
+    /// Read back the level 0 of the image (all layers) into a newly created host visible buffer
+    ///
+    /// Combined depth stencil formats are rejected, a copy can only read one aspect.
+    /// The image is transitioned back to `layout` after the copy.
+    ///
+    /// * `cmd_pool`: command pool used to record the copy
+    /// * `layout`: the current layout of the image
+    pub fn copy_to_buffer(
+        &self,
+        cmd_pool: &crate::cmd::Pool,
+        layout: vk::ImageLayout,
+    ) -> Result<super::Buffer> {
+        ensure!(
+            layout != vk::ImageLayout::UNDEFINED,
+            InvalidUsage,
+            "Trying to read back an image in UNDEFINED layout"
+        );
+        ensure!(
+            self.usage.contains(vk::ImageUsageFlags::TRANSFER_SRC),
+            InvalidUsage,
+            "Reading back an image requires the image to have TRANSFER_SRC usage"
+        );
+
+        /*
+         * A buffer copy reads exactly one aspect, the depth and the stencil of the combined
+         * formats would need separate copies with their own texel sizes
+         */
+        let aspect_mask = super::format_aspect_mask(self.format);
+        ensure!(
+            aspect_mask.as_raw().count_ones() == 1,
+            InvalidUsage,
+            "Can't read back the combined depth stencil format {:?} in one copy",
+            self.format
+        );
+
+        let buffer_size = self.size.0 as u64
+            * self.size.1 as u64
+            * self.num_of_layers as u64
+            * super::format_size(self.format);
+
+        let buffer = super::Buffer::new(
+            &self.device,
+            buffer_size,
+            crate::BufferUsageFlags::TRANSFER_DST,
+            crate::MemoryPropertyFlags::HOST_VISIBLE | crate::MemoryPropertyFlags::HOST_COHERENT,
+        )?;
+
+        let range = vk::ImageSubresourceRange {
+            level_count: 1,
+            ..self.full_range()
+        };
+
+        let region = vk::BufferImageCopy {
+            buffer_offset: 0,
+            buffer_row_length: 0,
+            buffer_image_height: 0,
+            image_subresource: vk::ImageSubresourceLayers {
+                aspect_mask,
+                mip_level: 0,
+                base_array_layer: 0,
+                layer_count: self.num_of_layers,
+            },
+            image_offset: vk::Offset3D::default(),
+            image_extent: vk::Extent3D {
+                width: self.size.0,
+                height: self.size.1,
+                depth: 1,
+            },
+        };
+
+        cmd_pool.execute_one_time(|cmd_buf| {
+            cmd_buf.transition_image_layout(
+                self,
+                layout,
+                vk::ImageLayout::TRANSFER_SRC_OPTIMAL,
+                range,
+            );
+            cmd_buf.copy_image_to_buffer(self, &buffer, std::slice::from_ref(&region));
+            cmd_buf.transition_image_layout(
+                self,
+                vk::ImageLayout::TRANSFER_SRC_OPTIMAL,
+                layout,
+                range,
+            );
+
+            return Ok(());
+        })?;
+
+        return Ok(buffer);
+    }
 }
 
 //-----------------------------------------------------------------------------
@@ -208,6 +525,7 @@ impl Image {
 impl Drop for Image {
     fn drop(&mut self) {
         self.free_memory();
+        crate::debug::leaks::untrack("Image", self.image);
         unsafe {
             self.device.destroy_image(self.image, None);
         }
@@ -1,5 +1,6 @@
 //-----------------------------------------------------------------------------
-use anyhow::{anyhow, Result};
+use crate::error::{bail, ensure};
+use crate::Result;
 use ash::vk;
 //-----------------------------------------------------------------------------
 
@@ -40,8 +41,9 @@ impl Memory {
     where
         T: Copy,
     {
-        anyhow::ensure!(
+        ensure!(
             self.can_be_mapped(),
+            InvalidUsage,
             "Buffer cannot be mapped to write memory"
         );
 
@@ -64,8 +66,9 @@ impl Memory {
     }
 
     pub fn map(&mut self) -> Result<()> {
-        anyhow::ensure!(
+        ensure!(
             !self.is_mapped(),
+            InvalidUsage,
             "Trying to map an already mapped GPU memory"
         );
 
@@ -89,20 +92,85 @@ impl Memory {
 
     /// Write data to mapped memory
     pub fn write<T>(&mut self, data: &[T]) -> Result<()>
+    where
+        T: Copy,
+    {
+        return self.write_at(0, data);
+    }
+
+    /// Write data to mapped memory starting at `offset` bytes
+    pub fn write_at<T>(&mut self, offset: u64, data: &[T]) -> Result<()>
     where
         T: Copy,
     {
         let buffer_size = size_of_val(data) as u64;
 
-        anyhow::ensure!(
-            self.size >= buffer_size,
+        ensure!(
+            self.size >= offset + buffer_size,
+            InvalidUsage,
             "Buffer memory is smaller than the data that is being written to it"
         );
 
-        anyhow::ensure!(self.is_mapped(), "Trying to write to unmapped GPU memory");
+        ensure!(
+            self.is_mapped(),
+            InvalidUsage,
+            "Trying to write to unmapped GPU memory"
+        );
+
+        unsafe {
+            std::ptr::copy_nonoverlapping(
+                data.as_ptr().cast::<u8>(),
+                self.data_ptr.cast::<u8>().add(offset as usize),
+                size_of_val(data),
+            );
+        }
+
+        return Ok(());
+    }
+
+    /// Map the buffer and read data from it
+    pub fn map_and_read<T>(&mut self, data: &mut [T]) -> Result<()>
+    where
+        T: Copy,
+    {
+        ensure!(
+            self.can_be_mapped(),
+            InvalidUsage,
+            "Buffer cannot be mapped to read memory"
+        );
+
+        self.map()?;
+        let res = self.read(data);
+        self.unmap();
+
+        return res;
+    }
+
+    /// Read data from mapped memory
+    pub fn read<T>(&self, data: &mut [T]) -> Result<()>
+    where
+        T: Copy,
+    {
+        let buffer_size = size_of_val(data) as u64;
+
+        ensure!(
+            self.size >= buffer_size,
+            InvalidUsage,
+            "Buffer memory is smaller than the data that is being read from it"
+        );
+
+        ensure!(
+            self.is_mapped(),
+            InvalidUsage,
+            "Trying to read from unmapped GPU memory"
+        );
 
         unsafe {
-            std::ptr::copy_nonoverlapping(data.as_ptr().cast(), self.data_ptr, size_of_val(data));
+            std::ptr::copy_nonoverlapping(
+                self.data_ptr.cast_const(),
+                data.as_mut_ptr().cast(),
+                size_of_val(data),
+            );
         }
 
         return Ok(());
@@ -112,6 +180,21 @@ impl Memory {
         device: &crate::DeviceRef,
         memory_requirements: vk::MemoryRequirements,
         properties: vk::MemoryPropertyFlags,
+    ) -> Result<Self> {
+        return Self::alloc_with_flags(
+            device,
+            memory_requirements,
+            properties,
+            vk::MemoryAllocateFlags::empty(),
+        );
+    }
+
+    /// Allocate memory with additional allocation flags (e.g. `DEVICE_ADDRESS`)
+    pub(crate) fn alloc_with_flags(
+        device: &crate::DeviceRef,
+        memory_requirements: vk::MemoryRequirements,
+        properties: vk::MemoryPropertyFlags,
+        alloc_flags: vk::MemoryAllocateFlags,
     ) -> Result<Self> {
         soh_log::log_debug!(
             "Allocating {} bytes of GPU memory",
@@ -125,17 +208,26 @@ impl Memory {
             .physical()
             .find_memory_type(memory_requirements.memory_type_bits, properties)
         else {
-            return Err(anyhow!("Failed to find GPU memory type"));
+            bail!(Unsupported, "Failed to find GPU memory type");
         };
 
         /*
          * Allocate memory
          */
-        let alloc_info = vk::MemoryAllocateInfo::default()
+        let mut alloc_flags_info = vk::MemoryAllocateFlagsInfo::default().flags(alloc_flags);
+
+        let mut alloc_info = vk::MemoryAllocateInfo::default()
             .allocation_size(memory_requirements.size)
             .memory_type_index(memory_type_index);
 
+        if !alloc_flags.is_empty() {
+            alloc_info = alloc_info.push_next(&mut alloc_flags_info);
+        }
+
         let memory = unsafe { device.allocate_memory(&alloc_info, None)? };
+        crate::debug::leaks::track("Memory", memory);
+
+        crate::debug::stats::record_memory_alloc(memory_requirements.size);
 
         return Ok(Memory {
             device: device.clone(),
@@ -153,9 +245,12 @@ impl Drop for Memory {
     fn drop(&mut self) {
         soh_log::log_debug!("Freeing {} bytes of GPU memory", self.size);
 
+        crate::debug::leaks::untrack("Memory", self.memory);
         unsafe {
             self.device.free_memory(**self, None);
         }
+
+        crate::debug::stats::record_memory_free(self.size);
     }
 }
 
@@ -2,13 +2,15 @@
 mod buffer;
 mod image;
 mod memory;
+mod texture;
 //-----------------------------------------------------------------------------
 pub use buffer::*;
 pub use image::*;
 pub use memory::*;
+pub use texture::*;
 //-----------------------------------------------------------------------------
 
-use anyhow::Result;
+use crate::Result;
 use ash::vk;
 
 //-----------------------------------------------------------------------------
@@ -23,30 +25,17 @@ pub fn copy_buffer(
     assert!(size <= src.memory_size());
     assert!(size <= dst.memory_size());
 
-    /*
-     * Create transfer command buffer
-     */
-    let cmd_buf = transfer_pool.allocate_buffer(crate::cmd::BufferLevel::Primary)?;
-
-    let begin_info =
-        vk::CommandBufferBeginInfo::default().flags(vk::CommandBufferUsageFlags::ONE_TIME_SUBMIT);
-
     let copy_info = vk::BufferCopy {
         size,
         ..Default::default()
     };
 
-    unsafe {
-        device.begin_command_buffer(*cmd_buf, &begin_info)?;
-        device.cmd_copy_buffer(*cmd_buf, **src, **dst, std::slice::from_ref(&copy_info));
-        device.end_command_buffer(*cmd_buf)?;
-    }
-
-    cmd_buf.submit_and_wait()?;
-
-    unsafe {
-        device.free_command_buffers(**transfer_pool, std::slice::from_ref(&cmd_buf));
-    }
+    transfer_pool.execute_one_time(|cmd_buf| {
+        unsafe {
+            device.cmd_copy_buffer(**cmd_buf, **src, **dst, std::slice::from_ref(&copy_info));
+        }
+        return Ok(());
+    })?;
 
     return Ok(());
 }
@@ -60,8 +49,28 @@ pub fn format_size(format: vk::Format) -> u64 {
         vk::Format::B8G8R8A8_UNORM => 4,
         vk::Format::B8G8R8A8_SRGB => 4,
         vk::Format::R32G32B32A32_SFLOAT => 16,
+        vk::Format::D16_UNORM => 2,
+        vk::Format::D32_SFLOAT | vk::Format::X8_D24_UNORM_PACK32 => 4,
+        vk::Format::S8_UINT => 1,
         _ => panic!("Unsupported format"),
     }
 }
 
 //-----------------------------------------------------------------------------
+/// Get the image aspect flags for a particular format
+pub fn format_aspect_mask(format: vk::Format) -> vk::ImageAspectFlags {
+    return match format {
+        vk::Format::D16_UNORM | vk::Format::D32_SFLOAT | vk::Format::X8_D24_UNORM_PACK32 => {
+            vk::ImageAspectFlags::DEPTH
+        }
+        vk::Format::S8_UINT => vk::ImageAspectFlags::STENCIL,
+        vk::Format::D16_UNORM_S8_UINT
+        | vk::Format::D24_UNORM_S8_UINT
+        | vk::Format::D32_SFLOAT_S8_UINT => {
+            vk::ImageAspectFlags::DEPTH | vk::ImageAspectFlags::STENCIL
+        }
+        _ => vk::ImageAspectFlags::COLOR,
+    };
+}
+
+//-----------------------------------------------------------------------------
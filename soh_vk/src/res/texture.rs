@@ -0,0 +1,175 @@
+//-----------------------------------------------------------------------------
+use crate::error::ensure;
+use crate::Result;
+use ash::vk;
+//-----------------------------------------------------------------------------
+
+/// Sampled image with its view and sampler, ready to be bound to a combined
+/// image sampler descriptor
+pub struct Texture {
+    device: crate::DeviceRef,
+
+    image: super::Image,
+    image_view: vk::ImageView,
+    sampler: vk::Sampler,
+}
+
+//-----------------------------------------------------------------------------
+// Getters
+impl Texture {
+    pub fn image(&self) -> &super::Image {
+        return &self.image;
+    }
+    pub fn image_view(&self) -> vk::ImageView {
+        return self.image_view;
+    }
+    pub fn sampler(&self) -> vk::Sampler {
+        return self.sampler;
+    }
+    pub fn size(&self) -> (u32, u32) {
+        return self.image.size();
+    }
+}
+
+//-----------------------------------------------------------------------------
+// Constructor
+impl Texture {
+    /// Uploads RGBA8 pixels ( row by row, without padding ) into a mipmapped
+    /// texture with linear filtering and repeat addressing.
+    ///
+    /// * `srgb`: whether the pixels are colors in sRGB ( base color textures )
+    ///   or linear data ( normal maps, masks )
+    pub fn new_rgba8(
+        context: &crate::VulkanContext,
+        size: (u32, u32),
+        pixels: &[u8],
+        srgb: bool,
+    ) -> Result<Self> {
+        ensure!(
+            pixels.len() as u64 == size.0 as u64 * size.1 as u64 * 4,
+            InvalidUsage,
+            "Texture of size {}x{} needs {} bytes of RGBA8 pixels, got {}",
+            size.0,
+            size.1,
+            size.0 as u64 * size.1 as u64 * 4,
+            pixels.len()
+        );
+
+        let device = context.device();
+        let format = if srgb {
+            vk::Format::R8G8B8A8_SRGB
+        } else {
+            vk::Format::R8G8B8A8_UNORM
+        };
+        let num_of_mip_levels = size.0.max(size.1).max(1).ilog2() + 1;
+
+        /*
+         * Create the image
+         */
+        let mut image = super::ImageBuilder::new()
+            .format(format)
+            .size(size)
+            .usage(
+                vk::ImageUsageFlags::SAMPLED
+                    | vk::ImageUsageFlags::TRANSFER_SRC
+                    | vk::ImageUsageFlags::TRANSFER_DST,
+            )
+            .tiling(vk::ImageTiling::OPTIMAL)
+            .mip_levels(num_of_mip_levels)
+            .build(device)?;
+
+        image.allocate_memory(vk::MemoryPropertyFlags::DEVICE_LOCAL)?;
+
+        /*
+         * Copy the pixels to the first mip level
+         */
+        let staging_buffer =
+            super::Buffer::new_mapped(device, pixels, crate::BufferUsageFlags::TRANSFER_SRC)?;
+
+        // Both pools may only be used from the main thread
+        let cmd_pool = unsafe { context.cmd_pool_graphics() };
+
+        cmd_pool.execute_one_time(|cmd_buf| {
+            cmd_buf.transition_image_layout(
+                &image,
+                vk::ImageLayout::UNDEFINED,
+                vk::ImageLayout::TRANSFER_DST_OPTIMAL,
+                image.full_range(),
+            );
+
+            let region = vk::BufferImageCopy::default()
+                .image_subresource(vk::ImageSubresourceLayers {
+                    aspect_mask: vk::ImageAspectFlags::COLOR,
+                    mip_level: 0,
+                    base_array_layer: 0,
+                    layer_count: 1,
+                })
+                .image_extent(vk::Extent3D {
+                    width: size.0,
+                    height: size.1,
+                    depth: 1,
+                });
+
+            cmd_buf.copy_buffer_to_image(&staging_buffer, &image, std::slice::from_ref(&region));
+
+            return Ok(());
+        })?;
+
+        /*
+         * Fill the rest of the mip levels
+         */
+        image.generate_mipmaps(
+            cmd_pool,
+            vk::ImageLayout::TRANSFER_DST_OPTIMAL,
+            vk::ImageLayout::SHADER_READ_ONLY_OPTIMAL,
+        )?;
+
+        /*
+         * Create the view and the sampler
+         */
+        let view_create_info = vk::ImageViewCreateInfo::default()
+            .image(*image)
+            .view_type(vk::ImageViewType::TYPE_2D)
+            .format(format)
+            .subresource_range(image.full_range());
+
+        let image_view = unsafe { device.create_image_view(&view_create_info, None)? };
+
+        let sampler_create_info = vk::SamplerCreateInfo::default()
+            .mag_filter(vk::Filter::LINEAR)
+            .min_filter(vk::Filter::LINEAR)
+            .mipmap_mode(vk::SamplerMipmapMode::LINEAR)
+            .address_mode_u(vk::SamplerAddressMode::REPEAT)
+            .address_mode_v(vk::SamplerAddressMode::REPEAT)
+            .address_mode_w(vk::SamplerAddressMode::REPEAT)
+            .max_lod(num_of_mip_levels as f32);
+
+        let sampler = match unsafe { device.create_sampler(&sampler_create_info, None) } {
+            Ok(sampler) => sampler,
+            Err(e) => {
+                unsafe { device.destroy_image_view(image_view, None) };
+                return Err(e.into());
+            }
+        };
+
+        return Ok(Texture {
+            device: device.clone(),
+            image,
+            image_view,
+            sampler,
+        });
+    }
+}
+
+//-----------------------------------------------------------------------------
+// Drop
+impl Drop for Texture {
+    fn drop(&mut self) {
+        unsafe {
+            self.device.destroy_sampler(self.sampler, None);
+            self.device.destroy_image_view(self.image_view, None);
+        }
+    }
+}
+
+//-----------------------------------------------------------------------------
@@ -0,0 +1,291 @@
+//-----------------------------------------------------------------------------
+use crate::error::bail;
+use crate::Result;
+use soh_math::{Mat4, Quaternion, Transform, Vec2, Vec3, Vec4};
+use std::collections::HashMap;
+//-----------------------------------------------------------------------------
+
+/// Meshes, materials and the node hierarchy loaded from a glTF 2.0 file.
+///
+/// Only triangle primitives and base color textures are loaded. Every glTF
+/// mesh becomes a [crate::Mesh] with a submesh per primitive, the material of
+/// a submesh indexes [Scene::materials].
+pub struct Scene {
+    pub meshes: Vec<crate::Mesh>,
+    pub materials: Vec<Material>,
+    pub textures: Vec<crate::Texture>,
+
+    pub nodes: Vec<SceneNode>,
+    /// Nodes without a parent
+    pub roots: Vec<usize>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Material {
+    pub name: Option<String>,
+    /// Multiplied with the texture
+    pub base_color: Vec4<f32>,
+    /// Index into [Scene::textures]
+    pub base_color_texture: Option<usize>,
+}
+
+#[derive(Debug, Clone)]
+pub struct SceneNode {
+    pub name: Option<String>,
+    /// Relative to the parent
+    pub transform: Transform<f32>,
+    /// Index into [Scene::meshes]
+    pub mesh: Option<usize>,
+    /// Indexes into [Scene::nodes]
+    pub children: Vec<usize>,
+}
+
+/// Vertex of the loaded meshes. Attributes without data in the file are zero
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SceneVertex {
+    pub position: Vec3<f32>,
+    pub normal: Vec3<f32>,
+    pub uv: Vec2<f32>,
+}
+
+impl crate::Vertex for SceneVertex {
+    fn get_attribute_description() -> Vec<crate::vertex::AttributeDescription> {
+        return vec![
+            crate::vertex::AttributeDescription {
+                location: 0,
+                format: crate::Format::R32G32B32_SFLOAT,
+                offset: std::mem::offset_of!(Self, position) as u32,
+            },
+            crate::vertex::AttributeDescription {
+                location: 1,
+                format: crate::Format::R32G32B32_SFLOAT,
+                offset: std::mem::offset_of!(Self, normal) as u32,
+            },
+            crate::vertex::AttributeDescription {
+                location: 2,
+                format: crate::Format::R32G32_SFLOAT,
+                offset: std::mem::offset_of!(Self, uv) as u32,
+            },
+        ];
+    }
+}
+
+//-----------------------------------------------------------------------------
+// Constructor
+impl Scene {
+    /// Loads the default scene ( or the first one ) of a .gltf or .glb file
+    pub fn load_gltf(
+        context: &crate::VulkanContext,
+        path: impl AsRef<std::path::Path>,
+    ) -> Result<Self> {
+        let (document, buffers, images) = gltf::import(path)?;
+
+        /*
+         * Materials and their base color textures
+         */
+        let mut textures = Vec::new();
+        let mut texture_indexes = HashMap::new();
+
+        let mut materials = Vec::new();
+
+        for material in document.materials() {
+            let pbr = material.pbr_metallic_roughness();
+
+            let base_color_texture = match pbr.base_color_texture() {
+                Some(info) => {
+                    let source = info.texture().source().index();
+
+                    if let Some(&idx) = texture_indexes.get(&source) {
+                        Some(idx)
+                    } else {
+                        textures.push(load_texture(context, &images[source])?);
+                        texture_indexes.insert(source, textures.len() - 1);
+                        Some(textures.len() - 1)
+                    }
+                }
+                None => None,
+            };
+
+            let [r, g, b, a] = pbr.base_color_factor();
+
+            materials.push(Material {
+                name: material.name().map(str::to_owned),
+                base_color: Vec4::new(r, g, b, a),
+                base_color_texture,
+            });
+        }
+
+        // Primitives without a material use the default one
+        let default_material = materials.len() as u32;
+        materials.push(Material {
+            name: None,
+            base_color: Vec4::new(1.0, 1.0, 1.0, 1.0),
+            base_color_texture: None,
+        });
+
+        /*
+         * Meshes
+         */
+        let mut meshes = Vec::new();
+        let mut mesh_indexes = HashMap::new();
+
+        for mesh in document.meshes() {
+            let mut builder = crate::MeshBuilder::new();
+
+            for primitive in mesh.primitives() {
+                if primitive.mode() != gltf::mesh::Mode::Triangles {
+                    soh_log::log_warning!(
+                        "Skipping a {:?} primitive of the glTF mesh {}",
+                        primitive.mode(),
+                        mesh.index()
+                    );
+                    continue;
+                }
+
+                let reader = primitive.reader(|buffer| Some(&buffers[buffer.index()]));
+
+                let Some(positions) = reader.read_positions() else {
+                    continue;
+                };
+
+                let mut vertices = positions
+                    .map(|[x, y, z]| SceneVertex {
+                        position: Vec3::new(x, y, z),
+                        ..Default::default()
+                    })
+                    .collect::<Vec<_>>();
+
+                if let Some(normals) = reader.read_normals() {
+                    for (vertex, [x, y, z]) in vertices.iter_mut().zip(normals) {
+                        vertex.normal = Vec3::new(x, y, z);
+                    }
+                }
+
+                if let Some(uvs) = reader.read_tex_coords(0) {
+                    for (vertex, [u, v]) in vertices.iter_mut().zip(uvs.into_f32()) {
+                        vertex.uv = Vec2::new(u, v);
+                    }
+                }
+
+                let indexes = match reader.read_indices() {
+                    Some(indexes) => indexes.into_u32().collect::<Vec<_>>(),
+                    None => (0..vertices.len() as u32).collect(),
+                };
+
+                let material = primitive
+                    .material()
+                    .index()
+                    .map_or(default_material, |idx| idx as u32);
+
+                builder = builder.submesh(&vertices, &indexes, material);
+            }
+
+            if builder.submeshes().is_empty() {
+                continue;
+            }
+
+            mesh_indexes.insert(mesh.index(), meshes.len());
+            meshes.push(builder.build(context)?);
+        }
+
+        /*
+         * Nodes
+         */
+        let nodes = document
+            .nodes()
+            .map(|node| {
+                let (translation, [x, y, z, w], scale) = node.transform().decomposed();
+
+                return SceneNode {
+                    name: node.name().map(str::to_owned),
+                    transform: Transform::new(
+                        Vec3::from(translation),
+                        Quaternion::new(w, Vec3::new(x, y, z)),
+                        Vec3::from(scale),
+                    ),
+                    mesh: node
+                        .mesh()
+                        .and_then(|mesh| mesh_indexes.get(&mesh.index()).copied()),
+                    children: node.children().map(|child| child.index()).collect(),
+                };
+            })
+            .collect::<Vec<_>>();
+
+        let roots = match document.default_scene().or(document.scenes().next()) {
+            Some(scene) => scene.nodes().map(|node| node.index()).collect(),
+            // Without scenes every node without a parent is a root
+            None => {
+                let mut is_child = vec![false; nodes.len()];
+                for &child in nodes.iter().flat_map(|node| &node.children) {
+                    is_child[child] = true;
+                }
+                (0..nodes.len()).filter(|&idx| !is_child[idx]).collect()
+            }
+        };
+
+        return Ok(Scene {
+            meshes,
+            materials,
+            textures,
+            nodes,
+            roots,
+        });
+    }
+}
+
+//-----------------------------------------------------------------------------
+// Specific implementation
+impl Scene {
+    /// Transforms of the nodes from the space of the node to the space of the
+    /// scene ( indexed like [Scene::nodes] ). Nodes which aren't reachable
+    /// from the roots get the identity.
+    pub fn world_transforms(&self) -> Vec<Mat4<f32>> {
+        let mut res = vec![Mat4::identity(); self.nodes.len()];
+
+        let mut stack = self
+            .roots
+            .iter()
+            .map(|&root| (root, Mat4::identity()))
+            .collect::<Vec<_>>();
+
+        while let Some((idx, parent)) = stack.pop() {
+            let node = &self.nodes[idx];
+            let world = parent * node.transform.to_mat4();
+
+            res[idx] = world;
+            stack.extend(node.children.iter().map(|&child| (child, world)));
+        }
+
+        return res;
+    }
+}
+
+//-----------------------------------------------------------------------------
+
+fn load_texture(
+    context: &crate::VulkanContext,
+    image: &gltf::image::Data,
+) -> Result<crate::Texture> {
+    use gltf::image::Format;
+
+    let pixels = &image.pixels;
+
+    let rgba = match image.format {
+        Format::R8G8B8A8 => pixels.clone(),
+        Format::R8G8B8 => pixels
+            .chunks_exact(3)
+            .flat_map(|p| [p[0], p[1], p[2], u8::MAX])
+            .collect(),
+        Format::R8G8 => pixels
+            .chunks_exact(2)
+            .flat_map(|p| [p[0], p[0], p[0], p[1]])
+            .collect(),
+        Format::R8 => pixels.iter().flat_map(|&p| [p, p, p, u8::MAX]).collect(),
+        format => bail!(Unsupported, "Unsupported glTF image format {:?}", format),
+    };
+
+    return crate::Texture::new_rgba8(context, (image.width, image.height), &rgba, true);
+}
+
+//-----------------------------------------------------------------------------
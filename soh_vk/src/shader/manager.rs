@@ -1,5 +1,6 @@
 //-----------------------------------------------------------------------------
-use anyhow::Result;
+use crate::error::ensure;
+use crate::Result;
 use std::path::Path;
 //-----------------------------------------------------------------------------
 
@@ -58,7 +59,6 @@ impl Default for ManagerBuilder {
 
 pub struct Manager {
     compiler: shaderc::Compiler,
-    options: shaderc::CompileOptions<'static>,
 
     mode: Mode,
     recompile: bool,
@@ -72,15 +72,8 @@ impl Manager {
         // Create compiler
         let compiler = shaderc::Compiler::new()?;
 
-        // Create options
-        let mut options = shaderc::CompileOptions::new()?;
-
-        options.set_source_language(shaderc::SourceLanguage::GLSL);
-        options.set_optimization_level(shaderc::OptimizationLevel::Performance);
-
         let manager = Manager {
             compiler,
-            options,
 
             mode,
             recompile,
@@ -108,9 +101,39 @@ impl Manager {
         return Ok(artifact.as_binary().to_owned());
     }
 
+    /// Compile GLSL source code which doesn't come from a file ( shaders built
+    /// into the library ). `name` is only used in the error messages.
+    pub fn compile_source(
+        &self,
+        source: &str,
+        kind: shaderc::ShaderKind,
+        name: &str,
+    ) -> Result<Vec<u32>> {
+        let artifact = self.compiler.compile_into_spirv(
+            source,
+            kind,
+            name,
+            "main",
+            Some(&Self::compile_options()?),
+        )?;
+
+        return Ok(artifact.as_binary().to_owned());
+    }
+
+    // Created for every compilation, the options can't be sent to another
+    // thread ( so the manager could not be either )
+    fn compile_options() -> Result<shaderc::CompileOptions<'static>> {
+        let mut options = shaderc::CompileOptions::new()?;
+
+        options.set_source_language(shaderc::SourceLanguage::GLSL);
+        options.set_optimization_level(shaderc::OptimizationLevel::Performance);
+
+        return Ok(options);
+    }
+
     // Loop over all shaders in `dir` and compile them
     fn precompile(&self) -> Result<()> {
-        let dir_iterator = std::fs::read_dir(&self.directory)?.filter_map(Result::ok);
+        let dir_iterator = std::fs::read_dir(&self.directory)?.filter_map(std::io::Result::ok);
 
         for entry in dir_iterator {
             let path = entry.path();
@@ -144,6 +167,14 @@ impl Manager {
                 return shaderc::ShaderKind::Vertex;
             } else if ext == "frag" {
                 return shaderc::ShaderKind::Fragment;
+            } else if ext == "comp" {
+                return shaderc::ShaderKind::Compute;
+            } else if ext == "tesc" {
+                return shaderc::ShaderKind::TessControl;
+            } else if ext == "tese" {
+                return shaderc::ShaderKind::TessEvaluation;
+            } else if ext == "geom" {
+                return shaderc::ShaderKind::Geometry;
             } else {
                 soh_log::log_warning!("Couldn't deduce shader type for file \"{}\". Defaulting to \"shaderc::ShaderKind::InferFromSource\"", path.display());
                 return shaderc::ShaderKind::InferFromSource;
@@ -184,7 +215,7 @@ impl Manager {
             shader_kind,
             path_str,
             "main",
-            Some(&self.options),
+            Some(&Self::compile_options()?),
         )?;
 
         save_compiled_shader(path, &artifact)?;
@@ -225,8 +256,9 @@ impl Manager {
         // This function looks very ugly
         let path = path.as_ref();
 
-        anyhow::ensure!(
+        ensure!(
             path.is_file(),
+            InvalidUsage,
             "Trying to get binary filename for a directory"
         );
 
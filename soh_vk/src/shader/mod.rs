@@ -1,10 +1,11 @@
 //-----------------------------------------------------------------------------
 mod manager;
+pub mod reflect;
 //-----------------------------------------------------------------------------
 pub use manager::*;
 //-----------------------------------------------------------------------------
 
-use anyhow::Result;
+use crate::Result;
 use ash::vk::{self, Handle};
 
 //-----------------------------------------------------------------------------
@@ -12,6 +13,22 @@ use ash::vk::{self, Handle};
 pub struct Shader {
     device: crate::DeviceRef,
     shader: vk::ShaderModule,
+
+    // Kept to recreate the shader on another device
+    code: Vec<u32>,
+}
+
+//-----------------------------------------------------------------------------
+// Getters
+impl Shader {
+    /// SPIR-V code of the shader
+    pub fn code(&self) -> &[u32] {
+        return &self.code;
+    }
+    /// Input variables of the shader, see [reflect::shader_inputs]
+    pub fn inputs(&self) -> Result<Vec<reflect::ShaderInput>> {
+        return reflect::shader_inputs(&self.code);
+    }
 }
 
 //-----------------------------------------------------------------------------
@@ -20,15 +37,39 @@ impl Shader {
     pub fn new(device: &crate::DeviceRef, shader_manager: &Manager, path: &str) -> Result<Shader> {
         let shader_code = shader_manager.get_shader(path)?;
 
-        let create_info = vk::ShaderModuleCreateInfo::default().code(&shader_code);
+        return Self::from_spirv(device, &shader_code);
+    }
+
+    /// Create the shader from GLSL source code, see [Manager::compile_source]
+    pub fn from_glsl(
+        device: &crate::DeviceRef,
+        shader_manager: &Manager,
+        source: &str,
+        kind: shaderc::ShaderKind,
+        name: &str,
+    ) -> Result<Shader> {
+        let shader_code = shader_manager.compile_source(source, kind, name)?;
+
+        return Self::from_spirv(device, &shader_code);
+    }
+
+    pub fn from_spirv(device: &crate::DeviceRef, shader_code: &[u32]) -> Result<Shader> {
+        let create_info = vk::ShaderModuleCreateInfo::default().code(shader_code);
 
         let shader = unsafe { device.create_shader_module(&create_info, None)? };
+        crate::debug::leaks::track("Shader", shader);
 
         return Ok(Shader {
             device: device.clone(),
             shader,
+            code: shader_code.to_vec(),
         });
     }
+
+    /// Create the same shader on the `device` ( after the device was lost )
+    pub fn recreate(&self, device: &crate::DeviceRef) -> Result<Shader> {
+        return Self::from_spirv(device, &self.code);
+    }
 }
 
 //-----------------------------------------------------------------------------
@@ -36,6 +77,7 @@ impl Shader {
 impl Drop for Shader {
     fn drop(&mut self) {
         soh_log::log_debug!("Destroying shader (0x{:x})", self.shader.as_raw());
+        crate::debug::leaks::untrack("Shader", self.shader);
         unsafe {
             self.device.destroy_shader_module(self.shader, None);
         }
@@ -0,0 +1,292 @@
+//-----------------------------------------------------------------------------
+//! Minimal SPIR-V reflection ( only what the pipelines validate against )
+//!
+//! See https://registry.khronos.org/SPIR-V/specs/unified1/SPIRV.html#_binary_form
+//-----------------------------------------------------------------------------
+use crate::error::{bail, ensure};
+use crate::Result;
+use std::collections::HashMap;
+//-----------------------------------------------------------------------------
+const MAGIC: u32 = 0x0723_0203;
+const HEADER_LEN: usize = 5;
+
+const OP_DECORATE: u32 = 71;
+const OP_TYPE_INT: u32 = 21;
+const OP_TYPE_FLOAT: u32 = 22;
+const OP_TYPE_VECTOR: u32 = 23;
+const OP_TYPE_MATRIX: u32 = 24;
+const OP_TYPE_POINTER: u32 = 32;
+const OP_VARIABLE: u32 = 59;
+
+const DECORATION_LOCATION: u32 = 30;
+const STORAGE_CLASS_INPUT: u32 = 1;
+//-----------------------------------------------------------------------------
+
+/// Numeric type of the components of an input
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NumericType {
+    Float,
+    Sint,
+    Uint,
+}
+
+/// A user-defined input variable of a shader ( built-ins are skipped )
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ShaderInput {
+    pub location: u32,
+    pub numeric_type: NumericType,
+    /// Number of components ( 1 for scalars )
+    pub num_of_components: u32,
+}
+
+#[derive(Clone, Copy)]
+enum Type {
+    Scalar(NumericType),
+    Vector(NumericType, u32),
+    // Column type and number of columns
+    Matrix(u32, u32),
+    // Pointee type
+    Pointer(u32),
+}
+
+//-----------------------------------------------------------------------------
+
+/// Input variables of the shader, sorted by location. Matrices take a
+/// location per column and are returned as a vector per column.
+pub fn shader_inputs(code: &[u32]) -> Result<Vec<ShaderInput>> {
+    ensure!(
+        code.len() >= HEADER_LEN && code[0] == MAGIC,
+        InvalidUsage,
+        "Shader code isn't SPIR-V"
+    );
+
+    let mut types = HashMap::new();
+    let mut locations = HashMap::new();
+    // Pointer type of every input variable
+    let mut input_variables = Vec::new();
+
+    /*
+     * Collect the types, the decorations and the variables
+     */
+    let mut idx = HEADER_LEN;
+    while idx < code.len() {
+        let word_count = (code[idx] >> 16) as usize;
+        let opcode = code[idx] & 0xffff;
+
+        ensure!(
+            word_count > 0 && idx + word_count <= code.len(),
+            InvalidUsage,
+            "Malformed SPIR-V instruction at word {}",
+            idx
+        );
+
+        let operands = &code[idx + 1..idx + word_count];
+        idx += word_count;
+
+        match (opcode, operands) {
+            (OP_DECORATE, &[target, DECORATION_LOCATION, location, ..]) => {
+                locations.insert(target, location);
+            }
+            (OP_TYPE_INT, &[result, _width, signedness, ..]) => {
+                let numeric_type = if signedness == 0 {
+                    NumericType::Uint
+                } else {
+                    NumericType::Sint
+                };
+                types.insert(result, Type::Scalar(numeric_type));
+            }
+            (OP_TYPE_FLOAT, &[result, ..]) => {
+                types.insert(result, Type::Scalar(NumericType::Float));
+            }
+            (OP_TYPE_VECTOR, &[result, component_type, count, ..]) => {
+                if let Some(&Type::Scalar(numeric_type)) = types.get(&component_type) {
+                    types.insert(result, Type::Vector(numeric_type, count));
+                }
+            }
+            (OP_TYPE_MATRIX, &[result, column_type, count, ..]) => {
+                types.insert(result, Type::Matrix(column_type, count));
+            }
+            (OP_TYPE_POINTER, &[result, _storage_class, pointee, ..]) => {
+                types.insert(result, Type::Pointer(pointee));
+            }
+            (OP_VARIABLE, &[result_type, result, STORAGE_CLASS_INPUT, ..]) => {
+                input_variables.push((result, result_type));
+            }
+            _ => {}
+        }
+    }
+
+    /*
+     * Resolve the inputs with a location
+     */
+    let mut res = Vec::new();
+
+    for (variable, pointer_type) in input_variables {
+        // Built-ins don't have a location
+        let Some(&location) = locations.get(&variable) else {
+            continue;
+        };
+
+        let Some(&Type::Pointer(pointee)) = types.get(&pointer_type) else {
+            bail!(InvalidUsage, "Input variable {} isn't a pointer", variable);
+        };
+
+        let (columns, column_type) = match types.get(&pointee) {
+            Some(&Type::Matrix(column_type, columns)) => (columns, column_type),
+            _ => (1, pointee),
+        };
+
+        let (numeric_type, num_of_components) = match types.get(&column_type) {
+            Some(&Type::Scalar(numeric_type)) => (numeric_type, 1),
+            Some(&Type::Vector(numeric_type, count)) => (numeric_type, count),
+            // Arrays and structs aren't used as vertex inputs here
+            _ => bail!(
+                Unsupported,
+                "Input at location {} has an unsupported type",
+                location
+            ),
+        };
+
+        for column in 0..columns {
+            res.push(ShaderInput {
+                location: location + column,
+                numeric_type,
+                num_of_components,
+            });
+        }
+    }
+
+    res.sort_by_key(|input| input.location);
+
+    return Ok(res);
+}
+
+//-----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const OP_TYPE_BOOL: u32 = 20;
+    const STORAGE_CLASS_OUTPUT: u32 = 3;
+
+    /// Instruction with the word count in the upper half of the first word
+    fn inst(opcode: u32, operands: &[u32]) -> Vec<u32> {
+        let mut words = vec![((operands.len() as u32 + 1) << 16) | opcode];
+        words.extend_from_slice(operands);
+        return words;
+    }
+
+    fn module(instructions: &[Vec<u32>]) -> Vec<u32> {
+        let mut code = vec![MAGIC, 0x0001_0000, 0, 100, 0];
+        for instruction in instructions {
+            code.extend_from_slice(instruction);
+        }
+        return code;
+    }
+
+    /// Inputs of every numeric type, a vector width, a matrix, a built-in and an output
+    fn vertex_module() -> Vec<u32> {
+        return module(&[
+            inst(OP_DECORATE, &[20, DECORATION_LOCATION, 0]),
+            inst(OP_DECORATE, &[21, DECORATION_LOCATION, 1]),
+            inst(OP_DECORATE, &[22, DECORATION_LOCATION, 2]),
+            inst(OP_DECORATE, &[23, DECORATION_LOCATION, 3]),
+            inst(OP_DECORATE, &[25, DECORATION_LOCATION, 0]),
+            inst(OP_TYPE_FLOAT, &[1, 32]),
+            inst(OP_TYPE_INT, &[2, 32, 1]),
+            inst(OP_TYPE_INT, &[3, 32, 0]),
+            inst(OP_TYPE_VECTOR, &[4, 1, 3]),
+            inst(OP_TYPE_VECTOR, &[5, 3, 2]),
+            inst(OP_TYPE_VECTOR, &[6, 1, 4]),
+            inst(OP_TYPE_MATRIX, &[7, 6, 4]),
+            inst(OP_TYPE_POINTER, &[10, STORAGE_CLASS_INPUT, 4]),
+            inst(OP_TYPE_POINTER, &[11, STORAGE_CLASS_INPUT, 2]),
+            inst(OP_TYPE_POINTER, &[12, STORAGE_CLASS_INPUT, 5]),
+            inst(OP_TYPE_POINTER, &[13, STORAGE_CLASS_INPUT, 7]),
+            inst(OP_TYPE_POINTER, &[14, STORAGE_CLASS_INPUT, 1]),
+            inst(OP_TYPE_POINTER, &[15, STORAGE_CLASS_OUTPUT, 6]),
+            // Declared out of the location order
+            inst(OP_VARIABLE, &[13, 23, STORAGE_CLASS_INPUT]),
+            inst(OP_VARIABLE, &[10, 20, STORAGE_CLASS_INPUT]),
+            inst(OP_VARIABLE, &[11, 21, STORAGE_CLASS_INPUT]),
+            inst(OP_VARIABLE, &[12, 22, STORAGE_CLASS_INPUT]),
+            // Built-in without a location
+            inst(OP_VARIABLE, &[14, 24, STORAGE_CLASS_INPUT]),
+            inst(OP_VARIABLE, &[15, 25, STORAGE_CLASS_OUTPUT]),
+        ]);
+    }
+
+    fn input(location: u32, numeric_type: NumericType, num_of_components: u32) -> ShaderInput {
+        return ShaderInput {
+            location,
+            numeric_type,
+            num_of_components,
+        };
+    }
+
+    #[test]
+    fn test_shader_inputs() {
+        let inputs = shader_inputs(&vertex_module()).unwrap();
+
+        assert_eq!(
+            inputs,
+            [
+                input(0, NumericType::Float, 3),
+                input(1, NumericType::Sint, 1),
+                input(2, NumericType::Uint, 2),
+                // A location per column of the matrix
+                input(3, NumericType::Float, 4),
+                input(4, NumericType::Float, 4),
+                input(5, NumericType::Float, 4),
+                input(6, NumericType::Float, 4),
+            ]
+        );
+
+        // No inputs
+        assert!(shader_inputs(&module(&[])).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_malformed_module() {
+        let is_invalid = |code: &[u32]| {
+            return matches!(shader_inputs(code), Err(crate::Error::InvalidUsage(_)));
+        };
+
+        // Not SPIR-V
+        assert!(is_invalid(&[]));
+        let mut code = vertex_module();
+        code[0] = 0x1234_5678;
+        assert!(is_invalid(&code));
+
+        // The last instruction is longer than the module
+        let mut code = vertex_module();
+        code.truncate(code.len() - 1);
+        assert!(is_invalid(&code));
+
+        // Instruction without words
+        let mut code = vertex_module();
+        code.push(OP_TYPE_FLOAT);
+        assert!(is_invalid(&code));
+
+        // Input variable whose type isn't a pointer
+        let code = module(&[
+            inst(OP_DECORATE, &[20, DECORATION_LOCATION, 0]),
+            inst(OP_TYPE_FLOAT, &[1, 32]),
+            inst(OP_VARIABLE, &[1, 20, STORAGE_CLASS_INPUT]),
+        ]);
+        assert!(is_invalid(&code));
+
+        // Input of a type which isn't a number
+        let code = module(&[
+            inst(OP_DECORATE, &[20, DECORATION_LOCATION, 0]),
+            inst(OP_TYPE_BOOL, &[1]),
+            inst(OP_TYPE_POINTER, &[10, STORAGE_CLASS_INPUT, 1]),
+            inst(OP_VARIABLE, &[10, 20, STORAGE_CLASS_INPUT]),
+        ]);
+        assert!(matches!(
+            shader_inputs(&code),
+            Err(crate::Error::Unsupported(_))
+        ));
+    }
+}
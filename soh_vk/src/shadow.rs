@@ -0,0 +1,307 @@
+//-----------------------------------------------------------------------------
+use crate::Result;
+use ash::vk;
+use soh_math::{Mat4, Vec3};
+//-----------------------------------------------------------------------------
+
+/// Depth-only render target rendered from the point of view of a light.
+///
+/// Usage:
+/// 1. Create a pipeline for the shadow casters with [ShadowMap::create_pipeline]
+///    ( the vertex shader should output `light_view_proj * world_position` )
+/// 2. Set the light with [ShadowMap::set_directional_light]
+/// 3. Draw the casters between [ShadowMap::begin] and [ShadowMap::end]
+/// 4. In the main pass bind [ShadowMap::descriptor_image_info] as a
+///    `sampler2DShadow` and compare against the depth from
+///    [ShadowMap::texture_matrix] `* world_position`
+pub struct ShadowMap {
+    device: crate::DeviceRef,
+
+    size: u32,
+    format: vk::Format,
+
+    image: crate::Image,
+    // Owned by the framebuffer
+    image_view: vk::ImageView,
+    sampler: vk::Sampler,
+
+    render_pass: crate::RenderPass,
+    framebuffer: crate::Framebuffer,
+
+    light_view_proj: Mat4<f32>,
+}
+
+//-----------------------------------------------------------------------------
+// Getters
+impl ShadowMap {
+    pub fn size(&self) -> u32 {
+        return self.size;
+    }
+    pub fn format(&self) -> vk::Format {
+        return self.format;
+    }
+    pub fn image(&self) -> &crate::Image {
+        return &self.image;
+    }
+    pub fn image_view(&self) -> vk::ImageView {
+        return self.image_view;
+    }
+    /// Comparison sampler ( `sampler2DShadow` ), everything outside of the map
+    /// is lit
+    pub fn sampler(&self) -> vk::Sampler {
+        return self.sampler;
+    }
+    pub fn render_pass(&self) -> &crate::RenderPass {
+        return &self.render_pass;
+    }
+    /// World space to the clip space of the light
+    pub fn light_view_proj(&self) -> Mat4<f32> {
+        return self.light_view_proj;
+    }
+    /// World space to the shadow map: xy are texture coordinates and z is the
+    /// depth to compare with
+    pub fn texture_matrix(&self) -> Mat4<f32> {
+        #[rustfmt::skip]
+        let clip_to_uv = Mat4::new([
+            0.5, 0.0, 0.0, 0.0,
+            0.0, 0.5, 0.0, 0.0,
+            0.0, 0.0, 1.0, 0.0,
+            0.5, 0.5, 0.0, 1.0,
+        ]);
+
+        return clip_to_uv * self.light_view_proj;
+    }
+    /// For [descriptor::Set::update_combined_image_samplers](crate::descriptor::Set::update_combined_image_samplers)
+    pub fn descriptor_image_info(&self) -> vk::DescriptorImageInfo {
+        return vk::DescriptorImageInfo::default()
+            .sampler(self.sampler)
+            .image_view(self.image_view)
+            .image_layout(vk::ImageLayout::SHADER_READ_ONLY_OPTIMAL);
+    }
+}
+
+//-----------------------------------------------------------------------------
+// Constructor
+impl ShadowMap {
+    /// Creates a square shadow map with `size` texels per side
+    pub fn new(device: &crate::DeviceRef, size: u32) -> Result<Self> {
+        let format = Self::pick_format(device);
+        let format_features = device
+            .physical()
+            .format_properties(format)
+            .optimal_tiling_features;
+
+        /*
+         * Depth image
+         */
+        let mut image = crate::ImageBuilder::new()
+            .format(format)
+            .size((size, size))
+            .usage(vk::ImageUsageFlags::DEPTH_STENCIL_ATTACHMENT | vk::ImageUsageFlags::SAMPLED)
+            .tiling(vk::ImageTiling::OPTIMAL)
+            .build(device)?;
+
+        image.allocate_memory(vk::MemoryPropertyFlags::DEVICE_LOCAL)?;
+
+        let view_create_info = vk::ImageViewCreateInfo::default()
+            .image(*image)
+            .view_type(vk::ImageViewType::TYPE_2D)
+            .format(format)
+            .subresource_range(image.full_range());
+
+        let image_view = unsafe { device.create_image_view(&view_create_info, None)? };
+
+        /*
+         * Render pass and framebuffer
+         */
+        let render_pass = match crate::RenderPass::new_depth_only(device, format) {
+            Ok(render_pass) => render_pass,
+            Err(e) => {
+                unsafe { device.destroy_image_view(image_view, None) };
+                return Err(e);
+            }
+        };
+
+        let framebuffer = match crate::Framebuffer::new_from_views(
+            device,
+            &render_pass,
+            vec![image_view],
+            vk::Extent2D {
+                width: size,
+                height: size,
+            },
+        ) {
+            Ok(framebuffer) => framebuffer,
+            Err(e) => {
+                unsafe { device.destroy_image_view(image_view, None) };
+                return Err(e);
+            }
+        };
+
+        /*
+         * Comparison sampler, linear filtering gives hardware PCF
+         */
+        let filter =
+            if format_features.contains(vk::FormatFeatureFlags::SAMPLED_IMAGE_FILTER_LINEAR) {
+                vk::Filter::LINEAR
+            } else {
+                vk::Filter::NEAREST
+            };
+
+        let sampler_create_info = vk::SamplerCreateInfo::default()
+            .mag_filter(filter)
+            .min_filter(filter)
+            .mipmap_mode(vk::SamplerMipmapMode::NEAREST)
+            .address_mode_u(vk::SamplerAddressMode::CLAMP_TO_BORDER)
+            .address_mode_v(vk::SamplerAddressMode::CLAMP_TO_BORDER)
+            .address_mode_w(vk::SamplerAddressMode::CLAMP_TO_BORDER)
+            .border_color(vk::BorderColor::FLOAT_OPAQUE_WHITE)
+            .compare_enable(true)
+            .compare_op(vk::CompareOp::LESS_OR_EQUAL);
+
+        let sampler = match unsafe { device.create_sampler(&sampler_create_info, None) } {
+            Ok(sampler) => sampler,
+            Err(e) => {
+                framebuffer.destroy();
+                return Err(e.into());
+            }
+        };
+
+        return Ok(ShadowMap {
+            device: device.clone(),
+            size,
+            format,
+            image,
+            image_view,
+            sampler,
+            render_pass,
+            framebuffer,
+            light_view_proj: Mat4::identity(),
+        });
+    }
+
+    /// Prefers 32 bit float depth, 16 bit depth is supported everywhere
+    fn pick_format(device: &crate::DeviceRef) -> vk::Format {
+        let required = vk::FormatFeatureFlags::DEPTH_STENCIL_ATTACHMENT
+            | vk::FormatFeatureFlags::SAMPLED_IMAGE;
+
+        let supported = device
+            .physical()
+            .format_properties(vk::Format::D32_SFLOAT)
+            .optimal_tiling_features
+            .contains(required);
+
+        return if supported {
+            vk::Format::D32_SFLOAT
+        } else {
+            vk::Format::D16_UNORM
+        };
+    }
+}
+
+//-----------------------------------------------------------------------------
+// Specific implementation
+impl ShadowMap {
+    /// Creates a depth-only pipeline for drawing the shadow casters into the
+    /// map, see [crate::Pipeline::new_depth_only]
+    pub fn create_pipeline(
+        &self,
+        descriptor_set_layouts: &[&crate::descriptor::SetLayout],
+        vertex_descriptions: &[crate::vertex::VertexDescription],
+        vertex_shader: &crate::Shader,
+        depth_bias: crate::DepthBias,
+    ) -> Result<crate::Pipeline> {
+        return crate::Pipeline::new_depth_only(
+            &self.device,
+            &self.render_pass,
+            descriptor_set_layouts,
+            vertex_descriptions,
+            vertex_shader,
+            depth_bias,
+        );
+    }
+
+    /// Sets up the light for a directional light ( sun ) shining in the
+    /// `direction`, covering the sphere with the `center` and `radius`
+    pub fn set_directional_light(&mut self, direction: Vec3<f32>, center: Vec3<f32>, radius: f32) {
+        self.light_view_proj = directional_light_view_proj(direction, center, radius);
+    }
+
+    /// Begins the render pass clearing the depth, and sets the viewport and
+    /// the scissor to the whole map
+    pub fn begin(&self, cmd_buffer: &crate::cmd::Buffer) {
+        let clear_value = vk::ClearValue {
+            depth_stencil: vk::ClearDepthStencilValue {
+                depth: 1.0,
+                stencil: 0,
+            },
+        };
+
+        cmd_buffer.begin_render_pass_with_clear(
+            &self.framebuffer,
+            &self.render_pass,
+            std::slice::from_ref(&clear_value),
+        );
+        cmd_buffer.set_fb_viewport_scissor(&self.framebuffer);
+    }
+
+    pub fn end(&self, cmd_buffer: &crate::cmd::Buffer) {
+        cmd_buffer.end_render_pass();
+    }
+}
+
+//-----------------------------------------------------------------------------
+/// Orthographic view-projection of a directional light shining in the
+/// `direction`, covering the sphere with the `center` and `radius`.
+///
+/// Follows the conventions of [Mat4::look_at] and [Mat4::perspective]: the
+/// light looks along +Z and the depth goes from 0 to 1.
+pub fn directional_light_view_proj(
+    direction: Vec3<f32>,
+    center: Vec3<f32>,
+    radius: f32,
+) -> Mat4<f32> {
+    let direction = direction.normalized();
+    let eye = center - direction * radius;
+
+    // Any up vector which isn't parallel to the direction
+    let up = if direction.y.abs() < 0.99 {
+        Vec3::Y
+    } else {
+        Vec3::X
+    };
+
+    /*
+     * View: world space to the space of the light
+     */
+    let view = Mat4::look_at(eye, center, up);
+
+    /*
+     * Orthographic projection of the box around the sphere
+     */
+    let scale = 1.0 / radius;
+    let depth = 1.0 / (2.0 * radius);
+
+    #[rustfmt::skip]
+    let proj = Mat4::new([
+        scale, 0.0,   0.0,   0.0,
+        0.0,   scale, 0.0,   0.0,
+        0.0,   0.0,   depth, 0.0,
+        0.0,   0.0,   0.0,   1.0,
+    ]);
+
+    return proj * view;
+}
+
+//-----------------------------------------------------------------------------
+// Drop
+impl Drop for ShadowMap {
+    fn drop(&mut self) {
+        unsafe {
+            self.device.destroy_sampler(self.sampler, None);
+        }
+        self.framebuffer.destroy();
+    }
+}
+
+//-----------------------------------------------------------------------------
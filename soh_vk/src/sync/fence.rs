@@ -1,5 +1,5 @@
 //-----------------------------------------------------------------------------
-use anyhow::Result;
+use crate::Result;
 use ash::vk;
 //-----------------------------------------------------------------------------
 
@@ -19,6 +19,7 @@ impl Fence {
         });
 
         let fence = unsafe { device.create_fence(&create_info, None)? };
+        crate::debug::leaks::track("Fence", fence);
         return Ok(Fence {
             device: device.clone(),
             fence,
@@ -54,6 +55,7 @@ impl Fence {
 // Drop
 impl Drop for Fence {
     fn drop(&mut self) {
+        crate::debug::leaks::untrack("Fence", self.fence);
         unsafe {
             self.device.destroy_fence(self.fence, None);
         }
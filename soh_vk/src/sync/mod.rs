@@ -1,7 +1,11 @@
 //-----------------------------------------------------------------------------
 mod fence;
 mod semaphore;
+#[cfg(feature = "timeline_semaphore")]
+mod timeline;
 //-----------------------------------------------------------------------------
 pub use fence::*;
 pub use semaphore::*;
+#[cfg(feature = "timeline_semaphore")]
+pub use timeline::*;
 //-----------------------------------------------------------------------------
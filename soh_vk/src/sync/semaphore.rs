@@ -1,5 +1,5 @@
 //-----------------------------------------------------------------------------
-use anyhow::Result;
+use crate::Result;
 use ash::vk;
 //-----------------------------------------------------------------------------
 
@@ -15,6 +15,7 @@ impl Semaphore {
         let create_info = vk::SemaphoreCreateInfo::default();
 
         let semaphore = unsafe { device.create_semaphore(&create_info, None)? };
+        crate::debug::leaks::track("Semaphore", semaphore);
         return Ok(Semaphore {
             device: device.clone(),
             semaphore,
@@ -26,6 +27,7 @@ impl Semaphore {
 // Drop
 impl Drop for Semaphore {
     fn drop(&mut self) {
+        crate::debug::leaks::untrack("Semaphore", **self);
         unsafe {
             self.device.destroy_semaphore(**self, None);
         }
@@ -0,0 +1,86 @@
+//-----------------------------------------------------------------------------
+use crate::Result;
+use ash::vk;
+//-----------------------------------------------------------------------------
+
+/// Semaphore with a counter which only increases: submits signal it to a
+/// value and the host or other submits wait until it reaches a value
+pub struct TimelineSemaphore {
+    device: crate::DeviceRef,
+    semaphore: vk::Semaphore,
+}
+
+//-----------------------------------------------------------------------------
+// Constructor
+impl TimelineSemaphore {
+    pub fn new(device: &crate::DeviceRef, initial_value: u64) -> Result<Self> {
+        let mut type_info = vk::SemaphoreTypeCreateInfo::default()
+            .semaphore_type(vk::SemaphoreType::TIMELINE)
+            .initial_value(initial_value);
+        let create_info = vk::SemaphoreCreateInfo::default().push_next(&mut type_info);
+
+        let semaphore = unsafe { device.create_semaphore(&create_info, None)? };
+        crate::debug::leaks::track("TimelineSemaphore", semaphore);
+        return Ok(TimelineSemaphore {
+            device: device.clone(),
+            semaphore,
+        });
+    }
+}
+
+//-----------------------------------------------------------------------------
+// Specific implementation
+impl TimelineSemaphore {
+    /// Current value of the counter
+    pub fn value(&self) -> Result<u64> {
+        let value = unsafe {
+            self.device
+                .device_timeline_semaphore()
+                .get_semaphore_counter_value(self.semaphore)?
+        };
+
+        return Ok(value);
+    }
+
+    /// Wait until the counter reaches the `value`, returns `false` on timeout
+    pub fn wait(&self, value: u64, timeout_ns: u64) -> Result<bool> {
+        let wait_info = vk::SemaphoreWaitInfo::default()
+            .semaphores(std::slice::from_ref(&self.semaphore))
+            .values(std::slice::from_ref(&value));
+
+        let res = unsafe {
+            self.device
+                .device_timeline_semaphore()
+                .wait_semaphores(&wait_info, timeout_ns)
+        };
+
+        return match res {
+            Ok(()) => Ok(true),
+            Err(vk::Result::TIMEOUT) => Ok(false),
+            Err(err) => Err(err.into()),
+        };
+    }
+}
+
+//-----------------------------------------------------------------------------
+// Drop
+impl Drop for TimelineSemaphore {
+    fn drop(&mut self) {
+        crate::debug::leaks::untrack("TimelineSemaphore", self.semaphore);
+        unsafe {
+            self.device.destroy_semaphore(self.semaphore, None);
+        }
+    }
+}
+
+//-----------------------------------------------------------------------------
+// Deref
+impl std::ops::Deref for TimelineSemaphore {
+    type Target = vk::Semaphore;
+
+    fn deref(&self) -> &Self::Target {
+        return &self.semaphore;
+    }
+}
+
+//-----------------------------------------------------------------------------
@@ -0,0 +1,258 @@
+//-----------------------------------------------------------------------------
+use crate::error::ensure;
+use crate::Result;
+use ash::vk;
+use std::collections::VecDeque;
+//-----------------------------------------------------------------------------
+
+/// Uploads recorded on the transfer queue which run in the background instead
+/// of waiting like [crate::Buffer::new_staged].
+///
+/// Each upload signals the timeline semaphore to its [Upload::value] once the
+/// copy is done and releases the buffer to the graphics queue family. Before
+/// the graphics queue uses the buffer:
+/// 1. Check [AsyncTransfer::is_complete] ( or wait for the value on the GPU
+///    with the [AsyncTransfer::timeline] )
+/// 2. Record [AsyncTransfer::acquire] into a graphics command buffer
+///
+/// ```ignore
+/// let (vertices, upload) = transfer.upload_buffer(&data, soh::vk::BufferUsageFlags::VERTEX_BUFFER)?;
+/// // ... frames later
+/// if transfer.is_complete(&upload)? {
+///     transfer.acquire(&cmd_buf, &vertices, vk::PipelineStageFlags::VERTEX_INPUT, vk::AccessFlags::VERTEX_ATTRIBUTE_READ);
+/// }
+/// ```
+pub struct AsyncTransfer {
+    device: crate::DeviceRef,
+
+    pool: crate::cmd::Pool,
+    timeline: crate::sync::TimelineSemaphore,
+    // Value signaled by the last submitted upload
+    last_value: u64,
+    graphics_family: u32,
+
+    // Uploads which may still be running, oldest first
+    in_flight: VecDeque<InFlight>,
+}
+
+/// Submitted upload, see [AsyncTransfer]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Upload {
+    value: u64,
+}
+
+// Resources kept alive until the upload completes
+struct InFlight {
+    value: u64,
+    cmd_buf: crate::cmd::Buffer,
+    _staging_buffer: crate::Buffer,
+}
+
+//-----------------------------------------------------------------------------
+// Getters
+impl AsyncTransfer {
+    /// The timeline semaphore signaled by the uploads
+    pub fn timeline(&self) -> &crate::sync::TimelineSemaphore {
+        return &self.timeline;
+    }
+    pub fn transfer_family(&self) -> u32 {
+        return self.pool.queue_family_index();
+    }
+    /// Number of uploads which weren't seen completed yet
+    pub fn num_of_in_flight(&self) -> usize {
+        return self.in_flight.len();
+    }
+}
+
+impl Upload {
+    /// Value of the timeline semaphore once the upload is done
+    pub fn value(&self) -> u64 {
+        return self.value;
+    }
+}
+
+//-----------------------------------------------------------------------------
+// Constructor
+impl AsyncTransfer {
+    pub fn new(device: &crate::DeviceRef) -> Result<Self> {
+        return Ok(AsyncTransfer {
+            device: device.clone(),
+            pool: crate::cmd::Pool::new_transfer(device)?,
+            timeline: crate::sync::TimelineSemaphore::new(device, 0)?,
+            last_value: 0,
+            graphics_family: device
+                .physical()
+                .queue_family_idx(crate::QueueType::Graphics),
+            in_flight: VecDeque::new(),
+        });
+    }
+}
+
+//-----------------------------------------------------------------------------
+// Specific implementation
+impl AsyncTransfer {
+    /// Create a device local buffer and upload the `data` to it in the
+    /// background
+    pub fn upload_buffer<T>(
+        &mut self,
+        data: &[T],
+        usage: crate::BufferUsageFlags,
+    ) -> Result<(crate::Buffer, Upload)>
+    where
+        T: Copy,
+    {
+        let buffer = crate::Buffer::new(
+            &self.device,
+            size_of_val(data) as u64,
+            usage | crate::BufferUsageFlags::TRANSFER_DST,
+            crate::MemoryPropertyFlags::DEVICE_LOCAL,
+        )?;
+
+        let upload = self.upload_to_buffer(&buffer, data)?;
+
+        return Ok((buffer, upload));
+    }
+
+    /// Upload the `data` to the start of the `dst` buffer in the background.
+    /// The buffer shouldn't be used until the upload is complete
+    pub fn upload_to_buffer<T>(&mut self, dst: &crate::Buffer, data: &[T]) -> Result<Upload>
+    where
+        T: Copy,
+    {
+        let size = size_of_val(data) as u64;
+        ensure!(size != 0, InvalidUsage, "Can't upload empty data");
+        ensure!(
+            size <= dst.buffer_size(),
+            InvalidUsage,
+            "Uploading {size} bytes to a buffer of {} bytes",
+            dst.buffer_size()
+        );
+
+        self.poll()?;
+
+        /*
+         * Write the data to a staging buffer
+         */
+        let staging_buffer =
+            crate::Buffer::new_mapped(&self.device, data, crate::BufferUsageFlags::TRANSFER_SRC)?;
+
+        /*
+         * Copy and release the buffer to the graphics queue family
+         */
+        let cmd_buf = self
+            .pool
+            .allocate_buffer(crate::cmd::BufferLevel::Primary)?;
+        let value = self.last_value + 1;
+
+        let copy_info = vk::BufferCopy {
+            size,
+            ..Default::default()
+        };
+
+        let res = cmd_buf
+            .begin(vk::CommandBufferUsageFlags::ONE_TIME_SUBMIT)
+            .and_then(|_| {
+                unsafe {
+                    self.device.cmd_copy_buffer(
+                        *cmd_buf,
+                        *staging_buffer,
+                        **dst,
+                        std::slice::from_ref(&copy_info),
+                    );
+                }
+                cmd_buf.release_buffer_ownership(
+                    dst,
+                    self.graphics_family,
+                    vk::PipelineStageFlags::TRANSFER,
+                    vk::AccessFlags::TRANSFER_WRITE,
+                );
+                return cmd_buf.end();
+            })
+            .and_then(|_| cmd_buf.submit_signal_timeline(&self.timeline, value));
+
+        if let Err(err) = res {
+            self.free_cmd_buffer(&cmd_buf);
+            return Err(err);
+        }
+
+        /*
+         * Keep the resources alive until the copy is done
+         */
+        self.last_value = value;
+        self.in_flight.push_back(InFlight {
+            value,
+            cmd_buf,
+            _staging_buffer: staging_buffer,
+        });
+
+        return Ok(Upload { value });
+    }
+
+    /// Record the acquire of the uploaded buffer into a graphics command
+    /// buffer, before the `dst_access` in the `dst_stage`
+    pub fn acquire(
+        &self,
+        cmd_buf: &crate::cmd::Buffer,
+        buffer: &crate::Buffer,
+        dst_stage: vk::PipelineStageFlags,
+        dst_access: vk::AccessFlags,
+    ) {
+        cmd_buf.acquire_buffer_ownership(buffer, self.transfer_family(), dst_stage, dst_access);
+    }
+
+    /// Whether the upload is done ( doesn't wait )
+    pub fn is_complete(&mut self, upload: &Upload) -> Result<bool> {
+        return Ok(self.poll()? >= upload.value);
+    }
+
+    /// Wait until the upload is done, returns `false` on timeout
+    pub fn wait(&mut self, upload: &Upload, timeout_ns: u64) -> Result<bool> {
+        let done = self.timeline.wait(upload.value, timeout_ns)?;
+        self.poll()?;
+
+        return Ok(done);
+    }
+
+    /// Free the resources of the completed uploads, returns the value of the
+    /// timeline semaphore
+    pub fn poll(&mut self) -> Result<u64> {
+        let completed = self.timeline.value()?;
+
+        while self
+            .in_flight
+            .front()
+            .is_some_and(|upload| upload.value <= completed)
+        {
+            let upload = self.in_flight.pop_front().unwrap();
+            self.free_cmd_buffer(&upload.cmd_buf);
+        }
+
+        return Ok(completed);
+    }
+
+    fn free_cmd_buffer(&self, cmd_buf: &crate::cmd::Buffer) {
+        unsafe {
+            self.device
+                .free_command_buffers(*self.pool, std::slice::from_ref(cmd_buf));
+        }
+    }
+}
+
+//-----------------------------------------------------------------------------
+// Drop
+impl Drop for AsyncTransfer {
+    fn drop(&mut self) {
+        /*
+         * Wait for the uploads still running before freeing their resources
+         */
+        if self.timeline.wait(self.last_value, u64::MAX).is_err() {
+            self.device.wait_idle();
+        }
+
+        for upload in std::mem::take(&mut self.in_flight) {
+            self.free_cmd_buffer(&upload.cmd_buf);
+        }
+    }
+}
+
+//-----------------------------------------------------------------------------
@@ -0,0 +1,210 @@
+//-----------------------------------------------------------------------------
+use crate::Result;
+use ash::vk;
+//-----------------------------------------------------------------------------
+/// Offsets of the allocations are aligned to this ( enough for any index or
+/// vertex attribute type )
+const ALIGNMENT: u64 = 16;
+//-----------------------------------------------------------------------------
+
+/// Arena for the vertices and indexes that are used only for one frame
+/// ( immediate-mode UI, debug drawing ).
+///
+/// Every frame in flight has its own host-visible buffer. The data is written
+/// to the buffer of the current frame, and the buffer is reused once the GPU is
+/// done with the frame. If a frame needs more memory than the buffer has, a
+/// bigger buffer is created and the old one is kept alive until the frame is
+/// reused.
+///
+/// Call [TransientGeometry::begin_frame] at the start of every frame, then bind
+/// the allocations with
+/// [cmd::Buffer::bind_transient_vertices](crate::cmd::Buffer::bind_transient_vertices)
+/// and [cmd::Buffer::bind_transient_indices](crate::cmd::Buffer::bind_transient_indices).
+pub struct TransientGeometry {
+    device: crate::DeviceRef,
+
+    frames: crate::PerFrame<FrameArena>,
+    frame_idx: usize,
+}
+
+/// Part of the transient buffer holding vertices
+#[derive(Debug, Clone, Copy)]
+pub struct TransientVertices {
+    buffer: vk::Buffer,
+    offset: u64,
+    num_of_vertexes: u32,
+}
+
+/// Part of the transient buffer holding indexes
+#[derive(Debug, Clone, Copy)]
+pub struct TransientIndices {
+    buffer: vk::Buffer,
+    offset: u64,
+    num_of_indexes: u32,
+    index_type: vk::IndexType,
+}
+
+struct FrameArena {
+    buffer: crate::Buffer,
+    offset: u64,
+
+    // Buffers which were replaced by a bigger one during the frame, the GPU
+    // may still read from them
+    retired: Vec<crate::Buffer>,
+}
+
+//-----------------------------------------------------------------------------
+// Getters
+impl TransientVertices {
+    pub fn buffer(&self) -> vk::Buffer {
+        return self.buffer;
+    }
+    pub fn offset(&self) -> u64 {
+        return self.offset;
+    }
+    pub fn num_of_vertexes(&self) -> u32 {
+        return self.num_of_vertexes;
+    }
+}
+
+impl TransientIndices {
+    pub fn buffer(&self) -> vk::Buffer {
+        return self.buffer;
+    }
+    pub fn offset(&self) -> u64 {
+        return self.offset;
+    }
+    pub fn num_of_indexes(&self) -> u32 {
+        return self.num_of_indexes;
+    }
+    pub fn index_type(&self) -> vk::IndexType {
+        return self.index_type;
+    }
+}
+
+//-----------------------------------------------------------------------------
+// Constructor
+impl TransientGeometry {
+    /// Creates the arena with `size` bytes for each frame in flight
+    pub fn new(context: &crate::VulkanContext, size: u64) -> Result<Self> {
+        let device = context.device();
+
+        let frames = crate::PerFrame::from_context(context, |_| FrameArena::new(device, size))?;
+
+        return Ok(TransientGeometry {
+            device: device.clone(),
+            frames,
+            frame_idx: 0,
+        });
+    }
+}
+
+//-----------------------------------------------------------------------------
+// Specific implementation
+impl TransientGeometry {
+    /// Starts a new frame, the memory of that frame is reused.
+    ///
+    /// Must be called after the fence of the frame has signaled ( for example
+    /// inside of [crate::VulkanContext::on_frame] with
+    /// [crate::PerFrameData::frame_idx] ).
+    pub fn begin_frame(&mut self, frame_idx: usize) {
+        self.frame_idx = frame_idx;
+
+        let frame = &mut self.frames[frame_idx];
+        frame.offset = 0;
+        frame.retired.clear();
+    }
+
+    pub fn alloc_vertices<T>(&mut self, vertices: &[T]) -> Result<TransientVertices>
+    where
+        T: crate::Vertex,
+    {
+        let (buffer, offset) = self.alloc(vertices)?;
+
+        return Ok(TransientVertices {
+            buffer,
+            offset,
+            num_of_vertexes: vertices.len() as u32,
+        });
+    }
+
+    pub fn alloc_indices_u16(&mut self, indexes: &[u16]) -> Result<TransientIndices> {
+        let (buffer, offset) = self.alloc(indexes)?;
+
+        return Ok(TransientIndices {
+            buffer,
+            offset,
+            num_of_indexes: indexes.len() as u32,
+            index_type: vk::IndexType::UINT16,
+        });
+    }
+
+    pub fn alloc_indices_u32(&mut self, indexes: &[u32]) -> Result<TransientIndices> {
+        let (buffer, offset) = self.alloc(indexes)?;
+
+        return Ok(TransientIndices {
+            buffer,
+            offset,
+            num_of_indexes: indexes.len() as u32,
+            index_type: vk::IndexType::UINT32,
+        });
+    }
+
+    /// Number of bytes used by the current frame
+    pub fn used(&self) -> u64 {
+        return self.frames[self.frame_idx].offset;
+    }
+
+    fn alloc<T: Copy>(&mut self, data: &[T]) -> Result<(vk::Buffer, u64)> {
+        let size = size_of_val(data) as u64;
+        let frame = &mut self.frames[self.frame_idx];
+
+        let mut offset = frame.offset.next_multiple_of(ALIGNMENT);
+
+        if offset + size > frame.buffer.buffer_size() {
+            frame.grow(&self.device, size)?;
+            offset = 0;
+        }
+
+        frame.buffer.memory_mut().write_at(offset, data)?;
+        frame.offset = offset + size;
+
+        return Ok((frame.buffer.buffer(), offset));
+    }
+}
+
+impl FrameArena {
+    fn new(device: &crate::DeviceRef, size: u64) -> Result<Self> {
+        let mut buffer = crate::Buffer::new(
+            device,
+            size.max(ALIGNMENT),
+            crate::BufferUsageFlags::VERTEX_BUFFER | crate::BufferUsageFlags::INDEX_BUFFER,
+            crate::MemoryPropertyFlags::HOST_VISIBLE | crate::MemoryPropertyFlags::HOST_COHERENT,
+        )?;
+
+        // Stays mapped for the whole lifetime of the buffer
+        buffer.memory_mut().map()?;
+
+        return Ok(FrameArena {
+            buffer,
+            offset: 0,
+            retired: Vec::new(),
+        });
+    }
+
+    /// Replaces the buffer with a buffer of at least `min_size` bytes. The
+    /// allocations of this frame start in the new buffer from the beginning.
+    fn grow(&mut self, device: &crate::DeviceRef, min_size: u64) -> Result<()> {
+        let size = min_size.max(self.buffer.buffer_size() * 2);
+
+        soh_log::log_debug!("Growing the transient geometry buffer to {} bytes", size);
+
+        let old = std::mem::replace(self, FrameArena::new(device, size)?);
+        self.retired = old.retired;
+        self.retired.push(old.buffer);
+
+        return Ok(());
+    }
+}
+
+//-----------------------------------------------------------------------------
@@ -9,6 +9,10 @@ pub type BufferUsageFlags = vk::BufferUsageFlags;
 pub type MemoryPropertyFlags = vk::MemoryPropertyFlags;
 pub type ImageLayout = vk::ImageLayout;
 //-----------------------------------------------------------------------------
+// For descriptor sets
+pub type DescriptorType = vk::DescriptorType;
+pub type ShaderStageFlags = vk::ShaderStageFlags;
+//-----------------------------------------------------------------------------
 // Other types
 #[derive(Debug, Clone, Copy)]
 pub enum QueueType {
@@ -17,3 +21,60 @@ pub enum QueueType {
     Transfer,
 }
 //-----------------------------------------------------------------------------
+// Conversion of the soh_math types
+pub trait ToVk {
+    type Output;
+
+    fn to_vk(&self) -> Self::Output;
+}
+
+impl ToVk for soh_math::Rect<i32> {
+    type Output = vk::Rect2D;
+
+    /// Empty rects get zero extent
+    fn to_vk(&self) -> vk::Rect2D {
+        return vk::Rect2D {
+            offset: vk::Offset2D {
+                x: self.min.x,
+                y: self.min.y,
+            },
+            extent: vk::Extent2D {
+                width: self.width().max(0) as u32,
+                height: self.height().max(0) as u32,
+            },
+        };
+    }
+}
+
+impl ToVk for soh_math::Rect<u32> {
+    type Output = vk::Rect2D;
+
+    fn to_vk(&self) -> vk::Rect2D {
+        return vk::Rect2D {
+            offset: vk::Offset2D {
+                x: self.min.x as i32,
+                y: self.min.y as i32,
+            },
+            extent: vk::Extent2D {
+                width: self.max.x.saturating_sub(self.min.x),
+                height: self.max.y.saturating_sub(self.min.y),
+            },
+        };
+    }
+}
+
+impl ToVk for soh_math::Viewport {
+    type Output = vk::Viewport;
+
+    fn to_vk(&self) -> vk::Viewport {
+        return vk::Viewport {
+            x: self.rect.min.x,
+            y: self.rect.min.y,
+            width: self.rect.width(),
+            height: self.rect.height(),
+            min_depth: self.min_depth,
+            max_depth: self.max_depth,
+        };
+    }
+}
+//-----------------------------------------------------------------------------
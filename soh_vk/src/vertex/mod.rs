@@ -92,17 +92,32 @@ impl_to_format!(
     soh_math::Complex<u32>, R32G32_UINT
     soh_math::Complex<f32>, R32G32_SFLOAT
     soh_math::Complex<f64>, R64G64_SFLOAT
+
+    soh_math::pack::Unorm8x4,  R8G8B8A8_UNORM
+    soh_math::pack::Snorm16x2, R16G16_SNORM
+    soh_math::pack::Snorm16x4, R16G16B16A16_SNORM
+    soh_math::pack::Rgb10A2,   A2B10G10R10_UNORM_PACK32
 );
 
 //-----------------------------------------------------------------------------
 // Getting the binding and attribute description
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct VertexDescription {
     pub stride: u32,
     pub attribute_descriptions: Vec<AttributeDescription>,
+    /// Whether the binding advances per vertex or per instance
+    pub input_rate: ash::vk::VertexInputRate,
+}
+
+impl VertexDescription {
+    /// Make the binding advance once per instance instead of once per vertex
+    pub fn per_instance(mut self) -> Self {
+        self.input_rate = ash::vk::VertexInputRate::INSTANCE;
+        return self;
+    }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct AttributeDescription {
     pub location: u32,
     pub format: crate::Format,
@@ -114,6 +129,7 @@ pub trait Vertex: Copy + Sized {
         return VertexDescription {
             stride: size_of::<Self>() as u32,
             attribute_descriptions: Self::get_attribute_description(),
+            input_rate: ash::vk::VertexInputRate::VERTEX,
         };
     }
     fn get_attribute_description() -> Vec<AttributeDescription>;
@@ -132,6 +148,125 @@ where
     }
 }
 
+/// Checks that the `vertex_descriptions` have an attribute for every input of
+/// the `vertex_shader`, with a format of the same numeric type ( float, signed
+/// or unsigned integer ). Lists all of the mismatched locations in the error.
+pub(crate) fn validate_vertex_input(
+    vertex_descriptions: &[VertexDescription],
+    vertex_shader: &crate::Shader,
+) -> crate::Result<()> {
+    use crate::shader::reflect::{NumericType, ShaderInput};
+
+    // The format of the attribute has to be read as the same numeric type
+    // ( normalized and scaled formats are read as floats )
+    fn format_numeric_type(format: crate::Format) -> NumericType {
+        use ash::vk::Format as F;
+
+        return match format {
+            F::R8_UINT
+            | F::R8G8_UINT
+            | F::R8G8B8_UINT
+            | F::B8G8R8_UINT
+            | F::R8G8B8A8_UINT
+            | F::B8G8R8A8_UINT
+            | F::A8B8G8R8_UINT_PACK32
+            | F::A2R10G10B10_UINT_PACK32
+            | F::A2B10G10R10_UINT_PACK32
+            | F::R16_UINT
+            | F::R16G16_UINT
+            | F::R16G16B16_UINT
+            | F::R16G16B16A16_UINT
+            | F::R32_UINT
+            | F::R32G32_UINT
+            | F::R32G32B32_UINT
+            | F::R32G32B32A32_UINT
+            | F::R64_UINT
+            | F::R64G64_UINT
+            | F::R64G64B64_UINT
+            | F::R64G64B64A64_UINT => NumericType::Uint,
+
+            F::R8_SINT
+            | F::R8G8_SINT
+            | F::R8G8B8_SINT
+            | F::B8G8R8_SINT
+            | F::R8G8B8A8_SINT
+            | F::B8G8R8A8_SINT
+            | F::A8B8G8R8_SINT_PACK32
+            | F::A2R10G10B10_SINT_PACK32
+            | F::A2B10G10R10_SINT_PACK32
+            | F::R16_SINT
+            | F::R16G16_SINT
+            | F::R16G16B16_SINT
+            | F::R16G16B16A16_SINT
+            | F::R32_SINT
+            | F::R32G32_SINT
+            | F::R32G32B32_SINT
+            | F::R32G32B32A32_SINT
+            | F::R64_SINT
+            | F::R64G64_SINT
+            | F::R64G64B64_SINT
+            | F::R64G64B64A64_SINT => NumericType::Sint,
+
+            _ => NumericType::Float,
+        };
+    }
+
+    // GLSL name of the input type
+    fn input_type_name(input: &ShaderInput) -> String {
+        let (scalar, prefix) = match input.numeric_type {
+            NumericType::Float => ("float", ""),
+            NumericType::Sint => ("int", "i"),
+            NumericType::Uint => ("uint", "u"),
+        };
+
+        if input.num_of_components == 1 {
+            return scalar.to_owned();
+        }
+        return format!("{}vec{}", prefix, input.num_of_components);
+    }
+
+    let inputs = match vertex_shader.inputs() {
+        Ok(inputs) => inputs,
+        Err(e) => {
+            soh_log::log_warning!("Skipping vertex input validation: {}", e);
+            return Ok(());
+        }
+    };
+
+    let attributes = vertex_descriptions
+        .iter()
+        .flat_map(|descr| descr.attribute_descriptions.iter())
+        .map(|attribute| (attribute.location, attribute.format))
+        .collect::<std::collections::HashMap<_, _>>();
+
+    let mismatches = inputs
+        .iter()
+        .filter_map(|input| match attributes.get(&input.location) {
+            None => Some(format!(
+                "location {}: no attribute for the `{}` input",
+                input.location,
+                input_type_name(input)
+            )),
+            Some(&format) if format_numeric_type(format) != input.numeric_type => Some(format!(
+                "location {}: attribute format {:?} can't be read as `{}`",
+                input.location,
+                format,
+                input_type_name(input)
+            )),
+            Some(_) => None,
+        })
+        .collect::<Vec<_>>();
+
+    crate::error::ensure!(
+        mismatches.is_empty(),
+        InvalidUsage,
+        "Vertex description doesn't match the vertex shader inputs:\n  {}",
+        mismatches.join("\n  ")
+    );
+
+    return Ok(());
+}
+
 pub(crate) fn get_vk_vertex_description(
     vertex_descriptions: &[VertexDescription],
 ) -> (
@@ -145,7 +280,7 @@ pub(crate) fn get_vk_vertex_description(
         let binding_description = ash::vk::VertexInputBindingDescription {
             binding: idx as u32,
             stride: descr.stride,
-            input_rate: ash::vk::VertexInputRate::VERTEX,
+            input_rate: descr.input_rate,
         };
 
         for descr in descr.attribute_descriptions.iter() {
@@ -20,3 +20,41 @@ pub enum Platform {
 }
 
 //-----------------------------------------------------------------------------
+
+/// Color space (and matching format) of the swapchain images
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ColorSpace {
+    /// 8 bit sRGB (B8G8R8A8_SRGB + SRGB_NONLINEAR)
+    #[default]
+    Srgb,
+    /// HDR10 (A2B10G10R10 + HDR10_ST2084)
+    Hdr10,
+    /// Extended linear sRGB (R16G16B16A16_SFLOAT + EXTENDED_SRGB_LINEAR)
+    ScRgb,
+}
+
+impl ColorSpace {
+    /// Check if the surface format matches the color space
+    pub fn matches(&self, surface_format: &ash::vk::SurfaceFormatKHR) -> bool {
+        use ash::vk::{ColorSpaceKHR, Format};
+
+        return match self {
+            ColorSpace::Srgb => {
+                surface_format.format == Format::B8G8R8A8_SRGB
+                    && surface_format.color_space == ColorSpaceKHR::SRGB_NONLINEAR
+            }
+            ColorSpace::Hdr10 => {
+                matches!(
+                    surface_format.format,
+                    Format::A2B10G10R10_UNORM_PACK32 | Format::A2R10G10B10_UNORM_PACK32
+                ) && surface_format.color_space == ColorSpaceKHR::HDR10_ST2084_EXT
+            }
+            ColorSpace::ScRgb => {
+                surface_format.format == Format::R16G16B16A16_SFLOAT
+                    && surface_format.color_space == ColorSpaceKHR::EXTENDED_SRGB_LINEAR_EXT
+            }
+        };
+    }
+}
+
+//-----------------------------------------------------------------------------
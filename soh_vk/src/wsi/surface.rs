@@ -1,5 +1,6 @@
 //-----------------------------------------------------------------------------
-use anyhow::Result;
+use crate::error::bail;
+use crate::Result;
 use ash::vk::{self, Handle};
 //-----------------------------------------------------------------------------
 
@@ -10,7 +11,7 @@ pub struct Surface {
 
 //-----------------------------------------------------------------------------
 // Surface reference stored inside other vulkan types
-pub type SurfaceRef = std::rc::Rc<Surface>;
+pub type SurfaceRef = std::sync::Arc<Surface>;
 //-----------------------------------------------------------------------------
 // Constructor
 impl Surface {
@@ -94,7 +95,8 @@ impl Surface {
              * Anything else
              */
             (h_win, h_disp) => {
-                anyhow::bail!(
+                bail!(
+                    Unsupported,
                     "Unsupported window and display handle type: {:#?}, {:?}",
                     h_win,
                     h_disp
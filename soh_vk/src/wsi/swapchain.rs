@@ -1,5 +1,5 @@
 //-----------------------------------------------------------------------------
-use anyhow::Result;
+use crate::Result;
 use ash::vk::{self, Handle};
 //-----------------------------------------------------------------------------
 
@@ -9,8 +9,11 @@ pub struct Swapchain {
     swapchain: vk::SwapchainKHR,
 
     image_format: crate::Format,
+    color_space: vk::ColorSpaceKHR,
     extent: vk::Extent2D,
     num_of_images: usize,
+
+    preferred_color_space: super::ColorSpace,
 }
 
 //-----------------------------------------------------------------------------
@@ -19,6 +22,12 @@ impl Swapchain {
     pub fn image_format(&self) -> crate::Format {
         return self.image_format;
     }
+    pub fn color_space(&self) -> vk::ColorSpaceKHR {
+        return self.color_space;
+    }
+    pub fn preferred_color_space(&self) -> super::ColorSpace {
+        return self.preferred_color_space;
+    }
     pub fn extent(&self) -> vk::Extent2D {
         return self.extent;
     }
@@ -31,23 +40,41 @@ impl Swapchain {
 // Constructor
 impl Swapchain {
     pub fn new(device: &crate::DeviceRef, window_size: (u32, u32)) -> Result<Self> {
+        return Self::new_with_color_space(device, window_size, super::ColorSpace::default());
+    }
+
+    /// Create a swapchain which uses the preferred color space if the surface supports it
+    /// (falls back to sRGB otherwise)
+    pub fn new_with_color_space(
+        device: &crate::DeviceRef,
+        window_size: (u32, u32),
+        preferred_color_space: super::ColorSpace,
+    ) -> Result<Self> {
         soh_log::log_debug!("Creating swapchain for window size {:?}", window_size);
 
-        return Self::create_swapchain(device, window_size, None);
+        return Self::create_swapchain(device, window_size, preferred_color_space, None);
     }
 
     pub fn recreate(&mut self, window_size: (u32, u32)) -> Result<()> {
         soh_log::log_debug!("Rereating swapchain for window size {:?}", window_size);
 
+        let preferred_color_space = self.preferred_color_space;
+
         self.destroy();
-        *self = Self::create_swapchain(&self.device, window_size, None)?;
+        *self = Self::create_swapchain(&self.device, window_size, preferred_color_space, None)?;
 
         return Ok(());
     }
 
+    /// The color space is used when the swapchain is recreated next time
+    pub fn set_preferred_color_space(&mut self, preferred_color_space: super::ColorSpace) {
+        self.preferred_color_space = preferred_color_space;
+    }
+
     fn create_swapchain(
         device: &crate::DeviceRef,
         window_size: (u32, u32),
+        preferred_color_space: super::ColorSpace,
         old_swapchain: Option<&Self>,
     ) -> Result<Self> {
         /*
@@ -68,7 +95,8 @@ impl Swapchain {
         /*
          * Choose format, present mode, extent and image count
          */
-        let surface_format = Self::choose_swapchain_format(&swapchain_support.formats);
+        let surface_format =
+            Self::choose_swapchain_format(&swapchain_support.formats, preferred_color_space);
         let present_mode = Self::choose_swapchain_present_mode(&swapchain_support.present_modes);
         let extent = Self::choose_swap_extent(&swapchain_support.capabilities, window_size);
         let image_count = Self::choose_image_count(&swapchain_support.capabilities);
@@ -100,6 +128,7 @@ impl Swapchain {
             .clipped(true);
 
         let swapchain = unsafe { device_swapchain.create_swapchain(&create_info, None)? };
+        crate::debug::leaks::track("Swapchain", swapchain);
 
         let num_of_images =
             unsafe { device.device_swapchain().get_swapchain_images(swapchain)? }.len();
@@ -108,16 +137,19 @@ impl Swapchain {
             device: device.clone(),
             swapchain,
             image_format: surface_format.format,
+            color_space: surface_format.color_space,
             extent,
             num_of_images,
+            preferred_color_space,
         });
     }
 
-    fn destroy(&mut self) {
+    pub(crate) fn destroy(&mut self) {
         if self.is_null() {
             return;
         }
 
+        crate::debug::leaks::untrack("Swapchain", self.swapchain);
         unsafe {
             self.device
                 .device_swapchain()
@@ -136,7 +168,7 @@ impl Swapchain {
         &self,
         signal_semaphore: Option<&crate::sync::Semaphore>,
         fence: Option<&crate::sync::Fence>,
-    ) -> Result<(u32, bool), vk::Result> {
+    ) -> std::result::Result<(u32, bool), vk::Result> {
         let semaphore = crate::get_opt_handle(signal_semaphore);
         let fence = crate::get_opt_handle(fence);
 
@@ -176,13 +208,33 @@ impl Swapchain {
         };
     }
 
-    fn choose_swapchain_format(available_formats: &[vk::SurfaceFormatKHR]) -> vk::SurfaceFormatKHR {
-        for &available_format in available_formats.iter() {
-            if available_format.format == vk::Format::B8G8R8A8_SRGB
-                && available_format.color_space == vk::ColorSpaceKHR::SRGB_NONLINEAR
-            {
-                return available_format;
-            }
+    fn choose_swapchain_format(
+        available_formats: &[vk::SurfaceFormatKHR],
+        preferred_color_space: super::ColorSpace,
+    ) -> vk::SurfaceFormatKHR {
+        let find_format = |color_space: super::ColorSpace| {
+            return available_formats
+                .iter()
+                .find(|format| color_space.matches(format))
+                .copied();
+        };
+
+        if let Some(format) = find_format(preferred_color_space) {
+            soh_log::log_info!(
+                "Using {:?} swapchain format {:?} ({:?})",
+                preferred_color_space,
+                format.format,
+                format.color_space
+            );
+            return format;
+        }
+
+        if let Some(format) = find_format(super::ColorSpace::Srgb) {
+            soh_log::log_warning!(
+                "Surface doesn't support {:?} color space! Falling back to sRGB",
+                preferred_color_space
+            );
+            return format;
         }
 
         soh_log::log_warning!(
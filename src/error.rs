@@ -0,0 +1,31 @@
+//-----------------------------------------------------------------------------
+//! Error type covering all of the enabled libraries
+//-----------------------------------------------------------------------------
+
+/// Error of any of the libraries (match on the variant to find out which one failed)
+#[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
+pub enum Error {
+    #[cfg(feature = "vk")]
+    #[error(transparent)]
+    Vk(#[from] soh_vk::Error),
+
+    #[cfg(feature = "log")]
+    #[error(transparent)]
+    Log(#[from] soh_log::Error),
+
+    #[cfg(feature = "ui")]
+    #[error(transparent)]
+    Ui(#[from] soh_ui::Error),
+
+    #[cfg(feature = "thread")]
+    #[error(transparent)]
+    Thread(#[from] soh_thread::JobError),
+
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+//-----------------------------------------------------------------------------
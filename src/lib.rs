@@ -27,3 +27,10 @@ pub use soh_game as game;
 pub use soh_utl as utl;
 
 //-----------------------------------------------------------------------------
+
+pub mod prelude;
+
+mod error;
+pub use error::{Error, Result};
+
+//-----------------------------------------------------------------------------
@@ -0,0 +1,29 @@
+//-----------------------------------------------------------------------------
+//! Most used items of the enabled libraries
+//!
+//! ```
+//! use soh_libs::prelude::*;
+//! ```
+//-----------------------------------------------------------------------------
+
+pub use crate::Error;
+
+#[cfg(feature = "math")]
+pub use soh_math::{
+    color::{Gradient, Hsluv, Hsv, Rgb},
+    Complex, Mat2, Mat3, Mat4, Quaternion, Vec2, Vec2i, Vec2u, Vec3, Vec3i, Vec3u, Vec4,
+};
+
+#[cfg(feature = "rng")]
+pub use soh_rng::prelude::*;
+
+#[cfg(feature = "log")]
+pub use soh_log::{log_debug, log_error, log_fatal, log_info, log_warning, LogError, Prio};
+
+#[cfg(feature = "ui")]
+pub use soh_ui::Error as UiError;
+
+#[cfg(feature = "thread")]
+pub use soh_thread::ThreadPool;
+
+//-----------------------------------------------------------------------------